@@ -0,0 +1,417 @@
+//! App lock.
+//!
+//! When a lock passphrase is set and an idle timeout is configured
+//! (`app_lock_idle_minutes` in preferences), the app locks after that much
+//! time without activity and on startup. While locked, mutating commands and
+//! credential reads fail until `unlock_app` is called with the passphrase.
+//!
+//! The lock is enforced here rather than in the UI: guarded commands call
+//! [`ensure_unlocked`] before doing anything. Background tasks (flow runs,
+//! monitors) keep running and read credentials internally.
+//!
+//! The passphrase is stored as a PBKDF2-HMAC-SHA256 hash in the OS keyring.
+
+use ring::{pbkdf2, rand::SecureRandom};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::num::NonZeroU32;
+use std::sync::{LazyLock, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::windows;
+
+/// Event emitted with the `AppLockStatus` when the app locks or unlocks.
+pub const APP_LOCK_CHANGED_EVENT: &str = "app-lock-changed";
+
+/// Keyring entry holding the passphrase hash.
+const KEYRING_SERVICE: &str = "ops-flow";
+const KEYRING_ENTRY: &str = "app-lock";
+
+/// PBKDF2 iterations for new passphrase hashes.
+const PBKDF2_ITERATIONS: u32 = 210_000;
+
+const MIN_PASSPHRASE_LENGTH: usize = 8;
+
+/// Failed unlock attempts allowed before unlocking is paused.
+const MAX_FAILED_ATTEMPTS: u32 = 5;
+
+/// Pause after too many failed unlock attempts.
+const FAILED_ATTEMPTS_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// How often the idle timeout is evaluated in the background.
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Error returned by guarded commands while the app is locked.
+const LOCKED_ERROR: &str = "The app is locked. Unlock it to continue.";
+
+static LOCK: LazyLock<Mutex<LockState>> = LazyLock::new(|| Mutex::new(LockState::new()));
+
+/// Lock state reported to the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct AppLockStatus {
+    /// Whether a lock passphrase is set
+    pub passphrase_set: bool,
+    /// Idle time before locking (None = never locks automatically)
+    pub idle_timeout_minutes: Option<u32>,
+    pub locked: bool,
+}
+
+struct LockState {
+    passphrase_set: bool,
+    idle_timeout: Option<Duration>,
+    last_activity: Instant,
+    locked: bool,
+    failed_attempts: u32,
+    retry_after: Option<Instant>,
+}
+
+impl LockState {
+    fn new() -> Self {
+        Self {
+            passphrase_set: false,
+            idle_timeout: None,
+            last_activity: Instant::now(),
+            locked: false,
+            failed_attempts: 0,
+            retry_after: None,
+        }
+    }
+
+    /// Locks the app if the idle timeout has passed. Returns true when the
+    /// app became locked.
+    fn lock_if_idle(&mut self, now: Instant) -> bool {
+        let idle = self
+            .idle_timeout
+            .is_some_and(|timeout| now.saturating_duration_since(self.last_activity) >= timeout);
+        if self.passphrase_set && !self.locked && idle {
+            self.locked = true;
+            return true;
+        }
+        false
+    }
+
+    fn status(&self) -> AppLockStatus {
+        AppLockStatus {
+            passphrase_set: self.passphrase_set,
+            idle_timeout_minutes: self
+                .idle_timeout
+                .map(|t| u32::try_from(t.as_secs() / 60).unwrap_or(u32::MAX)),
+            locked: self.locked,
+        }
+    }
+}
+
+fn with_state<T>(f: impl FnOnce(&mut LockState) -> T) -> T {
+    f(&mut LOCK.lock().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Salted passphrase hash as stored in the keyring.
+#[derive(Debug, Serialize, Deserialize)]
+struct PassphraseHash {
+    iterations: u32,
+    /// Hex-encoded salt
+    salt: String,
+    /// Hex-encoded PBKDF2 output
+    hash: String,
+}
+
+impl PassphraseHash {
+    fn new(passphrase: &str, iterations: u32) -> Result<Self, String> {
+        let mut salt = [0u8; 16];
+        ring::rand::SystemRandom::new()
+            .fill(&mut salt)
+            .map_err(|_| "Failed to generate salt".to_string())?;
+        let mut hash = [0u8; 32];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            non_zero(iterations),
+            &salt,
+            passphrase.as_bytes(),
+            &mut hash,
+        );
+        Ok(Self {
+            iterations,
+            salt: to_hex(&salt),
+            hash: to_hex(&hash),
+        })
+    }
+
+    fn verify(&self, passphrase: &str) -> bool {
+        let (Some(salt), Some(hash)) = (from_hex(&self.salt), from_hex(&self.hash)) else {
+            return false;
+        };
+        pbkdf2::verify(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            non_zero(self.iterations),
+            &salt,
+            passphrase.as_bytes(),
+            &hash,
+        )
+        .is_ok()
+    }
+}
+
+fn non_zero(iterations: u32) -> NonZeroU32 {
+    NonZeroU32::new(iterations).unwrap_or(NonZeroU32::MIN)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).ok().filter(|p| p.len() == 2)?;
+            u8::from_str_radix(pair, 16).ok()
+        })
+        .collect()
+}
+
+fn keyring_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_ENTRY)
+        .map_err(|e| format!("Failed to access keyring: {e}"))
+}
+
+fn load_hash() -> Result<Option<PassphraseHash>, String> {
+    match keyring_entry()?.get_password() {
+        Ok(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse app lock passphrase: {e}")),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read app lock passphrase: {e}")),
+    }
+}
+
+/// Loads the lock configuration and starts the idle watcher. The app starts
+/// locked when the lock is enabled, so restarting it doesn't bypass the lock.
+pub fn init(app: &AppHandle, idle_timeout_minutes: Option<u32>) {
+    let passphrase_set = match load_hash() {
+        Ok(hash) => hash.is_some(),
+        Err(e) => {
+            log::warn!("Failed to load app lock passphrase: {e}");
+            false
+        }
+    };
+    with_state(|state| {
+        state.passphrase_set = passphrase_set;
+        state.idle_timeout = idle_timeout(idle_timeout_minutes);
+        state.locked = passphrase_set && state.idle_timeout.is_some();
+    });
+    log::info!(
+        "App lock {}",
+        if passphrase_set {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(IDLE_CHECK_INTERVAL).await;
+            if with_state(|state| state.lock_if_idle(Instant::now())) {
+                log::info!("App locked after idle timeout");
+                emit_status(&app);
+            }
+        }
+    });
+}
+
+fn idle_timeout(minutes: Option<u32>) -> Option<Duration> {
+    minutes
+        .filter(|m| *m > 0)
+        .map(|m| Duration::from_secs(u64::from(m) * 60))
+}
+
+/// Applies a changed idle timeout from preferences.
+pub fn set_idle_timeout(minutes: Option<u32>) {
+    with_state(|state| {
+        state.idle_timeout = idle_timeout(minutes);
+        state.last_activity = Instant::now();
+    });
+}
+
+/// Fails while the app is locked; otherwise records activity.
+/// Called first by every mutating command and credential read.
+pub fn ensure_unlocked() -> Result<(), String> {
+    with_state(|state| {
+        let now = Instant::now();
+        state.lock_if_idle(now);
+        if state.locked {
+            return Err(LOCKED_ERROR.to_string());
+        }
+        state.last_activity = now;
+        Ok(())
+    })
+}
+
+/// Records user activity, postponing the idle lock.
+pub fn record_activity() {
+    with_state(|state| {
+        if !state.locked {
+            state.last_activity = Instant::now();
+        }
+    });
+}
+
+pub fn status() -> AppLockStatus {
+    with_state(|state| {
+        state.lock_if_idle(Instant::now());
+        state.status()
+    })
+}
+
+/// Locks the app now.
+pub fn lock(app: &AppHandle) -> Result<(), String> {
+    with_state(|state| {
+        if !state.passphrase_set {
+            return Err("Set a lock passphrase before locking the app".to_string());
+        }
+        state.locked = true;
+        Ok(())
+    })?;
+    log::info!("App locked");
+    emit_status(app);
+    Ok(())
+}
+
+/// Unlocks the app if the passphrase matches.
+pub fn unlock(app: &AppHandle, passphrase: &str) -> Result<(), String> {
+    with_state(|state| match state.retry_after {
+        Some(retry_after) if Instant::now() < retry_after => Err(format!(
+            "Too many failed attempts. Try again in {}s.",
+            retry_after
+                .saturating_duration_since(Instant::now())
+                .as_secs()
+                .max(1)
+        )),
+        _ => Ok(()),
+    })?;
+
+    let Some(hash) = load_hash()? else {
+        with_state(|state| {
+            state.passphrase_set = false;
+            state.locked = false;
+        });
+        emit_status(app);
+        return Ok(());
+    };
+
+    if !hash.verify(passphrase) {
+        with_state(|state| {
+            state.failed_attempts += 1;
+            if state.failed_attempts >= MAX_FAILED_ATTEMPTS {
+                state.failed_attempts = 0;
+                state.retry_after = Some(Instant::now() + FAILED_ATTEMPTS_COOLDOWN);
+            }
+        });
+        log::warn!("Failed app unlock attempt");
+        return Err("Incorrect passphrase".to_string());
+    }
+
+    with_state(|state| {
+        state.locked = false;
+        state.failed_attempts = 0;
+        state.retry_after = None;
+        state.last_activity = Instant::now();
+    });
+    log::info!("App unlocked");
+    emit_status(app);
+    Ok(())
+}
+
+/// Sets or changes the lock passphrase. Changing or removing an existing
+/// passphrase requires the current one.
+pub fn set_passphrase(current: Option<&str>, passphrase: Option<&str>) -> Result<(), String> {
+    if let Some(existing) = load_hash()? {
+        if !current.is_some_and(|c| existing.verify(c)) {
+            return Err("Current passphrase is incorrect".to_string());
+        }
+    }
+
+    let entry = keyring_entry()?;
+    match passphrase {
+        Some(passphrase) => {
+            if passphrase.chars().count() < MIN_PASSPHRASE_LENGTH {
+                return Err(format!(
+                    "Passphrase must be at least {MIN_PASSPHRASE_LENGTH} characters"
+                ));
+            }
+            let hash = PassphraseHash::new(passphrase, PBKDF2_ITERATIONS)?;
+            let json = serde_json::to_string(&hash)
+                .map_err(|e| format!("Failed to serialize passphrase hash: {e}"))?;
+            entry
+                .set_password(&json)
+                .map_err(|e| format!("Failed to save passphrase: {e}"))?;
+        }
+        None => match entry.delete_password() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(format!("Failed to remove passphrase: {e}")),
+        },
+    }
+
+    with_state(|state| {
+        state.passphrase_set = passphrase.is_some();
+        state.locked = false;
+        state.last_activity = Instant::now();
+    });
+    log::info!(
+        "App lock passphrase {}",
+        if passphrase.is_some() {
+            "set"
+        } else {
+            "removed"
+        }
+    );
+    Ok(())
+}
+
+fn emit_status(app: &AppHandle) {
+    windows::broadcast(app, APP_LOCK_CHANGED_EVENT, status());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passphrase_hash() {
+        let hash = PassphraseHash::new("correct horse", 1_000).unwrap();
+        assert!(hash.verify("correct horse"));
+        assert!(!hash.verify("correct horse "));
+
+        let other = PassphraseHash::new("correct horse", 1_000).unwrap();
+        assert_ne!(hash.salt, other.salt);
+        assert_eq!(from_hex(&to_hex(&[0, 15, 255])), Some(vec![0, 15, 255]));
+        assert_eq!(from_hex("0g"), None);
+        assert_eq!(from_hex("abc"), None);
+    }
+
+    #[test]
+    fn test_lock_if_idle() {
+        let start = Instant::now();
+        let mut state = LockState {
+            passphrase_set: true,
+            idle_timeout: idle_timeout(Some(5)),
+            last_activity: start,
+            ..LockState::new()
+        };
+        assert!(!state.lock_if_idle(start + Duration::from_secs(299)));
+        assert!(state.lock_if_idle(start + Duration::from_secs(300)));
+        assert!(!state.lock_if_idle(start + Duration::from_secs(301)));
+        assert!(state.locked);
+
+        let mut without_passphrase = LockState {
+            passphrase_set: false,
+            idle_timeout: idle_timeout(Some(5)),
+            last_activity: start,
+            ..LockState::new()
+        };
+        assert!(!without_passphrase.lock_if_idle(start + Duration::from_secs(3600)));
+        assert_eq!(idle_timeout(Some(0)), None);
+    }
+}
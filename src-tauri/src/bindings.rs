@@ -2,15 +2,24 @@ use tauri_specta::{collect_commands, Builder};
 
 pub fn generate_bindings() -> Builder<tauri::Wry> {
     use crate::commands::{
-        config, credentials, flows, gitlab, jenkins, keycloak, kubernetes, notifications,
-        preferences, quick_pane, recovery, sonarqube,
+        annotations, app_lock, builds, checks, config, credentials, demo, event_recorder, flows,
+        gitlab, initial_sync, jenkins, keycloak, kubernetes, linkage, metrics, notifications,
+        onboarding, preferences, project_inventory, quick_actions, quick_pane, recovery, refresh,
+        reports, saved_filters, sonarqube, tag_watcher, updates, webhooks, windows,
     };
 
     Builder::<tauri::Wry>::new().commands(collect_commands![
         preferences::greet,
         preferences::load_preferences,
         preferences::save_preferences,
+        preferences::get_feature_flags,
         notifications::send_native_notification,
+        notifications::send_actionable_notification,
+        notifications::list_actionable_notifications,
+        notifications::run_notification_action,
+        // First-run setup commands
+        onboarding::get_onboarding_state,
+        onboarding::complete_onboarding_step,
         recovery::save_emergency_data,
         recovery::load_emergency_data,
         recovery::cleanup_old_recovery_files,
@@ -19,6 +28,18 @@ pub fn generate_bindings() -> Builder<tauri::Wry> {
         quick_pane::toggle_quick_pane,
         quick_pane::get_default_quick_pane_shortcut,
         quick_pane::update_quick_pane_shortcut,
+        // Quick action and macro commands
+        quick_actions::run_quick_action,
+        quick_actions::start_macro_recording,
+        quick_actions::get_macro_recording_status,
+        quick_actions::stop_macro_recording,
+        quick_actions::run_quick_pane_macro,
+        // App lock commands
+        app_lock::get_app_lock_status,
+        app_lock::lock_app,
+        app_lock::unlock_app,
+        app_lock::set_app_lock_passphrase,
+        app_lock::record_app_activity,
         // Config management commands
         config::load_projects,
         config::save_projects,
@@ -27,38 +48,184 @@ pub fn generate_bindings() -> Builder<tauri::Wry> {
         config::load_integrations,
         config::save_integrations,
         config::test_integration_connection,
+        config::get_integration_api_version,
         config::load_mappings,
         config::save_mappings,
+        config::list_config_history,
+        config::diff_config_history,
+        config::load_smoke_tests,
+        config::save_smoke_tests,
+        config::load_uptime_targets,
+        config::save_uptime_targets,
+        config::load_quick_pane_macros,
+        config::save_quick_pane_macros,
+        config::load_cost_rates,
+        config::save_cost_rates,
+        config::load_image_scanners,
+        config::save_image_scanners,
+        config::load_desired_states,
+        config::save_desired_states,
+        config::load_tag_watches,
+        config::save_tag_watches,
+        config::load_event_recordings,
+        config::save_event_recordings,
+        config::load_failure_rules,
+        config::save_failure_rules,
+        config::get_default_failure_rules,
+        config::load_report_definitions,
+        config::save_report_definitions,
+        config::load_saved_filters,
+        config::save_saved_filters,
+        // Endpoint check commands
+        checks::run_smoke_tests,
+        checks::start_uptime_monitor,
+        checks::stop_uptime_monitor,
+        checks::get_uptime_status,
+        checks::list_certificates,
+        checks::check_certificates,
+        checks::probe_endpoint,
+        checks::check_image_security,
+        checks::check_k8s_running_images,
+        checks::fetch_image_sbom,
+        checks::diff_sboms,
+        checks::check_environment_drift,
+        // Annotation commands
+        annotations::list_annotations,
+        annotations::add_annotation,
+        annotations::update_annotation,
+        annotations::delete_annotation,
+        annotations::export_annotations,
+        // Build comparison commands
+        builds::compare_builds,
+        // App update commands
+        updates::check_for_updates,
+        updates::install_update,
+        // GitLab-Jenkins linkage commands
+        linkage::detect_gitlab_jenkins_links,
+        // Metrics commands
+        metrics::get_metrics_endpoint_status,
+        metrics::get_task_pool_status,
+        metrics::estimate_polling_cost,
+        // Saved filter commands
+        saved_filters::evaluate_saved_filter,
+        // Report commands
+        reports::generate_report,
+        reports::list_generated_reports,
+        reports::open_generated_report,
         // Credentials management commands
         credentials::save_integration_credentials,
+        credentials::check_integration_credentials,
         credentials::get_integration_credentials,
         credentials::delete_integration_credentials,
+        credentials::save_secret,
+        credentials::delete_secret,
+        credentials::check_secret_reference,
         // Flow editor commands
         flows::load_flows,
         flows::load_flow,
         flows::save_flow,
         flows::delete_flow,
+        flows::run_flow,
+        flows::run_flow_in_environment,
+        flows::simulate_flow,
+        flows::get_flow_run,
+        flows::list_flow_runs,
+        flows::cancel_flow_run,
+        flows::provide_flow_input,
+        flows::list_flow_run_artifacts,
+        flows::open_flow_run_artifact,
+        // Webhook receiver commands
+        webhooks::start_webhook_receiver,
+        webhooks::stop_webhook_receiver,
+        webhooks::get_webhook_receiver_status,
+        // Window commands
+        windows::open_view_window,
+        windows::subscribe_window_events,
+        windows::list_app_windows,
+        // Tag watcher commands
+        tag_watcher::start_tag_watcher,
+        tag_watcher::stop_tag_watcher,
+        tag_watcher::get_tag_watcher_status,
+        // Kubernetes event recorder commands
+        event_recorder::query_recorded_k8s_events,
+        event_recorder::get_event_recorder_status,
+        // Aggregate refresh commands
+        refresh::refresh_all,
+        // Initial sync commands
+        initial_sync::start_initial_sync,
+        initial_sync::get_initial_sync_status,
+        // GitLab project inventory commands
+        project_inventory::take_project_inventory_snapshot,
+        project_inventory::list_project_inventory_snapshots,
+        project_inventory::diff_project_inventory,
         // GitLab integration commands
         gitlab::fetch_gitlab_projects,
         gitlab::fetch_gitlab_pipelines,
+        gitlab::fetch_gitlab_project_overview,
         gitlab::fetch_gitlab_webhooks,
+        gitlab::fetch_gitlab_issues,
+        gitlab::fetch_gitlab_milestones,
+        gitlab::fetch_gitlab_epics,
+        gitlab::fetch_gitlab_freeze_periods,
+        gitlab::fetch_gitlab_packages,
+        gitlab::fetch_gitlab_file,
+        gitlab::lint_gitlab_ci,
+        gitlab::scan_gitlab_secrets,
+        gitlab::fetch_gitlab_runbooks,
+        gitlab::fetch_gitlab_runbook_content,
         gitlab::trigger_gitlab_pipeline,
         // Jenkins integration commands
         jenkins::fetch_jenkins_jobs,
         jenkins::fetch_jenkins_builds,
         jenkins::fetch_jenkins_build_details,
+        jenkins::fetch_jenkins_credentials_metadata,
+        jenkins::fetch_jenkins_job_parameters,
+        jenkins::fetch_jenkins_job_config_summary,
+        jenkins::fetch_jenkins_retention_info,
+        jenkins::classify_jenkins_build_failure,
+        jenkins::list_jenkins_failure_classifications,
+        jenkins::get_jenkins_failure_trends,
+        jenkins::scan_jenkins_secrets,
         jenkins::trigger_jenkins_build,
+        jenkins::rebuild_jenkins_build,
+        jenkins::replay_jenkins_pipeline,
+        jenkins::set_jenkins_job_enabled,
         // Kubernetes integration commands
         kubernetes::fetch_k8s_namespaces,
         kubernetes::fetch_k8s_pods,
         kubernetes::fetch_k8s_services,
         kubernetes::fetch_k8s_pod_details,
+        kubernetes::fetch_k8s_ingress_hosts,
+        kubernetes::fetch_k8s_image_inventory,
+        kubernetes::bulk_k8s_pod_action,
+        kubernetes::check_k8s_eviction_safety,
+        kubernetes::set_k8s_deployment_image,
+        kubernetes::list_k8s_deployment_revisions,
+        kubernetes::rollback_k8s_deployment,
+        kubernetes::start_canary,
+        kubernetes::promote_canary,
+        kubernetes::abort_canary,
+        kubernetes::switch_blue_green,
+        kubernetes::estimate_k8s_costs,
         // SonarQube integration commands
         sonarqube::fetch_sonarqube_projects,
         sonarqube::fetch_sonarqube_metrics,
+        sonarqube::fetch_sonarqube_quality_profiles,
+        sonarqube::check_sonarqube_profile_drift,
         // Keycloak integration commands
         keycloak::fetch_keycloak_realms,
         keycloak::fetch_keycloak_clients,
+        keycloak::fetch_keycloak_realm_keys,
+        keycloak::export_keycloak_users,
+        keycloak::import_keycloak_users,
+        keycloak::send_keycloak_actions_email,
+        // Demo integration commands
+        demo::fetch_demo_projects,
+        demo::fetch_demo_pipelines,
+        demo::fetch_demo_jobs,
+        demo::fetch_demo_builds,
+        demo::fetch_demo_pods,
+        demo::fetch_demo_metrics,
     ])
 }
 
@@ -0,0 +1,532 @@
+//! Jenkins build failure classification.
+//!
+//! The console log of a failed build is matched line by line against the
+//! failure rules (`failure_rules.yaml`, or built-in rules for common failures
+//! when none are configured). The first rule with a matching line gives the
+//! failure category, and the lines around the match are kept as an excerpt.
+//! Classifications are stored in `<app data>/failure_classifications.json`
+//! so categories can be reported over time.
+
+use chrono::{Days, NaiveDate, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex, PoisonError};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::config::load_failure_rules;
+use crate::commands::jenkins::jenkins_adapter;
+use crate::integrations::jenkins::JenkinsBuildStatus;
+use crate::types::{FailureRule, Timestamp};
+
+/// Category of failures no rule matches.
+pub const UNKNOWN_CATEGORY: &str = "unknown";
+
+/// Classifications kept (the most recent builds).
+const MAX_CLASSIFICATIONS: usize = 5_000;
+
+/// Lines shown before and after the matching line.
+const EXCERPT_CONTEXT: usize = 3;
+
+/// Characters kept of each excerpt line.
+const MAX_LINE_CHARS: usize = 500;
+
+/// Rules used while no failure rules are configured: (id, category, pattern).
+const DEFAULT_RULES: [(&str, &str, &str); 9] = [
+    (
+        "out-of-memory",
+        "out_of_memory",
+        r"OutOfMemoryError|Cannot allocate memory|JavaScript heap out of memory|exit code 137",
+    ),
+    ("disk-full", "disk_space", r"No space left on device"),
+    (
+        "network",
+        "network",
+        r"(?i)connection refused|connection timed out|could not resolve host|temporary failure in name resolution",
+    ),
+    (
+        "npm",
+        "dependency",
+        r"npm ERR!|ERR_PNPM_|error An unexpected error occurred",
+    ),
+    (
+        "maven-dependency",
+        "dependency",
+        r"Could not resolve dependencies|Could not transfer artifact",
+    ),
+    (
+        "compilation",
+        "compilation",
+        r"COMPILATION ERROR|error\[E\d+\]|error TS\d+:|error: cannot find symbol",
+    ),
+    (
+        "tests",
+        "test_failure",
+        r"There (are|were) test failures|Tests run:.*Failures: [1-9]|\b[1-9]\d* (failing|failed)\b",
+    ),
+    (
+        "timeout",
+        "timeout",
+        r"Timeout has been exceeded|Build timed out",
+    ),
+    (
+        "permission",
+        "permission",
+        r"(?i)401 Unauthorized|403 Forbidden|authentication failed|permission denied",
+    ),
+];
+
+/// Serializes read-modify-write cycles on the classifications file.
+static STORE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// Failure category of a build.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FailureClassification {
+    pub integration_id: String,
+    pub job_name: String,
+    pub build_number: u32,
+    pub status: JenkinsBuildStatus,
+    /// Category of the matching rule, or "unknown"
+    pub category: String,
+    /// Rule that matched
+    pub rule_id: Option<String>,
+    /// Matching line and the lines around it
+    pub excerpt: Option<String>,
+    /// Line number of the match in the (possibly truncated) log
+    pub line: Option<u32>,
+    pub build_started_at: Timestamp,
+    pub classified_at: Timestamp,
+}
+
+/// Failures of one category.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FailureCategoryCount {
+    pub category: String,
+    pub count: u32,
+}
+
+/// Failures per category of builds started on one day.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FailureTrendPoint {
+    /// Day (YYYY-MM-DD, UTC)
+    pub date: String,
+    pub categories: Vec<FailureCategoryCount>,
+}
+
+/// Classified failures over a period.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FailureTrends {
+    pub days: u32,
+    /// Failures per category, most frequent first
+    pub totals: Vec<FailureCategoryCount>,
+    /// Days with classified failures, oldest first
+    pub daily: Vec<FailureTrendPoint>,
+}
+
+/// A rule with its compiled pattern.
+pub struct CompiledRule {
+    id: String,
+    category: String,
+    regex: Regex,
+}
+
+/// Compiles failure rules, failing on the first invalid pattern.
+pub fn compile_rules(rules: &[FailureRule]) -> Result<Vec<CompiledRule>, String> {
+    rules
+        .iter()
+        .map(|rule| {
+            if rule.category.trim().is_empty() {
+                return Err(format!("Failure rule {} has no category", rule.id));
+            }
+            let regex = Regex::new(&rule.pattern)
+                .map_err(|e| format!("Invalid pattern in failure rule {}: {e}", rule.id))?;
+            Ok(CompiledRule {
+                id: rule.id.clone(),
+                category: rule.category.clone(),
+                regex,
+            })
+        })
+        .collect()
+}
+
+/// Built-in rules for common failures.
+pub fn default_rules() -> Vec<FailureRule> {
+    DEFAULT_RULES
+        .iter()
+        .map(|(id, category, pattern)| FailureRule {
+            id: id.to_string(),
+            category: category.to_string(),
+            pattern: pattern.to_string(),
+        })
+        .collect()
+}
+
+/// A rule matching a log.
+#[derive(Debug, PartialEq, Eq)]
+struct LogMatch {
+    rule_id: String,
+    category: String,
+    /// Zero-based index of the matching line
+    line: usize,
+    excerpt: String,
+}
+
+/// Finds the first rule (in rule order) matching a line of the log.
+fn classify_log(log: &str, rules: &[CompiledRule]) -> Option<LogMatch> {
+    let lines: Vec<&str> = log.lines().collect();
+    rules.iter().find_map(|rule| {
+        let line = lines.iter().position(|line| rule.regex.is_match(line))?;
+        let start = line.saturating_sub(EXCERPT_CONTEXT);
+        let end = (line + EXCERPT_CONTEXT + 1).min(lines.len());
+        let excerpt = lines[start..end]
+            .iter()
+            .map(|line| line.chars().take(MAX_LINE_CHARS).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some(LogMatch {
+            rule_id: rule.id.clone(),
+            category: rule.category.clone(),
+            line,
+            excerpt,
+        })
+    })
+}
+
+/// Classifies a failed or unstable build from its console log and stores
+/// the classification.
+pub async fn classify_build(
+    app: &AppHandle,
+    integration_id: &str,
+    job_name: &str,
+    build_number: u32,
+) -> Result<FailureClassification, String> {
+    let mut rules = load_failure_rules(app.clone()).await?;
+    if rules.is_empty() {
+        rules = default_rules();
+    }
+    let rules = compile_rules(&rules)?;
+
+    let adapter = jenkins_adapter(app, integration_id).await?;
+    let build = adapter
+        .fetch_build_details(job_name, build_number)
+        .await
+        .map_err(|e| format!("Failed to fetch build details: {e}"))?;
+    if !matches!(
+        build.status,
+        JenkinsBuildStatus::Failure | JenkinsBuildStatus::Unstable
+    ) {
+        return Err(format!(
+            "Build #{build_number} of {job_name} did not fail; only failed builds are classified"
+        ));
+    }
+    let log = adapter
+        .fetch_console_text(job_name, build_number)
+        .await
+        .map_err(|e| format!("Failed to fetch console log: {e}"))?;
+
+    let log_match = classify_log(&log, &rules);
+    let classification = FailureClassification {
+        integration_id: integration_id.to_string(),
+        job_name: job_name.to_string(),
+        build_number,
+        status: build.status,
+        category: log_match
+            .as_ref()
+            .map_or_else(|| UNKNOWN_CATEGORY.to_string(), |m| m.category.clone()),
+        rule_id: log_match.as_ref().map(|m| m.rule_id.clone()),
+        line: log_match.as_ref().map(|m| m.line as u32 + 1),
+        excerpt: log_match.map(|m| m.excerpt),
+        build_started_at: build.timestamp,
+        classified_at: Timestamp::from_datetime(Utc::now()),
+    };
+    log::info!(
+        "Classified {job_name} #{build_number} as {}",
+        classification.category
+    );
+
+    let _guard = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut stored = read_classifications(app)?;
+    store(&mut stored, classification.clone());
+    write_classifications(app, &stored)?;
+    Ok(classification)
+}
+
+/// Returns the stored classifications of an integration, newest build first,
+/// optionally limited to one job.
+pub fn list(
+    app: &AppHandle,
+    integration_id: &str,
+    job_name: Option<&str>,
+) -> Result<Vec<FailureClassification>, String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut classifications: Vec<FailureClassification> = read_classifications(app)?
+        .into_iter()
+        .filter(|c| c.integration_id == integration_id)
+        .filter(|c| job_name.is_none_or(|job| c.job_name == job))
+        .collect();
+    classifications.sort_by_key(|c| std::cmp::Reverse(c.build_started_at.datetime()));
+    Ok(classifications)
+}
+
+/// Counts the failures per category of builds started in the last `days`
+/// days, for one integration or all of them.
+pub fn trends(
+    app: &AppHandle,
+    integration_id: Option<&str>,
+    days: u32,
+) -> Result<FailureTrends, String> {
+    let classifications: Vec<FailureClassification> = {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        read_classifications(app)?
+    };
+    let classifications: Vec<FailureClassification> = classifications
+        .into_iter()
+        .filter(|c| integration_id.is_none_or(|id| c.integration_id == id))
+        .collect();
+    Ok(count_trends(
+        &classifications,
+        days,
+        Utc::now().date_naive(),
+    ))
+}
+
+fn count_trends(
+    classifications: &[FailureClassification],
+    days: u32,
+    today: NaiveDate,
+) -> FailureTrends {
+    let since = today
+        .checked_sub_days(Days::new(u64::from(days.saturating_sub(1))))
+        .unwrap_or(today);
+
+    let mut totals: BTreeMap<&str, u32> = BTreeMap::new();
+    let mut daily: BTreeMap<NaiveDate, BTreeMap<&str, u32>> = BTreeMap::new();
+    for classification in classifications {
+        let day = classification.build_started_at.datetime().date_naive();
+        if day < since || day > today {
+            continue;
+        }
+        *totals.entry(&classification.category).or_default() += 1;
+        *daily
+            .entry(day)
+            .or_default()
+            .entry(&classification.category)
+            .or_default() += 1;
+    }
+
+    let counts = |counts: BTreeMap<&str, u32>| -> Vec<FailureCategoryCount> {
+        let mut counts: Vec<FailureCategoryCount> = counts
+            .into_iter()
+            .map(|(category, count)| FailureCategoryCount {
+                category: category.to_string(),
+                count,
+            })
+            .collect();
+        counts.sort_by_key(|c| std::cmp::Reverse(c.count));
+        counts
+    };
+    FailureTrends {
+        days,
+        totals: counts(totals),
+        daily: daily
+            .into_iter()
+            .map(|(day, categories)| FailureTrendPoint {
+                date: day.format("%Y-%m-%d").to_string(),
+                categories: counts(categories),
+            })
+            .collect(),
+    }
+}
+
+/// Adds or replaces the classification of a build, keeping the most recent
+/// `MAX_CLASSIFICATIONS` builds.
+fn store(stored: &mut Vec<FailureClassification>, classification: FailureClassification) {
+    stored.retain(|c| {
+        (
+            c.integration_id.as_str(),
+            c.job_name.as_str(),
+            c.build_number,
+        ) != (
+            classification.integration_id.as_str(),
+            classification.job_name.as_str(),
+            classification.build_number,
+        )
+    });
+    stored.push(classification);
+    if stored.len() > MAX_CLASSIFICATIONS {
+        stored.sort_by_key(|c| std::cmp::Reverse(c.build_started_at.datetime()));
+        stored.truncate(MAX_CLASSIFICATIONS);
+    }
+}
+
+fn classifications_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    Ok(app_data_dir.join("failure_classifications.json"))
+}
+
+fn read_classifications(app: &AppHandle) -> Result<Vec<FailureClassification>, String> {
+    let path = classifications_path(app)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read failure classifications: {e}"))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse failure classifications: {e}"))
+}
+
+fn write_classifications(
+    app: &AppHandle,
+    classifications: &[FailureClassification],
+) -> Result<(), String> {
+    let path = classifications_path(app)?;
+    let json = serde_json::to_string(classifications)
+        .map_err(|e| format!("Failed to serialize failure classifications: {e}"))?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {e}"))?;
+    }
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json)
+        .map_err(|e| format!("Failed to write failure classifications: {e}"))?;
+    if let Err(rename_err) = std::fs::rename(&temp_path, &path) {
+        if let Err(remove_err) = std::fs::remove_file(&temp_path) {
+            log::warn!("Failed to remove temp file after rename failure: {remove_err}");
+        }
+        return Err(format!(
+            "Failed to finalize failure classifications: {rename_err}"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classification(
+        job: &str,
+        build: u32,
+        category: &str,
+        started: &str,
+    ) -> FailureClassification {
+        FailureClassification {
+            integration_id: "ci".to_string(),
+            job_name: job.to_string(),
+            build_number: build,
+            status: JenkinsBuildStatus::Failure,
+            category: category.to_string(),
+            rule_id: None,
+            excerpt: None,
+            line: None,
+            build_started_at: Timestamp::parse(started).unwrap(),
+            classified_at: Timestamp::parse(started).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_default_rules_compile() {
+        assert_eq!(
+            compile_rules(&default_rules()).unwrap().len(),
+            DEFAULT_RULES.len()
+        );
+
+        let invalid = FailureRule {
+            id: "broken".to_string(),
+            category: "other".to_string(),
+            pattern: "(unclosed".to_string(),
+        };
+        assert!(compile_rules(&[invalid]).is_err());
+    }
+
+    #[test]
+    fn test_classify_log() {
+        let rules = compile_rules(&default_rules()).unwrap();
+        let log = "\
+Started by user admin
++ npm ci
+npm ERR! code ECONNREFUSED
+npm ERR! errno ECONNREFUSED
+npm ERR! connect ECONNREFUSED 10.0.0.5:443
+line 6
+line 7
+line 8
+Finished: FAILURE";
+
+        let log_match = classify_log(log, &rules).unwrap();
+
+        // The network rule comes first but no line says "connection refused"
+        assert_eq!(log_match.category, "dependency");
+        assert_eq!(log_match.rule_id, "npm");
+        assert_eq!(log_match.line, 2);
+        assert_eq!(log_match.excerpt.lines().count(), 6);
+        assert!(log_match.excerpt.starts_with("Started by user admin"));
+
+        let oom = "java.lang.OutOfMemoryError: Java heap space\nnpm ERR! x";
+        assert_eq!(classify_log(oom, &rules).unwrap().category, "out_of_memory");
+        assert_eq!(classify_log("Finished: FAILURE", &rules), None);
+    }
+
+    #[test]
+    fn test_store_replaces_build() {
+        let mut stored = vec![classification("api", 7, "unknown", "2024-05-01T10:00:00Z")];
+
+        store(
+            &mut stored,
+            classification("api", 7, "network", "2024-05-01T10:00:00Z"),
+        );
+        store(
+            &mut stored,
+            classification("api", 8, "network", "2024-05-02T10:00:00Z"),
+        );
+
+        let categories: Vec<(u32, &str)> = stored
+            .iter()
+            .map(|c| (c.build_number, c.category.as_str()))
+            .collect();
+        assert_eq!(categories, [(7, "network"), (8, "network")]);
+    }
+
+    #[test]
+    fn test_count_trends() {
+        let classifications = [
+            classification("api", 1, "network", "2024-05-01T10:00:00Z"),
+            classification("api", 2, "network", "2024-05-03T10:00:00Z"),
+            classification("web", 3, "test_failure", "2024-05-03T11:00:00Z"),
+            classification("web", 4, "network", "2024-05-03T12:00:00Z"),
+            // Outside the period
+            classification("web", 5, "timeout", "2024-04-20T12:00:00Z"),
+        ];
+        let today = NaiveDate::from_ymd_opt(2024, 5, 3).unwrap();
+
+        let trends = count_trends(&classifications, 7, today);
+
+        assert_eq!(
+            trends.totals,
+            [
+                FailureCategoryCount {
+                    category: "network".to_string(),
+                    count: 3
+                },
+                FailureCategoryCount {
+                    category: "test_failure".to_string(),
+                    count: 1
+                },
+            ]
+        );
+        let days: Vec<(&str, usize)> = trends
+            .daily
+            .iter()
+            .map(|p| (p.date.as_str(), p.categories.len()))
+            .collect();
+        assert_eq!(days, [("2024-05-01", 1), ("2024-05-03", 2)]);
+    }
+}
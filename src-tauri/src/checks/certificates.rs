@@ -0,0 +1,506 @@
+//! TLS certificate expiry monitoring.
+//!
+//! Collects the HTTPS hosts of all integrations and of the TLS ingress hosts
+//! in every environment namespace, reads each host's certificate, and stores
+//! the results in `<app data>/certificates.json`. Certificates expiring within
+//! 30, 14 and 7 days raise a native notification once per threshold.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::config::{load_environments, load_integrations};
+use crate::commands::kubernetes::kubernetes_adapter;
+use crate::commands::notifications::send_native_notification;
+use crate::metrics;
+use crate::task_pool::{self, Priority};
+use crate::types::IntegrationType;
+
+/// Days before expiry at which a notification is raised.
+pub const ALERT_THRESHOLDS_DAYS: [i64; 3] = [30, 14, 7];
+
+/// Delay before the first automatic check after startup.
+const STARTUP_DELAY: Duration = Duration::from_secs(60);
+
+/// Interval between automatic checks.
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Timeout for connecting to a host and reading its certificate.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Certificate details of a host.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct CertificateInfo {
+    /// Host name, with the port if it is not 443
+    pub host: String,
+    /// Where the host was found (e.g., "integration: GitLab", "ingress: prod/shop")
+    pub sources: Vec<String>,
+    /// Subject common name
+    pub subject: Option<String>,
+    /// Issuer common name
+    pub issuer: Option<String>,
+    /// Expiry timestamp (RFC 3339)
+    pub expires_at: Option<String>,
+    /// Whole days until expiry (negative once expired)
+    pub days_remaining: Option<i32>,
+    /// Check timestamp (RFC 3339)
+    pub checked_at: String,
+    /// Why the certificate could not be read
+    pub error: Option<String>,
+}
+
+/// Persisted results and the notifications already sent.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CertificateStore {
+    certificates: Vec<CertificateInfo>,
+    /// Thresholds already notified, keyed by `<host>|<expires_at>` so a
+    /// renewed certificate starts over
+    alerts_sent: HashMap<String, Vec<i64>>,
+}
+
+/// Returns the results of the last check.
+pub fn list(app: &AppHandle) -> Result<Vec<CertificateInfo>, String> {
+    Ok(load_store(app)?.certificates)
+}
+
+/// Starts a background task checking certificates shortly after startup and then daily.
+pub fn start_daily_checks(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        loop {
+            let result = task_pool::run(Priority::Poller, "certificates", check_all(&app)).await;
+            metrics::record_poll("certificates", result.is_ok());
+            if let Err(e) = result {
+                log::warn!("Certificate check failed: {e}");
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Checks every known host, stores the results and sends due notifications.
+pub async fn check_all(app: &AppHandle) -> Result<Vec<CertificateInfo>, String> {
+    let hosts = collect_hosts(app).await?;
+    log::info!("Checking TLS certificates of {} host(s)", hosts.len());
+
+    let mut certificates = Vec::with_capacity(hosts.len());
+    for (host, sources) in hosts {
+        certificates.push(check_host(&host, sources).await);
+    }
+
+    let mut store = load_store(app)?;
+    let mut alerts = Vec::new();
+    for cert in &certificates {
+        let (Some(days), Some(expires_at)) = (cert.days_remaining, &cert.expires_at) else {
+            continue;
+        };
+        let sent = store
+            .alerts_sent
+            .entry(format!("{}|{expires_at}", cert.host))
+            .or_default();
+        if let Some(threshold) = due_alert(i64::from(days), sent) {
+            // Larger thresholds are passed as well; don't notify for them later.
+            sent.retain(|t| *t < threshold);
+            sent.extend(ALERT_THRESHOLDS_DAYS.iter().filter(|t| **t >= threshold));
+            alerts.push((cert.host.clone(), days));
+        }
+    }
+
+    // Forget alerts of certificates that are no longer served.
+    store.alerts_sent.retain(|key, _| {
+        certificates.iter().any(|c| {
+            c.expires_at
+                .as_ref()
+                .is_some_and(|e| *key == format!("{}|{e}", c.host))
+        })
+    });
+    store.certificates = certificates.clone();
+    save_store(app, &store)?;
+
+    for (host, days) in alerts {
+        let title = if days < 0 {
+            format!("TLS certificate of {host} has expired")
+        } else {
+            format!("TLS certificate of {host} expires in {days} day(s)")
+        };
+        log::warn!("{title}");
+        if let Err(e) = send_native_notification(app.clone(), title, None).await {
+            log::warn!("Failed to send certificate alert: {e}");
+        }
+    }
+
+    Ok(certificates)
+}
+
+/// Returns the most urgent threshold reached but not yet notified.
+fn due_alert(days_remaining: i64, sent: &[i64]) -> Option<i64> {
+    ALERT_THRESHOLDS_DAYS
+        .iter()
+        .copied()
+        .filter(|t| days_remaining <= *t)
+        .min()
+        .filter(|t| !sent.contains(t))
+}
+
+/// Gathers HTTPS hosts from integrations and environment ingresses.
+async fn collect_hosts(app: &AppHandle) -> Result<BTreeMap<String, Vec<String>>, String> {
+    let mut hosts: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let integrations = load_integrations(app.clone()).await?;
+
+    for integration in &integrations {
+        if let Some(host) = https_host(&integration.base_url) {
+            hosts
+                .entry(host)
+                .or_default()
+                .push(format!("integration: {}", integration.name));
+        }
+    }
+
+    let namespaces: Vec<String> = load_environments(app.clone())
+        .await?
+        .into_iter()
+        .filter_map(|e| e.namespace)
+        .collect();
+    if namespaces.is_empty() {
+        return Ok(hosts);
+    }
+
+    for integration in integrations
+        .iter()
+        .filter(|i| i.integration_type == IntegrationType::Kubernetes)
+    {
+        let adapter = match kubernetes_adapter(app, &integration.id).await {
+            Ok(adapter) => adapter,
+            Err(e) => {
+                log::warn!("Skipping ingress hosts of {}: {e}", integration.name);
+                continue;
+            }
+        };
+        for namespace in &namespaces {
+            match adapter.fetch_ingress_hosts(namespace).await {
+                Ok(ingress_hosts) => {
+                    for ingress_host in ingress_hosts.into_iter().filter(|h| h.tls) {
+                        hosts.entry(ingress_host.host).or_default().push(format!(
+                            "ingress: {}/{}",
+                            ingress_host.namespace, ingress_host.ingress
+                        ));
+                    }
+                }
+                Err(e) => log::debug!(
+                    "No ingress hosts for {namespace} on {}: {e}",
+                    integration.name
+                ),
+            }
+        }
+    }
+
+    Ok(hosts)
+}
+
+/// Returns `host[:port]` of an HTTPS URL.
+fn https_host(url: &str) -> Option<String> {
+    let url = reqwest::Url::parse(url.trim()).ok()?;
+    if url.scheme() != "https" {
+        return None;
+    }
+    let host = url.host_str()?;
+    Some(match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    })
+}
+
+/// Reads and parses the certificate presented by a host.
+async fn check_host(host: &str, sources: Vec<String>) -> CertificateInfo {
+    let mut info = CertificateInfo {
+        host: host.to_string(),
+        sources,
+        subject: None,
+        issuer: None,
+        expires_at: None,
+        days_remaining: None,
+        checked_at: Utc::now().to_rfc3339(),
+        error: None,
+    };
+
+    match fetch_certificate(host)
+        .await
+        .and_then(|der| parse_certificate(&der))
+    {
+        Ok(cert) => {
+            let days = cert.not_after.signed_duration_since(Utc::now()).num_days();
+            info.subject = cert.subject;
+            info.issuer = cert.issuer;
+            info.expires_at = Some(cert.not_after.to_rfc3339());
+            info.days_remaining = Some(i32::try_from(days).unwrap_or(i32::MAX));
+        }
+        Err(e) => {
+            log::warn!("Failed to read TLS certificate of {host}: {e}");
+            info.error = Some(e);
+        }
+    }
+    info
+}
+
+/// Connects to a host and returns its leaf certificate (DER).
+///
+/// Certificate validation is disabled so expired or self-signed certificates
+/// can still be reported; nothing but a `HEAD /` request is sent.
+async fn fetch_certificate(host: &str) -> Result<Vec<u8>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        .tls_info(true)
+        .danger_accept_invalid_certs(true)
+        .build()
+        .map_err(|e| format!("Failed to initialize HTTP client: {e}"))?;
+
+    let response = client
+        .head(format!("https://{host}/"))
+        .send()
+        .await
+        .map_err(|e| format!("Connection failed: {e}"))?;
+
+    response
+        .extensions()
+        .get::<reqwest::tls::TlsInfo>()
+        .and_then(|tls| tls.peer_certificate())
+        .map(<[u8]>::to_vec)
+        .ok_or_else(|| "Server did not present a certificate".to_string())
+}
+
+/// Fields read from an X.509 certificate.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ParsedCertificate {
+    pub subject: Option<String>,
+    pub issuer: Option<String>,
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+}
+
+/// Reads one DER element. Returns its tag, content and the remaining input.
+fn read_element(input: &[u8]) -> Result<(u8, &[u8], &[u8]), String> {
+    let invalid = || "Invalid certificate encoding".to_string();
+    let (&tag, rest) = input.split_first().ok_or_else(invalid)?;
+    let (&first, rest) = rest.split_first().ok_or_else(invalid)?;
+
+    let (len, rest) = if first < 0x80 {
+        (usize::from(first), rest)
+    } else {
+        let count = usize::from(first & 0x7f);
+        if count == 0 || count > 4 || rest.len() < count {
+            return Err(invalid());
+        }
+        let len = rest[..count]
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | usize::from(*b));
+        (len, &rest[count..])
+    };
+
+    if rest.len() < len {
+        return Err(invalid());
+    }
+    Ok((tag, &rest[..len], &rest[len..]))
+}
+
+/// Extracts the subject, issuer and validity of a DER-encoded certificate.
+pub(crate) fn parse_certificate(der: &[u8]) -> Result<ParsedCertificate, String> {
+    const SEQUENCE: u8 = 0x30;
+
+    let (_, certificate, _) = read_element(der)?;
+    let (tag, tbs, _) = read_element(certificate)?;
+    if tag != SEQUENCE {
+        return Err("Invalid certificate structure".to_string());
+    }
+
+    // TBSCertificate: [0] version (optional), serialNumber, signature,
+    // issuer, validity, subject, ...
+    let (tag, _, mut rest) = read_element(tbs)?;
+    if tag == 0xa0 {
+        (_, _, rest) = read_element(rest)?;
+    }
+    let (_, _, rest) = read_element(rest)?;
+    let (_, issuer, rest) = read_element(rest)?;
+    let (_, validity, rest) = read_element(rest)?;
+    let (_, subject, _) = read_element(rest)?;
+
+    let (before_tag, not_before, validity) = read_element(validity)?;
+    let (after_tag, not_after, _) = read_element(validity)?;
+
+    Ok(ParsedCertificate {
+        subject: common_name(subject),
+        issuer: common_name(issuer),
+        not_before: parse_time(before_tag, not_before)?,
+        not_after: parse_time(after_tag, not_after)?,
+    })
+}
+
+/// Finds the common name (OID 2.5.4.3) in a Name.
+fn common_name(mut name: &[u8]) -> Option<String> {
+    const COMMON_NAME_OID: &[u8] = &[0x55, 0x04, 0x03];
+
+    while !name.is_empty() {
+        let (_, set, rest) = read_element(name).ok()?;
+        name = rest;
+        let (_, attribute, _) = read_element(set).ok()?;
+        let (_, oid, value) = read_element(attribute).ok()?;
+        if oid == COMMON_NAME_OID {
+            let (_, value, _) = read_element(value).ok()?;
+            return Some(String::from_utf8_lossy(value).to_string());
+        }
+    }
+    None
+}
+
+/// Parses an ASN.1 UTCTime (tag 0x17) or GeneralizedTime (tag 0x18).
+fn parse_time(tag: u8, value: &[u8]) -> Result<DateTime<Utc>, String> {
+    let text = std::str::from_utf8(value).map_err(|_| "Invalid certificate time".to_string())?;
+    let full = match tag {
+        0x17 => {
+            let year: u32 = text
+                .get(..2)
+                .and_then(|y| y.parse().ok())
+                .ok_or_else(|| format!("Invalid certificate time: {text}"))?;
+            let century = if year >= 50 { "19" } else { "20" };
+            format!("{century}{text}")
+        }
+        0x18 => text.to_string(),
+        _ => return Err(format!("Unsupported certificate time type: {tag:#x}")),
+    };
+
+    NaiveDateTime::parse_from_str(&full, "%Y%m%d%H%M%SZ")
+        .map(|t| t.and_utc())
+        .map_err(|e| format!("Invalid certificate time '{text}': {e}"))
+}
+
+fn store_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    Ok(app_data_dir.join("certificates.json"))
+}
+
+fn load_store(app: &AppHandle) -> Result<CertificateStore, String> {
+    let path = store_path(app)?;
+    if !path.exists() {
+        return Ok(CertificateStore::default());
+    }
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read certificate results: {e}"))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse certificate results: {e}"))
+}
+
+fn save_store(app: &AppHandle, store: &CertificateStore) -> Result<(), String> {
+    let path = store_path(app)?;
+    let json = serde_json::to_string_pretty(store)
+        .map_err(|e| format!("Failed to serialize certificate results: {e}"))?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {e}"))?;
+    }
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json)
+        .map_err(|e| format!("Failed to write certificate results: {e}"))?;
+    if let Err(rename_err) = std::fs::rename(&temp_path, &path) {
+        if let Err(remove_err) = std::fs::remove_file(&temp_path) {
+            log::warn!("Failed to remove temp file after rename failure: {remove_err}");
+        }
+        return Err(format!(
+            "Failed to finalize certificate results: {rename_err}"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag];
+        if content.len() < 0x80 {
+            out.push(content.len() as u8);
+        } else {
+            out.extend([0x82, (content.len() >> 8) as u8, content.len() as u8]);
+        }
+        out.extend_from_slice(content);
+        out
+    }
+
+    fn name(cn: &str) -> Vec<u8> {
+        let attribute = [tlv(0x06, &[0x55, 0x04, 0x03]), tlv(0x0c, cn.as_bytes())].concat();
+        tlv(0x30, &tlv(0x31, &tlv(0x30, &attribute)))
+    }
+
+    fn certificate(not_after: Vec<u8>) -> Vec<u8> {
+        let tbs = [
+            tlv(0xa0, &tlv(0x02, &[2])),
+            tlv(0x02, &[0x01, 0x23]),
+            tlv(
+                0x30,
+                &tlv(0x06, &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02]),
+            ),
+            name("Example CA"),
+            tlv(0x30, &[tlv(0x17, b"260101000000Z"), not_after].concat()),
+            name("shop.example.com"),
+            // Padding standing in for the public key, long enough to need a
+            // multi-byte length
+            tlv(0x30, &[0u8; 200]),
+        ]
+        .concat();
+        tlv(
+            0x30,
+            &[tlv(0x30, &tbs), tlv(0x30, &[]), tlv(0x03, &[0])].concat(),
+        )
+    }
+
+    #[test]
+    fn test_parse_certificate() {
+        let parsed = parse_certificate(&certificate(tlv(0x17, b"261116023803Z"))).unwrap();
+        assert_eq!(parsed.subject.as_deref(), Some("shop.example.com"));
+        assert_eq!(parsed.issuer.as_deref(), Some("Example CA"));
+        assert_eq!(parsed.not_before.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+        assert_eq!(parsed.not_after.to_rfc3339(), "2026-11-16T02:38:03+00:00");
+
+        let generalized = parse_certificate(&certificate(tlv(0x18, b"20510101000000Z"))).unwrap();
+        assert_eq!(
+            generalized.not_after.to_rfc3339(),
+            "2051-01-01T00:00:00+00:00"
+        );
+
+        assert!(parse_certificate(&[0x30, 0x05, 0x01]).is_err());
+    }
+
+    #[test]
+    fn test_due_alert() {
+        assert_eq!(due_alert(45, &[]), None);
+        assert_eq!(due_alert(30, &[]), Some(30));
+        assert_eq!(due_alert(20, &[30]), None);
+        assert_eq!(due_alert(10, &[30]), Some(14));
+        // Only the most urgent threshold is reported when several are crossed at once
+        assert_eq!(due_alert(5, &[]), Some(7));
+        assert_eq!(due_alert(-3, &[30, 14, 7]), None);
+    }
+
+    #[test]
+    fn test_https_host() {
+        assert_eq!(
+            https_host("https://gitlab.example.com/api"),
+            Some("gitlab.example.com".to_string())
+        );
+        assert_eq!(
+            https_host("https://sonar.example.com:8443"),
+            Some("sonar.example.com:8443".to_string())
+        );
+        assert_eq!(https_host("http://jenkins.local:8080"), None);
+        assert_eq!(https_host(""), None);
+    }
+}
@@ -0,0 +1,185 @@
+//! Deploy freeze gating.
+//!
+//! Deployments to a namespace are held while a GitLab freeze period is in
+//! effect for a project mapped to it: a project of a mapping whose
+//! environment uses the namespace, or that names the namespace itself.
+//! Mappings don't record which GitLab instance hosts a project, so each
+//! GitLab integration is asked in turn. When GitLab can't be reached the
+//! deployment goes ahead with a warning.
+
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+use crate::commands::config::{load_environments, load_integrations, load_mappings};
+use crate::commands::gitlab::gitlab_adapter;
+use crate::integrations::gitlab::GitLabFreezePeriod;
+use crate::integrations::IntegrationError;
+use crate::types::{IntegrationType, Mapping};
+
+/// Freeze periods of each project, or why they couldn't be read. Projects
+/// no integration knows have no periods.
+pub type ProjectFreezes = BTreeMap<u32, Result<Vec<GitLabFreezePeriod>, String>>;
+
+/// GitLab project IDs of the mappings of `environment_ids` or `namespaces`.
+pub fn mapped_projects(
+    environment_ids: &[&str],
+    namespaces: &[&str],
+    mappings: &[Mapping],
+) -> Vec<u32> {
+    let mut projects: Vec<u32> = mappings
+        .iter()
+        .filter(|m| {
+            m.environment_id
+                .as_deref()
+                .is_some_and(|id| environment_ids.contains(&id))
+                || m.namespace
+                    .as_deref()
+                    .is_some_and(|ns| namespaces.contains(&ns))
+        })
+        .filter_map(|m| m.repo_id.as_deref()?.trim().parse().ok())
+        .collect();
+    projects.sort_unstable();
+    projects.dedup();
+    projects
+}
+
+/// Fetches the freeze periods of projects from `integration_id`, or from
+/// the first GitLab integration hosting each project when not given.
+pub async fn fetch_freezes(
+    app: &AppHandle,
+    project_ids: &[u32],
+    integration_id: Option<&str>,
+) -> Result<ProjectFreezes, String> {
+    let integration_ids: Vec<String> = match integration_id {
+        Some(id) => vec![id.to_string()],
+        None => load_integrations(app.clone())
+            .await?
+            .into_iter()
+            .filter(|i| i.integration_type == IntegrationType::GitLab)
+            .map(|i| i.id)
+            .collect(),
+    };
+
+    let mut adapters = Vec::new();
+    let mut adapter_error = None;
+    for id in &integration_ids {
+        match gitlab_adapter(app, id).await {
+            Ok(adapter) => adapters.push(adapter),
+            Err(e) => adapter_error = Some(e),
+        }
+    }
+
+    let mut freezes = ProjectFreezes::new();
+    for &project_id in project_ids {
+        let mut result = match &adapter_error {
+            Some(e) => Err(e.clone()),
+            None => Ok(Vec::new()),
+        };
+        for adapter in &adapters {
+            match adapter.fetch_freeze_periods(project_id).await {
+                Ok(periods) => {
+                    result = Ok(periods);
+                    break;
+                }
+                Err(IntegrationError::NotFound) => {}
+                Err(e) => result = Err(e.to_string()),
+            }
+        }
+        freezes.insert(project_id, result);
+    }
+    Ok(freezes)
+}
+
+/// Describes a freeze in effect, e.g. "project 42 is frozen until ...".
+pub fn describe_active(project_id: u32, period: &GitLabFreezePeriod) -> String {
+    match &period.ends_at {
+        Some(ends_at) => format!("project {project_id} is frozen until {ends_at}"),
+        None => format!("project {project_id} is frozen"),
+    }
+}
+
+/// Fails when a freeze is in effect for a project mapped to `namespaces`,
+/// unless `override_freeze` is set. Returns a note describing the freezes
+/// that were overridden.
+pub async fn ensure_not_frozen(
+    app: &AppHandle,
+    namespaces: &[&str],
+    override_freeze: bool,
+) -> Result<Option<String>, String> {
+    let environments = load_environments(app.clone()).await?;
+    let environment_ids: Vec<&str> = environments
+        .iter()
+        .filter(|e| {
+            e.namespace
+                .as_deref()
+                .is_some_and(|ns| namespaces.contains(&ns))
+        })
+        .map(|e| e.id.as_str())
+        .collect();
+    let mappings = load_mappings(app.clone()).await?;
+    let projects = mapped_projects(&environment_ids, namespaces, &mappings);
+    if projects.is_empty() {
+        return Ok(None);
+    }
+
+    let mut active = Vec::new();
+    for (project_id, periods) in fetch_freezes(app, &projects, None).await? {
+        match periods {
+            Ok(periods) => active.extend(
+                periods
+                    .iter()
+                    .filter(|p| p.active)
+                    .map(|p| describe_active(project_id, p)),
+            ),
+            Err(e) => log::warn!(
+                "Could not check freeze periods of project {project_id}, deploying anyway: {e}"
+            ),
+        }
+    }
+    if active.is_empty() {
+        return Ok(None);
+    }
+
+    let freezes = active.join("; ");
+    if override_freeze {
+        log::warn!("Deploying during a freeze period: {freezes}");
+        Ok(Some(format!("freeze overridden: {freezes}")))
+    } else {
+        Err(format!(
+            "Deploy blocked by a freeze period: {freezes} (set 'overrideFreeze' to deploy anyway)"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Ownership;
+
+    fn mapping(repo_id: &str, environment_id: Option<&str>, namespace: Option<&str>) -> Mapping {
+        Mapping {
+            id: repo_id.to_string(),
+            repo_id: Some(repo_id.to_string()),
+            job_id: None,
+            namespace: namespace.map(str::to_string),
+            service_name: None,
+            project_id: None,
+            environment_id: environment_id.map(str::to_string),
+            ownership: Ownership::default(),
+        }
+    }
+
+    #[test]
+    fn test_mapped_projects() {
+        let mappings = [
+            mapping("42", Some("prod"), None),
+            mapping("7", None, Some("shop")),
+            mapping("42", None, Some("shop")),
+            mapping("13", Some("staging"), None),
+            mapping("group/api", Some("prod"), None),
+        ];
+
+        assert_eq!(mapped_projects(&["prod"], &["shop"], &mappings), [7, 42]);
+        assert!(mapped_projects(&[], &["other"], &mappings).is_empty());
+    }
+}
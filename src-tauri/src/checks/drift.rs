@@ -0,0 +1,264 @@
+//! Desired-state drift.
+//!
+//! An environment's desired state (`desired_states.yaml`) lists the image,
+//! tag and Helm chart version each service should run. Drift checks compare
+//! it with the deployments in the environment's namespace and report every
+//! service that differs or is missing, so it can be redeployed.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+
+use crate::commands::config::{load_desired_states, load_environments};
+use crate::commands::kubernetes::kubernetes_adapter;
+use crate::integrations::kubernetes::K8sDeploymentState;
+use crate::types::{DesiredService, Timestamp};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftStatus {
+    InSync,
+    Drifted,
+    /// No deployment (or container) of that name
+    Missing,
+}
+
+/// A compared field whose live value differs.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct DriftDifference {
+    /// "image", "tag" or "chart_version"
+    pub field: String,
+    pub expected: String,
+    /// Live value; None when it is not set
+    pub actual: Option<String>,
+}
+
+/// Comparison of one desired service.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ServiceDrift {
+    pub service: String,
+    pub status: DriftStatus,
+    /// Live image of the compared container
+    pub live_image: Option<String>,
+    pub differences: Vec<DriftDifference>,
+}
+
+/// Drift of an environment from its desired state.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct EnvironmentDrift {
+    pub environment_id: String,
+    pub integration_id: String,
+    pub namespace: String,
+    pub checked_at: Timestamp,
+    /// Services drifted or missing
+    pub drifted: u32,
+    pub services: Vec<ServiceDrift>,
+}
+
+/// Compares an environment's desired state with its namespace.
+pub async fn check_environment(
+    app: &AppHandle,
+    environment_id: &str,
+) -> Result<EnvironmentDrift, String> {
+    let environment = load_environments(app.clone())
+        .await?
+        .into_iter()
+        .find(|e| e.id == environment_id)
+        .ok_or_else(|| format!("Environment not found: {environment_id}"))?;
+    let namespace = environment.namespace.ok_or_else(|| {
+        format!(
+            "Environment {} has no Kubernetes namespace",
+            environment.name
+        )
+    })?;
+    let desired = load_desired_states(app.clone())
+        .await?
+        .into_iter()
+        .find(|d| d.environment_id == environment_id)
+        .ok_or_else(|| format!("No desired state defined for {}", environment.name))?;
+
+    let deployments = kubernetes_adapter(app, &desired.integration_id)
+        .await?
+        .fetch_deployment_states(&namespace)
+        .await
+        .map_err(|e| format!("Failed to fetch deployments: {e}"))?;
+
+    let services: Vec<ServiceDrift> = desired
+        .services
+        .iter()
+        .map(|service| compare(service, &deployments))
+        .collect();
+    let drifted = services
+        .iter()
+        .filter(|s| s.status != DriftStatus::InSync)
+        .count() as u32;
+    if drifted > 0 {
+        log::info!("{drifted} service(s) of {} drifted", environment.name);
+    }
+
+    Ok(EnvironmentDrift {
+        environment_id: environment_id.to_string(),
+        integration_id: desired.integration_id,
+        namespace,
+        checked_at: Timestamp::from_datetime(Utc::now()),
+        drifted,
+        services,
+    })
+}
+
+/// Compares a desired service with the live deployments.
+fn compare(desired: &DesiredService, deployments: &[K8sDeploymentState]) -> ServiceDrift {
+    let missing = || ServiceDrift {
+        service: desired.name.clone(),
+        status: DriftStatus::Missing,
+        live_image: None,
+        differences: Vec::new(),
+    };
+    let Some(deployment) = deployments.iter().find(|d| d.name == desired.name) else {
+        return missing();
+    };
+    let container = match &desired.container {
+        Some(name) => deployment.containers.iter().find(|c| &c.name == name),
+        None => deployment.containers.first(),
+    };
+    let Some(container) = container else {
+        return missing();
+    };
+
+    let (image, tag) = split_image(&container.image);
+    let mut differences = Vec::new();
+    let mut expect = |field: &str, expected: &Option<String>, actual: Option<&str>| {
+        if let Some(expected) = expected {
+            if actual != Some(expected.as_str()) {
+                differences.push(DriftDifference {
+                    field: field.to_string(),
+                    expected: expected.clone(),
+                    actual: actual.map(str::to_string),
+                });
+            }
+        }
+    };
+    expect("image", &desired.image, Some(image));
+    expect("tag", &desired.tag, tag);
+    expect(
+        "chart_version",
+        &desired.chart_version,
+        deployment.chart_version.as_deref(),
+    );
+
+    ServiceDrift {
+        service: desired.name.clone(),
+        status: if differences.is_empty() {
+            DriftStatus::InSync
+        } else {
+            DriftStatus::Drifted
+        },
+        live_image: Some(container.image.clone()),
+        differences,
+    }
+}
+
+/// Splits an image reference into the image and its tag (or digest).
+fn split_image(image: &str) -> (&str, Option<&str>) {
+    if let Some((name, digest)) = image.split_once('@') {
+        return (name, Some(digest));
+    }
+    match image.rsplit_once(':') {
+        // A ':' before the last '/' belongs to the registry port
+        Some((name, tag)) if !tag.contains('/') => (name, Some(tag)),
+        _ => (image, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn deployment(name: &str, image: &str, chart_version: Option<&str>) -> K8sDeploymentState {
+        serde_json::from_value(json!({
+            "name": name,
+            "containers": [{ "name": name, "image": image }],
+            "chart": chart_version.map(|v| format!("{name}-{v}")),
+            "chart_version": chart_version,
+            "replicas": 1,
+            "ready_replicas": 1,
+        }))
+        .unwrap()
+    }
+
+    fn desired(name: &str, tag: &str, chart_version: Option<&str>) -> DesiredService {
+        DesiredService {
+            name: name.to_string(),
+            container: None,
+            image: Some("registry.example.com:5000/team/api".to_string()),
+            tag: Some(tag.to_string()),
+            chart_version: chart_version.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_compare_in_sync() {
+        let deployments = vec![deployment(
+            "api",
+            "registry.example.com:5000/team/api:1.4.2",
+            Some("1.4.2"),
+        )];
+        let drift = compare(&desired("api", "1.4.2", Some("1.4.2")), &deployments);
+        assert_eq!(drift.status, DriftStatus::InSync);
+        assert!(drift.differences.is_empty());
+    }
+
+    #[test]
+    fn test_compare_reports_differences() {
+        let deployments = vec![deployment(
+            "api",
+            "registry.example.com:5000/team/api:1.3.0",
+            None,
+        )];
+        let drift = compare(&desired("api", "1.4.2", Some("1.4.2")), &deployments);
+        assert_eq!(drift.status, DriftStatus::Drifted);
+        assert_eq!(
+            drift.differences,
+            vec![
+                DriftDifference {
+                    field: "tag".to_string(),
+                    expected: "1.4.2".to_string(),
+                    actual: Some("1.3.0".to_string()),
+                },
+                DriftDifference {
+                    field: "chart_version".to_string(),
+                    expected: "1.4.2".to_string(),
+                    actual: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_missing_deployment_or_container() {
+        let deployments = vec![deployment("api", "api:1.4.2", None)];
+        assert_eq!(
+            compare(&desired("worker", "1.4.2", None), &deployments).status,
+            DriftStatus::Missing
+        );
+
+        let mut sidecar = desired("api", "1.4.2", None);
+        sidecar.container = Some("proxy".to_string());
+        assert_eq!(compare(&sidecar, &deployments).status, DriftStatus::Missing);
+    }
+
+    #[test]
+    fn test_split_image() {
+        assert_eq!(split_image("api:1.4.2"), ("api", Some("1.4.2")));
+        assert_eq!(
+            split_image("registry:5000/team/api"),
+            ("registry:5000/team/api", None)
+        );
+        assert_eq!(
+            split_image("team/api@sha256:abc"),
+            ("team/api", Some("sha256:abc"))
+        );
+    }
+}
@@ -0,0 +1,391 @@
+//! Image vulnerability gating.
+//!
+//! Images pushed to a configured scanner (a Harbor instance, see
+//! `image_scanners.yaml`) are checked against its scan results before they
+//! are deployed. An image is blocked when it has more critical
+//! vulnerabilities than the scanner allows, or when it has no successful
+//! scan and the scanner blocks unscanned images. Images from registries
+//! without a scanner are not checked.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+
+use crate::commands::config::load_image_scanners;
+use crate::commands::credentials::get_integration_credentials;
+use crate::integrations::harbor::{HarborAdapter, HarborScan, ImageVulnerability};
+use crate::integrations::kubernetes::K8sImageUsage;
+use crate::types::ImageScanner;
+
+/// Severity counted against a scanner's `max_critical`.
+const CRITICAL: &str = "Critical";
+
+/// Outcome of an image security check.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageSecurityStatus {
+    /// Scanned and within the allowed number of critical vulnerabilities
+    Passed,
+    /// Deploys of this image are blocked
+    Blocked,
+    /// No successful scan yet (allowed by the scanner)
+    NotScanned,
+    /// No scanner is configured for the image's registry
+    Unavailable,
+}
+
+/// Security check result of an image.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ImageSecurityReport {
+    /// Image reference that was checked
+    pub image: String,
+    pub status: ImageSecurityStatus,
+    /// Name of the scanner consulted
+    pub scanner: Option<String>,
+    /// Artifact digest
+    pub digest: Option<String>,
+    /// Scan status reported by the scanner (e.g., "Success", "Running")
+    pub scan_status: Option<String>,
+    pub critical_count: u32,
+    pub high_count: u32,
+    /// Critical vulnerabilities allowed by the scanner
+    pub max_critical: u32,
+    /// Critical vulnerabilities found
+    pub offending: Vec<ImageVulnerability>,
+    pub message: String,
+}
+
+/// Parts of an image reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ImageRef {
+    /// Registry host (with port), if the reference names one
+    pub registry: Option<String>,
+    /// First path segment (the Harbor project)
+    pub project: String,
+    /// Remaining path segments
+    pub repository: String,
+    /// Tag or digest ("latest" when not given)
+    pub reference: String,
+}
+
+impl ImageRef {
+    /// Repository path within the registry (e.g., "team/api").
+    pub fn name(&self) -> String {
+        format!("{}/{}", self.project, self.repository)
+    }
+}
+
+/// Splits an image reference like `harbor.example.com/team/api:1.4.2`.
+pub(crate) fn parse_image_ref(image: &str) -> Result<ImageRef, String> {
+    let image = image.trim();
+    let invalid = || format!("Invalid image reference: {image}");
+
+    let (name, reference) = match image.split_once('@') {
+        Some((name, digest)) => (name, digest.to_string()),
+        None => match image.rsplit_once(':') {
+            // A ':' before the last '/' belongs to the registry port
+            Some((name, tag)) if !tag.contains('/') => (name, tag.to_string()),
+            _ => (image, "latest".to_string()),
+        },
+    };
+
+    let mut segments: Vec<&str> = name.split('/').collect();
+    let registry = match segments.first() {
+        Some(first)
+            if segments.len() > 1
+                && (first.contains('.') || first.contains(':') || *first == "localhost") =>
+        {
+            Some(segments.remove(0).to_lowercase())
+        }
+        _ => None,
+    };
+    if segments.len() < 2 || segments.iter().any(|s| s.is_empty()) || reference.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(ImageRef {
+        registry,
+        project: segments[0].to_string(),
+        repository: segments[1..].join("/"),
+        reference,
+    })
+}
+
+/// Finds the scanner covering a registry host.
+pub(crate) fn find_scanner<'a>(
+    scanners: &'a [ImageScanner],
+    registry: &str,
+) -> Option<&'a ImageScanner> {
+    scanners.iter().find(|scanner| {
+        if scanner.registry_hosts.is_empty() {
+            host_of(&scanner.base_url).is_some_and(|host| host.eq_ignore_ascii_case(registry))
+        } else {
+            scanner
+                .registry_hosts
+                .iter()
+                .any(|host| host.eq_ignore_ascii_case(registry))
+        }
+    })
+}
+
+/// Host (and port) of a URL.
+fn host_of(url: &str) -> Option<&str> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split('/').next().filter(|host| !host.is_empty())
+}
+
+/// Applies a scanner's policy to scan results.
+fn evaluate(image: &str, scanner: &ImageScanner, scan: HarborScan) -> ImageSecurityReport {
+    let count = |severity: &str| scan.severity_counts.get(severity).copied().unwrap_or(0);
+    let critical_count = count(CRITICAL);
+    let high_count = count("High");
+    let scanned = scan.scan_status.as_deref() == Some("Success");
+
+    let mut offending: Vec<ImageVulnerability> = scan
+        .vulnerabilities
+        .into_iter()
+        .filter(|v| v.severity.eq_ignore_ascii_case(CRITICAL))
+        .collect();
+    offending.sort_by(|a, b| a.id.cmp(&b.id).then_with(|| a.package.cmp(&b.package)));
+
+    let (status, message) = if !scanned {
+        let state = scan.scan_status.as_deref().unwrap_or("not scanned");
+        if scanner.block_unscanned {
+            (
+                ImageSecurityStatus::Blocked,
+                format!(
+                    "{image} has no successful scan ({state}) and unscanned images are blocked"
+                ),
+            )
+        } else {
+            (
+                ImageSecurityStatus::NotScanned,
+                format!("{image} has no successful scan ({state})"),
+            )
+        }
+    } else if critical_count > scanner.max_critical {
+        let ids: Vec<&str> = offending.iter().map(|v| v.id.as_str()).collect();
+        (
+            ImageSecurityStatus::Blocked,
+            format!(
+                "{image} has {critical_count} critical vulnerabilities (allowed: {}): {}",
+                scanner.max_critical,
+                ids.join(", ")
+            ),
+        )
+    } else {
+        (
+            ImageSecurityStatus::Passed,
+            format!("{image} has {critical_count} critical and {high_count} high vulnerabilities"),
+        )
+    };
+
+    ImageSecurityReport {
+        image: image.to_string(),
+        status,
+        scanner: Some(scanner.name.clone()),
+        digest: scan.digest,
+        scan_status: scan.scan_status,
+        critical_count,
+        high_count,
+        max_critical: scanner.max_critical,
+        offending,
+        message,
+    }
+}
+
+/// Username and password stored for a scanner, if any.
+pub(crate) async fn scanner_credentials(
+    app: &AppHandle,
+    scanner: &ImageScanner,
+) -> Result<Option<(String, String)>, String> {
+    let credentials_id = scanner.credentials_ref.as_ref().unwrap_or(&scanner.id);
+    Ok(
+        get_integration_credentials(app.clone(), credentials_id.clone())
+            .await?
+            .and_then(|c| Some((c.username?, c.password.or(c.token)?))),
+    )
+}
+
+/// Checks an image against the scan results of its registry's scanner.
+pub async fn check_image(app: &AppHandle, image: &str) -> Result<ImageSecurityReport, String> {
+    let image_ref = parse_image_ref(image)?;
+    let scanners = load_image_scanners(app.clone()).await?;
+    let scanner = image_ref
+        .registry
+        .as_deref()
+        .and_then(|registry| find_scanner(&scanners, registry));
+
+    let Some(scanner) = scanner else {
+        return Ok(ImageSecurityReport {
+            image: image.to_string(),
+            status: ImageSecurityStatus::Unavailable,
+            scanner: None,
+            digest: None,
+            scan_status: None,
+            critical_count: 0,
+            high_count: 0,
+            max_critical: 0,
+            offending: Vec::new(),
+            message: format!("No image scanner configured for {image}"),
+        });
+    };
+
+    let credentials = scanner_credentials(app, scanner).await?;
+    let adapter = HarborAdapter::new(scanner.base_url.clone(), credentials);
+    let scan = adapter
+        .fetch_scan(
+            &image_ref.project,
+            &image_ref.repository,
+            &image_ref.reference,
+        )
+        .await
+        .map_err(|e| format!("Failed to fetch scan results of {image}: {e}"))?;
+
+    Ok(evaluate(image, scanner, scan))
+}
+
+/// Checks every image running in a cluster (see `fetch_image_inventory`),
+/// so images that would no longer pass the gate can be found.
+///
+/// Images that can't be checked (e.g., unparsable references) are reported
+/// as unavailable rather than failing the whole check.
+pub async fn check_running_images(
+    app: &AppHandle,
+    images: &[K8sImageUsage],
+) -> Vec<ImageSecurityReport> {
+    let mut reports = Vec::with_capacity(images.len());
+    for usage in images {
+        let report = match check_image(app, &usage.image).await {
+            Ok(report) => report,
+            Err(e) => ImageSecurityReport {
+                image: usage.image.clone(),
+                status: ImageSecurityStatus::Unavailable,
+                scanner: None,
+                digest: usage.digests.first().cloned(),
+                scan_status: None,
+                critical_count: 0,
+                high_count: 0,
+                max_critical: 0,
+                offending: Vec::new(),
+                message: e,
+            },
+        };
+        reports.push(report);
+    }
+    reports
+}
+
+/// Fails when deploying the image is blocked by its scan results.
+pub async fn ensure_deployable(app: &AppHandle, image: &str) -> Result<(), String> {
+    let report = check_image(app, image).await?;
+    match report.status {
+        ImageSecurityStatus::Blocked => Err(format!("Deploy blocked: {}", report.message)),
+        ImageSecurityStatus::NotScanned => {
+            log::warn!("Deploying unscanned image: {}", report.message);
+            Ok(())
+        }
+        ImageSecurityStatus::Passed | ImageSecurityStatus::Unavailable => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn scanner(max_critical: u32, block_unscanned: bool) -> ImageScanner {
+        ImageScanner {
+            id: "harbor".to_string(),
+            name: "Harbor".to_string(),
+            base_url: "https://harbor.example.com".to_string(),
+            registry_hosts: Vec::new(),
+            credentials_ref: None,
+            max_critical,
+            block_unscanned,
+        }
+    }
+
+    fn vulnerability(id: &str, severity: &str) -> ImageVulnerability {
+        ImageVulnerability {
+            id: id.to_string(),
+            package: "openssl".to_string(),
+            version: "3.0.1".to_string(),
+            fix_version: None,
+            severity: severity.to_string(),
+            link: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_image_ref() {
+        let image = parse_image_ref("harbor.example.com:8443/team/api/worker:1.4.2").unwrap();
+        assert_eq!(image.registry.as_deref(), Some("harbor.example.com:8443"));
+        assert_eq!(
+            (image.project.as_str(), image.repository.as_str()),
+            ("team", "api/worker")
+        );
+        assert_eq!(image.reference, "1.4.2");
+
+        let digest = parse_image_ref("harbor.example.com/team/api@sha256:abc").unwrap();
+        assert_eq!(digest.reference, "sha256:abc");
+        assert_eq!(
+            parse_image_ref("localhost:5000/team/api")
+                .unwrap()
+                .reference,
+            "latest"
+        );
+        assert_eq!(parse_image_ref("library/nginx").unwrap().registry, None);
+        assert!(parse_image_ref("nginx").is_err());
+    }
+
+    #[test]
+    fn test_find_scanner() {
+        let scanners = vec![scanner(0, false)];
+        assert!(find_scanner(&scanners, "harbor.example.com").is_some());
+        assert!(find_scanner(&scanners, "ghcr.io").is_none());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let scan = HarborScan {
+            digest: Some("sha256:abc".to_string()),
+            scan_status: Some("Success".to_string()),
+            severity_counts: BTreeMap::from([("Critical".to_string(), 2), ("High".to_string(), 1)]),
+            vulnerabilities: vec![
+                vulnerability("CVE-2024-2", "Critical"),
+                vulnerability("CVE-2024-3", "High"),
+                vulnerability("CVE-2024-1", "Critical"),
+            ],
+        };
+
+        let blocked = evaluate(
+            "harbor.example.com/team/api:1",
+            &scanner(0, false),
+            scan.clone(),
+        );
+        assert_eq!(blocked.status, ImageSecurityStatus::Blocked);
+        let ids: Vec<&str> = blocked.offending.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, ["CVE-2024-1", "CVE-2024-2"]);
+
+        let passed = evaluate(
+            "harbor.example.com/team/api:1",
+            &scanner(2, false),
+            scan.clone(),
+        );
+        assert_eq!(passed.status, ImageSecurityStatus::Passed);
+
+        let unscanned = HarborScan {
+            scan_status: None,
+            ..scan
+        };
+        assert_eq!(
+            evaluate("img", &scanner(0, false), unscanned.clone()).status,
+            ImageSecurityStatus::NotScanned
+        );
+        assert_eq!(
+            evaluate("img", &scanner(0, true), unscanned).status,
+            ImageSecurityStatus::Blocked
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! Endpoint checks.
+//!
+//! HTTP and TLS checks run against environment endpoints: smoke tests that
+//! verify a deployment, scheduled uptime probes, certificate expiry and
+//! stage-by-stage connectivity probes. Image checks gate deployments on
+//! registry vulnerability scans and compare image SBOMs; freeze checks hold
+//! them during GitLab deploy freeze periods. Drift checks compare what runs
+//! in an environment with its desired state.
+
+pub mod certificates;
+pub mod deploy_freeze;
+pub mod drift;
+pub mod image_security;
+pub mod probe;
+pub mod sbom;
+pub mod smoke;
+pub mod uptime;
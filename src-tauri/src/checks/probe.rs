@@ -0,0 +1,447 @@
+//! Connectivity probe.
+//!
+//! Probes a URL stage by stage — DNS resolution, TCP connect, TLS handshake
+//! and an HTTP `HEAD` request — and times each stage, so a failing endpoint
+//! can be narrowed down to the VPN/DNS, the network path or the server.
+//! The probe stops at the first failed stage; later stages are skipped.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{self, ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+
+/// Timeout of the DNS and TCP stages.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Timeout of the TLS and HTTP stages.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest response head read while looking for the status line.
+const MAX_HEAD_BYTES: usize = 8192;
+
+/// Probe stages, in order.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProbeStageKind {
+    Dns,
+    Tcp,
+    Tls,
+    Http,
+}
+
+/// Outcome of a probe stage.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProbeStageStatus {
+    Ok,
+    Failed,
+    /// Not run (plain HTTP has no TLS stage; stages after a failure are skipped)
+    Skipped,
+}
+
+/// Timing and outcome of a probe stage.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ProbeStage {
+    pub stage: ProbeStageKind,
+    pub status: ProbeStageStatus,
+    /// Duration in milliseconds (unset for skipped stages)
+    pub duration_ms: Option<u32>,
+    /// Result details (e.g., resolved addresses) or the error
+    pub detail: Option<String>,
+}
+
+/// Result of probing an endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct EndpointProbe {
+    pub url: String,
+    pub host: String,
+    pub port: u16,
+    /// Addresses the host resolved to
+    pub resolved_addresses: Vec<String>,
+    /// Address the TCP connection was made to
+    pub connected_address: Option<String>,
+    /// HTTP status of the `HEAD` request
+    pub http_status: Option<u16>,
+    pub stages: Vec<ProbeStage>,
+    /// Whether every stage that ran succeeded
+    pub success: bool,
+    /// Total duration in milliseconds
+    pub total_ms: u32,
+    /// Human-readable verdict naming the failing stage
+    pub diagnosis: String,
+}
+
+/// Parsed probe target.
+#[derive(Debug, PartialEq, Eq)]
+struct Target {
+    host: String,
+    port: u16,
+    tls: bool,
+    /// Path and query sent in the `HEAD` request
+    path: String,
+}
+
+/// Probes an endpoint. Invalid URLs are an error; stage failures are reported
+/// in the result.
+pub async fn probe(url: &str) -> Result<EndpointProbe, String> {
+    let target = parse_target(url)?;
+    let started = Instant::now();
+
+    let mut result = EndpointProbe {
+        url: url.trim().to_string(),
+        host: target.host.clone(),
+        port: target.port,
+        resolved_addresses: Vec::new(),
+        connected_address: None,
+        http_status: None,
+        stages: Vec::new(),
+        success: false,
+        total_ms: 0,
+        diagnosis: String::new(),
+    };
+
+    run_stages(&target, &mut result).await;
+
+    // Stages after a failure never ran
+    for kind in [
+        ProbeStageKind::Dns,
+        ProbeStageKind::Tcp,
+        ProbeStageKind::Tls,
+        ProbeStageKind::Http,
+    ] {
+        if !result.stages.iter().any(|s| s.stage == kind) {
+            result.stages.push(skipped(kind));
+        }
+    }
+
+    result.success = result
+        .stages
+        .iter()
+        .all(|s| s.status != ProbeStageStatus::Failed);
+    result.total_ms = elapsed_ms(started);
+    result.diagnosis = diagnose(&result.stages, result.http_status);
+    log::info!("Probed {}: {}", result.url, result.diagnosis);
+    Ok(result)
+}
+
+/// Runs the stages in order, recording each one, until a stage fails.
+async fn run_stages(target: &Target, result: &mut EndpointProbe) {
+    let Some(addresses) = stage(result, ProbeStageKind::Dns, CONNECT_TIMEOUT, async {
+        let addresses: Vec<SocketAddr> =
+            tokio::net::lookup_host((target.host.as_str(), target.port))
+                .await
+                .map_err(|e| format!("Failed to resolve {}: {e}", target.host))?
+                .collect();
+        if addresses.is_empty() {
+            return Err(format!("{} did not resolve to any address", target.host));
+        }
+        let list: Vec<String> = addresses.iter().map(|a| a.ip().to_string()).collect();
+        Ok((addresses, list.join(", ")))
+    })
+    .await
+    else {
+        return;
+    };
+    result.resolved_addresses = addresses.iter().map(|a| a.ip().to_string()).collect();
+
+    let Some(stream) = stage(result, ProbeStageKind::Tcp, CONNECT_TIMEOUT, async {
+        let mut last_error = String::new();
+        for address in &addresses {
+            match TcpStream::connect(address).await {
+                Ok(stream) => return Ok((stream, format!("Connected to {address}"))),
+                Err(e) => last_error = format!("Failed to connect to {address}: {e}"),
+            }
+        }
+        Err(last_error)
+    })
+    .await
+    else {
+        return;
+    };
+    result.connected_address = stream.peer_addr().ok().map(|a| a.to_string());
+
+    let status = if target.tls {
+        let Some(stream) = stage(result, ProbeStageKind::Tls, RESPONSE_TIMEOUT, async {
+            let stream = tls_connect(&target.host, stream).await?;
+            let (_, connection) = stream.get_ref();
+            let version = connection
+                .protocol_version()
+                .and_then(|v| v.as_str())
+                .unwrap_or("TLS");
+            Ok((stream, format!("Negotiated {version}, certificate valid")))
+        })
+        .await
+        else {
+            return;
+        };
+        stage(
+            result,
+            ProbeStageKind::Http,
+            RESPONSE_TIMEOUT,
+            head(target, stream),
+        )
+        .await
+    } else {
+        result.stages.push(skipped(ProbeStageKind::Tls));
+        stage(
+            result,
+            ProbeStageKind::Http,
+            RESPONSE_TIMEOUT,
+            head(target, stream),
+        )
+        .await
+    };
+    result.http_status = status;
+}
+
+/// Runs one stage with a timeout and records its outcome.
+async fn stage<T>(
+    result: &mut EndpointProbe,
+    kind: ProbeStageKind,
+    timeout: Duration,
+    future: impl Future<Output = Result<(T, String), String>>,
+) -> Option<T> {
+    let started = Instant::now();
+    let outcome = match tokio::time::timeout(timeout, future).await {
+        Ok(outcome) => outcome,
+        Err(_) => Err(format!("Timed out after {}s", timeout.as_secs())),
+    };
+
+    let (value, status, detail) = match outcome {
+        Ok((value, detail)) => (Some(value), ProbeStageStatus::Ok, detail),
+        Err(e) => (None, ProbeStageStatus::Failed, e),
+    };
+    result.stages.push(ProbeStage {
+        stage: kind,
+        status,
+        duration_ms: Some(elapsed_ms(started)),
+        detail: Some(detail),
+    });
+    value
+}
+
+fn skipped(kind: ProbeStageKind) -> ProbeStage {
+    ProbeStage {
+        stage: kind,
+        status: ProbeStageStatus::Skipped,
+        duration_ms: None,
+        detail: None,
+    }
+}
+
+/// Performs the TLS handshake, verifying the certificate against the
+/// bundled web PKI roots.
+async fn tls_connect(
+    host: &str,
+    stream: TcpStream,
+) -> Result<tokio_rustls::client::TlsStream<TcpStream>, String> {
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config =
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| format!("Failed to initialize TLS: {e}"))?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+    let server_name = ServerName::try_from(host.to_string())
+        .map_err(|e| format!("Invalid TLS server name {host}: {e}"))?;
+    TlsConnector::from(Arc::new(config))
+        .connect(server_name, stream)
+        .await
+        .map_err(|e| format!("TLS handshake failed: {e}"))
+}
+
+/// Sends a `HEAD` request and reads the response status.
+async fn head<S>(target: &Target, mut stream: S) -> Result<(u16, String), String>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let request = format!(
+        "HEAD {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: ops-flow\r\nAccept: */*\r\nConnection: close\r\n\r\n",
+        target.path,
+        host_header(target)
+    );
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to send request: {e}"))?;
+
+    let mut head = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !head.contains(&b'\n') && head.len() < MAX_HEAD_BYTES {
+        let read = stream
+            .read(&mut buffer)
+            .await
+            .map_err(|e| format!("Failed to read response: {e}"))?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+
+    let response = String::from_utf8_lossy(&head);
+    let status_line = response.lines().next().unwrap_or_default();
+    let status = parse_status_line(status_line)
+        .ok_or_else(|| format!("Invalid HTTP response: '{}'", status_line.trim()))?;
+    Ok((status, status_line.trim().to_string()))
+}
+
+/// Parses an `http://` or `https://` URL into a probe target.
+fn parse_target(url: &str) -> Result<Target, String> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|e| format!("Invalid URL: {e}"))?;
+    let tls = match parsed.scheme() {
+        "https" => true,
+        "http" => false,
+        scheme => return Err(format!("Unsupported URL scheme '{scheme}'")),
+    };
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| "URL has no host".to_string())?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let port = parsed
+        .port_or_known_default()
+        .ok_or_else(|| "URL has no port".to_string())?;
+    let path = match parsed.query() {
+        Some(query) => format!("{}?{query}", parsed.path()),
+        None => parsed.path().to_string(),
+    };
+    Ok(Target {
+        host,
+        port,
+        tls,
+        path,
+    })
+}
+
+/// Host header value; the port is included when it isn't the scheme default.
+fn host_header(target: &Target) -> String {
+    let host = if target.host.contains(':') {
+        format!("[{}]", target.host)
+    } else {
+        target.host.clone()
+    };
+    let default_port = if target.tls { 443 } else { 80 };
+    if target.port == default_port {
+        host
+    } else {
+        format!("{host}:{}", target.port)
+    }
+}
+
+/// Extracts the status code from an HTTP status line (e.g., "HTTP/1.1 200 OK").
+fn parse_status_line(line: &str) -> Option<u16> {
+    let mut parts = line.split_whitespace();
+    if !parts.next()?.starts_with("HTTP/") {
+        return None;
+    }
+    parts
+        .next()?
+        .parse()
+        .ok()
+        .filter(|s| (100..600).contains(s))
+}
+
+/// Names the failing stage and what it usually means.
+fn diagnose(stages: &[ProbeStage], http_status: Option<u16>) -> String {
+    let Some(failed) = stages.iter().find(|s| s.status == ProbeStageStatus::Failed) else {
+        return match http_status {
+            Some(status) if status >= 500 => {
+                format!("Server reachable but responded with {status}")
+            }
+            Some(status) => format!("Endpoint reachable (HTTP {status})"),
+            None => "Endpoint reachable".to_string(),
+        };
+    };
+
+    let hint = match failed.stage {
+        ProbeStageKind::Dns => "DNS resolution failed; check the VPN connection and DNS settings",
+        ProbeStageKind::Tcp => {
+            "Host resolved but is unreachable; check the VPN, firewall or that the service is listening"
+        }
+        ProbeStageKind::Tls => {
+            "Connected but the TLS handshake failed; check the certificate and TLS configuration"
+        }
+        ProbeStageKind::Http => "TLS/TCP succeeded but the server did not answer the HTTP request",
+    };
+    match failed.detail.as_deref() {
+        Some(detail) => format!("{hint} ({detail})"),
+        None => hint.to_string(),
+    }
+}
+
+fn elapsed_ms(started: Instant) -> u32 {
+    u32::try_from(started.elapsed().as_millis()).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(
+            parse_target("https://api.example.com/health?full=1").unwrap(),
+            Target {
+                host: "api.example.com".to_string(),
+                port: 443,
+                tls: true,
+                path: "/health?full=1".to_string(),
+            }
+        );
+        let target = parse_target("http://[::1]:8080").unwrap();
+        assert_eq!((target.host.as_str(), target.port), ("::1", 8080));
+        assert_eq!(host_header(&target), "[::1]:8080");
+        assert!(parse_target("ftp://example.com").is_err());
+        assert!(parse_target("not a url").is_err());
+    }
+
+    #[test]
+    fn test_parse_status_line() {
+        assert_eq!(parse_status_line("HTTP/1.1 200 OK"), Some(200));
+        assert_eq!(parse_status_line("HTTP/2 404"), Some(404));
+        assert_eq!(parse_status_line("SSH-2.0-OpenSSH_9.6"), None);
+        assert_eq!(parse_status_line("HTTP/1.1 999 Odd"), None);
+    }
+
+    #[tokio::test]
+    async fn test_probe_plain_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = [0u8; 1024];
+            let _ = socket.read(&mut buffer).await;
+            let _ = socket
+                .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+                .await;
+        });
+
+        let probe = probe(&format!("http://127.0.0.1:{port}/health"))
+            .await
+            .unwrap();
+        assert!(probe.success);
+        assert_eq!(probe.http_status, Some(204));
+        let statuses: Vec<ProbeStageStatus> = probe.stages.iter().map(|s| s.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                ProbeStageStatus::Ok,
+                ProbeStageStatus::Ok,
+                ProbeStageStatus::Skipped,
+                ProbeStageStatus::Ok
+            ]
+        );
+    }
+}
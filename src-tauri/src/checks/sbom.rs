@@ -0,0 +1,405 @@
+//! SBOM retrieval and diff.
+//!
+//! SBOMs are read from Harbor when a scanner covers the image's registry and
+//! has generated one, and otherwise from SBOM artifacts attached to the image
+//! through the OCI referrers API. SPDX and CycloneDX JSON documents are
+//! supported, bare or wrapped in an in-toto attestation.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+use std::collections::{BTreeMap, BTreeSet};
+use tauri::AppHandle;
+
+use super::image_security::{find_scanner, parse_image_ref, scanner_credentials, ImageRef};
+use crate::commands::config::load_image_scanners;
+use crate::integrations::harbor::HarborAdapter;
+use crate::integrations::oci::{OciRegistry, DEFAULT_REGISTRY};
+
+/// Artifact types of SBOMs attached through the referrers API.
+const SBOM_ARTIFACT_TYPES: &[&str] = &["application/spdx+json", "application/vnd.cyclonedx+json"];
+
+/// Largest SBOM document read (20MB).
+const MAX_SBOM_BYTES: usize = 20_971_520;
+
+/// Where an SBOM was read from.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SbomSource {
+    Harbor,
+    OciReferrers,
+}
+
+/// SBOM document format.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SbomFormat {
+    Spdx,
+    CycloneDx,
+}
+
+/// A package listed in an SBOM.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SbomPackage {
+    pub name: String,
+    pub version: Option<String>,
+    /// Package URL (e.g., "pkg:npm/lodash@4.17.21")
+    pub purl: Option<String>,
+    /// Ecosystem from the package URL (e.g., "npm", "deb", "maven")
+    pub package_type: Option<String>,
+}
+
+/// SBOM of an image.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ImageSbom {
+    pub image: String,
+    pub source: SbomSource,
+    pub format: SbomFormat,
+    /// Packages sorted by type and name
+    pub packages: Vec<SbomPackage>,
+}
+
+/// A package whose version differs between two images.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SbomPackageChange {
+    pub name: String,
+    pub package_type: Option<String>,
+    /// Versions in the first image
+    pub from_versions: Vec<String>,
+    /// Versions in the second image
+    pub to_versions: Vec<String>,
+}
+
+/// Dependency changes between two images.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SbomDiff {
+    pub image_a: String,
+    pub image_b: String,
+    /// Packages only in the second image
+    pub added: Vec<SbomPackage>,
+    /// Packages only in the first image
+    pub removed: Vec<SbomPackage>,
+    pub changed: Vec<SbomPackageChange>,
+    /// Number of packages with the same versions in both images
+    pub unchanged: u32,
+}
+
+/// Fetches and parses the SBOM of an image.
+pub async fn fetch_sbom(app: &AppHandle, image: &str) -> Result<ImageSbom, String> {
+    let image_ref = parse_image_ref(image)?;
+    let scanners = load_image_scanners(app.clone()).await?;
+    let scanner = image_ref
+        .registry
+        .as_deref()
+        .and_then(|registry| find_scanner(&scanners, registry));
+    let credentials = match scanner {
+        Some(scanner) => scanner_credentials(app, scanner).await?,
+        None => None,
+    };
+
+    let mut source = SbomSource::OciReferrers;
+    let mut document = None;
+    if let Some(scanner) = scanner {
+        let adapter = HarborAdapter::new(scanner.base_url.clone(), credentials.clone());
+        match adapter
+            .fetch_sbom(
+                &image_ref.project,
+                &image_ref.repository,
+                &image_ref.reference,
+            )
+            .await
+        {
+            Ok(Some(sbom)) => {
+                source = SbomSource::Harbor;
+                document = Some(sbom);
+            }
+            Ok(None) => log::debug!("Harbor has no SBOM for {image}; trying referrers"),
+            Err(e) => log::warn!("Failed to fetch SBOM of {image} from Harbor: {e}"),
+        }
+    }
+    let document = match document {
+        Some(document) => document,
+        None => fetch_referrer_sbom(&image_ref, credentials)
+            .await?
+            .ok_or_else(|| format!("No SBOM found for {image}"))?,
+    };
+
+    let (format, packages) = parse_sbom(&document)?;
+    Ok(ImageSbom {
+        image: image.to_string(),
+        source,
+        format,
+        packages,
+    })
+}
+
+/// Reads the first SBOM artifact attached to an image.
+async fn fetch_referrer_sbom(
+    image_ref: &ImageRef,
+    credentials: Option<(String, String)>,
+) -> Result<Option<Value>, String> {
+    let registry = OciRegistry::new(
+        image_ref.registry.as_deref().unwrap_or(DEFAULT_REGISTRY),
+        credentials,
+    );
+    let name = image_ref.name();
+    let digest = registry
+        .resolve_digest(&name, &image_ref.reference)
+        .await
+        .map_err(|e| format!("Failed to resolve {name}:{}: {e}", image_ref.reference))?;
+
+    let referrers = registry
+        .fetch_referrers(&name, &digest)
+        .await
+        .map_err(|e| format!("Failed to list referrers of {name}@{digest}: {e}"))?;
+    let Some(referrer) = referrers.iter().find(|r| {
+        r.artifact_type
+            .as_deref()
+            .is_some_and(|t| SBOM_ARTIFACT_TYPES.contains(&t))
+    }) else {
+        return Ok(None);
+    };
+
+    let manifest = registry
+        .fetch_manifest(&name, &referrer.digest)
+        .await
+        .map_err(|e| format!("Failed to fetch SBOM manifest: {e}"))?;
+    let layer = manifest
+        .pointer("/layers/0/digest")
+        .and_then(|d| d.as_str())
+        .ok_or_else(|| "SBOM manifest has no layers".to_string())?;
+    let blob = registry
+        .fetch_blob(&name, layer, MAX_SBOM_BYTES)
+        .await
+        .map_err(|e| format!("Failed to fetch SBOM: {e}"))?;
+
+    serde_json::from_slice(&blob)
+        .map(Some)
+        .map_err(|e| format!("Invalid SBOM document: {e}"))
+}
+
+/// Compares the SBOMs of two images.
+pub async fn diff(app: &AppHandle, image_a: &str, image_b: &str) -> Result<SbomDiff, String> {
+    let (a, b) = tokio::try_join!(fetch_sbom(app, image_a), fetch_sbom(app, image_b))?;
+    Ok(diff_packages(image_a, image_b, &a.packages, &b.packages))
+}
+
+/// Reads the packages of an SPDX or CycloneDX JSON document.
+fn parse_sbom(document: &Value) -> Result<(SbomFormat, Vec<SbomPackage>), String> {
+    // in-toto attestations carry the SBOM as their predicate
+    let document = document.get("predicate").unwrap_or(document);
+
+    let (format, mut packages) = if document.get("spdxVersion").is_some() {
+        let packages = document
+            .get("packages")
+            .and_then(|p| p.as_array())
+            .map(|items| items.iter().filter_map(spdx_package).collect())
+            .unwrap_or_default();
+        (SbomFormat::Spdx, packages)
+    } else if document.get("bomFormat").and_then(|f| f.as_str()) == Some("CycloneDX") {
+        let mut packages = Vec::new();
+        collect_cyclonedx(document, &mut packages);
+        (SbomFormat::CycloneDx, packages)
+    } else {
+        return Err("Unsupported SBOM format (expected SPDX or CycloneDX JSON)".to_string());
+    };
+
+    packages.sort_by(|a, b| {
+        (&a.package_type, &a.name, &a.version).cmp(&(&b.package_type, &b.name, &b.version))
+    });
+    packages.dedup();
+    Ok((format, packages))
+}
+
+fn spdx_package(item: &Value) -> Option<SbomPackage> {
+    let purl = item
+        .get("externalRefs")
+        .and_then(|r| r.as_array())
+        .and_then(|refs| {
+            refs.iter()
+                .find(|r| r.get("referenceType").and_then(|t| t.as_str()) == Some("purl"))
+        })
+        .and_then(|r| r.get("referenceLocator"))
+        .and_then(|l| l.as_str());
+    package(
+        item.get("name")?.as_str()?,
+        item.get("versionInfo").and_then(|v| v.as_str()),
+        purl,
+    )
+}
+
+/// Collects components, including nested ones.
+fn collect_cyclonedx(parent: &Value, packages: &mut Vec<SbomPackage>) {
+    let Some(components) = parent.get("components").and_then(|c| c.as_array()) else {
+        return;
+    };
+    for component in components {
+        let name = component.get("name").and_then(|n| n.as_str());
+        let group = component.get("group").and_then(|g| g.as_str());
+        let name = match (group, name) {
+            (Some(group), Some(name)) if !group.is_empty() => Some(format!("{group}:{name}")),
+            (_, name) => name.map(str::to_string),
+        };
+        if let Some(package) = name.and_then(|name| {
+            package(
+                &name,
+                component.get("version").and_then(|v| v.as_str()),
+                component.get("purl").and_then(|p| p.as_str()),
+            )
+        }) {
+            packages.push(package);
+        }
+        collect_cyclonedx(component, packages);
+    }
+}
+
+/// Builds a package, leaving out the image itself.
+fn package(name: &str, version: Option<&str>, purl: Option<&str>) -> Option<SbomPackage> {
+    if purl.is_some_and(|p| p.starts_with("pkg:oci/") || p.starts_with("pkg:docker/")) {
+        return None;
+    }
+    let package_type = purl
+        .and_then(|p| p.strip_prefix("pkg:"))
+        .and_then(|p| p.split('/').next())
+        .map(str::to_string);
+    Some(SbomPackage {
+        name: name.to_string(),
+        version: version.filter(|v| !v.is_empty()).map(str::to_string),
+        purl: purl.map(str::to_string),
+        package_type,
+    })
+}
+
+/// Compares packages by type and name.
+fn diff_packages(image_a: &str, image_b: &str, a: &[SbomPackage], b: &[SbomPackage]) -> SbomDiff {
+    type Key = (Option<String>, String);
+    let index = |packages: &[SbomPackage]| {
+        let mut map: BTreeMap<Key, (BTreeSet<String>, SbomPackage)> = BTreeMap::new();
+        for package in packages {
+            let entry = map
+                .entry((package.package_type.clone(), package.name.clone()))
+                .or_insert_with(|| (BTreeSet::new(), package.clone()));
+            entry.0.extend(package.version.clone());
+        }
+        map
+    };
+    let (a, mut b) = (index(a), index(b));
+
+    let mut diff = SbomDiff {
+        image_a: image_a.to_string(),
+        image_b: image_b.to_string(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+        unchanged: 0,
+    };
+    for (key, (from_versions, package)) in a {
+        match b.remove(&key) {
+            None => diff.removed.push(package),
+            Some((to_versions, _)) if to_versions == from_versions => diff.unchanged += 1,
+            Some((to_versions, _)) => diff.changed.push(SbomPackageChange {
+                name: key.1,
+                package_type: key.0,
+                from_versions: from_versions.into_iter().collect(),
+                to_versions: to_versions.into_iter().collect(),
+            }),
+        }
+    }
+    diff.added = b.into_values().map(|(_, package)| package).collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn packages(document: Value) -> Vec<(Option<String>, String, Option<String>)> {
+        parse_sbom(&document)
+            .unwrap()
+            .1
+            .into_iter()
+            .map(|p| (p.package_type, p.name, p.version))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_spdx() {
+        let document = json!({
+            "spdxVersion": "SPDX-2.3",
+            "packages": [
+                {
+                    "name": "team/api",
+                    "externalRefs": [{ "referenceType": "purl", "referenceLocator": "pkg:oci/api@sha256:abc" }]
+                },
+                {
+                    "name": "openssl",
+                    "versionInfo": "3.0.11",
+                    "externalRefs": [{ "referenceType": "purl", "referenceLocator": "pkg:deb/debian/openssl@3.0.11" }]
+                },
+                { "name": "debian", "versionInfo": "12.4" }
+            ]
+        });
+        assert_eq!(
+            packages(document),
+            [
+                (None, "debian".to_string(), Some("12.4".to_string())),
+                (
+                    Some("deb".to_string()),
+                    "openssl".to_string(),
+                    Some("3.0.11".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_cyclonedx_attestation() {
+        let document = json!({
+            "_type": "https://in-toto.io/Statement/v0.1",
+            "predicate": {
+                "bomFormat": "CycloneDX",
+                "components": [{
+                    "name": "spring-core",
+                    "group": "org.springframework",
+                    "version": "6.1.2",
+                    "purl": "pkg:maven/org.springframework/spring-core@6.1.2",
+                    "components": [{ "name": "lodash", "version": "4.17.21", "purl": "pkg:npm/lodash@4.17.21" }]
+                }]
+            }
+        });
+        let (format, parsed) = parse_sbom(&document).unwrap();
+        assert_eq!(format, SbomFormat::CycloneDx);
+        let names: Vec<&str> = parsed.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["org.springframework:spring-core", "lodash"]);
+        assert!(parse_sbom(&json!({ "artifacts": [] })).is_err());
+    }
+
+    #[test]
+    fn test_diff_packages() {
+        let pkg = |name: &str, version: &str| SbomPackage {
+            name: name.to_string(),
+            version: Some(version.to_string()),
+            purl: None,
+            package_type: Some("npm".to_string()),
+        };
+        let a = vec![
+            pkg("express", "4.18.2"),
+            pkg("lodash", "4.17.20"),
+            pkg("qs", "6.11.0"),
+        ];
+        let b = vec![
+            pkg("express", "4.18.2"),
+            pkg("lodash", "4.17.21"),
+            pkg("zod", "3.22.4"),
+        ];
+
+        let diff = diff_packages("api:1", "api:2", &a, &b);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.removed[0].name, "qs");
+        assert_eq!(diff.added[0].name, "zod");
+        assert_eq!(diff.changed[0].name, "lodash");
+        assert_eq!(diff.changed[0].from_versions, ["4.17.20"]);
+        assert_eq!(diff.changed[0].to_versions, ["4.17.21"]);
+    }
+}
@@ -0,0 +1,291 @@
+//! Post-deployment smoke tests.
+//!
+//! Smoke tests are HTTP checks defined per environment (`smoke_tests.yaml`).
+//! Running them for an environment requests every URL, retrying failed
+//! attempts, and reports whether all checks passed. Header values may be
+//! secret references, resolved before the first attempt.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::commands::config::load_smoke_tests;
+use crate::types::SmokeTest;
+
+/// Delay between attempts of a failing check.
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Largest response body read when matching `expected_body` (1MB).
+const MAX_BODY_BYTES: usize = 1_048_576;
+
+/// Outcome of a single smoke test.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SmokeTestResult {
+    pub test_id: String,
+    pub name: String,
+    pub url: String,
+    pub passed: bool,
+    /// HTTP status of the last attempt (if a response was received)
+    pub status: Option<u16>,
+    /// Number of attempts made
+    pub attempts: u32,
+    /// Duration of the last attempt in milliseconds
+    pub duration_ms: u32,
+    /// Why the last attempt failed
+    pub error: Option<String>,
+}
+
+/// Outcome of all smoke tests of an environment.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SmokeTestReport {
+    pub environment_id: String,
+    /// Whether every check passed
+    pub passed: bool,
+    pub results: Vec<SmokeTestResult>,
+    /// Start timestamp (RFC 3339)
+    pub started_at: String,
+    /// End timestamp (RFC 3339)
+    pub finished_at: String,
+}
+
+impl SmokeTestReport {
+    /// One-line summary (e.g., "3/4 smoke tests passed; failed: Login").
+    pub fn summary(&self) -> String {
+        let passed = self.results.iter().filter(|r| r.passed).count();
+        let failed: Vec<&str> = self
+            .results
+            .iter()
+            .filter(|r| !r.passed)
+            .map(|r| r.name.as_str())
+            .collect();
+
+        let mut summary = format!("{passed}/{} smoke tests passed", self.results.len());
+        if !failed.is_empty() {
+            summary.push_str(&format!("; failed: {}", failed.join(", ")));
+        }
+        summary
+    }
+}
+
+/// Runs every smoke test defined for an environment.
+pub async fn run_for_environment(
+    app: &AppHandle,
+    environment_id: &str,
+) -> Result<SmokeTestReport, String> {
+    let tests: Vec<SmokeTest> = load_smoke_tests(app.clone())
+        .await?
+        .into_iter()
+        .filter(|t| t.environment_id == environment_id)
+        .collect();
+    if tests.is_empty() {
+        return Err(format!(
+            "No smoke tests defined for environment {environment_id}"
+        ));
+    }
+
+    let started_at = Utc::now().to_rfc3339();
+    let mut results = Vec::with_capacity(tests.len());
+    for test in &tests {
+        let result = run_test(test).await;
+        if result.passed {
+            log::debug!("Smoke test '{}' passed", test.name);
+        } else {
+            log::warn!(
+                "Smoke test '{}' failed after {} attempt(s): {}",
+                test.name,
+                result.attempts,
+                result.error.as_deref().unwrap_or_default()
+            );
+        }
+        results.push(result);
+    }
+
+    let report = SmokeTestReport {
+        environment_id: environment_id.to_string(),
+        passed: results.iter().all(|r| r.passed),
+        results,
+        started_at,
+        finished_at: Utc::now().to_rfc3339(),
+    };
+    log::info!(
+        "Smoke tests for environment {environment_id}: {}",
+        report.summary()
+    );
+    Ok(report)
+}
+
+/// Runs one check, retrying up to `retries` times.
+async fn run_test(test: &SmokeTest) -> SmokeTestResult {
+    let mut result = SmokeTestResult {
+        test_id: test.id.clone(),
+        name: test.name.clone(),
+        url: test.url.clone(),
+        passed: false,
+        status: None,
+        attempts: 0,
+        duration_ms: 0,
+        error: None,
+    };
+
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(u64::from(test.timeout_seconds.max(1))))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            result.error = Some(format!("Failed to initialize HTTP client: {e}"));
+            return result;
+        }
+    };
+
+    let mut headers = Vec::with_capacity(test.headers.len());
+    for (name, value) in &test.headers {
+        match crate::secrets::resolve(value).await {
+            Ok(value) => headers.push((name.as_str(), value)),
+            Err(e) => {
+                result.error = Some(format!("Header {name}: {e}"));
+                return result;
+            }
+        }
+    }
+
+    for attempt in 0..=test.retries {
+        if attempt > 0 {
+            tokio::time::sleep(RETRY_DELAY).await;
+        }
+        result.attempts = attempt + 1;
+
+        let started = Instant::now();
+        let outcome = attempt_request(&client, test, &headers).await;
+        result.duration_ms = u32::try_from(started.elapsed().as_millis()).unwrap_or(u32::MAX);
+
+        match outcome {
+            Ok(status) => {
+                result.status = Some(status);
+                result.passed = true;
+                result.error = None;
+                break;
+            }
+            Err((status, error)) => {
+                result.status = status;
+                result.error = Some(error);
+            }
+        }
+    }
+    result
+}
+
+/// Performs one request. Returns the status, or the status (if any) and a reason.
+async fn attempt_request(
+    client: &reqwest::Client,
+    test: &SmokeTest,
+    headers: &[(&str, String)],
+) -> Result<u16, (Option<u16>, String)> {
+    let mut request = client.get(&test.url);
+    for (name, value) in headers {
+        request = request.header(*name, value);
+    }
+    let mut response = request
+        .send()
+        .await
+        .map_err(|e| (None, format!("Request failed: {e}")))?;
+
+    let status = response.status().as_u16();
+    let body = if test.expected_body.is_some() {
+        // Stops at the limit, so a huge or endless body isn't buffered
+        let mut bytes = Vec::new();
+        while bytes.len() < MAX_BODY_BYTES {
+            let chunk = response
+                .chunk()
+                .await
+                .map_err(|e| (Some(status), format!("Failed to read response: {e}")))?;
+            let Some(chunk) = chunk else {
+                break;
+            };
+            bytes.extend_from_slice(&chunk);
+        }
+        bytes.truncate(MAX_BODY_BYTES);
+        String::from_utf8_lossy(&bytes).to_string()
+    } else {
+        String::new()
+    };
+
+    evaluate(test, status, &body)
+        .map(|()| status)
+        .map_err(|e| (Some(status), e))
+}
+
+/// Checks a response against the test's expectations.
+fn evaluate(test: &SmokeTest, status: u16, body: &str) -> Result<(), String> {
+    if status != test.expected_status {
+        return Err(format!(
+            "Expected status {}, got {status}",
+            test.expected_status
+        ));
+    }
+    if let Some(expected) = test.expected_body.as_deref() {
+        if !body.contains(expected) {
+            return Err(format!("Response body does not contain '{expected}'"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smoke_test(expected_body: Option<&str>) -> SmokeTest {
+        SmokeTest {
+            id: "health".to_string(),
+            environment_id: "staging".to_string(),
+            name: "Health".to_string(),
+            url: "https://staging.example.com/health".to_string(),
+            expected_status: 200,
+            expected_body: expected_body.map(str::to_string),
+            timeout_seconds: 5,
+            retries: 0,
+            headers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        assert!(evaluate(&smoke_test(None), 200, "").is_ok());
+        assert_eq!(
+            evaluate(&smoke_test(None), 503, "").unwrap_err(),
+            "Expected status 200, got 503"
+        );
+        assert!(evaluate(
+            &smoke_test(Some("\"status\":\"UP\"")),
+            200,
+            r#"{"status":"UP"}"#
+        )
+        .is_ok());
+        assert!(evaluate(&smoke_test(Some("UP")), 200, "DOWN").is_err());
+    }
+
+    #[test]
+    fn test_report_summary() {
+        let result = |name: &str, passed: bool| SmokeTestResult {
+            test_id: name.to_lowercase(),
+            name: name.to_string(),
+            url: String::new(),
+            passed,
+            status: Some(200),
+            attempts: 1,
+            duration_ms: 12,
+            error: None,
+        };
+        let report = SmokeTestReport {
+            environment_id: "staging".to_string(),
+            passed: false,
+            results: vec![result("Health", true), result("Login", false)],
+            started_at: String::new(),
+            finished_at: String::new(),
+        };
+        assert_eq!(report.summary(), "1/2 smoke tests passed; failed: Login");
+    }
+}
@@ -0,0 +1,462 @@
+//! Synthetic uptime monitoring.
+//!
+//! While running, the monitor probes every configured uptime target
+//! (`uptime_targets.yaml`) at its interval and keeps a latency/status history
+//! per target in `<app data>/uptime_history.json`. A target that fails
+//! [`DOWN_THRESHOLD`] checks in a row raises a native notification, and so
+//! does its recovery. Status changes are emitted as `uptime-status-changed`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::sync::oneshot;
+use tokio::task::JoinSet;
+
+use crate::commands::config::load_uptime_targets;
+use crate::commands::notifications::send_native_notification;
+use crate::task_pool::{self, Priority};
+use crate::types::UptimeTarget;
+use crate::windows;
+
+/// Event emitted with an `UptimeTargetStatus` when a target goes down or recovers.
+pub const UPTIME_STATUS_CHANGED_EVENT: &str = "uptime-status-changed";
+
+/// Consecutive failed checks before a target counts as down.
+pub const DOWN_THRESHOLD: u32 = 2;
+
+/// Samples kept per target (a day of one-minute checks).
+const MAX_SAMPLES: usize = 1440;
+
+/// How often the monitor looks for targets that are due.
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Result of one check.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct UptimeSample {
+    /// Check timestamp (RFC 3339)
+    pub checked_at: String,
+    pub up: bool,
+    /// HTTP status (if a response was received)
+    pub status: Option<u16>,
+    /// Response time in milliseconds
+    pub latency_ms: u32,
+    /// Why the check failed
+    pub error: Option<String>,
+}
+
+/// Current state and history of a target.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct UptimeTargetStatus {
+    pub target_id: String,
+    pub name: String,
+    pub url: String,
+    /// Whether the target is up; `None` until it was checked
+    pub up: Option<bool>,
+    /// Failed checks in a row
+    pub consecutive_failures: u32,
+    /// Share of successful checks in the history (0-100)
+    pub uptime_percent: Option<f64>,
+    /// Checks, oldest first
+    pub history: Vec<UptimeSample>,
+}
+
+/// Current state of the uptime monitor.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UptimeMonitorStatus {
+    pub running: bool,
+    /// Start timestamp (RFC 3339)
+    pub started_at: Option<String>,
+    pub targets: Vec<UptimeTargetStatus>,
+}
+
+/// What a check changed about a target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transition {
+    WentDown,
+    Recovered,
+}
+
+/// Recorded checks of a target.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TargetHistory {
+    samples: VecDeque<UptimeSample>,
+    consecutive_failures: u32,
+}
+
+impl TargetHistory {
+    /// Adds a sample and reports whether the target went down or recovered.
+    fn record(&mut self, sample: UptimeSample) -> Option<Transition> {
+        let was_down = self.consecutive_failures >= DOWN_THRESHOLD;
+        self.consecutive_failures = if sample.up {
+            0
+        } else {
+            self.consecutive_failures + 1
+        };
+
+        self.samples.push_back(sample);
+        while self.samples.len() > MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+
+        match (was_down, self.consecutive_failures >= DOWN_THRESHOLD) {
+            (false, true) => Some(Transition::WentDown),
+            (true, false) => Some(Transition::Recovered),
+            _ => None,
+        }
+    }
+
+    fn is_due(&self, target: &UptimeTarget, now: DateTime<Utc>) -> bool {
+        let Some(last) = self.samples.back() else {
+            return true;
+        };
+        match DateTime::parse_from_rfc3339(&last.checked_at) {
+            Ok(checked_at) => {
+                now.signed_duration_since(checked_at).num_seconds()
+                    >= i64::from(target.interval_seconds.max(1))
+            }
+            Err(_) => true,
+        }
+    }
+
+    fn status(&self, target: &UptimeTarget) -> UptimeTargetStatus {
+        let up_count = self.samples.iter().filter(|s| s.up).count();
+        UptimeTargetStatus {
+            target_id: target.id.clone(),
+            name: target.name.clone(),
+            url: target.url.clone(),
+            up: self
+                .samples
+                .back()
+                .map(|_| self.consecutive_failures < DOWN_THRESHOLD),
+            consecutive_failures: self.consecutive_failures,
+            uptime_percent: (!self.samples.is_empty())
+                .then(|| up_count as f64 * 100.0 / self.samples.len() as f64),
+            history: self.samples.iter().cloned().collect(),
+        }
+    }
+}
+
+/// The running monitor.
+struct Monitor {
+    started_at: String,
+    shutdown: oneshot::Sender<()>,
+}
+
+static MONITOR: LazyLock<Mutex<Option<Monitor>>> = LazyLock::new(|| Mutex::new(None));
+
+/// History per target ID; `None` until loaded from disk.
+static HISTORY: LazyLock<Mutex<Option<HashMap<String, TargetHistory>>>> =
+    LazyLock::new(|| Mutex::new(None));
+
+fn monitor() -> MutexGuard<'static, Option<Monitor>> {
+    MONITOR.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Runs `f` on the history, loading it from disk on first use.
+fn with_history<R>(app: &AppHandle, f: impl FnOnce(&mut HashMap<String, TargetHistory>) -> R) -> R {
+    let mut history = HISTORY.lock().unwrap_or_else(PoisonError::into_inner);
+    let history = history.get_or_insert_with(|| {
+        load_history(app).unwrap_or_else(|e| {
+            log::warn!("Failed to load uptime history, starting empty: {e}");
+            HashMap::new()
+        })
+    });
+    f(history)
+}
+
+/// Starts the monitor. Fails if it is already running.
+pub fn start(app: &AppHandle) -> Result<(), String> {
+    let mut monitor = monitor();
+    if monitor.is_some() {
+        return Err("Uptime monitor is already running".to_string());
+    }
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    *monitor = Some(Monitor {
+        started_at: Utc::now().to_rfc3339(),
+        shutdown: shutdown_tx,
+    });
+
+    log::info!("Uptime monitor started");
+    tauri::async_runtime::spawn(monitor_loop(app.clone(), shutdown_rx));
+    Ok(())
+}
+
+/// Stops the monitor if it is running.
+pub fn stop() {
+    if let Some(running) = monitor().take() {
+        let _ = running.shutdown.send(());
+        log::info!("Uptime monitor stopped");
+    }
+}
+
+/// Returns the monitor state with the history of every configured target.
+pub async fn status(app: &AppHandle) -> Result<UptimeMonitorStatus, String> {
+    let targets = load_uptime_targets(app.clone()).await?;
+    let started_at = monitor().as_ref().map(|m| m.started_at.clone());
+
+    let targets = with_history(app, |history| {
+        targets
+            .iter()
+            .map(|target| {
+                history
+                    .get(&target.id)
+                    .cloned()
+                    .unwrap_or_default()
+                    .status(target)
+            })
+            .collect()
+    });
+
+    Ok(UptimeMonitorStatus {
+        running: started_at.is_some(),
+        started_at,
+        targets,
+    })
+}
+
+async fn monitor_loop(app: AppHandle, mut shutdown: oneshot::Receiver<()>) {
+    loop {
+        match load_uptime_targets(app.clone()).await {
+            Ok(targets) => check_due_targets(&app, targets).await,
+            Err(e) => log::warn!("Failed to load uptime targets: {e}"),
+        }
+
+        tokio::select! {
+            _ = &mut shutdown => break,
+            _ = tokio::time::sleep(TICK_INTERVAL) => {}
+        }
+    }
+}
+
+/// Probes every target whose interval has elapsed, in parallel.
+async fn check_due_targets(app: &AppHandle, targets: Vec<UptimeTarget>) {
+    let now = Utc::now();
+    let due: Vec<UptimeTarget> = with_history(app, |history| {
+        targets
+            .into_iter()
+            .filter(|t| history.get(&t.id).is_none_or(|h| h.is_due(t, now)))
+            .collect()
+    });
+    if due.is_empty() {
+        return;
+    }
+
+    let mut probes = JoinSet::new();
+    for target in due {
+        probes.spawn(async move {
+            let sample = task_pool::run(Priority::Poller, &target.id, probe(&target)).await;
+            (target, sample)
+        });
+    }
+
+    let mut alerts = Vec::new();
+    while let Some(joined) = probes.join_next().await {
+        let Ok((target, sample)) = joined else {
+            continue;
+        };
+
+        let transition = with_history(app, |history| {
+            let entry = history.entry(target.id.clone()).or_default();
+            entry
+                .record(sample)
+                .map(|transition| (transition, entry.status(&target)))
+        });
+        if let Some((transition, status)) = transition {
+            alerts.push((transition, status, target));
+        }
+    }
+
+    if let Err(e) = save_history(app) {
+        log::warn!("Failed to save uptime history: {e}");
+    }
+
+    for (transition, status, target) in alerts {
+        alert(app, transition, status, &target).await;
+    }
+}
+
+async fn alert(
+    app: &AppHandle,
+    transition: Transition,
+    status: UptimeTargetStatus,
+    target: &UptimeTarget,
+) {
+    let error = status
+        .history
+        .last()
+        .and_then(|s| s.error.clone())
+        .unwrap_or_default();
+    let (title, body) = match transition {
+        Transition::WentDown => {
+            log::warn!("Uptime target {} is down: {error}", target.name);
+            (format!("{} is down", target.name), error)
+        }
+        Transition::Recovered => {
+            log::info!("Uptime target {} recovered", target.name);
+            (format!("{} is back up", target.name), target.url.clone())
+        }
+    };
+
+    windows::broadcast(app, UPTIME_STATUS_CHANGED_EVENT, &status);
+    if let Err(e) = send_native_notification(app.clone(), title, Some(body)).await {
+        log::warn!("Failed to send uptime alert: {e}");
+    }
+}
+
+/// Checks a target once.
+async fn probe(target: &UptimeTarget) -> UptimeSample {
+    let checked_at = Utc::now().to_rfc3339();
+    let started = Instant::now();
+
+    let result = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(u64::from(
+            target.timeout_seconds.max(1),
+        )))
+        .build()
+    {
+        Ok(client) => client.get(&target.url).send().await,
+        Err(e) => {
+            return UptimeSample {
+                checked_at,
+                up: false,
+                status: None,
+                latency_ms: 0,
+                error: Some(format!("Failed to initialize HTTP client: {e}")),
+            };
+        }
+    };
+    let latency_ms = u32::try_from(started.elapsed().as_millis()).unwrap_or(u32::MAX);
+
+    match result {
+        Ok(response) => {
+            let status = response.status().as_u16();
+            let up = match target.expected_status {
+                Some(expected) => status == expected,
+                None => status < 400,
+            };
+            UptimeSample {
+                checked_at,
+                up,
+                status: Some(status),
+                latency_ms,
+                error: (!up).then(|| format!("Unexpected status {status}")),
+            }
+        }
+        Err(e) => UptimeSample {
+            checked_at,
+            up: false,
+            status: None,
+            latency_ms,
+            error: Some(format!("Request failed: {e}")),
+        },
+    }
+}
+
+fn history_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    Ok(app_data_dir.join("uptime_history.json"))
+}
+
+fn load_history(app: &AppHandle) -> Result<HashMap<String, TargetHistory>, String> {
+    let path = history_path(app)?;
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let contents =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read history: {e}"))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse history: {e}"))
+}
+
+fn save_history(app: &AppHandle) -> Result<(), String> {
+    let path = history_path(app)?;
+    let json = with_history(app, |history| serde_json::to_string(history))
+        .map_err(|e| format!("Failed to serialize history: {e}"))?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {e}"))?;
+    }
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json).map_err(|e| format!("Failed to write history: {e}"))?;
+    if let Err(rename_err) = std::fs::rename(&temp_path, &path) {
+        if let Err(remove_err) = std::fs::remove_file(&temp_path) {
+            log::warn!("Failed to remove temp file after rename failure: {remove_err}");
+        }
+        return Err(format!("Failed to finalize history: {rename_err}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(up: bool) -> UptimeSample {
+        UptimeSample {
+            checked_at: "2026-03-02T10:00:00Z".to_string(),
+            up,
+            status: Some(if up { 200 } else { 503 }),
+            latency_ms: 40,
+            error: (!up).then(|| "Unexpected status 503".to_string()),
+        }
+    }
+
+    fn target(interval_seconds: u32) -> UptimeTarget {
+        UptimeTarget {
+            id: "shop".to_string(),
+            environment_id: Some("prod".to_string()),
+            name: "Shop".to_string(),
+            url: "https://shop.example.com".to_string(),
+            interval_seconds,
+            timeout_seconds: 10,
+            expected_status: None,
+        }
+    }
+
+    #[test]
+    fn test_down_after_threshold_and_recovery() {
+        let mut history = TargetHistory::default();
+        assert_eq!(history.record(sample(true)), None);
+        assert_eq!(history.record(sample(false)), None);
+        assert_eq!(history.record(sample(false)), Some(Transition::WentDown));
+        assert_eq!(history.record(sample(false)), None);
+        assert_eq!(history.record(sample(true)), Some(Transition::Recovered));
+
+        let status = history.status(&target(60));
+        assert_eq!(status.up, Some(true));
+        assert_eq!(status.uptime_percent, Some(40.0));
+    }
+
+    #[test]
+    fn test_is_due() {
+        let mut history = TargetHistory::default();
+        let now = DateTime::parse_from_rfc3339("2026-03-02T10:00:30Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert!(history.is_due(&target(60), now));
+
+        history.record(sample(true));
+        assert!(!history.is_due(&target(60), now));
+        assert!(history.is_due(&target(30), now));
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut history = TargetHistory::default();
+        for _ in 0..MAX_SAMPLES + 5 {
+            history.record(sample(true));
+        }
+        assert_eq!(history.samples.len(), MAX_SAMPLES);
+    }
+}
@@ -0,0 +1,342 @@
+//! Resource annotation commands.
+//!
+//! Notes and bookmarks attached to resource keys such as
+//! `jenkins/<integration id>/job/<name>` or `kubernetes/<integration
+//! id>/namespace/<name>`, so knowledge like "this job flakes on Mondays" is
+//! shown next to the resource. Keys are opaque to the backend; the frontend
+//! builds them. Annotations are stored in `<app data>/annotations.json`.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex, PoisonError};
+use tauri::{AppHandle, Manager};
+
+use crate::types::Timestamp;
+use crate::utils::platform::current_user;
+
+/// Serializes read-modify-write cycles on the annotations file.
+static STORE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnotationKind {
+    Note,
+    Bookmark,
+}
+
+/// A note or bookmark on a resource.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct Annotation {
+    pub id: String,
+    /// Key of the annotated resource
+    pub resource_key: String,
+    pub kind: AnnotationKind,
+    pub text: String,
+    /// OS user that created the annotation
+    pub author: String,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnotationExportFormat {
+    Json,
+    Markdown,
+}
+
+fn annotations_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    Ok(app_data_dir.join("annotations.json"))
+}
+
+fn read_annotations(app: &AppHandle) -> Result<Vec<Annotation>, String> {
+    let path = annotations_path(app)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read annotations: {e}"))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse annotations: {e}"))
+}
+
+fn write_annotations(app: &AppHandle, annotations: &[Annotation]) -> Result<(), String> {
+    let path = annotations_path(app)?;
+    let json = serde_json::to_string_pretty(annotations)
+        .map_err(|e| format!("Failed to serialize annotations: {e}"))?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {e}"))?;
+    }
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json).map_err(|e| format!("Failed to write annotations: {e}"))?;
+    if let Err(rename_err) = std::fs::rename(&temp_path, &path) {
+        if let Err(remove_err) = std::fs::remove_file(&temp_path) {
+            log::warn!("Failed to remove temp file after rename failure: {remove_err}");
+        }
+        return Err(format!("Failed to finalize annotations: {rename_err}"));
+    }
+    Ok(())
+}
+
+/// Runs `update` on the stored annotations and writes them back.
+fn modify_annotations<T>(
+    app: &AppHandle,
+    update: impl FnOnce(&mut Vec<Annotation>) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut annotations = read_annotations(app)?;
+    let result = update(&mut annotations)?;
+    write_annotations(app, &annotations)?;
+    Ok(result)
+}
+
+fn validate_text(text: &str) -> Result<String, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Annotation text cannot be empty".to_string());
+    }
+    Ok(text.to_string())
+}
+
+/// Returns `base`, or `base-N` with the first N not used by `annotations`.
+fn unique_id(annotations: &[Annotation], base: &str) -> String {
+    let taken = |id: &str| annotations.iter().any(|a| a.id == id);
+    if !taken(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|id| !taken(id))
+        .expect("unbounded range")
+}
+
+/// Renders annotations grouped by resource key, oldest first.
+fn render_markdown(annotations: &[Annotation]) -> String {
+    let mut by_resource: BTreeMap<&str, Vec<&Annotation>> = BTreeMap::new();
+    for annotation in annotations {
+        by_resource
+            .entry(&annotation.resource_key)
+            .or_default()
+            .push(annotation);
+    }
+
+    let mut markdown = String::from("# Annotations\n");
+    for (resource_key, mut entries) in by_resource {
+        entries.sort_by(|a, b| a.created_at.rfc3339.cmp(&b.created_at.rfc3339));
+        markdown.push_str(&format!("\n## {resource_key}\n\n"));
+        for entry in entries {
+            let marker = match entry.kind {
+                AnnotationKind::Note => "",
+                AnnotationKind::Bookmark => "Bookmark: ",
+            };
+            markdown.push_str(&format!(
+                "- {marker}{} ({}, {})\n",
+                entry.text.replace('\n', " "),
+                entry.author,
+                entry.created_at
+            ));
+        }
+    }
+    markdown
+}
+
+/// Lists annotations, optionally only those on one resource.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_annotations(
+    app: AppHandle,
+    resource_key: Option<String>,
+) -> Result<Vec<Annotation>, String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut annotations = read_annotations(&app)?;
+    if let Some(key) = resource_key {
+        annotations.retain(|a| a.resource_key == key);
+    }
+    Ok(annotations)
+}
+
+/// Attaches a note or bookmark to a resource.
+#[tauri::command]
+#[specta::specta]
+pub async fn add_annotation(
+    app: AppHandle,
+    resource_key: String,
+    kind: AnnotationKind,
+    text: String,
+) -> Result<Annotation, String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    let resource_key = resource_key.trim().to_string();
+    if resource_key.is_empty() {
+        return Err("Resource key cannot be empty".to_string());
+    }
+    let text = validate_text(&text)?;
+
+    modify_annotations(&app, |annotations| {
+        let now = Utc::now();
+        let annotation = Annotation {
+            id: unique_id(annotations, &format!("note-{}", now.timestamp_millis())),
+            resource_key,
+            kind,
+            text,
+            author: current_user(),
+            created_at: Timestamp::from_datetime(now),
+            updated_at: Timestamp::from_datetime(now),
+        };
+        log::info!(
+            "Adding annotation {} on {}",
+            annotation.id,
+            annotation.resource_key
+        );
+        annotations.push(annotation.clone());
+        Ok(annotation)
+    })
+}
+
+/// Changes the text and kind of an annotation.
+#[tauri::command]
+#[specta::specta]
+pub async fn update_annotation(
+    app: AppHandle,
+    annotation_id: String,
+    kind: AnnotationKind,
+    text: String,
+) -> Result<Annotation, String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    let text = validate_text(&text)?;
+
+    modify_annotations(&app, |annotations| {
+        let annotation = annotations
+            .iter_mut()
+            .find(|a| a.id == annotation_id)
+            .ok_or_else(|| format!("Annotation not found: {annotation_id}"))?;
+        annotation.kind = kind;
+        annotation.text = text;
+        annotation.updated_at = Timestamp::from_datetime(Utc::now());
+        Ok(annotation.clone())
+    })
+}
+
+/// Deletes an annotation.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_annotation(app: AppHandle, annotation_id: String) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    modify_annotations(&app, |annotations| {
+        let before = annotations.len();
+        annotations.retain(|a| a.id != annotation_id);
+        if annotations.len() == before {
+            return Err(format!("Annotation not found: {annotation_id}"));
+        }
+        log::info!("Deleted annotation {annotation_id}");
+        Ok(())
+    })
+}
+
+/// Exports all annotations as JSON (same shape as `list_annotations`) or as
+/// a Markdown document grouped by resource.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_annotations(
+    app: AppHandle,
+    format: AnnotationExportFormat,
+) -> Result<String, String> {
+    let annotations = {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        read_annotations(&app)?
+    };
+
+    match format {
+        AnnotationExportFormat::Json => serde_json::to_string_pretty(&annotations)
+            .map_err(|e| format!("Failed to serialize annotations: {e}")),
+        AnnotationExportFormat::Markdown => Ok(render_markdown(&annotations)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotation(id: &str, resource_key: &str, kind: AnnotationKind, at: &str) -> Annotation {
+        Annotation {
+            id: id.to_string(),
+            resource_key: resource_key.to_string(),
+            kind,
+            text: format!("text of {id}"),
+            author: "alice".to_string(),
+            created_at: Timestamp::parse(at).unwrap(),
+            updated_at: Timestamp::parse(at).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_unique_id() {
+        let existing = vec![
+            annotation("note-1", "a", AnnotationKind::Note, "2024-05-01T10:00:00Z"),
+            annotation(
+                "note-1-2",
+                "a",
+                AnnotationKind::Note,
+                "2024-05-01T10:00:00Z",
+            ),
+        ];
+
+        assert_eq!(unique_id(&existing, "note-2"), "note-2");
+        assert_eq!(unique_id(&existing, "note-1"), "note-1-3");
+    }
+
+    #[test]
+    fn test_render_markdown_groups_by_resource() {
+        let annotations = vec![
+            annotation(
+                "b",
+                "jenkins/ci/job/deploy",
+                AnnotationKind::Note,
+                "2024-05-02T10:00:00Z",
+            ),
+            annotation(
+                "a",
+                "jenkins/ci/job/deploy",
+                AnnotationKind::Bookmark,
+                "2024-05-01T10:00:00Z",
+            ),
+            annotation(
+                "c",
+                "gitlab/main/project/42",
+                AnnotationKind::Note,
+                "2024-05-03T10:00:00Z",
+            ),
+        ];
+
+        assert_eq!(
+            render_markdown(&annotations),
+            "# Annotations\n\
+             \n## gitlab/main/project/42\n\n\
+             - text of c (alice, 2024-05-03T10:00:00.000Z)\n\
+             \n## jenkins/ci/job/deploy\n\n\
+             - Bookmark: text of a (alice, 2024-05-01T10:00:00.000Z)\n\
+             - text of b (alice, 2024-05-02T10:00:00.000Z)\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_text() {
+        assert!(validate_text("  \n").is_err());
+        assert_eq!(
+            validate_text(" flakes on Mondays ").unwrap(),
+            "flakes on Mondays"
+        );
+    }
+}
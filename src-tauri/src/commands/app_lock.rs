@@ -0,0 +1,49 @@
+//! App lock commands.
+//!
+//! Locking, unlocking and passphrase management. The idle timeout itself is
+//! part of the preferences (`app_lock_idle_minutes`).
+
+use tauri::AppHandle;
+
+use crate::app_lock::{self, AppLockStatus};
+
+/// Get whether the app lock is enabled and currently locked.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_app_lock_status() -> Result<AppLockStatus, String> {
+    Ok(app_lock::status())
+}
+
+/// Lock the app now.
+#[tauri::command]
+#[specta::specta]
+pub async fn lock_app(app: AppHandle) -> Result<(), String> {
+    app_lock::lock(&app)
+}
+
+/// Unlock the app with the lock passphrase.
+#[tauri::command]
+#[specta::specta]
+pub async fn unlock_app(app: AppHandle, passphrase: String) -> Result<(), String> {
+    app_lock::unlock(&app, &passphrase)
+}
+
+/// Set, change or remove (`passphrase: null`) the lock passphrase.
+/// Requires the current passphrase when one is set.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_app_lock_passphrase(
+    current_passphrase: Option<String>,
+    passphrase: Option<String>,
+) -> Result<(), String> {
+    app_lock::ensure_unlocked()?;
+    app_lock::set_passphrase(current_passphrase.as_deref(), passphrase.as_deref())
+}
+
+/// Record user activity, postponing the idle lock.
+#[tauri::command]
+#[specta::specta]
+pub async fn record_app_activity() -> Result<(), String> {
+    app_lock::record_activity();
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! Build comparison commands.
+//!
+//! Compares two Jenkins builds or two GitLab pipelines for the "why did this
+//! build get slower/fail" panel.
+
+use tauri::AppHandle;
+
+use crate::commands::gitlab::gitlab_adapter;
+use crate::commands::jenkins::jenkins_adapter;
+use crate::integrations::builds::BuildComparison;
+use crate::metrics::CommandTimer;
+use crate::types::IntegrationType;
+
+/// Compares build `build_a` (base) with build `build_b` (head).
+///
+/// # Arguments
+/// * `target` - Job name for Jenkins integrations, project ID for GitLab
+/// * `build_a`, `build_b` - Build numbers (Jenkins) or pipeline IDs (GitLab)
+#[tauri::command]
+#[specta::specta]
+pub async fn compare_builds(
+    app: AppHandle,
+    integration_id: String,
+    target: String,
+    build_a: u32,
+    build_b: u32,
+) -> Result<BuildComparison, String> {
+    let _timer = CommandTimer::start("compare_builds");
+    log::debug!("Comparing builds {build_a} and {build_b} of {target} on {integration_id}");
+
+    let integrations = crate::commands::config::load_integrations(app.clone()).await?;
+    let integration = integrations
+        .into_iter()
+        .find(|i| i.id == integration_id)
+        .ok_or_else(|| format!("Integration not found: {integration_id}"))?;
+
+    let result = match integration.integration_type {
+        IntegrationType::Jenkins => {
+            jenkins_adapter(&app, &integration_id)
+                .await?
+                .compare_builds(&target, build_a, build_b)
+                .await
+        }
+        IntegrationType::GitLab => {
+            let project_id: u32 = target
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid GitLab project ID: {target}"))?;
+            gitlab_adapter(&app, &integration_id)
+                .await?
+                .compare_pipelines(project_id, build_a, build_b)
+                .await
+        }
+        other => return Err(format!("Build comparison is not supported for {other:?}")),
+    };
+
+    result.map_err(|e| {
+        log::error!("Failed to compare builds: {e}");
+        e.to_string()
+    })
+}
@@ -0,0 +1,138 @@
+//! Endpoint check commands.
+//!
+//! Runs the HTTP checks defined for environments, controls the uptime
+//! monitor, reports TLS certificate expiry, probes endpoint connectivity and
+//! checks images against registry vulnerability scans and compares their SBOMs
+//! and environments against their desired state.
+
+use tauri::AppHandle;
+
+use crate::checks::certificates::{self, CertificateInfo};
+use crate::checks::drift::{self, EnvironmentDrift};
+use crate::checks::image_security::{self, ImageSecurityReport};
+use crate::checks::probe::{self, EndpointProbe};
+use crate::checks::sbom::{self, ImageSbom, SbomDiff};
+use crate::checks::smoke::{self, SmokeTestReport};
+use crate::checks::uptime::{self, UptimeMonitorStatus};
+use crate::commands::kubernetes::kubernetes_adapter;
+
+/// Run every smoke test defined for an environment.
+#[tauri::command]
+#[specta::specta]
+pub async fn run_smoke_tests(
+    app: AppHandle,
+    environment_id: String,
+) -> Result<SmokeTestReport, String> {
+    log::debug!("Running smoke tests for environment: {environment_id}");
+    smoke::run_for_environment(&app, &environment_id).await
+}
+
+/// Start probing uptime targets on their schedule.
+#[tauri::command]
+#[specta::specta]
+pub async fn start_uptime_monitor(app: AppHandle) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    uptime::start(&app)
+}
+
+/// Stop the uptime monitor.
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_uptime_monitor() -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    uptime::stop();
+    Ok(())
+}
+
+/// Get the uptime monitor state with every target's history.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_uptime_status(app: AppHandle) -> Result<UptimeMonitorStatus, String> {
+    uptime::status(&app).await
+}
+
+/// List the TLS certificates found by the last certificate check.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_certificates(app: AppHandle) -> Result<Vec<CertificateInfo>, String> {
+    certificates::list(&app)
+}
+
+/// Check the TLS certificates of all integrations and ingress hosts now.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_certificates(app: AppHandle) -> Result<Vec<CertificateInfo>, String> {
+    certificates::check_all(&app).await
+}
+
+/// Probe a URL stage by stage (DNS, TCP, TLS, HTTP HEAD) with per-stage timing.
+#[tauri::command]
+#[specta::specta]
+pub async fn probe_endpoint(url: String) -> Result<EndpointProbe, String> {
+    log::debug!("Probing endpoint: {url}");
+    probe::probe(&url).await
+}
+
+/// Check an image against its registry's vulnerability scan (e.g., Harbor),
+/// returning whether deploys of it would be blocked and the critical CVEs.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_image_security(
+    app: AppHandle,
+    image_ref: String,
+) -> Result<ImageSecurityReport, String> {
+    log::debug!("Checking image security: {image_ref}");
+    image_security::check_image(&app, &image_ref).await
+}
+
+/// Check every image running in a cluster (or in some of its namespaces)
+/// against its registry's vulnerability scan.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_k8s_running_images(
+    app: AppHandle,
+    integration_id: String,
+    namespaces: Vec<String>,
+) -> Result<Vec<ImageSecurityReport>, String> {
+    log::debug!("Checking images running on {integration_id} in {namespaces:?}");
+    let images = kubernetes_adapter(&app, &integration_id)
+        .await?
+        .fetch_image_inventory(&namespaces)
+        .await
+        .map_err(|e| format!("Failed to fetch image inventory: {e}"))?;
+    Ok(image_security::check_running_images(&app, &images).await)
+}
+
+/// Fetch the SBOM of an image from Harbor or the registry's referrers API.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_image_sbom(app: AppHandle, image_ref: String) -> Result<ImageSbom, String> {
+    log::debug!("Fetching SBOM of image: {image_ref}");
+    sbom::fetch_sbom(&app, &image_ref).await
+}
+
+/// Compare the SBOMs of two images (e.g., the deployed and a candidate image).
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_sboms(
+    app: AppHandle,
+    image_a: String,
+    image_b: String,
+) -> Result<SbomDiff, String> {
+    log::debug!("Comparing SBOMs of {image_a} and {image_b}");
+    sbom::diff(&app, &image_a, &image_b).await
+}
+
+/// Compare the deployments of an environment's namespace with its desired
+/// state, reporting drifted and missing services.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_environment_drift(
+    app: AppHandle,
+    environment_id: String,
+) -> Result<EnvironmentDrift, String> {
+    log::debug!("Checking drift of environment: {environment_id}");
+    drift::check_environment(&app, &environment_id).await
+}
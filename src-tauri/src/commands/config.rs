@@ -1,10 +1,21 @@
-//! Config management commands for Projects, Environments, Integrations, and Mappings.
+//! Config management commands for Projects, Environments, Integrations, Mappings,
+//! Smoke Tests, Uptime Targets, Quick Pane Macros, Cost Rates, Image Scanners,
+//! Tag Watches, Event Recordings, Failure Rules, Report Definitions, and Saved
+//! Filters.
 //!
 //! Handles loading and saving configuration files with atomic writes.
-//! Config files are stored in YAML format for human readability.
-
-use crate::types::{Environment, Integration, Mapping, Project};
-use std::path::PathBuf;
+//! Config files are stored in YAML format for human readability. Saves of
+//! integrations and mappings are also recorded in the config history.
+
+use crate::config_history::{self, ConfigHistoryDiff, ConfigHistoryEntity, ConfigSnapshotInfo};
+use crate::integrations::compat::{pinned_version, IntegrationApiVersion};
+use crate::metrics::{self, CommandTimer};
+use crate::types::{
+    CostRate, DesiredState, Environment, EventRecording, FailureRule, ImageScanner, Integration,
+    Mapping, Project, QuickPaneMacro, ReportDefinition, SavedFilter, SmokeTest, TagWatch,
+    UptimeTarget,
+};
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Manager};
 
 /// Gets the path to the config directory.
@@ -78,6 +89,17 @@ where
     Ok(())
 }
 
+/// Records a history snapshot after a successful save. A failure only loses
+/// the history entry, so it doesn't fail the save.
+fn record_history<T>(config_dir: &Path, entity: ConfigHistoryEntity, data: &[T])
+where
+    T: serde::Serialize,
+{
+    if let Err(e) = config_history::record_snapshot(config_dir, entity, data) {
+        log::warn!("Failed to record config history for {entity:?}: {e}");
+    }
+}
+
 // ============================================================================
 // Projects Commands
 // ============================================================================
@@ -96,6 +118,8 @@ pub async fn load_projects(app: AppHandle) -> Result<Vec<Project>, String> {
 #[tauri::command]
 #[specta::specta]
 pub async fn save_projects(app: AppHandle, projects: Vec<Project>) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
     log::debug!("Saving {} projects to disk", projects.len());
     let config_dir = get_config_dir(&app)?;
     let projects_path = config_dir.join("projects.yaml");
@@ -123,7 +147,12 @@ pub async fn save_environments(
     app: AppHandle,
     environments: Vec<Environment>,
 ) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
     log::debug!("Saving {} environments to disk", environments.len());
+    for environment in &environments {
+        crate::flow_engine::validate_environment_variables(environment)?;
+    }
     let config_dir = get_config_dir(&app)?;
     let environments_path = config_dir.join("environments.yaml");
     save_yaml_config(&environments_path, &environments)
@@ -150,10 +179,22 @@ pub async fn save_integrations(
     app: AppHandle,
     integrations: Vec<Integration>,
 ) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    for integration in &integrations {
+        pinned_version(integration)?;
+    }
+
     log::debug!("Saving {} integrations to disk", integrations.len());
     let config_dir = get_config_dir(&app)?;
     let integrations_path = config_dir.join("integrations.yaml");
-    save_yaml_config(&integrations_path, &integrations)
+    save_yaml_config(&integrations_path, &integrations)?;
+    record_history(
+        &config_dir,
+        ConfigHistoryEntity::Integrations,
+        &integrations,
+    );
+    Ok(())
 }
 
 /// Tests the connection to an integration service.
@@ -163,24 +204,82 @@ pub async fn test_integration_connection(
     app: AppHandle,
     integration_id: String,
 ) -> Result<bool, String> {
+    let _timer = CommandTimer::start("test_integration_connection");
     log::debug!("Testing connection for integration: {}", integration_id);
 
-    use crate::integrations::create_adapter;
-    use crate::integrations::errors::IntegrationError;
-    use crate::integrations::registry::load_credentials;
+    check_integration_connection(&app, &integration_id).await?;
+    log::info!(
+        "Successfully tested connection for integration: {}",
+        integration_id
+    );
+    Ok(true)
+}
+
+/// Returns the API version used for an integration (pinned or detected) and
+/// the compatibility shims applied to its responses.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_integration_api_version(
+    app: AppHandle,
+    integration_id: String,
+) -> Result<IntegrationApiVersion, String> {
     use crate::types::IntegrationType;
 
-    // Load integration
+    let _timer = CommandTimer::start("get_integration_api_version");
     let integrations = load_integrations(app.clone()).await?;
     let integration = integrations
         .into_iter()
         .find(|i| i.id == integration_id)
         .ok_or_else(|| format!("Integration not found: {}", integration_id))?;
 
+    let pinned = pinned_version(&integration)?;
+    let (version, shims) = match integration.integration_type {
+        IntegrationType::GitLab => {
+            let adapter = crate::commands::gitlab::gitlab_adapter(&app, &integration.id).await?;
+            (adapter.api_version(), adapter.active_shims())
+        }
+        IntegrationType::Keycloak => (
+            pinned,
+            crate::integrations::keycloak::KeycloakAdapter::compatibility_notes(pinned),
+        ),
+        _ => (pinned, Vec::new()),
+    };
+
+    Ok(IntegrationApiVersion {
+        integration_id,
+        version: version.map(|v| v.to_string()),
+        pinned: pinned.is_some(),
+        shims,
+    })
+}
+
+/// Tests the connection to an integration and records the result in the
+/// integration health metrics.
+pub(crate) async fn check_integration_connection(
+    app: &AppHandle,
+    integration_id: &str,
+) -> Result<(), String> {
+    let integrations = load_integrations(app.clone()).await?;
+    let integration = integrations
+        .into_iter()
+        .find(|i| i.id == integration_id)
+        .ok_or_else(|| format!("Integration not found: {}", integration_id))?;
+
+    let result = connect(app, &integration).await;
+    metrics::record_integration_health(&integration, result.is_ok());
+    result
+}
+
+async fn connect(app: &AppHandle, integration: &Integration) -> Result<(), String> {
+    use crate::integrations::create_adapter;
+    use crate::integrations::errors::IntegrationError;
+    use crate::integrations::registry::load_credentials;
+    use crate::types::IntegrationType;
+
     // Special handling for Kubernetes (async adapter creation)
     if integration.integration_type == IntegrationType::Kubernetes {
         use crate::integrations::{kubernetes::KubernetesAdapter, IntegrationAdapter};
-        let credentials = load_credentials(&app, &integration)
+        let credentials = load_credentials(app, integration)
             .await
             .map_err(|e| format!("Failed to load credentials: {}", e))?;
 
@@ -212,32 +311,30 @@ pub async fn test_integration_connection(
             .map_err(|e| format!("Failed to create Kubernetes adapter: {}", e))?;
 
         let result: Result<(), IntegrationError> = adapter.test_connection().await;
-        result.map_err(|e| format!("Connection test failed: {}", e))?;
-        log::info!(
-            "Successfully tested connection for integration: {}",
-            integration_id
-        );
-        return Ok(true);
+        return result.map_err(|e| format!("Connection test failed: {}", e));
+    }
+
+    // Demo integrations have no credentials
+    if integration.integration_type == IntegrationType::Demo {
+        use crate::integrations::{demo::DemoAdapter, IntegrationAdapter};
+        return DemoAdapter::new(integration)
+            .test_connection()
+            .await
+            .map_err(|e| format!("Connection test failed: {}", e));
     }
 
     // For other integrations, use the standard adapter creation
-    let credentials = load_credentials(&app, &integration)
+    let credentials = load_credentials(app, integration)
         .await
         .map_err(|e| format!("Failed to load credentials: {}", e))?;
 
-    let adapter = create_adapter(&integration, &credentials)
+    let adapter = create_adapter(integration, &credentials)
         .map_err(|e| format!("Failed to create adapter: {}", e))?;
 
     adapter
         .test_connection()
         .await
-        .map_err(|e| format!("Connection test failed: {}", e))?;
-
-    log::info!(
-        "Successfully tested connection for integration: {}",
-        integration_id
-    );
-    Ok(true)
+        .map_err(|e| format!("Connection test failed: {}", e))
 }
 
 // ============================================================================
@@ -258,15 +355,381 @@ pub async fn load_mappings(app: AppHandle) -> Result<Vec<Mapping>, String> {
 #[tauri::command]
 #[specta::specta]
 pub async fn save_mappings(app: AppHandle, mappings: Vec<Mapping>) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
     log::debug!("Saving {} mappings to disk", mappings.len());
     let config_dir = get_config_dir(&app)?;
     let mappings_path = config_dir.join("mappings.yaml");
-    save_yaml_config(&mappings_path, &mappings)
+    save_yaml_config(&mappings_path, &mappings)?;
+    record_history(&config_dir, ConfigHistoryEntity::Mappings, &mappings);
+    Ok(())
+}
+
+// ============================================================================
+// Config History Commands
+// ============================================================================
+
+/// Lists the recorded snapshots of integrations or mappings, oldest first.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_config_history(
+    app: AppHandle,
+    entity: ConfigHistoryEntity,
+) -> Result<Vec<ConfigSnapshotInfo>, String> {
+    let config_dir = get_config_dir(&app)?;
+    config_history::list_snapshots(&config_dir, entity)
+}
+
+/// Returns the field-level changes to integrations or mappings between two
+/// snapshots, each attributed to the save that made it.
+///
+/// `from` defaults to before the first snapshot and `to` to the latest one.
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_config_history(
+    app: AppHandle,
+    entity: ConfigHistoryEntity,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<ConfigHistoryDiff, String> {
+    log::debug!("Diffing {entity:?} history from {from:?} to {to:?}");
+    let config_dir = get_config_dir(&app)?;
+    config_history::diff_history(&config_dir, entity, from.as_deref(), to.as_deref())
+}
+
+// ============================================================================
+// Smoke Tests Commands
+// ============================================================================
+
+/// Loads all smoke test definitions from disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn load_smoke_tests(app: AppHandle) -> Result<Vec<SmokeTest>, String> {
+    log::debug!("Loading smoke tests from disk");
+    let config_dir = get_config_dir(&app)?;
+    let smoke_tests_path = config_dir.join("smoke_tests.yaml");
+    load_yaml_config(&smoke_tests_path)
+}
+
+/// Saves all smoke test definitions to disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_smoke_tests(app: AppHandle, smoke_tests: Vec<SmokeTest>) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Saving {} smoke tests to disk", smoke_tests.len());
+    let config_dir = get_config_dir(&app)?;
+    let smoke_tests_path = config_dir.join("smoke_tests.yaml");
+    save_yaml_config(&smoke_tests_path, &smoke_tests)
+}
+
+// ============================================================================
+// Uptime Targets Commands
+// ============================================================================
+
+/// Loads all uptime targets from disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn load_uptime_targets(app: AppHandle) -> Result<Vec<UptimeTarget>, String> {
+    log::debug!("Loading uptime targets from disk");
+    let config_dir = get_config_dir(&app)?;
+    let uptime_targets_path = config_dir.join("uptime_targets.yaml");
+    load_yaml_config(&uptime_targets_path)
+}
+
+/// Saves all uptime targets to disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_uptime_targets(
+    app: AppHandle,
+    uptime_targets: Vec<UptimeTarget>,
+) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Saving {} uptime targets to disk", uptime_targets.len());
+    let config_dir = get_config_dir(&app)?;
+    let uptime_targets_path = config_dir.join("uptime_targets.yaml");
+    save_yaml_config(&uptime_targets_path, &uptime_targets)
+}
+
+// ============================================================================
+// Quick Pane Macros Commands
+// ============================================================================
+
+/// Loads all quick pane macros from disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn load_quick_pane_macros(app: AppHandle) -> Result<Vec<QuickPaneMacro>, String> {
+    log::debug!("Loading quick pane macros from disk");
+    let config_dir = get_config_dir(&app)?;
+    let macros_path = config_dir.join("quick_pane_macros.yaml");
+    load_yaml_config(&macros_path)
+}
+
+/// Saves all quick pane macros to disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_quick_pane_macros(
+    app: AppHandle,
+    macros: Vec<QuickPaneMacro>,
+) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Saving {} quick pane macros to disk", macros.len());
+    let config_dir = get_config_dir(&app)?;
+    let macros_path = config_dir.join("quick_pane_macros.yaml");
+    save_yaml_config(&macros_path, &macros)
+}
+
+// ============================================================================
+// Cost Rates Commands
+// ============================================================================
+
+/// Loads all cost rates from disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn load_cost_rates(app: AppHandle) -> Result<Vec<CostRate>, String> {
+    log::debug!("Loading cost rates from disk");
+    let config_dir = get_config_dir(&app)?;
+    let cost_rates_path = config_dir.join("cost_rates.yaml");
+    load_yaml_config(&cost_rates_path)
+}
+
+/// Saves all cost rates to disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_cost_rates(app: AppHandle, cost_rates: Vec<CostRate>) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Saving {} cost rates to disk", cost_rates.len());
+    let config_dir = get_config_dir(&app)?;
+    let cost_rates_path = config_dir.join("cost_rates.yaml");
+    save_yaml_config(&cost_rates_path, &cost_rates)
+}
+
+// ============================================================================
+// Image Scanners Commands
+// ============================================================================
+
+/// Loads all image scanners from disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn load_image_scanners(app: AppHandle) -> Result<Vec<ImageScanner>, String> {
+    log::debug!("Loading image scanners from disk");
+    let config_dir = get_config_dir(&app)?;
+    let image_scanners_path = config_dir.join("image_scanners.yaml");
+    load_yaml_config(&image_scanners_path)
+}
+
+/// Saves all image scanners to disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_image_scanners(
+    app: AppHandle,
+    image_scanners: Vec<ImageScanner>,
+) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Saving {} image scanners to disk", image_scanners.len());
+    let config_dir = get_config_dir(&app)?;
+    let image_scanners_path = config_dir.join("image_scanners.yaml");
+    save_yaml_config(&image_scanners_path, &image_scanners)
+}
+
+// ============================================================================
+// Desired State Commands
+// ============================================================================
+
+/// Loads the desired state of every environment from disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn load_desired_states(app: AppHandle) -> Result<Vec<DesiredState>, String> {
+    log::debug!("Loading desired states from disk");
+    let config_dir = get_config_dir(&app)?;
+    let desired_states_path = config_dir.join("desired_states.yaml");
+    load_yaml_config(&desired_states_path)
+}
+
+/// Saves the desired state of every environment to disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_desired_states(
+    app: AppHandle,
+    desired_states: Vec<DesiredState>,
+) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    for (index, state) in desired_states.iter().enumerate() {
+        if desired_states[..index]
+            .iter()
+            .any(|other| other.environment_id == state.environment_id)
+        {
+            return Err(format!(
+                "Environment {} has more than one desired state",
+                state.environment_id
+            ));
+        }
+    }
+
+    log::debug!("Saving {} desired states to disk", desired_states.len());
+    let config_dir = get_config_dir(&app)?;
+    let desired_states_path = config_dir.join("desired_states.yaml");
+    save_yaml_config(&desired_states_path, &desired_states)
+}
+
+// ============================================================================
+// Tag Watches Commands
+// ============================================================================
+
+/// Loads all tag watches from disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn load_tag_watches(app: AppHandle) -> Result<Vec<TagWatch>, String> {
+    log::debug!("Loading tag watches from disk");
+    let config_dir = get_config_dir(&app)?;
+    let tag_watches_path = config_dir.join("tag_watches.yaml");
+    load_yaml_config(&tag_watches_path)
+}
+
+/// Saves all tag watches to disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_tag_watches(app: AppHandle, tag_watches: Vec<TagWatch>) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Saving {} tag watches to disk", tag_watches.len());
+    let config_dir = get_config_dir(&app)?;
+    let tag_watches_path = config_dir.join("tag_watches.yaml");
+    save_yaml_config(&tag_watches_path, &tag_watches)
+}
+
+// ============================================================================
+// Event Recordings Commands
+// ============================================================================
+
+/// Loads all event recordings from disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn load_event_recordings(app: AppHandle) -> Result<Vec<EventRecording>, String> {
+    log::debug!("Loading event recordings from disk");
+    let config_dir = get_config_dir(&app)?;
+    let recordings_path = config_dir.join("event_recordings.yaml");
+    load_yaml_config(&recordings_path)
+}
+
+/// Saves all event recordings to disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_event_recordings(
+    app: AppHandle,
+    recordings: Vec<EventRecording>,
+) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Saving {} event recordings to disk", recordings.len());
+    let config_dir = get_config_dir(&app)?;
+    let recordings_path = config_dir.join("event_recordings.yaml");
+    save_yaml_config(&recordings_path, &recordings)
+}
+
+// ============================================================================
+// Failure Rules Commands
+// ============================================================================
+
+/// Loads all build failure rules from disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn load_failure_rules(app: AppHandle) -> Result<Vec<FailureRule>, String> {
+    log::debug!("Loading failure rules from disk");
+    let config_dir = get_config_dir(&app)?;
+    let rules_path = config_dir.join("failure_rules.yaml");
+    load_yaml_config(&rules_path)
+}
+
+/// Saves all build failure rules to disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_failure_rules(app: AppHandle, rules: Vec<FailureRule>) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    crate::build_failures::compile_rules(&rules)?;
+
+    log::debug!("Saving {} failure rules to disk", rules.len());
+    let config_dir = get_config_dir(&app)?;
+    let rules_path = config_dir.join("failure_rules.yaml");
+    save_yaml_config(&rules_path, &rules)
+}
+
+/// Returns the built-in failure rules used while none are configured.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_default_failure_rules() -> Result<Vec<FailureRule>, String> {
+    Ok(crate::build_failures::default_rules())
+}
+
+// ============================================================================
+// Report Definitions Commands
+// ============================================================================
+
+/// Loads all report definitions from disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn load_report_definitions(app: AppHandle) -> Result<Vec<ReportDefinition>, String> {
+    log::debug!("Loading report definitions from disk");
+    let config_dir = get_config_dir(&app)?;
+    let reports_path = config_dir.join("report_definitions.yaml");
+    load_yaml_config(&reports_path)
+}
+
+/// Saves all report definitions to disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_report_definitions(
+    app: AppHandle,
+    reports: Vec<ReportDefinition>,
+) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Saving {} report definitions to disk", reports.len());
+    let config_dir = get_config_dir(&app)?;
+    let reports_path = config_dir.join("report_definitions.yaml");
+    save_yaml_config(&reports_path, &reports)
+}
+
+// ============================================================================
+// Saved Filters Commands
+// ============================================================================
+
+/// Loads all saved filters from disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn load_saved_filters(app: AppHandle) -> Result<Vec<SavedFilter>, String> {
+    log::debug!("Loading saved filters from disk");
+    let config_dir = get_config_dir(&app)?;
+    let filters_path = config_dir.join("saved_filters.yaml");
+    load_yaml_config(&filters_path)
+}
+
+/// Saves all saved filters to disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_saved_filters(app: AppHandle, filters: Vec<SavedFilter>) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Saving {} saved filters to disk", filters.len());
+    let config_dir = get_config_dir(&app)?;
+    let filters_path = config_dir.join("saved_filters.yaml");
+    save_yaml_config(&filters_path, &filters)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::types::{Environment, Integration, IntegrationType, Mapping, Project};
+    use std::collections::BTreeMap;
+
+    use crate::types::{
+        CostRate, Environment, Integration, IntegrationType, Mapping, OnCallProvider,
+        OnCallRotation, Ownership, Project, QuickAction, QuickPaneMacro, SmokeTest,
+    };
 
     /// Test that Project can be serialized and deserialized to/from YAML
     #[test]
@@ -276,6 +739,7 @@ mod tests {
             name: "Test Project".to_string(),
             description: Some("A test project".to_string()),
             environments: vec!["env-1".to_string(), "env-2".to_string()],
+            ownership: Ownership::default(),
         };
 
         // Test serialization
@@ -297,12 +761,13 @@ mod tests {
             name: "dev".to_string(),
             namespace: Some("dev-namespace".to_string()),
             project_id: "project-1".to_string(),
+            variables: BTreeMap::from([("domain".to_string(), "dev.example.com".to_string())]),
         };
 
         let yaml = serde_yaml::to_string(&vec![environment.clone()]).unwrap();
         let environments: Vec<Environment> = serde_yaml::from_str(&yaml).unwrap();
         assert_eq!(environments.len(), 1);
-        assert_eq!(environments[0].id, environment.id);
+        assert_eq!(environments[0], environment);
     }
 
     /// Test that Integration can be serialized and deserialized to/from YAML
@@ -314,6 +779,7 @@ mod tests {
             name: "GitLab Main".to_string(),
             base_url: "https://gitlab.com".to_string(),
             credentials_ref: Some("gitlab-main-creds".to_string()),
+            api_version: None,
         };
 
         let yaml = serde_yaml::to_string(&vec![integration.clone()]).unwrap();
@@ -333,11 +799,69 @@ mod tests {
             service_name: Some("my-service".to_string()),
             project_id: Some("project-1".to_string()),
             environment_id: Some("env-1".to_string()),
+            ownership: Ownership {
+                owner_team: Some("payments".to_string()),
+                slack_channel: Some("#payments-alerts".to_string()),
+                on_call: Some(OnCallRotation {
+                    provider: OnCallProvider::PagerDuty,
+                    rotation_id: "PD12345".to_string(),
+                }),
+            },
         };
 
         let yaml = serde_yaml::to_string(&vec![mapping.clone()]).unwrap();
         let mappings: Vec<Mapping> = serde_yaml::from_str(&yaml).unwrap();
         assert_eq!(mappings.len(), 1);
-        assert_eq!(mappings[0].id, mapping.id);
+        assert_eq!(mappings[0], mapping);
+    }
+
+    /// Test that SmokeTest fills in defaults when loaded from YAML
+    #[test]
+    fn test_smoke_test_defaults() {
+        let yaml = "- id: health\n  environment_id: env-1\n  name: Health\n  url: https://staging.example.com/health\n";
+        let smoke_tests: Vec<SmokeTest> = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(smoke_tests[0].expected_status, 200);
+        assert_eq!(smoke_tests[0].timeout_seconds, 10);
+        assert_eq!(smoke_tests[0].retries, 0);
+    }
+
+    #[test]
+    fn test_cost_rate_defaults() {
+        let yaml = "- id: default\n  environment_id: null\n  cpu_hour_price: 0.04\n  memory_gb_hour_price: 0.005\n";
+        let rates: Vec<CostRate> = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(rates[0].currency, "USD");
+        assert_eq!(rates[0].environment_id, None);
+    }
+
+    /// Test that QuickPaneMacro steps are read from tagged YAML actions
+    #[test]
+    fn test_quick_pane_macro_steps() {
+        let yaml = r#"
+- id: release
+  name: Release
+  created_at: "2024-05-01T10:00:00Z"
+  steps:
+    - action: trigger_gitlab_pipeline
+      integrationId: gitlab
+      projectId: 42
+      ref: main
+    - action: notify
+      title: Release started
+"#;
+        let macros: Vec<QuickPaneMacro> = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            macros[0].steps,
+            vec![
+                QuickAction::TriggerGitlabPipeline {
+                    integration_id: "gitlab".to_string(),
+                    project_id: 42,
+                    ref_name: "main".to_string(),
+                },
+                QuickAction::Notify {
+                    title: "Release started".to_string(),
+                    body: None,
+                },
+            ]
+        );
     }
 }
@@ -3,7 +3,8 @@
 //! Credentials are stored securely in the OS keychain and are never
 //! written to config files.
 
-use crate::types::IntegrationCredentials;
+use crate::types::{IntegrationCredentials, IntegrationType};
+use crate::utils::credential_checks::{self, CredentialIssue};
 use keyring::Entry;
 use tauri::AppHandle;
 
@@ -23,6 +24,8 @@ pub async fn save_integration_credentials(
     integration_id: String,
     credentials: IntegrationCredentials,
 ) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
     log::debug!("Saving credentials for integration: {integration_id}");
 
     // Serialize credentials to JSON
@@ -42,6 +45,26 @@ pub async fn save_integration_credentials(
     Ok(())
 }
 
+/// Checks credentials locally before saving (password strength, known
+/// defaults, token format for the integration type).
+///
+/// Returns the issues found; an empty list means nothing looks wrong.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_integration_credentials(
+    integration_type: IntegrationType,
+    credentials: IntegrationCredentials,
+) -> Result<Vec<CredentialIssue>, String> {
+    let issues = credential_checks::check_credentials(&integration_type, &credentials);
+    if !issues.is_empty() {
+        log::debug!(
+            "Credential check found {} issue(s) for {integration_type:?} credentials",
+            issues.len()
+        );
+    }
+    Ok(issues)
+}
+
 /// Gets integration credentials from the OS keyring.
 #[tauri::command]
 #[specta::specta]
@@ -49,6 +72,8 @@ pub async fn get_integration_credentials(
     _app: AppHandle,
     integration_id: String,
 ) -> Result<Option<IntegrationCredentials>, String> {
+    crate::app_lock::ensure_unlocked()?;
+
     log::debug!("Loading credentials for integration: {integration_id}");
 
     let entry = get_keyring_entry(&integration_id)?;
@@ -81,6 +106,8 @@ pub async fn delete_integration_credentials(
     _app: AppHandle,
     integration_id: String,
 ) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
     log::debug!("Deleting credentials for integration: {integration_id}");
 
     let entry = get_keyring_entry(&integration_id)?;
@@ -92,3 +119,41 @@ pub async fn delete_integration_credentials(
     log::info!("Successfully deleted credentials for integration: {integration_id}");
     Ok(())
 }
+
+/// Saves a secret in the OS keyring for `keyring:<name>` references in config.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_secret(name: String, value: String) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Secret name is required".to_string());
+    }
+    log::debug!("Saving secret: {name}");
+    crate::secrets::save_keyring_secret(name, &value)?;
+    log::info!("Successfully saved secret: {name}");
+    Ok(())
+}
+
+/// Deletes a secret saved with `save_secret`.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_secret(name: String) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Deleting secret: {name}");
+    crate::secrets::delete_keyring_secret(name.trim())?;
+    log::info!("Successfully deleted secret: {name}");
+    Ok(())
+}
+
+/// Checks that a secret reference (e.g., "vault:secret/data/ops#token")
+/// resolves, without returning the secret. Plain values always pass.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_secret_reference(reference: String) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    crate::secrets::resolve(&reference).await.map(|_| ())
+}
@@ -0,0 +1,133 @@
+//! Demo integration commands.
+//!
+//! Serve the synthetic data of demo integrations, in the same shapes as the
+//! GitLab, Jenkins and Kubernetes commands.
+
+use tauri::AppHandle;
+
+use crate::integrations::demo::{DemoAdapter, DemoMetricSeries};
+use crate::integrations::gitlab::{GitLabPipeline, GitLabProject};
+use crate::integrations::jenkins::{JenkinsBuild, JenkinsJob};
+use crate::integrations::kubernetes::K8sPod;
+use crate::metrics::CommandTimer;
+use crate::types::IntegrationType;
+
+/// Default metrics window, in minutes.
+const DEFAULT_METRICS_MINUTES: u32 = 60;
+
+/// Creates the adapter of a demo integration.
+async fn demo_adapter(app: &AppHandle, integration_id: &str) -> Result<DemoAdapter, String> {
+    let integrations = crate::commands::config::load_integrations(app.clone()).await?;
+    let integration = integrations
+        .into_iter()
+        .find(|i| i.id == integration_id)
+        .ok_or_else(|| format!("Integration not found: {}", integration_id))?;
+    if integration.integration_type != IntegrationType::Demo {
+        return Err(format!(
+            "Integration {} is not a demo integration",
+            integration.id
+        ));
+    }
+    Ok(DemoAdapter::new(&integration))
+}
+
+/// Fetches the projects of a demo integration.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_demo_projects(
+    app: AppHandle,
+    integration_id: String,
+) -> Result<Vec<GitLabProject>, String> {
+    let _timer = CommandTimer::start("fetch_demo_projects");
+    log::debug!("Fetching demo projects for integration: {}", integration_id);
+
+    Ok(demo_adapter(&app, &integration_id).await?.projects())
+}
+
+/// Fetches the pipelines of a demo project.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_demo_pipelines(
+    app: AppHandle,
+    integration_id: String,
+    project_id: u32,
+) -> Result<Vec<GitLabPipeline>, String> {
+    let _timer = CommandTimer::start("fetch_demo_pipelines");
+    log::debug!(
+        "Fetching demo pipelines for integration: {}, project: {}",
+        integration_id,
+        project_id
+    );
+
+    demo_adapter(&app, &integration_id)
+        .await?
+        .pipelines(project_id)
+        .map_err(|e| format!("Failed to fetch pipelines: {}", e))
+}
+
+/// Fetches the jobs of a demo integration.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_demo_jobs(
+    app: AppHandle,
+    integration_id: String,
+) -> Result<Vec<JenkinsJob>, String> {
+    let _timer = CommandTimer::start("fetch_demo_jobs");
+    log::debug!("Fetching demo jobs for integration: {}", integration_id);
+
+    Ok(demo_adapter(&app, &integration_id).await?.jobs())
+}
+
+/// Fetches the builds of a demo job.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_demo_builds(
+    app: AppHandle,
+    integration_id: String,
+    job_name: String,
+) -> Result<Vec<JenkinsBuild>, String> {
+    let _timer = CommandTimer::start("fetch_demo_builds");
+    log::debug!(
+        "Fetching demo builds for integration: {}, job: {}",
+        integration_id,
+        job_name
+    );
+
+    demo_adapter(&app, &integration_id)
+        .await?
+        .builds(&job_name)
+        .map_err(|e| format!("Failed to fetch builds: {}", e))
+}
+
+/// Fetches the pods of a demo integration, in one namespace or all.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_demo_pods(
+    app: AppHandle,
+    integration_id: String,
+    namespace: Option<String>,
+) -> Result<Vec<K8sPod>, String> {
+    let _timer = CommandTimer::start("fetch_demo_pods");
+    log::debug!("Fetching demo pods for integration: {}", integration_id);
+
+    Ok(demo_adapter(&app, &integration_id)
+        .await?
+        .pods(namespace.as_deref()))
+}
+
+/// Fetches per-minute service metrics of a demo integration over the last
+/// `minutes` (default 60, at most a day).
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_demo_metrics(
+    app: AppHandle,
+    integration_id: String,
+    minutes: Option<u32>,
+) -> Result<Vec<DemoMetricSeries>, String> {
+    let _timer = CommandTimer::start("fetch_demo_metrics");
+    log::debug!("Fetching demo metrics for integration: {}", integration_id);
+
+    Ok(demo_adapter(&app, &integration_id)
+        .await?
+        .metrics(minutes.unwrap_or(DEFAULT_METRICS_MINUTES)))
+}
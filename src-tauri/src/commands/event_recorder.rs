@@ -0,0 +1,33 @@
+//! Kubernetes event recorder commands.
+//!
+//! Queries the events recorded for the namespaces of the event recordings.
+
+use tauri::AppHandle;
+
+use crate::event_recorder::{self, EventStreamStatus};
+use crate::integrations::kubernetes::K8sEvent;
+
+/// Get the recorded events last seen between two times (RFC 3339), in one
+/// namespace or all recorded namespaces of an integration.
+#[tauri::command]
+#[specta::specta]
+pub async fn query_recorded_k8s_events(
+    app: AppHandle,
+    integration_id: String,
+    namespace: Option<String>,
+    from: String,
+    to: String,
+) -> Result<Vec<K8sEvent>, String> {
+    tokio::task::spawn_blocking(move || {
+        event_recorder::query(&app, &integration_id, namespace.as_deref(), &from, &to)
+    })
+    .await
+    .map_err(|e| format!("Event query failed: {e}"))?
+}
+
+/// Get the state of the event stream of every recorded namespace.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_event_recorder_status() -> Result<Vec<EventStreamStatus>, String> {
+    Ok(event_recorder::status())
+}
@@ -7,6 +7,7 @@ use specta::Type;
 use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
 
+use crate::flow_engine::{self, FlowFixtures, FlowRun, FlowRunArtifact, RunOptions};
 use crate::types::validate_string_input;
 
 /// Flow metadata for listing saved flows
@@ -67,12 +68,9 @@ fn get_flow_path(app: &AppHandle, flow_id: &str) -> Result<PathBuf, String> {
     Ok(flows_dir.join(format!("{sanitized_id}.json")))
 }
 
-/// Load list of all saved flows (metadata only).
-#[tauri::command]
-#[specta::specta]
-pub async fn load_flows(app: AppHandle) -> Result<Vec<FlowMetadata>, String> {
-    log::debug!("Loading flows list");
-    let flows_dir = get_flows_dir(&app)?;
+/// Reads every saved flow, skipping files that cannot be read or parsed.
+pub fn read_saved_flows(app: &AppHandle) -> Result<Vec<Flow>, String> {
+    let flows_dir = get_flows_dir(app)?;
 
     if !flows_dir.exists() {
         log::info!("Flows directory does not exist, returning empty list");
@@ -99,14 +97,7 @@ pub async fn load_flows(app: AppHandle) -> Result<Vec<FlowMetadata>, String> {
 
         match std::fs::read_to_string(&path) {
             Ok(contents) => match serde_json::from_str::<Flow>(&contents) {
-                Ok(flow) => {
-                    flows.push(FlowMetadata {
-                        id: flow.id,
-                        name: flow.name,
-                        created_at: flow.created_at,
-                        updated_at: flow.updated_at,
-                    });
-                }
+                Ok(flow) => flows.push(flow),
                 Err(e) => {
                     log::warn!("Failed to parse flow file {:?}: {e}", path);
                 }
@@ -117,6 +108,25 @@ pub async fn load_flows(app: AppHandle) -> Result<Vec<FlowMetadata>, String> {
         }
     }
 
+    Ok(flows)
+}
+
+/// Load list of all saved flows (metadata only).
+#[tauri::command]
+#[specta::specta]
+pub async fn load_flows(app: AppHandle) -> Result<Vec<FlowMetadata>, String> {
+    log::debug!("Loading flows list");
+
+    let mut flows: Vec<FlowMetadata> = read_saved_flows(&app)?
+        .into_iter()
+        .map(|flow| FlowMetadata {
+            id: flow.id,
+            name: flow.name,
+            created_at: flow.created_at,
+            updated_at: flow.updated_at,
+        })
+        .collect();
+
     // Sort by updated_at descending (most recent first)
     flows.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
 
@@ -154,6 +164,8 @@ pub async fn load_flow(app: AppHandle, flow_id: String) -> Result<Flow, String>
 #[tauri::command]
 #[specta::specta]
 pub async fn save_flow(app: AppHandle, flow: Flow) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
     validate_string_input(&flow.id, 100, "Flow ID").map_err(|e| {
         log::warn!("Invalid flow ID: {e}");
         e
@@ -197,6 +209,8 @@ pub async fn save_flow(app: AppHandle, flow: Flow) -> Result<(), String> {
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_flow(app: AppHandle, flow_id: String) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
     log::debug!("Deleting flow: {flow_id}");
     let flow_path = get_flow_path(&app, &flow_id)?;
 
@@ -212,3 +226,154 @@ pub async fn delete_flow(app: AppHandle, flow_id: String) -> Result<(), String>
     log::info!("Successfully deleted flow: {flow_id}");
     Ok(())
 }
+
+// ============================================================================
+// Flow Run Commands
+// ============================================================================
+
+/// Start executing a saved flow.
+/// Returns the initial run state; progress is emitted as `flow-run-updated` events.
+#[tauri::command]
+#[specta::specta]
+pub async fn run_flow(app: AppHandle, flow_id: String) -> Result<FlowRun, String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Starting run for flow: {flow_id}");
+    let flow = load_flow(app.clone(), flow_id).await?;
+    flow_engine::start_run(&app, &flow, RunOptions::default())
+}
+
+/// Start executing a saved flow in an environment.
+///
+/// The environment's variables are available to the flow's templates as
+/// `{{ env.<key> }}`, along with `{{ env.name }}` and `{{ env.namespace }}`,
+/// and are passed on to its sub-flows.
+#[tauri::command]
+#[specta::specta]
+pub async fn run_flow_in_environment(
+    app: AppHandle,
+    flow_id: String,
+    environment_id: String,
+) -> Result<FlowRun, String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Starting run for flow: {flow_id} in environment: {environment_id}");
+    let environment = crate::commands::config::load_environments(app.clone())
+        .await?
+        .into_iter()
+        .find(|e| e.id == environment_id)
+        .ok_or_else(|| format!("Environment not found: {environment_id}"))?;
+    let flow = load_flow(app.clone(), flow_id).await?;
+
+    let options = RunOptions {
+        environment: Some(environment),
+        ..RunOptions::default()
+    };
+    flow_engine::start_run(&app, &flow, options)
+}
+
+/// Run a saved flow in simulation mode.
+///
+/// Integration nodes and gates resolve without touching real services and
+/// nodes with a fixture report its recorded outcome. When `fixtures` is not
+/// provided, the flow's recorded fixtures are loaded from disk.
+#[tauri::command]
+#[specta::specta]
+pub async fn simulate_flow(
+    app: AppHandle,
+    flow_id: String,
+    fixtures: Option<FlowFixtures>,
+) -> Result<FlowRun, String> {
+    log::debug!("Starting simulated run for flow: {flow_id}");
+    let flow = load_flow(app.clone(), flow_id.clone()).await?;
+    let fixtures = match fixtures {
+        Some(fixtures) => fixtures,
+        None => flow_engine::load_fixtures(&app, &flow_id)?,
+    };
+
+    let options = RunOptions {
+        triggered_by: Some("simulation".to_string()),
+        simulation: Some(fixtures),
+        ..RunOptions::default()
+    };
+    flow_engine::start_run(&app, &flow, options)
+}
+
+/// Get the current state of a flow run.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_flow_run(run_id: String) -> Result<FlowRun, String> {
+    flow_engine::get_run(&run_id).ok_or_else(|| format!("Flow run not found: {run_id}"))
+}
+
+/// List flow runs started since the app launched (most recent first).
+#[tauri::command]
+#[specta::specta]
+pub async fn list_flow_runs() -> Result<Vec<FlowRun>, String> {
+    Ok(flow_engine::list_runs())
+}
+
+/// Cancel a running flow.
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_flow_run(run_id: String) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Cancelling flow run: {run_id}");
+    flow_engine::cancel_run(&run_id)
+}
+
+/// Provides the value requested by an input node of a running flow.
+#[tauri::command]
+#[specta::specta]
+pub async fn provide_flow_input(
+    app: AppHandle,
+    run_id: String,
+    node_id: String,
+    value: String,
+) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!("Providing input for node {node_id} in flow run {run_id}");
+    flow_engine::provide_input(&app, &run_id, &node_id, &value)
+}
+
+/// List the artifacts attached to a flow run.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_flow_run_artifacts(
+    app: AppHandle,
+    run_id: String,
+) -> Result<Vec<FlowRunArtifact>, String> {
+    log::debug!("Listing artifacts for flow run {run_id}");
+    flow_engine::list_run_artifacts(&app, &run_id)
+}
+
+/// Open a flow run artifact. Text artifacts (.txt, .md, .json, .log, .diff)
+/// open with the system's default application; other files are revealed in
+/// their folder rather than run.
+#[tauri::command]
+#[specta::specta]
+pub async fn open_flow_run_artifact(
+    app: AppHandle,
+    run_id: String,
+    node_id: String,
+    name: String,
+) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    crate::app_lock::ensure_unlocked()?;
+
+    let path = flow_engine::run_artifact_path(&app, &run_id, &node_id, &name)?;
+    let result = if flow_engine::is_inert_artifact(&name) {
+        log::info!("Opening flow run artifact {path:?}");
+        app.opener().open_path(path.to_string_lossy(), None::<&str>)
+    } else {
+        log::info!("Revealing flow run artifact {path:?}");
+        app.opener().reveal_item_in_dir(&path)
+    };
+    result.map_err(|e| {
+        log::error!("Failed to open artifact {path:?}: {e}");
+        format!("Failed to open artifact: {e}")
+    })
+}
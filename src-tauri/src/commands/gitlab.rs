@@ -2,8 +2,15 @@
 //!
 //! Provides Tauri commands for interacting with GitLab API through the adapter.
 
-use crate::integrations::gitlab::{GitLabAdapter, GitLabPipeline, GitLabProject, GitLabWebhook};
+use crate::integrations::compat::pinned_version;
+use crate::integrations::gitlab::{
+    GitLabAdapter, GitLabApi, GitLabCiLintResult, GitLabEpicList, GitLabFile, GitLabFreezePeriod,
+    GitLabIssueSummary, GitLabMilestone, GitLabPackage, GitLabPipeline, GitLabProject,
+    GitLabProjectOverview, GitLabRunbook, GitLabRunbookContent, GitLabWebhook,
+};
 use crate::integrations::registry::load_credentials;
+use crate::leak_scan::{self, SecretScanReport};
+use crate::metrics::CommandTimer;
 use crate::types::Integration;
 use tauri::AppHandle;
 
@@ -36,7 +43,23 @@ async fn create_gitlab_adapter(
         .token
         .ok_or_else(|| "GitLab integration requires a token".to_string())?;
 
-    Ok(GitLabAdapter::new(integration.base_url.clone(), token))
+    let adapter = GitLabAdapter::new(integration.base_url.clone(), token);
+    let api_version = match pinned_version(integration)? {
+        Some(version) => Some(version),
+        None => adapter.detect_api_version().await,
+    };
+    Ok(adapter.with_api_version(api_version))
+}
+
+/// Creates a GitLab adapter for an integration ID.
+///
+/// Used by flow nodes that act on GitLab.
+pub(crate) async fn gitlab_adapter(
+    app: &AppHandle,
+    integration_id: &str,
+) -> Result<GitLabAdapter, String> {
+    let integration = get_integration(app, integration_id).await?;
+    create_gitlab_adapter(app, &integration).await
 }
 
 /// Fetches GitLab projects for a given integration.
@@ -46,6 +69,7 @@ pub async fn fetch_gitlab_projects(
     app: AppHandle,
     integration_id: String,
 ) -> Result<Vec<GitLabProject>, String> {
+    let _timer = CommandTimer::start("fetch_gitlab_projects");
     log::debug!(
         "Fetching GitLab projects for integration: {}",
         integration_id
@@ -68,6 +92,7 @@ pub async fn fetch_gitlab_pipelines(
     integration_id: String,
     project_id: u32,
 ) -> Result<Vec<GitLabPipeline>, String> {
+    let _timer = CommandTimer::start("fetch_gitlab_pipelines");
     log::debug!(
         "Fetching GitLab pipelines for integration: {}, project: {}",
         integration_id,
@@ -83,6 +108,37 @@ pub async fn fetch_gitlab_pipelines(
         .map_err(|e| format!("Failed to fetch pipelines: {}", e))
 }
 
+/// Fetches a GitLab project with its latest pipelines and open merge
+/// requests.
+///
+/// `api` selects GraphQL (default, one request) or REST (three requests, for
+/// instances with GraphQL disabled).
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_gitlab_project_overview(
+    app: AppHandle,
+    integration_id: String,
+    project_id: u32,
+    api: Option<GitLabApi>,
+) -> Result<GitLabProjectOverview, String> {
+    let _timer = CommandTimer::start("fetch_gitlab_project_overview");
+    let api = api.unwrap_or_default();
+    log::debug!(
+        "Fetching GitLab project overview for integration: {}, project: {}, api: {:?}",
+        integration_id,
+        project_id,
+        api
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_gitlab_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_project_overview(project_id, api)
+        .await
+        .map_err(|e| format!("Failed to fetch project overview: {}", e))
+}
+
 /// Fetches GitLab webhooks for a given project.
 #[tauri::command]
 #[specta::specta]
@@ -91,6 +147,7 @@ pub async fn fetch_gitlab_webhooks(
     integration_id: String,
     project_id: u32,
 ) -> Result<Vec<GitLabWebhook>, String> {
+    let _timer = CommandTimer::start("fetch_gitlab_webhooks");
     log::debug!(
         "Fetching GitLab webhooks for integration: {}, project: {}",
         integration_id,
@@ -106,6 +163,273 @@ pub async fn fetch_gitlab_webhooks(
         .map_err(|e| format!("Failed to fetch webhooks: {}", e))
 }
 
+/// Fetches GitLab issues for a given project, aggregated per milestone and label.
+///
+/// `state` is "opened", "closed" or "all" (default); `labels` keeps only issues
+/// having all of the given labels.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_gitlab_issues(
+    app: AppHandle,
+    integration_id: String,
+    project_id: u32,
+    state: Option<String>,
+    labels: Option<Vec<String>>,
+) -> Result<GitLabIssueSummary, String> {
+    let _timer = CommandTimer::start("fetch_gitlab_issues");
+    log::debug!(
+        "Fetching GitLab issues for integration: {}, project: {}",
+        integration_id,
+        project_id
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_gitlab_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_issues(project_id, state.as_deref(), &labels.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Failed to fetch issues: {}", e))
+}
+
+/// Fetches a project's milestones with issue counts and a daily burn-down.
+///
+/// `state` is "active" or "closed"; all milestones are returned when omitted.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_gitlab_milestones(
+    app: AppHandle,
+    integration_id: String,
+    project_id: u32,
+    state: Option<String>,
+) -> Result<Vec<GitLabMilestone>, String> {
+    let _timer = CommandTimer::start("fetch_gitlab_milestones");
+    log::debug!(
+        "Fetching GitLab milestones for integration: {}, project: {}",
+        integration_id,
+        project_id
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_gitlab_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_milestones(project_id, state.as_deref())
+        .await
+        .map_err(|e| format!("Failed to fetch milestones: {}", e))
+}
+
+/// Fetches a project's deploy freeze periods and whether each is in effect.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_gitlab_freeze_periods(
+    app: AppHandle,
+    integration_id: String,
+    project_id: u32,
+) -> Result<Vec<GitLabFreezePeriod>, String> {
+    let _timer = CommandTimer::start("fetch_gitlab_freeze_periods");
+    log::debug!(
+        "Fetching GitLab freeze periods for integration: {}, project: {}",
+        integration_id,
+        project_id
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_gitlab_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_freeze_periods(project_id)
+        .await
+        .map_err(|e| format!("Failed to fetch freeze periods: {}", e))
+}
+
+/// Fetches a project's Maven, npm, PyPI and generic packages with their
+/// versions, newest first.
+///
+/// `package_type` narrows the list to one of those formats.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_gitlab_packages(
+    app: AppHandle,
+    integration_id: String,
+    project_id: u32,
+    package_type: Option<String>,
+) -> Result<Vec<GitLabPackage>, String> {
+    let _timer = CommandTimer::start("fetch_gitlab_packages");
+    log::debug!(
+        "Fetching GitLab packages for integration: {}, project: {}",
+        integration_id,
+        project_id
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_gitlab_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_packages(project_id, package_type.as_deref())
+        .await
+        .map_err(|e| format!("Failed to fetch packages: {}", e))
+}
+
+/// Fetches a group's epics with issue counts. Groups without epics (a GitLab
+/// Premium feature) return an empty, unlicensed list.
+///
+/// `group` is the group ID or full path; `state` is "opened", "closed" or
+/// "all" (default).
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_gitlab_epics(
+    app: AppHandle,
+    integration_id: String,
+    group: String,
+    state: Option<String>,
+) -> Result<GitLabEpicList, String> {
+    let _timer = CommandTimer::start("fetch_gitlab_epics");
+    log::debug!(
+        "Fetching GitLab epics for integration: {}, group: {}",
+        integration_id,
+        group
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_gitlab_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_epics(&group, state.as_deref())
+        .await
+        .map_err(|e| format!("Failed to fetch epics: {}", e))
+}
+
+/// Reads a text file from a project's repository (e.g., `.gitlab-ci.yml`).
+///
+/// `ref_name` is a branch, tag or commit; the default branch is used when omitted.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_gitlab_file(
+    app: AppHandle,
+    integration_id: String,
+    project_id: u32,
+    path: String,
+    ref_name: Option<String>,
+) -> Result<GitLabFile, String> {
+    let _timer = CommandTimer::start("fetch_gitlab_file");
+    log::debug!(
+        "Fetching GitLab file {} of project {} for integration: {}",
+        path,
+        project_id,
+        integration_id
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_gitlab_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_file(project_id, &path, ref_name.as_deref())
+        .await
+        .map_err(|e| format!("Failed to fetch file: {}", e))
+}
+
+/// Scans the pipeline definition and CI variable names of projects for
+/// likely leaked secrets. Findings are redacted.
+#[tauri::command]
+#[specta::specta]
+pub async fn scan_gitlab_secrets(
+    app: AppHandle,
+    integration_id: String,
+    project_ids: Vec<u32>,
+) -> Result<SecretScanReport, String> {
+    let _timer = CommandTimer::start("scan_gitlab_secrets");
+    log::debug!(
+        "Scanning GitLab projects for secrets for integration: {}, projects: {:?}",
+        integration_id,
+        project_ids
+    );
+
+    leak_scan::scan_gitlab(&app, &integration_id, &project_ids).await
+}
+
+/// Lints CI configuration in the context of a project.
+///
+/// Lints `content` when given (e.g., configuration edited in the app),
+/// otherwise the project's current configuration at `ref_name`.
+#[tauri::command]
+#[specta::specta]
+pub async fn lint_gitlab_ci(
+    app: AppHandle,
+    integration_id: String,
+    project_id: u32,
+    content: Option<String>,
+    ref_name: Option<String>,
+) -> Result<GitLabCiLintResult, String> {
+    let _timer = CommandTimer::start("lint_gitlab_ci");
+    log::debug!(
+        "Linting GitLab CI configuration of project {} for integration: {}",
+        project_id,
+        integration_id
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_gitlab_adapter(&app, &integration).await?;
+
+    adapter
+        .lint_ci(project_id, content.as_deref(), ref_name.as_deref())
+        .await
+        .map_err(|e| format!("Failed to lint CI configuration: {}", e))
+}
+
+/// Lists snippets tagged as runbooks (`#runbook`) in a project, or the user's
+/// personal snippets when `project_id` is not provided.
+///
+/// `service` keeps only runbooks tagged `service:<service>`.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_gitlab_runbooks(
+    app: AppHandle,
+    integration_id: String,
+    project_id: Option<u32>,
+    service: Option<String>,
+) -> Result<Vec<GitLabRunbook>, String> {
+    let _timer = CommandTimer::start("fetch_gitlab_runbooks");
+    log::debug!(
+        "Fetching GitLab runbooks for integration: {}, project: {:?}",
+        integration_id,
+        project_id
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_gitlab_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_runbooks(project_id, service.as_deref())
+        .await
+        .map_err(|e| format!("Failed to fetch runbooks: {}", e))
+}
+
+/// Fetches the content of a runbook snippet.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_gitlab_runbook_content(
+    app: AppHandle,
+    integration_id: String,
+    project_id: Option<u32>,
+    snippet_id: u32,
+) -> Result<GitLabRunbookContent, String> {
+    let _timer = CommandTimer::start("fetch_gitlab_runbook_content");
+    log::debug!(
+        "Fetching GitLab runbook {} for integration: {}",
+        snippet_id,
+        integration_id
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_gitlab_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_runbook_content(project_id, snippet_id)
+        .await
+        .map_err(|e| format!("Failed to fetch runbook: {}", e))
+}
+
 /// Triggers a GitLab pipeline for a given project.
 #[tauri::command]
 #[specta::specta]
@@ -115,6 +439,9 @@ pub async fn trigger_gitlab_pipeline(
     project_id: u32,
     r#ref: String,
 ) -> Result<GitLabPipeline, String> {
+    let _timer = CommandTimer::start("trigger_gitlab_pipeline");
+    crate::app_lock::ensure_unlocked()?;
+
     log::debug!(
         "Triggering GitLab pipeline for integration: {}, project: {}, ref: {}",
         integration_id,
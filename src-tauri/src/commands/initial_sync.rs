@@ -0,0 +1,30 @@
+//! Initial sync commands.
+//!
+//! Reports and drives the initial crawl of large integrations.
+
+use tauri::AppHandle;
+
+use crate::initial_sync::{self, InitialSyncStatus};
+use crate::metrics::CommandTimer;
+
+/// Crawl one time-boxed slice of an integration's initial sync now (e.g.,
+/// right after it is configured); the rest continues in the background.
+#[tauri::command]
+#[specta::specta]
+pub async fn start_initial_sync(
+    app: AppHandle,
+    integration_id: String,
+) -> Result<InitialSyncStatus, String> {
+    let _timer = CommandTimer::start("start_initial_sync");
+    log::debug!("Starting initial sync of {}", integration_id);
+    crate::app_lock::ensure_unlocked()?;
+
+    initial_sync::sync_now(&app, &integration_id).await
+}
+
+/// Get the initial sync progress of every GitLab integration that started one.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_initial_sync_status(app: AppHandle) -> Result<Vec<InitialSyncStatus>, String> {
+    initial_sync::list(&app).await
+}
@@ -2,8 +2,14 @@
 //!
 //! Provides Tauri commands for interacting with Jenkins API through the adapter.
 
-use crate::integrations::jenkins::{JenkinsAdapter, JenkinsBuild, JenkinsJob};
+use crate::build_failures::{self, FailureClassification, FailureTrends};
+use crate::integrations::jenkins::{
+    JenkinsAdapter, JenkinsBuild, JenkinsCredentialMetadata, JenkinsJob, JenkinsJobConfigSummary,
+    JenkinsParameterDefinition, JenkinsRetentionInfo,
+};
 use crate::integrations::registry::load_credentials;
+use crate::leak_scan::{self, SecretScanReport};
+use crate::metrics::CommandTimer;
 use crate::types::Integration;
 use std::collections::HashMap;
 use tauri::AppHandle;
@@ -50,6 +56,17 @@ async fn create_jenkins_adapter(
     ))
 }
 
+/// Creates a Jenkins adapter for an integration ID.
+///
+/// Used by flow nodes that act on Jenkins.
+pub(crate) async fn jenkins_adapter(
+    app: &AppHandle,
+    integration_id: &str,
+) -> Result<JenkinsAdapter, String> {
+    let integration = get_integration(app, integration_id).await?;
+    create_jenkins_adapter(app, &integration).await
+}
+
 /// Fetches Jenkins jobs for a given integration.
 #[tauri::command]
 #[specta::specta]
@@ -57,6 +74,7 @@ pub async fn fetch_jenkins_jobs(
     app: AppHandle,
     integration_id: String,
 ) -> Result<Vec<JenkinsJob>, String> {
+    let _timer = CommandTimer::start("fetch_jenkins_jobs");
     log::debug!("Fetching Jenkins jobs for integration: {}", integration_id);
 
     let integration = get_integration(&app, &integration_id).await?;
@@ -76,6 +94,7 @@ pub async fn fetch_jenkins_builds(
     integration_id: String,
     job_name: String,
 ) -> Result<Vec<JenkinsBuild>, String> {
+    let _timer = CommandTimer::start("fetch_jenkins_builds");
     log::debug!(
         "Fetching Jenkins builds for integration: {}, job: {}",
         integration_id,
@@ -100,6 +119,7 @@ pub async fn fetch_jenkins_build_details(
     job_name: String,
     build_number: u32,
 ) -> Result<JenkinsBuild, String> {
+    let _timer = CommandTimer::start("fetch_jenkins_build_details");
     log::debug!(
         "Fetching Jenkins build details for integration: {}, job: {}, build: {}",
         integration_id,
@@ -116,7 +136,114 @@ pub async fn fetch_jenkins_build_details(
         .map_err(|e| format!("Failed to fetch build details: {}", e))
 }
 
+/// Fetches credential metadata (IDs, types, store and domain; never secrets)
+/// from the system credential store, or from a folder's store.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_jenkins_credentials_metadata(
+    app: AppHandle,
+    integration_id: String,
+    folder: Option<String>,
+) -> Result<Vec<JenkinsCredentialMetadata>, String> {
+    let _timer = CommandTimer::start("fetch_jenkins_credentials_metadata");
+    log::debug!(
+        "Fetching Jenkins credentials metadata for integration: {}",
+        integration_id
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_jenkins_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_credentials_metadata(folder.as_deref())
+        .await
+        .map_err(|e| format!("Failed to fetch credentials metadata: {}", e))
+}
+
+/// Fetches the parameter definitions of a Jenkins job, including node and
+/// label parameters with the agents they can target.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_jenkins_job_parameters(
+    app: AppHandle,
+    integration_id: String,
+    job_name: String,
+) -> Result<Vec<JenkinsParameterDefinition>, String> {
+    let _timer = CommandTimer::start("fetch_jenkins_job_parameters");
+    log::debug!(
+        "Fetching Jenkins job parameters for integration: {}, job: {}",
+        integration_id,
+        job_name
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_jenkins_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_parameter_definitions(&job_name)
+        .await
+        .map_err(|e| format!("Failed to fetch job parameters: {}", e))
+}
+
+/// Fetches a summary of a Jenkins job's configuration: SCM URLs, branches,
+/// triggers, parameters, the assigned node and referenced credentials.
+///
+/// Reading `config.xml` requires the Job/ExtendedRead or Configure permission.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_jenkins_job_config_summary(
+    app: AppHandle,
+    integration_id: String,
+    job_name: String,
+) -> Result<JenkinsJobConfigSummary, String> {
+    let _timer = CommandTimer::start("fetch_jenkins_job_config_summary");
+    log::debug!(
+        "Fetching Jenkins job config summary for integration: {}, job: {}",
+        integration_id,
+        job_name
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_jenkins_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_job_config_summary(&job_name)
+        .await
+        .map_err(|e| format!("Failed to fetch job configuration: {}", e))
+}
+
+/// Fetches the build retention of a Jenkins job: its build discarder, the
+/// builds the controller stores and, with the Disk Usage plugin, the disk
+/// they use. Warnings flag jobs storing more than their discarder keeps.
+///
+/// Without permission to read `config.xml`, the discarder is left out and a
+/// warning says so.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_jenkins_retention_info(
+    app: AppHandle,
+    integration_id: String,
+    job_name: String,
+) -> Result<JenkinsRetentionInfo, String> {
+    let _timer = CommandTimer::start("fetch_jenkins_retention_info");
+    log::debug!(
+        "Fetching Jenkins retention info for integration: {}, job: {}",
+        integration_id,
+        job_name
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_jenkins_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_retention_info(&job_name)
+        .await
+        .map_err(|e| format!("Failed to fetch build retention: {}", e))
+}
+
 /// Triggers a Jenkins build for a given job.
+///
+/// Node parameter values may list several agents separated by commas.
 #[tauri::command]
 #[specta::specta]
 pub async fn trigger_jenkins_build(
@@ -125,6 +252,9 @@ pub async fn trigger_jenkins_build(
     job_name: String,
     parameters: Option<HashMap<String, String>>,
 ) -> Result<(), String> {
+    let _timer = CommandTimer::start("trigger_jenkins_build");
+    crate::app_lock::ensure_unlocked()?;
+
     log::debug!(
         "Triggering Jenkins build for integration: {}, job: {}",
         integration_id,
@@ -139,3 +269,153 @@ pub async fn trigger_jenkins_build(
         .await
         .map_err(|e| format!("Failed to trigger build: {}", e))
 }
+
+/// Triggers a Jenkins job again with the exact parameters of one of its
+/// builds. Returns the parameters used.
+#[tauri::command]
+#[specta::specta]
+pub async fn rebuild_jenkins_build(
+    app: AppHandle,
+    integration_id: String,
+    job_name: String,
+    build_number: u32,
+) -> Result<HashMap<String, String>, String> {
+    let _timer = CommandTimer::start("rebuild_jenkins_build");
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!(
+        "Rebuilding Jenkins build for integration: {}, job: {}, build: {}",
+        integration_id,
+        job_name,
+        build_number
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_jenkins_adapter(&app, &integration).await?;
+
+    adapter
+        .rebuild_build(&job_name, build_number)
+        .await
+        .map_err(|e| format!("Failed to rebuild build: {}", e))
+}
+
+/// Replays a Jenkins Pipeline build, with its original script or with
+/// `script` as its main script.
+#[tauri::command]
+#[specta::specta]
+pub async fn replay_jenkins_pipeline(
+    app: AppHandle,
+    integration_id: String,
+    job_name: String,
+    build_number: u32,
+    script: Option<String>,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("replay_jenkins_pipeline");
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!(
+        "Replaying Jenkins pipeline for integration: {}, job: {}, build: {}",
+        integration_id,
+        job_name,
+        build_number
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_jenkins_adapter(&app, &integration).await?;
+
+    adapter
+        .replay_pipeline(&job_name, build_number, script.as_deref())
+        .await
+        .map_err(|e| format!("Failed to replay pipeline: {}", e))
+}
+
+/// Enables or disables a Jenkins job (e.g., to pause a broken job while it is fixed).
+#[tauri::command]
+#[specta::specta]
+pub async fn set_jenkins_job_enabled(
+    app: AppHandle,
+    integration_id: String,
+    job_name: String,
+    enabled: bool,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("set_jenkins_job_enabled");
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!(
+        "Setting Jenkins job enabled={} for integration: {}, job: {}",
+        enabled,
+        integration_id,
+        job_name
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_jenkins_adapter(&app, &integration).await?;
+
+    adapter
+        .set_job_enabled(&job_name, enabled)
+        .await
+        .map_err(|e| format!("Failed to update job: {}", e))
+}
+
+/// Classifies a failed Jenkins build by matching its console log against the
+/// failure rules, and stores the classification for trend reporting.
+#[tauri::command]
+#[specta::specta]
+pub async fn classify_jenkins_build_failure(
+    app: AppHandle,
+    integration_id: String,
+    job_name: String,
+    build_number: u32,
+) -> Result<FailureClassification, String> {
+    let _timer = CommandTimer::start("classify_jenkins_build_failure");
+    log::debug!(
+        "Classifying Jenkins build failure for integration: {}, job: {}, build: {}",
+        integration_id,
+        job_name,
+        build_number
+    );
+
+    build_failures::classify_build(&app, &integration_id, &job_name, build_number).await
+}
+
+/// Lists the stored failure classifications of an integration, newest build
+/// first, optionally for one job.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_jenkins_failure_classifications(
+    app: AppHandle,
+    integration_id: String,
+    job_name: Option<String>,
+) -> Result<Vec<FailureClassification>, String> {
+    build_failures::list(&app, &integration_id, job_name.as_deref())
+}
+
+/// Counts classified failures per category and day over the last `days`
+/// days, for one integration or all of them.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_jenkins_failure_trends(
+    app: AppHandle,
+    integration_id: Option<String>,
+    days: u32,
+) -> Result<FailureTrends, String> {
+    build_failures::trends(&app, integration_id.as_deref(), days)
+}
+
+/// Scans job configurations for likely leaked secrets, every top-level job
+/// when `job_names` is omitted. Findings are redacted.
+#[tauri::command]
+#[specta::specta]
+pub async fn scan_jenkins_secrets(
+    app: AppHandle,
+    integration_id: String,
+    job_names: Option<Vec<String>>,
+) -> Result<SecretScanReport, String> {
+    let _timer = CommandTimer::start("scan_jenkins_secrets");
+    log::debug!(
+        "Scanning Jenkins job configs for secrets for integration: {}",
+        integration_id
+    );
+
+    leak_scan::scan_jenkins(&app, &integration_id, job_names).await
+}
@@ -2,9 +2,17 @@
 //!
 //! Provides Tauri commands for interacting with Keycloak API through the adapter.
 
-use crate::integrations::keycloak::{KeycloakAdapter, KeycloakClient, KeycloakRealm};
+use crate::integrations::compat::pinned_version;
+use crate::integrations::keycloak::{
+    export_users, parse_users, KeycloakActionsEmail, KeycloakAdapter, KeycloakClient,
+    KeycloakRealm, KeycloakRealmKeys, KeycloakRequiredAction, KeycloakUserFileFormat,
+    KeycloakUserImportResult, DEFAULT_ACTIONS_LIFESPAN_HOURS, DEFAULT_MAX_KEY_AGE_DAYS,
+};
 use crate::integrations::registry::load_credentials;
+use crate::metrics::CommandTimer;
 use crate::types::Integration;
+use std::collections::BTreeMap;
+use std::path::Path;
 use tauri::AppHandle;
 
 /// Helper function to get an integration by ID.
@@ -42,11 +50,10 @@ async fn create_keycloak_adapter(
         .or(credentials.token)
         .ok_or_else(|| "Keycloak integration requires a password or token".to_string())?;
 
-    Ok(KeycloakAdapter::new(
-        integration.base_url.clone(),
-        username,
-        password,
-    ))
+    Ok(
+        KeycloakAdapter::new(integration.base_url.clone(), username, password)
+            .with_api_version(pinned_version(integration)?),
+    )
 }
 
 /// Fetches Keycloak realms for a given integration.
@@ -56,6 +63,7 @@ pub async fn fetch_keycloak_realms(
     app: AppHandle,
     integration_id: String,
 ) -> Result<Vec<KeycloakRealm>, String> {
+    let _timer = CommandTimer::start("fetch_keycloak_realms");
     log::debug!(
         "Fetching Keycloak realms for integration: {}",
         integration_id
@@ -78,6 +86,7 @@ pub async fn fetch_keycloak_clients(
     integration_id: String,
     realm: String,
 ) -> Result<Vec<KeycloakClient>, String> {
+    let _timer = CommandTimer::start("fetch_keycloak_clients");
     log::debug!(
         "Fetching Keycloak clients for integration: {}, realm: {}",
         integration_id,
@@ -92,3 +101,157 @@ pub async fn fetch_keycloak_clients(
         .await
         .map_err(|e| format!("Failed to fetch clients: {}", e))
 }
+
+/// Fetches a realm's keys with their providers, algorithms and ages.
+///
+/// Active keys older than `max_key_age_days` (default 365) are reported as
+/// overdue for rotation.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_keycloak_realm_keys(
+    app: AppHandle,
+    integration_id: String,
+    realm: String,
+    max_key_age_days: Option<u32>,
+) -> Result<KeycloakRealmKeys, String> {
+    let _timer = CommandTimer::start("fetch_keycloak_realm_keys");
+    log::debug!(
+        "Fetching Keycloak realm keys for integration: {}, realm: {}",
+        integration_id,
+        realm
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_keycloak_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_realm_keys(&realm, max_key_age_days.unwrap_or(DEFAULT_MAX_KEY_AGE_DAYS))
+        .await
+        .map_err(|e| format!("Failed to fetch realm keys: {}", e))
+}
+
+/// Exports the users of a realm (with their attributes) as JSON or CSV.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_keycloak_users(
+    app: AppHandle,
+    integration_id: String,
+    realm: String,
+    format: KeycloakUserFileFormat,
+) -> Result<String, String> {
+    let _timer = CommandTimer::start("export_keycloak_users");
+    log::debug!(
+        "Exporting Keycloak users for integration: {}, realm: {}",
+        integration_id,
+        realm
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_keycloak_adapter(&app, &integration).await?;
+
+    let users = adapter
+        .fetch_users(&realm)
+        .await
+        .map_err(|e| format!("Failed to fetch users: {}", e))?;
+    export_users(&users, format)
+}
+
+/// Imports users into a realm from a JSON or CSV file (by its extension),
+/// skipping users the realm already has.
+///
+/// `attribute_mapping` renames source fields to user fields or attributes
+/// (an empty name drops the field). A dry run reports which users would be
+/// created without creating them.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_keycloak_users(
+    app: AppHandle,
+    integration_id: String,
+    realm: String,
+    file: String,
+    attribute_mapping: Option<BTreeMap<String, String>>,
+    dry_run: bool,
+) -> Result<KeycloakUserImportResult, String> {
+    let _timer = CommandTimer::start("import_keycloak_users");
+    if !dry_run {
+        crate::app_lock::ensure_unlocked()?;
+    }
+    log::debug!(
+        "Importing Keycloak users for integration: {}, realm: {}, file: {}",
+        integration_id,
+        realm,
+        file
+    );
+
+    let format = match Path::new(&file).extension().and_then(|e| e.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("csv") => KeycloakUserFileFormat::Csv,
+        _ => KeycloakUserFileFormat::Json,
+    };
+    let contents =
+        std::fs::read_to_string(&file).map_err(|e| format!("Failed to read {}: {}", file, e))?;
+    let (users, failures) = parse_users(&contents, format, &attribute_mapping.unwrap_or_default())
+        .map_err(|e| format!("Failed to read users from {}: {}", file, e))?;
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_keycloak_adapter(&app, &integration).await?;
+
+    let mut result = adapter
+        .import_users(&realm, users, dry_run)
+        .await
+        .map_err(|e| format!("Failed to import users: {}", e))?;
+    result.failed.splice(0..0, failures);
+    if !dry_run {
+        log::info!(
+            "Imported {} users into Keycloak realm {} ({} existing, {} failed)",
+            result.created.len(),
+            realm,
+            result.existing.len(),
+            result.failed.len()
+        );
+    }
+    Ok(result)
+}
+
+/// Emails a user a link to complete required actions, e.g. `UPDATE_PASSWORD`
+/// for a password reset or `VERIFY_EMAIL`.
+///
+/// The link stays valid for `lifespan_hours` (default 12, at most 720).
+#[tauri::command]
+#[specta::specta]
+pub async fn send_keycloak_actions_email(
+    app: AppHandle,
+    integration_id: String,
+    realm: String,
+    username: String,
+    actions: Vec<KeycloakRequiredAction>,
+    lifespan_hours: Option<u32>,
+) -> Result<KeycloakActionsEmail, String> {
+    let _timer = CommandTimer::start("send_keycloak_actions_email");
+    crate::app_lock::ensure_unlocked()?;
+    log::debug!(
+        "Sending Keycloak actions email for integration: {}, realm: {}, user: {}",
+        integration_id,
+        realm,
+        username
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_keycloak_adapter(&app, &integration).await?;
+
+    let sent = adapter
+        .send_actions_email(
+            &realm,
+            &username,
+            &actions,
+            lifespan_hours.unwrap_or(DEFAULT_ACTIONS_LIFESPAN_HOURS),
+        )
+        .await
+        .map_err(|e| format!("Failed to send actions email: {}", e))?;
+    log::info!(
+        "Sent Keycloak actions email {:?} to {} in realm {}",
+        sent.actions,
+        username,
+        realm
+    );
+    Ok(sent)
+}
@@ -2,9 +2,15 @@
 //!
 //! Provides Tauri commands for interacting with Kubernetes API through the adapter.
 
-use crate::integrations::kubernetes::{K8sNamespace, K8sPod, K8sService, KubernetesAdapter};
+use crate::commands::config::{load_cost_rates, load_environments, load_integrations};
+use crate::integrations::kubernetes::{
+    estimate_monthly_cost, rate_for, K8sBlueGreenSwitch, K8sBulkPodResult, K8sCanaryStatus,
+    K8sDeploymentRevision, K8sEnvironmentCost, K8sEvictionSafety, K8sImageUpdate, K8sImageUsage,
+    K8sIngressHost, K8sNamespace, K8sPod, K8sPodAction, K8sRollback, K8sService, KubernetesAdapter,
+};
 use crate::integrations::registry::load_credentials;
-use crate::types::Integration;
+use crate::metrics::CommandTimer;
+use crate::types::{Integration, IntegrationType};
 use tauri::AppHandle;
 
 /// Helper function to get an integration by ID.
@@ -60,6 +66,17 @@ async fn create_kubernetes_adapter(
         .map_err(|e| format!("Failed to create Kubernetes adapter: {}", e))
 }
 
+/// Creates a Kubernetes adapter for an integration ID.
+///
+/// Used by flow nodes that act on a cluster.
+pub(crate) async fn kubernetes_adapter(
+    app: &AppHandle,
+    integration_id: &str,
+) -> Result<KubernetesAdapter, String> {
+    let integration = get_integration(app, integration_id).await?;
+    create_kubernetes_adapter(app, &integration).await
+}
+
 /// Fetches Kubernetes namespaces for a given integration.
 #[tauri::command]
 #[specta::specta]
@@ -67,6 +84,7 @@ pub async fn fetch_k8s_namespaces(
     app: AppHandle,
     integration_id: String,
 ) -> Result<Vec<K8sNamespace>, String> {
+    let _timer = CommandTimer::start("fetch_k8s_namespaces");
     log::debug!(
         "Fetching Kubernetes namespaces for integration: {}",
         integration_id
@@ -89,6 +107,7 @@ pub async fn fetch_k8s_pods(
     integration_id: String,
     namespace: String,
 ) -> Result<Vec<K8sPod>, String> {
+    let _timer = CommandTimer::start("fetch_k8s_pods");
     log::debug!(
         "Fetching Kubernetes pods for integration: {}, namespace: {}",
         integration_id,
@@ -112,6 +131,7 @@ pub async fn fetch_k8s_services(
     integration_id: String,
     namespace: String,
 ) -> Result<Vec<K8sService>, String> {
+    let _timer = CommandTimer::start("fetch_k8s_services");
     log::debug!(
         "Fetching Kubernetes services for integration: {}, namespace: {}",
         integration_id,
@@ -127,6 +147,58 @@ pub async fn fetch_k8s_services(
         .map_err(|e| format!("Failed to fetch services: {}", e))
 }
 
+/// Fetches the hosts routed by ingresses in a namespace (e.g., to add them as uptime targets).
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_k8s_ingress_hosts(
+    app: AppHandle,
+    integration_id: String,
+    namespace: String,
+) -> Result<Vec<K8sIngressHost>, String> {
+    let _timer = CommandTimer::start("fetch_k8s_ingress_hosts");
+    log::debug!(
+        "Fetching Kubernetes ingress hosts for integration: {}, namespace: {}",
+        integration_id,
+        namespace
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_kubernetes_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_ingress_hosts(&namespace)
+        .await
+        .map_err(|e| format!("Failed to fetch ingress hosts: {}", e))
+}
+
+/// Fetches the images of the running pods with their pod and container
+/// counts and namespaces.
+///
+/// # Arguments
+/// * `namespaces` - Namespaces to look in; the whole cluster when empty
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_k8s_image_inventory(
+    app: AppHandle,
+    integration_id: String,
+    namespaces: Vec<String>,
+) -> Result<Vec<K8sImageUsage>, String> {
+    let _timer = CommandTimer::start("fetch_k8s_image_inventory");
+    log::debug!(
+        "Fetching Kubernetes image inventory for integration: {}, namespaces: {:?}",
+        integration_id,
+        namespaces
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_kubernetes_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_image_inventory(&namespaces)
+        .await
+        .map_err(|e| format!("Failed to fetch image inventory: {}", e))
+}
+
 /// Fetches detailed information for a specific Kubernetes pod.
 #[tauri::command]
 #[specta::specta]
@@ -136,6 +208,7 @@ pub async fn fetch_k8s_pod_details(
     namespace: String,
     pod_name: String,
 ) -> Result<K8sPod, String> {
+    let _timer = CommandTimer::start("fetch_k8s_pod_details");
     log::debug!(
         "Fetching Kubernetes pod details for integration: {}, namespace: {}, pod: {}",
         integration_id,
@@ -151,3 +224,358 @@ pub async fn fetch_k8s_pod_details(
         .await
         .map_err(|e| format!("Failed to fetch pod details: {}", e))
 }
+
+/// Applies an action to every pod matching a label selector.
+///
+/// With `dry_run`, only returns the pods the action would affect.
+#[tauri::command]
+#[specta::specta]
+pub async fn bulk_k8s_pod_action(
+    app: AppHandle,
+    integration_id: String,
+    namespace: String,
+    selector: String,
+    action: K8sPodAction,
+    dry_run: bool,
+) -> Result<K8sBulkPodResult, String> {
+    let _timer = CommandTimer::start("bulk_k8s_pod_action");
+    if !dry_run {
+        crate::app_lock::ensure_unlocked()?;
+    }
+
+    log::debug!(
+        "Bulk pod action for integration: {}, pods: {}/{}",
+        integration_id,
+        namespace,
+        selector
+    );
+
+    let adapter = kubernetes_adapter(&app, &integration_id).await?;
+
+    adapter
+        .bulk_pod_action(&namespace, &selector, &action, dry_run)
+        .await
+        .map_err(|e| format!("Failed to run bulk pod action: {}", e))
+}
+
+/// Checks whether deleting pods of a deployment or scaling it down keeps the
+/// guarantees of its PodDisruptionBudgets, and warns about what would break.
+///
+/// # Arguments
+/// * `delete_pods` - Pods about to be deleted (one when not given)
+/// * `scale_to` - Replica count about to be set
+#[tauri::command]
+#[specta::specta]
+pub async fn check_k8s_eviction_safety(
+    app: AppHandle,
+    integration_id: String,
+    namespace: String,
+    deployment: String,
+    delete_pods: Option<u32>,
+    scale_to: Option<u32>,
+) -> Result<K8sEvictionSafety, String> {
+    let _timer = CommandTimer::start("check_k8s_eviction_safety");
+    log::debug!(
+        "Checking eviction safety for integration: {}, deployment: {}/{}",
+        integration_id,
+        namespace,
+        deployment
+    );
+
+    let adapter = kubernetes_adapter(&app, &integration_id).await?;
+
+    adapter
+        .check_eviction_safety(&namespace, &deployment, delete_pods, scale_to)
+        .await
+        .map_err(|e| format!("Failed to check eviction safety: {}", e))
+}
+
+/// Sets the image of a deployment container (e.g., for an image-bump deployment).
+#[tauri::command]
+#[specta::specta]
+pub async fn set_k8s_deployment_image(
+    app: AppHandle,
+    integration_id: String,
+    namespace: String,
+    deployment: String,
+    container: String,
+    image: String,
+) -> Result<K8sImageUpdate, String> {
+    let _timer = CommandTimer::start("set_k8s_deployment_image");
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!(
+        "Setting Kubernetes deployment image for integration: {}, deployment: {}/{}",
+        integration_id,
+        namespace,
+        deployment
+    );
+
+    let adapter = kubernetes_adapter(&app, &integration_id).await?;
+
+    adapter
+        .set_deployment_image(&namespace, &deployment, &container, &image)
+        .await
+        .map_err(|e| format!("Failed to set deployment image: {}", e))
+}
+
+/// Lists the revisions of a deployment (newest first) with their change causes.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_k8s_deployment_revisions(
+    app: AppHandle,
+    integration_id: String,
+    namespace: String,
+    deployment: String,
+) -> Result<Vec<K8sDeploymentRevision>, String> {
+    let _timer = CommandTimer::start("list_k8s_deployment_revisions");
+
+    log::debug!(
+        "Listing Kubernetes deployment revisions for integration: {}, deployment: {}/{}",
+        integration_id,
+        namespace,
+        deployment
+    );
+
+    let adapter = kubernetes_adapter(&app, &integration_id).await?;
+
+    adapter
+        .fetch_deployment_revisions(&namespace, &deployment)
+        .await
+        .map_err(|e| format!("Failed to list deployment revisions: {}", e))
+}
+
+/// Rolls a deployment back to `to_revision`, or to the previous revision when omitted.
+#[tauri::command]
+#[specta::specta]
+pub async fn rollback_k8s_deployment(
+    app: AppHandle,
+    integration_id: String,
+    namespace: String,
+    deployment: String,
+    to_revision: Option<u32>,
+) -> Result<K8sRollback, String> {
+    let _timer = CommandTimer::start("rollback_k8s_deployment");
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!(
+        "Rolling back Kubernetes deployment for integration: {}, deployment: {}/{}",
+        integration_id,
+        namespace,
+        deployment
+    );
+
+    let adapter = kubernetes_adapter(&app, &integration_id).await?;
+
+    adapter
+        .rollback_deployment(&namespace, &deployment, to_revision)
+        .await
+        .map_err(|e| format!("Failed to roll back deployment: {}", e))
+}
+
+/// Creates or updates a canary deployment serving about `percent` of the traffic.
+#[tauri::command]
+#[specta::specta]
+pub async fn start_canary(
+    app: AppHandle,
+    integration_id: String,
+    namespace: String,
+    deployment: String,
+    image: String,
+    percent: u32,
+) -> Result<K8sCanaryStatus, String> {
+    let _timer = CommandTimer::start("start_canary");
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!(
+        "Starting canary for integration: {}, deployment: {}/{}",
+        integration_id,
+        namespace,
+        deployment
+    );
+
+    let adapter = kubernetes_adapter(&app, &integration_id).await?;
+
+    adapter
+        .start_canary(&namespace, &deployment, &image, percent)
+        .await
+        .map_err(|e| format!("Failed to start canary: {}", e))
+}
+
+/// Rolls a canary's images out to the stable deployment and removes the canary.
+#[tauri::command]
+#[specta::specta]
+pub async fn promote_canary(
+    app: AppHandle,
+    integration_id: String,
+    namespace: String,
+    deployment: String,
+) -> Result<Vec<K8sImageUpdate>, String> {
+    let _timer = CommandTimer::start("promote_canary");
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!(
+        "Promoting canary for integration: {}, deployment: {}/{}",
+        integration_id,
+        namespace,
+        deployment
+    );
+
+    let adapter = kubernetes_adapter(&app, &integration_id).await?;
+
+    adapter
+        .promote_canary(&namespace, &deployment)
+        .await
+        .map_err(|e| format!("Failed to promote canary: {}", e))
+}
+
+/// Removes a canary deployment, leaving the stable deployment untouched.
+#[tauri::command]
+#[specta::specta]
+pub async fn abort_canary(
+    app: AppHandle,
+    integration_id: String,
+    namespace: String,
+    deployment: String,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("abort_canary");
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!(
+        "Aborting canary for integration: {}, deployment: {}/{}",
+        integration_id,
+        namespace,
+        deployment
+    );
+
+    let adapter = kubernetes_adapter(&app, &integration_id).await?;
+
+    adapter
+        .abort_canary(&namespace, &deployment)
+        .await
+        .map_err(|e| format!("Failed to abort canary: {}", e))
+}
+
+/// Switches a Service between its blue and green deployments.
+///
+/// Reverts to the previous color if the post-switch health checks fail.
+#[tauri::command]
+#[specta::specta]
+pub async fn switch_blue_green(
+    app: AppHandle,
+    integration_id: String,
+    namespace: String,
+    service: String,
+    target_color: String,
+) -> Result<K8sBlueGreenSwitch, String> {
+    let _timer = CommandTimer::start("switch_blue_green");
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!(
+        "Switching blue-green service for integration: {}, service: {}/{}, target: {}",
+        integration_id,
+        namespace,
+        service,
+        target_color
+    );
+
+    let adapter = kubernetes_adapter(&app, &integration_id).await?;
+
+    adapter
+        .switch_blue_green(&namespace, &service, &target_color)
+        .await
+        .map_err(|e| format!("Failed to switch blue-green service: {}", e))
+}
+
+/// Estimates the monthly cost of environments from their pods' resource
+/// requests and the configured cost rates.
+///
+/// Estimates one environment, or every environment with a namespace when
+/// `environment_id` is not given. Each namespace is looked up on every
+/// Kubernetes integration; clusters without pods in it are left out.
+#[tauri::command]
+#[specta::specta]
+pub async fn estimate_k8s_costs(
+    app: AppHandle,
+    environment_id: Option<String>,
+) -> Result<Vec<K8sEnvironmentCost>, String> {
+    let _timer = CommandTimer::start("estimate_k8s_costs");
+    log::debug!("Estimating Kubernetes costs for: {:?}", environment_id);
+
+    let environments: Vec<_> = load_environments(app.clone())
+        .await?
+        .into_iter()
+        .filter(|e| environment_id.as_ref().is_none_or(|id| &e.id == id))
+        .collect();
+    if let Some(id) = &environment_id {
+        match environments.first() {
+            None => return Err(format!("Environment not found: {}", id)),
+            Some(e) if e.namespace.is_none() => {
+                return Err(format!(
+                    "Environment {} has no Kubernetes namespace",
+                    e.name
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    let rates = load_cost_rates(app.clone()).await?;
+    let integrations: Vec<Integration> = load_integrations(app.clone())
+        .await?
+        .into_iter()
+        .filter(|i| i.integration_type == IntegrationType::Kubernetes)
+        .collect();
+    if integrations.is_empty() {
+        return Err("No Kubernetes integration configured".to_string());
+    }
+
+    let mut adapters = Vec::new();
+    for integration in &integrations {
+        let adapter = create_kubernetes_adapter(&app, integration).await;
+        adapters.push((integration, adapter));
+    }
+
+    let mut results = Vec::new();
+    for environment in &environments {
+        let Some(namespace) = &environment.namespace else {
+            continue;
+        };
+        let rate = rate_for(&rates, &environment.id);
+
+        let mut costs = Vec::new();
+        for (integration, adapter) in &adapters {
+            let outcome = match (adapter, rate) {
+                (Err(e), _) => Err(e.clone()),
+                (Ok(_), None) => Err(format!(
+                    "No cost rate configured for environment {}",
+                    environment.name
+                )),
+                (Ok(adapter), Some(rate)) => adapter
+                    .fetch_resource_requests(namespace)
+                    .await
+                    .map(|requests| estimate_monthly_cost(&requests, rate))
+                    .map_err(|e| format!("Failed to fetch resource requests: {}", e)),
+            };
+            costs.push(K8sEnvironmentCost {
+                environment_id: environment.id.clone(),
+                environment_name: environment.name.clone(),
+                integration_id: integration.id.clone(),
+                integration_name: integration.name.clone(),
+                error: outcome.as_ref().err().cloned(),
+                estimate: outcome.ok(),
+            });
+        }
+
+        // Keep clusters running the namespace, or all results if none do
+        if costs
+            .iter()
+            .any(|c| c.estimate.as_ref().is_some_and(|e| e.requests.pods > 0))
+        {
+            costs.retain(|c| c.estimate.as_ref().is_some_and(|e| e.requests.pods > 0));
+        }
+        results.extend(costs);
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,33 @@
+//! GitLab-to-Jenkins linkage commands.
+
+use tauri::AppHandle;
+
+use crate::linkage::{self, LinkageReport};
+use crate::metrics::CommandTimer;
+
+/// Match Jenkins jobs to the GitLab projects their Git remotes point at and
+/// propose mappings. With `create_mappings`, new links are saved as mappings.
+#[tauri::command]
+#[specta::specta]
+pub async fn detect_gitlab_jenkins_links(
+    app: AppHandle,
+    gitlab_integration_id: String,
+    jenkins_integration_id: String,
+    create_mappings: bool,
+) -> Result<LinkageReport, String> {
+    let _timer = CommandTimer::start("detect_gitlab_jenkins_links");
+    if create_mappings {
+        crate::app_lock::ensure_unlocked()?;
+    }
+    log::debug!(
+        "Detecting links between GitLab {gitlab_integration_id} and Jenkins {jenkins_integration_id}"
+    );
+
+    linkage::detect(
+        &app,
+        &gitlab_integration_id,
+        &jenkins_integration_id,
+        create_mappings,
+    )
+    .await
+}
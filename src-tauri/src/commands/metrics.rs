@@ -0,0 +1,30 @@
+//! Metrics endpoint commands.
+//!
+//! The endpoint itself is configured with the `metrics_port` preference.
+
+use crate::metrics::{self, MetricsEndpointStatus};
+use crate::polling_cost::{self, PollingCostEstimate};
+use crate::task_pool::{self, TaskPoolStatus};
+use tauri::AppHandle;
+
+/// Get the metrics endpoint status.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_metrics_endpoint_status() -> Result<MetricsEndpointStatus, String> {
+    Ok(metrics::status())
+}
+
+/// Get the load of the background task pool.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_task_pool_status() -> Result<TaskPoolStatus, String> {
+    Ok(task_pool::status())
+}
+
+/// Estimate the requests per hour the background pollers send to each
+/// integration, with warnings for integrations close to a known rate limit.
+#[tauri::command]
+#[specta::specta]
+pub async fn estimate_polling_cost(app: AppHandle) -> Result<PollingCostEstimate, String> {
+    polling_cost::estimate(&app).await
+}
@@ -3,15 +3,34 @@
 //! Each submodule contains related commands and their helper functions.
 //! Import specific commands via their submodule (e.g., `commands::preferences::greet`).
 
+pub mod annotations;
+pub mod app_lock;
+pub mod builds;
+pub mod checks;
 pub mod config;
 pub mod credentials;
+pub mod demo;
+pub mod event_recorder;
 pub mod flows;
 pub mod gitlab;
+pub mod initial_sync;
 pub mod jenkins;
 pub mod keycloak;
 pub mod kubernetes;
+pub mod linkage;
+pub mod metrics;
 pub mod notifications;
+pub mod onboarding;
 pub mod preferences;
+pub mod project_inventory;
+pub mod quick_actions;
 pub mod quick_pane;
 pub mod recovery;
+pub mod refresh;
+pub mod reports;
+pub mod saved_filters;
 pub mod sonarqube;
+pub mod tag_watcher;
+pub mod updates;
+pub mod webhooks;
+pub mod windows;
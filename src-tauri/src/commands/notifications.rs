@@ -1,8 +1,90 @@
 //! Native notification commands.
 //!
 //! Provides cross-platform native notification support using the Tauri notification plugin.
+//!
+//! Notifications can carry actions ("Open build", "Retry pipeline") that
+//! deep-link back into the app or run a quick action. The desktop
+//! notification backends don't report button presses, so actionable
+//! notifications are kept in a short in-memory list, announced with
+//! [`NOTIFICATION_ACTIONS_EVENT`] and listed in the tray menu (see
+//! [`crate::notification_tray`]). The frontend shows their buttons and runs
+//! them with `run_notification_action`; the tray runs them directly.
+//!
+//! Notifications about a service are routed to its owner: the owning team,
+//! Slack channel and on-call rotation are resolved from the project and
+//! mapping (see [`crate::ownership`]) and attached to the notification.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::flow_engine::{self, FlowRun, RunOptions};
+use crate::ownership::{self, ServiceRef};
+use crate::types::{Ownership, QuickAction, Timestamp};
+use crate::windows;
+
+/// Emitted with an [`ActionableNotification`] when one is sent.
+pub const NOTIFICATION_ACTIONS_EVENT: &str = "notification-actions";
 
-use tauri::AppHandle;
+/// Emitted with the route of an `open` action, after focusing the main window.
+pub const NOTIFICATION_OPEN_EVENT: &str = "notification-open";
+
+/// Number of actionable notifications kept.
+const MAX_RECENT_NOTIFICATIONS: usize = 50;
+
+/// What a notification action does.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NotificationActionTarget {
+    /// Brings the app to the front and navigates to a route (e.g., a build page)
+    Open { route: String },
+    /// Runs a quick action (e.g., retry a pipeline)
+    Run { action: QuickAction },
+}
+
+/// Button shown with a notification.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationAction {
+    /// Unique within the notification
+    pub id: String,
+    pub label: String,
+    pub target: NotificationActionTarget,
+}
+
+/// Notification sent with actions.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionableNotification {
+    pub id: u32,
+    pub title: String,
+    pub body: Option<String>,
+    pub actions: Vec<NotificationAction>,
+    /// Service the notification is about
+    pub service: Option<ServiceRef>,
+    /// Owner of the service, whose Slack channel the notification targets
+    pub owner: Option<Ownership>,
+    pub sent_at: Timestamp,
+}
+
+static NEXT_NOTIFICATION_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Actionable notifications sent, newest first.
+static RECENT: LazyLock<Mutex<VecDeque<ActionableNotification>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::new()));
+
+fn recent() -> MutexGuard<'static, VecDeque<ActionableNotification>> {
+    RECENT.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns recent actionable notifications, newest first.
+pub fn recent_notifications() -> Vec<ActionableNotification> {
+    recent().iter().cloned().collect()
+}
 
 /// Sends a native system notification.
 /// On mobile platforms, returns an error as notifications are not yet supported.
@@ -44,3 +126,230 @@ pub async fn send_native_notification(
         Err("Native notifications not supported on mobile".to_string())
     }
 }
+
+/// Sends a native notification with actions and announces it to the
+/// frontend. Returns the notification ID.
+///
+/// A notification about a `service` is routed to the service's owner, and
+/// names the owner in its body.
+pub async fn notify_with_actions(
+    app: &AppHandle,
+    title: String,
+    body: Option<String>,
+    actions: Vec<NotificationAction>,
+    service: Option<ServiceRef>,
+) -> Result<u32, String> {
+    validate_actions(&actions)?;
+    let owner = match &service {
+        Some(service) => ownership::lookup(app, service).await?,
+        None => None,
+    };
+    let body = match &owner {
+        Some(owner) => {
+            let line = format!("Owner: {}", ownership::summary(owner));
+            Some(match body {
+                Some(body) => format!("{body}\n{line}"),
+                None => line,
+            })
+        }
+        None => body,
+    };
+    let notification = ActionableNotification {
+        id: NEXT_NOTIFICATION_ID.fetch_add(1, Ordering::Relaxed),
+        title,
+        body,
+        actions,
+        service,
+        owner,
+        sent_at: Timestamp::from_datetime(Utc::now()),
+    };
+    remember(&mut recent(), notification.clone());
+
+    #[cfg(desktop)]
+    crate::notification_tray::refresh(app, &recent_notifications());
+    windows::broadcast(app, NOTIFICATION_ACTIONS_EVENT, &notification);
+    // The actions stay available in the app even if the OS notification fails
+    if let Err(e) =
+        send_native_notification(app.clone(), notification.title, notification.body).await
+    {
+        log::warn!("{e}");
+    }
+    Ok(notification.id)
+}
+
+fn validate_actions(actions: &[NotificationAction]) -> Result<(), String> {
+    if actions.is_empty() {
+        return Err("A notification needs at least one action".to_string());
+    }
+    for (index, action) in actions.iter().enumerate() {
+        if actions[..index].iter().any(|other| other.id == action.id) {
+            return Err(format!("Duplicate notification action: {}", action.id));
+        }
+        if action.label.trim().is_empty() {
+            return Err(format!("Notification action {} has no label", action.id));
+        }
+    }
+    Ok(())
+}
+
+fn remember(recent: &mut VecDeque<ActionableNotification>, notification: ActionableNotification) {
+    recent.push_front(notification);
+    recent.truncate(MAX_RECENT_NOTIFICATIONS);
+}
+
+fn find_action(
+    recent: &VecDeque<ActionableNotification>,
+    notification_id: u32,
+    action_id: &str,
+) -> Result<NotificationAction, String> {
+    let notification = recent
+        .iter()
+        .find(|n| n.id == notification_id)
+        .ok_or_else(|| format!("Notification not found: {notification_id}"))?;
+    notification
+        .actions
+        .iter()
+        .find(|a| a.id == action_id)
+        .cloned()
+        .ok_or_else(|| format!("Notification action not found: {action_id}"))
+}
+
+/// Sends a native notification with action buttons. Returns its ID.
+///
+/// With `service`, the notification is routed to the service's owner.
+#[tauri::command]
+#[specta::specta]
+pub async fn send_actionable_notification(
+    app: AppHandle,
+    title: String,
+    body: Option<String>,
+    actions: Vec<NotificationAction>,
+    service: Option<ServiceRef>,
+) -> Result<u32, String> {
+    log::info!(
+        "Sending notification '{title}' with {} action(s)",
+        actions.len()
+    );
+    notify_with_actions(&app, title, body, actions, service).await
+}
+
+/// Lists recent actionable notifications, newest first.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_actionable_notifications() -> Result<Vec<ActionableNotification>, String> {
+    Ok(recent_notifications())
+}
+
+/// Runs an action of a notification. `open` actions focus the main window
+/// and emit [`NOTIFICATION_OPEN_EVENT`] (returning no run); `run` actions
+/// start a flow run of the quick action.
+#[tauri::command]
+#[specta::specta]
+pub async fn run_notification_action(
+    app: AppHandle,
+    notification_id: u32,
+    action_id: String,
+) -> Result<Option<FlowRun>, String> {
+    run_action(&app, notification_id, &action_id).await
+}
+
+/// Runs an action of a recent notification, from the frontend or the tray.
+pub async fn run_action(
+    app: &AppHandle,
+    notification_id: u32,
+    action_id: &str,
+) -> Result<Option<FlowRun>, String> {
+    let action = find_action(&recent(), notification_id, action_id)?;
+    log::debug!(
+        "Running action {} of notification {}",
+        action.id,
+        notification_id
+    );
+
+    match action.target {
+        NotificationActionTarget::Open { route } => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            // Only the main window navigates; view windows stay on their view
+            if let Err(e) = app.emit_to("main", NOTIFICATION_OPEN_EVENT, &route) {
+                log::warn!("Failed to emit {NOTIFICATION_OPEN_EVENT} event: {e}");
+            }
+            Ok(None)
+        }
+        NotificationActionTarget::Run {
+            action: quick_action,
+        } => {
+            crate::app_lock::ensure_unlocked()?;
+            let flow = flow_engine::action_flow(
+                &format!("notification-{notification_id}"),
+                &action.label,
+                std::slice::from_ref(&quick_action),
+            )?;
+            let options = RunOptions {
+                triggered_by: Some("notification".to_string()),
+                ..RunOptions::default()
+            };
+            flow_engine::start_run(app, &flow, options).map(Some)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_action(id: &str) -> NotificationAction {
+        NotificationAction {
+            id: id.to_string(),
+            label: "Open build".to_string(),
+            target: NotificationActionTarget::Open {
+                route: "/jenkins/jobs/api/42".to_string(),
+            },
+        }
+    }
+
+    fn notification(id: u32) -> ActionableNotification {
+        ActionableNotification {
+            id,
+            title: format!("Build {id} failed"),
+            body: None,
+            actions: vec![open_action("open")],
+            service: None,
+            owner: None,
+            sent_at: Timestamp::from_datetime(Utc::now()),
+        }
+    }
+
+    #[test]
+    fn test_validate_actions() {
+        assert!(validate_actions(&[open_action("open")]).is_ok());
+        assert!(validate_actions(&[]).is_err());
+        assert_eq!(
+            validate_actions(&[open_action("open"), open_action("open")]).unwrap_err(),
+            "Duplicate notification action: open"
+        );
+    }
+
+    #[test]
+    fn test_recent_notifications_are_bounded() {
+        let mut recent = VecDeque::new();
+        for id in 1..=(MAX_RECENT_NOTIFICATIONS as u32 + 1) {
+            remember(&mut recent, notification(id));
+        }
+        assert_eq!(recent.len(), MAX_RECENT_NOTIFICATIONS);
+        assert_eq!(recent[0].id, MAX_RECENT_NOTIFICATIONS as u32 + 1);
+
+        assert!(find_action(&recent, 1, "open").is_err());
+        assert_eq!(
+            find_action(&recent, 2, "open").unwrap(),
+            open_action("open")
+        );
+        assert_eq!(
+            find_action(&recent, 2, "retry").unwrap_err(),
+            "Notification action not found: retry"
+        );
+    }
+}
@@ -0,0 +1,27 @@
+//! First-run setup commands.
+//!
+//! Reports and advances the guided setup progress.
+
+use tauri::AppHandle;
+
+use crate::onboarding::{self, OnboardingState, OnboardingStep};
+
+/// Get the setup progress. Starts at the first step on first run.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_onboarding_state(app: AppHandle) -> Result<OnboardingState, String> {
+    onboarding::state(&app)
+}
+
+/// Run a setup step (check integrations, test their connections, or create
+/// the example project) and return the updated progress.
+#[tauri::command]
+#[specta::specta]
+pub async fn complete_onboarding_step(
+    app: AppHandle,
+    step: OnboardingStep,
+) -> Result<OnboardingState, String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    onboarding::complete_step(&app, step).await
+}
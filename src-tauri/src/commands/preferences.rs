@@ -5,7 +5,10 @@
 use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
 
-use crate::types::{validate_string_input, validate_theme, AppPreferences};
+use crate::types::{
+    validate_string_input, validate_theme, validate_update_install_window, AppPreferences,
+    FeatureFlags,
+};
 
 /// Gets the path to the preferences file.
 fn get_preferences_path(app: &AppHandle) -> Result<PathBuf, String> {
@@ -21,9 +24,9 @@ fn get_preferences_path(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(app_data_dir.join("preferences.json"))
 }
 
-/// Load the saved quick pane shortcut from preferences, returning None on any failure.
+/// Read the saved preferences, returning None on any failure.
 /// Used at startup before the full preferences system is available.
-pub fn load_quick_pane_shortcut(app: &AppHandle) -> Option<String> {
+pub fn read_preferences(app: &AppHandle) -> Option<AppPreferences> {
     let path = get_preferences_path(app).ok()?;
     if !path.exists() {
         return None;
@@ -31,10 +34,14 @@ pub fn load_quick_pane_shortcut(app: &AppHandle) -> Option<String> {
     let contents = std::fs::read_to_string(&path)
         .inspect_err(|e| log::warn!("Failed to read preferences: {e}"))
         .ok()?;
-    let prefs: AppPreferences = serde_json::from_str(&contents)
+    serde_json::from_str(&contents)
         .inspect_err(|e| log::warn!("Failed to parse preferences: {e}"))
-        .ok()?;
-    prefs.quick_pane_shortcut
+        .ok()
+}
+
+/// Load the saved quick pane shortcut from preferences, returning None on any failure.
+pub fn load_quick_pane_shortcut(app: &AppHandle) -> Option<String> {
+    read_preferences(app)?.quick_pane_shortcut
 }
 
 /// Simple greeting command for demonstration purposes.
@@ -83,8 +90,13 @@ pub async fn load_preferences(app: AppHandle) -> Result<AppPreferences, String>
 #[tauri::command]
 #[specta::specta]
 pub async fn save_preferences(app: AppHandle, preferences: AppPreferences) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
     // Validate theme value
     validate_theme(&preferences.theme)?;
+    if let Some(window) = &preferences.update_install_window {
+        validate_update_install_window(window)?;
+    }
 
     log::debug!("Saving preferences to disk: {preferences:?}");
     let prefs_path = get_preferences_path(&app)?;
@@ -111,6 +123,16 @@ pub async fn save_preferences(app: AppHandle, preferences: AppPreferences) -> Re
         return Err(format!("Failed to finalize preferences file: {rename_err}"));
     }
 
+    crate::app_lock::set_idle_timeout(preferences.app_lock_idle_minutes);
+    crate::metrics::configure(&app, preferences.metrics_port).await?;
+
     log::info!("Successfully saved preferences to {prefs_path:?}");
     Ok(())
 }
+
+/// Returns the feature flags in effect (the saved ones, or the defaults).
+#[tauri::command]
+#[specta::specta]
+pub async fn get_feature_flags(app: AppHandle) -> Result<FeatureFlags, String> {
+    Ok(crate::feature_flags::current(&app))
+}
@@ -0,0 +1,49 @@
+//! GitLab project inventory commands.
+//!
+//! Takes, lists and diffs snapshots of the projects of GitLab integrations.
+
+use tauri::AppHandle;
+
+use crate::metrics::CommandTimer;
+use crate::project_inventory::{
+    self, ProjectInventoryDiff, ProjectInventorySnapshot, ProjectInventorySnapshotInfo,
+};
+
+/// Snapshot the project inventory of a GitLab integration now.
+#[tauri::command]
+#[specta::specta]
+pub async fn take_project_inventory_snapshot(
+    app: AppHandle,
+    integration_id: String,
+) -> Result<ProjectInventorySnapshot, String> {
+    let _timer = CommandTimer::start("take_project_inventory_snapshot");
+    log::debug!("Taking project inventory snapshot of {}", integration_id);
+    crate::app_lock::ensure_unlocked()?;
+
+    project_inventory::take_snapshot(&app, &integration_id).await
+}
+
+/// List the project inventory snapshots of a GitLab integration, oldest first.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_project_inventory_snapshots(
+    app: AppHandle,
+    integration_id: String,
+) -> Result<Vec<ProjectInventorySnapshotInfo>, String> {
+    project_inventory::list_snapshots(&app, &integration_id)
+}
+
+/// Diff two project inventory snapshots of a GitLab integration.
+///
+/// # Arguments
+/// * `to` - End snapshot; the latest one when omitted
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_project_inventory(
+    app: AppHandle,
+    integration_id: String,
+    from: String,
+    to: Option<String>,
+) -> Result<ProjectInventoryDiff, String> {
+    project_inventory::diff(&app, &integration_id, &from, to.as_deref())
+}
@@ -0,0 +1,196 @@
+//! Quick action and macro commands.
+//!
+//! Quick pane actions run as generated flows of `quick_action` nodes. While a
+//! recording is active, every action run is also appended to the recording;
+//! stopping it saves the sequence as a named macro that replays with
+//! `run_quick_pane_macro`.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::{Mutex, PoisonError};
+use tauri::AppHandle;
+
+use crate::commands::config::{load_quick_pane_macros, save_quick_pane_macros};
+use crate::flow_engine::{self, FlowRun, RunOptions};
+use crate::types::{QuickAction, QuickPaneMacro};
+
+/// Longest macro that can be recorded.
+const MAX_MACRO_STEPS: usize = 20;
+
+/// Steps recorded so far; `None` when not recording.
+static RECORDING: Mutex<Option<Vec<QuickAction>>> = Mutex::new(None);
+
+/// Current macro recording state.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MacroRecordingStatus {
+    pub recording: bool,
+    /// Actions recorded so far
+    pub steps: Vec<QuickAction>,
+}
+
+fn recording() -> std::sync::MutexGuard<'static, Option<Vec<QuickAction>>> {
+    RECORDING.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Run a single quick pane action. Recorded when a macro recording is active.
+#[tauri::command]
+#[specta::specta]
+pub async fn run_quick_action(app: AppHandle, action: QuickAction) -> Result<FlowRun, String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    let flow = flow_engine::action_flow(
+        "quick-action",
+        "Quick action",
+        std::slice::from_ref(&action),
+    )?;
+    let run = flow_engine::start_run(&app, &flow, quick_pane_options())?;
+
+    if let Some(steps) = recording().as_mut() {
+        if steps.len() < MAX_MACRO_STEPS {
+            steps.push(action);
+        } else {
+            log::warn!("Macro recording is full ({MAX_MACRO_STEPS} steps); action not recorded");
+        }
+    }
+    Ok(run)
+}
+
+/// Start recording quick pane actions into a new macro.
+#[tauri::command]
+#[specta::specta]
+pub async fn start_macro_recording() -> Result<(), String> {
+    let mut recording = recording();
+    if recording.is_some() {
+        return Err("A macro is already being recorded".to_string());
+    }
+    *recording = Some(Vec::new());
+    log::info!("Started macro recording");
+    Ok(())
+}
+
+/// Get whether a macro is being recorded and its steps so far.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_macro_recording_status() -> Result<MacroRecordingStatus, String> {
+    let recording = recording();
+    Ok(MacroRecordingStatus {
+        recording: recording.is_some(),
+        steps: recording.clone().unwrap_or_default(),
+    })
+}
+
+/// Stop recording. Saves the recorded actions as a macro named `name`, or
+/// discards them when `name` is not provided.
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_macro_recording(
+    app: AppHandle,
+    name: Option<String>,
+) -> Result<Option<QuickPaneMacro>, String> {
+    // Checked before taking the steps, so a locked app keeps the recording
+    crate::app_lock::ensure_unlocked()?;
+
+    let steps = recording()
+        .take()
+        .ok_or_else(|| "No macro is being recorded".to_string())?;
+
+    let Some(name) = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()) else {
+        log::info!("Discarded macro recording with {} step(s)", steps.len());
+        return Ok(None);
+    };
+    if steps.is_empty() {
+        return Err("No actions were recorded".to_string());
+    }
+
+    let mut macros = load_quick_pane_macros(app.clone()).await?;
+    let quick_macro = QuickPaneMacro {
+        id: unique_macro_id(&name, &macros),
+        name,
+        steps,
+        created_at: Utc::now().to_rfc3339(),
+    };
+    macros.push(quick_macro.clone());
+    save_quick_pane_macros(app, macros).await?;
+
+    log::info!(
+        "Saved macro '{}' with {} step(s)",
+        quick_macro.name,
+        quick_macro.steps.len()
+    );
+    Ok(Some(quick_macro))
+}
+
+/// Replay a saved macro. Its actions run in order as one flow run.
+#[tauri::command]
+#[specta::specta]
+pub async fn run_quick_pane_macro(app: AppHandle, macro_id: String) -> Result<FlowRun, String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    let quick_macro = load_quick_pane_macros(app.clone())
+        .await?
+        .into_iter()
+        .find(|m| m.id == macro_id)
+        .ok_or_else(|| format!("Macro not found: {macro_id}"))?;
+
+    log::debug!("Running macro: {}", quick_macro.name);
+    let flow = flow_engine::action_flow(
+        &format!("macro-{}", quick_macro.id),
+        &quick_macro.name,
+        &quick_macro.steps,
+    )?;
+    flow_engine::start_run(&app, &flow, quick_pane_options())
+}
+
+fn quick_pane_options() -> RunOptions {
+    RunOptions {
+        triggered_by: Some("quick-pane".to_string()),
+        ..RunOptions::default()
+    }
+}
+
+/// Derives a macro ID from its name (e.g., "Deploy API" -> "deploy-api"),
+/// adding a suffix when the ID is taken.
+fn unique_macro_id(name: &str, existing: &[QuickPaneMacro]) -> String {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let base = if slug.is_empty() {
+        "macro".to_string()
+    } else {
+        slug
+    };
+
+    let taken = |id: &str| existing.iter().any(|m| m.id == id);
+    if !taken(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|id| !taken(id))
+        .unwrap_or(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_macro_id() {
+        let existing = vec![QuickPaneMacro {
+            id: "deploy-api".to_string(),
+            name: "Deploy API".to_string(),
+            steps: Vec::new(),
+            created_at: String::new(),
+        }];
+        assert_eq!(unique_macro_id("Deploy  API!", &existing), "deploy-api-2");
+        assert_eq!(
+            unique_macro_id("Restart staging", &existing),
+            "restart-staging"
+        );
+        assert_eq!(unique_macro_id("🚀", &existing), "macro");
+    }
+}
@@ -0,0 +1,21 @@
+//! Aggregate refresh commands.
+//!
+//! Refreshes everything mapped to a project, streaming results as events.
+
+use tauri::AppHandle;
+
+use crate::metrics::CommandTimer;
+use crate::refresh::{self, RefreshStarted};
+
+/// Start fetching the pipelines, builds and pods mapped to a project.
+///
+/// Returns the fetches right away; each result arrives as a `refresh-result`
+/// event as soon as it completes, and `refresh-completed` follows the last.
+#[tauri::command]
+#[specta::specta]
+pub async fn refresh_all(app: AppHandle, project_id: String) -> Result<RefreshStarted, String> {
+    let _timer = CommandTimer::start("refresh_all");
+    log::debug!("Refreshing project: {}", project_id);
+
+    refresh::refresh_all(&app, &project_id).await
+}
@@ -0,0 +1,51 @@
+//! Report commands.
+//!
+//! Generates, lists and opens the HTML/PDF reports defined in
+//! `report_definitions.yaml`.
+
+use tauri::AppHandle;
+
+use crate::commands::config::load_report_definitions;
+use crate::reports::{self, GeneratedReport};
+
+/// Generates a report now, regardless of its schedule.
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_report(app: AppHandle, report_id: String) -> Result<GeneratedReport, String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    let definition = load_report_definitions(app.clone())
+        .await?
+        .into_iter()
+        .find(|r| r.id == report_id)
+        .ok_or_else(|| format!("Report not found: {report_id}"))?;
+
+    reports::generate(&app, &definition).await.inspect_err(|e| {
+        log::error!("Failed to generate report {report_id}: {e}");
+    })
+}
+
+/// Lists generated report files, newest first.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_generated_reports(app: AppHandle) -> Result<Vec<GeneratedReport>, String> {
+    reports::list(&app)
+}
+
+/// Opens a generated report with the system's default application.
+#[tauri::command]
+#[specta::specta]
+pub async fn open_generated_report(app: AppHandle, file_name: String) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    crate::app_lock::ensure_unlocked()?;
+
+    let path = reports::path_of(&app, &file_name)?;
+    log::info!("Opening report {path:?}");
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| {
+            log::error!("Failed to open report {path:?}: {e}");
+            format!("Failed to open report: {e}")
+        })
+}
@@ -0,0 +1,23 @@
+//! Saved filter commands.
+
+use tauri::AppHandle;
+
+use crate::commands::config::load_saved_filters;
+use crate::saved_filters::{self, SavedFilterResult};
+
+/// Evaluate a saved filter against the current (cached) resources.
+#[tauri::command]
+#[specta::specta]
+pub async fn evaluate_saved_filter(
+    app: AppHandle,
+    filter_id: String,
+) -> Result<SavedFilterResult, String> {
+    log::debug!("Evaluating saved filter {filter_id}");
+    let filters = load_saved_filters(app.clone()).await?;
+    let filter = filters
+        .iter()
+        .find(|f| f.id == filter_id)
+        .ok_or_else(|| format!("Saved filter not found: {filter_id}"))?;
+
+    saved_filters::evaluate(&app, filter).await
+}
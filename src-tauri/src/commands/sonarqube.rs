@@ -3,7 +3,11 @@
 //! Provides Tauri commands for interacting with SonarQube API through the adapter.
 
 use crate::integrations::registry::load_credentials;
-use crate::integrations::sonarqube::{SonarQubeAdapter, SonarQubeMetrics, SonarQubeProject};
+use crate::integrations::sonarqube::{
+    SonarQubeAdapter, SonarQubeMetrics, SonarQubeProfileDrift, SonarQubeProject,
+    SonarQubeQualityProfile,
+};
+use crate::metrics::CommandTimer;
 use crate::types::Integration;
 use tauri::AppHandle;
 
@@ -46,6 +50,7 @@ pub async fn fetch_sonarqube_projects(
     app: AppHandle,
     integration_id: String,
 ) -> Result<Vec<SonarQubeProject>, String> {
+    let _timer = CommandTimer::start("fetch_sonarqube_projects");
     log::debug!(
         "Fetching SonarQube projects for integration: {}",
         integration_id
@@ -68,6 +73,7 @@ pub async fn fetch_sonarqube_metrics(
     integration_id: String,
     project_key: String,
 ) -> Result<SonarQubeMetrics, String> {
+    let _timer = CommandTimer::start("fetch_sonarqube_metrics");
     log::debug!(
         "Fetching SonarQube metrics for integration: {}, project: {}",
         integration_id,
@@ -82,3 +88,68 @@ pub async fn fetch_sonarqube_metrics(
         .await
         .map_err(|e| format!("Failed to fetch metrics: {}", e))
 }
+
+/// Fetches SonarQube quality profiles: those a project uses (one per
+/// language) when `project_key` is given, all of them otherwise.
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_sonarqube_quality_profiles(
+    app: AppHandle,
+    integration_id: String,
+    project_key: Option<String>,
+) -> Result<Vec<SonarQubeQualityProfile>, String> {
+    let _timer = CommandTimer::start("fetch_sonarqube_quality_profiles");
+    log::debug!(
+        "Fetching SonarQube quality profiles for integration: {}, project: {:?}",
+        integration_id,
+        project_key
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_sonarqube_adapter(&app, &integration).await?;
+
+    adapter
+        .fetch_quality_profiles(project_key.as_deref())
+        .await
+        .map_err(|e| format!("Failed to fetch quality profiles: {}", e))
+}
+
+/// Compares the quality profile each project uses against the golden profile
+/// of its language, listing the rules that differ.
+///
+/// `golden_profile_keys` holds at most one profile per language; languages
+/// without one are reported without differences. All projects are checked
+/// when `project_keys` is None.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_sonarqube_profile_drift(
+    app: AppHandle,
+    integration_id: String,
+    golden_profile_keys: Vec<String>,
+    project_keys: Option<Vec<String>>,
+) -> Result<Vec<SonarQubeProfileDrift>, String> {
+    let _timer = CommandTimer::start("check_sonarqube_profile_drift");
+    log::debug!(
+        "Checking SonarQube profile drift for integration: {}",
+        integration_id
+    );
+
+    let integration = get_integration(&app, &integration_id).await?;
+    let adapter = create_sonarqube_adapter(&app, &integration).await?;
+
+    let project_keys = match project_keys {
+        Some(keys) => keys,
+        None => adapter
+            .fetch_projects()
+            .await
+            .map_err(|e| format!("Failed to fetch projects: {}", e))?
+            .into_iter()
+            .map(|p| p.key)
+            .collect(),
+    };
+
+    adapter
+        .check_profile_drift(&golden_profile_keys, &project_keys)
+        .await
+        .map_err(|e| format!("Failed to check profile drift: {}", e))
+}
@@ -0,0 +1,33 @@
+//! Tag watcher commands.
+//!
+//! Starts and stops polling GitLab projects for new tags.
+
+use tauri::AppHandle;
+
+use crate::tag_watcher::{self, TagWatcherStatus};
+
+/// Start polling the configured tag watches.
+#[tauri::command]
+#[specta::specta]
+pub async fn start_tag_watcher(app: AppHandle) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    tag_watcher::start(&app)
+}
+
+/// Stop the tag watcher.
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_tag_watcher() -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    tag_watcher::stop();
+    Ok(())
+}
+
+/// Get the tag watcher state with the tags detected by every watch.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_tag_watcher_status(app: AppHandle) -> Result<TagWatcherStatus, String> {
+    tag_watcher::status(&app).await
+}
@@ -0,0 +1,27 @@
+//! App update commands.
+//!
+//! The update channel, deferral and install window are preferences; see
+//! `crate::updates` for how they are applied.
+
+use tauri::AppHandle;
+
+use crate::updates::{self, UpdateCheck};
+
+/// Check the configured update channel for a newer release.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_for_updates(app: AppHandle) -> Result<UpdateCheck, String> {
+    updates::check(&app).await
+}
+
+/// Download and install `version`, the release returned by
+/// `check_for_updates`. Fails if the channel now offers another release or
+/// the release is deferred. Returns the installed version, which runs after
+/// a restart.
+#[tauri::command]
+#[specta::specta]
+pub async fn install_update(app: AppHandle, version: String) -> Result<String, String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    updates::install(&app, &version).await
+}
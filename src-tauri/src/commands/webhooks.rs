@@ -0,0 +1,42 @@
+//! Webhook receiver commands.
+//!
+//! Starts and stops the local HTTP listener that turns incoming webhooks into
+//! flow runs.
+
+use tauri::AppHandle;
+
+use crate::webhooks::{self, WebhookReceiverConfig, WebhookReceiverStatus};
+
+/// Start the webhook receiver.
+#[tauri::command]
+#[specta::specta]
+pub async fn start_webhook_receiver(
+    app: AppHandle,
+    config: WebhookReceiverConfig,
+) -> Result<WebhookReceiverStatus, String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    log::debug!(
+        "Starting webhook receiver on {}:{}",
+        config.bind_address,
+        config.port
+    );
+    webhooks::start(&app, config).await
+}
+
+/// Stop the webhook receiver.
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_webhook_receiver() -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+
+    webhooks::stop();
+    Ok(())
+}
+
+/// Get the webhook receiver status.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_webhook_receiver_status() -> Result<WebhookReceiverStatus, String> {
+    Ok(webhooks::status())
+}
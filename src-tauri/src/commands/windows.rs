@@ -0,0 +1,40 @@
+//! Window commands.
+//!
+//! Opens extra windows for a view and narrows the backend events a window
+//! receives.
+
+use tauri::{AppHandle, WebviewWindow};
+
+use crate::windows::{self, AppWindow};
+
+/// Opens a window for a view (e.g., "logs"), or focuses it when already
+/// open. Returns the window label (`view-<view>`).
+#[tauri::command]
+#[specta::specta]
+pub async fn open_view_window(
+    app: AppHandle,
+    view: String,
+    title: Option<String>,
+) -> Result<String, String> {
+    log::debug!("Opening window for view: {}", view);
+    windows::open_view(&app, &view, title.as_deref())
+}
+
+/// Limits the backend events the calling window receives; None restores
+/// every event. App lock changes are always delivered.
+#[tauri::command]
+#[specta::specta]
+pub async fn subscribe_window_events(
+    window: WebviewWindow,
+    events: Option<Vec<String>>,
+) -> Result<(), String> {
+    windows::subscribe(window.label(), events);
+    Ok(())
+}
+
+/// Lists the open windows with their event subscriptions.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_app_windows(app: AppHandle) -> Result<Vec<AppWindow>, String> {
+    Ok(windows::list(&app))
+}
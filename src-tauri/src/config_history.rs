@@ -0,0 +1,486 @@
+//! Configuration history.
+//!
+//! Every save of `integrations.yaml` or `mappings.yaml` also writes a
+//! snapshot of the saved items to `<config>/history/<entity>/<id>.yaml`,
+//! together with the time and the OS user that saved it. Diffing consecutive
+//! snapshots attributes each field change to the save that made it, which
+//! answers questions like "who changed the prod base URL and when".
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use specta::Type;
+use std::path::{Path, PathBuf};
+
+use crate::types::Timestamp;
+use crate::utils::platform::current_user;
+
+/// Snapshots kept per entity; older ones are removed on save.
+const MAX_SNAPSHOTS: usize = 200;
+
+/// Config file whose history is tracked.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigHistoryEntity {
+    Integrations,
+    Mappings,
+}
+
+impl ConfigHistoryEntity {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Integrations => "integrations",
+            Self::Mappings => "mappings",
+        }
+    }
+}
+
+/// One saved version of a config file.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ConfigSnapshotInfo {
+    /// Snapshot identifier (sortable by save time)
+    pub id: String,
+    pub saved_at: Timestamp,
+    /// OS user that saved the config
+    pub author: String,
+    /// Number of items in the saved config
+    pub item_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigChangeKind {
+    ItemAdded,
+    ItemRemoved,
+    FieldChanged,
+}
+
+/// A change between two consecutive snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ConfigFieldChange {
+    /// `id` of the changed integration or mapping
+    pub item_id: String,
+    pub kind: ConfigChangeKind,
+    /// Dotted field path; None for added and removed items
+    pub field: Option<String>,
+    /// Previous value (the whole item, as JSON, for removed items)
+    pub old_value: Option<String>,
+    /// New value (the whole item, as JSON, for added items)
+    pub new_value: Option<String>,
+    /// Snapshot that introduced the change
+    pub snapshot_id: String,
+    pub changed_at: Timestamp,
+    pub changed_by: String,
+}
+
+/// Changes between two snapshots, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ConfigHistoryDiff {
+    pub entity: ConfigHistoryEntity,
+    /// Start of the range; None means before the first snapshot
+    pub from: Option<ConfigSnapshotInfo>,
+    pub to: ConfigSnapshotInfo,
+    pub changes: Vec<ConfigFieldChange>,
+}
+
+/// Snapshot file contents.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct StoredSnapshot {
+    saved_at: Timestamp,
+    author: String,
+    items: Vec<Value>,
+}
+
+fn history_dir(config_dir: &Path, entity: ConfigHistoryEntity) -> PathBuf {
+    config_dir.join("history").join(entity.name())
+}
+
+fn snapshot_id(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%S%3fZ").to_string()
+}
+
+/// Records `items` as the newest snapshot of `entity`.
+///
+/// Saves that don't change anything since the last snapshot are skipped.
+pub fn record_snapshot<T: Serialize>(
+    config_dir: &Path,
+    entity: ConfigHistoryEntity,
+    items: &[T],
+) -> Result<(), String> {
+    let items = items
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to serialize config snapshot: {e}"))?;
+
+    let dir = history_dir(config_dir, entity);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create config history directory: {e}"))?;
+
+    let ids = snapshot_ids(&dir)?;
+    if let Some(latest) = ids.last() {
+        if read_snapshot(&dir, latest)?.items == items {
+            log::debug!("{} unchanged, skipping history snapshot", entity.name());
+            return Ok(());
+        }
+    }
+
+    let now = Utc::now();
+    let snapshot = StoredSnapshot {
+        saved_at: Timestamp::from_datetime(now),
+        author: current_user(),
+        items,
+    };
+    let content = serde_yaml::to_string(&snapshot)
+        .map_err(|e| format!("Failed to serialize config snapshot: {e}"))?;
+    let path = dir.join(format!("{}.yaml", snapshot_id(now)));
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write config snapshot: {e}"))?;
+    log::debug!("Recorded config snapshot {path:?}");
+
+    let excess = (ids.len() + 1).saturating_sub(MAX_SNAPSHOTS);
+    for id in ids.iter().take(excess) {
+        if let Err(e) = std::fs::remove_file(dir.join(format!("{id}.yaml"))) {
+            log::warn!("Failed to remove old config snapshot {id}: {e}");
+        }
+    }
+    Ok(())
+}
+
+/// Lists the snapshots of `entity`, oldest first.
+pub fn list_snapshots(
+    config_dir: &Path,
+    entity: ConfigHistoryEntity,
+) -> Result<Vec<ConfigSnapshotInfo>, String> {
+    let dir = history_dir(config_dir, entity);
+    snapshot_ids(&dir)?
+        .into_iter()
+        .map(|id| {
+            let snapshot = read_snapshot(&dir, &id)?;
+            Ok(snapshot_info(id, &snapshot))
+        })
+        .collect()
+}
+
+/// Diffs the snapshots of `entity` from `from` (exclusive) to `to`.
+///
+/// `from: None` starts before the first snapshot, so every item of the first
+/// one shows up as added; `to: None` ends at the latest snapshot.
+pub fn diff_history(
+    config_dir: &Path,
+    entity: ConfigHistoryEntity,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<ConfigHistoryDiff, String> {
+    let dir = history_dir(config_dir, entity);
+    let ids = snapshot_ids(&dir)?;
+    let position = |id: &str| {
+        ids.iter()
+            .position(|candidate| candidate == id)
+            .ok_or_else(|| format!("Config snapshot not found: {id}"))
+    };
+
+    let end = match to {
+        Some(id) => position(id)?,
+        None => ids
+            .len()
+            .checked_sub(1)
+            .ok_or_else(|| format!("No history recorded for {}", entity.name()))?,
+    };
+    let start = from.map(position).transpose()?;
+    if start.is_some_and(|start| start > end) {
+        return Err("The start snapshot is newer than the end snapshot".to_string());
+    }
+
+    let mut previous = match start {
+        Some(index) => Some((ids[index].clone(), read_snapshot(&dir, &ids[index])?)),
+        None => None,
+    };
+    let from_info = previous
+        .as_ref()
+        .map(|(id, snapshot)| snapshot_info(id.clone(), snapshot));
+
+    let mut changes = Vec::new();
+    for id in &ids[start.map_or(0, |start| start + 1)..=end] {
+        let snapshot = read_snapshot(&dir, id)?;
+        let old_items = previous.as_ref().map_or(&[][..], |(_, s)| &s.items[..]);
+        changes.extend(diff_items(old_items, id, &snapshot));
+        previous = Some((id.clone(), snapshot));
+    }
+
+    let (to_id, to_snapshot) = previous.expect("range contains the end snapshot");
+    Ok(ConfigHistoryDiff {
+        entity,
+        from: from_info,
+        to: snapshot_info(to_id, &to_snapshot),
+        changes,
+    })
+}
+
+fn snapshot_ids(dir: &Path) -> Result<Vec<String>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read config history directory: {e}"))?;
+
+    let mut ids: Vec<String> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_suffix(".yaml").map(str::to_string)
+        })
+        .collect();
+    ids.sort();
+    Ok(ids)
+}
+
+fn read_snapshot(dir: &Path, id: &str) -> Result<StoredSnapshot, String> {
+    let content = std::fs::read_to_string(dir.join(format!("{id}.yaml")))
+        .map_err(|e| format!("Failed to read config snapshot {id}: {e}"))?;
+    serde_yaml::from_str(&content).map_err(|e| format!("Failed to parse config snapshot {id}: {e}"))
+}
+
+fn snapshot_info(id: String, snapshot: &StoredSnapshot) -> ConfigSnapshotInfo {
+    ConfigSnapshotInfo {
+        id,
+        saved_at: snapshot.saved_at.clone(),
+        author: snapshot.author.clone(),
+        item_count: snapshot.items.len() as u32,
+    }
+}
+
+/// Field-level changes from `old` to the items of `snapshot`, matched by
+/// their `id`.
+fn diff_items(
+    old: &[Value],
+    snapshot_id: &str,
+    snapshot: &StoredSnapshot,
+) -> Vec<ConfigFieldChange> {
+    let new = &snapshot.items[..];
+    let item_id = |item: &Value| item["id"].as_str().unwrap_or_default().to_string();
+    let find = |items: &[Value], id: &str| items.iter().find(|item| item_id(item) == id).cloned();
+    let change = |item_id: String,
+                  kind: ConfigChangeKind,
+                  field: Option<String>,
+                  old_value: Option<String>,
+                  new_value: Option<String>| ConfigFieldChange {
+        item_id,
+        kind,
+        field,
+        old_value,
+        new_value,
+        snapshot_id: snapshot_id.to_string(),
+        changed_at: snapshot.saved_at.clone(),
+        changed_by: snapshot.author.clone(),
+    };
+
+    let mut changes = Vec::new();
+    for old_item in old {
+        let id = item_id(old_item);
+        let Some(new_item) = find(new, &id) else {
+            changes.push(change(
+                id,
+                ConfigChangeKind::ItemRemoved,
+                None,
+                Some(old_item.to_string()),
+                None,
+            ));
+            continue;
+        };
+
+        let mut old_fields = Vec::new();
+        let mut new_fields = Vec::new();
+        flatten("", old_item, &mut old_fields);
+        flatten("", &new_item, &mut new_fields);
+
+        let mut paths: Vec<&String> = old_fields
+            .iter()
+            .chain(&new_fields)
+            .map(|(p, _)| p)
+            .collect();
+        paths.sort();
+        paths.dedup();
+        for path in paths {
+            let value = |fields: &[(String, Option<String>)]| {
+                fields
+                    .iter()
+                    .find(|(p, _)| p == path)
+                    .and_then(|(_, v)| v.clone())
+            };
+            let (old_value, new_value) = (value(&old_fields), value(&new_fields));
+            if old_value != new_value {
+                changes.push(change(
+                    id.clone(),
+                    ConfigChangeKind::FieldChanged,
+                    Some(path.clone()),
+                    old_value,
+                    new_value,
+                ));
+            }
+        }
+    }
+    for new_item in new {
+        let id = item_id(new_item);
+        if find(old, &id).is_none() {
+            changes.push(change(
+                id,
+                ConfigChangeKind::ItemAdded,
+                None,
+                None,
+                Some(new_item.to_string()),
+            ));
+        }
+    }
+    changes
+}
+
+/// Flattens nested objects into dotted paths; other values are rendered as
+/// text (strings without quotes, null as None).
+fn flatten(prefix: &str, value: &Value, out: &mut Vec<(String, Option<String>)>) {
+    match value {
+        Value::Object(map) => flatten_object(prefix, map, out),
+        Value::Null => out.push((prefix.to_string(), None)),
+        Value::String(text) => out.push((prefix.to_string(), Some(text.clone()))),
+        other => out.push((prefix.to_string(), Some(other.to_string()))),
+    }
+}
+
+fn flatten_object(prefix: &str, map: &Map<String, Value>, out: &mut Vec<(String, Option<String>)>) {
+    for (key, value) in map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        flatten(&path, value, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_items_reports_field_changes() {
+        let old = vec![
+            json!({ "id": "prod", "base_url": "https://old", "credentials_ref": null }),
+            json!({ "id": "legacy", "base_url": "https://legacy" }),
+        ];
+        let snapshot = StoredSnapshot {
+            saved_at: Timestamp::parse("2024-05-01T10:00:00Z").unwrap(),
+            author: "alice".to_string(),
+            items: vec![
+                json!({ "id": "prod", "base_url": "https://new", "credentials_ref": "prod-token" }),
+                json!({ "id": "staging", "base_url": "https://staging" }),
+            ],
+        };
+
+        let changes = diff_items(&old, "20240501T100000000Z", &snapshot);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| {
+                (
+                    c.item_id.as_str(),
+                    c.kind.clone(),
+                    c.field.as_deref(),
+                    c.old_value.as_deref(),
+                    c.new_value.is_some(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "prod",
+                    ConfigChangeKind::FieldChanged,
+                    Some("base_url"),
+                    Some("https://old"),
+                    true
+                ),
+                (
+                    "prod",
+                    ConfigChangeKind::FieldChanged,
+                    Some("credentials_ref"),
+                    None,
+                    true
+                ),
+                (
+                    "legacy",
+                    ConfigChangeKind::ItemRemoved,
+                    None,
+                    Some(r#"{"base_url":"https://legacy","id":"legacy"}"#),
+                    false
+                ),
+                ("staging", ConfigChangeKind::ItemAdded, None, None, true),
+            ]
+        );
+        assert_eq!(changes[0].changed_by, "alice");
+        assert!(diff_items(&snapshot.items, "x", &snapshot).is_empty());
+    }
+
+    #[test]
+    fn test_flatten_nested_fields() {
+        let mut fields = Vec::new();
+        flatten(
+            "",
+            &json!({ "a": { "b": 1, "c": "x" }, "d": null }),
+            &mut fields,
+        );
+
+        assert_eq!(
+            fields,
+            vec![
+                ("a.b".to_string(), Some("1".to_string())),
+                ("a.c".to_string(), Some("x".to_string())),
+                ("d".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_history_round_trip() {
+        let config_dir =
+            std::env::temp_dir().join(format!("ops-flow-config-history-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&config_dir);
+        let entity = ConfigHistoryEntity::Integrations;
+
+        record_snapshot(
+            &config_dir,
+            entity,
+            &[json!({ "id": "prod", "base_url": "a" })],
+        )
+        .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        // Unchanged saves don't add snapshots
+        record_snapshot(
+            &config_dir,
+            entity,
+            &[json!({ "id": "prod", "base_url": "a" })],
+        )
+        .unwrap();
+        record_snapshot(
+            &config_dir,
+            entity,
+            &[json!({ "id": "prod", "base_url": "b" })],
+        )
+        .unwrap();
+
+        let snapshots = list_snapshots(&config_dir, entity).unwrap();
+        assert_eq!(snapshots.len(), 2);
+
+        let diff = diff_history(&config_dir, entity, Some(&snapshots[0].id), None).unwrap();
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].field.as_deref(), Some("base_url"));
+        assert_eq!(diff.changes[0].new_value.as_deref(), Some("b"));
+        assert_eq!(diff.changes[0].snapshot_id, snapshots[1].id);
+
+        let full = diff_history(&config_dir, entity, None, None).unwrap();
+        assert_eq!(full.changes[0].kind, ConfigChangeKind::ItemAdded);
+        assert_eq!(full.changes.len(), 2);
+
+        assert!(diff_history(&config_dir, entity, Some("missing"), None).is_err());
+        let _ = std::fs::remove_dir_all(&config_dir);
+    }
+}
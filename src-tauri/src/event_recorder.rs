@@ -0,0 +1,513 @@
+//! Kubernetes event recorder.
+//!
+//! Kubernetes only keeps events for about an hour. For every enabled event
+//! recording (`event_recordings.yaml`) the recorder watches the events of the
+//! recording's namespaces and appends each new occurrence to
+//! `<app data>/k8s_events/<integration>/<namespace>/<YYYY-MM-DD>.jsonl`, so
+//! the events of a time range can be queried long after the cluster dropped
+//! them. Files older than the recording's retention are deleted.
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::config::load_event_recordings;
+use crate::commands::kubernetes::kubernetes_adapter;
+use crate::feature_flags::{self, FeatureFlag};
+use crate::integrations::kubernetes::K8sEvent;
+use crate::metrics;
+use crate::types::EventRecording;
+
+/// How often the recordings are re-read and streams started or stopped.
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often expired event files are deleted.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Delay before reconnecting a stream whose adapter couldn't be created.
+const RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Days kept of namespaces no recording covers anymore.
+const ORPHAN_RETENTION_DAYS: u32 = 14;
+
+/// Occurrences remembered per stream to skip the ones a re-list repeats.
+const MAX_SEEN: usize = 10_000;
+
+/// Events returned at most by a query (the earliest ones).
+const MAX_QUERY_RESULTS: usize = 5_000;
+
+/// State of the event stream of one namespace.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct EventStreamStatus {
+    pub integration_id: String,
+    pub namespace: String,
+    /// Whether the watch is currently receiving events
+    pub connected: bool,
+    /// Events recorded since the app started
+    pub recorded: u32,
+    /// Last recorded occurrence (RFC 3339)
+    pub last_event_at: Option<String>,
+    /// Why the stream last failed
+    pub last_error: Option<String>,
+}
+
+/// Integration ID and namespace of a stream.
+type StreamKey = (String, String);
+
+static STREAMS: LazyLock<Mutex<HashMap<StreamKey, JoinHandle<()>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static STATUS: LazyLock<Mutex<BTreeMap<StreamKey, EventStreamStatus>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+fn streams() -> MutexGuard<'static, HashMap<StreamKey, JoinHandle<()>>> {
+    STREAMS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn status_of() -> MutexGuard<'static, BTreeMap<StreamKey, EventStreamStatus>> {
+    STATUS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Starts recording the namespaces of the enabled recordings in the
+/// background, following changes to the recordings.
+pub fn start_recording(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut last_prune: Option<DateTime<Utc>> = None;
+        loop {
+            match load_event_recordings(app.clone()).await {
+                Ok(recordings) => {
+                    // Turning the flag off stops the running streams
+                    if feature_flags::is_enabled(&app, FeatureFlag::WatchStreams) {
+                        reconcile(&app, &recordings);
+                    } else {
+                        reconcile(&app, &[]);
+                    }
+
+                    let now = Utc::now();
+                    let prune_due = last_prune.is_none_or(|last| {
+                        (now - last)
+                            .to_std()
+                            .is_ok_and(|elapsed| elapsed >= PRUNE_INTERVAL)
+                    });
+                    if prune_due {
+                        last_prune = Some(now);
+                        if let Err(e) = prune(&app, &recordings, now.date_naive()) {
+                            log::warn!("Failed to prune recorded events: {e}");
+                        }
+                    }
+                }
+                Err(e) => log::warn!("Failed to load event recordings: {e}"),
+            }
+            tokio::time::sleep(RECONCILE_INTERVAL).await;
+        }
+    });
+}
+
+/// Returns the state of every running stream.
+pub fn status() -> Vec<EventStreamStatus> {
+    status_of().values().cloned().collect()
+}
+
+/// Returns the recorded events of an integration whose last occurrence falls
+/// between `from` and `to` (RFC 3339), in one namespace or all of them,
+/// oldest first.
+pub fn query(
+    app: &AppHandle,
+    integration_id: &str,
+    namespace: Option<&str>,
+    from: &str,
+    to: &str,
+) -> Result<Vec<K8sEvent>, String> {
+    let from = parse_time(from)?;
+    let to = parse_time(to)?;
+    if from > to {
+        return Err("The start of the range is after its end".to_string());
+    }
+
+    let integration_dir = events_dir(app)?.join(path_component(integration_id)?);
+    let namespace_dirs = match namespace {
+        Some(namespace) => vec![integration_dir.join(path_component(namespace)?)],
+        None if integration_dir.exists() => read_dir(&integration_dir)?
+            .into_iter()
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.path())
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let mut events = Vec::new();
+    for dir in namespace_dirs {
+        for day in days_between(from.date_naive(), to.date_naive()) {
+            events.extend(read_day(&dir, day)?);
+        }
+    }
+    Ok(select_events(events, from, to))
+}
+
+/// Starts the streams of the enabled recordings and stops the others.
+fn reconcile(app: &AppHandle, recordings: &[EventRecording]) {
+    let wanted: HashSet<StreamKey> = recordings
+        .iter()
+        .filter(|r| r.enabled)
+        .flat_map(|r| {
+            r.namespaces
+                .iter()
+                .map(|namespace| (r.integration_id.clone(), namespace.clone()))
+        })
+        .collect();
+
+    let mut streams = streams();
+    streams.retain(|key, handle| {
+        let keep = wanted.contains(key);
+        if !keep {
+            handle.abort();
+            status_of().remove(key);
+            log::info!("Stopped recording events of {}/{}", key.0, key.1);
+        }
+        keep
+    });
+    for key in wanted {
+        if streams.contains_key(&key) {
+            continue;
+        }
+        status_of().insert(
+            key.clone(),
+            EventStreamStatus {
+                integration_id: key.0.clone(),
+                namespace: key.1.clone(),
+                connected: false,
+                recorded: 0,
+                last_event_at: None,
+                last_error: None,
+            },
+        );
+        log::info!("Recording events of {}/{}", key.0, key.1);
+        let handle = tauri::async_runtime::spawn(record_stream(app.clone(), key.clone()));
+        streams.insert(key, handle);
+    }
+}
+
+/// Records the events of one namespace until the stream is stopped.
+async fn record_stream(app: AppHandle, key: StreamKey) {
+    let (integration_id, namespace) = &key;
+    loop {
+        let adapter = match kubernetes_adapter(&app, integration_id).await {
+            Ok(adapter) => adapter,
+            Err(e) => {
+                metrics::record_poll("k8s_events", false);
+                update_status(&key, |s| {
+                    s.connected = false;
+                    s.last_error = Some(e);
+                });
+                tokio::time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+        };
+
+        let dir = match namespace_dir(&app, &key) {
+            Ok(dir) => dir,
+            Err(e) => {
+                log::error!("Cannot record events of {integration_id}/{namespace}: {e}");
+                return;
+            }
+        };
+        // Events the cluster still retains may have been recorded before a restart
+        let today = Utc::now().date_naive();
+        let mut seen: HashMap<(String, u32), DateTime<Utc>> = today
+            .pred_opt()
+            .into_iter()
+            .chain([today])
+            .flat_map(|day| read_day(&dir, day).unwrap_or_default())
+            .map(|event| (occurrence_key(&event), event.last_seen.datetime()))
+            .collect();
+
+        metrics::record_poll("k8s_events", true);
+        update_status(&key, |s| s.connected = true);
+
+        let mut events = Box::pin(adapter.watch_events(namespace));
+        while let Some(item) = events.next().await {
+            let event = match item {
+                Ok(event) => event,
+                Err(e) => {
+                    metrics::record_poll("k8s_events", false);
+                    update_status(&key, |s| {
+                        s.connected = false;
+                        s.last_error = Some(e.to_string());
+                    });
+                    continue;
+                }
+            };
+            update_status(&key, |s| s.connected = true);
+            let last_seen = event.last_seen.datetime();
+            if seen.insert(occurrence_key(&event), last_seen).is_some() {
+                continue;
+            }
+            if seen.len() > MAX_SEEN {
+                forget_oldest(&mut seen);
+            }
+
+            match append(&dir, &event) {
+                Ok(()) => update_status(&key, |s| {
+                    s.recorded += 1;
+                    s.last_event_at = Some(event.last_seen.rfc3339.clone());
+                }),
+                Err(e) => {
+                    log::warn!("Failed to record event of {integration_id}/{namespace}: {e}");
+                    update_status(&key, |s| s.last_error = Some(e));
+                }
+            }
+        }
+    }
+}
+
+fn update_status(key: &StreamKey, f: impl FnOnce(&mut EventStreamStatus)) {
+    if let Some(status) = status_of().get_mut(key) {
+        f(status);
+    }
+}
+
+/// Occurrences are identified by the event UID and its occurrence count.
+fn occurrence_key(event: &K8sEvent) -> (String, u32) {
+    (event.uid.clone(), event.count)
+}
+
+/// Forgets the older half of the remembered occurrences.
+fn forget_oldest(seen: &mut HashMap<(String, u32), DateTime<Utc>>) {
+    let mut times: Vec<DateTime<Utc>> = seen.values().copied().collect();
+    times.sort_unstable();
+    let cutoff = times[times.len() / 2];
+    seen.retain(|_, last_seen| *last_seen >= cutoff);
+}
+
+/// Deletes event files older than the retention of their recordings.
+fn prune(app: &AppHandle, recordings: &[EventRecording], today: NaiveDate) -> Result<(), String> {
+    let root = events_dir(app)?;
+    if !root.exists() {
+        return Ok(());
+    }
+
+    let mut retention: HashMap<(String, String), u32> = HashMap::new();
+    for recording in recordings {
+        for namespace in &recording.namespaces {
+            let (Ok(integration), Ok(namespace)) = (
+                path_component(&recording.integration_id),
+                path_component(namespace),
+            ) else {
+                continue;
+            };
+            let days = retention.entry((integration, namespace)).or_default();
+            *days = (*days).max(recording.retention_days);
+        }
+    }
+
+    for integration_dir in read_dir(&root)? {
+        let integration = integration_dir.file_name().to_string_lossy().to_string();
+        for namespace_dir in read_dir(&integration_dir.path())? {
+            let namespace = namespace_dir.file_name().to_string_lossy().to_string();
+            let days = retention
+                .get(&(integration.clone(), namespace))
+                .copied()
+                .unwrap_or(ORPHAN_RETENTION_DAYS);
+            for file in read_dir(&namespace_dir.path())? {
+                let name = file.file_name().to_string_lossy().to_string();
+                let day = name
+                    .strip_suffix(".jsonl")
+                    .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok());
+                if day.is_some_and(|day| is_expired(day, days, today)) {
+                    std::fs::remove_file(file.path())
+                        .map_err(|e| format!("Failed to delete {name}: {e}"))?;
+                    log::debug!("Deleted recorded events {}", file.path().display());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether the file of `day` falls outside a retention of `days`.
+fn is_expired(day: NaiveDate, days: u32, today: NaiveDate) -> bool {
+    today
+        .checked_sub_days(Days::new(u64::from(days)))
+        .is_some_and(|cutoff| day < cutoff)
+}
+
+/// Keeps the events last seen between `from` and `to`, once per occurrence,
+/// ordered by time.
+fn select_events(events: Vec<K8sEvent>, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<K8sEvent> {
+    let mut seen = HashSet::new();
+    let mut selected: Vec<K8sEvent> = events
+        .into_iter()
+        .filter(|e| (from..=to).contains(&e.last_seen.datetime()))
+        .filter(|e| seen.insert(occurrence_key(e)))
+        .collect();
+    selected.sort_by_key(|e| e.last_seen.datetime());
+    selected.truncate(MAX_QUERY_RESULTS);
+    selected
+}
+
+fn days_between(from: NaiveDate, to: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    from.iter_days().take_while(move |day| *day <= to)
+}
+
+fn parse_time(value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.to_utc())
+        .map_err(|e| format!("Invalid time '{value}': {e}"))
+}
+
+fn events_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    Ok(app_data_dir.join("k8s_events"))
+}
+
+fn namespace_dir(
+    app: &AppHandle,
+    (integration_id, namespace): &StreamKey,
+) -> Result<PathBuf, String> {
+    Ok(events_dir(app)?
+        .join(path_component(integration_id)?)
+        .join(path_component(namespace)?))
+}
+
+/// Keeps only characters that are safe in a path component.
+fn path_component(id: &str) -> Result<String, String> {
+    let sanitized: String = id
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+
+    if sanitized.is_empty() {
+        return Err(format!("Invalid ID: '{id}'"));
+    }
+    Ok(sanitized)
+}
+
+fn day_file(dir: &Path, day: NaiveDate) -> PathBuf {
+    dir.join(format!("{}.jsonl", day.format("%Y-%m-%d")))
+}
+
+/// Reads the events recorded on a day, skipping unreadable lines.
+fn read_day(dir: &Path, day: NaiveDate) -> Result<Vec<K8sEvent>, String> {
+    let path = day_file(dir, day);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read recorded events: {e}"))?;
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Appends an event to the file of the day it was last seen.
+fn append(dir: &Path, event: &K8sEvent) -> Result<(), String> {
+    let line =
+        serde_json::to_string(event).map_err(|e| format!("Failed to serialize event: {e}"))?;
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {e}"))?;
+
+    let path = day_file(dir, event.last_seen.datetime().date_naive());
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+    writeln!(file, "{line}").map_err(|e| format!("Failed to write event: {e}"))
+}
+
+fn read_dir(path: &Path) -> Result<Vec<std::fs::DirEntry>, String> {
+    std::fs::read_dir(path)
+        .and_then(|entries| entries.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Timestamp;
+
+    fn event(uid: &str, count: u32, last_seen: &str) -> K8sEvent {
+        let last_seen = Timestamp::parse(last_seen).unwrap();
+        K8sEvent {
+            uid: uid.to_string(),
+            namespace: "prod".to_string(),
+            object_kind: "Pod".to_string(),
+            object_name: "api-7d9f8-x2k4p".to_string(),
+            r#type: "Warning".to_string(),
+            reason: "BackOff".to_string(),
+            message: "Back-off restarting failed container".to_string(),
+            source: Some("kubelet".to_string()),
+            count,
+            first_seen: last_seen.clone(),
+            last_seen,
+        }
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_select_events() {
+        let events = vec![
+            event("b", 1, "2024-05-01T14:20:00Z"),
+            event("a", 1, "2024-05-01T13:59:59Z"),
+            event("a", 2, "2024-05-01T14:05:00Z"),
+            // Repeated by a re-list after a restart
+            event("a", 2, "2024-05-01T14:05:00Z"),
+            event("c", 1, "2024-05-01T14:31:00Z"),
+        ];
+
+        let selected = select_events(
+            events,
+            parse_time("2024-05-01T14:00:00Z").unwrap(),
+            parse_time("2024-05-01T16:30:00+02:00").unwrap(),
+        );
+
+        let keys: Vec<(&str, u32)> = selected.iter().map(|e| (e.uid.as_str(), e.count)).collect();
+        assert_eq!(keys, [("a", 2), ("b", 1)]);
+        assert!(parse_time("14:00").is_err());
+    }
+
+    #[test]
+    fn test_retention() {
+        let today = date("2024-05-15");
+
+        assert!(!is_expired(date("2024-05-01"), 14, today));
+        assert!(is_expired(date("2024-04-30"), 14, today));
+        assert!(is_expired(date("2024-05-14"), 0, today));
+        assert_eq!(
+            days_between(date("2024-04-30"), date("2024-05-02")).count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_forget_oldest() {
+        let mut seen: HashMap<(String, u32), DateTime<Utc>> = (0..4)
+            .map(|i| {
+                let time = parse_time(&format!("2024-05-01T14:0{i}:00Z")).unwrap();
+                ((format!("e{i}"), 1), time)
+            })
+            .collect();
+
+        forget_oldest(&mut seen);
+
+        let mut kept: Vec<&str> = seen.keys().map(|(uid, _)| uid.as_str()).collect();
+        kept.sort_unstable();
+        assert_eq!(kept, ["e2", "e3"]);
+    }
+}
@@ -0,0 +1,85 @@
+//! Feature flags.
+//!
+//! Experimental subsystems check their flag (`feature_flags` in preferences,
+//! defaults in [`FeatureFlags::default`]) before starting, so a risky
+//! feature can ship turned off and be enabled per user. Flags are read from
+//! the preferences file on every check; a saved change applies the next
+//! time the subsystem starts.
+
+use tauri::AppHandle;
+
+use crate::commands::preferences::read_preferences;
+use crate::types::FeatureFlags;
+
+/// A subsystem guarded by a feature flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureFlag {
+    WatchStreams,
+    WebhookReceiver,
+    FlowEngine,
+}
+
+impl FeatureFlag {
+    /// Name of the flag in the preferences.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::WatchStreams => "watch_streams",
+            Self::WebhookReceiver => "webhook_receiver",
+            Self::FlowEngine => "flow_engine",
+        }
+    }
+}
+
+impl FeatureFlags {
+    pub fn is_enabled(&self, flag: FeatureFlag) -> bool {
+        match flag {
+            FeatureFlag::WatchStreams => self.watch_streams,
+            FeatureFlag::WebhookReceiver => self.webhook_receiver,
+            FeatureFlag::FlowEngine => self.flow_engine,
+        }
+    }
+}
+
+/// Returns the flags in effect: the saved ones, or the defaults.
+pub fn current(app: &AppHandle) -> FeatureFlags {
+    read_preferences(app)
+        .and_then(|p| p.feature_flags)
+        .unwrap_or_default()
+}
+
+/// Whether a flag is on.
+pub fn is_enabled(app: &AppHandle, flag: FeatureFlag) -> bool {
+    current(app).is_enabled(flag)
+}
+
+/// Fails when a flag is off.
+pub fn ensure_enabled(app: &AppHandle, flag: FeatureFlag) -> Result<(), String> {
+    if is_enabled(app, flag) {
+        Ok(())
+    } else {
+        Err(format!(
+            "This feature is turned off (feature flag `{}`)",
+            flag.name()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_flags_take_their_default() {
+        let flags: FeatureFlags = serde_json::from_str(r#"{"flow_engine": false}"#).unwrap();
+        assert!(!flags.is_enabled(FeatureFlag::FlowEngine));
+        assert!(flags.is_enabled(FeatureFlag::WatchStreams));
+        assert!(flags.is_enabled(FeatureFlag::WebhookReceiver));
+    }
+
+    #[test]
+    fn test_preferences_without_flags_parse() {
+        let preferences: crate::types::AppPreferences =
+            serde_json::from_str(r#"{"theme": "dark", "quick_pane_shortcut": null}"#).unwrap();
+        assert_eq!(preferences.feature_flags, None);
+    }
+}
@@ -0,0 +1,251 @@
+//! Flow run artifacts.
+//!
+//! Nodes can attach files to a run (release notes, collected logs, diff
+//! reports). Artifacts outlive the in-memory run registry and are stored under
+//! `<app data>/flow_runs/<run id>/artifacts/<node id>/<name>`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::types::validate_filename;
+
+/// Largest artifact a node may write (10MB).
+const MAX_ARTIFACT_BYTES: usize = 10_485_760;
+
+/// Extensions of artifacts that are opened with the default application.
+/// Names and contents can come from templated, webhook-triggered runs, so
+/// anything else (scripts, HTML, ...) is only revealed in its folder.
+const INERT_EXTENSIONS: [&str; 5] = ["txt", "md", "json", "log", "diff"];
+
+/// A file attached to a flow run.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FlowRunArtifact {
+    /// Node that produced the artifact
+    pub node_id: String,
+    /// File name (e.g., "release-notes.md")
+    pub name: String,
+    pub size_bytes: u32,
+    /// Last modification timestamp (RFC 3339)
+    pub created_at: String,
+}
+
+/// Configuration of an `artifact` node, read from the node's `data` object.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactConfig {
+    /// File name (e.g., "release-notes.md")
+    pub name: String,
+    /// File content; usually built from templates
+    pub content: String,
+}
+
+/// Returns the directory holding all data of a run.
+pub fn run_dir(app: &AppHandle, run_id: &str) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+
+    Ok(app_data_dir.join("flow_runs").join(sanitize_id(run_id)?))
+}
+
+/// Lists the artifacts stored for a run.
+pub fn list_run_artifacts(app: &AppHandle, run_id: &str) -> Result<Vec<FlowRunArtifact>, String> {
+    list_artifacts(&run_dir(app, run_id)?)
+}
+
+/// Returns the path of an existing run artifact.
+pub fn run_artifact_path(
+    app: &AppHandle,
+    run_id: &str,
+    node_id: &str,
+    name: &str,
+) -> Result<PathBuf, String> {
+    let path = artifact_path(&run_dir(app, run_id)?, node_id, name)?;
+    if !path.is_file() {
+        return Err(format!("Artifact not found: {name}"));
+    }
+    Ok(path)
+}
+
+/// Whether an artifact is plain data that is safe to open with the default
+/// application.
+pub fn is_inert(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| INERT_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Writes an artifact for a node, replacing any previous file with the same name.
+pub fn write_artifact(
+    run_dir: &Path,
+    node_id: &str,
+    name: &str,
+    contents: &[u8],
+) -> Result<FlowRunArtifact, String> {
+    if contents.len() > MAX_ARTIFACT_BYTES {
+        return Err(format!(
+            "Artifact '{name}' is too large (max {MAX_ARTIFACT_BYTES} bytes)"
+        ));
+    }
+
+    let path = artifact_path(run_dir, node_id, name)?;
+    let dir = path
+        .parent()
+        .ok_or_else(|| "Invalid artifact path".to_string())?;
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create artifact directory: {e}"))?;
+
+    // Write to a temporary file first, then rename (atomic operation).
+    // `<name>.tmp` fails filename validation, so listings never show it.
+    let temp_path = path.with_file_name(format!("{name}.tmp"));
+    std::fs::write(&temp_path, contents).map_err(|e| {
+        log::error!("Failed to write artifact {path:?}: {e}");
+        format!("Failed to write artifact: {e}")
+    })?;
+    if let Err(rename_err) = std::fs::rename(&temp_path, &path) {
+        if let Err(remove_err) = std::fs::remove_file(&temp_path) {
+            log::warn!("Failed to remove temp file after rename failure: {remove_err}");
+        }
+        return Err(format!("Failed to finalize artifact: {rename_err}"));
+    }
+
+    log::debug!("Wrote artifact {path:?}");
+    read_metadata(&path, node_id, name)
+}
+
+/// Lists the artifacts of a run, ordered by node and name.
+pub fn list_artifacts(run_dir: &Path) -> Result<Vec<FlowRunArtifact>, String> {
+    let artifacts_dir = run_dir.join("artifacts");
+    if !artifacts_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut artifacts = Vec::new();
+    for node_entry in read_dir(&artifacts_dir)? {
+        let node_path = node_entry.path();
+        if !node_path.is_dir() {
+            continue;
+        }
+        let node_id = node_entry.file_name().to_string_lossy().to_string();
+
+        for file_entry in read_dir(&node_path)? {
+            let path = file_entry.path();
+            let name = file_entry.file_name().to_string_lossy().to_string();
+            if !path.is_file() || validate_filename(&name).is_err() {
+                continue;
+            }
+            artifacts.push(read_metadata(&path, &node_id, &name)?);
+        }
+    }
+
+    artifacts.sort_by(|a, b| (&a.node_id, &a.name).cmp(&(&b.node_id, &b.name)));
+    Ok(artifacts)
+}
+
+/// Returns the path of an artifact after validating its components.
+pub fn artifact_path(run_dir: &Path, node_id: &str, name: &str) -> Result<PathBuf, String> {
+    validate_filename(name)?;
+    Ok(run_dir
+        .join("artifacts")
+        .join(sanitize_id(node_id)?)
+        .join(name))
+}
+
+/// Keeps only characters that are safe in a path component.
+fn sanitize_id(id: &str) -> Result<String, String> {
+    let sanitized: String = id
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+
+    if sanitized.is_empty() {
+        return Err(format!("Invalid ID: '{id}'"));
+    }
+    Ok(sanitized)
+}
+
+fn read_dir(path: &Path) -> Result<Vec<std::fs::DirEntry>, String> {
+    std::fs::read_dir(path)
+        .and_then(|entries| entries.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| format!("Failed to read artifact directory: {e}"))
+}
+
+fn read_metadata(path: &Path, node_id: &str, name: &str) -> Result<FlowRunArtifact, String> {
+    let metadata =
+        std::fs::metadata(path).map_err(|e| format!("Failed to read artifact metadata: {e}"))?;
+    let created_at = metadata
+        .modified()
+        .map(|t| DateTime::<Utc>::from(t).to_rfc3339())
+        .unwrap_or_default();
+
+    Ok(FlowRunArtifact {
+        node_id: node_id.to_string(),
+        name: name.to_string(),
+        size_bytes: u32::try_from(metadata.len()).unwrap_or(u32::MAX),
+        created_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_run_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("ops-flow-artifacts-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_write_and_list_artifacts() {
+        let dir = temp_run_dir("list");
+        write_artifact(&dir, "notes", "release-notes.md", b"# v1.4.2").unwrap();
+        write_artifact(&dir, "collect", "pods.log", b"ready").unwrap();
+
+        let artifacts = list_artifacts(&dir).unwrap();
+        let names: Vec<(&str, &str)> = artifacts
+            .iter()
+            .map(|a| (a.node_id.as_str(), a.name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("collect", "pods.log"), ("notes", "release-notes.md")]
+        );
+        assert_eq!(artifacts[1].size_bytes, 8);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_path_traversal() {
+        let dir = temp_run_dir("traversal");
+        assert!(artifact_path(&dir, "node", "../secrets").is_err());
+        assert!(artifact_path(&dir, "..", "notes.md").is_err());
+        assert_eq!(
+            artifact_path(&dir, "../deploy", "notes.md").unwrap(),
+            dir.join("artifacts").join("deploy").join("notes.md")
+        );
+    }
+
+    #[test]
+    fn test_only_text_artifacts_are_inert() {
+        assert!(is_inert("release-notes.md"));
+        assert!(is_inert("pods.LOG"));
+        assert!(!is_inert("deploy.sh"));
+        assert!(!is_inert("report.html"));
+        assert!(!is_inert("notes.md.command"));
+        assert!(!is_inert("README"));
+    }
+
+    #[test]
+    fn test_missing_dir_lists_nothing() {
+        let dir = temp_run_dir("missing");
+        assert!(list_artifacts(&dir).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,27 @@
+//! Verification nodes.
+//!
+//! A `smoke_tests` node runs the smoke tests defined for an environment and
+//! fails unless all of them pass, so deploy flows can verify themselves.
+
+use serde::Deserialize;
+use tauri::AppHandle;
+
+use crate::checks::smoke;
+
+/// Configuration of a `smoke_tests` node, read from the node's `data` object.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SmokeTestsConfig {
+    /// Environment whose smoke tests are run
+    pub environment_id: String,
+}
+
+/// Runs the smoke tests and returns the node message.
+pub async fn run_smoke_tests(app: &AppHandle, config: &SmokeTestsConfig) -> Result<String, String> {
+    let report = smoke::run_for_environment(app, &config.environment_id).await?;
+    if report.passed {
+        Ok(report.summary())
+    } else {
+        Err(report.summary())
+    }
+}
@@ -0,0 +1,419 @@
+//! Deployment nodes.
+//!
+//! A `k8s_set_image` node changes the image of a deployment container
+//! directly, so simple image-bump deployments don't need a CI round-trip.
+//! A `k8s_canary` node starts, promotes or aborts a canary deployment.
+//! A `k8s_apply` node applies multi-document manifests in sync waves.
+//! All fields may use templates (e.g., `registry/api:{{ inputs.version }}`).
+//! Images are checked against registry vulnerability scans before they are
+//! deployed; blocked images fail the node without touching the cluster.
+//! Nodes also fail during a GitLab deploy freeze of a project mapped to the
+//! target namespace, unless `overrideFreeze` is set.
+
+use serde::Deserialize;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::checks::{deploy_freeze, image_security};
+use crate::commands::kubernetes::kubernetes_adapter;
+use crate::integrations::kubernetes::{plan_waves, ApplyWave};
+
+/// How long each apply wave may take to become ready by default.
+const DEFAULT_READINESS_TIMEOUT_SECONDS: u32 = 300;
+
+/// Configuration of a `k8s_set_image` node, read from the node's `data` object.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SetImageConfig {
+    /// Kubernetes integration to use
+    pub integration_id: String,
+    pub namespace: String,
+    pub deployment: String,
+    pub container: String,
+    /// New image reference (e.g., "registry/api:1.4.2")
+    pub image: String,
+    /// Deploy even during a freeze period
+    #[serde(default)]
+    pub override_freeze: bool,
+}
+
+impl SetImageConfig {
+    /// Checks that every field is set.
+    pub fn validate(&self) -> Result<(), String> {
+        let fields = [
+            ("integrationId", &self.integration_id),
+            ("namespace", &self.namespace),
+            ("deployment", &self.deployment),
+            ("container", &self.container),
+            ("image", &self.image),
+        ];
+        match fields.iter().find(|(_, value)| value.trim().is_empty()) {
+            Some((name, _)) => Err(format!("'{name}' is required")),
+            None => Ok(()),
+        }
+    }
+
+    /// Describes the change for run messages.
+    pub fn describe(&self) -> String {
+        format!(
+            "{}/{} container {} to {}",
+            self.namespace, self.deployment, self.container, self.image
+        )
+    }
+}
+
+/// What a `k8s_canary` node does.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CanaryAction {
+    Start,
+    Promote,
+    Abort,
+}
+
+/// Configuration of a `k8s_canary` node, read from the node's `data` object.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CanaryConfig {
+    /// Kubernetes integration to use
+    pub integration_id: String,
+    pub namespace: String,
+    /// Stable deployment the canary belongs to
+    pub deployment: String,
+    pub action: CanaryAction,
+    /// Canary image (required to start)
+    pub image: Option<String>,
+    /// Share of traffic for the canary (required to start)
+    pub percent: Option<u32>,
+    /// Start or promote even during a freeze period
+    #[serde(default)]
+    pub override_freeze: bool,
+}
+
+impl CanaryConfig {
+    /// Checks that the fields required by the action are set.
+    pub fn validate(&self) -> Result<(), String> {
+        let fields = [
+            ("integrationId", &self.integration_id),
+            ("namespace", &self.namespace),
+            ("deployment", &self.deployment),
+        ];
+        if let Some((name, _)) = fields.iter().find(|(_, value)| value.trim().is_empty()) {
+            return Err(format!("'{name}' is required"));
+        }
+
+        if self.action == CanaryAction::Start {
+            if self.image.as_deref().is_none_or(|i| i.trim().is_empty()) {
+                return Err("'image' is required to start a canary".to_string());
+            }
+            match self.percent {
+                Some(1..=99) => {}
+                Some(p) => return Err(format!("'percent' must be between 1 and 99, got {p}")),
+                None => return Err("'percent' is required to start a canary".to_string()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Describes the action for run messages.
+    pub fn describe(&self) -> String {
+        let target = format!("{}/{}", self.namespace, self.deployment);
+        match self.action {
+            CanaryAction::Start => format!(
+                "start canary of {target} with {} at {}%",
+                self.image.as_deref().unwrap_or_default(),
+                self.percent.unwrap_or_default()
+            ),
+            CanaryAction::Promote => format!("promote canary of {target}"),
+            CanaryAction::Abort => format!("abort canary of {target}"),
+        }
+    }
+}
+
+/// Configuration of a `k8s_apply` node, read from the node's `data` object.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyConfig {
+    /// Kubernetes integration to use
+    pub integration_id: String,
+    /// Namespace of namespaced resources that don't set one
+    pub namespace: Option<String>,
+    /// Multi-document YAML manifests
+    pub manifests: String,
+    /// How long each wave may take to become ready
+    pub readiness_timeout_seconds: Option<u32>,
+    /// Apply even during a freeze period
+    #[serde(default)]
+    pub override_freeze: bool,
+}
+
+impl ApplyConfig {
+    /// Checks that the integration is set and the manifests parse.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.integration_id.trim().is_empty() {
+            return Err("'integrationId' is required".to_string());
+        }
+        match self.waves()?.is_empty() {
+            true => Err("'manifests' contains no resources".to_string()),
+            false => Ok(()),
+        }
+    }
+
+    /// Groups the manifests into waves in application order.
+    pub fn waves(&self) -> Result<Vec<ApplyWave>, String> {
+        plan_waves(&self.manifests).map_err(|e| e.to_string())
+    }
+
+    /// Describes the waves for run messages.
+    pub fn describe(&self) -> String {
+        match self.waves() {
+            Ok(waves) => describe_waves(&waves),
+            Err(e) => e,
+        }
+    }
+}
+
+fn describe_waves(waves: &[ApplyWave]) -> String {
+    let resources: usize = waves.iter().map(|w| w.resources.len()).sum();
+    let order: Vec<String> = waves
+        .iter()
+        .enumerate()
+        .map(|(index, wave)| format!("{}. {}", index + 1, wave.describe()))
+        .collect();
+    format!(
+        "{resources} resource(s) in {} wave(s): {}",
+        waves.len(),
+        order.join("; ")
+    )
+}
+
+/// Appends the note of an overridden freeze to a node message.
+fn with_freeze_note(message: String, note: Option<String>) -> String {
+    match note {
+        Some(note) => format!("{message} ({note})"),
+        None => message,
+    }
+}
+
+/// Applies the manifests wave by wave and returns the node message.
+pub async fn apply(app: &AppHandle, config: &ApplyConfig) -> Result<String, String> {
+    let waves = config.waves()?;
+
+    let mut namespaces: Vec<&str> = waves
+        .iter()
+        .flat_map(|w| &w.resources)
+        .filter_map(|r| r.namespace.as_deref())
+        .chain(config.namespace.as_deref())
+        .collect();
+    namespaces.sort_unstable();
+    namespaces.dedup();
+    let freeze_note =
+        deploy_freeze::ensure_not_frozen(app, &namespaces, config.override_freeze).await?;
+
+    // Check every image before the first wave touches the cluster
+    let mut images: Vec<String> = waves
+        .iter()
+        .flat_map(|w| &w.resources)
+        .flat_map(|r| r.images())
+        .collect();
+    images.sort();
+    images.dedup();
+    for image in &images {
+        image_security::ensure_deployable(app, image).await?;
+    }
+
+    let adapter = kubernetes_adapter(app, &config.integration_id).await?;
+    let timeout = config
+        .readiness_timeout_seconds
+        .unwrap_or(DEFAULT_READINESS_TIMEOUT_SECONDS);
+    let total = waves.len();
+    adapter
+        .apply_waves(
+            config.namespace.as_deref(),
+            &waves,
+            Duration::from_secs(u64::from(timeout)),
+            |index, wave| {
+                log::info!(
+                    "Applying wave {} of {total} (sync wave {}): {}",
+                    index + 1,
+                    wave.sync_wave,
+                    wave.describe()
+                );
+            },
+        )
+        .await
+        .map_err(|e| format!("Failed to apply manifests: {e}"))?;
+
+    Ok(with_freeze_note(
+        format!("Applied {}", describe_waves(&waves)),
+        freeze_note,
+    ))
+}
+
+/// Applies the image change and returns the node message.
+pub async fn set_image(app: &AppHandle, config: &SetImageConfig) -> Result<String, String> {
+    let freeze_note =
+        deploy_freeze::ensure_not_frozen(app, &[&config.namespace], config.override_freeze).await?;
+    image_security::ensure_deployable(app, &config.image).await?;
+    let adapter = kubernetes_adapter(app, &config.integration_id).await?;
+    let update = adapter
+        .set_deployment_image(
+            &config.namespace,
+            &config.deployment,
+            &config.container,
+            &config.image,
+        )
+        .await
+        .map_err(|e| format!("Failed to set deployment image: {e}"))?;
+
+    let message = match update.previous_image {
+        Some(previous) => format!("Set image of {} (was {previous})", config.describe()),
+        None => format!("Set image of {}", config.describe()),
+    };
+    Ok(with_freeze_note(message, freeze_note))
+}
+
+/// Runs the canary action and returns the node message.
+pub async fn run_canary(app: &AppHandle, config: &CanaryConfig) -> Result<String, String> {
+    let adapter = kubernetes_adapter(app, &config.integration_id).await?;
+    let (namespace, deployment) = (&config.namespace, &config.deployment);
+
+    // Aborting only rolls back, so it's allowed during a freeze
+    let freeze_note = match config.action {
+        CanaryAction::Start | CanaryAction::Promote => {
+            deploy_freeze::ensure_not_frozen(app, &[namespace], config.override_freeze).await?
+        }
+        CanaryAction::Abort => None,
+    };
+
+    match config.action {
+        CanaryAction::Start => {
+            let image = config.image.as_deref().unwrap_or_default();
+            image_security::ensure_deployable(app, image).await?;
+            let status = adapter
+                .start_canary(
+                    namespace,
+                    deployment,
+                    image,
+                    config.percent.unwrap_or_default(),
+                )
+                .await
+                .map_err(|e| format!("Failed to start canary: {e}"))?;
+            let message = format!(
+                "Canary {} runs {image} on {} of {} replicas (~{}% of traffic)",
+                status.canary_deployment,
+                status.canary_replicas,
+                status.canary_replicas + status.stable_replicas,
+                status.traffic_percent
+            );
+            Ok(with_freeze_note(message, freeze_note))
+        }
+        CanaryAction::Promote => {
+            let updates = adapter
+                .promote_canary(namespace, deployment)
+                .await
+                .map_err(|e| format!("Failed to promote canary: {e}"))?;
+            let images: Vec<String> = updates
+                .iter()
+                .map(|u| format!("{}={}", u.container, u.image))
+                .collect();
+            let message = if images.is_empty() {
+                format!("Promoted canary of {namespace}/{deployment} (images unchanged)")
+            } else {
+                format!(
+                    "Promoted canary of {namespace}/{deployment}: {}",
+                    images.join(", ")
+                )
+            };
+            Ok(with_freeze_note(message, freeze_note))
+        }
+        CanaryAction::Abort => {
+            adapter
+                .abort_canary(namespace, deployment)
+                .await
+                .map_err(|e| format!("Failed to abort canary: {e}"))?;
+            Ok(format!("Aborted canary of {namespace}/{deployment}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_validate() {
+        let config: SetImageConfig = serde_json::from_value(serde_json::json!({
+            "integrationId": "k8s-prod",
+            "namespace": "shop",
+            "deployment": "api",
+            "container": "api",
+            "image": "registry/api:1.4.2"
+        }))
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.describe(),
+            "shop/api container api to registry/api:1.4.2"
+        );
+
+        let missing = SetImageConfig {
+            image: " ".to_string(),
+            ..config
+        };
+        assert_eq!(missing.validate().unwrap_err(), "'image' is required");
+    }
+
+    #[test]
+    fn test_canary_validation() {
+        let config: CanaryConfig = serde_json::from_value(serde_json::json!({
+            "integrationId": "k8s-prod",
+            "namespace": "shop",
+            "deployment": "api",
+            "action": "start",
+            "image": "registry/api:1.4.2",
+            "percent": 10
+        }))
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        let too_much = CanaryConfig {
+            percent: Some(100),
+            ..config.clone()
+        };
+        assert!(too_much.validate().is_err());
+
+        let promote = CanaryConfig {
+            action: CanaryAction::Promote,
+            image: None,
+            percent: None,
+            ..config
+        };
+        assert!(promote.validate().is_ok());
+        assert_eq!(promote.describe(), "promote canary of shop/api");
+    }
+
+    #[test]
+    fn test_apply_validation() {
+        let config: ApplyConfig = serde_json::from_value(serde_json::json!({
+            "integrationId": "k8s-prod",
+            "namespace": "shop",
+            "manifests": "apiVersion: apps/v1\nkind: Deployment\nmetadata:\n  name: api\n---\napiVersion: v1\nkind: ConfigMap\nmetadata:\n  name: api"
+        }))
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.describe(),
+            "2 resource(s) in 2 wave(s): 1. ConfigMap/api; 2. Deployment/api"
+        );
+
+        let empty = ApplyConfig {
+            manifests: "---\n".to_string(),
+            ..config
+        };
+        assert_eq!(
+            empty.validate().unwrap_err(),
+            "'manifests' contains no resources"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Environment variables in the run context.
+//!
+//! A flow run in an environment sees the environment's catalog as
+//! `env.<key>` (plus `env.name` and `env.namespace`), so one flow can render
+//! different domains, replica counts or registries per environment.
+
+use std::collections::HashMap;
+
+use crate::types::Environment;
+
+/// Prefix of environment variables in the run context.
+const ENV_PREFIX: &str = "env.";
+
+/// Keys set from the environment itself rather than its catalog.
+const RESERVED_KEYS: [&str; 2] = ["name", "namespace"];
+
+/// Parts of keys that suggest a secret, which doesn't belong in the catalog.
+const SECRET_KEY_PARTS: [&str; 5] = ["password", "secret", "token", "apikey", "privatekey"];
+
+/// Builds the run context of an environment.
+pub fn context(environment: &Environment) -> HashMap<String, String> {
+    let mut variables: HashMap<String, String> = environment
+        .variables
+        .iter()
+        .map(|(key, value)| (format!("{ENV_PREFIX}{key}"), value.clone()))
+        .collect();
+    variables.insert(format!("{ENV_PREFIX}name"), environment.name.clone());
+    if let Some(namespace) = &environment.namespace {
+        variables.insert(format!("{ENV_PREFIX}namespace"), namespace.clone());
+    }
+    variables
+}
+
+/// Checks that the catalog keys can be used in templates and don't look
+/// like secrets, since environments are saved in plain text.
+pub fn validate_variables(environment: &Environment) -> Result<(), String> {
+    for key in environment.variables.keys() {
+        let valid = !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid {
+            return Err(format!(
+                "Invalid variable '{key}' in environment '{}': use letters, digits, '_' and '-'",
+                environment.name
+            ));
+        }
+        if RESERVED_KEYS.contains(&key.as_str()) {
+            return Err(format!(
+                "Variable '{key}' in environment '{}' is reserved",
+                environment.name
+            ));
+        }
+        let normalized = key.to_lowercase().replace(['_', '-'], "");
+        if SECRET_KEY_PARTS
+            .iter()
+            .any(|part| normalized.contains(part))
+        {
+            return Err(format!(
+                "Variable '{key}' in environment '{}' looks like a secret; \
+                 store secrets as integration credentials instead",
+                environment.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn environment(variables: &[(&str, &str)]) -> Environment {
+        Environment {
+            id: "env-staging".to_string(),
+            name: "staging".to_string(),
+            namespace: Some("checkout-staging".to_string()),
+            project_id: "checkout".to_string(),
+            variables: variables
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn test_context() {
+        let context = context(&environment(&[("domain", "staging.example.com")]));
+
+        assert_eq!(context["env.domain"], "staging.example.com");
+        assert_eq!(context["env.name"], "staging");
+        assert_eq!(context["env.namespace"], "checkout-staging");
+    }
+
+    #[test]
+    fn test_validate_variables() {
+        assert!(validate_variables(&environment(&[
+            ("domain", "staging.example.com"),
+            ("image_registry", "registry.example.com/checkout"),
+            ("default-replicas", "2"),
+        ]))
+        .is_ok());
+
+        for key in [
+            "image.registry",
+            "",
+            "name",
+            "DB_PASSWORD",
+            "api-key-x",
+            "apiKey",
+        ] {
+            assert!(
+                validate_variables(&environment(&[(key, "x")])).is_err(),
+                "{key} should be rejected"
+            );
+        }
+    }
+}
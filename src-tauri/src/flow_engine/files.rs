@@ -0,0 +1,105 @@
+//! Repository file nodes.
+//!
+//! A `gitlab_file` node reads a file from a GitLab repository into the run
+//! context, so later nodes can use configuration such as a Helm values file
+//! (e.g., `{{ files.values }}` in an artifact) without cloning the repo.
+
+use serde::Deserialize;
+use tauri::AppHandle;
+
+use crate::commands::gitlab::gitlab_adapter;
+use crate::integrations::gitlab::GitLabFile;
+
+/// Configuration of a `gitlab_file` node, read from the node's `data` object.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLabFileConfig {
+    /// GitLab integration to use
+    pub integration_id: String,
+    pub project_id: u32,
+    /// Path within the repository (e.g., ".gitlab-ci.yml")
+    pub path: String,
+    /// Branch, tag or commit (default branch when unset)
+    #[serde(rename = "ref")]
+    pub ref_name: Option<String>,
+    /// Context variable name (`files.<variable>`), defaults to the node ID
+    pub variable: Option<String>,
+}
+
+impl GitLabFileConfig {
+    /// Checks that the integration and path are set.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.integration_id.trim().is_empty() {
+            return Err("'integrationId' is required".to_string());
+        }
+        if self.path.trim().is_empty() {
+            return Err("'path' is required".to_string());
+        }
+        Ok(())
+    }
+
+    /// Returns the context key the file content is stored under.
+    pub fn context_key(&self, node_id: &str) -> String {
+        let name = self
+            .variable
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .unwrap_or(node_id);
+        format!("files.{name}")
+    }
+
+    /// Describes the file for run messages.
+    pub fn describe(&self) -> String {
+        match &self.ref_name {
+            Some(ref_name) => format!("{} of project {} at {ref_name}", self.path, self.project_id),
+            None => format!("{} of project {}", self.path, self.project_id),
+        }
+    }
+}
+
+/// Reads the file; truncated files fail the node rather than passing on
+/// partial configuration.
+pub async fn read_file(app: &AppHandle, config: &GitLabFileConfig) -> Result<GitLabFile, String> {
+    let adapter = gitlab_adapter(app, &config.integration_id).await?;
+    let file = adapter
+        .fetch_file(config.project_id, &config.path, config.ref_name.as_deref())
+        .await
+        .map_err(|e| format!("Failed to read {}: {e}", config.describe()))?;
+
+    if file.truncated {
+        return Err(format!("{} is larger than 1MB", config.describe()));
+    }
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_context_key() {
+        let config: GitLabFileConfig = serde_json::from_value(serde_json::json!({
+            "integrationId": "gitlab",
+            "projectId": 42,
+            "path": "charts/api/values.yaml",
+            "ref": "main",
+            "variable": "values"
+        }))
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.context_key("node-3"), "files.values");
+        assert_eq!(
+            config.describe(),
+            "charts/api/values.yaml of project 42 at main"
+        );
+
+        let config = GitLabFileConfig {
+            variable: None,
+            path: " ".to_string(),
+            ..config
+        };
+        assert_eq!(config.context_key("node-3"), "files.node-3");
+        assert_eq!(config.validate().unwrap_err(), "'path' is required");
+    }
+}
@@ -0,0 +1,321 @@
+//! Wait nodes and time-window gates.
+//!
+//! These nodes do not call any integration. They only hold a run until a
+//! point in time, which is what change-management policies usually require
+//! ("wait 5 minutes after deploy", "only deploy between 22:00 and 02:00").
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveTime, TimeZone, Utc, Weekday};
+use serde::Deserialize;
+
+/// Longest `duration` wait (7 days); runs are not kept across restarts.
+pub const MAX_WAIT_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// Configuration of a `wait` node, read from the node's `data` object.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub enum WaitConfig {
+    /// Wait for a fixed number of seconds (e.g., "wait 5 minutes")
+    Duration { seconds: u64 },
+    /// Wait until the next occurrence of a wall-clock time (e.g., "wait until 02:00")
+    Until {
+        time: String,
+        /// Interpret `time` in UTC instead of the local time zone
+        #[serde(default)]
+        utc: bool,
+    },
+}
+
+impl WaitConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            WaitConfig::Duration { seconds } if *seconds > MAX_WAIT_SECONDS => Err(format!(
+                "Wait duration too large: {seconds} seconds (at most {MAX_WAIT_SECONDS})"
+            )),
+            WaitConfig::Duration { .. } | WaitConfig::Until { .. } => Ok(()),
+        }
+    }
+
+    /// Returns how long the node must wait, starting at `now`.
+    pub fn remaining(&self, now: DateTime<Utc>) -> Result<Duration, String> {
+        match self {
+            WaitConfig::Duration { seconds } => {
+                self.validate()?;
+                i64::try_from(*seconds)
+                    .ok()
+                    .and_then(Duration::try_seconds)
+                    .ok_or_else(|| "Wait duration too large".to_string())
+            }
+            WaitConfig::Until { time, utc } => {
+                let target = parse_time_of_day(time)?;
+                let next = if *utc {
+                    next_occurrence(target, now)
+                } else {
+                    next_occurrence(target, now.with_timezone(&Local)).with_timezone(&Utc)
+                };
+                Ok(next - now)
+            }
+        }
+    }
+
+    /// Returns when the node stops waiting, starting at `now`.
+    pub fn deadline(&self, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+        now.checked_add_signed(self.remaining(now)?)
+            .ok_or_else(|| "Wait duration too large".to_string())
+    }
+}
+
+/// What a time-window gate does when the run reaches it outside the window.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutsideWindowAction {
+    /// Hold the run until the window opens
+    #[default]
+    Wait,
+    /// Fail the node immediately
+    Fail,
+}
+
+/// Configuration of a `time_window` gate node.
+///
+/// A window whose `end` is earlier than its `start` spans midnight and
+/// belongs to the day it opens on (e.g., Friday 22:00 - Saturday 02:00).
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeWindowConfig {
+    /// Days the window opens on ("mon", "tuesday", ...). Empty means every day.
+    #[serde(default)]
+    pub days: Vec<String>,
+    /// Opening time ("HH:MM")
+    pub start: String,
+    /// Closing time ("HH:MM")
+    pub end: String,
+    /// Interpret times in UTC instead of the local time zone
+    #[serde(default)]
+    pub utc: bool,
+    /// Behavior when the gate is reached outside the window
+    #[serde(default)]
+    pub on_outside: OutsideWindowAction,
+}
+
+/// Result of evaluating a time-window gate at a given instant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowDecision {
+    /// The window is open; the run may proceed
+    Open,
+    /// The window is closed and opens again at the given instant
+    OpensAt(DateTime<Utc>),
+}
+
+impl TimeWindowConfig {
+    /// Evaluates the gate at `now`.
+    pub fn evaluate(&self, now: DateTime<Utc>) -> Result<WindowDecision, String> {
+        let window = ParsedWindow::parse(self)?;
+        let decision = if self.utc {
+            window.evaluate(now)
+        } else {
+            window.evaluate(now.with_timezone(&Local))
+        };
+        Ok(decision)
+    }
+}
+
+/// A time-window configuration with validated days and times.
+struct ParsedWindow {
+    days: Vec<Weekday>,
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl ParsedWindow {
+    fn parse(config: &TimeWindowConfig) -> Result<Self, String> {
+        let days = config
+            .days
+            .iter()
+            .map(|day| {
+                day.trim()
+                    .parse::<Weekday>()
+                    .map_err(|_| format!("Invalid day in time window: '{day}'"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let start = parse_time_of_day(&config.start)?;
+        let end = parse_time_of_day(&config.end)?;
+        if start == end {
+            return Err("Time window start and end must differ".to_string());
+        }
+
+        Ok(Self { days, start, end })
+    }
+
+    fn opens_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+
+    fn evaluate<Tz: TimeZone>(&self, now: DateTime<Tz>) -> WindowDecision {
+        let today = now.weekday();
+        let time = now.time();
+
+        let open = if self.start < self.end {
+            self.opens_on(today) && time >= self.start && time < self.end
+        } else {
+            // Overnight window: open from `start` until midnight on an allowed day,
+            // or from midnight until `end` on the day after an allowed day.
+            (self.opens_on(today) && time >= self.start)
+                || (self.opens_on(today.pred()) && time < self.end)
+        };
+
+        if open {
+            return WindowDecision::Open;
+        }
+
+        // Look ahead up to a week for the next allowed opening time.
+        let mut candidate = next_occurrence(self.start, now.clone());
+        for _ in 0..7 {
+            if self.opens_on(candidate.weekday()) {
+                break;
+            }
+            candidate = next_occurrence(self.start, candidate);
+        }
+        WindowDecision::OpensAt(candidate.with_timezone(&Utc))
+    }
+}
+
+/// Parses a wall-clock time in "HH:MM" or "HH:MM:SS" format.
+pub fn parse_time_of_day(value: &str) -> Result<NaiveTime, String> {
+    let value = value.trim();
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M:%S"))
+        .map_err(|_| format!("Invalid time '{value}': expected HH:MM"))
+}
+
+/// Returns the first instant strictly after `now` whose wall-clock time is `time`.
+fn next_occurrence<Tz: TimeZone>(time: NaiveTime, now: DateTime<Tz>) -> DateTime<Tz> {
+    let tz = now.timezone();
+    let mut date = now.date_naive();
+    loop {
+        // `earliest()` skips over times that do not exist due to DST transitions
+        if let Some(candidate) = tz.from_local_datetime(&date.and_time(time)).earliest() {
+            if candidate > now {
+                return candidate;
+            }
+        }
+        date = date.succ_opt().unwrap_or(date);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn window(days: &[&str], start: &str, end: &str) -> TimeWindowConfig {
+        TimeWindowConfig {
+            days: days.iter().map(|d| d.to_string()).collect(),
+            start: start.to_string(),
+            end: end.to_string(),
+            utc: true,
+            on_outside: OutsideWindowAction::Wait,
+        }
+    }
+
+    #[test]
+    fn test_wait_duration() {
+        let config = WaitConfig::Duration { seconds: 300 };
+        let now = utc("2025-01-06T10:00:00Z");
+        assert_eq!(config.remaining(now).unwrap(), Duration::minutes(5));
+    }
+
+    #[test]
+    fn test_rejects_huge_wait_duration() {
+        let config = WaitConfig::Duration {
+            seconds: 18_446_744_073_709_551,
+        };
+        let now = utc("2025-01-06T10:00:00Z");
+        assert!(config.validate().is_err());
+        assert!(config.remaining(now).is_err());
+        assert!(config.deadline(now).is_err());
+
+        let longest = WaitConfig::Duration {
+            seconds: MAX_WAIT_SECONDS,
+        };
+        assert_eq!(longest.deadline(now).unwrap(), utc("2025-01-13T10:00:00Z"));
+    }
+
+    #[test]
+    fn test_wait_until_later_today() {
+        let config = WaitConfig::Until {
+            time: "14:30".to_string(),
+            utc: true,
+        };
+        let now = utc("2025-01-06T10:00:00Z");
+        assert_eq!(config.remaining(now).unwrap(), Duration::minutes(270));
+    }
+
+    #[test]
+    fn test_wait_until_rolls_over_to_tomorrow() {
+        let config = WaitConfig::Until {
+            time: "02:00".to_string(),
+            utc: true,
+        };
+        let now = utc("2025-01-06T10:00:00Z");
+        assert_eq!(config.remaining(now).unwrap(), Duration::hours(16));
+    }
+
+    #[test]
+    fn test_wait_config_from_node_data() {
+        let data = serde_json::json!({
+            "label": "Cool down",
+            "mode": "duration",
+            "seconds": 60
+        });
+        let config: WaitConfig = serde_json::from_value(data).unwrap();
+        assert_eq!(config, WaitConfig::Duration { seconds: 60 });
+    }
+
+    #[test]
+    fn test_invalid_time_rejected() {
+        assert!(parse_time_of_day("25:00").is_err());
+        assert!(parse_time_of_day("2am").is_err());
+    }
+
+    #[test]
+    fn test_window_open_same_day() {
+        // 2025-01-06 is a Monday
+        let config = window(&["mon"], "09:00", "17:00");
+        let decision = config.evaluate(utc("2025-01-06T10:00:00Z")).unwrap();
+        assert_eq!(decision, WindowDecision::Open);
+    }
+
+    #[test]
+    fn test_window_closed_waits_for_next_allowed_day() {
+        let config = window(&["mon", "wed"], "09:00", "17:00");
+        let decision = config.evaluate(utc("2025-01-06T18:00:00Z")).unwrap();
+        assert_eq!(
+            decision,
+            WindowDecision::OpensAt(utc("2025-01-08T09:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_overnight_window_after_midnight() {
+        // Friday 22:00 - Saturday 02:00; 2025-01-11 is a Saturday
+        let config = window(&["fri"], "22:00", "02:00");
+        let decision = config.evaluate(utc("2025-01-11T01:30:00Z")).unwrap();
+        assert_eq!(decision, WindowDecision::Open);
+
+        let decision = config.evaluate(utc("2025-01-11T03:00:00Z")).unwrap();
+        assert_eq!(
+            decision,
+            WindowDecision::OpensAt(utc("2025-01-17T22:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_invalid_window_day() {
+        let config = window(&["someday"], "09:00", "17:00");
+        assert!(config.evaluate(utc("2025-01-06T10:00:00Z")).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+//! Manual input nodes.
+//!
+//! An `input` node pauses the run and asks the user for a value (release
+//! version, rollout percentage, ...). The value is delivered through the
+//! `provide_flow_input` command and stored in the run context as
+//! `inputs.<variable>` for downstream templating.
+
+use serde::Deserialize;
+
+use super::types::{FlowInputKind, FlowInputRequest};
+
+/// Configuration of an `input` node, read from the node's `data` object.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InputConfig {
+    /// Question shown to the user
+    pub prompt: String,
+    /// Expected value type
+    #[serde(default)]
+    pub input_type: FlowInputKind,
+    /// Allowed values when `input_type` is `choice`
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// Name under which the value is exposed (`inputs.<variable>`).
+    /// Defaults to the node ID.
+    pub variable: Option<String>,
+    /// Value suggested to the user
+    pub default_value: Option<String>,
+}
+
+impl InputConfig {
+    /// Returns the context key the provided value is stored under.
+    pub fn context_key(&self, node_id: &str) -> String {
+        let name = self
+            .variable
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .unwrap_or(node_id);
+        format!("inputs.{name}")
+    }
+
+    /// Builds the request sent to the frontend.
+    pub fn to_request(&self, run_id: &str, node_id: &str) -> FlowInputRequest {
+        FlowInputRequest {
+            run_id: run_id.to_string(),
+            node_id: node_id.to_string(),
+            prompt: self.prompt.clone(),
+            input_type: self.input_type,
+            options: self.options.clone(),
+            default_value: self.default_value.clone(),
+        }
+    }
+}
+
+/// Validates a user-provided value against an input request.
+///
+/// Returns the normalized (trimmed) value.
+pub fn validate_input_value(request: &FlowInputRequest, value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("Input value cannot be empty".to_string());
+    }
+
+    match request.input_type {
+        FlowInputKind::Text => {}
+        FlowInputKind::Number => {
+            value
+                .parse::<f64>()
+                .map_err(|_| format!("'{value}' is not a number"))?;
+        }
+        FlowInputKind::Choice => {
+            if !request.options.iter().any(|o| o == value) {
+                return Err(format!(
+                    "'{value}' is not one of: {}",
+                    request.options.join(", ")
+                ));
+            }
+        }
+    }
+
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(input_type: FlowInputKind, options: &[&str]) -> FlowInputRequest {
+        InputConfig {
+            prompt: "Value?".to_string(),
+            input_type,
+            options: options.iter().map(|o| o.to_string()).collect(),
+            variable: None,
+            default_value: None,
+        }
+        .to_request("run-1", "node-1")
+    }
+
+    #[test]
+    fn test_context_key_defaults_to_node_id() {
+        let config: InputConfig =
+            serde_json::from_value(serde_json::json!({ "prompt": "Version?" })).unwrap();
+        assert_eq!(config.context_key("node-7"), "inputs.node-7");
+
+        let config: InputConfig = serde_json::from_value(
+            serde_json::json!({ "prompt": "Version?", "variable": "version" }),
+        )
+        .unwrap();
+        assert_eq!(config.context_key("node-7"), "inputs.version");
+    }
+
+    #[test]
+    fn test_validate_number() {
+        let request = request(FlowInputKind::Number, &[]);
+        assert_eq!(validate_input_value(&request, " 25 ").unwrap(), "25");
+        assert!(validate_input_value(&request, "a lot").is_err());
+    }
+
+    #[test]
+    fn test_validate_choice() {
+        let request = request(FlowInputKind::Choice, &["blue", "green"]);
+        assert!(validate_input_value(&request, "green").is_ok());
+        assert!(validate_input_value(&request, "red").is_err());
+    }
+
+    #[test]
+    fn test_empty_value_rejected() {
+        let request = request(FlowInputKind::Text, &[]);
+        assert!(validate_input_value(&request, "   ").is_err());
+    }
+}
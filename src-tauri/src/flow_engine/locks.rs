@@ -0,0 +1,211 @@
+//! Named run locks.
+//!
+//! A `lock` node declares that the whole run needs a named lock (e.g.,
+//! "prod-deploy"). Before the first node executes, the run acquires every lock
+//! it declares; runs that cannot get a lock stay `queued` until it is released.
+//! A lock's `limit` turns it into a semaphore allowing that many concurrent runs.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Configuration of a `lock` node, read from the node's `data` object.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockConfig {
+    /// Lock name shared between flows (e.g., "prod-deploy")
+    pub name: String,
+    /// Number of runs allowed to hold the lock at once
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+fn default_limit() -> u32 {
+    1
+}
+
+impl LockConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Lock name cannot be empty".to_string());
+        }
+        if self.limit == 0 {
+            return Err(format!("Lock '{}' must allow at least one run", self.name));
+        }
+        Ok(())
+    }
+}
+
+/// A semaphore together with the limit it was created with.
+struct LockSlot {
+    limit: u32,
+    semaphore: Arc<Semaphore>,
+}
+
+/// All locks used since the app launched, keyed by name.
+static LOCKS: LazyLock<Mutex<HashMap<String, LockSlot>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Returns the semaphore for a lock, creating it on first use.
+///
+/// A lock's limit can only change while no run holds it.
+fn semaphore(config: &LockConfig) -> Result<Arc<Semaphore>, String> {
+    let mut locks = LOCKS.lock().unwrap_or_else(PoisonError::into_inner);
+    let name = config.name.trim();
+
+    if let Some(slot) = locks.get(name) {
+        if slot.limit == config.limit {
+            return Ok(slot.semaphore.clone());
+        }
+        let in_use = slot.semaphore.available_permits() != slot.limit as usize;
+        if in_use {
+            return Err(format!(
+                "Lock '{name}' is held with limit {}, but this flow declares limit {}",
+                slot.limit, config.limit
+            ));
+        }
+    }
+
+    let semaphore = Arc::new(Semaphore::new(config.limit as usize));
+    locks.insert(
+        name.to_string(),
+        LockSlot {
+            limit: config.limit,
+            semaphore: semaphore.clone(),
+        },
+    );
+    Ok(semaphore)
+}
+
+/// Why a run could not acquire its locks.
+#[derive(Debug)]
+pub enum AcquireError {
+    Invalid(String),
+    Cancelled,
+}
+
+/// Acquires every lock in `configs`, in name order so two runs declaring the
+/// same locks cannot deadlock.
+///
+/// `on_queued` is called once, with the name of the lock being waited for,
+/// if a lock is not immediately available. Permits are released when the
+/// returned guards are dropped.
+pub async fn acquire_all(
+    configs: &[LockConfig],
+    cancelled: &AtomicBool,
+    poll_interval: Duration,
+    mut on_queued: impl FnMut(&str),
+) -> Result<Vec<OwnedSemaphorePermit>, AcquireError> {
+    let mut sorted: Vec<&LockConfig> = configs.iter().collect();
+    sorted.sort_by(|a, b| a.name.trim().cmp(b.name.trim()));
+    sorted.dedup_by(|a, b| a.name.trim() == b.name.trim());
+
+    let mut permits = Vec::with_capacity(sorted.len());
+    let mut queued = false;
+
+    for config in sorted {
+        config.validate().map_err(AcquireError::Invalid)?;
+        let semaphore = semaphore(config).map_err(AcquireError::Invalid)?;
+
+        if let Ok(permit) = semaphore.clone().try_acquire_owned() {
+            permits.push(permit);
+            continue;
+        }
+
+        if !queued {
+            on_queued(config.name.trim());
+            queued = true;
+        }
+
+        let acquire = semaphore.acquire_owned();
+        tokio::pin!(acquire);
+        loop {
+            if cancelled.load(Ordering::SeqCst) {
+                return Err(AcquireError::Cancelled);
+            }
+            tokio::select! {
+                permit = &mut acquire => {
+                    // Semaphores are never closed
+                    permits.push(permit.map_err(|_| AcquireError::Cancelled)?);
+                    break;
+                }
+                _ = tokio::time::sleep(poll_interval) => {}
+            }
+        }
+    }
+
+    Ok(permits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn lock(name: &str, limit: u32) -> LockConfig {
+        LockConfig {
+            name: name.to_string(),
+            limit,
+        }
+    }
+
+    #[test]
+    fn test_limit_defaults_to_one() {
+        let config: LockConfig =
+            serde_json::from_value(serde_json::json!({ "name": "prod-deploy" })).unwrap();
+        assert_eq!(config.limit, 1);
+        assert!(lock(" ", 1).validate().is_err());
+        assert!(lock("prod", 0).validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_second_run_queues_until_release() {
+        let cancelled = AtomicBool::new(false);
+        let interval = Duration::from_millis(10);
+        let locks = [lock("test-queue", 1)];
+
+        let first = acquire_all(&locks, &cancelled, interval, |_| {})
+            .await
+            .unwrap();
+
+        let queued_on = RefCell::new(None);
+        let second = acquire_all(&locks, &cancelled, interval, |name| {
+            *queued_on.borrow_mut() = Some(name.to_string());
+        });
+        tokio::pin!(second);
+
+        tokio::select! {
+            _ = &mut second => panic!("lock acquired while held"),
+            _ = tokio::time::sleep(Duration::from_millis(50)) => {}
+        }
+
+        drop(first);
+        assert!(second.await.is_ok());
+        assert_eq!(queued_on.borrow().as_deref(), Some("test-queue"));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_while_queued() {
+        let cancelled = AtomicBool::new(false);
+        let interval = Duration::from_millis(10);
+        let locks = [lock("test-cancel", 1)];
+
+        let _held = acquire_all(&locks, &cancelled, interval, |_| {})
+            .await
+            .unwrap();
+
+        cancelled.store(true, Ordering::SeqCst);
+        let result = acquire_all(&locks, &cancelled, interval, |_| {}).await;
+        assert!(matches!(result, Err(AcquireError::Cancelled)));
+    }
+
+    #[test]
+    fn test_limit_change_rejected_while_held() {
+        let held = semaphore(&lock("test-limit", 2)).unwrap();
+        let _permit = held.try_acquire_owned().unwrap();
+        assert!(semaphore(&lock("test-limit", 1)).is_err());
+    }
+}
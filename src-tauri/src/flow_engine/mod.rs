@@ -0,0 +1,33 @@
+//! Flow execution engine.
+//!
+//! Executes saved flows node by node in dependency order. Runs are tracked in
+//! memory and every state change is broadcast to the frontend through the
+//! `flow-run-updated` event. Input nodes additionally emit
+//! `flow-input-requested` when they pause for the user.
+
+mod artifacts;
+mod checks;
+mod deployments;
+mod environments;
+mod files;
+mod gates;
+mod inputs;
+mod locks;
+mod model;
+mod quick_actions;
+mod runner;
+mod simulation;
+mod subflows;
+mod templating;
+mod triggers;
+mod types;
+
+pub use artifacts::{
+    is_inert as is_inert_artifact, list_run_artifacts, run_artifact_path, FlowRunArtifact,
+};
+pub use environments::validate_variables as validate_environment_variables;
+pub use quick_actions::action_flow;
+pub use runner::{cancel_run, get_run, list_runs, provide_input, start_run, RunOptions};
+pub use simulation::{load_fixtures, FlowFixtures};
+pub use triggers::{glob_matches, trigger_flows, WebhookEvent};
+pub use types::FlowRun;
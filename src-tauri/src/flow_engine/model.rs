@@ -0,0 +1,607 @@
+//! Executable flow model.
+//!
+//! Saved flows store React Flow nodes and edges as raw JSON. This module turns
+//! them into a typed graph the engine can validate and execute.
+
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+
+use super::artifacts::ArtifactConfig;
+use super::checks::SmokeTestsConfig;
+use super::deployments::{ApplyConfig, CanaryConfig, SetImageConfig};
+use super::files::GitLabFileConfig;
+use super::gates::{TimeWindowConfig, WaitConfig};
+use super::inputs::InputConfig;
+use super::locks::LockConfig;
+use super::subflows::SubflowConfig;
+use super::templating::render_value;
+use super::triggers::WebhookTriggerConfig;
+use crate::commands::flows::Flow;
+use crate::types::QuickAction;
+
+/// Node types backed by an integration. They carry configuration for the
+/// editor but have no executable action yet, so the engine passes through them.
+const INTEGRATION_NODE_TYPES: &[&str] =
+    &["gitlab", "jenkins", "kubernetes", "sonarqube", "keycloak"];
+
+/// What a node does when the engine reaches it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlowNodeKind {
+    /// Integration node without an executable action
+    Integration,
+    /// Delay node (`type: "wait"`)
+    Wait(WaitConfig),
+    /// Time-window gate (`type: "time_window"`)
+    TimeWindow(TimeWindowConfig),
+    /// Manual input prompt (`type: "input"`)
+    Input(InputConfig),
+    /// Named lock held for the whole run (`type: "lock"`)
+    Lock(LockConfig),
+    /// Starts the flow on matching webhook events (`type: "webhook_trigger"`)
+    WebhookTrigger(WebhookTriggerConfig),
+    /// Saves a file to the run's artifacts (`type: "artifact"`)
+    Artifact(ArtifactConfig),
+    /// Runs another saved flow as a nested run (`type: "subflow"`)
+    Subflow(SubflowConfig),
+    /// Changes a deployment container image (`type: "k8s_set_image"`)
+    SetImage(SetImageConfig),
+    /// Starts, promotes or aborts a canary deployment (`type: "k8s_canary"`)
+    Canary(CanaryConfig),
+    /// Applies manifests in sync waves (`type: "k8s_apply"`)
+    Apply(ApplyConfig),
+    /// Reads a repository file into the run context (`type: "gitlab_file"`)
+    GitLabFile(GitLabFileConfig),
+    /// Runs an environment's smoke tests (`type: "smoke_tests"`)
+    SmokeTests(SmokeTestsConfig),
+    /// Runs a quick pane action (`type: "quick_action"`)
+    QuickAction(QuickAction),
+}
+
+impl FlowNodeKind {
+    /// Parses the node configuration for a given node type.
+    pub fn parse(node_type: &str, label: &str, data: Value) -> Result<Self, String> {
+        let kind = match node_type {
+            "wait" => {
+                let config: WaitConfig = serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid wait node '{label}': {e}"))?;
+                config
+                    .validate()
+                    .map_err(|e| format!("Invalid wait node '{label}': {e}"))?;
+                FlowNodeKind::Wait(config)
+            }
+            "time_window" => FlowNodeKind::TimeWindow(
+                serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid time window node '{label}': {e}"))?,
+            ),
+            "input" => FlowNodeKind::Input(
+                serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid input node '{label}': {e}"))?,
+            ),
+            "lock" => {
+                let config: LockConfig = serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid lock node '{label}': {e}"))?;
+                config
+                    .validate()
+                    .map_err(|e| format!("Invalid lock node '{label}': {e}"))?;
+                FlowNodeKind::Lock(config)
+            }
+            "webhook_trigger" => FlowNodeKind::WebhookTrigger(
+                serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid webhook trigger node '{label}': {e}"))?,
+            ),
+            "subflow" => FlowNodeKind::Subflow(
+                serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid sub-flow node '{label}': {e}"))?,
+            ),
+            "artifact" => FlowNodeKind::Artifact(
+                serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid artifact node '{label}': {e}"))?,
+            ),
+            "k8s_set_image" => {
+                let config: SetImageConfig = serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid set image node '{label}': {e}"))?;
+                config
+                    .validate()
+                    .map_err(|e| format!("Invalid set image node '{label}': {e}"))?;
+                FlowNodeKind::SetImage(config)
+            }
+            "k8s_canary" => {
+                let config: CanaryConfig = serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid canary node '{label}': {e}"))?;
+                config
+                    .validate()
+                    .map_err(|e| format!("Invalid canary node '{label}': {e}"))?;
+                FlowNodeKind::Canary(config)
+            }
+            "k8s_apply" => {
+                let config: ApplyConfig = serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid apply node '{label}': {e}"))?;
+                config
+                    .validate()
+                    .map_err(|e| format!("Invalid apply node '{label}': {e}"))?;
+                FlowNodeKind::Apply(config)
+            }
+            "gitlab_file" => {
+                let config: GitLabFileConfig = serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid GitLab file node '{label}': {e}"))?;
+                config
+                    .validate()
+                    .map_err(|e| format!("Invalid GitLab file node '{label}': {e}"))?;
+                FlowNodeKind::GitLabFile(config)
+            }
+            "smoke_tests" => FlowNodeKind::SmokeTests(
+                serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid smoke tests node '{label}': {e}"))?,
+            ),
+            "quick_action" => FlowNodeKind::QuickAction(
+                serde_json::from_value(data)
+                    .map_err(|e| format!("Invalid quick action node '{label}': {e}"))?,
+            ),
+            t if INTEGRATION_NODE_TYPES.contains(&t) => FlowNodeKind::Integration,
+            other => return Err(format!("Unsupported node type '{other}' on node '{label}'")),
+        };
+        Ok(kind)
+    }
+}
+
+/// A node of the executable graph.
+#[derive(Debug, Clone)]
+pub struct FlowNode {
+    pub id: String,
+    pub label: String,
+    /// React Flow node type (e.g., "wait")
+    pub node_type: String,
+    /// Raw node configuration, rendered against the run context before execution
+    pub data: Value,
+    /// Flow-level failure handler (`data.onFlowFailure`): skipped by the
+    /// normal pass and only executed after another node failed
+    pub on_flow_failure: bool,
+}
+
+impl FlowNode {
+    /// Renders the node configuration against `variables` and parses it.
+    pub fn resolve(&self, variables: &HashMap<String, String>) -> Result<FlowNodeKind, String> {
+        let data = render_value(&self.data, variables)
+            .map_err(|e| format!("Failed to render node '{}': {e}", self.label))?;
+        FlowNodeKind::parse(&self.node_type, &self.label, data)
+    }
+}
+
+/// When an edge lets its target run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeCondition {
+    /// Default edge: the source node succeeded
+    OnSuccess,
+    /// Edge leaving the source's `on_failure` handle: the source node failed
+    OnFailure,
+}
+
+/// React Flow source handle for failure branches.
+const ON_FAILURE_HANDLE: &str = "on_failure";
+
+/// A directed edge between two nodes.
+#[derive(Debug, Clone)]
+pub struct FlowEdge {
+    pub source: String,
+    pub target: String,
+    pub condition: EdgeCondition,
+}
+
+/// Validated, executable representation of a saved flow.
+#[derive(Debug, Clone)]
+pub struct FlowGraph {
+    pub nodes: Vec<FlowNode>,
+    pub edges: Vec<FlowEdge>,
+}
+
+impl FlowGraph {
+    /// Builds the executable graph from a saved flow.
+    ///
+    /// Fails on unknown node types, invalid node configuration, dangling edges
+    /// and cycles, so problems surface before a run starts. Configuration that
+    /// contains `{{ ... }}` placeholders is validated when the node executes.
+    pub fn from_flow(flow: &Flow) -> Result<Self, String> {
+        let raw_nodes = flow
+            .nodes
+            .as_array()
+            .ok_or_else(|| "Invalid flow: 'nodes' must be an array".to_string())?;
+
+        let nodes = raw_nodes
+            .iter()
+            .map(parse_node)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let raw_edges = match &flow.edges {
+            Value::Null => Vec::new(),
+            Value::Array(edges) => edges.clone(),
+            _ => return Err("Invalid flow: 'edges' must be an array".to_string()),
+        };
+
+        let edges = raw_edges
+            .iter()
+            .map(parse_edge)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for edge in &edges {
+            for endpoint in [&edge.source, &edge.target] {
+                if !nodes.iter().any(|n| &n.id == endpoint) {
+                    return Err(format!(
+                        "Invalid flow: edge references unknown node '{endpoint}'"
+                    ));
+                }
+            }
+
+            // Handlers run after every regular node, so the two sets can't be linked
+            let is_handler = |id: &str| nodes.iter().any(|n| n.id == id && n.on_flow_failure);
+            match (is_handler(&edge.source), is_handler(&edge.target)) {
+                (false, true) => {
+                    return Err(format!(
+                        "Invalid flow: flow failure handler '{}' cannot depend on regular node '{}'",
+                        edge.target, edge.source
+                    ));
+                }
+                (true, false) => {
+                    return Err(format!(
+                        "Invalid flow: regular node '{}' cannot depend on flow failure handler '{}'",
+                        edge.target, edge.source
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        let graph = Self { nodes, edges };
+        graph.execution_order()?;
+        Ok(graph)
+    }
+
+    /// Returns node indices in dependency order (Kahn's algorithm).
+    ///
+    /// Nodes without dependencies keep their editor order, which keeps runs
+    /// predictable for flows built as a simple chain.
+    pub fn execution_order(&self) -> Result<Vec<usize>, String> {
+        let index: HashMap<&str, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+
+        let mut in_degree = vec![0usize; self.nodes.len()];
+        for edge in &self.edges {
+            if let Some(&target) = index.get(edge.target.as_str()) {
+                in_degree[target] += 1;
+            }
+        }
+
+        let mut ready: VecDeque<usize> = (0..self.nodes.len())
+            .filter(|&i| in_degree[i] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+
+        while let Some(current) = ready.pop_front() {
+            order.push(current);
+            for edge in self
+                .edges
+                .iter()
+                .filter(|e| e.source == self.nodes[current].id)
+            {
+                if let Some(&target) = index.get(edge.target.as_str()) {
+                    in_degree[target] -= 1;
+                    if in_degree[target] == 0 {
+                        ready.push_back(target);
+                    }
+                }
+            }
+        }
+
+        if order.len() != self.nodes.len() {
+            return Err("Invalid flow: the graph contains a cycle".to_string());
+        }
+
+        Ok(order)
+    }
+
+    /// Returns the locks declared by `lock` nodes.
+    ///
+    /// Locks are acquired before the run starts, so their configuration
+    /// cannot use templates.
+    pub fn locks(&self) -> Result<Vec<LockConfig>, String> {
+        let mut locks = Vec::new();
+        for node in self.nodes.iter().filter(|n| n.node_type == "lock") {
+            match node.resolve(&HashMap::new())? {
+                FlowNodeKind::Lock(config) => locks.push(config),
+                _ => unreachable!("lock nodes always parse as FlowNodeKind::Lock"),
+            }
+        }
+        Ok(locks)
+    }
+
+    /// Returns the configuration of `webhook_trigger` nodes.
+    ///
+    /// Triggers are matched before a run exists, so they cannot use templates.
+    pub fn webhook_triggers(&self) -> Result<Vec<WebhookTriggerConfig>, String> {
+        let mut triggers = Vec::new();
+        for node in self
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == "webhook_trigger")
+        {
+            match node.resolve(&HashMap::new())? {
+                FlowNodeKind::WebhookTrigger(config) => triggers.push(config),
+                _ => unreachable!(
+                    "webhook trigger nodes always parse as FlowNodeKind::WebhookTrigger"
+                ),
+            }
+        }
+        Ok(triggers)
+    }
+
+    /// Returns the edges into `node_id`.
+    pub fn incoming(&self, node_id: &str) -> Vec<&FlowEdge> {
+        self.edges.iter().filter(|e| e.target == node_id).collect()
+    }
+}
+
+fn parse_node(value: &Value) -> Result<FlowNode, String> {
+    let id = value
+        .get("id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Invalid flow: node is missing 'id'".to_string())?
+        .to_string();
+
+    let node_type = value
+        .get("type")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let data = value.get("data").cloned().unwrap_or(Value::Null);
+
+    let label = data
+        .get("label")
+        .and_then(|v| v.as_str())
+        .filter(|l| !l.is_empty())
+        .unwrap_or(&id)
+        .to_string();
+
+    let on_flow_failure = data
+        .get("onFlowFailure")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    // Nodes with placeholders can only be parsed once the run context is known
+    let has_placeholders = render_value(&data, &HashMap::new()).is_err();
+    if !has_placeholders {
+        FlowNodeKind::parse(&node_type, &label, data.clone())?;
+    }
+
+    Ok(FlowNode {
+        id,
+        label,
+        node_type,
+        data,
+        on_flow_failure,
+    })
+}
+
+fn parse_edge(value: &Value) -> Result<FlowEdge, String> {
+    let field = |name: &str| {
+        value
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| format!("Invalid flow: edge is missing '{name}'"))
+    };
+
+    let condition = match value.get("sourceHandle").and_then(|v| v.as_str()) {
+        Some(ON_FAILURE_HANDLE) => EdgeCondition::OnFailure,
+        _ => EdgeCondition::OnSuccess,
+    };
+
+    Ok(FlowEdge {
+        source: field("source")?,
+        target: field("target")?,
+        condition,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn flow(nodes: Value, edges: Value) -> Flow {
+        Flow {
+            id: "flow-1".to_string(),
+            name: "Test".to_string(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            nodes,
+            edges,
+            viewport: None,
+        }
+    }
+
+    #[test]
+    fn test_parses_wait_and_integration_nodes() {
+        let flow = flow(
+            json!([
+                { "id": "a", "type": "jenkins", "data": { "label": "Build" } },
+                { "id": "b", "type": "wait", "data": { "mode": "duration", "seconds": 30 } }
+            ]),
+            json!([{ "id": "e1", "source": "a", "target": "b" }]),
+        );
+
+        let graph = FlowGraph::from_flow(&flow).unwrap();
+        let vars = HashMap::new();
+        assert_eq!(
+            graph.nodes[0].resolve(&vars).unwrap(),
+            FlowNodeKind::Integration
+        );
+        assert_eq!(
+            graph.nodes[1].resolve(&vars).unwrap(),
+            FlowNodeKind::Wait(WaitConfig::Duration { seconds: 30 })
+        );
+        assert_eq!(graph.nodes[1].label, "b");
+
+        let incoming = graph.incoming("b");
+        assert_eq!(incoming.len(), 1);
+        assert_eq!(incoming[0].source, "a");
+        assert_eq!(incoming[0].condition, EdgeCondition::OnSuccess);
+    }
+
+    #[test]
+    fn test_execution_order_follows_edges() {
+        let flow = flow(
+            json!([
+                { "id": "deploy", "type": "kubernetes", "data": {} },
+                { "id": "gate", "type": "time_window", "data": { "start": "22:00", "end": "02:00" } },
+                { "id": "build", "type": "jenkins", "data": {} }
+            ]),
+            json!([
+                { "source": "build", "target": "gate" },
+                { "source": "gate", "target": "deploy" }
+            ]),
+        );
+
+        let graph = FlowGraph::from_flow(&flow).unwrap();
+        let order: Vec<&str> = graph
+            .execution_order()
+            .unwrap()
+            .into_iter()
+            .map(|i| graph.nodes[i].id.as_str())
+            .collect();
+        assert_eq!(order, vec!["build", "gate", "deploy"]);
+    }
+
+    #[test]
+    fn test_resolve_renders_templates() {
+        let flow = flow(
+            json!([{
+                "id": "w",
+                "type": "wait",
+                "data": { "mode": "until", "time": "{{inputs.deploy_at}}" }
+            }]),
+            json!([]),
+        );
+        let graph = FlowGraph::from_flow(&flow).unwrap();
+        let variables = HashMap::from([("inputs.deploy_at".to_string(), "02:00".to_string())]);
+
+        assert_eq!(
+            graph.nodes[0].resolve(&variables).unwrap(),
+            FlowNodeKind::Wait(WaitConfig::Until {
+                time: "02:00".to_string(),
+                utc: false
+            })
+        );
+        assert!(graph.nodes[0].resolve(&HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_collects_locks() {
+        let locked = flow(
+            json!([
+                { "id": "l", "type": "lock", "data": { "name": "prod-deploy" } },
+                { "id": "d", "type": "kubernetes", "data": {} }
+            ]),
+            json!([]),
+        );
+        let graph = FlowGraph::from_flow(&locked).unwrap();
+        assert_eq!(
+            graph.locks().unwrap(),
+            vec![LockConfig {
+                name: "prod-deploy".to_string(),
+                limit: 1
+            }]
+        );
+
+        let templated = flow(
+            json!([{ "id": "l", "type": "lock", "data": { "name": "{{inputs.env}}" } }]),
+            json!([]),
+        );
+        let graph = FlowGraph::from_flow(&templated).unwrap();
+        assert!(graph.locks().is_err());
+    }
+
+    #[test]
+    fn test_parses_failure_branches() {
+        let flow = flow(
+            json!([
+                { "id": "deploy", "type": "kubernetes", "data": {} },
+                { "id": "rollback", "type": "kubernetes", "data": {} },
+                { "id": "notify", "type": "gitlab", "data": { "onFlowFailure": true } }
+            ]),
+            json!([
+                { "source": "deploy", "target": "rollback", "sourceHandle": "on_failure" }
+            ]),
+        );
+
+        let graph = FlowGraph::from_flow(&flow).unwrap();
+        assert_eq!(
+            graph.incoming("rollback")[0].condition,
+            EdgeCondition::OnFailure
+        );
+        assert!(graph.nodes[2].on_flow_failure);
+        assert!(!graph.nodes[0].on_flow_failure);
+    }
+
+    #[test]
+    fn test_rejects_edge_into_flow_failure_handler() {
+        let flow = flow(
+            json!([
+                { "id": "deploy", "type": "kubernetes", "data": {} },
+                { "id": "notify", "type": "gitlab", "data": { "onFlowFailure": true } }
+            ]),
+            json!([{ "source": "deploy", "target": "notify" }]),
+        );
+        assert!(FlowGraph::from_flow(&flow).is_err());
+    }
+
+    #[test]
+    fn test_rejects_edge_out_of_flow_failure_handler() {
+        let backwards = flow(
+            json!([
+                { "id": "notify", "type": "gitlab", "data": { "onFlowFailure": true } },
+                { "id": "page", "type": "gitlab", "data": { "onFlowFailure": true } },
+                { "id": "deploy", "type": "kubernetes", "data": {} }
+            ]),
+            json!([{ "source": "notify", "target": "deploy" }]),
+        );
+        let err = FlowGraph::from_flow(&backwards).unwrap_err();
+        assert!(err.contains("regular node 'deploy'"));
+
+        let chained = flow(
+            json!([
+                { "id": "notify", "type": "gitlab", "data": { "onFlowFailure": true } },
+                { "id": "page", "type": "gitlab", "data": { "onFlowFailure": true } }
+            ]),
+            json!([{ "source": "notify", "target": "page" }]),
+        );
+        assert!(FlowGraph::from_flow(&chained).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_cycles() {
+        let flow = flow(
+            json!([
+                { "id": "a", "type": "gitlab", "data": {} },
+                { "id": "b", "type": "gitlab", "data": {} }
+            ]),
+            json!([
+                { "source": "a", "target": "b" },
+                { "source": "b", "target": "a" }
+            ]),
+        );
+        assert!(FlowGraph::from_flow(&flow).is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_node_type() {
+        let flow = flow(
+            json!([{ "id": "a", "type": "teleport", "data": {} }]),
+            json!([]),
+        );
+        let err = FlowGraph::from_flow(&flow).unwrap_err();
+        assert!(err.contains("teleport"));
+    }
+}
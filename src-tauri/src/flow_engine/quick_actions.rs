@@ -0,0 +1,163 @@
+//! Quick action nodes.
+//!
+//! A `quick_action` node runs one action from the quick pane (trigger a
+//! build or pipeline, run smoke tests, notify). Quick actions and recorded
+//! macros run as small generated flows made only of these nodes: one node
+//! per action, chained in order.
+
+use chrono::Utc;
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+use super::checks::{self, SmokeTestsConfig};
+use crate::commands::flows::Flow;
+use crate::commands::gitlab::gitlab_adapter;
+use crate::commands::jenkins::jenkins_adapter;
+use crate::commands::notifications::send_native_notification;
+use crate::types::QuickAction;
+
+/// Describes an action for node labels and run messages.
+pub fn describe(action: &QuickAction) -> String {
+    match action {
+        QuickAction::TriggerJenkinsBuild { job_name, .. } => {
+            format!("trigger Jenkins job {job_name}")
+        }
+        QuickAction::TriggerGitlabPipeline {
+            project_id,
+            ref_name,
+            ..
+        } => format!("trigger GitLab pipeline of project {project_id} on {ref_name}"),
+        QuickAction::RunSmokeTests { environment_id } => {
+            format!("run smoke tests for environment {environment_id}")
+        }
+        QuickAction::Notify { title, .. } => format!("send notification '{title}'"),
+    }
+}
+
+/// Runs the action and returns the node message.
+pub async fn run_action(app: &AppHandle, action: &QuickAction) -> Result<String, String> {
+    match action {
+        QuickAction::TriggerJenkinsBuild {
+            integration_id,
+            job_name,
+            parameters,
+        } => {
+            let adapter = jenkins_adapter(app, integration_id).await?;
+            let parameters = (!parameters.is_empty()).then(|| parameters.clone());
+            adapter
+                .trigger_build(job_name, parameters)
+                .await
+                .map_err(|e| format!("Failed to trigger build: {e}"))?;
+            Ok(format!("Triggered Jenkins job {job_name}"))
+        }
+        QuickAction::TriggerGitlabPipeline {
+            integration_id,
+            project_id,
+            ref_name,
+        } => {
+            let adapter = gitlab_adapter(app, integration_id).await?;
+            let pipeline = adapter
+                .trigger_pipeline(*project_id, ref_name.clone())
+                .await
+                .map_err(|e| format!("Failed to trigger pipeline: {e}"))?;
+            Ok(format!(
+                "Triggered pipeline #{} of project {project_id} on {ref_name}",
+                pipeline.id
+            ))
+        }
+        QuickAction::RunSmokeTests { environment_id } => {
+            let config = SmokeTestsConfig {
+                environment_id: environment_id.clone(),
+            };
+            checks::run_smoke_tests(app, &config).await
+        }
+        QuickAction::Notify { title, body } => {
+            send_native_notification(app.clone(), title.clone(), body.clone()).await?;
+            Ok(format!("Sent notification '{title}'"))
+        }
+    }
+}
+
+/// Builds a flow running the actions one after another.
+pub fn action_flow(id: &str, name: &str, actions: &[QuickAction]) -> Result<Flow, String> {
+    if actions.is_empty() {
+        return Err("No actions to run".to_string());
+    }
+
+    let mut nodes = Vec::with_capacity(actions.len());
+    for (index, action) in actions.iter().enumerate() {
+        let mut data = serde_json::to_value(action)
+            .map_err(|e| format!("Failed to serialize quick action: {e}"))?;
+        if let Value::Object(fields) = &mut data {
+            fields.insert("label".to_string(), Value::String(describe(action)));
+        }
+        nodes.push(json!({
+            "id": format!("step-{}", index + 1),
+            "type": "quick_action",
+            "data": data,
+        }));
+    }
+    let edges: Vec<Value> = (1..actions.len())
+        .map(|i| {
+            json!({
+                "id": format!("step-{i}-step-{}", i + 1),
+                "source": format!("step-{i}"),
+                "target": format!("step-{}", i + 1),
+            })
+        })
+        .collect();
+
+    let now = Utc::now().to_rfc3339();
+    Ok(Flow {
+        id: id.to_string(),
+        name: name.to_string(),
+        created_at: now.clone(),
+        updated_at: now,
+        nodes: Value::Array(nodes),
+        edges: Value::Array(edges),
+        viewport: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flow_engine::model::{FlowGraph, FlowNodeKind};
+
+    #[test]
+    fn test_action_flow() {
+        let actions = vec![
+            QuickAction::TriggerJenkinsBuild {
+                integration_id: "jenkins".to_string(),
+                job_name: "api-build".to_string(),
+                parameters: Default::default(),
+            },
+            QuickAction::RunSmokeTests {
+                environment_id: "staging".to_string(),
+            },
+        ];
+        let flow = action_flow("macro-deploy", "Deploy", &actions).unwrap();
+        let graph = FlowGraph::from_flow(&flow).unwrap();
+
+        let order: Vec<&str> = graph
+            .execution_order()
+            .unwrap()
+            .into_iter()
+            .map(|i| graph.nodes[i].label.as_str())
+            .collect();
+        assert_eq!(
+            order,
+            [
+                "trigger Jenkins job api-build",
+                "run smoke tests for environment staging"
+            ]
+        );
+
+        let node = &graph.nodes[0];
+        assert_eq!(
+            FlowNodeKind::parse(&node.node_type, &node.label, node.data.clone()).unwrap(),
+            FlowNodeKind::QuickAction(actions[0].clone())
+        );
+        assert!(action_flow("empty", "Empty", &[]).is_err());
+    }
+}
@@ -0,0 +1,1134 @@
+//! Flow run execution and the in-memory run registry.
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::oneshot;
+
+use super::artifacts;
+use super::checks;
+use super::deployments;
+use super::environments;
+use super::files;
+use super::gates::{OutsideWindowAction, WindowDecision};
+use super::inputs::{validate_input_value, InputConfig};
+use super::locks::{self, AcquireError, LockConfig};
+use super::model::{EdgeCondition, FlowGraph, FlowNode, FlowNodeKind};
+use super::quick_actions;
+use super::simulation::FlowFixtures;
+use super::subflows::{SubflowConfig, MAX_SUBFLOW_DEPTH};
+use super::types::{FlowNodeRun, FlowNodeStatus, FlowRun, FlowRunStatus};
+use crate::commands::flows::Flow;
+use crate::feature_flags::{self, FeatureFlag};
+use crate::types::Environment;
+use crate::windows;
+
+/// Event emitted with the full `FlowRun` whenever a run or one of its nodes changes.
+pub const FLOW_RUN_UPDATED_EVENT: &str = "flow-run-updated";
+
+/// Event emitted with a `FlowInputRequest` when an input node waits for the user.
+pub const FLOW_INPUT_REQUESTED_EVENT: &str = "flow-input-requested";
+
+/// Number of finished runs kept in memory for `list_flow_runs`.
+const MAX_RETAINED_RUNS: usize = 100;
+
+/// Longest single sleep while waiting, so cancellation is noticed promptly.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A run tracked by the registry.
+struct RunEntry {
+    run: FlowRun,
+    cancelled: Arc<AtomicBool>,
+    /// Channels of input nodes waiting for a value, keyed by node ID
+    input_senders: HashMap<String, oneshot::Sender<String>>,
+    /// Sub-flow nesting level (0 for top-level runs)
+    depth: u32,
+    /// Locks held by this run and its ancestors
+    held_locks: Vec<String>,
+    /// Fixtures of a simulated run, inherited by its sub-flows
+    fixtures: Option<Arc<FlowFixtures>>,
+    /// Environment the run deploys to, inherited by its sub-flows
+    environment: Option<Arc<Environment>>,
+}
+
+/// How a run is started.
+#[derive(Debug, Default)]
+pub struct RunOptions {
+    /// Initial run context used for templating
+    pub variables: HashMap<String, String>,
+    /// What started the run automatically; `None` for manual runs
+    pub triggered_by: Option<String>,
+    /// Set when the run is started by a `subflow` node
+    pub parent: Option<ParentRun>,
+    /// Runs the flow in simulation mode with these fixtures
+    pub simulation: Option<FlowFixtures>,
+    /// Environment whose variables are added to the run context as `env.*`
+    pub environment: Option<Environment>,
+}
+
+/// The sub-flow node that started a nested run.
+#[derive(Debug)]
+pub struct ParentRun {
+    pub run_id: String,
+    pub node_id: String,
+    depth: u32,
+    held_locks: Vec<String>,
+    fixtures: Option<Arc<FlowFixtures>>,
+    environment: Option<Arc<Environment>>,
+    /// Receives the child's final status, so the parent doesn't depend on
+    /// the child still being in the registry
+    finished: Option<oneshot::Sender<FlowRunStatus>>,
+}
+
+/// All runs started since the app launched, keyed by run ID.
+static FLOW_RUNS: LazyLock<Mutex<HashMap<String, RunEntry>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Sequence number appended to run IDs to keep them unique within a millisecond.
+static NEXT_RUN_SEQ: AtomicU64 = AtomicU64::new(1);
+
+/// Why a node stopped without succeeding.
+enum NodeFailure {
+    Failed(String),
+    Cancelled,
+}
+
+fn runs() -> MutexGuard<'static, HashMap<String, RunEntry>> {
+    FLOW_RUNS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn now_rfc3339() -> String {
+    Utc::now().to_rfc3339()
+}
+
+/// Validates a flow and starts executing it in the background.
+///
+/// Returns the initial run state; progress is reported through
+/// [`FLOW_RUN_UPDATED_EVENT`].
+pub fn start_run(app: &AppHandle, flow: &Flow, mut options: RunOptions) -> Result<FlowRun, String> {
+    feature_flags::ensure_enabled(app, FeatureFlag::FlowEngine)?;
+    let graph = FlowGraph::from_flow(flow)?;
+    let order = graph.execution_order()?;
+    let mut lock_configs = graph.locks()?;
+
+    let (depth, mut held_locks, inherited_fixtures, inherited_environment) = match &options.parent {
+        Some(parent) => (
+            parent.depth + 1,
+            parent.held_locks.clone(),
+            parent.fixtures.clone(),
+            parent.environment.clone(),
+        ),
+        None => (0, Vec::new(), None, None),
+    };
+    let fixtures = options.simulation.map(Arc::new).or(inherited_fixtures);
+    let environment = options.environment.map(Arc::new).or(inherited_environment);
+
+    if fixtures.is_some() {
+        // Simulated runs must never block real runs
+        lock_configs.clear();
+    } else {
+        // A sub-flow already runs under its ancestors' locks; waiting for them would deadlock
+        lock_configs.retain(|l| !held_locks.iter().any(|h| h == l.name.trim()));
+    }
+    held_locks.extend(lock_configs.iter().map(|l| l.name.trim().to_string()));
+
+    let run_id = format!(
+        "run-{}-{}",
+        Utc::now().timestamp_millis(),
+        NEXT_RUN_SEQ.fetch_add(1, Ordering::Relaxed)
+    );
+
+    let run = FlowRun {
+        id: run_id.clone(),
+        flow_id: flow.id.clone(),
+        flow_name: flow.name.clone(),
+        triggered_by: options.triggered_by,
+        parent_run_id: options.parent.as_ref().map(|p| p.run_id.clone()),
+        parent_node_id: options.parent.as_ref().map(|p| p.node_id.clone()),
+        simulated: fixtures.is_some(),
+        environment_id: environment.as_ref().map(|e| e.id.clone()),
+        status: FlowRunStatus::Running,
+        started_at: now_rfc3339(),
+        finished_at: None,
+        nodes: order
+            .iter()
+            .map(|&i| FlowNodeRun {
+                node_id: graph.nodes[i].id.clone(),
+                label: graph.nodes[i].label.clone(),
+                status: FlowNodeStatus::Pending,
+                message: None,
+                started_at: None,
+                finished_at: None,
+                child_run_id: None,
+            })
+            .collect(),
+        pending_inputs: Vec::new(),
+        locks: lock_configs
+            .iter()
+            .map(|l| l.name.trim().to_string())
+            .collect(),
+        queued_on: None,
+    };
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let mut runs = runs();
+        prune_finished_runs(&mut runs);
+        runs.insert(
+            run_id.clone(),
+            RunEntry {
+                run: run.clone(),
+                cancelled: cancelled.clone(),
+                input_senders: HashMap::new(),
+                depth,
+                held_locks,
+                fixtures: fixtures.clone(),
+                environment: environment.clone(),
+            },
+        );
+    }
+
+    log::info!("Starting flow run {run_id} for flow {}", flow.id);
+    emit_run(app, &run);
+
+    let app = app.clone();
+    let mut variables = environment
+        .as_deref()
+        .map(environments::context)
+        .unwrap_or_default();
+    variables.extend(options.variables);
+    let state = RunState {
+        variables,
+        fixtures,
+        ..RunState::default()
+    };
+    let finished = options
+        .parent
+        .as_mut()
+        .and_then(|parent| parent.finished.take());
+    tauri::async_runtime::spawn(async move {
+        let status = execute_run(app, run_id, graph, order, lock_configs, state, cancelled).await;
+        if let Some(finished) = finished {
+            // The parent is gone if it was cancelled first
+            let _ = finished.send(status);
+        }
+    });
+
+    Ok(run)
+}
+
+/// Returns the current state of a run.
+pub fn get_run(run_id: &str) -> Option<FlowRun> {
+    runs().get(run_id).map(|entry| entry.run.clone())
+}
+
+/// Returns all known runs, most recent first.
+pub fn list_runs() -> Vec<FlowRun> {
+    let mut list: Vec<FlowRun> = runs().values().map(|entry| entry.run.clone()).collect();
+    list.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    list
+}
+
+/// Requests cancellation of a queued or running flow.
+///
+/// The node currently executing stops at its next cancellation check.
+pub fn cancel_run(run_id: &str) -> Result<(), String> {
+    let runs = runs();
+    let entry = runs
+        .get(run_id)
+        .ok_or_else(|| format!("Flow run not found: {run_id}"))?;
+
+    if !entry.run.status.is_active() {
+        return Err(format!("Flow run {run_id} is not running"));
+    }
+
+    entry.cancelled.store(true, Ordering::SeqCst);
+    log::info!("Cancellation requested for flow run {run_id}");
+    Ok(())
+}
+
+/// Delivers a user-provided value to an input node waiting in a run.
+pub fn provide_input(
+    app: &AppHandle,
+    run_id: &str,
+    node_id: &str,
+    value: &str,
+) -> Result<(), String> {
+    let snapshot = {
+        let mut runs = runs();
+        let entry = runs
+            .get_mut(run_id)
+            .ok_or_else(|| format!("Flow run not found: {run_id}"))?;
+
+        let request = entry
+            .run
+            .pending_inputs
+            .iter()
+            .find(|r| r.node_id == node_id)
+            .ok_or_else(|| format!("Node {node_id} is not waiting for input"))?;
+        let value = validate_input_value(request, value)?;
+
+        let sender = entry
+            .input_senders
+            .remove(node_id)
+            .ok_or_else(|| format!("Node {node_id} is not waiting for input"))?;
+        sender
+            .send(value)
+            .map_err(|_| format!("Flow run {run_id} is no longer waiting for input"))?;
+
+        entry.run.pending_inputs.retain(|r| r.node_id != node_id);
+        entry.run.clone()
+    };
+
+    log::info!("Input provided for node {node_id} in flow run {run_id}");
+    emit_run(app, &snapshot);
+    Ok(())
+}
+
+/// Drops the oldest finished runs once the registry grows past its limit.
+fn prune_finished_runs(runs: &mut HashMap<String, RunEntry>) {
+    if runs.len() < MAX_RETAINED_RUNS {
+        return;
+    }
+
+    let mut finished: Vec<(String, String)> = runs
+        .values()
+        .filter(|entry| !entry.run.status.is_active())
+        .map(|entry| (entry.run.started_at.clone(), entry.run.id.clone()))
+        .collect();
+    finished.sort();
+
+    let excess = runs.len() + 1 - MAX_RETAINED_RUNS;
+    for (_, id) in finished.into_iter().take(excess) {
+        runs.remove(&id);
+    }
+}
+
+fn emit_run(app: &AppHandle, run: &FlowRun) {
+    windows::broadcast(app, FLOW_RUN_UPDATED_EVENT, run.clone());
+}
+
+/// Applies `update` to a run and broadcasts the new state.
+fn update_run(app: &AppHandle, run_id: &str, update: impl FnOnce(&mut FlowRun)) {
+    let snapshot = {
+        let mut runs = runs();
+        let Some(entry) = runs.get_mut(run_id) else {
+            return;
+        };
+        update(&mut entry.run);
+        entry.run.clone()
+    };
+    emit_run(app, &snapshot);
+}
+
+/// Sets the status (and optionally the message) of a node and broadcasts it.
+fn update_node(
+    app: &AppHandle,
+    run_id: &str,
+    node_id: &str,
+    status: FlowNodeStatus,
+    message: Option<String>,
+) {
+    update_run(app, run_id, |run| {
+        if let Some(node) = run.nodes.iter_mut().find(|n| n.node_id == node_id) {
+            match status {
+                FlowNodeStatus::Running if node.started_at.is_none() => {
+                    node.started_at = Some(now_rfc3339());
+                }
+                FlowNodeStatus::Succeeded
+                | FlowNodeStatus::Failed
+                | FlowNodeStatus::Skipped
+                | FlowNodeStatus::Cancelled => {
+                    node.finished_at = Some(now_rfc3339());
+                }
+                _ => {}
+            }
+            node.status = status;
+            if message.is_some() {
+                node.message = message;
+            }
+        }
+    });
+}
+
+async fn execute_run(
+    app: AppHandle,
+    run_id: String,
+    graph: FlowGraph,
+    order: Vec<usize>,
+    lock_configs: Vec<LockConfig>,
+    mut state: RunState,
+    cancelled: Arc<AtomicBool>,
+) -> FlowRunStatus {
+    // Held until the run finishes
+    let _permits = match acquire_locks(&app, &run_id, &lock_configs, &cancelled).await {
+        Ok(permits) => permits,
+        Err(failure) => {
+            let (status, message) = match failure {
+                NodeFailure::Failed(message) => (FlowNodeStatus::Skipped, Some(message)),
+                NodeFailure::Cancelled => (FlowNodeStatus::Cancelled, None),
+            };
+            for &index in &order {
+                update_node(
+                    &app,
+                    &run_id,
+                    &graph.nodes[index].id,
+                    status,
+                    message.clone(),
+                );
+            }
+            let final_status = if status == FlowNodeStatus::Cancelled {
+                FlowRunStatus::Cancelled
+            } else {
+                FlowRunStatus::Failed
+            };
+            log::info!("Flow run {run_id} finished before acquiring locks: {final_status:?}");
+            update_run(&app, &run_id, |run| {
+                run.status = final_status;
+                run.queued_on = None;
+                run.finished_at = Some(now_rfc3339());
+            });
+            return final_status;
+        }
+    };
+
+    let executor = AppExecutor {
+        app: &app,
+        run_id: &run_id,
+        cancelled: &cancelled,
+    };
+    let final_status =
+        execute_graph(&executor, &run_id, &graph, order, &mut state, &cancelled).await;
+
+    log::info!("Flow run {run_id} finished: {final_status:?}");
+    {
+        let mut runs = runs();
+        if let Some(entry) = runs.get_mut(&run_id) {
+            entry.input_senders.clear();
+        }
+    }
+    update_run(&app, &run_id, |run| {
+        run.status = final_status;
+        run.finished_at = Some(now_rfc3339());
+        run.pending_inputs.clear();
+    });
+    final_status
+}
+
+/// Executes the regular nodes, then the flow failure handlers if a node
+/// failed. Returns the final status of the run.
+async fn execute_graph(
+    executor: &impl NodeExecutor,
+    run_id: &str,
+    graph: &FlowGraph,
+    order: Vec<usize>,
+    state: &mut RunState,
+    cancelled: &AtomicBool,
+) -> FlowRunStatus {
+    let (handlers, regular): (Vec<usize>, Vec<usize>) = order
+        .into_iter()
+        .partition(|&i| graph.nodes[i].on_flow_failure);
+
+    execute_nodes(executor, run_id, graph, &regular, state, cancelled).await;
+
+    if !handlers.is_empty() {
+        if state.failed && !cancelled.load(Ordering::SeqCst) {
+            log::info!("Flow run {run_id} failed, running flow failure handlers");
+            execute_nodes(executor, run_id, graph, &handlers, state, cancelled).await;
+        } else {
+            let (status, message) = if cancelled.load(Ordering::SeqCst) {
+                (FlowNodeStatus::Cancelled, None)
+            } else {
+                (
+                    FlowNodeStatus::Skipped,
+                    Some("Flow did not fail".to_string()),
+                )
+            };
+            for &index in &handlers {
+                executor.report(&graph.nodes[index].id, status, message.clone());
+            }
+        }
+    }
+
+    if cancelled.load(Ordering::SeqCst) {
+        FlowRunStatus::Cancelled
+    } else if state.failed {
+        FlowRunStatus::Failed
+    } else {
+        FlowRunStatus::Succeeded
+    }
+}
+
+/// Runs nodes and records their status; implemented by the app for real
+/// runs and by tests.
+trait NodeExecutor {
+    /// Records a node's status (and optionally its message).
+    fn report(&self, node_id: &str, status: FlowNodeStatus, message: Option<String>);
+
+    /// Executes a node whose upstream conditions are met.
+    async fn execute(
+        &self,
+        node: &FlowNode,
+        state: &mut RunState,
+    ) -> Result<Option<String>, NodeFailure>;
+}
+
+/// Executes nodes of a run against the app and broadcasts their status.
+struct AppExecutor<'a> {
+    app: &'a AppHandle,
+    run_id: &'a str,
+    cancelled: &'a AtomicBool,
+}
+
+impl NodeExecutor for AppExecutor<'_> {
+    fn report(&self, node_id: &str, status: FlowNodeStatus, message: Option<String>) {
+        update_node(self.app, self.run_id, node_id, status, message);
+    }
+
+    async fn execute(
+        &self,
+        node: &FlowNode,
+        state: &mut RunState,
+    ) -> Result<Option<String>, NodeFailure> {
+        execute_node(
+            self.app,
+            self.run_id,
+            node,
+            &mut state.variables,
+            state.fixtures.as_deref(),
+            self.cancelled,
+        )
+        .await
+    }
+}
+
+/// Mutable state shared by the nodes of a run.
+#[derive(Default)]
+struct RunState {
+    /// Final status of every node executed or skipped so far
+    statuses: HashMap<String, FlowNodeStatus>,
+    /// Run context used for templating (`inputs.*`, ...)
+    variables: HashMap<String, String>,
+    /// Whether any node failed
+    failed: bool,
+    /// Set for simulated runs
+    fixtures: Option<Arc<FlowFixtures>>,
+}
+
+/// Executes `indices` in order, skipping nodes whose incoming edge
+/// conditions are not met.
+async fn execute_nodes(
+    executor: &impl NodeExecutor,
+    run_id: &str,
+    graph: &FlowGraph,
+    indices: &[usize],
+    state: &mut RunState,
+    cancelled: &AtomicBool,
+) {
+    for &index in indices {
+        let node = &graph.nodes[index];
+
+        if cancelled.load(Ordering::SeqCst) {
+            executor.report(&node.id, FlowNodeStatus::Cancelled, None);
+            state
+                .statuses
+                .insert(node.id.clone(), FlowNodeStatus::Cancelled);
+            continue;
+        }
+
+        let conditions_met = graph.incoming(&node.id).iter().all(|edge| {
+            let expected = match edge.condition {
+                EdgeCondition::OnSuccess => FlowNodeStatus::Succeeded,
+                EdgeCondition::OnFailure => FlowNodeStatus::Failed,
+            };
+            state.statuses.get(&edge.source) == Some(&expected)
+        });
+
+        if !conditions_met {
+            executor.report(
+                &node.id,
+                FlowNodeStatus::Skipped,
+                Some("Upstream conditions not met".to_string()),
+            );
+            state
+                .statuses
+                .insert(node.id.clone(), FlowNodeStatus::Skipped);
+            continue;
+        }
+
+        executor.report(&node.id, FlowNodeStatus::Running, None);
+
+        let status = match executor.execute(node, state).await {
+            Ok(message) => {
+                executor.report(&node.id, FlowNodeStatus::Succeeded, message);
+                FlowNodeStatus::Succeeded
+            }
+            Err(NodeFailure::Failed(message)) => {
+                log::warn!("Flow run {run_id}: node '{}' failed: {message}", node.label);
+                executor.report(&node.id, FlowNodeStatus::Failed, Some(message));
+                state.failed = true;
+                FlowNodeStatus::Failed
+            }
+            Err(NodeFailure::Cancelled) => {
+                executor.report(&node.id, FlowNodeStatus::Cancelled, None);
+                FlowNodeStatus::Cancelled
+            }
+        };
+        state.statuses.insert(node.id.clone(), status);
+    }
+}
+
+/// Acquires the run's locks, marking the run `queued` while it waits.
+async fn acquire_locks(
+    app: &AppHandle,
+    run_id: &str,
+    lock_configs: &[LockConfig],
+    cancelled: &AtomicBool,
+) -> Result<Vec<tokio::sync::OwnedSemaphorePermit>, NodeFailure> {
+    if lock_configs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut queued = false;
+    let result = locks::acquire_all(lock_configs, cancelled, WAIT_POLL_INTERVAL, |name| {
+        log::info!("Flow run {run_id} queued on lock '{name}'");
+        queued = true;
+        update_run(app, run_id, |run| {
+            run.status = FlowRunStatus::Queued;
+            run.queued_on = Some(name.to_string());
+        });
+    })
+    .await;
+
+    match result {
+        Ok(permits) => {
+            if queued {
+                update_run(app, run_id, |run| {
+                    run.status = FlowRunStatus::Running;
+                    run.queued_on = None;
+                });
+            }
+            Ok(permits)
+        }
+        Err(AcquireError::Invalid(message)) => Err(NodeFailure::Failed(message)),
+        Err(AcquireError::Cancelled) => Err(NodeFailure::Cancelled),
+    }
+}
+
+/// Executes a single node. Returns an optional message describing the outcome.
+async fn execute_node(
+    app: &AppHandle,
+    run_id: &str,
+    node: &FlowNode,
+    variables: &mut HashMap<String, String>,
+    fixtures: Option<&FlowFixtures>,
+    cancelled: &AtomicBool,
+) -> Result<Option<String>, NodeFailure> {
+    if let Some(fixture) = fixtures.and_then(|f| f.nodes.get(&node.id)) {
+        return fixture
+            .apply(variables)
+            .map(Some)
+            .map_err(NodeFailure::Failed);
+    }
+
+    let kind = node.resolve(variables).map_err(NodeFailure::Failed)?;
+
+    if fixtures.is_some() {
+        if let Some(result) = simulate_node(&kind) {
+            return result.map(Some).map_err(NodeFailure::Failed);
+        }
+    }
+
+    match &kind {
+        FlowNodeKind::Integration => Ok(None),
+        FlowNodeKind::Lock(config) => Ok(Some(format!("Holding lock '{}'", config.name.trim()))),
+        FlowNodeKind::Subflow(config) => {
+            run_subflow(app, run_id, &node.id, config, cancelled).await
+        }
+        FlowNodeKind::Artifact(config) => {
+            let dir = artifacts::run_dir(app, run_id).map_err(NodeFailure::Failed)?;
+            let artifact =
+                artifacts::write_artifact(&dir, &node.id, &config.name, config.content.as_bytes())
+                    .map_err(NodeFailure::Failed)?;
+            Ok(Some(format!(
+                "Saved artifact {} ({} bytes)",
+                artifact.name, artifact.size_bytes
+            )))
+        }
+        FlowNodeKind::SetImage(config) => deployments::set_image(app, config)
+            .await
+            .map(Some)
+            .map_err(NodeFailure::Failed),
+        FlowNodeKind::Canary(config) => deployments::run_canary(app, config)
+            .await
+            .map(Some)
+            .map_err(NodeFailure::Failed),
+        FlowNodeKind::Apply(config) => deployments::apply(app, config)
+            .await
+            .map(Some)
+            .map_err(NodeFailure::Failed),
+        FlowNodeKind::GitLabFile(config) => {
+            let file = files::read_file(app, config)
+                .await
+                .map_err(NodeFailure::Failed)?;
+            let key = config.context_key(&node.id);
+            let message = format!(
+                "Read {} ({} bytes, commit {}) into {key}",
+                file.file_path, file.size_bytes, file.last_commit_id
+            );
+            variables.insert(key, file.content);
+            Ok(Some(message))
+        }
+        FlowNodeKind::SmokeTests(config) => checks::run_smoke_tests(app, config)
+            .await
+            .map(Some)
+            .map_err(NodeFailure::Failed),
+        FlowNodeKind::QuickAction(action) => quick_actions::run_action(app, action)
+            .await
+            .map(Some)
+            .map_err(NodeFailure::Failed),
+        FlowNodeKind::WebhookTrigger(_) => Ok(Some(match variables.get("trigger.event") {
+            Some(event) => format!("Triggered by {event} event"),
+            None => "Started manually".to_string(),
+        })),
+        FlowNodeKind::Wait(config) => {
+            let now = Utc::now();
+            let deadline = config.deadline(now).map_err(NodeFailure::Failed)?;
+            update_node(
+                app,
+                run_id,
+                &node.id,
+                FlowNodeStatus::Waiting,
+                Some(format!("Waiting until {}", deadline.to_rfc3339())),
+            );
+            sleep_until(deadline, cancelled).await?;
+            Ok(Some(format!("Waited until {}", deadline.to_rfc3339())))
+        }
+        FlowNodeKind::TimeWindow(config) => loop {
+            match config.evaluate(Utc::now()).map_err(NodeFailure::Failed)? {
+                WindowDecision::Open => {
+                    return Ok(Some("Inside time window".to_string()));
+                }
+                WindowDecision::OpensAt(opens_at) => {
+                    if config.on_outside == OutsideWindowAction::Fail {
+                        return Err(NodeFailure::Failed(format!(
+                            "Outside time window (next opening at {})",
+                            opens_at.to_rfc3339()
+                        )));
+                    }
+                    update_node(
+                        app,
+                        run_id,
+                        &node.id,
+                        FlowNodeStatus::Waiting,
+                        Some(format!(
+                            "Waiting for time window at {}",
+                            opens_at.to_rfc3339()
+                        )),
+                    );
+                    sleep_until(opens_at, cancelled).await?;
+                }
+            }
+        },
+        FlowNodeKind::Input(config) => {
+            let value = wait_for_input(app, run_id, &node.id, config, cancelled).await?;
+            let key = config.context_key(&node.id);
+            let message = format!("{key} = {value}");
+            variables.insert(key, value);
+            Ok(Some(message))
+        }
+    }
+}
+
+/// Publishes an input request and waits until the user answers or the run is cancelled.
+async fn wait_for_input(
+    app: &AppHandle,
+    run_id: &str,
+    node_id: &str,
+    config: &InputConfig,
+    cancelled: &AtomicBool,
+) -> Result<String, NodeFailure> {
+    let request = config.to_request(run_id, node_id);
+    let (sender, mut receiver) = oneshot::channel();
+
+    {
+        let mut runs = runs();
+        let Some(entry) = runs.get_mut(run_id) else {
+            return Err(NodeFailure::Cancelled);
+        };
+        entry.input_senders.insert(node_id.to_string(), sender);
+        entry.run.pending_inputs.push(request.clone());
+    }
+
+    windows::broadcast(app, FLOW_INPUT_REQUESTED_EVENT, request);
+    update_node(
+        app,
+        run_id,
+        node_id,
+        FlowNodeStatus::Waiting,
+        Some(format!("Waiting for input: {}", config.prompt)),
+    );
+
+    loop {
+        if cancelled.load(Ordering::SeqCst) {
+            update_run(app, run_id, |run| {
+                run.pending_inputs.retain(|r| r.node_id != node_id);
+            });
+            return Err(NodeFailure::Cancelled);
+        }
+
+        tokio::select! {
+            value = &mut receiver => {
+                return value.map_err(|_| NodeFailure::Cancelled);
+            }
+            _ = tokio::time::sleep(WAIT_POLL_INTERVAL) => {}
+        }
+    }
+}
+
+/// Resolves nodes that would touch real services or wait in a simulated run.
+///
+/// Returns `None` for nodes that are safe to execute as usual (inputs,
+/// artifacts, sub-flows, which inherit the simulation).
+fn simulate_node(kind: &FlowNodeKind) -> Option<Result<String, String>> {
+    let now = Utc::now();
+    let result = match kind {
+        FlowNodeKind::Integration => Ok("Simulated: no fixture recorded".to_string()),
+        FlowNodeKind::SetImage(config) => Ok(format!(
+            "Simulated: would set image of {}",
+            config.describe()
+        )),
+        FlowNodeKind::Canary(config) => Ok(format!("Simulated: would {}", config.describe())),
+        FlowNodeKind::Apply(config) => Ok(format!("Simulated: would apply {}", config.describe())),
+        FlowNodeKind::GitLabFile(config) => {
+            Ok(format!("Simulated: would read {}", config.describe()))
+        }
+        FlowNodeKind::SmokeTests(config) => Ok(format!(
+            "Simulated: would run smoke tests for environment {}",
+            config.environment_id
+        )),
+        FlowNodeKind::QuickAction(action) => Ok(format!(
+            "Simulated: would {}",
+            quick_actions::describe(action)
+        )),
+        FlowNodeKind::Lock(config) => Ok(format!(
+            "Simulated: lock '{}' not acquired",
+            config.name.trim()
+        )),
+        FlowNodeKind::Wait(config) => config
+            .deadline(now)
+            .map(|deadline| format!("Simulated: would wait until {}", deadline.to_rfc3339())),
+        FlowNodeKind::TimeWindow(config) => {
+            config.evaluate(now).and_then(|decision| match decision {
+                WindowDecision::Open => Ok("Simulated: inside time window".to_string()),
+                WindowDecision::OpensAt(opens_at)
+                    if config.on_outside == OutsideWindowAction::Fail =>
+                {
+                    Err(format!(
+                        "Outside time window (next opening at {})",
+                        opens_at.to_rfc3339()
+                    ))
+                }
+                WindowDecision::OpensAt(opens_at) => Ok(format!(
+                    "Simulated: would wait for time window at {}",
+                    opens_at.to_rfc3339()
+                )),
+            })
+        }
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// Starts a nested run for a `subflow` node and waits for it to finish.
+async fn run_subflow(
+    app: &AppHandle,
+    run_id: &str,
+    node_id: &str,
+    config: &SubflowConfig,
+    cancelled: &AtomicBool,
+) -> Result<Option<String>, NodeFailure> {
+    let (finished, mut receiver) = oneshot::channel();
+    let parent = {
+        let runs = runs();
+        let entry = runs.get(run_id).ok_or(NodeFailure::Cancelled)?;
+        ParentRun {
+            run_id: run_id.to_string(),
+            node_id: node_id.to_string(),
+            depth: entry.depth,
+            held_locks: entry.held_locks.clone(),
+            fixtures: entry.fixtures.clone(),
+            environment: entry.environment.clone(),
+            finished: Some(finished),
+        }
+    };
+    if parent.depth + 1 > MAX_SUBFLOW_DEPTH {
+        return Err(NodeFailure::Failed(format!(
+            "Sub-flows are nested more than {MAX_SUBFLOW_DEPTH} levels deep"
+        )));
+    }
+
+    let flow = crate::commands::flows::load_flow(app.clone(), config.flow_id.clone())
+        .await
+        .map_err(NodeFailure::Failed)?;
+    let options = RunOptions {
+        variables: config.child_variables(),
+        triggered_by: Some(format!("sub-flow of {run_id}")),
+        parent: Some(parent),
+        simulation: None,
+        environment: None,
+    };
+    let child = start_run(app, &flow, options)
+        .map_err(|e| NodeFailure::Failed(format!("Failed to start sub-flow: {e}")))?;
+
+    update_run(app, run_id, |run| {
+        if let Some(node) = run.nodes.iter_mut().find(|n| n.node_id == node_id) {
+            node.child_run_id = Some(child.id.clone());
+            node.message = Some(format!("Running sub-flow '{}' ({})", flow.name, child.id));
+        }
+    });
+
+    loop {
+        if cancelled.load(Ordering::SeqCst) {
+            // The child may have finished on its own in the meantime
+            let _ = cancel_run(&child.id);
+            return Err(NodeFailure::Cancelled);
+        }
+
+        tokio::select! {
+            status = &mut receiver => {
+                return subflow_outcome(&flow.name, &child.id, status.ok());
+            }
+            _ = tokio::time::sleep(WAIT_POLL_INTERVAL) => {}
+        }
+    }
+}
+
+/// Maps a sub-flow's final status to the result of its `subflow` node.
+///
+/// `None` means the child stopped without reporting a status.
+fn subflow_outcome(
+    flow_name: &str,
+    child_id: &str,
+    status: Option<FlowRunStatus>,
+) -> Result<Option<String>, NodeFailure> {
+    match status {
+        Some(FlowRunStatus::Succeeded) => Ok(Some(format!(
+            "Sub-flow '{flow_name}' succeeded ({child_id})"
+        ))),
+        Some(FlowRunStatus::Failed) => Err(NodeFailure::Failed(format!(
+            "Sub-flow '{flow_name}' failed ({child_id})"
+        ))),
+        Some(FlowRunStatus::Cancelled) => Err(NodeFailure::Failed(format!(
+            "Sub-flow '{flow_name}' was cancelled ({child_id})"
+        ))),
+        Some(FlowRunStatus::Queued | FlowRunStatus::Running) | None => Err(NodeFailure::Failed(
+            format!("Sub-flow run {child_id} stopped without reporting an outcome"),
+        )),
+    }
+}
+
+/// Sleeps until `deadline`, checking for cancellation at least once per second.
+async fn sleep_until(deadline: DateTime<Utc>, cancelled: &AtomicBool) -> Result<(), NodeFailure> {
+    loop {
+        if cancelled.load(Ordering::SeqCst) {
+            return Err(NodeFailure::Cancelled);
+        }
+
+        let remaining = match (deadline - Utc::now()).to_std() {
+            Ok(remaining) if !remaining.is_zero() => remaining,
+            _ => return Ok(()),
+        };
+
+        tokio::time::sleep(remaining.min(WAIT_POLL_INTERVAL)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::cell::RefCell;
+
+    /// Executes nodes without side effects; the listed nodes fail.
+    struct Scripted {
+        failing: Vec<&'static str>,
+        executed: RefCell<Vec<String>>,
+        statuses: RefCell<HashMap<String, FlowNodeStatus>>,
+    }
+
+    impl Scripted {
+        fn failing(failing: &[&'static str]) -> Self {
+            Self {
+                failing: failing.to_vec(),
+                executed: RefCell::new(Vec::new()),
+                statuses: RefCell::new(HashMap::new()),
+            }
+        }
+
+        fn executed(&self) -> Vec<String> {
+            self.executed.borrow().clone()
+        }
+
+        fn status(&self, node_id: &str) -> Option<FlowNodeStatus> {
+            self.statuses.borrow().get(node_id).copied()
+        }
+    }
+
+    impl NodeExecutor for Scripted {
+        fn report(&self, node_id: &str, status: FlowNodeStatus, _message: Option<String>) {
+            self.statuses
+                .borrow_mut()
+                .insert(node_id.to_string(), status);
+        }
+
+        async fn execute(
+            &self,
+            node: &FlowNode,
+            _state: &mut RunState,
+        ) -> Result<Option<String>, NodeFailure> {
+            self.executed.borrow_mut().push(node.id.clone());
+            if self.failing.contains(&node.id.as_str()) {
+                Err(NodeFailure::Failed("Scripted failure".to_string()))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    fn graph(nodes: Value, edges: Value) -> FlowGraph {
+        FlowGraph::from_flow(&Flow {
+            id: "flow-1".to_string(),
+            name: "Test".to_string(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            nodes,
+            edges,
+            viewport: None,
+        })
+        .unwrap()
+    }
+
+    /// Deploy with a rollback branch and two chained flow failure handlers.
+    fn deploy_graph() -> FlowGraph {
+        graph(
+            json!([
+                { "id": "deploy", "type": "kubernetes", "data": {} },
+                { "id": "verify", "type": "kubernetes", "data": {} },
+                { "id": "rollback", "type": "kubernetes", "data": {} },
+                { "id": "notify", "type": "gitlab", "data": { "onFlowFailure": true } },
+                { "id": "page", "type": "gitlab", "data": { "onFlowFailure": true } }
+            ]),
+            json!([
+                { "source": "deploy", "target": "verify" },
+                { "source": "deploy", "target": "rollback", "sourceHandle": "on_failure" },
+                { "source": "notify", "target": "page" }
+            ]),
+        )
+    }
+
+    async fn run(graph: &FlowGraph, executor: &Scripted) -> FlowRunStatus {
+        let order = graph.execution_order().unwrap();
+        let mut state = RunState::default();
+        execute_graph(
+            executor,
+            "run-test",
+            graph,
+            order,
+            &mut state,
+            &AtomicBool::new(false),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_successful_run_skips_failure_branch_and_handlers() {
+        let executor = Scripted::failing(&[]);
+
+        assert_eq!(
+            run(&deploy_graph(), &executor).await,
+            FlowRunStatus::Succeeded
+        );
+        assert_eq!(executor.executed(), ["deploy", "verify"]);
+        assert_eq!(executor.status("rollback"), Some(FlowNodeStatus::Skipped));
+        assert_eq!(executor.status("notify"), Some(FlowNodeStatus::Skipped));
+        assert_eq!(executor.status("page"), Some(FlowNodeStatus::Skipped));
+    }
+
+    #[tokio::test]
+    async fn test_failure_branch_runs_when_its_source_fails() {
+        let executor = Scripted::failing(&["deploy"]);
+
+        assert_eq!(run(&deploy_graph(), &executor).await, FlowRunStatus::Failed);
+        assert_eq!(
+            executor.executed(),
+            ["deploy", "rollback", "notify", "page"]
+        );
+        assert_eq!(executor.status("verify"), Some(FlowNodeStatus::Skipped));
+        assert_eq!(executor.status("rollback"), Some(FlowNodeStatus::Succeeded));
+    }
+
+    #[tokio::test]
+    async fn test_handlers_run_once_after_a_later_failure() {
+        // The failure branch is not taken, but the run still fails
+        let executor = Scripted::failing(&["verify"]);
+
+        assert_eq!(run(&deploy_graph(), &executor).await, FlowRunStatus::Failed);
+        assert_eq!(executor.executed(), ["deploy", "verify", "notify", "page"]);
+        assert_eq!(executor.status("rollback"), Some(FlowNodeStatus::Skipped));
+        assert_eq!(executor.status("page"), Some(FlowNodeStatus::Succeeded));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_run_cancels_handlers() {
+        let graph = deploy_graph();
+        let executor = Scripted::failing(&["deploy"]);
+        let order = graph.execution_order().unwrap();
+        let status = execute_graph(
+            &executor,
+            "run-test",
+            &graph,
+            order,
+            &mut RunState::default(),
+            &AtomicBool::new(true),
+        )
+        .await;
+
+        assert_eq!(status, FlowRunStatus::Cancelled);
+        assert!(executor.executed().is_empty());
+        assert_eq!(executor.status("notify"), Some(FlowNodeStatus::Cancelled));
+    }
+
+    #[test]
+    fn test_subflow_outcome() {
+        let message = |result: Result<Option<String>, NodeFailure>| match result {
+            Ok(message) => message.unwrap_or_default(),
+            Err(NodeFailure::Failed(message)) => format!("failed: {message}"),
+            Err(NodeFailure::Cancelled) => "cancelled".to_string(),
+        };
+
+        assert_eq!(
+            message(subflow_outcome(
+                "Deploy",
+                "run-2",
+                Some(FlowRunStatus::Succeeded)
+            )),
+            "Sub-flow 'Deploy' succeeded (run-2)"
+        );
+        assert_eq!(
+            message(subflow_outcome(
+                "Deploy",
+                "run-2",
+                Some(FlowRunStatus::Cancelled)
+            )),
+            "failed: Sub-flow 'Deploy' was cancelled (run-2)"
+        );
+        assert_eq!(
+            message(subflow_outcome("Deploy", "run-2", None)),
+            "failed: Sub-flow run run-2 stopped without reporting an outcome"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Simulation mode.
+//!
+//! A simulated run walks the same graph as a real run but never touches real
+//! services: integration nodes and gates resolve immediately, locks are not
+//! taken, and any node can be given a recorded outcome through fixtures (for
+//! example, a failing deploy to exercise a rollback branch).
+//!
+//! Fixtures are passed to `simulate_flow` or read from
+//! `<app data>/flow_fixtures/<flow id>.json`.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// Outcome recorded for a node.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FixtureOutcome {
+    #[default]
+    Succeeded,
+    Failed,
+}
+
+/// Recorded result of a single node.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct NodeFixture {
+    #[serde(default)]
+    pub outcome: FixtureOutcome,
+    /// Message reported for the node (or failure reason)
+    pub message: Option<String>,
+    /// Values added to the run context (e.g., `inputs.version`)
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+/// Recorded node results for a simulated run, keyed by node ID.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FlowFixtures {
+    #[serde(default)]
+    pub nodes: HashMap<String, NodeFixture>,
+}
+
+impl NodeFixture {
+    /// Applies the fixture: merges its variables and returns the node result.
+    pub fn apply(&self, variables: &mut HashMap<String, String>) -> Result<String, String> {
+        variables.extend(self.variables.clone());
+        match self.outcome {
+            FixtureOutcome::Succeeded => Ok(self
+                .message
+                .clone()
+                .unwrap_or_else(|| "Simulated: succeeded (fixture)".to_string())),
+            FixtureOutcome::Failed => Err(self
+                .message
+                .clone()
+                .unwrap_or_else(|| "Simulated: failed (fixture)".to_string())),
+        }
+    }
+}
+
+fn fixtures_path(app: &AppHandle, flow_id: &str) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+
+    let sanitized_id: String = flow_id
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    if sanitized_id.is_empty() {
+        return Err("Flow ID cannot be empty".to_string());
+    }
+
+    Ok(app_data_dir
+        .join("flow_fixtures")
+        .join(format!("{sanitized_id}.json")))
+}
+
+/// Loads the recorded fixtures of a flow; no file means no fixtures.
+pub fn load_fixtures(app: &AppHandle, flow_id: &str) -> Result<FlowFixtures, String> {
+    let path = fixtures_path(app, flow_id)?;
+    if !path.exists() {
+        log::debug!("No fixtures recorded for flow {flow_id}");
+        return Ok(FlowFixtures::default());
+    }
+
+    let contents =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read fixtures: {e}"))?;
+    serde_json::from_str(&contents).map_err(|e| {
+        log::error!("Failed to parse fixtures {path:?}: {e}");
+        format!("Failed to parse fixtures: {e}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_outcomes() {
+        let fixtures: FlowFixtures = serde_json::from_value(serde_json::json!({
+            "nodes": {
+                "build": { "variables": { "build.number": "42" } },
+                "deploy": { "outcome": "failed", "message": "ImagePullBackOff" }
+            }
+        }))
+        .unwrap();
+
+        let mut variables = HashMap::new();
+        assert!(fixtures.nodes["build"].apply(&mut variables).is_ok());
+        assert_eq!(variables["build.number"], "42");
+        assert_eq!(
+            fixtures.nodes["deploy"].apply(&mut variables).unwrap_err(),
+            "ImagePullBackOff"
+        );
+    }
+}
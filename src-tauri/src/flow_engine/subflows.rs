@@ -0,0 +1,56 @@
+//! Sub-flow nodes.
+//!
+//! A `subflow` node runs another saved flow as a nested run and waits for it.
+//! Parameters are rendered against the parent's run context and exposed to the
+//! child as `params.<name>`, so shared sequences (smoke tests, notifications)
+//! can be factored out into their own flows.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Maximum nesting of sub-flow runs; guards against flows invoking themselves.
+pub const MAX_SUBFLOW_DEPTH: u32 = 5;
+
+/// Configuration of a `subflow` node, read from the node's `data` object.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubflowConfig {
+    /// ID of the saved flow to run
+    pub flow_id: String,
+    /// Parameter name -> value (templates are rendered by the parent run)
+    #[serde(default)]
+    pub parameters: HashMap<String, String>,
+}
+
+impl SubflowConfig {
+    /// Builds the child run's initial context.
+    pub fn child_variables(&self) -> HashMap<String, String> {
+        self.parameters
+            .iter()
+            .map(|(name, value)| (format!("params.{name}"), value.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_child_variables() {
+        let config: SubflowConfig = serde_json::from_value(serde_json::json!({
+            "flowId": "smoke-test",
+            "parameters": { "url": "https://staging.example.com" }
+        }))
+        .unwrap();
+
+        assert_eq!(config.flow_id, "smoke-test");
+        assert_eq!(
+            config.child_variables(),
+            HashMap::from([(
+                "params.url".to_string(),
+                "https://staging.example.com".to_string()
+            )])
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! `{{ variable }}` templating for node configuration.
+//!
+//! Values produced while a run executes (user input, etc.) are stored in the
+//! run context under dotted names such as `inputs.release_version`. Before a
+//! node executes, every string in its `data` is rendered against that context.
+
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Matches `{{ name }}` placeholders; names are dotted identifiers.
+static PLACEHOLDER_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{\{\s*([A-Za-z0-9_.-]+)\s*\}\}").expect("Failed to compile placeholder regex")
+});
+
+/// Renders all placeholders in a string.
+///
+/// Unknown variables are an error rather than being left in place, so a node
+/// never acts on a literal `{{ ... }}` value.
+pub fn render_str(template: &str, variables: &HashMap<String, String>) -> Result<String, String> {
+    let mut missing = None;
+    let rendered = PLACEHOLDER_PATTERN.replace_all(template, |caps: &regex::Captures| {
+        let name = &caps[1];
+        match variables.get(name) {
+            Some(value) => value.clone(),
+            None => {
+                missing.get_or_insert_with(|| name.to_string());
+                String::new()
+            }
+        }
+    });
+
+    match missing {
+        Some(name) => Err(format!("Unknown template variable '{name}'")),
+        None => Ok(rendered.into_owned()),
+    }
+}
+
+/// Renders every string inside a JSON value, recursively.
+pub fn render_value(value: &Value, variables: &HashMap<String, String>) -> Result<Value, String> {
+    Ok(match value {
+        Value::String(s) => Value::String(render_str(s, variables)?),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| render_value(item, variables))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| Ok((k.clone(), render_value(v, variables)?)))
+                .collect::<Result<_, String>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn vars() -> HashMap<String, String> {
+        HashMap::from([("inputs.version".to_string(), "1.4.2".to_string())])
+    }
+
+    #[test]
+    fn test_render_str() {
+        let rendered = render_str("registry/app:{{ inputs.version }}", &vars()).unwrap();
+        assert_eq!(rendered, "registry/app:1.4.2");
+    }
+
+    #[test]
+    fn test_render_str_without_placeholders() {
+        assert_eq!(render_str("plain", &vars()).unwrap(), "plain");
+    }
+
+    #[test]
+    fn test_unknown_variable_is_error() {
+        let err = render_str("{{inputs.missing}}", &vars()).unwrap_err();
+        assert!(err.contains("inputs.missing"));
+    }
+
+    #[test]
+    fn test_render_value_recurses() {
+        let data = json!({
+            "label": "Deploy {{inputs.version}}",
+            "seconds": 30,
+            "tags": ["v{{inputs.version}}"]
+        });
+        let rendered = render_value(&data, &vars()).unwrap();
+        assert_eq!(
+            rendered,
+            json!({
+                "label": "Deploy 1.4.2",
+                "seconds": 30,
+                "tags": ["v1.4.2"]
+            })
+        );
+    }
+}
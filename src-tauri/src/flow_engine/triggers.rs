@@ -0,0 +1,227 @@
+//! Webhook triggers.
+//!
+//! A `webhook_trigger` node makes its flow start automatically when the
+//! webhook receiver gets a matching event. The event payload is flattened into
+//! the run context as `trigger.*` variables (e.g., `{{ trigger.ref }}`).
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use super::model::FlowGraph;
+use super::runner::{self, RunOptions};
+use super::types::FlowRun;
+use crate::commands::flows::Flow;
+
+/// An event delivered to the webhook receiver.
+#[derive(Debug, Clone)]
+pub struct WebhookEvent {
+    /// Sender of the event (e.g., "gitlab", "harbor", "generic")
+    pub source: String,
+    /// Normalized event name (e.g., "tag_push", "push_artifact")
+    pub event: String,
+    /// Parsed JSON body
+    pub payload: Value,
+}
+
+/// Configuration of a `webhook_trigger` node, read from the node's `data` object.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookTriggerConfig {
+    /// Required event source
+    pub source: String,
+    /// Required event name; any event from `source` when omitted
+    pub event: Option<String>,
+    /// Dotted payload path -> expected value; `*` matches any characters
+    #[serde(default)]
+    pub filters: HashMap<String, String>,
+}
+
+impl WebhookTriggerConfig {
+    /// Whether an incoming event satisfies this trigger.
+    pub fn matches(&self, event: &WebhookEvent) -> bool {
+        if !self.source.eq_ignore_ascii_case(&event.source) {
+            return false;
+        }
+        if let Some(name) = &self.event {
+            if !name.eq_ignore_ascii_case(&event.event) {
+                return false;
+            }
+        }
+
+        self.filters.iter().all(|(path, pattern)| {
+            lookup(&event.payload, path)
+                .and_then(scalar_to_string)
+                .is_some_and(|value| glob_matches(pattern, &value))
+        })
+    }
+}
+
+/// Builds the run context for a triggered run.
+pub fn event_variables(event: &WebhookEvent) -> HashMap<String, String> {
+    let mut variables = HashMap::new();
+    flatten("trigger", &event.payload, &mut variables);
+    variables.insert("trigger.source".to_string(), event.source.clone());
+    variables.insert("trigger.event".to_string(), event.event.clone());
+    variables
+}
+
+/// Starts every flow with a `webhook_trigger` node matching `event`.
+///
+/// Flows that fail validation are logged and skipped so one broken flow does
+/// not block the others.
+pub fn trigger_flows(app: &AppHandle, flows: &[Flow], event: &WebhookEvent) -> Vec<FlowRun> {
+    let mut started = Vec::new();
+
+    for flow in flows {
+        let graph = match FlowGraph::from_flow(flow) {
+            Ok(graph) => graph,
+            Err(e) => {
+                log::warn!("Skipping flow {} for webhook matching: {e}", flow.id);
+                continue;
+            }
+        };
+
+        let matched = match graph.webhook_triggers() {
+            Ok(triggers) => triggers.iter().any(|t| t.matches(event)),
+            Err(e) => {
+                log::warn!("Skipping flow {} for webhook matching: {e}", flow.id);
+                continue;
+            }
+        };
+        if !matched {
+            continue;
+        }
+
+        let options = RunOptions {
+            variables: event_variables(event),
+            triggered_by: Some(format!("webhook: {} {}", event.source, event.event)),
+            parent: None,
+            simulation: None,
+            environment: None,
+        };
+        match runner::start_run(app, flow, options) {
+            Ok(run) => started.push(run),
+            Err(e) => log::warn!("Failed to start flow {} from webhook: {e}", flow.id),
+        }
+    }
+
+    started
+}
+
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |current, segment| match current {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut HashMap<String, String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                flatten(&format!("{prefix}.{key}"), child, out);
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                flatten(&format!("{prefix}.{i}"), child, out);
+            }
+        }
+        other => {
+            if let Some(s) = scalar_to_string(other) {
+                out.insert(prefix.to_string(), s);
+            }
+        }
+    }
+}
+
+/// Matches `value` against a pattern where `*` stands for any characters.
+pub fn glob_matches(pattern: &str, value: &str) -> bool {
+    let escaped: Vec<String> = pattern.split('*').map(regex::escape).collect();
+    Regex::new(&format!("^{}$", escaped.join(".*")))
+        .map(|re| re.is_match(value))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tag_push() -> WebhookEvent {
+        WebhookEvent {
+            source: "gitlab".to_string(),
+            event: "tag_push".to_string(),
+            payload: json!({
+                "ref": "refs/tags/v1.4.2",
+                "project": { "path_with_namespace": "platform/api" },
+                "commits": [{ "id": "abc123" }]
+            }),
+        }
+    }
+
+    fn trigger(value: Value) -> WebhookTriggerConfig {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_matches_source_event_and_filters() {
+        let config = trigger(json!({
+            "source": "gitlab",
+            "event": "tag_push",
+            "filters": {
+                "ref": "refs/tags/v*",
+                "project.path_with_namespace": "platform/api"
+            }
+        }));
+        assert!(config.matches(&tag_push()));
+
+        let other_project = trigger(json!({
+            "source": "gitlab",
+            "filters": { "project.path_with_namespace": "platform/web" }
+        }));
+        assert!(!other_project.matches(&tag_push()));
+
+        let harbor = trigger(json!({ "source": "harbor" }));
+        assert!(!harbor.matches(&tag_push()));
+    }
+
+    #[test]
+    fn test_missing_filter_path_does_not_match() {
+        let config = trigger(json!({ "source": "gitlab", "filters": { "tag": "*" } }));
+        assert!(!config.matches(&tag_push()));
+    }
+
+    #[test]
+    fn test_event_variables() {
+        let variables = event_variables(&tag_push());
+        assert_eq!(variables["trigger.ref"], "refs/tags/v1.4.2");
+        assert_eq!(
+            variables["trigger.project.path_with_namespace"],
+            "platform/api"
+        );
+        assert_eq!(variables["trigger.commits.0.id"], "abc123");
+        assert_eq!(variables["trigger.event"], "tag_push");
+    }
+
+    #[test]
+    fn test_glob_escapes_regex_characters() {
+        assert!(glob_matches("v1.*", "v1.4"));
+        assert!(!glob_matches("v1.4", "v1x4"));
+        assert!(glob_matches("*", ""));
+    }
+}
@@ -0,0 +1,123 @@
+//! Flow run types shared with the frontend.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Overall status of a flow run.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FlowRunStatus {
+    /// Waiting for a lock held by another run
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl FlowRunStatus {
+    /// Whether the run has not finished yet.
+    pub fn is_active(self) -> bool {
+        matches!(self, Self::Queued | Self::Running)
+    }
+}
+
+/// Status of a single node within a flow run.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FlowNodeStatus {
+    /// Not started yet
+    Pending,
+    /// Currently executing
+    Running,
+    /// Paused by a wait node, time-window gate or pending user input
+    Waiting,
+    Succeeded,
+    Failed,
+    /// Not executed because an upstream node did not succeed
+    Skipped,
+    Cancelled,
+}
+
+/// Execution state of a single node within a flow run.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FlowNodeRun {
+    /// ID of the node in the flow graph
+    pub node_id: String,
+    /// Node label at the time the run started
+    pub label: String,
+    /// Current node status
+    pub status: FlowNodeStatus,
+    /// Human-readable detail (wait target, error message, etc.)
+    pub message: Option<String>,
+    /// Start timestamp (RFC 3339)
+    pub started_at: Option<String>,
+    /// Completion timestamp (RFC 3339)
+    pub finished_at: Option<String>,
+    /// Nested run started by a `subflow` node
+    pub child_run_id: Option<String>,
+}
+
+/// A single execution of a saved flow.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FlowRun {
+    /// Unique run identifier
+    pub id: String,
+    /// ID of the flow being executed
+    pub flow_id: String,
+    /// Flow name at the time the run started
+    pub flow_name: String,
+    /// What started the run automatically (e.g., "webhook: gitlab tag_push");
+    /// `None` for manual runs
+    pub triggered_by: Option<String>,
+    /// Run that started this one through a `subflow` node
+    pub parent_run_id: Option<String>,
+    /// `subflow` node of the parent run
+    pub parent_node_id: Option<String>,
+    /// Whether the run is a simulation against fixtures
+    pub simulated: bool,
+    /// Environment the run deploys to, when started in one
+    pub environment_id: Option<String>,
+    /// Overall run status
+    pub status: FlowRunStatus,
+    /// Start timestamp (RFC 3339)
+    pub started_at: String,
+    /// Completion timestamp (RFC 3339)
+    pub finished_at: Option<String>,
+    /// Per-node execution state, in execution order
+    pub nodes: Vec<FlowNodeRun>,
+    /// Input nodes currently waiting for `provide_flow_input`
+    pub pending_inputs: Vec<FlowInputRequest>,
+    /// Names of the locks the run holds or waits for
+    pub locks: Vec<String>,
+    /// Lock the run is queued on, while `status` is `queued`
+    pub queued_on: Option<String>,
+}
+
+/// Type of value an input node asks for.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FlowInputKind {
+    #[default]
+    Text,
+    Number,
+    /// One of the request's `options`
+    Choice,
+}
+
+/// A request for user input, emitted as the `flow-input-requested` event.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FlowInputRequest {
+    /// Run waiting for the input
+    pub run_id: String,
+    /// Input node waiting for the input
+    pub node_id: String,
+    /// Question shown to the user
+    pub prompt: String,
+    /// Expected value type
+    pub input_type: FlowInputKind,
+    /// Allowed values for `choice` inputs
+    pub options: Vec<String>,
+    /// Value suggested to the user
+    pub default_value: Option<String>,
+}
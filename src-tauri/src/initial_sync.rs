@@ -0,0 +1,384 @@
+//! Initial sync of large integrations.
+//!
+//! A newly configured GitLab integration may hold thousands of projects, too
+//! many to crawl before showing anything. The initial sync crawls its
+//! project inventory in slices bounded by time and pages, storing the
+//! cursor (the next page) and the projects read so far in
+//! `<app data>/initial_sync/<integration id>.json` after every page. A
+//! background task picks up integrations that have not finished, one slice
+//! at a time, also after a restart. Progress is reported with
+//! `initial-sync-progress` events; the finished crawl becomes the first
+//! project inventory snapshot.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::config::load_integrations;
+use crate::commands::gitlab::gitlab_adapter;
+use crate::integrations::gitlab::GitLabInventoryProject;
+use crate::metrics;
+use crate::project_inventory;
+use crate::task_pool::{self, Priority};
+use crate::types::{IntegrationType, Timestamp};
+use crate::windows;
+
+/// Event emitted with an `InitialSyncStatus` after every page crawled.
+pub const INITIAL_SYNC_PROGRESS_EVENT: &str = "initial-sync-progress";
+
+/// Longest a slice crawls before yielding.
+const SLICE_DURATION: Duration = Duration::from_secs(2 * 60);
+
+/// Pages crawled at most per slice.
+const SLICE_PAGES: u32 = 20;
+
+/// Delay before the background task first looks for unfinished syncs.
+const STARTUP_DELAY: Duration = Duration::from_secs(30);
+
+/// How often the background task looks for unfinished syncs.
+const TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Delay before a sync whose last slice failed is retried.
+const RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InitialSyncState {
+    /// Crawling; more pages follow
+    InProgress,
+    Completed,
+}
+
+/// Progress of the initial sync of an integration.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct InitialSyncStatus {
+    pub integration_id: String,
+    pub state: InitialSyncState,
+    /// Next page to crawl (the cursor)
+    pub next_page: u32,
+    /// Projects read so far
+    pub items: u32,
+    /// Whether a slice is crawling right now
+    pub running: bool,
+    pub started_at: Timestamp,
+    pub updated_at: Timestamp,
+    pub completed_at: Option<Timestamp>,
+    /// Why the last slice stopped early; it is retried on the next tick
+    pub last_error: Option<String>,
+}
+
+/// Sync file contents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredSync {
+    status: InitialSyncStatus,
+    /// Projects read so far; cleared once recorded as a snapshot
+    projects: Vec<GitLabInventoryProject>,
+}
+
+impl StoredSync {
+    fn new(integration_id: &str) -> Self {
+        let now = Timestamp::from_datetime(chrono::Utc::now());
+        Self {
+            status: InitialSyncStatus {
+                integration_id: integration_id.to_string(),
+                state: InitialSyncState::InProgress,
+                next_page: 1,
+                items: 0,
+                running: false,
+                started_at: now.clone(),
+                updated_at: now,
+                completed_at: None,
+                last_error: None,
+            },
+            projects: Vec::new(),
+        }
+    }
+
+    /// Adds a crawled page and advances the cursor.
+    fn record_page(&mut self, projects: Vec<GitLabInventoryProject>, more: bool) {
+        self.projects.extend(projects);
+        self.status.items = self.projects.len() as u32;
+        self.status.next_page += 1;
+        self.status.last_error = None;
+        if !more {
+            self.status.state = InitialSyncState::Completed;
+        }
+    }
+}
+
+/// Integrations with a slice crawling right now.
+static RUNNING: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+fn running() -> MutexGuard<'static, HashSet<String>> {
+    RUNNING.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Starts a background task continuing the initial sync of every GitLab
+/// integration that has not finished it, newly configured ones included.
+pub fn start_background_sync(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        loop {
+            match pending_integrations(&app).await {
+                Ok(pending) => {
+                    for integration_id in pending {
+                        let result = task_pool::run(
+                            Priority::Poller,
+                            &integration_id,
+                            run_slice(&app, &integration_id),
+                        )
+                        .await;
+                        metrics::record_poll("initial_sync", result.is_ok());
+                        if let Err(e) = result {
+                            log::warn!("Initial sync of {integration_id} failed: {e}");
+                        }
+                    }
+                }
+                Err(e) => log::warn!("Failed to look for pending initial syncs: {e}"),
+            }
+            tokio::time::sleep(TICK_INTERVAL).await;
+        }
+    });
+}
+
+/// Crawls one slice of an integration's initial sync right away, starting
+/// the sync if needed. The background task continues it.
+pub async fn sync_now(app: &AppHandle, integration_id: &str) -> Result<InitialSyncStatus, String> {
+    run_slice(app, integration_id).await?;
+    status(app, integration_id)
+}
+
+/// Returns the initial sync progress of every GitLab integration that has
+/// started one.
+pub async fn list(app: &AppHandle) -> Result<Vec<InitialSyncStatus>, String> {
+    let mut statuses = Vec::new();
+    for integration in load_integrations(app.clone()).await? {
+        if integration.integration_type != IntegrationType::GitLab {
+            continue;
+        }
+        if let Some(stored) = load(app, &integration.id)? {
+            statuses.push(with_running(stored.status));
+        }
+    }
+    Ok(statuses)
+}
+
+fn status(app: &AppHandle, integration_id: &str) -> Result<InitialSyncStatus, String> {
+    let stored = load(app, integration_id)?.unwrap_or_else(|| StoredSync::new(integration_id));
+    Ok(with_running(stored.status))
+}
+
+fn with_running(mut status: InitialSyncStatus) -> InitialSyncStatus {
+    status.running = running().contains(&status.integration_id);
+    status
+}
+
+/// GitLab integrations whose initial sync has not completed, leaving out
+/// failed ones until their retry delay has passed.
+async fn pending_integrations(app: &AppHandle) -> Result<Vec<String>, String> {
+    let now = chrono::Utc::now();
+    let mut pending = Vec::new();
+    for integration in load_integrations(app.clone()).await? {
+        if integration.integration_type != IntegrationType::GitLab {
+            continue;
+        }
+        let due = load(app, &integration.id)?.is_none_or(|stored| is_due(&stored.status, now));
+        if due {
+            pending.push(integration.id);
+        }
+    }
+    Ok(pending)
+}
+
+fn is_due(status: &InitialSyncStatus, now: chrono::DateTime<chrono::Utc>) -> bool {
+    match status.state {
+        InitialSyncState::Completed => false,
+        InitialSyncState::InProgress if status.last_error.is_some() => status
+            .updated_at
+            .elapsed(now)
+            .to_std()
+            .is_ok_and(|elapsed| elapsed >= RETRY_DELAY),
+        InitialSyncState::InProgress => true,
+    }
+}
+
+/// Crawls pages from the stored cursor until the slice's time or page
+/// budget runs out or the last page is read. Does nothing while another
+/// slice of the integration runs.
+async fn run_slice(app: &AppHandle, integration_id: &str) -> Result<(), String> {
+    if !running().insert(integration_id.to_string()) {
+        return Ok(());
+    }
+    let result = crawl(app, integration_id).await;
+    running().remove(integration_id);
+    result
+}
+
+async fn crawl(app: &AppHandle, integration_id: &str) -> Result<(), String> {
+    let mut stored = load(app, integration_id)?.unwrap_or_else(|| StoredSync::new(integration_id));
+    if stored.status.state == InitialSyncState::Completed {
+        return Ok(());
+    }
+    let adapter = gitlab_adapter(app, integration_id).await?;
+    log::debug!(
+        "Initial sync of {integration_id} resuming at page {}",
+        stored.status.next_page
+    );
+
+    let began = Instant::now();
+    let mut pages = 0;
+    while stored.status.state == InitialSyncState::InProgress
+        && pages < SLICE_PAGES
+        && began.elapsed() < SLICE_DURATION
+    {
+        let page = adapter
+            .fetch_project_inventory_page(stored.status.next_page)
+            .await;
+        stored.status.updated_at = Timestamp::from_datetime(chrono::Utc::now());
+        match page {
+            Ok((projects, more)) => stored.record_page(projects, more),
+            Err(e) => {
+                let message = format!("Failed to fetch GitLab projects: {e}");
+                stored.status.last_error = Some(message.clone());
+                save(app, &stored)?;
+                report(app, &stored.status);
+                return Err(message);
+            }
+        }
+        pages += 1;
+
+        if stored.status.state == InitialSyncState::Completed {
+            let projects = std::mem::take(&mut stored.projects);
+            project_inventory::record_snapshot(app, integration_id, projects)?;
+            stored.status.completed_at = Some(stored.status.updated_at.clone());
+            log::info!(
+                "Initial sync of {integration_id} completed with {} project(s)",
+                stored.status.items
+            );
+        }
+        save(app, &stored)?;
+        report(app, &stored.status);
+    }
+    Ok(())
+}
+
+fn report(app: &AppHandle, status: &InitialSyncStatus) {
+    windows::broadcast(
+        app,
+        INITIAL_SYNC_PROGRESS_EVENT,
+        with_running(status.clone()),
+    );
+}
+
+fn sync_path(app: &AppHandle, integration_id: &str) -> Result<PathBuf, String> {
+    if integration_id.is_empty() || integration_id.contains(['/', '\\']) || integration_id == ".." {
+        return Err(format!("Invalid integration ID: {integration_id}"));
+    }
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    Ok(app_data_dir
+        .join("initial_sync")
+        .join(format!("{integration_id}.json")))
+}
+
+fn load(app: &AppHandle, integration_id: &str) -> Result<Option<StoredSync>, String> {
+    let path = sync_path(app, integration_id)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read initial sync state: {e}"))?;
+    serde_json::from_str(&contents)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse initial sync state: {e}"))
+}
+
+fn save(app: &AppHandle, stored: &StoredSync) -> Result<(), String> {
+    let path = sync_path(app, &stored.status.integration_id)?;
+    let json = serde_json::to_string(stored)
+        .map_err(|e| format!("Failed to serialize initial sync state: {e}"))?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {e}"))?;
+    }
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json)
+        .map_err(|e| format!("Failed to write initial sync state: {e}"))?;
+    if let Err(rename_err) = std::fs::rename(&temp_path, &path) {
+        if let Err(remove_err) = std::fs::remove_file(&temp_path) {
+            log::warn!("Failed to remove temp file after rename failure: {remove_err}");
+        }
+        return Err(format!(
+            "Failed to finalize initial sync state: {rename_err}"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(id: u32) -> GitLabInventoryProject {
+        GitLabInventoryProject {
+            id,
+            name: format!("p{id}"),
+            path_with_namespace: format!("group/p{id}"),
+            default_branch: Some("main".to_string()),
+            visibility: "private".to_string(),
+            archived: false,
+            web_url: format!("https://gitlab.example.com/group/p{id}"),
+        }
+    }
+
+    #[test]
+    fn test_record_page_advances_cursor_until_last_page() {
+        let mut stored = StoredSync::new("gitlab");
+        stored.status.last_error = Some("timeout".to_string());
+
+        stored.record_page(vec![project(1), project(2)], true);
+        assert_eq!(stored.status.next_page, 2);
+        assert_eq!(stored.status.items, 2);
+        assert_eq!(stored.status.state, InitialSyncState::InProgress);
+        assert_eq!(stored.status.last_error, None);
+
+        stored.record_page(vec![project(3)], false);
+        assert_eq!(stored.status.next_page, 3);
+        assert_eq!(stored.status.items, 3);
+        assert_eq!(stored.status.state, InitialSyncState::Completed);
+    }
+
+    #[test]
+    fn test_failed_sync_waits_for_retry_delay() {
+        let mut status = StoredSync::new("gitlab").status;
+        let failed_at = status.updated_at.datetime();
+        assert!(is_due(&status, failed_at));
+
+        status.last_error = Some("401 Unauthorized".to_string());
+        assert!(!is_due(&status, failed_at + chrono::TimeDelta::minutes(1)));
+        assert!(is_due(&status, failed_at + chrono::TimeDelta::minutes(5)));
+
+        status.state = InitialSyncState::Completed;
+        assert!(!is_due(&status, failed_at + chrono::TimeDelta::minutes(5)));
+    }
+
+    #[test]
+    fn test_stored_sync_round_trips() {
+        let mut stored = StoredSync::new("gitlab");
+        stored.record_page(vec![project(1)], true);
+
+        let json = serde_json::to_string(&stored).unwrap();
+        let parsed: StoredSync = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.status, stored.status);
+        assert_eq!(parsed.projects, stored.projects);
+    }
+}
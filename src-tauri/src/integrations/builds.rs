@@ -0,0 +1,277 @@
+//! Build comparison.
+//!
+//! Compares two builds of a Jenkins job or two pipelines of a GitLab project
+//! to explain why one got slower or failed: overall and per-stage duration
+//! deltas, tests that started or stopped failing, and the commits that went
+//! in between. The adapters collect `BuildData` for each side and
+//! `compare_build_data` computes the deltas.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeSet;
+
+use crate::types::Timestamp;
+
+/// Build or pipeline being compared.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct BuildSummary {
+    /// Build number or pipeline ID
+    pub id: String,
+    /// Status as reported by the service (e.g., "success", "failed")
+    pub status: String,
+    pub url: String,
+    pub started_at: Option<Timestamp>,
+    /// None while the build is running
+    pub duration_seconds: Option<u32>,
+    /// None if the build has no test report
+    pub tests: Option<TestCounts>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct TestCounts {
+    pub total: u32,
+    pub failed: u32,
+    pub skipped: u32,
+}
+
+/// Duration of a stage (Jenkins) or job (GitLab) in both builds.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct BuildStepDelta {
+    pub name: String,
+    /// None if the step didn't run in the base build
+    pub base_seconds: Option<u32>,
+    /// None if the step didn't run in the head build
+    pub head_seconds: Option<u32>,
+    /// Head minus base; None unless the step ran in both
+    pub delta_seconds: Option<i32>,
+    pub base_status: Option<String>,
+    pub head_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct TestResultDelta {
+    /// Tests failing in head but not in base
+    pub newly_failing: Vec<String>,
+    /// Tests failing in base but not in head (passing or removed)
+    pub fixed: Vec<String>,
+    /// Tests failing in both builds
+    pub still_failing: Vec<String>,
+}
+
+/// Commit that went in between the two builds.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct BuildChange {
+    pub commit_id: String,
+    /// First line of the commit message
+    pub title: String,
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct BuildComparison {
+    /// `build_a` of the request
+    pub base: BuildSummary,
+    /// `build_b` of the request
+    pub head: BuildSummary,
+    /// Head minus base; None if either build is still running
+    pub duration_delta_seconds: Option<i32>,
+    /// Steps of both builds, largest slowdown first
+    pub steps: Vec<BuildStepDelta>,
+    /// None unless both builds have a test report
+    pub tests: Option<TestResultDelta>,
+    /// Commits in head that are not in base
+    pub changes: Vec<BuildChange>,
+}
+
+/// Step timing collected by an adapter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildStep {
+    pub name: String,
+    pub status: String,
+    pub duration_seconds: Option<u32>,
+}
+
+/// Test report collected by an adapter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestRun {
+    pub counts: TestCounts,
+    /// Names of failed tests (e.g., "com.example.ApiTest.testLogin")
+    pub failed: BTreeSet<String>,
+}
+
+/// One side of a comparison, as collected by an adapter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildData {
+    pub summary: BuildSummary,
+    pub steps: Vec<BuildStep>,
+    pub tests: Option<TestRun>,
+}
+
+/// Rounds a duration in fractional seconds to whole seconds.
+pub fn whole_seconds(seconds: f64) -> u32 {
+    seconds.max(0.0).round().min(u32::MAX as f64) as u32
+}
+
+fn delta(base: Option<u32>, head: Option<u32>) -> Option<i32> {
+    let delta = i64::from(head?) - i64::from(base?);
+    Some(delta.clamp(i32::MIN.into(), i32::MAX.into()) as i32)
+}
+
+pub fn compare_build_data(
+    mut base: BuildData,
+    mut head: BuildData,
+    changes: Vec<BuildChange>,
+) -> BuildComparison {
+    base.summary.tests = base.tests.as_ref().map(|t| t.counts.clone());
+    head.summary.tests = head.tests.as_ref().map(|t| t.counts.clone());
+
+    let find = |steps: &[BuildStep], name: &str| steps.iter().find(|s| s.name == name).cloned();
+    let mut names: Vec<&str> = Vec::new();
+    for step in head.steps.iter().chain(&base.steps) {
+        if !names.contains(&step.name.as_str()) {
+            names.push(&step.name);
+        }
+    }
+    let mut steps: Vec<BuildStepDelta> = names
+        .into_iter()
+        .map(|name| {
+            let base_step = find(&base.steps, name);
+            let head_step = find(&head.steps, name);
+            let base_seconds = base_step.as_ref().and_then(|s| s.duration_seconds);
+            let head_seconds = head_step.as_ref().and_then(|s| s.duration_seconds);
+            BuildStepDelta {
+                name: name.to_string(),
+                base_seconds,
+                head_seconds,
+                delta_seconds: delta(base_seconds, head_seconds),
+                base_status: base_step.map(|s| s.status),
+                head_status: head_step.map(|s| s.status),
+            }
+        })
+        .collect();
+    // Stable sort keeps head order for steps without a delta
+    steps.sort_by_key(|step| std::cmp::Reverse(step.delta_seconds.unwrap_or(i32::MIN)));
+
+    let tests = match (&base.tests, &head.tests) {
+        (Some(base_tests), Some(head_tests)) => Some(TestResultDelta {
+            newly_failing: head_tests
+                .failed
+                .difference(&base_tests.failed)
+                .cloned()
+                .collect(),
+            fixed: base_tests
+                .failed
+                .difference(&head_tests.failed)
+                .cloned()
+                .collect(),
+            still_failing: base_tests
+                .failed
+                .intersection(&head_tests.failed)
+                .cloned()
+                .collect(),
+        }),
+        _ => None,
+    };
+
+    BuildComparison {
+        duration_delta_seconds: delta(base.summary.duration_seconds, head.summary.duration_seconds),
+        base: base.summary,
+        head: head.summary,
+        steps,
+        tests,
+        changes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(id: &str, duration: Option<u32>, steps: &[(&str, u32)], failed: &[&str]) -> BuildData {
+        BuildData {
+            summary: BuildSummary {
+                id: id.to_string(),
+                status: "success".to_string(),
+                url: format!("https://ci.example.com/{}", id),
+                started_at: None,
+                duration_seconds: duration,
+                tests: None,
+            },
+            steps: steps
+                .iter()
+                .map(|(name, seconds)| BuildStep {
+                    name: name.to_string(),
+                    status: "success".to_string(),
+                    duration_seconds: Some(*seconds),
+                })
+                .collect(),
+            tests: Some(TestRun {
+                counts: TestCounts {
+                    total: 10,
+                    failed: failed.len() as u32,
+                    skipped: 0,
+                },
+                failed: failed.iter().map(|name| name.to_string()).collect(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_compare_build_data() {
+        let base = build(
+            "41",
+            Some(300),
+            &[("build", 120), ("test", 150), ("lint", 30)],
+            &["ApiTest.login", "ApiTest.logout"],
+        );
+        let head = build(
+            "42",
+            Some(420),
+            &[("build", 110), ("test", 280), ("deploy", 30)],
+            &["ApiTest.logout", "DbTest.migrate"],
+        );
+
+        let comparison = compare_build_data(base, head, Vec::new());
+
+        assert_eq!(comparison.duration_delta_seconds, Some(120));
+        let steps: Vec<_> = comparison
+            .steps
+            .iter()
+            .map(|s| (s.name.as_str(), s.delta_seconds))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("test", Some(130)),
+                ("build", Some(-10)),
+                ("deploy", None),
+                ("lint", None)
+            ]
+        );
+
+        let tests = comparison.tests.unwrap();
+        assert_eq!(tests.newly_failing, vec!["DbTest.migrate"]);
+        assert_eq!(tests.fixed, vec!["ApiTest.login"]);
+        assert_eq!(tests.still_failing, vec!["ApiTest.logout"]);
+        assert_eq!(comparison.head.tests.map(|t| t.failed), Some(2));
+    }
+
+    #[test]
+    fn test_compare_without_tests_or_duration() {
+        let mut base = build("1", Some(60), &[], &[]);
+        base.tests = None;
+        let head = build("2", None, &[], &[]);
+
+        let comparison = compare_build_data(base, head, Vec::new());
+
+        assert_eq!(comparison.duration_delta_seconds, None);
+        assert_eq!(comparison.tests, None);
+        assert_eq!(comparison.base.tests, None);
+    }
+
+    #[test]
+    fn test_whole_seconds() {
+        assert_eq!(whole_seconds(12.6), 13);
+        assert_eq!(whole_seconds(-1.0), 0);
+    }
+}
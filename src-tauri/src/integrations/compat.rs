@@ -0,0 +1,179 @@
+//! Remote API versions and response compatibility shims.
+//!
+//! Adapters use the API version pinned on the integration or, when none is
+//! pinned, detect and record the version of the server they talk to. Responses
+//! of servers older than the version that introduced a field go through shims
+//! that rewrite them into the shape the response types expect, so older
+//! self-hosted instances don't fail to parse.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use specta::Type;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+
+use crate::types::Integration;
+
+/// Major and minor version of a remote API (e.g., GitLab 13.12).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl ApiVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// Parses a version string such as "13.12.15-ee", "v16.4" or "17".
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim().trim_start_matches(['v', 'V']);
+        let mut parts = version.split(|c: char| !c.is_ascii_digit());
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+        Some(Self::new(major, minor))
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Rewrites one response object of servers older than `until`.
+pub struct Shim {
+    /// First version whose responses don't need the shim
+    pub until: ApiVersion,
+    /// What the shim does (shown to users)
+    pub description: &'static str,
+    pub apply: fn(&mut Map<String, Value>),
+}
+
+impl Shim {
+    /// Whether the shim applies to a server version. Every shim applies when
+    /// the version is unknown; shims only fill in what is missing.
+    fn applies_to(&self, version: Option<ApiVersion>) -> bool {
+        version.is_none_or(|version| version < self.until)
+    }
+}
+
+/// Applies the shims for `version` to a response object, or to every object
+/// of a response array.
+pub fn apply_shims(value: &mut Value, version: Option<ApiVersion>, shims: &[Shim]) {
+    let shims: Vec<&Shim> = shims.iter().filter(|s| s.applies_to(version)).collect();
+    if shims.is_empty() {
+        return;
+    }
+    let objects: Vec<&mut Map<String, Value>> = match value {
+        Value::Array(items) => items.iter_mut().filter_map(Value::as_object_mut).collect(),
+        Value::Object(object) => vec![object],
+        _ => Vec::new(),
+    };
+    for object in objects {
+        for shim in &shims {
+            (shim.apply)(object);
+        }
+    }
+}
+
+/// Descriptions of the shims applied for `version`.
+pub fn active_shims(version: Option<ApiVersion>, shims: &[Shim]) -> Vec<String> {
+    shims
+        .iter()
+        .filter(|s| s.applies_to(version))
+        .map(|s| s.description.to_string())
+        .collect()
+}
+
+/// API version of an integration and the shims applied to its responses.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct IntegrationApiVersion {
+    pub integration_id: String,
+    /// Version in use (e.g., "13.12"); None when it could not be detected
+    pub version: Option<String>,
+    /// Whether the version is pinned on the integration instead of detected
+    pub pinned: bool,
+    pub shims: Vec<String>,
+}
+
+/// Returns the version pinned on an integration.
+pub fn pinned_version(integration: &Integration) -> Result<Option<ApiVersion>, String> {
+    match integration.api_version.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(version) => ApiVersion::parse(version).map(Some).ok_or_else(|| {
+            format!(
+                "Invalid API version for integration {}: {}",
+                integration.name, version
+            )
+        }),
+    }
+}
+
+/// Versions detected this session, per base URL.
+static DETECTED: LazyLock<Mutex<HashMap<String, ApiVersion>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn detected() -> MutexGuard<'static, HashMap<String, ApiVersion>> {
+    DETECTED.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Records the version a server reported.
+pub fn record_version(base_url: &str, version: ApiVersion) {
+    let previous = detected().insert(base_url.to_string(), version);
+    if previous != Some(version) {
+        log::info!("Detected API version {} at {}", version, base_url);
+    }
+}
+
+/// Returns the version recorded for a server.
+pub fn recorded_version(base_url: &str) -> Option<ApiVersion> {
+    detected().get(base_url).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SHIMS: [Shim; 1] = [Shim {
+        until: ApiVersion::new(12, 0),
+        description: "Fill in created_at",
+        apply: |object| {
+            object
+                .entry("created_at")
+                .or_insert_with(|| json!("1970-01-01T00:00:00Z"));
+        },
+    }];
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            ApiVersion::parse("13.12.15-ee"),
+            Some(ApiVersion::new(13, 12))
+        );
+        assert_eq!(ApiVersion::parse("v16.4"), Some(ApiVersion::new(16, 4)));
+        assert_eq!(ApiVersion::parse("17"), Some(ApiVersion::new(17, 0)));
+        assert_eq!(ApiVersion::parse("latest"), None);
+        assert!(ApiVersion::new(11, 10) < ApiVersion::new(12, 0));
+    }
+
+    #[test]
+    fn test_apply_shims_by_version() {
+        let mut old = json!([{ "id": 1 }, { "id": 2, "created_at": "2024-05-01T00:00:00Z" }]);
+        apply_shims(&mut old, Some(ApiVersion::new(11, 10)), &SHIMS);
+        assert_eq!(old[0]["created_at"], "1970-01-01T00:00:00Z");
+        assert_eq!(old[1]["created_at"], "2024-05-01T00:00:00Z");
+
+        let mut current = json!({ "id": 1 });
+        apply_shims(&mut current, Some(ApiVersion::new(16, 4)), &SHIMS);
+        assert_eq!(current, json!({ "id": 1 }));
+
+        let mut unknown = json!({ "id": 1 });
+        apply_shims(&mut unknown, None, &SHIMS);
+        assert_eq!(unknown["created_at"], "1970-01-01T00:00:00Z");
+        assert_eq!(active_shims(Some(ApiVersion::new(16, 4)), &SHIMS).len(), 0);
+    }
+}
@@ -0,0 +1,159 @@
+use super::serve;
+use crate::integrations::gitlab::{GitLabAdapter, GitLabApi};
+use crate::integrations::IntegrationError;
+
+fn adapter(uri: String) -> GitLabAdapter {
+    GitLabAdapter::new(uri, "glpat-test".to_string())
+}
+
+#[tokio::test]
+async fn test_fetch_projects() {
+    let server = serve("gitlab", &["projects"]).await;
+    let projects = adapter(server.uri()).fetch_projects().await.unwrap();
+
+    assert_eq!(
+        projects
+            .iter()
+            .map(|p| (p.id, p.path.as_str()))
+            .collect::<Vec<_>>(),
+        [(42, "checkout-api"), (57, "web-frontend")]
+    );
+    assert_eq!(
+        projects[0].web_url,
+        "https://gitlab.example.com/platform/checkout-api"
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_pipelines() {
+    let server = serve("gitlab", &["pipelines"]).await;
+    let pipelines = adapter(server.uri()).fetch_pipelines(42).await.unwrap();
+
+    assert_eq!(
+        pipelines
+            .iter()
+            .map(|p| (p.id, p.status.as_str(), p.r#ref.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            (1203, "running", "main"),
+            (1202, "failed", "feature/cart"),
+            (1201, "success", "main"),
+        ]
+    );
+    assert_eq!(
+        pipelines[0].created_at.to_string(),
+        "2024-05-02T16:38:02.118Z"
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_packages_reads_every_page() {
+    let server = serve("gitlab", &["packages-page-1", "packages-page-2"]).await;
+    let packages = adapter(server.uri())
+        .fetch_packages(42, None)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.versions.len()))
+            .collect::<Vec<_>>(),
+        [
+            ("com/example/checkout-client", 51),
+            ("@example/ui-kit", 50),
+            ("deploy-scripts", 1),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_project_overview_graphql() {
+    let server = serve("gitlab", &["project-overview-graphql"]).await;
+    let overview = adapter(server.uri())
+        .fetch_project_overview(42, GitLabApi::Graphql)
+        .await
+        .unwrap();
+
+    assert_eq!(overview.api, GitLabApi::Graphql);
+    assert_eq!(overview.project.id, 42);
+    assert_eq!(
+        overview
+            .pipelines
+            .iter()
+            .map(|p| (p.id, p.status.as_str()))
+            .collect::<Vec<_>>(),
+        [(1203, "running"), (1202, "failed"), (1201, "success")]
+    );
+    assert_eq!(
+        overview
+            .merge_requests
+            .iter()
+            .map(|mr| (mr.iid, mr.draft, mr.author.as_deref()))
+            .collect::<Vec<_>>(),
+        [
+            (311, false, Some("mkovacs")),
+            (308, true, Some("renovate-bot"))
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_project_overview_rest_matches_graphql() {
+    let server = serve(
+        "gitlab",
+        &[
+            "project-overview-graphql",
+            "project",
+            "pipelines-overview",
+            "merge-requests",
+        ],
+    )
+    .await;
+    let adapter = adapter(server.uri());
+    let graphql = adapter
+        .fetch_project_overview(42, GitLabApi::Graphql)
+        .await
+        .unwrap();
+    let rest = adapter
+        .fetch_project_overview(42, GitLabApi::Rest)
+        .await
+        .unwrap();
+
+    assert_eq!(rest.api, GitLabApi::Rest);
+    assert_eq!(rest.project, graphql.project);
+    assert_eq!(rest.pipelines, graphql.pipelines);
+    assert_eq!(rest.merge_requests, graphql.merge_requests);
+}
+
+#[tokio::test]
+async fn test_project_overview_graphql_errors() {
+    let server = serve("gitlab", &["project-overview-graphql-errors"]).await;
+    let error = adapter(server.uri())
+        .fetch_project_overview(42, GitLabApi::Graphql)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, IntegrationError::ApiError { ref message, .. } if message.contains("draft"))
+    );
+}
+
+#[tokio::test]
+async fn test_project_overview_graphql_hidden_project() {
+    let server = serve("gitlab", &["project-overview-graphql-hidden"]).await;
+    let error = adapter(server.uri())
+        .fetch_project_overview(42, GitLabApi::Graphql)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, IntegrationError::NotFound));
+}
+
+#[tokio::test]
+async fn test_unauthorized() {
+    let server = serve("gitlab", &["projects-unauthorized"]).await;
+    let error = adapter(server.uri()).fetch_projects().await.unwrap_err();
+
+    assert!(matches!(error, IntegrationError::AuthError { .. }));
+}
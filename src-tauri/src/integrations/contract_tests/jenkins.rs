@@ -0,0 +1,125 @@
+use super::serve;
+use crate::integrations::jenkins::{JenkinsAdapter, JenkinsBuildStatus};
+use crate::integrations::IntegrationError;
+
+fn adapter(uri: String) -> JenkinsAdapter {
+    JenkinsAdapter::new(
+        uri,
+        "ci-bot".to_string(),
+        "11aa22bb33cc44dd55ee66ff77889900".to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_fetch_jobs_walks_folders() {
+    let server = serve("jenkins", &["jobs", "jobs-platform"]).await;
+    let jobs = adapter(server.uri()).fetch_jobs().await.unwrap();
+
+    assert_eq!(
+        jobs.iter()
+            .map(|j| (j.name.as_str(), j.color.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            ("checkout-api", "blue"),
+            ("nightly-e2e", "red_anime"),
+            ("platform/deploy", "notbuilt"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_builds() {
+    let server = serve("jenkins", &["builds"]).await;
+    let builds = adapter(server.uri())
+        .fetch_builds("checkout-api")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        builds
+            .iter()
+            .map(|b| (b.number, b.status.clone()))
+            .collect::<Vec<_>>(),
+        [
+            (88, JenkinsBuildStatus::Building),
+            (87, JenkinsBuildStatus::Failure),
+            (86, JenkinsBuildStatus::Success),
+        ]
+    );
+    assert_eq!(builds[1].duration.as_deref(), Some("412877"));
+    assert_eq!(builds[2].timestamp.to_string(), "2024-05-01T08:01:13.004Z");
+}
+
+#[tokio::test]
+async fn test_missing_job() {
+    let server = serve("jenkins", &["builds-not-found"]).await;
+    let error = adapter(server.uri())
+        .fetch_builds("retired")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, IntegrationError::NotFound));
+}
+
+#[tokio::test]
+async fn test_rebuild_with_same_parameters() {
+    let server = serve(
+        "jenkins",
+        &["build-actions", "job-parameters", "build-with-parameters"],
+    )
+    .await;
+    let parameters = adapter(server.uri())
+        .rebuild_build("checkout-api", 87)
+        .await
+        .unwrap();
+
+    assert_eq!(parameters["VERSION"], "1.4.2");
+    assert_eq!(parameters["ENV"], "staging");
+    assert_eq!(parameters["DRY_RUN"], "false");
+}
+
+#[tokio::test]
+async fn test_rebuild_with_hidden_parameters() {
+    let server = serve("jenkins", &["build-actions-secret"]).await;
+    let error = adapter(server.uri())
+        .rebuild_build("checkout-api", 86)
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("DEPLOY_TOKEN"));
+}
+
+#[tokio::test]
+async fn test_replay_pipeline() {
+    let server = serve(
+        "jenkins",
+        &[
+            "build-actions",
+            "build-actions",
+            "replay-rebuild",
+            "replay-run",
+        ],
+    )
+    .await;
+    let adapter = adapter(server.uri());
+
+    adapter
+        .replay_pipeline("checkout-api", 87, None)
+        .await
+        .unwrap();
+    adapter
+        .replay_pipeline("checkout-api", 87, Some("pipeline { agent any }"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_replay_requires_pipeline_build() {
+    let server = serve("jenkins", &["build-actions-secret"]).await;
+    let error = adapter(server.uri())
+        .replay_pipeline("checkout-api", 86, None)
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("can't be replayed"));
+}
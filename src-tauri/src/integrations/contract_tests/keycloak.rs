@@ -0,0 +1,55 @@
+use super::serve;
+use crate::integrations::keycloak::{KeycloakAdapter, KeycloakRequiredAction};
+
+fn adapter(uri: String) -> KeycloakAdapter {
+    KeycloakAdapter::new(uri, "admin".to_string(), "correct-horse".to_string())
+}
+
+#[tokio::test]
+async fn test_fetch_realms() {
+    let server = serve("keycloak", &["realms"]).await;
+    let realms = adapter(server.uri()).fetch_realms().await.unwrap();
+
+    assert_eq!(
+        realms
+            .iter()
+            .map(|r| (r.realm.as_str(), r.enabled))
+            .collect::<Vec<_>>(),
+        [("master", true), ("staff", false)]
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_realms_without_admin_access() {
+    let server = serve("keycloak", &["realms-forbidden"]).await;
+    let realms = adapter(server.uri()).fetch_realms().await.unwrap();
+
+    assert!(realms.is_empty());
+}
+
+#[tokio::test]
+async fn test_fetch_users_reads_every_page() {
+    let server = serve("keycloak", &["users-page-1", "users-page-2"]).await;
+    let users = adapter(server.uri()).fetch_users("staff").await.unwrap();
+
+    assert_eq!(users.len(), 103);
+    assert_eq!(users[102].username, "user102");
+    assert_eq!(users[4].attributes["department"], ["sales"]);
+    assert!(!users[0].enabled);
+}
+
+#[tokio::test]
+async fn test_send_actions_email() {
+    let server = serve("keycloak", &["user-by-username", "execute-actions-email"]).await;
+    let sent = adapter(server.uri())
+        .send_actions_email(
+            "staff",
+            "user004",
+            &[KeycloakRequiredAction::UpdatePassword],
+            12,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(sent.email, "user004@example.com");
+}
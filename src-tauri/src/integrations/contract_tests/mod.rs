@@ -0,0 +1,70 @@
+//! Contract tests.
+//!
+//! The adapters run against a mock server replaying fixtures recorded from
+//! real instances (`tests/fixtures/<service>/<name>.json`, in the format
+//! written by `--record`, see [`super::fixtures`]), so parsing, pagination
+//! and error handling are checked against what the services return.
+
+mod gitlab;
+mod jenkins;
+mod keycloak;
+mod sonarqube;
+
+use reqwest::Url;
+use std::path::PathBuf;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use super::fixtures::Fixture;
+
+fn load(service: &str, name: &str) -> Fixture {
+    let file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(service)
+        .join(format!("{name}.json"));
+    let contents = std::fs::read_to_string(&file)
+        .unwrap_or_else(|e| panic!("Failed to read fixture {}: {e}", file.display()));
+    serde_json::from_str(&contents)
+        .unwrap_or_else(|e| panic!("Invalid fixture {}: {e}", file.display()))
+}
+
+/// Starts a server answering the requests of the named fixtures. Each
+/// fixture answers once and must be requested by the end of the test; name a
+/// fixture twice for a request sent twice.
+async fn serve(service: &str, names: &[&str]) -> MockServer {
+    let server = MockServer::start().await;
+    for name in names {
+        let Fixture { request, response } = load(service, name);
+
+        let mut mock = Mock::given(method(request.method.as_str())).and(path(request.path));
+        let query = Url::parse(&format!(
+            "http://fixture/?{}",
+            request.query.unwrap_or_default()
+        ))
+        .unwrap();
+        for (key, value) in query.query_pairs() {
+            mock = mock.and(query_param(key, value));
+        }
+
+        let content_type = response
+            .headers
+            .get("content-type")
+            .cloned()
+            .unwrap_or_else(|| "application/json".to_string());
+        let body = match response.json {
+            Some(json) => json.to_string(),
+            None => response.text.unwrap_or_default(),
+        };
+        let mut template = ResponseTemplate::new(response.status);
+        for (header, value) in &response.headers {
+            template = template.insert_header(header.as_str(), value.as_str());
+        }
+        mock.respond_with(template.set_body_raw(body, &content_type))
+            .up_to_n_times(1)
+            .expect(1)
+            .named(*name)
+            .mount(&server)
+            .await;
+    }
+    server
+}
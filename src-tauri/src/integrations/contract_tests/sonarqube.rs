@@ -0,0 +1,47 @@
+use super::serve;
+use crate::integrations::sonarqube::SonarQubeAdapter;
+use crate::integrations::IntegrationError;
+
+fn adapter(uri: String) -> SonarQubeAdapter {
+    SonarQubeAdapter::new(uri, "squ_test".to_string())
+}
+
+#[tokio::test]
+async fn test_fetch_projects() {
+    let server = serve("sonarqube", &["projects"]).await;
+    let projects = adapter(server.uri()).fetch_projects().await.unwrap();
+
+    assert_eq!(
+        projects
+            .iter()
+            .map(|p| (p.key.as_str(), p.name.as_str(), p.qualifier.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            ("checkout-api", "Checkout API", "TRK"),
+            ("web-frontend", "Web Frontend", "TRK"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_metrics() {
+    let server = serve("sonarqube", &["metrics"]).await;
+    let metrics = adapter(server.uri())
+        .fetch_metrics("checkout-api")
+        .await
+        .unwrap();
+
+    assert_eq!(metrics.coverage, Some(81.4));
+    assert_eq!(metrics.bugs, 3);
+    assert_eq!(metrics.vulnerabilities, 0);
+    assert_eq!(metrics.code_smells, 57);
+    assert_eq!(metrics.technical_debt.as_deref(), Some("1260"));
+}
+
+#[tokio::test]
+async fn test_unauthorized() {
+    let server = serve("sonarqube", &["projects-unauthorized"]).await;
+    let error = adapter(server.uri()).fetch_projects().await.unwrap_err();
+
+    assert!(matches!(error, IntegrationError::AuthError { .. }));
+}
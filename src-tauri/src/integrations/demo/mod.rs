@@ -0,0 +1,518 @@
+//! Demo integration adapter.
+//!
+//! Serves synthetic projects, pipelines, builds, pods and metrics so the app
+//! can be evaluated (and screenshots made) without real services or
+//! credentials. Nothing is stored: the data is a pure function of the
+//! integration ID and the current time. Pipelines and builds start at a
+//! regular interval and finish some minutes later with a pseudo-random
+//! outcome, services roll out new pods every few hours and pods fail now and
+//! then, so the data changes over time like a live system's.
+
+mod types;
+
+pub use types::DemoMetricSeries;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use self::types::DemoMetricPoint;
+use crate::integrations::gitlab::{GitLabPipeline, GitLabProject};
+use crate::integrations::jenkins::{JenkinsBuild, JenkinsBuildStatus, JenkinsJob};
+use crate::integrations::kubernetes::K8sPod;
+use crate::integrations::{IntegrationAdapter, IntegrationError};
+use crate::types::{Integration, IntegrationType, Timestamp};
+
+/// Base URL of the links in demo data (`.invalid` never resolves).
+const DEFAULT_BASE_URL: &str = "https://demo.invalid";
+
+/// Services of the demo: GitLab projects, Jenkins build jobs and deployments.
+const SERVICES: [&str; 5] = [
+    "checkout-api",
+    "payments-service",
+    "web-frontend",
+    "inventory-worker",
+    "auth-gateway",
+];
+
+/// Jenkins jobs besides the build job of every service.
+const EXTRA_JOBS: [&str; 2] = ["nightly-e2e", "deploy-production"];
+
+/// Namespaces with their replica count per service.
+const NAMESPACES: [(&str, u32); 2] = [("demo-staging", 1), ("demo-production", 3)];
+
+/// Branches pipelines run on besides `main`.
+const BRANCHES: [&str; 3] = ["feature/new-cart", "fix/token-refresh", "chore/deps"];
+
+const NODES: u64 = 3;
+
+/// First GitLab project ID.
+const PROJECT_ID_BASE: u32 = 101;
+
+/// Jenkins build numbers count from here (2024-01-01).
+const BUILD_NUMBER_ORIGIN: i64 = 1_704_067_200;
+
+/// Runs listed per project or job.
+const MAX_RUNS: usize = 20;
+
+/// Longest metrics window, in minutes.
+const MAX_METRICS_MINUTES: u32 = 24 * 60;
+
+/// A run of a recurring activity (pipeline, build, rollout).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Run {
+    /// Number of the run since the Unix epoch
+    index: i64,
+    started_at: i64,
+    duration: i64,
+}
+
+impl Run {
+    fn running(&self, now: i64) -> bool {
+        now < self.started_at + self.duration
+    }
+}
+
+/// Demo integration adapter.
+pub struct DemoAdapter {
+    name: String,
+    base_url: String,
+    seed: u64,
+}
+
+impl DemoAdapter {
+    /// Creates the adapter of a demo integration. The integration ID seeds
+    /// the data, so every demo integration tells a different story.
+    pub fn new(integration: &Integration) -> Self {
+        let base_url = integration.base_url.trim().trim_end_matches('/');
+        Self {
+            name: integration.name.clone(),
+            base_url: if base_url.is_empty() {
+                DEFAULT_BASE_URL.to_string()
+            } else {
+                base_url.to_string()
+            },
+            seed: fnv1a(&integration.id),
+        }
+    }
+
+    /// Lists the demo GitLab projects.
+    pub fn projects(&self) -> Vec<GitLabProject> {
+        SERVICES
+            .iter()
+            .zip(PROJECT_ID_BASE..)
+            .map(|(service, id)| GitLabProject {
+                id,
+                name: service.to_string(),
+                path: format!("demo/{service}"),
+                web_url: format!("{}/demo/{}", self.base_url, service),
+            })
+            .collect()
+    }
+
+    /// Lists the latest pipelines of a project, newest first.
+    pub fn pipelines(&self, project_id: u32) -> Result<Vec<GitLabPipeline>, IntegrationError> {
+        self.pipelines_at(project_id, Utc::now())
+    }
+
+    fn pipelines_at(
+        &self,
+        project_id: u32,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<GitLabPipeline>, IntegrationError> {
+        let service = project_id
+            .checked_sub(PROJECT_ID_BASE)
+            .and_then(|index| SERVICES.get(index as usize))
+            .ok_or(IntegrationError::NotFound)?;
+        let key = format!("pipeline:{service}");
+        let now = now.timestamp();
+
+        Ok(self
+            .runs(&key, 20 * 60, 4 * 60, 12 * 60, now)
+            .into_iter()
+            .map(|run| {
+                let status = if now < run.started_at + 30 {
+                    "pending"
+                } else if run.running(now) {
+                    "running"
+                } else {
+                    match self.unit(&key, run.index) {
+                        u if u < 0.78 => "success",
+                        u if u < 0.93 => "failed",
+                        _ => "canceled",
+                    }
+                };
+                let branch = self.roll(&format!("{key}:ref"), run.index) as usize;
+                GitLabPipeline {
+                    // Unique across projects, like GitLab's global IDs
+                    id: (run.index as u32)
+                        .wrapping_mul(SERVICES.len() as u32)
+                        .wrapping_add(project_id - PROJECT_ID_BASE),
+                    status: status.to_string(),
+                    r#ref: match branch % 8 {
+                        b if b < BRANCHES.len() => BRANCHES[b].to_string(),
+                        _ => "main".to_string(),
+                    },
+                    created_at: timestamp(run.started_at),
+                }
+            })
+            .collect())
+    }
+
+    /// Lists the demo Jenkins jobs, colored after their latest build.
+    pub fn jobs(&self) -> Vec<JenkinsJob> {
+        let now = Utc::now();
+        Self::job_names()
+            .into_iter()
+            .map(|name| {
+                let builds = self.builds_at(&name, now).unwrap_or_default();
+                let building = builds
+                    .first()
+                    .is_some_and(|b| b.status == JenkinsBuildStatus::Building);
+                let color = match builds
+                    .iter()
+                    .find(|b| b.status != JenkinsBuildStatus::Building)
+                    .map(|b| &b.status)
+                {
+                    Some(JenkinsBuildStatus::Success) => "blue",
+                    Some(JenkinsBuildStatus::Failure) => "red",
+                    Some(JenkinsBuildStatus::Unstable) => "yellow",
+                    Some(JenkinsBuildStatus::Aborted) => "aborted",
+                    _ => "notbuilt",
+                };
+                JenkinsJob {
+                    url: format!("{}/job/{}/", self.base_url, name),
+                    color: if building {
+                        format!("{color}_anime")
+                    } else {
+                        color.to_string()
+                    },
+                    name,
+                }
+            })
+            .collect()
+    }
+
+    /// Lists the latest builds of a job, newest first.
+    pub fn builds(&self, job_name: &str) -> Result<Vec<JenkinsBuild>, IntegrationError> {
+        self.builds_at(job_name, Utc::now())
+    }
+
+    fn job_names() -> Vec<String> {
+        SERVICES
+            .iter()
+            .map(|service| format!("{service}-build"))
+            .chain(EXTRA_JOBS.iter().map(|job| job.to_string()))
+            .collect()
+    }
+
+    fn builds_at(
+        &self,
+        job_name: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<JenkinsBuild>, IntegrationError> {
+        let (interval, min, max) = match job_name {
+            "nightly-e2e" => (24 * 3600, 25 * 60, 50 * 60),
+            "deploy-production" => (6 * 3600, 3 * 60, 8 * 60),
+            name if Self::job_names().iter().any(|job| job == name) => (30 * 60, 2 * 60, 9 * 60),
+            _ => return Err(IntegrationError::NotFound),
+        };
+        let key = format!("build:{job_name}");
+        let now = now.timestamp();
+
+        Ok(self
+            .runs(&key, interval, min, max, now)
+            .into_iter()
+            .map(|run| {
+                let number = run.index - BUILD_NUMBER_ORIGIN / interval;
+                let running = run.running(now);
+                let status = if running {
+                    JenkinsBuildStatus::Building
+                } else {
+                    match self.unit(&key, run.index) {
+                        u if u < 0.75 => JenkinsBuildStatus::Success,
+                        u if u < 0.9 => JenkinsBuildStatus::Failure,
+                        u if u < 0.97 => JenkinsBuildStatus::Unstable,
+                        _ => JenkinsBuildStatus::Aborted,
+                    }
+                };
+                JenkinsBuild {
+                    number: number.max(1) as u32,
+                    status,
+                    timestamp: timestamp(run.started_at),
+                    url: format!("{}/job/{}/{}/", self.base_url, job_name, number),
+                    duration: (!running).then(|| (run.duration * 1000).to_string()),
+                }
+            })
+            .collect())
+    }
+
+    /// Lists the pods of the demo services, in one namespace or all.
+    pub fn pods(&self, namespace: Option<&str>) -> Vec<K8sPod> {
+        self.pods_at(namespace, Utc::now())
+    }
+
+    fn pods_at(&self, namespace: Option<&str>, now: DateTime<Utc>) -> Vec<K8sPod> {
+        let now = now.timestamp();
+        let mut pods = Vec::new();
+        for (ns, replicas) in NAMESPACES {
+            if namespace.is_some_and(|n| n != ns) {
+                continue;
+            }
+            for service in SERVICES {
+                let key = format!("rollout:{ns}/{service}");
+                let runs = self.runs(&key, 3 * 3600, 60, 3 * 60, now);
+                let Some(current) = runs.first() else {
+                    continue;
+                };
+
+                // While rolling out, the new pods start next to the old ones
+                let mut revisions = vec![(current, current.running(now))];
+                if current.running(now) {
+                    revisions.extend(runs.get(1).map(|previous| (previous, false)));
+                }
+                for (run, starting) in revisions {
+                    let template_hash = self.roll(&key, run.index) & 0xff_ffff_ffff;
+                    for replica in 0..replicas {
+                        let name = format!(
+                            "{service}-{template_hash:010x}-{:05x}",
+                            self.roll(&format!("{key}:{replica}"), run.index) & 0xf_ffff
+                        );
+                        let status = if starting {
+                            "Pending"
+                        } else if self.unit(&name, now / 300) < 0.03 {
+                            "Failed"
+                        } else {
+                            "Running"
+                        };
+                        let node = self.roll(&name, 0) % NODES + 1;
+                        let mut containers = vec![service.to_string()];
+                        if replicas > 1 {
+                            containers.push("log-shipper".to_string());
+                        }
+                        pods.push(K8sPod {
+                            name,
+                            namespace: ns.to_string(),
+                            status: status.to_string(),
+                            containers,
+                            node: (!starting).then(|| format!("demo-node-{node}")),
+                        });
+                    }
+                }
+            }
+        }
+        pods
+    }
+
+    /// Returns per-minute metrics of the production services over the last
+    /// `minutes` (at most a day).
+    pub fn metrics(&self, minutes: u32) -> Vec<DemoMetricSeries> {
+        self.metrics_at(minutes, Utc::now())
+    }
+
+    fn metrics_at(&self, minutes: u32, now: DateTime<Utc>) -> Vec<DemoMetricSeries> {
+        let (namespace, replicas) = NAMESPACES[NAMESPACES.len() - 1];
+        let minutes = i64::from(minutes.clamp(1, MAX_METRICS_MINUTES));
+        let last_minute = now.timestamp() / 60;
+
+        SERVICES
+            .iter()
+            .map(|service| {
+                let base_rps = 20.0 + self.unit(service, 0) * 180.0;
+                let points = (last_minute - minutes + 1..=last_minute)
+                    .map(|minute| {
+                        let time = minute * 60;
+                        // Daily traffic curve, peaking in the afternoon (UTC)
+                        let day = (time % 86_400) as f64 / 86_400.0;
+                        let daily = 1.0 - 0.5 * (2.0 * std::f64::consts::PI * day).cos();
+                        let noise = 0.9 + 0.2 * self.unit(&format!("rps:{service}"), minute);
+                        let rps = base_rps * daily * noise;
+
+                        let incident_key = format!("incident:{service}");
+                        let incident = self.unit(&incident_key, time / 1800) < 0.08;
+                        let mut error_rate =
+                            0.002 + 0.004 * self.unit(&format!("errors:{service}"), minute);
+                        if incident {
+                            error_rate += 0.05 + 0.1 * self.unit(&incident_key, minute);
+                        }
+
+                        let memory_wave =
+                            (2.0 * std::f64::consts::PI * (time % 7200) as f64 / 7200.0).sin();
+                        DemoMetricPoint {
+                            timestamp: timestamp(time),
+                            cpu_millicores: round(f64::from(replicas) * (40.0 + rps * 2.5 * noise)),
+                            memory_mib: round(
+                                f64::from(replicas)
+                                    * (256.0
+                                        + 48.0 * memory_wave
+                                        + 16.0 * self.unit(&format!("mem:{service}"), minute)),
+                            ),
+                            requests_per_second: round(rps),
+                            error_rate: (error_rate * 10_000.0).round() / 10_000.0,
+                        }
+                    })
+                    .collect();
+                DemoMetricSeries {
+                    service: service.to_string(),
+                    namespace: namespace.to_string(),
+                    points,
+                }
+            })
+            .collect()
+    }
+
+    /// Lists the runs of an activity that starts every `interval` seconds
+    /// (offset per key) and takes `min` to `max` seconds: the latest
+    /// [`MAX_RUNS`] started by `now`, newest first.
+    fn runs(&self, key: &str, interval: i64, min: i64, max: i64, now: i64) -> Vec<Run> {
+        let offset = (self.roll(key, -1) % interval as u64) as i64;
+        let latest = (now - offset).div_euclid(interval);
+        (0..MAX_RUNS as i64)
+            .map(|back| latest - back)
+            .map(|index| Run {
+                index,
+                started_at: index * interval + offset,
+                duration: min + (self.roll(key, index) % (max - min + 1) as u64) as i64,
+            })
+            .collect()
+    }
+
+    /// Pseudo-random number for a key and step, stable for an integration.
+    fn roll(&self, key: &str, step: i64) -> u64 {
+        splitmix64(self.seed ^ fnv1a(key) ^ splitmix64(step as u64))
+    }
+
+    /// Pseudo-random number in `[0, 1)` for a key and step.
+    fn unit(&self, key: &str, step: i64) -> f64 {
+        (self.roll(key, step) >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[async_trait]
+impl IntegrationAdapter for DemoAdapter {
+    async fn test_connection(&self) -> Result<(), IntegrationError> {
+        // Nothing to connect to
+        Ok(())
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_integration_type(&self) -> IntegrationType {
+        IntegrationType::Demo
+    }
+
+    fn get_base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+fn timestamp(seconds: i64) -> Timestamp {
+    Timestamp::from_datetime(DateTime::from_timestamp(seconds, 0).unwrap_or_default())
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// FNV-1a hash; stable across builds, unlike `DefaultHasher`.
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn splitmix64(value: u64) -> u64 {
+    let mut x = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    fn adapter() -> DemoAdapter {
+        DemoAdapter::new(&Integration {
+            id: "demo".to_string(),
+            integration_type: IntegrationType::Demo,
+            name: "Demo".to_string(),
+            base_url: String::new(),
+            credentials_ref: None,
+            api_version: None,
+        })
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_760_000_000, 0).unwrap()
+    }
+
+    #[test]
+    fn test_pipelines_are_stable_and_progress() {
+        let adapter = adapter();
+        let pipelines = adapter.pipelines_at(PROJECT_ID_BASE, now()).unwrap();
+        assert_eq!(pipelines.len(), MAX_RUNS);
+        assert_eq!(
+            pipelines,
+            adapter.pipelines_at(PROJECT_ID_BASE, now()).unwrap()
+        );
+        assert!(pipelines
+            .windows(2)
+            .all(|pair| pair[0].created_at.datetime() > pair[1].created_at.datetime()));
+
+        // An hour later, the latest pipelines are new ones and all finished
+        // pipelines kept their outcome
+        let later = adapter
+            .pipelines_at(PROJECT_ID_BASE, now() + TimeDelta::hours(1))
+            .unwrap();
+        assert_ne!(later[0].id, pipelines[0].id);
+        for pipeline in pipelines.iter().skip(1) {
+            if let Some(same) = later.iter().find(|p| p.id == pipeline.id) {
+                assert_eq!(same.status, pipeline.status);
+            }
+        }
+
+        assert!(matches!(
+            adapter.pipelines_at(1, now()),
+            Err(IntegrationError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn test_builds() {
+        let adapter = adapter();
+        let builds = adapter.builds_at("checkout-api-build", now()).unwrap();
+        assert_eq!(builds[0].number, builds[1].number + 1);
+        for build in &builds {
+            assert_eq!(
+                build.duration.is_none(),
+                build.status == JenkinsBuildStatus::Building
+            );
+        }
+        assert!(adapter.builds_at("unknown", now()).is_err());
+    }
+
+    #[test]
+    fn test_pods() {
+        let adapter = adapter();
+        let pods = adapter.pods_at(Some("demo-production"), now());
+        assert!(pods.len() >= SERVICES.len() * 3);
+        assert!(pods.iter().all(|p| p.namespace == "demo-production"));
+        assert!(adapter.pods_at(None, now()).len() > pods.len());
+    }
+
+    #[test]
+    fn test_metrics() {
+        let series = adapter().metrics_at(60, now());
+        assert_eq!(series.len(), SERVICES.len());
+        for s in &series {
+            assert_eq!(s.points.len(), 60);
+            assert!(s
+                .points
+                .iter()
+                .all(|p| p.requests_per_second > 0.0 && (0.0..1.0).contains(&p.error_rate)));
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! Demo-specific types.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::types::Timestamp;
+
+/// Metrics of a service at one point in time.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct DemoMetricPoint {
+    pub timestamp: Timestamp,
+    pub cpu_millicores: f64,
+    pub memory_mib: f64,
+    pub requests_per_second: f64,
+    /// Share of failed requests (0 to 1)
+    pub error_rate: f64,
+}
+
+/// Metrics of a service over a time window, one point per minute.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct DemoMetricSeries {
+    pub service: String,
+    pub namespace: String,
+    /// Oldest first
+    pub points: Vec<DemoMetricPoint>,
+}
@@ -16,6 +16,7 @@ use specta::Type;
 ///   | { type: 'ApiError'; status: number; message: string }
 ///   | { type: 'ConfigError'; message: string }
 ///   | { type: 'NotFound' }
+///   | { type: 'ExecPluginNotFound'; command: string; message: string }
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(tag = "type")]
@@ -30,6 +31,9 @@ pub enum IntegrationError {
     ConfigError { message: String },
     /// Resource not found
     NotFound,
+    /// Credential plugin of a kubeconfig (e.g., `aws`, `gke-gcloud-auth-plugin`)
+    /// not installed or not on the PATH
+    ExecPluginNotFound { command: String, message: String },
 }
 
 impl std::fmt::Display for IntegrationError {
@@ -48,6 +52,9 @@ impl std::fmt::Display for IntegrationError {
                 write!(f, "Configuration error: {message}")
             }
             IntegrationError::NotFound => write!(f, "Resource not found"),
+            IntegrationError::ExecPluginNotFound { command, message } => {
+                write!(f, "Credential plugin '{command}' not found: {message}")
+            }
         }
     }
 }
@@ -129,6 +136,18 @@ mod tests {
         assert_eq!(err.to_string(), "API error (status 404): Not found");
     }
 
+    #[test]
+    fn test_exec_plugin_not_found_display() {
+        let err = IntegrationError::ExecPluginNotFound {
+            command: "aws".to_string(),
+            message: "Install the AWS CLI".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Credential plugin 'aws' not found: Install the AWS CLI"
+        );
+    }
+
     #[test]
     fn test_status_to_error_401() {
         let err = status_to_error(401, Some("Unauthorized".to_string()));
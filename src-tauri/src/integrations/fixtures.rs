@@ -0,0 +1,285 @@
+//! API response fixtures.
+//!
+//! The GitLab, Jenkins, SonarQube and Keycloak adapters read every response
+//! through [`capture`]. When the app is started with `--record` (or
+//! `--record=<dir>`), each response is also saved as a fixture: the request
+//! path and query, the status, the pagination headers and the body, with
+//! secret-looking fields redacted. Fixtures are what the contract tests
+//! replay, so a response the app fails to parse can be recorded on the
+//! instance that produced it and contributed as a test case.
+
+use reqwest::header::HeaderMap;
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::OnceLock;
+
+use crate::integrations::IntegrationError;
+
+/// Command line flag that enables recording.
+pub const RECORD_FLAG: &str = "--record";
+
+/// Response headers kept in fixtures (content type and pagination).
+const RECORDED_HEADERS: [&str; 7] = [
+    "content-type",
+    "link",
+    "x-next-page",
+    "x-page",
+    "x-per-page",
+    "x-total",
+    "x-total-pages",
+];
+
+/// Parts of JSON keys whose values are replaced in recorded bodies.
+const SECRET_KEY_PARTS: [&str; 6] = [
+    "token",
+    "secret",
+    "password",
+    "privatekey",
+    "credential",
+    "apikey",
+];
+
+const REDACTED: &str = "REDACTED";
+
+/// Directory fixtures are recorded to; unset unless recording.
+static RECORD_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Numbers fixtures recorded in this session, so none overwrites another.
+static NEXT_FIXTURE: AtomicU32 = AtomicU32::new(1);
+
+/// Recorded request and response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Fixture {
+    pub request: FixtureRequest,
+    pub response: FixtureResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureRequest {
+    pub method: String,
+    /// Path relative to the integration's base URL
+    pub path: String,
+    /// Query string, without the leading `?`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Body, when it is JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<Value>,
+    /// Body, when it isn't JSON (e.g., XML or an HTML error page)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// Reads the value of `--record[=<dir>]`: `None` without the flag,
+/// `Some(None)` when no directory is given.
+pub fn record_flag(args: impl IntoIterator<Item = String>) -> Option<Option<PathBuf>> {
+    args.into_iter().find_map(|arg| {
+        if arg == RECORD_FLAG {
+            return Some(None);
+        }
+        let dir = arg.strip_prefix(RECORD_FLAG)?.strip_prefix('=')?;
+        Some((!dir.is_empty()).then(|| PathBuf::from(dir)))
+    })
+}
+
+/// Starts recording fixtures to `dir`.
+pub fn enable_recording(dir: PathBuf) {
+    log::warn!(
+        "Recording API responses to {}. Review fixtures for private data before sharing them.",
+        dir.display()
+    );
+    if RECORD_DIR.set(dir).is_err() {
+        log::warn!("Fixture recording was already enabled");
+    }
+}
+
+/// Reads a response body, recording it when recording is enabled.
+///
+/// The body of an error response that can't be read is returned empty, so
+/// the status still determines the error.
+pub(crate) async fn capture(
+    service: &str,
+    base_url: &str,
+    method: &str,
+    response: reqwest::Response,
+) -> Result<(StatusCode, String), IntegrationError> {
+    let status = response.status();
+    let url = response.url().clone();
+    let headers = recorded_headers(response.headers());
+    let body = match response.text().await {
+        Ok(body) => body,
+        Err(_) if !status.is_success() => String::new(),
+        Err(e) => {
+            return Err(IntegrationError::NetworkError {
+                message: format!("Failed to read response: {}", e),
+            })
+        }
+    };
+
+    if let Some(dir) = RECORD_DIR.get() {
+        let fixture = fixture(base_url, method, &url, status.as_u16(), headers, &body);
+        if let Err(e) = save(dir, service, &fixture) {
+            log::warn!("Failed to record fixture: {}", e);
+        }
+    }
+    Ok((status, body))
+}
+
+fn recorded_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    RECORDED_HEADERS
+        .iter()
+        .filter_map(|name| {
+            let value = headers.get(*name)?.to_str().ok()?;
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn fixture(
+    base_url: &str,
+    method: &str,
+    url: &Url,
+    status: u16,
+    headers: BTreeMap<String, String>,
+    body: &str,
+) -> Fixture {
+    // Paths are stored relative to the base URL, so fixtures of an instance
+    // served under a prefix (e.g., /jenkins) replay against any server
+    let base_path = Url::parse(base_url)
+        .map(|base| base.path().trim_end_matches('/').to_string())
+        .unwrap_or_default();
+    let path = url
+        .path()
+        .strip_prefix(base_path.as_str())
+        .filter(|path| path.starts_with('/'))
+        .unwrap_or(url.path());
+
+    let (json, text) = match serde_json::from_str::<Value>(body) {
+        Ok(mut json) => {
+            redact(&mut json);
+            (Some(json), None)
+        }
+        Err(_) => (None, Some(body.to_string())),
+    };
+    Fixture {
+        request: FixtureRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            query: url.query().map(str::to_string),
+        },
+        response: FixtureResponse {
+            status,
+            headers,
+            json,
+            text,
+        },
+    }
+}
+
+/// Replaces the values of secret-looking keys.
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_lowercase().replace(['_', '-'], "");
+                if SECRET_KEY_PARTS.iter().any(|part| key.contains(part)) && !value.is_null() {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// Saves a fixture as `<dir>/<service>/<number>-<method>-<path>.json`.
+fn save(dir: &Path, service: &str, fixture: &Fixture) -> Result<PathBuf, String> {
+    let slug = fixture
+        .request
+        .path
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let dir = dir.join(service);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create directory: {e}"))?;
+
+    let path = dir.join(format!(
+        "{:04}-{}-{}.json",
+        NEXT_FIXTURE.fetch_add(1, Ordering::Relaxed),
+        fixture.request.method.to_lowercase(),
+        slug
+    ));
+    let json = serde_json::to_string_pretty(fixture)
+        .map_err(|e| format!("Failed to serialize fixture: {e}"))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write fixture: {e}"))?;
+    log::debug!("Recorded fixture {}", path.display());
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_record_flag() {
+        let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(record_flag(args(&["ops-flow"])), None);
+        assert_eq!(record_flag(args(&["ops-flow", "--record"])), Some(None));
+        assert_eq!(
+            record_flag(args(&["ops-flow", "--record=/tmp/fixtures"])),
+            Some(Some(PathBuf::from("/tmp/fixtures")))
+        );
+        assert_eq!(record_flag(args(&["ops-flow", "--recorder"])), None);
+    }
+
+    #[test]
+    fn test_fixture_is_relative_and_redacted() {
+        let url = Url::parse("https://ci.example.com/jenkins/job/api/api/json?tree=builds[number]")
+            .unwrap();
+        let body = json!({
+            "builds": [{ "number": 7 }],
+            "credentials": [{ "id": "deploy" }],
+            "nested": { "private_token": "glpat-abc", "apiKey": null }
+        });
+        let fixture = fixture(
+            "https://ci.example.com/jenkins/",
+            "GET",
+            &url,
+            200,
+            BTreeMap::new(),
+            &body.to_string(),
+        );
+
+        assert_eq!(fixture.request.path, "/job/api/api/json");
+        assert_eq!(
+            fixture.request.query.as_deref(),
+            Some("tree=builds[number]")
+        );
+        assert_eq!(
+            fixture.response.json,
+            Some(json!({
+                "builds": [{ "number": 7 }],
+                "credentials": "REDACTED",
+                "nested": { "private_token": "REDACTED", "apiKey": null }
+            }))
+        );
+    }
+}
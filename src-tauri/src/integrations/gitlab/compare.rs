@@ -0,0 +1,226 @@
+//! GitLab pipeline comparison.
+//!
+//! Job timings come from the pipeline's jobs, test results from the pipeline
+//! test report and commits from comparing the two pipelines' SHAs.
+
+use serde::Deserialize;
+use std::collections::BTreeSet;
+
+use super::GitLabAdapter;
+use crate::integrations::builds::{
+    compare_build_data, whole_seconds, BuildChange, BuildComparison, BuildData, BuildStep,
+    BuildSummary, TestCounts, TestRun,
+};
+use crate::integrations::IntegrationError;
+use crate::types::Timestamp;
+
+/// Jobs read per pipeline (one page).
+const MAX_JOBS: u32 = 100;
+
+/// Response of `GET /projects/:id/pipelines/:pipeline_id`.
+#[derive(Debug, Deserialize)]
+struct PipelineDetails {
+    id: u32,
+    status: String,
+    sha: String,
+    web_url: String,
+    started_at: Option<Timestamp>,
+    created_at: Timestamp,
+    /// Seconds; None while running
+    duration: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PipelineJob {
+    name: String,
+    status: String,
+    duration: Option<f64>,
+}
+
+/// Response of `GET /projects/:id/pipelines/:pipeline_id/test_report`.
+#[derive(Debug, Deserialize)]
+struct TestReport {
+    total_count: u32,
+    failed_count: u32,
+    skipped_count: u32,
+    error_count: u32,
+    #[serde(default)]
+    test_suites: Vec<TestSuite>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TestSuite {
+    #[serde(default)]
+    test_cases: Vec<TestCase>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TestCase {
+    status: String,
+    name: String,
+    classname: Option<String>,
+}
+
+/// Response of `GET /projects/:id/repository/compare`.
+#[derive(Debug, Deserialize)]
+struct Compare {
+    #[serde(default)]
+    commits: Vec<CompareCommit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompareCommit {
+    id: String,
+    title: String,
+    author_name: Option<String>,
+}
+
+impl GitLabAdapter {
+    /// Compares pipeline `pipeline_a` (base) with pipeline `pipeline_b`
+    /// (head) of a project.
+    pub async fn compare_pipelines(
+        &self,
+        project_id: u32,
+        pipeline_a: u32,
+        pipeline_b: u32,
+    ) -> Result<BuildComparison, IntegrationError> {
+        let (base, base_sha) = self.fetch_pipeline_data(project_id, pipeline_a).await?;
+        let (head, head_sha) = self.fetch_pipeline_data(project_id, pipeline_b).await?;
+
+        let changes = if base_sha == head_sha {
+            Vec::new()
+        } else {
+            let compare: Compare = self
+                .get(&format!(
+                    "/projects/{}/repository/compare?from={}&to={}",
+                    project_id, base_sha, head_sha
+                ))
+                .await?;
+            compare
+                .commits
+                .into_iter()
+                .map(|commit| BuildChange {
+                    commit_id: commit.id,
+                    title: commit.title,
+                    author: commit.author_name,
+                })
+                .collect()
+        };
+
+        Ok(compare_build_data(base, head, changes))
+    }
+
+    /// Returns the pipeline's data and its commit SHA.
+    async fn fetch_pipeline_data(
+        &self,
+        project_id: u32,
+        pipeline_id: u32,
+    ) -> Result<(BuildData, String), IntegrationError> {
+        let pipeline: PipelineDetails = self
+            .get(&format!(
+                "/projects/{}/pipelines/{}",
+                project_id, pipeline_id
+            ))
+            .await?;
+        let jobs: Vec<PipelineJob> = self
+            .get(&format!(
+                "/projects/{}/pipelines/{}/jobs?per_page={}",
+                project_id, pipeline_id, MAX_JOBS
+            ))
+            .await?;
+
+        let tests = match self
+            .get::<TestReport>(&format!(
+                "/projects/{}/pipelines/{}/test_report",
+                project_id, pipeline_id
+            ))
+            .await
+        {
+            // Pipelines without JUnit artifacts report an empty test report
+            Ok(report) if report.total_count > 0 => Some(test_run(report)),
+            Ok(_) => None,
+            Err(e) => {
+                log::debug!("No test report for pipeline {}: {}", pipeline_id, e);
+                None
+            }
+        };
+
+        let data = BuildData {
+            summary: BuildSummary {
+                id: pipeline.id.to_string(),
+                status: pipeline.status,
+                url: pipeline.web_url,
+                started_at: Some(pipeline.started_at.unwrap_or(pipeline.created_at)),
+                duration_seconds: pipeline.duration.map(whole_seconds),
+                tests: None,
+            },
+            steps: jobs
+                .into_iter()
+                .map(|job| BuildStep {
+                    name: job.name,
+                    status: job.status,
+                    duration_seconds: job.duration.map(whole_seconds),
+                })
+                .collect(),
+            tests,
+        };
+        Ok((data, pipeline.sha))
+    }
+}
+
+fn test_run(report: TestReport) -> TestRun {
+    let failed: BTreeSet<String> = report
+        .test_suites
+        .into_iter()
+        .flat_map(|suite| suite.test_cases)
+        .filter(|case| case.status == "failed" || case.status == "error")
+        .map(|case| match case.classname.filter(|c| !c.is_empty()) {
+            Some(classname) => format!("{}.{}", classname, case.name),
+            None => case.name,
+        })
+        .collect();
+
+    TestRun {
+        counts: TestCounts {
+            total: report.total_count,
+            failed: report.failed_count + report.error_count,
+            skipped: report.skipped_count,
+        },
+        failed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_test_run_from_report() {
+        let report: TestReport = serde_json::from_value(json!({
+            "total_count": 12, "success_count": 9, "failed_count": 1,
+            "skipped_count": 1, "error_count": 1,
+            "test_suites": [{ "name": "rspec", "test_cases": [
+                { "status": "failed", "name": "logs in", "classname": "spec.auth" },
+                { "status": "error", "name": "migrates", "classname": null },
+                { "status": "success", "name": "logs out", "classname": "spec.auth" },
+            ] }]
+        }))
+        .unwrap();
+
+        let run = test_run(report);
+
+        assert_eq!(
+            run.counts,
+            TestCounts {
+                total: 12,
+                failed: 2,
+                skipped: 1
+            }
+        );
+        assert_eq!(
+            run.failed.into_iter().collect::<Vec<_>>(),
+            vec!["migrates", "spec.auth.logs in"]
+        );
+    }
+}
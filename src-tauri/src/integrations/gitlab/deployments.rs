@@ -0,0 +1,119 @@
+//! Environment deployments.
+
+use serde::Deserialize;
+
+use super::types::GitLabDeployment;
+use super::GitLabAdapter;
+use crate::integrations::IntegrationError;
+use crate::types::Timestamp;
+
+/// Pages read at most (100 deployments each).
+const MAX_PAGES: u32 = 10;
+
+/// Deployment as returned by `GET /projects/:id/deployments`.
+#[derive(Debug, Deserialize)]
+struct RawDeployment {
+    id: u32,
+    status: String,
+    sha: String,
+    created_at: Timestamp,
+    finished_at: Option<Timestamp>,
+    environment: RawEnvironment,
+    deployable: Option<RawDeployable>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawEnvironment {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawDeployable {
+    commit: Option<RawCommit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCommit {
+    created_at: Option<Timestamp>,
+}
+
+impl From<RawDeployment> for GitLabDeployment {
+    fn from(raw: RawDeployment) -> Self {
+        Self {
+            id: raw.id,
+            status: raw.status,
+            environment: raw.environment.name,
+            sha: raw.sha,
+            commit_created_at: raw
+                .deployable
+                .and_then(|d| d.commit)
+                .and_then(|c| c.created_at),
+            created_at: raw.created_at,
+            finished_at: raw.finished_at,
+        }
+    }
+}
+
+impl GitLabAdapter {
+    /// Fetches the deployments of a project to `environment` updated since
+    /// `updated_after`, oldest first (up to 1000).
+    pub async fn fetch_deployments(
+        &self,
+        project_id: u32,
+        environment: &str,
+        updated_after: &Timestamp,
+    ) -> Result<Vec<GitLabDeployment>, IntegrationError> {
+        let mut deployments = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let endpoint = format!(
+                "/projects/{}/deployments?environment={}&updated_after={}&order_by=updated_at&sort=asc&per_page=100&page={}",
+                project_id,
+                urlencoding::encode(environment),
+                urlencoding::encode(&updated_after.rfc3339),
+                page
+            );
+            let batch: Vec<RawDeployment> = self.get(&endpoint).await?;
+            let done = batch.len() < 100;
+            deployments.extend(batch.into_iter().map(GitLabDeployment::from));
+            if done {
+                break;
+            }
+        }
+        Ok(deployments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deployment_from_response() {
+        let raw: RawDeployment = serde_json::from_value(json!({
+            "id": 41, "iid": 3, "ref": "main", "sha": "a1b2c3",
+            "status": "success",
+            "created_at": "2024-05-01T10:00:00.000Z",
+            "finished_at": "2024-05-01T10:05:00.000Z",
+            "environment": { "id": 9, "name": "production" },
+            "deployable": { "id": 77, "commit": { "id": "a1b2c3", "created_at": "2024-05-01T08:00:00.000+02:00" } }
+        }))
+        .unwrap();
+
+        let deployment = GitLabDeployment::from(raw);
+
+        assert_eq!(deployment.environment, "production");
+        assert_eq!(
+            deployment.commit_created_at.map(|t| t.rfc3339),
+            Some("2024-05-01T06:00:00.000Z".to_string())
+        );
+
+        let manual: RawDeployment = serde_json::from_value(json!({
+            "id": 42, "sha": "d4e5f6", "status": "failed",
+            "created_at": "2024-05-02T10:00:00Z", "finished_at": null,
+            "environment": { "name": "production" }, "deployable": null
+        }))
+        .unwrap();
+        assert_eq!(GitLabDeployment::from(manual).commit_created_at, None);
+    }
+}
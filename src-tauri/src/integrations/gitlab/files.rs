@@ -0,0 +1,116 @@
+//! Repository file reads.
+//!
+//! Returns the content of a single file (e.g., `.gitlab-ci.yml` or a Helm
+//! `values.yaml`) so configuration can be inspected without cloning.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::Deserialize;
+
+use super::types::GitLabFile;
+use super::{truncate_content, GitLabAdapter};
+use crate::integrations::IntegrationError;
+
+/// Largest file content returned (1MB).
+const MAX_FILE_BYTES: usize = 1_048_576;
+
+/// Response of `GET /projects/:id/repository/files/:path`.
+#[derive(Debug, Deserialize)]
+struct RepositoryFile {
+    file_name: String,
+    file_path: String,
+    size: u64,
+    /// "base64" or "text"
+    encoding: String,
+    content: String,
+    #[serde(rename = "ref")]
+    ref_name: String,
+    last_commit_id: String,
+}
+
+impl GitLabAdapter {
+    /// Fetches a text file from a project's repository.
+    ///
+    /// # Arguments
+    /// * `ref_name` - Branch, tag or commit; the default branch when None
+    pub async fn fetch_file(
+        &self,
+        project_id: u32,
+        path: &str,
+        ref_name: Option<&str>,
+    ) -> Result<GitLabFile, IntegrationError> {
+        let path = path.trim().trim_start_matches('/');
+        let ref_name = ref_name.map(str::trim).filter(|r| !r.is_empty());
+        let endpoint = format!(
+            "/projects/{}/repository/files/{}?ref={}",
+            project_id,
+            urlencoding::encode(path),
+            urlencoding::encode(ref_name.unwrap_or("HEAD"))
+        );
+
+        let file: RepositoryFile = self.get(&endpoint).await?;
+        decode_file(project_id, file)
+    }
+}
+
+/// Decodes the file content, rejecting binary files.
+fn decode_file(project_id: u32, file: RepositoryFile) -> Result<GitLabFile, IntegrationError> {
+    let bytes = match file.encoding.as_str() {
+        "base64" => {
+            let encoded: String = file.content.split_whitespace().collect();
+            STANDARD
+                .decode(encoded)
+                .map_err(|e| IntegrationError::ConfigError {
+                    message: format!("Failed to decode {}: {}", file.file_path, e),
+                })?
+        }
+        _ => file.content.into_bytes(),
+    };
+    let mut content = String::from_utf8(bytes).map_err(|_| IntegrationError::ConfigError {
+        message: format!("{} is not a text file", file.file_path),
+    })?;
+    let truncated = truncate_content(&mut content, MAX_FILE_BYTES);
+
+    Ok(GitLabFile {
+        project_id,
+        file_name: file.file_name,
+        file_path: file.file_path,
+        ref_name: file.ref_name,
+        last_commit_id: file.last_commit_id,
+        size_bytes: u32::try_from(file.size).unwrap_or(u32::MAX),
+        content,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(encoding: &str, content: &str) -> RepositoryFile {
+        RepositoryFile {
+            file_name: "values.yaml".to_string(),
+            file_path: "charts/api/values.yaml".to_string(),
+            size: 14,
+            encoding: encoding.to_string(),
+            content: content.to_string(),
+            ref_name: "main".to_string(),
+            last_commit_id: "abc123".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_decode_file() {
+        let decoded = decode_file(7, file("base64", "cmVwbGljYXM6\nIDMK")).unwrap();
+        assert_eq!(decoded.content, "replicas: 3\n");
+        assert_eq!(decoded.ref_name, "main");
+        assert!(!decoded.truncated);
+
+        assert_eq!(
+            decode_file(7, file("text", "image: api")).unwrap().content,
+            "image: api"
+        );
+        // Binary content (PNG signature)
+        assert!(decode_file(7, file("base64", "iVBORw0KGgo=")).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! Deploy freeze periods.
+//!
+//! GitLab stores freeze periods as a pair of cron expressions: one starting
+//! the freeze, one ending it. A freeze is in effect when the last start came
+//! after the last end.
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use serde::Deserialize;
+
+use super::types::GitLabFreezePeriod;
+use super::GitLabAdapter;
+use crate::integrations::IntegrationError;
+use crate::types::Timestamp;
+use crate::utils::cron::CronSchedule;
+
+/// Freeze period as returned by `GET /projects/:id/freeze_periods`.
+#[derive(Debug, Deserialize)]
+struct RawFreezePeriod {
+    id: u32,
+    freeze_start: String,
+    freeze_end: String,
+    cron_timezone: Option<String>,
+}
+
+/// Start and end of a freeze period relative to a point in time.
+#[derive(Debug, PartialEq, Eq)]
+struct FreezeWindow {
+    active: bool,
+    starts_at: Option<DateTime<Utc>>,
+    ends_at: Option<DateTime<Utc>>,
+}
+
+impl GitLabAdapter {
+    /// Fetches the deploy freeze periods of a project and whether each is in
+    /// effect now.
+    pub async fn fetch_freeze_periods(
+        &self,
+        project_id: u32,
+    ) -> Result<Vec<GitLabFreezePeriod>, IntegrationError> {
+        let endpoint = format!("/projects/{}/freeze_periods?per_page=100", project_id);
+        let raw: Vec<RawFreezePeriod> = self.get(&endpoint).await?;
+        let now = Utc::now();
+
+        Ok(raw
+            .into_iter()
+            .map(|period| {
+                let timezone = period.cron_timezone.unwrap_or_else(|| "UTC".to_string());
+                let window =
+                    freeze_window(&period.freeze_start, &period.freeze_end, &timezone, now)
+                        .unwrap_or_else(|e| {
+                            log::warn!("Skipping freeze period {}: {}", period.id, e);
+                            FreezeWindow {
+                                active: false,
+                                starts_at: None,
+                                ends_at: None,
+                            }
+                        });
+                GitLabFreezePeriod {
+                    id: period.id,
+                    freeze_start: period.freeze_start,
+                    freeze_end: period.freeze_end,
+                    cron_timezone: timezone,
+                    active: window.active,
+                    starts_at: window.starts_at.map(Timestamp::from_datetime),
+                    ends_at: window.ends_at.map(Timestamp::from_datetime),
+                }
+            })
+            .collect())
+    }
+}
+
+/// Evaluates a freeze period at `now`.
+///
+/// Expressions in UTC are evaluated exactly; any other time zone is taken to
+/// be the local one, since no time zone database is bundled.
+fn freeze_window(
+    freeze_start: &str,
+    freeze_end: &str,
+    timezone: &str,
+    now: DateTime<Utc>,
+) -> Result<FreezeWindow, String> {
+    let start = CronSchedule::parse(freeze_start)?;
+    let end = CronSchedule::parse(freeze_end)?;
+
+    let utc = matches!(timezone, "UTC" | "Etc/UTC" | "GMT" | "Etc/GMT");
+    let local_now = if utc {
+        now.naive_utc()
+    } else {
+        now.with_timezone(&Local).naive_local()
+    };
+    let to_utc = |time: NaiveDateTime| -> Option<DateTime<Utc>> {
+        if utc {
+            Some(time.and_utc())
+        } else {
+            Local
+                .from_local_datetime(&time)
+                .earliest()
+                .map(|t| t.with_timezone(&Utc))
+        }
+    };
+
+    let last_start = start.previous(local_now);
+    let last_end = end.previous(local_now);
+    let active = match (last_start, last_end) {
+        (Some(started), Some(ended)) => started > ended,
+        (Some(_), None) => true,
+        (None, _) => false,
+    };
+    let starts_at = if active {
+        last_start
+    } else {
+        start.next(local_now)
+    };
+
+    Ok(FreezeWindow {
+        active,
+        starts_at: starts_at.and_then(to_utc),
+        ends_at: end.next(local_now).and_then(to_utc),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_freeze_window() {
+        // Friday 23:00 to Monday 07:00
+        let weekend = |now: &str| freeze_window("0 23 * * 5", "0 7 * * 1", "UTC", utc(now));
+
+        // Saturday 2024-05-04
+        assert_eq!(
+            weekend("2024-05-04T12:00:00Z").unwrap(),
+            FreezeWindow {
+                active: true,
+                starts_at: Some(utc("2024-05-03T23:00:00Z")),
+                ends_at: Some(utc("2024-05-06T07:00:00Z")),
+            }
+        );
+        // Wednesday 2024-05-08
+        assert_eq!(
+            weekend("2024-05-08T12:00:00Z").unwrap(),
+            FreezeWindow {
+                active: false,
+                starts_at: Some(utc("2024-05-10T23:00:00Z")),
+                ends_at: Some(utc("2024-05-13T07:00:00Z")),
+            }
+        );
+
+        assert!(
+            freeze_window("0 23 * *", "0 7 * * 1", "UTC", utc("2024-05-04T12:00:00Z")).is_err()
+        );
+    }
+}
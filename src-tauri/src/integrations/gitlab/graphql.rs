@@ -0,0 +1,139 @@
+//! GitLab GraphQL client.
+//!
+//! Reads that would take several REST round-trips are sent as one query to
+//! `POST /api/graphql`. Mutations stay on REST.
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::GitLabAdapter;
+use crate::integrations::compat::ApiVersion;
+use crate::integrations::fixtures;
+use crate::integrations::IntegrationError;
+
+/// Oldest GitLab version whose GraphQL schema has every field the adapter
+/// queries (merge request `draft` was added in 13.12).
+const GRAPHQL_SINCE: ApiVersion = ApiVersion::new(13, 12);
+
+/// Body of a GraphQL response.
+#[derive(Debug, Deserialize)]
+struct GraphqlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphqlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlError {
+    message: String,
+}
+
+impl GitLabAdapter {
+    /// Whether the instance is known to be too old for the adapter's GraphQL
+    /// queries. Unknown versions are assumed to support them.
+    pub(super) fn graphql_unsupported(&self) -> bool {
+        self.api_version
+            .is_some_and(|version| version < GRAPHQL_SINCE)
+    }
+
+    /// Runs a GraphQL query and parses its `data`.
+    ///
+    /// A response carrying `errors` fails, even with partial data.
+    pub(super) async fn graphql<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> Result<T, IntegrationError> {
+        let url = format!("{}/api/graphql", self.base_url);
+        log::debug!("GitLab GraphQL: {}", url);
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.token)
+            .json(&json!({ "query": query, "variables": variables }))
+            .timeout(std::time::Duration::from_secs(30))
+            .send()
+            .await?;
+
+        let (status, response_text) =
+            fixtures::capture("gitlab", &self.base_url, "POST", response).await?;
+        if !status.is_success() {
+            log::error!("GitLab GraphQL error ({}): {}", status, response_text);
+            return Err(crate::integrations::errors::status_to_error(
+                status.as_u16(),
+                Some(response_text),
+            ));
+        }
+
+        let response: GraphqlResponse<T> = serde_json::from_str(&response_text).map_err(|e| {
+            log::error!("Failed to parse GitLab GraphQL response: {}", e);
+            log::error!(
+                "Response body (first 500 chars): {}",
+                response_text.chars().take(500).collect::<String>()
+            );
+            IntegrationError::ConfigError {
+                message: format!(
+                    "Failed to parse response: error decoding response body: {}",
+                    e
+                ),
+            }
+        })?;
+
+        if !response.errors.is_empty() {
+            let message = response
+                .errors
+                .into_iter()
+                .map(|e| e.message)
+                .collect::<Vec<_>>()
+                .join("; ");
+            log::error!("GitLab GraphQL query failed: {}", message);
+            return Err(IntegrationError::ApiError {
+                status: status.as_u16(),
+                message,
+            });
+        }
+        response.data.ok_or_else(|| IntegrationError::ConfigError {
+            message: "GitLab GraphQL response has no data".to_string(),
+        })
+    }
+}
+
+/// Global ID of a project, as GraphQL arguments expect it.
+pub(super) fn project_gid(project_id: u32) -> String {
+    format!("gid://gitlab/Project/{}", project_id)
+}
+
+/// Reads the numeric ID of a global ID (e.g., `gid://gitlab/Ci::Pipeline/1203`).
+pub(super) fn parse_gid(gid: &str) -> Result<u32, IntegrationError> {
+    gid.rsplit('/')
+        .next()
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| IntegrationError::ConfigError {
+            message: format!("Invalid GitLab global ID: {}", gid),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gid() {
+        assert_eq!(parse_gid("gid://gitlab/Ci::Pipeline/1203").unwrap(), 1203);
+        assert_eq!(parse_gid(&project_gid(42)).unwrap(), 42);
+        assert!(parse_gid("gid://gitlab/Project/").is_err());
+    }
+
+    #[test]
+    fn test_graphql_unsupported() {
+        let adapter = |version| {
+            GitLabAdapter::new("https://gitlab.com".to_string(), "token".to_string())
+                .with_api_version(version)
+        };
+        assert!(adapter(Some(ApiVersion::new(13, 11))).graphql_unsupported());
+        assert!(!adapter(Some(GRAPHQL_SINCE)).graphql_unsupported());
+        assert!(!adapter(None).graphql_unsupported());
+    }
+}
@@ -0,0 +1,50 @@
+//! Project inventory: every project the token is a member of, with the
+//! fields whose changes are worth noticing.
+
+use super::types::GitLabInventoryProject;
+use super::GitLabAdapter;
+use crate::integrations::IntegrationError;
+
+/// Projects requested per page (GitLab maximum).
+const PER_PAGE: usize = 100;
+
+/// Pages of projects read at most.
+const MAX_PAGES: u32 = 50;
+
+impl GitLabAdapter {
+    /// Fetches every project the token is a member of, archived ones
+    /// included, ordered by ID.
+    pub async fn fetch_project_inventory(
+        &self,
+    ) -> Result<Vec<GitLabInventoryProject>, IntegrationError> {
+        let mut projects = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let (batch, more) = self.fetch_project_inventory_page(page).await?;
+            projects.extend(batch);
+            if !more {
+                return Ok(projects);
+            }
+        }
+        log::warn!(
+            "GitLab has more than {} projects; the inventory is truncated",
+            projects.len()
+        );
+        Ok(projects)
+    }
+
+    /// Fetches one page (1-based) of the project inventory, and whether
+    /// more pages follow.
+    pub async fn fetch_project_inventory_page(
+        &self,
+        page: u32,
+    ) -> Result<(Vec<GitLabInventoryProject>, bool), IntegrationError> {
+        let batch: Vec<GitLabInventoryProject> = self
+            .get(&format!(
+                "/projects?membership=true&order_by=id&sort=asc&per_page={}&page={}",
+                PER_PAGE, page
+            ))
+            .await?;
+        let more = batch.len() == PER_PAGE;
+        Ok((batch, more))
+    }
+}
@@ -0,0 +1,217 @@
+//! GitLab issues with milestone and label aggregation.
+
+use std::collections::BTreeMap;
+
+use super::types::{GitLabIssue, GitLabIssueSummary, GitLabLabelSummary, GitLabMilestoneSummary};
+use super::GitLabAdapter;
+use crate::integrations::IntegrationError;
+
+/// Issues requested per page (GitLab maximum).
+const ISSUES_PER_PAGE: usize = 100;
+
+/// Pages fetched at most per summary.
+const MAX_ISSUE_PAGES: u32 = 10;
+
+/// Issue states accepted by the GitLab API.
+const ISSUE_STATES: &[&str] = &["opened", "closed", "all"];
+
+impl GitLabAdapter {
+    /// Fetches a project's issues and aggregates them per milestone and label.
+    ///
+    /// # Arguments
+    /// * `state` - "opened", "closed" or "all" (default)
+    /// * `labels` - Only issues having all of these labels
+    pub async fn fetch_issues(
+        &self,
+        project_id: u32,
+        state: Option<&str>,
+        labels: &[String],
+    ) -> Result<GitLabIssueSummary, IntegrationError> {
+        let state = state.unwrap_or("all");
+        if !ISSUE_STATES.contains(&state) {
+            return Err(IntegrationError::ConfigError {
+                message: format!(
+                    "Invalid issue state '{}' (expected one of: {})",
+                    state,
+                    ISSUE_STATES.join(", ")
+                ),
+            });
+        }
+
+        let mut query = format!("state={}&per_page={}", state, ISSUES_PER_PAGE);
+        if !labels.is_empty() {
+            query.push_str(&format!(
+                "&labels={}",
+                urlencoding::encode(&labels.join(","))
+            ));
+        }
+
+        let mut issues = Vec::new();
+        let mut truncated = false;
+        for page in 1..=MAX_ISSUE_PAGES {
+            let batch: Vec<GitLabIssue> = self
+                .get(&format!(
+                    "/projects/{}/issues?{}&page={}",
+                    project_id, query, page
+                ))
+                .await?;
+            let full_page = batch.len() == ISSUES_PER_PAGE;
+            issues.extend(batch);
+            if !full_page {
+                break;
+            }
+            truncated = page == MAX_ISSUE_PAGES;
+        }
+        if truncated {
+            log::warn!(
+                "Project {} has more than {} matching issues; summary is truncated",
+                project_id,
+                issues.len()
+            );
+        }
+
+        let mut summary = summarize_issues(project_id, issues);
+        summary.truncated = truncated;
+        Ok(summary)
+    }
+}
+
+/// Counts issues per state, milestone and label.
+fn summarize_issues(project_id: u32, issues: Vec<GitLabIssue>) -> GitLabIssueSummary {
+    let mut milestones: BTreeMap<Option<String>, GitLabMilestoneSummary> = BTreeMap::new();
+    let mut labels: BTreeMap<String, GitLabLabelSummary> = BTreeMap::new();
+    let (mut opened, mut closed) = (0, 0);
+
+    for issue in &issues {
+        let is_closed = issue.state == "closed";
+        let count = |opened: &mut u32, closed: &mut u32| {
+            if is_closed {
+                *closed += 1;
+            } else {
+                *opened += 1;
+            }
+        };
+        count(&mut opened, &mut closed);
+
+        let title = issue.milestone.as_ref().map(|m| m.title.clone());
+        let milestone = milestones
+            .entry(title.clone())
+            .or_insert_with(|| GitLabMilestoneSummary {
+                milestone: title,
+                due_date: issue.milestone.as_ref().and_then(|m| m.due_date.clone()),
+                opened: 0,
+                closed: 0,
+                progress_percent: 0,
+            });
+        count(&mut milestone.opened, &mut milestone.closed);
+
+        for label in &issue.labels {
+            let summary = labels
+                .entry(label.clone())
+                .or_insert_with(|| GitLabLabelSummary {
+                    label: label.clone(),
+                    opened: 0,
+                    closed: 0,
+                });
+            count(&mut summary.opened, &mut summary.closed);
+        }
+    }
+
+    let mut milestones: Vec<GitLabMilestoneSummary> = milestones
+        .into_values()
+        .map(|mut m| {
+            m.progress_percent = (m.closed * 100)
+                .checked_div(m.opened + m.closed)
+                .unwrap_or(0);
+            m
+        })
+        .collect();
+    // Milestones by due date (undated after dated), issues without a milestone last
+    milestones.sort_by(|a, b| {
+        (a.milestone.is_none(), a.due_date.is_none(), &a.due_date).cmp(&(
+            b.milestone.is_none(),
+            b.due_date.is_none(),
+            &b.due_date,
+        ))
+    });
+
+    let mut labels: Vec<GitLabLabelSummary> = labels.into_values().collect();
+    labels.sort_by(|a, b| b.opened.cmp(&a.opened).then_with(|| a.label.cmp(&b.label)));
+
+    GitLabIssueSummary {
+        project_id,
+        opened,
+        closed,
+        milestones,
+        labels,
+        issues,
+        truncated: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::gitlab::types::GitLabMilestoneRef;
+    use crate::types::Timestamp;
+
+    fn issue(
+        iid: u32,
+        state: &str,
+        milestone: Option<(&str, &str)>,
+        labels: &[&str],
+    ) -> GitLabIssue {
+        GitLabIssue {
+            id: iid + 1000,
+            iid,
+            title: format!("Issue {}", iid),
+            state: state.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            milestone: milestone.map(|(title, due)| GitLabMilestoneRef {
+                id: 1,
+                title: title.to_string(),
+                due_date: Some(due.to_string()),
+            }),
+            assignees: Vec::new(),
+            web_url: String::new(),
+            created_at: Timestamp::from_epoch_millis(0).unwrap(),
+            updated_at: Timestamp::from_epoch_millis(0).unwrap(),
+            due_date: None,
+        }
+    }
+
+    #[test]
+    fn test_summarize_issues() {
+        let summary = summarize_issues(
+            7,
+            vec![
+                issue(1, "closed", Some(("Sprint 2", "2024-06-14")), &["bug"]),
+                issue(
+                    2,
+                    "opened",
+                    Some(("Sprint 2", "2024-06-14")),
+                    &["bug", "api"],
+                ),
+                issue(3, "opened", None, &["api"]),
+                issue(4, "closed", Some(("Sprint 1", "2024-05-31")), &[]),
+            ],
+        );
+
+        assert_eq!((summary.opened, summary.closed), (2, 2));
+        let milestones: Vec<(Option<&str>, u32)> = summary
+            .milestones
+            .iter()
+            .map(|m| (m.milestone.as_deref(), m.progress_percent))
+            .collect();
+        assert_eq!(
+            milestones,
+            [(Some("Sprint 1"), 100), (Some("Sprint 2"), 50), (None, 0)]
+        );
+        let labels: Vec<(&str, u32, u32)> = summary
+            .labels
+            .iter()
+            .map(|l| (l.label.as_str(), l.opened, l.closed))
+            .collect();
+        assert_eq!(labels, [("api", 2, 0), ("bug", 1, 1)]);
+    }
+}
@@ -0,0 +1,72 @@
+//! CI configuration linting.
+//!
+//! Validates pipeline configuration with the project's CI Lint API, either
+//! edited content or the configuration currently in the repository. Linting
+//! in the project context resolves local includes and project variables.
+
+use serde::Deserialize;
+use serde_json::json;
+
+use super::types::GitLabCiLintResult;
+use super::GitLabAdapter;
+use crate::integrations::IntegrationError;
+
+/// Response of the project CI Lint API.
+#[derive(Debug, Deserialize)]
+struct LintResponse {
+    valid: bool,
+    #[serde(default)]
+    errors: Vec<String>,
+    #[serde(default)]
+    warnings: Vec<String>,
+    merged_yaml: Option<String>,
+    #[serde(default)]
+    jobs: Vec<LintJob>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LintJob {
+    name: String,
+}
+
+impl GitLabAdapter {
+    /// Lints CI configuration in the context of a project.
+    ///
+    /// # Arguments
+    /// * `content` - YAML to lint; the project's current configuration when None
+    /// * `ref_name` - Branch or tag whose configuration is linted when no
+    ///   content is given (default branch when None)
+    pub async fn lint_ci(
+        &self,
+        project_id: u32,
+        content: Option<&str>,
+        ref_name: Option<&str>,
+    ) -> Result<GitLabCiLintResult, IntegrationError> {
+        let ref_name = ref_name.map(str::trim).filter(|r| !r.is_empty());
+        let response: LintResponse = match content {
+            Some(content) => {
+                self.post(
+                    &format!("/projects/{}/ci/lint", project_id),
+                    json!({ "content": content, "include_jobs": true }),
+                )
+                .await?
+            }
+            None => {
+                let mut endpoint = format!("/projects/{}/ci/lint?include_jobs=true", project_id);
+                if let Some(ref_name) = ref_name {
+                    endpoint.push_str(&format!("&content_ref={}", urlencoding::encode(ref_name)));
+                }
+                self.get(&endpoint).await?
+            }
+        };
+
+        Ok(GitLabCiLintResult {
+            project_id,
+            valid: response.valid && response.errors.is_empty(),
+            errors: response.errors,
+            warnings: response.warnings,
+            jobs: response.jobs.into_iter().map(|job| job.name).collect(),
+            merged_yaml: response.merged_yaml,
+        })
+    }
+}
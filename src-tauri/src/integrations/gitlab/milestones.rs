@@ -0,0 +1,305 @@
+//! Milestones and epics with issue counts and burn-down.
+//!
+//! Epics are a GitLab Premium feature; groups without them are reported as
+//! unlicensed instead of failing.
+
+use chrono::{Days, NaiveDate, Utc};
+use serde::Deserialize;
+
+use super::types::{GitLabBurndownPoint, GitLabEpic, GitLabEpicList, GitLabMilestone};
+use super::GitLabAdapter;
+use crate::integrations::IntegrationError;
+use crate::types::Timestamp;
+
+/// Items requested per page (GitLab maximum).
+const PER_PAGE: usize = 100;
+
+/// Pages of issues read at most per milestone or epic.
+const MAX_ISSUE_PAGES: u32 = 5;
+
+/// Days shown at most in a burn-down (the most recent ones).
+const MAX_BURNDOWN_DAYS: u64 = 180;
+
+/// Milestone as returned by `GET /projects/:id/milestones`.
+#[derive(Debug, Deserialize)]
+struct RawMilestone {
+    id: u32,
+    iid: u32,
+    title: String,
+    state: String,
+    start_date: Option<String>,
+    due_date: Option<String>,
+    web_url: String,
+}
+
+/// Epic as returned by `GET /groups/:id/epics`.
+#[derive(Debug, Deserialize)]
+struct RawEpic {
+    id: u32,
+    iid: u32,
+    title: String,
+    state: String,
+    start_date: Option<String>,
+    due_date: Option<String>,
+    web_url: String,
+}
+
+/// The issue fields counts and burn-downs need.
+#[derive(Debug, Deserialize)]
+struct RawIssue {
+    state: String,
+    created_at: Timestamp,
+    closed_at: Option<Timestamp>,
+}
+
+impl GitLabAdapter {
+    /// Fetches a project's milestones with issue counts and burn-down.
+    ///
+    /// # Arguments
+    /// * `state` - "active" or "closed"; all milestones when None
+    pub async fn fetch_milestones(
+        &self,
+        project_id: u32,
+        state: Option<&str>,
+    ) -> Result<Vec<GitLabMilestone>, IntegrationError> {
+        let mut endpoint = format!("/projects/{}/milestones?per_page={}", project_id, PER_PAGE);
+        if let Some(state) = state {
+            endpoint.push_str(&format!("&state={}", urlencoding::encode(state)));
+        }
+        let raw: Vec<RawMilestone> = self.get(&endpoint).await?;
+
+        let today = Utc::now().date_naive();
+        let mut milestones = Vec::new();
+        for milestone in raw {
+            let issues: Vec<RawIssue> = self
+                .get_issue_pages(&format!(
+                    "/projects/{}/milestones/{}/issues",
+                    project_id, milestone.id
+                ))
+                .await?;
+            let (opened, closed) = count_issues(&issues);
+            let start = parse_date(milestone.start_date.as_deref());
+            let due = parse_date(milestone.due_date.as_deref());
+
+            milestones.push(GitLabMilestone {
+                id: milestone.id,
+                iid: milestone.iid,
+                title: milestone.title,
+                state: milestone.state,
+                start_date: milestone.start_date,
+                due_date: milestone.due_date,
+                web_url: milestone.web_url,
+                opened,
+                closed,
+                progress_percent: progress_percent(opened, closed),
+                elapsed_percent: elapsed_percent(start, due, today),
+                burndown: burndown(&issues, start, due, today),
+            });
+        }
+        // Soonest due date first, undated last
+        milestones.sort_by(|a, b| {
+            (a.due_date.is_none(), &a.due_date).cmp(&(b.due_date.is_none(), &b.due_date))
+        });
+        Ok(milestones)
+    }
+
+    /// Fetches a group's epics with the counts of their issues.
+    ///
+    /// # Arguments
+    /// * `group` - Group ID or full path (e.g., "platform/payments")
+    /// * `state` - "opened", "closed" or "all" (default)
+    pub async fn fetch_epics(
+        &self,
+        group: &str,
+        state: Option<&str>,
+    ) -> Result<GitLabEpicList, IntegrationError> {
+        let encoded_group = urlencoding::encode(group);
+        let endpoint = format!(
+            "/groups/{}/epics?state={}&per_page={}",
+            encoded_group,
+            urlencoding::encode(state.unwrap_or("all")),
+            PER_PAGE
+        );
+        let raw: Vec<RawEpic> = match self.get(&endpoint).await {
+            Ok(raw) => raw,
+            Err(IntegrationError::NotFound | IntegrationError::AuthError { .. }) => {
+                log::info!("Epics are not available for group {}", group);
+                return Ok(GitLabEpicList {
+                    group: group.to_string(),
+                    licensed: false,
+                    epics: Vec::new(),
+                });
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut epics = Vec::new();
+        for epic in raw {
+            let issues: Vec<RawIssue> = self
+                .get_issue_pages(&format!(
+                    "/groups/{}/epics/{}/issues",
+                    encoded_group, epic.iid
+                ))
+                .await?;
+            let (opened, closed) = count_issues(&issues);
+            epics.push(GitLabEpic {
+                id: epic.id,
+                iid: epic.iid,
+                title: epic.title,
+                state: epic.state,
+                start_date: epic.start_date,
+                due_date: epic.due_date,
+                web_url: epic.web_url,
+                opened,
+                closed,
+                progress_percent: progress_percent(opened, closed),
+            });
+        }
+        Ok(GitLabEpicList {
+            group: group.to_string(),
+            licensed: true,
+            epics,
+        })
+    }
+
+    /// Reads up to `MAX_ISSUE_PAGES` pages of issues from a list endpoint.
+    async fn get_issue_pages(&self, endpoint: &str) -> Result<Vec<RawIssue>, IntegrationError> {
+        let mut issues = Vec::new();
+        for page in 1..=MAX_ISSUE_PAGES {
+            let batch: Vec<RawIssue> = self
+                .get(&format!("{}?per_page={}&page={}", endpoint, PER_PAGE, page))
+                .await?;
+            let full_page = batch.len() == PER_PAGE;
+            issues.extend(batch);
+            if !full_page {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+}
+
+fn parse_date(date: Option<&str>) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date?, "%Y-%m-%d").ok()
+}
+
+fn count_issues(issues: &[RawIssue]) -> (u32, u32) {
+    let closed = issues.iter().filter(|i| i.state == "closed").count() as u32;
+    (issues.len() as u32 - closed, closed)
+}
+
+fn progress_percent(opened: u32, closed: u32) -> u32 {
+    (closed * 100).checked_div(opened + closed).unwrap_or(0)
+}
+
+fn elapsed_percent(
+    start: Option<NaiveDate>,
+    due: Option<NaiveDate>,
+    today: NaiveDate,
+) -> Option<u32> {
+    let (start, due) = (start?, due?);
+    let total = (due - start).num_days();
+    let elapsed = (today - start).num_days();
+    if total <= 0 {
+        return Some(if elapsed >= 0 { 100 } else { 0 });
+    }
+    Some((elapsed.clamp(0, total) * 100 / total) as u32)
+}
+
+/// Counts the issues open at the end of each day from `start` (or the first
+/// issue) until today or the due date, whichever is earlier.
+fn burndown(
+    issues: &[RawIssue],
+    start: Option<NaiveDate>,
+    due: Option<NaiveDate>,
+    today: NaiveDate,
+) -> Vec<GitLabBurndownPoint> {
+    let created = |issue: &RawIssue| issue.created_at.datetime().date_naive();
+    let Some(start) = start.or_else(|| issues.iter().map(created).min()) else {
+        return Vec::new();
+    };
+    let end = due.map_or(today, |due| due.min(today));
+    let start = end
+        .checked_sub_days(Days::new(MAX_BURNDOWN_DAYS - 1))
+        .map_or(start, |earliest| start.max(earliest));
+
+    start
+        .iter_days()
+        .take_while(|day| *day <= end)
+        .map(|day| {
+            let remaining = issues
+                .iter()
+                .filter(|issue| created(issue) <= day)
+                .filter(|issue| {
+                    issue
+                        .closed_at
+                        .as_ref()
+                        .is_none_or(|closed| closed.datetime().date_naive() > day)
+                })
+                .count();
+            GitLabBurndownPoint {
+                date: day.format("%Y-%m-%d").to_string(),
+                remaining: remaining as u32,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        parse_date(Some(value)).unwrap()
+    }
+
+    fn issue(created: &str, closed: Option<&str>) -> RawIssue {
+        RawIssue {
+            state: if closed.is_some() { "closed" } else { "opened" }.to_string(),
+            created_at: Timestamp::parse(created).unwrap(),
+            closed_at: closed.and_then(Timestamp::parse),
+        }
+    }
+
+    #[test]
+    fn test_burndown() {
+        let issues = vec![
+            issue("2024-05-01T09:00:00Z", Some("2024-05-02T15:00:00Z")),
+            issue("2024-05-01T10:00:00Z", None),
+            issue("2024-05-02T08:00:00Z", Some("2024-05-03T08:00:00Z")),
+        ];
+
+        let points = burndown(
+            &issues,
+            Some(date("2024-05-01")),
+            Some(date("2024-05-10")),
+            date("2024-05-03"),
+        );
+
+        let remaining: Vec<(&str, u32)> = points
+            .iter()
+            .map(|p| (p.date.as_str(), p.remaining))
+            .collect();
+        assert_eq!(
+            remaining,
+            [("2024-05-01", 2), ("2024-05-02", 2), ("2024-05-03", 1)]
+        );
+        assert_eq!(count_issues(&issues), (1, 2));
+        assert!(burndown(&[], None, None, date("2024-05-03")).is_empty());
+    }
+
+    #[test]
+    fn test_elapsed_percent() {
+        let today = date("2024-05-06");
+
+        assert_eq!(
+            elapsed_percent(Some(date("2024-05-01")), Some(date("2024-05-11")), today),
+            Some(50)
+        );
+        assert_eq!(
+            elapsed_percent(Some(date("2024-04-01")), Some(date("2024-04-30")), today),
+            Some(100)
+        );
+        assert_eq!(elapsed_percent(None, Some(date("2024-05-11")), today), None);
+    }
+}
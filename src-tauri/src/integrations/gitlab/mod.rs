@@ -2,15 +2,56 @@
 //!
 //! Implements the IntegrationAdapter trait for GitLab API interactions.
 
+mod compare;
+mod deployments;
+mod files;
+mod freeze_periods;
+mod graphql;
+mod inventory;
+mod issues;
+mod lint;
+mod milestones;
+mod overview;
+mod packages;
+mod runbooks;
+mod shims;
+mod tags;
 mod types;
+mod variables;
 
-pub use types::{GitLabPipeline, GitLabProject, GitLabWebhook};
+pub use types::{
+    GitLabApi, GitLabCiLintResult, GitLabCiVariable, GitLabDeployment, GitLabEpicList, GitLabFile,
+    GitLabFreezePeriod, GitLabInventoryProject, GitLabIssueSummary, GitLabMilestone, GitLabPackage,
+    GitLabPipeline, GitLabProject, GitLabProjectOverview, GitLabRunbook, GitLabRunbookContent,
+    GitLabTag, GitLabWebhook,
+};
 
+#[cfg(test)]
+pub use types::GitLabTagCommit;
+
+use crate::integrations::compat::{self, ApiVersion, Shim};
+use crate::integrations::fixtures;
 use crate::integrations::{IntegrationAdapter, IntegrationError};
 use crate::types::IntegrationType;
 use async_trait::async_trait;
 use reqwest::Client;
+use serde::Deserialize;
 use serde_json::json;
+use shims::PIPELINE_SHIMS;
+
+/// Cuts `content` to at most `max_bytes` on a character boundary. Returns
+/// whether anything was cut.
+fn truncate_content(content: &mut String, max_bytes: usize) -> bool {
+    if content.len() <= max_bytes {
+        return false;
+    }
+    let mut end = max_bytes;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    content.truncate(end);
+    true
+}
 
 /// GitLab integration adapter.
 ///
@@ -24,6 +65,14 @@ pub struct GitLabAdapter {
     token: String,
     /// HTTP client for API requests
     client: Client,
+    /// Version of the GitLab instance, selecting response shims
+    api_version: Option<ApiVersion>,
+}
+
+/// Response of `GET /version`.
+#[derive(Debug, Deserialize)]
+struct RawVersion {
+    version: String,
 }
 
 impl GitLabAdapter {
@@ -33,9 +82,47 @@ impl GitLabAdapter {
             base_url: base_url.trim_end_matches('/').to_string(),
             token,
             client: Client::new(),
+            api_version: None,
+        }
+    }
+
+    /// Sets the GitLab version whose responses the adapter expects.
+    pub fn with_api_version(mut self, api_version: Option<ApiVersion>) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    /// Returns the GitLab version the adapter expects.
+    pub fn api_version(&self) -> Option<ApiVersion> {
+        self.api_version
+    }
+
+    /// Returns the version of the GitLab instance, asking it once per session.
+    ///
+    /// Returns None when the version can't be read (e.g., the token lacks the
+    /// `read_api` scope).
+    pub async fn detect_api_version(&self) -> Option<ApiVersion> {
+        if let Some(version) = compat::recorded_version(&self.base_url) {
+            return Some(version);
+        }
+        match self.get::<RawVersion>("/version").await {
+            Ok(raw) => {
+                let version = ApiVersion::parse(&raw.version)?;
+                compat::record_version(&self.base_url, version);
+                Some(version)
+            }
+            Err(e) => {
+                log::debug!("Failed to detect GitLab version: {}", e);
+                None
+            }
         }
     }
 
+    /// Descriptions of the shims applied to this instance's responses.
+    pub fn active_shims(&self) -> Vec<String> {
+        compat::active_shims(self.api_version, PIPELINE_SHIMS)
+    }
+
     /// Builds the full API URL for a given endpoint.
     fn api_url(&self, endpoint: &str) -> String {
         format!("{}/api/v4{}", self.base_url, endpoint)
@@ -57,24 +144,17 @@ impl GitLabAdapter {
             .send()
             .await?;
 
-        let status = response.status();
+        // Body read as text first to log it if parsing fails
+        let (status, response_text) =
+            fixtures::capture("gitlab", &self.base_url, "GET", response).await?;
         if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            log::error!("GitLab API error ({}): {}", status, error_text);
+            log::error!("GitLab API error ({}): {}", status, response_text);
             return Err(crate::integrations::errors::status_to_error(
                 status.as_u16(),
-                Some(error_text),
+                Some(response_text),
             ));
         }
 
-        // Get response body as text first to log it if parsing fails
-        let response_text = response.text().await.map_err(|e| {
-            log::error!("Failed to read GitLab API response body: {}", e);
-            IntegrationError::NetworkError {
-                message: format!("Failed to read response: {}", e),
-            }
-        })?;
-
         // Try to parse as JSON
         serde_json::from_str::<T>(&response_text).map_err(|e| {
             log::error!("Failed to parse GitLab API response as JSON: {}", e);
@@ -91,6 +171,50 @@ impl GitLabAdapter {
         })
     }
 
+    /// Makes an authenticated GET request, applying response shims for the
+    /// instance's version before parsing.
+    async fn get_shimmed<T: for<'de> serde::Deserialize<'de>>(
+        &self,
+        endpoint: &str,
+        shims: &[Shim],
+    ) -> Result<T, IntegrationError> {
+        let mut value: serde_json::Value = self.get(endpoint).await?;
+        compat::apply_shims(&mut value, self.api_version, shims);
+        parse_shimmed(value)
+    }
+
+    /// Makes an authenticated GET request returning a plain-text body
+    /// (e.g., raw file content).
+    async fn get_text(&self, endpoint: &str) -> Result<String, IntegrationError> {
+        let url = self.api_url(endpoint);
+        log::debug!("GitLab API GET (text): {}", url);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .timeout(std::time::Duration::from_secs(30))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            log::error!("GitLab API error ({}): {}", status, error_text);
+            return Err(crate::integrations::errors::status_to_error(
+                status.as_u16(),
+                Some(error_text),
+            ));
+        }
+
+        response.text().await.map_err(|e| {
+            log::error!("Failed to read GitLab API response body: {}", e);
+            IntegrationError::NetworkError {
+                message: format!("Failed to read response: {}", e),
+            }
+        })
+    }
+
     /// Makes an authenticated POST request to the GitLab API.
     async fn post<T: for<'de> serde::Deserialize<'de>>(
         &self,
@@ -154,8 +278,11 @@ impl GitLabAdapter {
         &self,
         project_id: u32,
     ) -> Result<Vec<GitLabPipeline>, IntegrationError> {
-        self.get(&format!("/projects/{}/pipelines?per_page=100", project_id))
-            .await
+        self.get_shimmed(
+            &format!("/projects/{}/pipelines?per_page=100", project_id),
+            PIPELINE_SHIMS,
+        )
+        .await
     }
 
     /// Fetches webhooks for a specific project.
@@ -175,11 +302,29 @@ impl GitLabAdapter {
         let body = json!({
             "ref": r#ref
         });
-        self.post(&format!("/projects/{}/trigger/pipeline", project_id), body)
-            .await
+        let mut value: serde_json::Value = self
+            .post(&format!("/projects/{}/trigger/pipeline", project_id), body)
+            .await?;
+        compat::apply_shims(&mut value, self.api_version, PIPELINE_SHIMS);
+        parse_shimmed(value)
     }
 }
 
+/// Parses a shimmed response.
+fn parse_shimmed<T: for<'de> serde::Deserialize<'de>>(
+    value: serde_json::Value,
+) -> Result<T, IntegrationError> {
+    serde_json::from_value(value).map_err(|e| {
+        log::error!("Failed to parse GitLab API response: {}", e);
+        IntegrationError::ConfigError {
+            message: format!(
+                "Failed to parse response: error decoding response body: {}",
+                e
+            ),
+        }
+    })
+}
+
 #[async_trait]
 impl IntegrationAdapter for GitLabAdapter {
     async fn test_connection(&self) -> Result<(), IntegrationError> {
@@ -0,0 +1,155 @@
+//! Package registry browsing.
+//!
+//! GitLab lists every published version as a package of its own; versions
+//! are grouped here by package name and format, so the newest release of a
+//! library can be checked before consumers are bumped.
+
+use serde::Deserialize;
+
+use super::types::{GitLabPackage, GitLabPackageVersion};
+use super::GitLabAdapter;
+use crate::integrations::IntegrationError;
+use crate::types::Timestamp;
+
+/// Items requested per page (GitLab maximum).
+const PER_PAGE: usize = 100;
+
+/// Pages of packages read at most.
+const MAX_PAGES: u32 = 10;
+
+/// Package formats that can be browsed.
+const PACKAGE_TYPES: [&str; 4] = ["maven", "npm", "pypi", "generic"];
+
+/// Package as returned by `GET /projects/:id/packages`.
+#[derive(Debug, Deserialize)]
+struct RawPackage {
+    id: u32,
+    name: String,
+    version: String,
+    package_type: String,
+    #[serde(default)]
+    status: Option<String>,
+    created_at: Timestamp,
+}
+
+impl GitLabAdapter {
+    /// Fetches the Maven, npm, PyPI and generic packages of a project with
+    /// their versions.
+    ///
+    /// # Arguments
+    /// * `package_type` - One of "maven", "npm", "pypi" or "generic"; all of
+    ///   them when None
+    pub async fn fetch_packages(
+        &self,
+        project_id: u32,
+        package_type: Option<&str>,
+    ) -> Result<Vec<GitLabPackage>, IntegrationError> {
+        let mut endpoint = format!(
+            "/projects/{}/packages?order_by=created_at&sort=desc&per_page={}",
+            project_id, PER_PAGE
+        );
+        if let Some(package_type) = package_type {
+            if !PACKAGE_TYPES.contains(&package_type) {
+                return Err(IntegrationError::ConfigError {
+                    message: format!(
+                        "Unsupported package type '{}' (expected one of: {})",
+                        package_type,
+                        PACKAGE_TYPES.join(", ")
+                    ),
+                });
+            }
+            endpoint.push_str(&format!("&package_type={}", package_type));
+        }
+
+        let mut raw = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let batch: Vec<RawPackage> = self.get(&format!("{}&page={}", endpoint, page)).await?;
+            let full_page = batch.len() == PER_PAGE;
+            raw.extend(batch);
+            if !full_page {
+                break;
+            }
+        }
+        Ok(group_packages(raw))
+    }
+}
+
+/// Groups package versions by name and format, keeping the order in which
+/// packages were first seen and listing versions newest first.
+fn group_packages(raw: Vec<RawPackage>) -> Vec<GitLabPackage> {
+    let mut packages: Vec<GitLabPackage> = Vec::new();
+    for package in raw
+        .into_iter()
+        .filter(|p| PACKAGE_TYPES.contains(&p.package_type.as_str()))
+    {
+        let version = GitLabPackageVersion {
+            id: package.id,
+            version: package.version,
+            status: package.status.unwrap_or_else(|| "default".to_string()),
+            created_at: package.created_at,
+        };
+        match packages
+            .iter_mut()
+            .find(|p| p.name == package.name && p.package_type == package.package_type)
+        {
+            Some(existing) => existing.versions.push(version),
+            None => packages.push(GitLabPackage {
+                name: package.name,
+                package_type: package.package_type,
+                latest_version: None,
+                versions: vec![version],
+            }),
+        }
+    }
+
+    for package in &mut packages {
+        package
+            .versions
+            .sort_by_key(|v| std::cmp::Reverse(v.created_at.to_string()));
+        package.latest_version = package
+            .versions
+            .iter()
+            .find(|v| v.status == "default")
+            .map(|v| v.version.clone());
+    }
+    packages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_group_packages() {
+        let raw: Vec<RawPackage> = serde_json::from_value(json!([
+            { "id": 3, "name": "core", "version": "1.3.0", "package_type": "maven",
+              "status": "processing", "created_at": "2024-05-03T10:00:00Z" },
+            { "id": 2, "name": "core", "version": "1.2.0", "package_type": "maven",
+              "status": "default", "created_at": "2024-05-02T10:00:00Z" },
+            { "id": 4, "name": "ui", "version": "0.1.0", "package_type": "npm",
+              "created_at": "2024-05-02T12:00:00Z" },
+            { "id": 1, "name": "core", "version": "1.1.0", "package_type": "maven",
+              "status": "default", "created_at": "2024-05-01T10:00:00Z" },
+            { "id": 5, "name": "lib", "version": "2.0", "package_type": "conan",
+              "created_at": "2024-05-01T10:00:00Z" }
+        ]))
+        .unwrap();
+
+        let packages = group_packages(raw);
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "core");
+        assert_eq!(
+            packages[0]
+                .versions
+                .iter()
+                .map(|v| v.version.as_str())
+                .collect::<Vec<_>>(),
+            ["1.3.0", "1.2.0", "1.1.0"]
+        );
+        // Versions still processing aren't released yet
+        assert_eq!(packages[0].latest_version.as_deref(), Some("1.2.0"));
+        assert_eq!(packages[1].package_type, "npm");
+        assert_eq!(packages[1].latest_version.as_deref(), Some("0.1.0"));
+    }
+}
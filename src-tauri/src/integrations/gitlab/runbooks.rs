@@ -0,0 +1,162 @@
+//! Runbooks stored as GitLab snippets.
+//!
+//! A snippet is a runbook when its title or description contains the
+//! `#runbook` tag. `service:<name>` tags in the description link it to
+//! services (e.g., "Restart procedure #runbook service:checkout-api").
+
+use super::types::{GitLabRunbook, GitLabRunbookContent, GitLabSnippet};
+use super::{truncate_content, GitLabAdapter};
+use crate::integrations::IntegrationError;
+
+/// Tag marking a snippet as a runbook.
+const RUNBOOK_TAG: &str = "#runbook";
+
+/// Prefix of tags linking a runbook to a service.
+const SERVICE_TAG_PREFIX: &str = "service:";
+
+/// Largest runbook returned (1MB).
+const MAX_RUNBOOK_BYTES: usize = 1_048_576;
+
+impl GitLabAdapter {
+    /// Lists runbook snippets of a project, or the user's personal snippets
+    /// when no project is given.
+    ///
+    /// # Arguments
+    /// * `service` - Only runbooks tagged with this service
+    pub async fn fetch_runbooks(
+        &self,
+        project_id: Option<u32>,
+        service: Option<&str>,
+    ) -> Result<Vec<GitLabRunbook>, IntegrationError> {
+        let endpoint = match project_id {
+            Some(id) => format!("/projects/{}/snippets?per_page=100", id),
+            None => "/snippets?per_page=100".to_string(),
+        };
+        let snippets: Vec<GitLabSnippet> = self.get(&endpoint).await?;
+
+        let mut runbooks: Vec<GitLabRunbook> = snippets
+            .into_iter()
+            .filter_map(|snippet| to_runbook(snippet, project_id))
+            .filter(|runbook| {
+                service.is_none_or(|s| {
+                    runbook
+                        .services
+                        .iter()
+                        .any(|name| name.eq_ignore_ascii_case(s))
+                })
+            })
+            .collect();
+        runbooks.sort_by_key(|r| r.title.to_lowercase());
+        Ok(runbooks)
+    }
+
+    /// Fetches the content of a runbook snippet.
+    pub async fn fetch_runbook_content(
+        &self,
+        project_id: Option<u32>,
+        snippet_id: u32,
+    ) -> Result<GitLabRunbookContent, IntegrationError> {
+        let base = match project_id {
+            Some(id) => format!("/projects/{}/snippets/{}", id, snippet_id),
+            None => format!("/snippets/{}", snippet_id),
+        };
+        let snippet: GitLabSnippet = self.get(&base).await?;
+        let mut content = self.get_text(&format!("{}/raw", base)).await?;
+
+        let truncated = truncate_content(&mut content, MAX_RUNBOOK_BYTES);
+
+        Ok(GitLabRunbookContent {
+            id: snippet.id,
+            title: snippet.title,
+            file_name: snippet.file_name,
+            content,
+            truncated,
+        })
+    }
+}
+
+/// Converts a snippet into a runbook if it carries the runbook tag.
+fn to_runbook(snippet: GitLabSnippet, project_id: Option<u32>) -> Option<GitLabRunbook> {
+    let description = snippet.description.clone().unwrap_or_default();
+    let is_runbook = has_tag(&snippet.title, RUNBOOK_TAG) || has_tag(&description, RUNBOOK_TAG);
+    if !is_runbook {
+        return None;
+    }
+
+    Some(GitLabRunbook {
+        id: snippet.id,
+        project_id: snippet.project_id.or(project_id),
+        title: strip_tags(&snippet.title),
+        description: Some(strip_tags(&description)).filter(|d| !d.is_empty()),
+        services: service_tags(&description),
+        file_name: snippet.file_name,
+        web_url: snippet.web_url,
+        updated_at: snippet.updated_at,
+        author: snippet.author,
+    })
+}
+
+fn has_tag(text: &str, tag: &str) -> bool {
+    text.split_whitespace()
+        .any(|word| word.eq_ignore_ascii_case(tag))
+}
+
+/// Services named by `service:<name>` tags.
+fn service_tags(text: &str) -> Vec<String> {
+    let mut services: Vec<String> = text
+        .split_whitespace()
+        .filter_map(|word| word.strip_prefix(SERVICE_TAG_PREFIX))
+        .map(|name| name.trim_end_matches([',', '.', ';']).to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    services.dedup();
+    services
+}
+
+/// Removes the runbook and service tags from text shown to the user.
+fn strip_tags(text: &str) -> String {
+    text.split_whitespace()
+        .filter(|word| {
+            !word.eq_ignore_ascii_case(RUNBOOK_TAG) && !word.starts_with(SERVICE_TAG_PREFIX)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Timestamp;
+
+    fn snippet(title: &str, description: Option<&str>) -> GitLabSnippet {
+        GitLabSnippet {
+            id: 5,
+            project_id: None,
+            title: title.to_string(),
+            description: description.map(|d| d.to_string()),
+            file_name: "restart.md".to_string(),
+            web_url: "https://gitlab.example.com/-/snippets/5".to_string(),
+            updated_at: Timestamp::parse("2024-05-01T10:00:00Z").unwrap(),
+            author: None,
+        }
+    }
+
+    #[test]
+    fn test_to_runbook() {
+        let runbook = to_runbook(
+            snippet(
+                "Restart checkout #runbook",
+                Some("Steps to restart. service:checkout-api service:payments,"),
+            ),
+            Some(12),
+        )
+        .unwrap();
+        assert_eq!(runbook.title, "Restart checkout");
+        assert_eq!(runbook.description.as_deref(), Some("Steps to restart."));
+        assert_eq!(runbook.services, ["checkout-api", "payments"]);
+        assert_eq!(runbook.project_id, Some(12));
+
+        assert!(to_runbook(snippet("Notes", Some("scratch pad")), None).is_none());
+        assert!(to_runbook(snippet("DB failover", Some("#RUNBOOK")), None).is_some());
+    }
+}
@@ -0,0 +1,49 @@
+//! Compatibility shims for responses of older GitLab versions.
+
+use serde_json::{Map, Value};
+
+use crate::integrations::compat::{ApiVersion, Shim};
+
+/// Shims for pipeline responses.
+pub(super) const PIPELINE_SHIMS: &[Shim] = &[Shim {
+    until: ApiVersion::new(12, 0),
+    description: "Pipelines without created_at (GitLab 11 and older) use updated_at",
+    apply: fill_pipeline_created_at,
+}];
+
+/// Uses `updated_at` as the creation time, or the Unix epoch when the
+/// pipeline has no timestamps at all.
+fn fill_pipeline_created_at(pipeline: &mut Map<String, Value>) {
+    if pipeline.get("created_at").is_some_and(|v| !v.is_null()) {
+        return;
+    }
+    let created_at = pipeline
+        .get("updated_at")
+        .filter(|v| v.is_string())
+        .cloned()
+        .unwrap_or_else(|| Value::from("1970-01-01T00:00:00Z"));
+    pipeline.insert("created_at".to_string(), created_at);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::compat::apply_shims;
+    use crate::integrations::gitlab::GitLabPipeline;
+    use serde_json::json;
+
+    #[test]
+    fn test_pipeline_shims() {
+        // GitLab 11 pipeline list entries
+        let mut response = json!([
+            { "id": 47, "status": "success", "ref": "main", "updated_at": "2019-03-01T10:00:00Z" },
+            { "id": 46, "status": "failed", "ref": "main" }
+        ]);
+
+        apply_shims(&mut response, Some(ApiVersion::new(11, 10)), PIPELINE_SHIMS);
+
+        let pipelines: Vec<GitLabPipeline> = serde_json::from_value(response).unwrap();
+        assert_eq!(pipelines[0].created_at.rfc3339, "2019-03-01T10:00:00.000Z");
+        assert_eq!(pipelines[1].created_at.rfc3339, "1970-01-01T00:00:00.000Z");
+    }
+}
@@ -0,0 +1,29 @@
+//! GitLab repository tags.
+
+use super::types::GitLabTag;
+use super::GitLabAdapter;
+use crate::integrations::IntegrationError;
+
+impl GitLabAdapter {
+    /// Fetches the most recently updated tags of a project (up to 100).
+    ///
+    /// # Arguments
+    /// * `prefix` - Only tags starting with this text
+    pub async fn fetch_tags(
+        &self,
+        project_id: u32,
+        prefix: Option<&str>,
+    ) -> Result<Vec<GitLabTag>, IntegrationError> {
+        let mut endpoint = format!(
+            "/projects/{}/repository/tags?order_by=updated&sort=desc&per_page=100",
+            project_id
+        );
+        if let Some(prefix) = prefix.filter(|p| !p.is_empty()) {
+            endpoint.push_str(&format!(
+                "&search={}",
+                urlencoding::encode(&format!("^{}", prefix))
+            ));
+        }
+        self.get(&endpoint).await
+    }
+}
@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
+use crate::types::Timestamp;
+
 /// GitLab project representation.
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
 pub struct GitLabProject {
@@ -25,8 +27,8 @@ pub struct GitLabPipeline {
     pub status: String,
     /// Git reference (branch or tag)
     pub r#ref: String,
-    /// Creation timestamp (ISO 8601 format)
-    pub created_at: String,
+    /// Creation time
+    pub created_at: Timestamp,
 }
 
 /// GitLab webhook representation.
@@ -39,3 +41,390 @@ pub struct GitLabWebhook {
     /// List of events this webhook subscribes to
     pub events: Vec<String>,
 }
+
+/// GitLab user reference (e.g., an issue assignee).
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabUserRef {
+    /// Username (e.g., "jdoe")
+    pub username: String,
+    /// Display name
+    pub name: String,
+}
+
+/// Milestone an issue belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabMilestoneRef {
+    /// Milestone ID
+    pub id: u32,
+    /// Milestone title (e.g., "Sprint 42")
+    pub title: String,
+    /// Due date (YYYY-MM-DD)
+    pub due_date: Option<String>,
+}
+
+/// GitLab issue representation.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabIssue {
+    /// Issue ID
+    pub id: u32,
+    /// Issue number within the project
+    pub iid: u32,
+    pub title: String,
+    /// Issue state ("opened" or "closed")
+    pub state: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub milestone: Option<GitLabMilestoneRef>,
+    #[serde(default)]
+    pub assignees: Vec<GitLabUserRef>,
+    /// Web URL to access the issue
+    pub web_url: String,
+    /// Creation time
+    pub created_at: Timestamp,
+    /// Last update time
+    pub updated_at: Timestamp,
+    /// Due date (YYYY-MM-DD)
+    pub due_date: Option<String>,
+}
+
+/// Issue counts of a milestone.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabMilestoneSummary {
+    /// Milestone title; None groups issues without a milestone
+    pub milestone: Option<String>,
+    /// Due date (YYYY-MM-DD)
+    pub due_date: Option<String>,
+    pub opened: u32,
+    pub closed: u32,
+    /// Share of closed issues (0-100)
+    pub progress_percent: u32,
+}
+
+/// Issue counts of a label.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabLabelSummary {
+    pub label: String,
+    pub opened: u32,
+    pub closed: u32,
+}
+
+/// Issues of a project with milestone and label aggregation.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabIssueSummary {
+    /// Project ID
+    pub project_id: u32,
+    pub opened: u32,
+    pub closed: u32,
+    /// Per milestone, soonest due date first (issues without a milestone last)
+    pub milestones: Vec<GitLabMilestoneSummary>,
+    /// Per label, most open issues first
+    pub labels: Vec<GitLabLabelSummary>,
+    pub issues: Vec<GitLabIssue>,
+    /// Whether more issues matched than were fetched
+    pub truncated: bool,
+}
+
+/// GitLab snippet representation.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabSnippet {
+    /// Snippet ID
+    pub id: u32,
+    /// Project ID (project snippets only)
+    pub project_id: Option<u32>,
+    pub title: String,
+    pub description: Option<String>,
+    pub file_name: String,
+    /// Web URL to access the snippet
+    pub web_url: String,
+    /// Last update time
+    pub updated_at: Timestamp,
+    pub author: Option<GitLabUserRef>,
+}
+
+/// Snippet tagged as a runbook (`#runbook`).
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabRunbook {
+    /// Snippet ID
+    pub id: u32,
+    /// Project ID (None for personal snippets)
+    pub project_id: Option<u32>,
+    /// Title without tags
+    pub title: String,
+    /// Description without tags
+    pub description: Option<String>,
+    /// Services from `service:<name>` tags
+    pub services: Vec<String>,
+    pub file_name: String,
+    /// Web URL to access the snippet
+    pub web_url: String,
+    /// Last update time
+    pub updated_at: Timestamp,
+    pub author: Option<GitLabUserRef>,
+}
+
+/// Content of a runbook snippet.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabRunbookContent {
+    /// Snippet ID
+    pub id: u32,
+    pub title: String,
+    /// File name (the extension tells how to render it, e.g., ".md")
+    pub file_name: String,
+    pub content: String,
+    /// Whether the content was cut at 1MB
+    pub truncated: bool,
+}
+
+/// Commit a tag points to.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabTagCommit {
+    /// Commit SHA
+    pub id: String,
+    pub title: Option<String>,
+    /// Commit time
+    pub created_at: Option<Timestamp>,
+}
+
+/// GitLab repository tag representation.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabTag {
+    /// Tag name (e.g., "v1.4.2")
+    pub name: String,
+    /// Annotation message (annotated tags only)
+    pub message: Option<String>,
+    pub commit: GitLabTagCommit,
+    /// Tag creation time (annotated tags only)
+    pub created_at: Option<Timestamp>,
+}
+
+/// Text file read from a repository.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabFile {
+    /// Project ID
+    pub project_id: u32,
+    pub file_name: String,
+    /// Path within the repository (e.g., "charts/api/values.yaml")
+    pub file_path: String,
+    /// Branch, tag or commit the file was read at
+    pub ref_name: String,
+    /// Last commit that changed the file
+    pub last_commit_id: String,
+    /// Size of the whole file
+    pub size_bytes: u32,
+    pub content: String,
+    /// Whether the content was cut at 1MB
+    pub truncated: bool,
+}
+
+/// Result of linting CI configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabCiLintResult {
+    /// Project ID
+    pub project_id: u32,
+    pub valid: bool,
+    /// Errors (e.g., "jobs:build config contains unknown keys: scrip")
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    /// Names of the jobs the configuration defines
+    pub jobs: Vec<String>,
+    /// Configuration with includes expanded (valid configurations only)
+    pub merged_yaml: Option<String>,
+}
+
+/// Deployment of a project to an environment.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabDeployment {
+    /// Deployment ID
+    pub id: u32,
+    /// Deployment status (e.g., "success", "failed", "running", "canceled")
+    pub status: String,
+    /// Environment name
+    pub environment: String,
+    /// Deployed commit SHA
+    pub sha: String,
+    /// Commit time of the deployed commit (if the deployment ran as a job)
+    pub commit_created_at: Option<Timestamp>,
+    pub created_at: Timestamp,
+    /// Completion time (finished deployments only)
+    pub finished_at: Option<Timestamp>,
+}
+
+/// Deploy freeze period of a project, with its state at fetch time.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabFreezePeriod {
+    /// Freeze period ID
+    pub id: u32,
+    /// Cron expression starting the freeze (e.g., "0 23 * * 5")
+    pub freeze_start: String,
+    /// Cron expression ending the freeze (e.g., "0 7 * * 1")
+    pub freeze_end: String,
+    /// Time zone of the cron expressions (e.g., "UTC")
+    pub cron_timezone: String,
+    /// Whether the freeze is in effect now
+    pub active: bool,
+    /// Start of the current freeze, or of the next one when inactive
+    pub starts_at: Option<Timestamp>,
+    /// End of the current or next freeze
+    pub ends_at: Option<Timestamp>,
+}
+
+/// Open issues of a milestone at the end of a day.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabBurndownPoint {
+    /// Day (YYYY-MM-DD)
+    pub date: String,
+    pub remaining: u32,
+}
+
+/// Project milestone with its issue counts and burn-down.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabMilestone {
+    /// Milestone ID
+    pub id: u32,
+    /// Milestone number within the project
+    pub iid: u32,
+    pub title: String,
+    /// Milestone state ("active" or "closed")
+    pub state: String,
+    /// Start date (YYYY-MM-DD)
+    pub start_date: Option<String>,
+    /// Due date (YYYY-MM-DD)
+    pub due_date: Option<String>,
+    pub web_url: String,
+    pub opened: u32,
+    pub closed: u32,
+    /// Share of closed issues (0-100)
+    pub progress_percent: u32,
+    /// Share of the time from start to due date that has passed (0-100)
+    pub elapsed_percent: Option<u32>,
+    /// Open issues per day from the start date (or first issue) until today
+    /// or the due date
+    pub burndown: Vec<GitLabBurndownPoint>,
+}
+
+/// Group epic with the counts of its issues.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabEpic {
+    /// Epic ID
+    pub id: u32,
+    /// Epic number within the group
+    pub iid: u32,
+    pub title: String,
+    /// Epic state ("opened" or "closed")
+    pub state: String,
+    /// Start date (YYYY-MM-DD)
+    pub start_date: Option<String>,
+    /// Due date (YYYY-MM-DD)
+    pub due_date: Option<String>,
+    pub web_url: String,
+    pub opened: u32,
+    pub closed: u32,
+    /// Share of closed issues (0-100)
+    pub progress_percent: u32,
+}
+
+/// Epics of a group.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabEpicList {
+    /// Group ID or full path
+    pub group: String,
+    /// False when epics are not available (GitLab Premium feature) or not
+    /// visible to the token
+    pub licensed: bool,
+    pub epics: Vec<GitLabEpic>,
+}
+
+/// Published version of a package.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabPackageVersion {
+    /// Package ID (each version is a package of its own in GitLab)
+    pub id: u32,
+    pub version: String,
+    /// Package status ("default", "hidden", "processing", "error", ...)
+    pub status: String,
+    pub created_at: Timestamp,
+}
+
+/// Package of a project's registry with its versions.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabPackage {
+    pub name: String,
+    /// Package format ("maven", "npm", "pypi", "generic", ...)
+    pub package_type: String,
+    /// Newest published version (status "default")
+    pub latest_version: Option<String>,
+    /// Versions, newest first
+    pub versions: Vec<GitLabPackageVersion>,
+}
+
+/// API a GitLab read goes through.
+///
+/// GraphQL reads related data in one round-trip; REST works on instances
+/// where GraphQL is disabled. Mutations always use REST.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GitLabApi {
+    Rest,
+    #[default]
+    Graphql,
+}
+
+/// Open merge request of a project.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabMergeRequest {
+    /// Merge request number within the project
+    pub iid: u32,
+    pub title: String,
+    /// State ("opened", "merged", "closed", "locked")
+    pub state: String,
+    pub source_branch: String,
+    pub target_branch: String,
+    /// Username of the author
+    pub author: Option<String>,
+    pub draft: bool,
+    pub web_url: String,
+    pub updated_at: Timestamp,
+}
+
+/// Project with its latest pipelines and open merge requests, as shown on
+/// the dashboard.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabProjectOverview {
+    pub project: GitLabProject,
+    /// Latest pipelines, newest first
+    pub pipelines: Vec<GitLabPipeline>,
+    /// Open merge requests, most recently updated first
+    pub merge_requests: Vec<GitLabMergeRequest>,
+    /// API the overview was read through
+    pub api: GitLabApi,
+}
+
+/// Project as recorded in inventory snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabInventoryProject {
+    pub id: u32,
+    pub name: String,
+    /// Full path (e.g., "platform/checkout-api")
+    pub path_with_namespace: String,
+    /// Missing for empty repositories
+    pub default_branch: Option<String>,
+    /// "private", "internal" or "public"
+    pub visibility: String,
+    #[serde(default)]
+    pub archived: bool,
+    pub web_url: String,
+}
+
+/// CI/CD variable of a project, without its value.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitLabCiVariable {
+    pub key: String,
+    /// "env_var" or "file"
+    pub variable_type: String,
+    #[serde(default)]
+    pub protected: bool,
+    #[serde(default)]
+    pub masked: bool,
+    /// Environments the variable applies to ("*" for all)
+    pub environment_scope: String,
+}
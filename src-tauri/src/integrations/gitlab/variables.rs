@@ -0,0 +1,38 @@
+//! Project CI/CD variables (requires the Maintainer role).
+//!
+//! Only names and flags are read; values are dropped while parsing so they
+//! are never held or logged.
+
+use super::types::GitLabCiVariable;
+use super::GitLabAdapter;
+use crate::integrations::IntegrationError;
+
+/// Variables requested per page (GitLab maximum).
+const PER_PAGE: usize = 100;
+
+/// Pages of variables read at most.
+const MAX_PAGES: u32 = 10;
+
+impl GitLabAdapter {
+    /// Fetches the CI/CD variables of a project.
+    pub async fn fetch_ci_variables(
+        &self,
+        project_id: u32,
+    ) -> Result<Vec<GitLabCiVariable>, IntegrationError> {
+        let mut variables = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let batch: Vec<GitLabCiVariable> = self
+                .get(&format!(
+                    "/projects/{}/variables?per_page={}&page={}",
+                    project_id, PER_PAGE, page
+                ))
+                .await?;
+            let more = batch.len() == PER_PAGE;
+            variables.extend(batch);
+            if !more {
+                break;
+            }
+        }
+        Ok(variables)
+    }
+}
@@ -0,0 +1,241 @@
+//! Harbor registry adapter.
+//!
+//! Reads vulnerability scan results of image artifacts. Harbor instances are
+//! configured as image scanners rather than integrations, and anonymous
+//! access is used when no credentials are stored for a scanner.
+
+mod sbom;
+mod types;
+
+pub use types::{HarborScan, ImageVulnerability};
+
+use crate::integrations::IntegrationError;
+use reqwest::Client;
+use serde_json::Value;
+
+/// Harbor registry adapter.
+pub struct HarborAdapter {
+    /// Base URL of the Harbor instance
+    base_url: String,
+    /// Username and password (robot account) for Basic Auth
+    credentials: Option<(String, String)>,
+    /// HTTP client for API requests
+    client: Client,
+}
+
+impl HarborAdapter {
+    /// Creates a new Harbor adapter instance.
+    pub fn new(base_url: String, credentials: Option<(String, String)>) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            credentials,
+            client: Client::new(),
+        }
+    }
+
+    /// Builds the full API URL for a given endpoint.
+    fn api_url(&self, endpoint: &str) -> String {
+        format!("{}/api/v2.0{}", self.base_url, endpoint)
+    }
+
+    /// Makes a GET request to the Harbor API.
+    async fn get<T: for<'de> serde::Deserialize<'de>>(
+        &self,
+        endpoint: &str,
+    ) -> Result<T, IntegrationError> {
+        let url = self.api_url(endpoint);
+        log::debug!("Harbor API GET: {}", url);
+
+        let mut request = self
+            .client
+            .get(&url)
+            .header("Accept", "application/json")
+            .timeout(std::time::Duration::from_secs(30));
+        if let Some((username, password)) = &self.credentials {
+            request = request.basic_auth(username, Some(password));
+        }
+        let response = request.send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            log::error!("Harbor API error ({}): {}", status, error_text);
+            return Err(crate::integrations::errors::status_to_error(
+                status.as_u16(),
+                Some(error_text),
+            ));
+        }
+
+        response.json::<T>().await.map_err(|e| {
+            log::error!("Failed to parse Harbor API response: {}", e);
+            IntegrationError::ConfigError {
+                message: format!("Failed to parse response: {}", e),
+            }
+        })
+    }
+
+    /// Fetches the scan results of an artifact.
+    ///
+    /// # Arguments
+    /// * `project` - Harbor project (first path segment of the image)
+    /// * `repository` - Repository within the project (e.g., "team/api")
+    /// * `reference` - Tag or digest
+    pub async fn fetch_scan(
+        &self,
+        project: &str,
+        repository: &str,
+        reference: &str,
+    ) -> Result<HarborScan, IntegrationError> {
+        let artifact_path = artifact_path(project, repository, reference);
+        let artifact: Value = self
+            .get(&format!("{}?with_scan_overview=true", artifact_path))
+            .await?;
+        let mut scan = parse_scan_overview(&artifact);
+
+        if scan.scan_status.as_deref() == Some("Success") {
+            let report: Value = self
+                .get(&format!("{}/additions/vulnerabilities", artifact_path))
+                .await?;
+            scan.vulnerabilities = parse_vulnerabilities(&report);
+        }
+        Ok(scan)
+    }
+}
+
+/// API path of an artifact.
+fn artifact_path(project: &str, repository: &str, reference: &str) -> String {
+    // Repository names containing '/' must be encoded twice
+    format!(
+        "/projects/{}/repositories/{}/artifacts/{}",
+        urlencoding::encode(project),
+        urlencoding::encode(&urlencoding::encode(repository)),
+        urlencoding::encode(reference)
+    )
+}
+
+/// Returns the first report of a map keyed by report MIME type.
+fn first_report(reports: Option<&Value>) -> Option<&Value> {
+    reports
+        .and_then(|r| r.as_object())
+        .and_then(|r| r.values().next())
+}
+
+/// Reads the scan status and severity counts of an artifact.
+fn parse_scan_overview(artifact: &Value) -> HarborScan {
+    let overview = first_report(artifact.get("scan_overview"));
+    let severity_counts = overview
+        .and_then(|o| o.pointer("/summary/summary"))
+        .and_then(|s| s.as_object())
+        .map(|counts| {
+            counts
+                .iter()
+                .filter_map(|(severity, count)| {
+                    Some((severity.clone(), u32::try_from(count.as_u64()?).ok()?))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    HarborScan {
+        digest: artifact
+            .get("digest")
+            .and_then(|d| d.as_str())
+            .map(str::to_string),
+        scan_status: overview
+            .and_then(|o| o.get("scan_status"))
+            .and_then(|s| s.as_str())
+            .map(str::to_string),
+        severity_counts,
+        vulnerabilities: Vec::new(),
+    }
+}
+
+/// Reads the vulnerabilities of a vulnerability report.
+fn parse_vulnerabilities(report: &Value) -> Vec<ImageVulnerability> {
+    let Some(items) = first_report(Some(report))
+        .and_then(|r| r.get("vulnerabilities"))
+        .and_then(|v| v.as_array())
+    else {
+        return Vec::new();
+    };
+
+    let text = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            Some(ImageVulnerability {
+                id: text(item, "id")?,
+                package: text(item, "package").unwrap_or_default(),
+                version: text(item, "version").unwrap_or_default(),
+                fix_version: text(item, "fix_version"),
+                severity: text(item, "severity").unwrap_or_else(|| "Unknown".to_string()),
+                link: item
+                    .get("links")
+                    .and_then(|l| l.as_array())
+                    .and_then(|l| l.first())
+                    .and_then(|l| l.as_str())
+                    .map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const MIME: &str = "application/vnd.security.vulnerability.report; version=1.1";
+
+    #[test]
+    fn test_parse_scan() {
+        let artifact = json!({
+            "digest": "sha256:abc",
+            "scan_overview": {
+                MIME: {
+                    "scan_status": "Success",
+                    "severity": "Critical",
+                    "summary": { "total": 3, "summary": { "Critical": 1, "High": 2 } }
+                }
+            }
+        });
+        let scan = parse_scan_overview(&artifact);
+        assert_eq!(scan.scan_status.as_deref(), Some("Success"));
+        assert_eq!(scan.severity_counts.get("Critical"), Some(&1));
+        assert_eq!(scan.severity_counts.get("High"), Some(&2));
+
+        let unscanned = parse_scan_overview(&json!({ "digest": "sha256:abc" }));
+        assert_eq!(unscanned.scan_status, None);
+
+        let report = json!({
+            MIME: {
+                "vulnerabilities": [{
+                    "id": "CVE-2024-3094",
+                    "package": "xz-utils",
+                    "version": "5.6.0",
+                    "fix_version": "5.6.1",
+                    "severity": "Critical",
+                    "links": ["https://nvd.nist.gov/vuln/detail/CVE-2024-3094"]
+                }]
+            }
+        });
+        let vulnerabilities = parse_vulnerabilities(&report);
+        assert_eq!(vulnerabilities.len(), 1);
+        assert_eq!(vulnerabilities[0].fix_version.as_deref(), Some("5.6.1"));
+    }
+
+    #[test]
+    fn test_api_url() {
+        let adapter = HarborAdapter::new("https://harbor.example.com/".to_string(), None);
+        assert_eq!(
+            adapter.api_url("/projects"),
+            "https://harbor.example.com/api/v2.0/projects"
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! SBOMs generated by Harbor.
+//!
+//! Harbor (2.11+) stores a generated SBOM as an accessory of the image
+//! artifact. The artifact's SBOM overview names the accessory digest, whose
+//! `sbom` addition holds the document.
+
+use serde_json::Value;
+
+use super::{artifact_path, HarborAdapter};
+use crate::integrations::IntegrationError;
+
+impl HarborAdapter {
+    /// Fetches the SBOM document of an artifact, or `None` when Harbor has
+    /// not generated one.
+    pub async fn fetch_sbom(
+        &self,
+        project: &str,
+        repository: &str,
+        reference: &str,
+    ) -> Result<Option<Value>, IntegrationError> {
+        let artifact: Value = self
+            .get(&format!(
+                "{}?with_sbom_overview=true",
+                artifact_path(project, repository, reference)
+            ))
+            .await?;
+        let Some(sbom_digest) = sbom_digest(&artifact) else {
+            return Ok(None);
+        };
+
+        let document: Value = self
+            .get(&format!(
+                "{}/additions/sbom",
+                artifact_path(project, repository, &sbom_digest)
+            ))
+            .await?;
+        Ok(Some(document))
+    }
+}
+
+/// Digest of the SBOM accessory of a successfully generated SBOM.
+fn sbom_digest(artifact: &Value) -> Option<String> {
+    let overview = artifact.get("sbom_overview")?;
+    if overview.get("scan_status").and_then(|s| s.as_str()) != Some("Success") {
+        return None;
+    }
+    overview
+        .get("sbom_digest")
+        .and_then(|d| d.as_str())
+        .filter(|d| !d.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sbom_digest() {
+        let artifact = json!({
+            "sbom_overview": { "scan_status": "Success", "sbom_digest": "sha256:sbom" }
+        });
+        assert_eq!(sbom_digest(&artifact).as_deref(), Some("sha256:sbom"));
+
+        let running = json!({ "sbom_overview": { "scan_status": "Running", "sbom_digest": "" } });
+        assert_eq!(sbom_digest(&running), None);
+        assert_eq!(sbom_digest(&json!({})), None);
+    }
+}
@@ -0,0 +1,35 @@
+//! Harbor-specific types for API responses.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+
+/// A vulnerability reported by the registry scanner.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ImageVulnerability {
+    /// Vulnerability ID (e.g., "CVE-2024-3094")
+    pub id: String,
+    /// Affected package
+    pub package: String,
+    /// Installed package version
+    pub version: String,
+    /// First version with a fix (if any)
+    pub fix_version: Option<String>,
+    /// Severity ("Critical", "High", "Medium", "Low", "Unknown")
+    pub severity: String,
+    /// Link to the advisory
+    pub link: Option<String>,
+}
+
+/// Scan results of an image artifact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HarborScan {
+    /// Artifact digest
+    pub digest: Option<String>,
+    /// Scan status (e.g., "Success", "Running", "Error"); `None` when never scanned
+    pub scan_status: Option<String>,
+    /// Number of vulnerabilities per severity
+    pub severity_counts: BTreeMap<String, u32>,
+    /// Vulnerabilities found (only read for successful scans)
+    pub vulnerabilities: Vec<ImageVulnerability>,
+}
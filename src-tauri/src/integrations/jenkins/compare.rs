@@ -0,0 +1,305 @@
+//! Jenkins build comparison.
+//!
+//! Stage timings come from the Pipeline REST API (`wfapi`), test results from
+//! the JUnit test report and commits from the change sets of the builds in
+//! between. Freestyle jobs and builds without tests just leave those parts
+//! empty.
+
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+use super::JenkinsAdapter;
+use crate::integrations::builds::{
+    compare_build_data, whole_seconds, BuildChange, BuildComparison, BuildData, BuildStep,
+    BuildSummary, TestCounts, TestRun,
+};
+use crate::integrations::IntegrationError;
+
+impl JenkinsAdapter {
+    /// Compares build `build_a` (base) with build `build_b` (head) of a job.
+    pub async fn compare_builds(
+        &self,
+        job_name: &str,
+        build_a: u32,
+        build_b: u32,
+    ) -> Result<BuildComparison, IntegrationError> {
+        let base = self.fetch_build_data(job_name, build_a).await?;
+        let head = self.fetch_build_data(job_name, build_b).await?;
+        let changes = self
+            .fetch_changes_between(job_name, build_a, build_b)
+            .await?;
+
+        Ok(compare_build_data(base, head, changes))
+    }
+
+    async fn fetch_build_data(
+        &self,
+        job_name: &str,
+        build_number: u32,
+    ) -> Result<BuildData, IntegrationError> {
+        let build = self.fetch_build_details(job_name, build_number).await?;
+        let encoded_job_name = urlencoding::encode(job_name);
+
+        let steps = match self
+            .get::<Value>(&format!(
+                "/job/{}/{}/wfapi/describe",
+                encoded_job_name, build_number
+            ))
+            .await
+        {
+            Ok(response) => parse_stages(&response),
+            Err(e) => {
+                log::debug!("No stage timings for {} #{}: {}", job_name, build_number, e);
+                Vec::new()
+            }
+        };
+
+        let tests = match self
+            .get::<Value>(&format!(
+                "/job/{}/{}/testReport/api/json?tree=passCount,failCount,skipCount,suites[cases[className,name,status]]",
+                encoded_job_name, build_number
+            ))
+            .await
+        {
+            Ok(response) => Some(parse_test_report(&response)),
+            Err(e) => {
+                log::debug!(
+                    "No test report for {} #{}: {}",
+                    job_name,
+                    build_number,
+                    e
+                );
+                None
+            }
+        };
+
+        let status = serde_json::to_value(&build.status)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+
+        Ok(BuildData {
+            summary: BuildSummary {
+                id: build.number.to_string(),
+                status,
+                url: build.url,
+                started_at: Some(build.timestamp),
+                duration_seconds: build
+                    .duration
+                    .and_then(|d| d.parse::<f64>().ok())
+                    .filter(|d| *d > 0.0)
+                    .map(|d| whole_seconds(d / 1000.0)),
+                tests: None,
+            },
+            steps,
+            tests,
+        })
+    }
+
+    /// Collects the change sets of the builds after the older and up to the
+    /// newer of the two builds (within the last 100 builds of the job).
+    async fn fetch_changes_between(
+        &self,
+        job_name: &str,
+        build_a: u32,
+        build_b: u32,
+    ) -> Result<Vec<BuildChange>, IntegrationError> {
+        if build_b <= build_a {
+            return Ok(Vec::new());
+        }
+        let endpoint = format!(
+            "/job/{}/api/json?tree=builds[number,changeSets[items[commitId,msg,author[fullName]]]]",
+            urlencoding::encode(job_name)
+        );
+        let response: Value = self.get(&endpoint).await?;
+        Ok(parse_changes(&response, build_a, build_b))
+    }
+}
+
+/// Reads stage timings from a `wfapi/describe` response.
+fn parse_stages(response: &Value) -> Vec<BuildStep> {
+    response
+        .get("stages")
+        .and_then(|s| s.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|stage| {
+            Some(BuildStep {
+                name: stage.get("name")?.as_str()?.to_string(),
+                status: stage
+                    .get("status")
+                    .and_then(|s| s.as_str())
+                    .unwrap_or("UNKNOWN")
+                    .to_lowercase(),
+                duration_seconds: stage
+                    .get("durationMillis")
+                    .and_then(|d| d.as_f64())
+                    .map(|d| whole_seconds(d / 1000.0)),
+            })
+        })
+        .collect()
+}
+
+/// Reads counts and failed test names from a JUnit test report.
+fn parse_test_report(response: &Value) -> TestRun {
+    let count = |key: &str| response.get(key).and_then(|c| c.as_u64()).unwrap_or(0) as u32;
+    let (passed, failed_count, skipped) =
+        (count("passCount"), count("failCount"), count("skipCount"));
+
+    let failed: BTreeSet<String> = response
+        .get("suites")
+        .and_then(|s| s.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|suite| suite.get("cases").and_then(|c| c.as_array()))
+        .flatten()
+        .filter(|case| {
+            matches!(
+                case.get("status").and_then(|s| s.as_str()),
+                Some("FAILED") | Some("REGRESSION")
+            )
+        })
+        .map(|case| {
+            let name = case
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or_default();
+            match case.get("className").and_then(|c| c.as_str()) {
+                Some(class_name) if !class_name.is_empty() => format!("{}.{}", class_name, name),
+                _ => name.to_string(),
+            }
+        })
+        .collect();
+
+    TestRun {
+        counts: TestCounts {
+            total: passed + failed_count + skipped,
+            failed: failed_count,
+            skipped,
+        },
+        failed,
+    }
+}
+
+/// Reads the commits of builds in `(from, to]`, oldest first.
+fn parse_changes(response: &Value, from: u32, to: u32) -> Vec<BuildChange> {
+    let mut builds: Vec<&Value> = response
+        .get("builds")
+        .and_then(|b| b.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|build| {
+            build
+                .get("number")
+                .and_then(|n| n.as_u64())
+                .is_some_and(|n| n > u64::from(from) && n <= u64::from(to))
+        })
+        .collect();
+    builds.sort_by_key(|build| build.get("number").and_then(|n| n.as_u64()));
+
+    builds
+        .into_iter()
+        .filter_map(|build| build.get("changeSets").and_then(|c| c.as_array()))
+        .flatten()
+        .filter_map(|change_set| change_set.get("items").and_then(|i| i.as_array()))
+        .flatten()
+        .map(|item| BuildChange {
+            commit_id: item
+                .get("commitId")
+                .and_then(|c| c.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            title: item
+                .get("msg")
+                .and_then(|m| m.as_str())
+                .and_then(|m| m.lines().next())
+                .unwrap_or_default()
+                .to_string(),
+            author: item
+                .get("author")
+                .and_then(|a| a.get("fullName"))
+                .and_then(|n| n.as_str())
+                .map(str::to_string),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_stages() {
+        let response = json!({ "stages": [
+            { "name": "Build", "status": "SUCCESS", "durationMillis": 61400 },
+            { "name": "Test", "status": "FAILED", "durationMillis": 1200 },
+        ] });
+
+        assert_eq!(
+            parse_stages(&response),
+            vec![
+                BuildStep {
+                    name: "Build".to_string(),
+                    status: "success".to_string(),
+                    duration_seconds: Some(61),
+                },
+                BuildStep {
+                    name: "Test".to_string(),
+                    status: "failed".to_string(),
+                    duration_seconds: Some(1),
+                },
+            ]
+        );
+        assert!(parse_stages(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_parse_test_report() {
+        let response = json!({
+            "passCount": 8, "failCount": 2, "skipCount": 1,
+            "suites": [{ "cases": [
+                { "className": "com.example.ApiTest", "name": "login", "status": "REGRESSION" },
+                { "className": "com.example.ApiTest", "name": "logout", "status": "PASSED" },
+                { "className": "", "name": "smoke", "status": "FAILED" },
+            ] }]
+        });
+
+        let run = parse_test_report(&response);
+
+        assert_eq!(
+            run.counts,
+            TestCounts {
+                total: 11,
+                failed: 2,
+                skipped: 1
+            }
+        );
+        assert_eq!(
+            run.failed.into_iter().collect::<Vec<_>>(),
+            vec!["com.example.ApiTest.login", "smoke"]
+        );
+    }
+
+    #[test]
+    fn test_parse_changes_between_builds() {
+        let commit = |id: &str| json!({ "commitId": id, "msg": format!("{}\n\nbody", id), "author": { "fullName": "Dana" } });
+        let response = json!({ "builds": [
+            { "number": 43, "changeSets": [{ "items": [commit("c3")] }] },
+            { "number": 42, "changeSets": [{ "items": [commit("c2")] }] },
+            { "number": 41, "changeSets": [{ "items": [commit("c1")] }] },
+        ] });
+
+        let changes = parse_changes(&response, 41, 43);
+
+        assert_eq!(
+            changes
+                .iter()
+                .map(|c| c.commit_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["c2", "c3"]
+        );
+        assert_eq!(changes[0].title, "c2");
+        assert_eq!(changes[0].author.as_deref(), Some("Dana"));
+    }
+}
@@ -0,0 +1,130 @@
+//! Jenkins credential store inventory (read-only).
+//!
+//! Lists credential IDs and types from the credentials plugin API. The API
+//! never exposes secret values, and only metadata fields are read here.
+
+use serde_json::Value;
+
+use super::types::JenkinsCredentialMetadata;
+use super::JenkinsAdapter;
+use crate::integrations::IntegrationError;
+
+impl JenkinsAdapter {
+    /// Fetches credential metadata from the system store, or from a folder's
+    /// store when `folder` is given (e.g., "team-a/services").
+    pub async fn fetch_credentials_metadata(
+        &self,
+        folder: Option<&str>,
+    ) -> Result<Vec<JenkinsCredentialMetadata>, IntegrationError> {
+        let (endpoint, store) = match folder
+            .map(|f| f.trim_matches('/'))
+            .filter(|f| !f.is_empty())
+        {
+            Some(folder) => {
+                let encoded_path = folder
+                    .split('/')
+                    .map(|segment| urlencoding::encode(segment))
+                    .collect::<Vec<_>>()
+                    .join("/job/");
+                (
+                    format!(
+                        "/job/{}/credentials/store/folder/api/json?depth=2",
+                        encoded_path
+                    ),
+                    folder.to_string(),
+                )
+            }
+            None => (
+                "/credentials/store/system/api/json?depth=2".to_string(),
+                "system".to_string(),
+            ),
+        };
+
+        let response: Value = self.get(&endpoint).await?;
+        Ok(parse_credentials(&response, &store))
+    }
+}
+
+/// Reads credential metadata from a store response (`domains` keyed by name).
+fn parse_credentials(response: &Value, store: &str) -> Vec<JenkinsCredentialMetadata> {
+    let Some(domains) = response.get("domains").and_then(|d| d.as_object()) else {
+        log::warn!("Invalid credentials store response: missing 'domains'");
+        return Vec::new();
+    };
+
+    let text = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+
+    let mut credentials = Vec::new();
+    for (domain, domain_value) in domains {
+        let Some(items) = domain_value.get("credentials").and_then(|c| c.as_array()) else {
+            continue;
+        };
+        for item in items {
+            let Some(id) = text(item, "id") else {
+                continue;
+            };
+            credentials.push(JenkinsCredentialMetadata {
+                id,
+                type_name: text(item, "typeName").unwrap_or_else(|| "Unknown".to_string()),
+                display_name: text(item, "displayName"),
+                description: text(item, "description"),
+                store: store.to_string(),
+                domain: if domain == "_" {
+                    "global".to_string()
+                } else {
+                    domain.clone()
+                },
+            });
+        }
+    }
+    credentials.sort_by(|a, b| a.id.cmp(&b.id));
+    credentials
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_credentials() {
+        let response = json!({
+            "domains": {
+                "_": {
+                    "credentials": [
+                        {
+                            "id": "registry-push",
+                            "typeName": "Username with password",
+                            "displayName": "ci/****** (registry)",
+                            "description": "registry",
+                            "fingerprint": null
+                        },
+                        { "id": "deploy-key", "typeName": "SSH Username with private key" }
+                    ]
+                },
+                "prod": { "credentials": [{ "id": "kubeconfig-prod", "typeName": "Secret file" }] }
+            }
+        });
+
+        let credentials = parse_credentials(&response, "system");
+        let summary: Vec<(&str, &str, &str)> = credentials
+            .iter()
+            .map(|c| (c.id.as_str(), c.type_name.as_str(), c.domain.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("deploy-key", "SSH Username with private key", "global"),
+                ("kubeconfig-prod", "Secret file", "prod"),
+                ("registry-push", "Username with password", "global"),
+            ]
+        );
+        assert!(parse_credentials(&json!({}), "system").is_empty());
+    }
+}
@@ -0,0 +1,394 @@
+//! Job configuration summary.
+//!
+//! Reads a job's `config.xml` (requires the Job/ExtendedRead or Configure
+//! permission) and picks out the fields used to link jobs to repositories and
+//! agents. The configuration is parsed with a small reader that handles the
+//! subset of XML Jenkins writes: elements, text, entities and CDATA sections;
+//! attributes are skipped.
+
+use super::types::{JenkinsJobConfigParameter, JenkinsJobConfigSummary, JenkinsJobTrigger};
+use super::JenkinsAdapter;
+use crate::integrations::IntegrationError;
+
+impl JenkinsAdapter {
+    /// Fetches and summarizes the configuration of a job.
+    pub async fn fetch_job_config_summary(
+        &self,
+        job_name: &str,
+    ) -> Result<JenkinsJobConfigSummary, IntegrationError> {
+        let xml = self.fetch_job_config(job_name).await?;
+
+        let root = parse_xml(&xml).map_err(|message| IntegrationError::ConfigError {
+            message: format!("Failed to parse config.xml of {}: {}", job_name, message),
+        })?;
+        Ok(summarize(job_name, &root))
+    }
+
+    /// Fetches the raw `config.xml` of a job.
+    pub async fn fetch_job_config(&self, job_name: &str) -> Result<String, IntegrationError> {
+        let encoded_path = job_name
+            .trim_matches('/')
+            .split('/')
+            .map(|segment| urlencoding::encode(segment))
+            .collect::<Vec<_>>()
+            .join("/job/");
+        self.get_text(&format!("/job/{}/config.xml", encoded_path))
+            .await
+    }
+}
+
+/// Element of a parsed XML document.
+#[derive(Debug, Default)]
+pub(super) struct Element {
+    pub(super) name: String,
+    pub(super) text: String,
+    pub(super) children: Vec<Element>,
+}
+
+impl Element {
+    pub(super) fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// Trimmed text of a child, if not empty.
+    pub(super) fn child_text(&self, name: &str) -> Option<String> {
+        self.child(name)
+            .map(|c| c.text.trim())
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+    }
+
+    /// Visits every descendant with its parent, depth first.
+    pub(super) fn walk<'a>(&'a self, visit: &mut impl FnMut(&'a Element, &'a Element)) {
+        for child in &self.children {
+            visit(child, self);
+            child.walk(visit);
+        }
+    }
+}
+
+/// Type name without its Java package (e.g., "hudson.triggers.TimerTrigger"
+/// becomes "TimerTrigger").
+fn short_name(name: &str) -> String {
+    name.rsplit('.').next().unwrap_or(name).to_string()
+}
+
+fn push_unique(values: &mut Vec<String>, value: Option<String>) {
+    if let Some(value) = value.filter(|v| !values.contains(v)) {
+        values.push(value);
+    }
+}
+
+fn summarize(job_name: &str, root: &Element) -> JenkinsJobConfigSummary {
+    let mut summary = JenkinsJobConfigSummary {
+        job_name: job_name.to_string(),
+        job_type: root.name.clone(),
+        description: root.child_text("description"),
+        disabled: root.child_text("disabled").as_deref() == Some("true"),
+        scm_urls: Vec::new(),
+        branches: Vec::new(),
+        script_path: None,
+        triggers: Vec::new(),
+        parameters: Vec::new(),
+        // Freestyle jobs that can roam run on any agent
+        assigned_node: root
+            .child_text("assignedNode")
+            .filter(|_| root.child_text("canRoam").as_deref() != Some("true")),
+        credential_ids: Vec::new(),
+    };
+
+    root.walk(&mut |element, parent| {
+        let text = || Some(element.text.trim().to_string()).filter(|t| !t.is_empty());
+        match element.name.as_str() {
+            // Git SCM remotes and multibranch Git sources
+            "url" if parent.name.ends_with("UserRemoteConfig") => {
+                push_unique(&mut summary.scm_urls, text());
+            }
+            "remote" => push_unique(&mut summary.scm_urls, text()),
+            "name" if parent.name.ends_with("BranchSpec") => {
+                push_unique(&mut summary.branches, text());
+            }
+            "scriptPath" if summary.script_path.is_none() => summary.script_path = text(),
+            "credentialsId" => push_unique(&mut summary.credential_ids, text()),
+            "triggers" => summary
+                .triggers
+                .extend(element.children.iter().map(|trigger| JenkinsJobTrigger {
+                    kind: short_name(&trigger.name),
+                    spec: trigger.child_text("spec"),
+                })),
+            "parameterDefinitions" => {
+                summary
+                    .parameters
+                    .extend(element.children.iter().filter_map(|definition| {
+                        Some(JenkinsJobConfigParameter {
+                            name: definition.child_text("name")?,
+                            type_name: short_name(&definition.name),
+                            default_value: definition.child_text("defaultValue"),
+                        })
+                    }))
+            }
+            _ => {}
+        }
+    });
+    summary
+}
+
+/// Parses an XML document into its root element.
+pub(super) fn parse_xml(xml: &str) -> Result<Element, String> {
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+    let mut rest = xml;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            append_text(&mut stack, rest);
+            break;
+        };
+        append_text(&mut stack, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let end = after.find("]]>").ok_or("Unterminated CDATA section")?;
+            if let Some(current) = stack.last_mut() {
+                current.text.push_str(&after[..end]);
+            }
+            rest = &after[end + 3..];
+        } else if let Some(after) = rest.strip_prefix("<!--") {
+            let end = after.find("-->").ok_or("Unterminated comment")?;
+            rest = &after[end + 3..];
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            let end = rest.find('>').ok_or("Unterminated declaration")?;
+            rest = &rest[end + 1..];
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let end = after.find('>').ok_or("Unterminated end tag")?;
+            let name = after[..end].trim();
+            let element = stack
+                .pop()
+                .ok_or_else(|| format!("Unexpected </{}>", name))?;
+            if element.name != name {
+                return Err(format!("Expected </{}>, found </{}>", element.name, name));
+            }
+            close(&mut stack, &mut root, element);
+            rest = &after[end + 1..];
+        } else {
+            let end = tag_end(rest).ok_or("Unterminated start tag")?;
+            let tag = &rest[1..end];
+            let self_closing = tag.ends_with('/');
+            let name = tag
+                .trim_end_matches('/')
+                .split(|c: char| c.is_whitespace())
+                .next()
+                .unwrap_or_default();
+            if name.is_empty() {
+                return Err("Empty tag name".to_string());
+            }
+            let element = Element {
+                name: name.to_string(),
+                ..Element::default()
+            };
+            if self_closing {
+                close(&mut stack, &mut root, element);
+            } else {
+                stack.push(element);
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+
+    if let Some(open) = stack.last() {
+        return Err(format!("Unclosed <{}>", open.name));
+    }
+    root.ok_or_else(|| "No root element".to_string())
+}
+
+/// Index of the `>` closing the tag at the start of `rest`, skipping quoted
+/// attribute values.
+fn tag_end(rest: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in rest.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn close(stack: &mut [Element], root: &mut Option<Element>, element: Element) {
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(element),
+        None => *root = Some(element),
+    }
+}
+
+fn append_text(stack: &mut [Element], text: &str) {
+    if let Some(current) = stack.last_mut() {
+        current.text.push_str(&decode_entities(text));
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';').filter(|end| *end <= 10) else {
+            decoded.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..end];
+        let character = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16).ok())
+                .unwrap_or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match character {
+            Some(character) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIPELINE_CONFIG: &str = r#"<?xml version='1.1' encoding='UTF-8'?>
+<flow-definition plugin="workflow-job@1400.v7fd111b_ec82f">
+  <description>Deploys the API &amp; worker</description>
+  <keepDependencies>false</keepDependencies>
+  <properties>
+    <hudson.model.ParametersDefinitionProperty>
+      <parameterDefinitions>
+        <hudson.model.StringParameterDefinition>
+          <name>VERSION</name>
+          <defaultValue>latest</defaultValue>
+          <trim>false</trim>
+        </hudson.model.StringParameterDefinition>
+        <hudson.model.BooleanParameterDefinition>
+          <name>DRY_RUN</name>
+          <defaultValue>true</defaultValue>
+        </hudson.model.BooleanParameterDefinition>
+      </parameterDefinitions>
+    </hudson.model.ParametersDefinitionProperty>
+    <org.jenkinsci.plugins.workflow.job.properties.PipelineTriggersJobProperty>
+      <triggers>
+        <hudson.triggers.TimerTrigger>
+          <spec>H 2 * * 1-5</spec>
+        </hudson.triggers.TimerTrigger>
+        <com.dabsquared.gitlabjenkins.GitLabPushTrigger plugin="gitlab-plugin@1.8.0"/>
+      </triggers>
+    </org.jenkinsci.plugins.workflow.job.properties.PipelineTriggersJobProperty>
+  </properties>
+  <definition class="org.jenkinsci.plugins.workflow.cps.CpsScmFlowDefinition" plugin="workflow-cps@3894">
+    <scm class="hudson.plugins.git.GitSCM" plugin="git@5.2.1">
+      <configVersion>2</configVersion>
+      <userRemoteConfigs>
+        <hudson.plugins.git.UserRemoteConfig>
+          <url>https://gitlab.example.com/platform/api.git</url>
+          <credentialsId>gitlab-deploy-token</credentialsId>
+        </hudson.plugins.git.UserRemoteConfig>
+      </userRemoteConfigs>
+      <branches>
+        <hudson.plugins.git.BranchSpec>
+          <name>*/main</name>
+        </hudson.plugins.git.BranchSpec>
+      </branches>
+    </scm>
+    <scriptPath>ci/Jenkinsfile</scriptPath>
+    <lightweight>true</lightweight>
+  </definition>
+  <disabled>false</disabled>
+</flow-definition>"#;
+
+    #[test]
+    fn test_summarize_pipeline() {
+        let root = parse_xml(PIPELINE_CONFIG).unwrap();
+        let summary = summarize("platform/deploy-api", &root);
+
+        assert_eq!(summary.job_type, "flow-definition");
+        assert_eq!(
+            summary.description.as_deref(),
+            Some("Deploys the API & worker")
+        );
+        assert!(!summary.disabled);
+        assert_eq!(
+            summary.scm_urls,
+            ["https://gitlab.example.com/platform/api.git"]
+        );
+        assert_eq!(summary.branches, ["*/main"]);
+        assert_eq!(summary.script_path.as_deref(), Some("ci/Jenkinsfile"));
+        assert_eq!(summary.credential_ids, ["gitlab-deploy-token"]);
+        assert_eq!(
+            summary.triggers,
+            [
+                JenkinsJobTrigger {
+                    kind: "TimerTrigger".to_string(),
+                    spec: Some("H 2 * * 1-5".to_string()),
+                },
+                JenkinsJobTrigger {
+                    kind: "GitLabPushTrigger".to_string(),
+                    spec: None,
+                },
+            ]
+        );
+        assert_eq!(summary.parameters.len(), 2);
+        assert_eq!(summary.parameters[0].type_name, "StringParameterDefinition");
+        assert_eq!(
+            summary.parameters[0].default_value.as_deref(),
+            Some("latest")
+        );
+        assert_eq!(summary.assigned_node, None);
+    }
+
+    #[test]
+    fn test_summarize_freestyle() {
+        let xml = r#"<project>
+  <assignedNode>linux &amp;&amp; docker</assignedNode>
+  <canRoam>false</canRoam>
+  <disabled>true</disabled>
+  <builders><hudson.tasks.Shell><command><![CDATA[make <all>]]></command></hudson.tasks.Shell></builders>
+</project>"#;
+
+        let root = parse_xml(xml).unwrap();
+        let summary = summarize("build", &root);
+
+        assert_eq!(summary.job_type, "project");
+        assert!(summary.disabled);
+        assert_eq!(summary.assigned_node.as_deref(), Some("linux && docker"));
+        assert!(summary.scm_urls.is_empty());
+    }
+
+    #[test]
+    fn test_parse_xml_errors() {
+        assert!(parse_xml("<project><disabled>true</project>").is_err());
+        assert!(parse_xml("<project>").is_err());
+        assert!(parse_xml("not xml").is_err());
+        assert_eq!(
+            decode_entities("a &lt; b &#38; &#x41; &unknown;"),
+            "a < b & A &unknown;"
+        );
+    }
+}
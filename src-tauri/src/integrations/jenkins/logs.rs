@@ -0,0 +1,57 @@
+//! Build console logs.
+
+use super::JenkinsAdapter;
+use crate::integrations::IntegrationError;
+
+/// Bytes of a console log kept at most (the end of the log, where failures
+/// are reported).
+const MAX_LOG_BYTES: usize = 4 * 1024 * 1024;
+
+impl JenkinsAdapter {
+    /// Fetches the plain-text console log of a build, truncated to its last
+    /// `MAX_LOG_BYTES`.
+    pub async fn fetch_console_text(
+        &self,
+        job_name: &str,
+        build_number: u32,
+    ) -> Result<String, IntegrationError> {
+        let encoded_job_name = urlencoding::encode(job_name);
+        let log = self
+            .get_text(&format!(
+                "/job/{}/{}/consoleText",
+                encoded_job_name, build_number
+            ))
+            .await?;
+        Ok(tail(log, MAX_LOG_BYTES))
+    }
+}
+
+/// Keeps the last `max_bytes` of a log, starting at a line boundary.
+fn tail(log: String, max_bytes: usize) -> String {
+    if log.len() <= max_bytes {
+        return log;
+    }
+    let mut start = log.len() - max_bytes;
+    while !log.is_char_boundary(start) {
+        start += 1;
+    }
+    let kept = &log[start..];
+    match kept.find('\n') {
+        Some(newline) => kept[newline + 1..].to_string(),
+        None => kept.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail() {
+        let log = "first line\nsecond line\nthird line\n".to_string();
+
+        assert_eq!(tail(log.clone(), 100), log);
+        assert_eq!(tail(log.clone(), 15), "third line\n");
+        assert_eq!(tail("héhé".to_string(), 3), "hé");
+    }
+}
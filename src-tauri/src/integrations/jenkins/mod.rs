@@ -2,13 +2,25 @@
 //!
 //! Implements the IntegrationAdapter trait for Jenkins API interactions.
 
+mod compare;
+mod credentials;
+mod job_config;
+mod logs;
+mod parameters;
+mod rebuild;
+mod retention;
 mod types;
 
-pub use types::{JenkinsBuild, JenkinsBuildStatus, JenkinsJob};
+pub use types::{
+    JenkinsBuild, JenkinsBuildStatus, JenkinsCredentialMetadata, JenkinsJob,
+    JenkinsJobConfigSummary, JenkinsParameterDefinition, JenkinsRetentionInfo,
+};
 
+use crate::integrations::fixtures;
 use crate::integrations::{IntegrationAdapter, IntegrationError};
-use crate::types::IntegrationType;
+use crate::types::{IntegrationType, Timestamp};
 use async_trait::async_trait;
+use parameters::build_query;
 use reqwest::Client;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -51,6 +63,37 @@ impl JenkinsAdapter {
         let url = self.api_url(endpoint);
         log::debug!("Jenkins API GET: {}", url);
 
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.username, Some(&self.password))
+            .timeout(std::time::Duration::from_secs(30))
+            .send()
+            .await?;
+
+        let (status, body) = fixtures::capture("jenkins", &self.base_url, "GET", response).await?;
+        if !status.is_success() {
+            log::error!("Jenkins API error ({}): {}", status, body);
+            return Err(crate::integrations::errors::status_to_error(
+                status.as_u16(),
+                Some(body),
+            ));
+        }
+
+        serde_json::from_str::<T>(&body).map_err(|e| {
+            log::error!("Failed to parse Jenkins API response: {}", e);
+            IntegrationError::ConfigError {
+                message: format!("Failed to parse response: {}", e),
+            }
+        })
+    }
+
+    /// Makes an authenticated GET request returning a plain-text body
+    /// (e.g., a job's `config.xml`).
+    async fn get_text(&self, endpoint: &str) -> Result<String, IntegrationError> {
+        let url = self.api_url(endpoint);
+        log::debug!("Jenkins API GET (text): {}", url);
+
         let response = self
             .client
             .get(&url)
@@ -69,10 +112,10 @@ impl JenkinsAdapter {
             ));
         }
 
-        response.json::<T>().await.map_err(|e| {
-            log::error!("Failed to parse Jenkins API response: {}", e);
-            IntegrationError::ConfigError {
-                message: format!("Failed to parse response: {}", e),
+        response.text().await.map_err(|e| {
+            log::error!("Failed to read Jenkins API response body: {}", e);
+            IntegrationError::NetworkError {
+                message: format!("Failed to read response: {}", e),
             }
         })
     }
@@ -103,6 +146,37 @@ impl JenkinsAdapter {
         Ok(())
     }
 
+    /// Makes an authenticated form POST request to the Jenkins API.
+    async fn post_form(
+        &self,
+        endpoint: &str,
+        form: &[(&str, &str)],
+    ) -> Result<(), IntegrationError> {
+        let url = self.api_url(endpoint);
+        log::debug!("Jenkins API POST (form): {}", url);
+
+        let response = self
+            .client
+            .post(&url)
+            .basic_auth(&self.username, Some(&self.password))
+            .form(form)
+            .timeout(std::time::Duration::from_secs(30))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            log::error!("Jenkins API error ({}): {}", status, error_text);
+            return Err(crate::integrations::errors::status_to_error(
+                status.as_u16(),
+                Some(error_text),
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Fetches all jobs from Jenkins, including jobs inside folders (recursively).
     pub async fn fetch_jobs(&self) -> Result<Vec<JenkinsJob>, IntegrationError> {
         use std::collections::VecDeque;
@@ -231,10 +305,10 @@ impl JenkinsAdapter {
             let timestamp = build_value
                 .get("timestamp")
                 .and_then(|t| t.as_i64())
+                .and_then(Timestamp::from_epoch_millis)
                 .ok_or_else(|| IntegrationError::ConfigError {
                     message: "Invalid build format: missing 'timestamp'".to_string(),
-                })?
-                .to_string();
+                })?;
 
             let duration = build_value
                 .get("duration")
@@ -296,10 +370,10 @@ impl JenkinsAdapter {
         let timestamp = response
             .get("timestamp")
             .and_then(|t| t.as_i64())
+            .and_then(Timestamp::from_epoch_millis)
             .ok_or_else(|| IntegrationError::ConfigError {
                 message: "Invalid build format: missing 'timestamp'".to_string(),
-            })?
-            .to_string();
+            })?;
 
         let duration = response
             .get("duration")
@@ -338,6 +412,9 @@ impl JenkinsAdapter {
     }
 
     /// Triggers a build for a specific job.
+    ///
+    /// Values of node parameters may list several agents separated by commas;
+    /// label parameters take a label expression (e.g., "linux && docker").
     pub async fn trigger_build(
         &self,
         job_name: &str,
@@ -350,15 +427,18 @@ impl JenkinsAdapter {
             if params.is_empty() {
                 format!("/job/{}/build", encoded_job_name)
             } else {
-                // Build query string for parameters
-                let query_params: Vec<String> = params
-                    .iter()
-                    .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
-                    .collect();
+                // Node parameters need their definitions to be expanded
+                let definitions = self
+                    .parameter_definitions(job_name)
+                    .await
+                    .unwrap_or_else(|e| {
+                        log::warn!("Failed to fetch parameters of {}: {}", job_name, e);
+                        Vec::new()
+                    });
                 format!(
                     "/job/{}/buildWithParameters?{}",
                     encoded_job_name,
-                    query_params.join("&")
+                    build_query(&params, &definitions)
                 )
             }
         } else {
@@ -367,6 +447,19 @@ impl JenkinsAdapter {
 
         self.post(&endpoint).await
     }
+
+    /// Enables or disables a job. Disabled jobs keep their configuration and
+    /// history but don't start new builds.
+    pub async fn set_job_enabled(
+        &self,
+        job_name: &str,
+        enabled: bool,
+    ) -> Result<(), IntegrationError> {
+        let action = if enabled { "enable" } else { "disable" };
+        let endpoint = format!("/job/{}/{}", urlencoding::encode(job_name), action);
+
+        self.post(&endpoint).await
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,337 @@
+//! Job parameter definitions.
+//!
+//! Besides the built-in parameter types this recognizes the label and node
+//! parameters of the Node and Label Parameter plugin, which deploy jobs use to
+//! pick the agents they run on. Their allowed values are completed from the
+//! agents Jenkins knows about.
+
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+use super::types::{JenkinsParameterDefinition, JenkinsParameterKind};
+use super::JenkinsAdapter;
+use crate::integrations::IntegrationError;
+
+/// Value of `allowedSlaves` that allows every agent.
+const ALL_NODES: &str = "ALL (no restriction)";
+
+/// A Jenkins agent and its labels.
+struct Agent {
+    name: String,
+    labels: Vec<String>,
+}
+
+impl JenkinsAdapter {
+    /// Fetches the parameter definitions of a job, with the agent labels and
+    /// names available to label and node parameters.
+    pub async fn fetch_parameter_definitions(
+        &self,
+        job_name: &str,
+    ) -> Result<Vec<JenkinsParameterDefinition>, IntegrationError> {
+        let mut definitions = self.parameter_definitions(job_name).await?;
+
+        let needs_agents = definitions.iter().any(|d| {
+            d.kind == JenkinsParameterKind::Label
+                || (d.kind == JenkinsParameterKind::Node
+                    && (d.choices.is_empty() || d.choices.iter().any(|c| c == ALL_NODES)))
+        });
+        if needs_agents {
+            let response: Value = self
+                .get("/computer/api/json?tree=computer[displayName,assignedLabels[name]]")
+                .await?;
+            complete_choices(&mut definitions, &parse_agents(&response));
+        }
+        Ok(definitions)
+    }
+
+    /// Fetches the parameter definitions of a job as Jenkins reports them.
+    pub(super) async fn parameter_definitions(
+        &self,
+        job_name: &str,
+    ) -> Result<Vec<JenkinsParameterDefinition>, IntegrationError> {
+        let endpoint = format!(
+            "/job/{}/api/json?tree=property[parameterDefinitions[name,type,description,choices,defaultValue,defaultParameterValue[value],allowedSlaves,defaultSlaves,allowMultiNodeSelection]]",
+            urlencoding::encode(job_name)
+        );
+        let response: Value = self.get(&endpoint).await?;
+        Ok(parse_definitions(&response))
+    }
+}
+
+/// Reads the parameter definitions from the job's properties.
+fn parse_definitions(response: &Value) -> Vec<JenkinsParameterDefinition> {
+    let properties = response.get("property").and_then(|p| p.as_array());
+    let definitions = properties
+        .into_iter()
+        .flatten()
+        .filter_map(|p| p.get("parameterDefinitions").and_then(|d| d.as_array()))
+        .flatten();
+
+    let text = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let list = |value: &Value, key: &str| -> Vec<String> {
+        value
+            .get(key)
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str())
+            .map(str::to_string)
+            .collect()
+    };
+
+    let mut parsed = Vec::new();
+    for definition in definitions {
+        let Some(name) = text(definition, "name") else {
+            continue;
+        };
+        let type_name = text(definition, "type").unwrap_or_else(|| "Unknown".to_string());
+        let kind = match type_name.as_str() {
+            "StringParameterDefinition" => JenkinsParameterKind::String,
+            "TextParameterDefinition" => JenkinsParameterKind::Text,
+            "BooleanParameterDefinition" => JenkinsParameterKind::Boolean,
+            "ChoiceParameterDefinition" => JenkinsParameterKind::Choice,
+            "PasswordParameterDefinition" => JenkinsParameterKind::Password,
+            "LabelParameterDefinition" => JenkinsParameterKind::Label,
+            "NodeParameterDefinition" => JenkinsParameterKind::Node,
+            _ => JenkinsParameterKind::Other,
+        };
+
+        let (default_value, choices) = match kind {
+            JenkinsParameterKind::Node => {
+                let defaults = list(definition, "defaultSlaves");
+                let default_value = (!defaults.is_empty()).then(|| defaults.join(","));
+                (default_value, list(definition, "allowedSlaves"))
+            }
+            _ => {
+                let default_value = text(definition, "defaultValue").or_else(|| {
+                    match definition.get("defaultParameterValue")?.get("value")? {
+                        Value::String(s) if !s.is_empty() => Some(s.clone()),
+                        Value::Bool(b) => Some(b.to_string()),
+                        _ => None,
+                    }
+                });
+                (default_value, list(definition, "choices"))
+            }
+        };
+
+        parsed.push(JenkinsParameterDefinition {
+            name,
+            kind,
+            type_name,
+            description: text(definition, "description"),
+            default_value,
+            choices,
+            multiple: kind == JenkinsParameterKind::Node
+                && definition
+                    .get("allowMultiNodeSelection")
+                    .and_then(|m| m.as_bool())
+                    .unwrap_or(false),
+        });
+    }
+    parsed
+}
+
+fn parse_agents(response: &Value) -> Vec<Agent> {
+    let computers = response.get("computer").and_then(|c| c.as_array());
+    computers
+        .into_iter()
+        .flatten()
+        .filter_map(|computer| {
+            let name = computer.get("displayName")?.as_str()?.to_string();
+            let labels = computer
+                .get("assignedLabels")
+                .and_then(|l| l.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|l| l.get("name")?.as_str())
+                .map(str::to_string)
+                .collect();
+            Some(Agent { name, labels })
+        })
+        .collect()
+}
+
+/// Offers the known agent labels for label parameters and every agent for
+/// node parameters that don't restrict them.
+fn complete_choices(definitions: &mut [JenkinsParameterDefinition], agents: &[Agent]) {
+    let labels: BTreeSet<&str> = agents
+        .iter()
+        .flat_map(|a| a.labels.iter().map(String::as_str))
+        .collect();
+    let mut names: Vec<&str> = agents.iter().map(|a| a.name.as_str()).collect();
+    names.sort_unstable();
+
+    for definition in definitions {
+        match definition.kind {
+            JenkinsParameterKind::Label => {
+                definition.choices = labels.iter().map(|l| l.to_string()).collect();
+            }
+            JenkinsParameterKind::Node
+                if definition.choices.is_empty()
+                    || definition.choices.iter().any(|c| c == ALL_NODES) =>
+            {
+                definition.choices = names.iter().map(|n| n.to_string()).collect();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Builds the `buildWithParameters` query. Node parameters take one query
+/// parameter per selected agent.
+pub(super) fn build_query(
+    parameters: &HashMap<String, String>,
+    definitions: &[JenkinsParameterDefinition],
+) -> String {
+    let mut names: Vec<&String> = parameters.keys().collect();
+    names.sort();
+
+    let mut pairs = Vec::new();
+    for name in names {
+        let value = &parameters[name];
+        let is_node = definitions
+            .iter()
+            .any(|d| &d.name == name && d.kind == JenkinsParameterKind::Node);
+        let values: Vec<&str> = if is_node {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .collect()
+        } else {
+            vec![value.as_str()]
+        };
+        for value in values {
+            pairs.push(format!(
+                "{}={}",
+                urlencoding::encode(name),
+                urlencoding::encode(value)
+            ));
+        }
+    }
+    pairs.join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn definitions() -> Vec<JenkinsParameterDefinition> {
+        parse_definitions(&json!({
+            "property": [
+                { "_class": "hudson.model.ParametersDefinitionProperty", "parameterDefinitions": [
+                    {
+                        "name": "AGENT",
+                        "type": "LabelParameterDefinition",
+                        "defaultValue": "linux",
+                        "defaultParameterValue": { "value": null }
+                    },
+                    {
+                        "name": "TARGET_NODES",
+                        "type": "NodeParameterDefinition",
+                        "allowedSlaves": ["ALL (no restriction)"],
+                        "defaultSlaves": ["deploy-1"],
+                        "allowMultiNodeSelection": true
+                    },
+                    {
+                        "name": "DRY_RUN",
+                        "type": "BooleanParameterDefinition",
+                        "defaultParameterValue": { "value": false }
+                    },
+                    {
+                        "name": "ENV",
+                        "type": "ChoiceParameterDefinition",
+                        "description": "Target environment",
+                        "choices": ["staging", "prod"],
+                        "defaultParameterValue": { "value": "staging" }
+                    }
+                ]},
+                { "_class": "jenkins.model.BuildDiscarderProperty" }
+            ]
+        }))
+    }
+
+    #[test]
+    fn test_parse_definitions() {
+        let definitions = definitions();
+        let summary: Vec<(&str, JenkinsParameterKind, Option<&str>, bool)> = definitions
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.kind,
+                    d.default_value.as_deref(),
+                    d.multiple,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                ("AGENT", JenkinsParameterKind::Label, Some("linux"), false),
+                (
+                    "TARGET_NODES",
+                    JenkinsParameterKind::Node,
+                    Some("deploy-1"),
+                    true
+                ),
+                (
+                    "DRY_RUN",
+                    JenkinsParameterKind::Boolean,
+                    Some("false"),
+                    false
+                ),
+                ("ENV", JenkinsParameterKind::Choice, Some("staging"), false),
+            ]
+        );
+        assert_eq!(definitions[3].choices, ["staging", "prod"]);
+        assert_eq!(
+            definitions[3].description.as_deref(),
+            Some("Target environment")
+        );
+    }
+
+    #[test]
+    fn test_complete_choices() {
+        let mut definitions = definitions();
+        let agents = parse_agents(&json!({
+            "computer": [
+                { "displayName": "deploy-2", "assignedLabels": [{ "name": "linux" }, { "name": "deploy-2" }] },
+                { "displayName": "deploy-1", "assignedLabels": [{ "name": "docker" }, { "name": "linux" }] }
+            ]
+        }));
+
+        complete_choices(&mut definitions, &agents);
+
+        assert_eq!(definitions[0].choices, ["deploy-2", "docker", "linux"]);
+        assert_eq!(definitions[1].choices, ["deploy-1", "deploy-2"]);
+        assert_eq!(definitions[3].choices, ["staging", "prod"]);
+    }
+
+    #[test]
+    fn test_build_query() {
+        let parameters = HashMap::from([
+            ("TARGET_NODES".to_string(), "deploy-1, deploy-2".to_string()),
+            ("AGENT".to_string(), "linux && docker".to_string()),
+            ("ENV".to_string(), "prod".to_string()),
+        ]);
+
+        assert_eq!(
+            build_query(&parameters, &definitions()),
+            "AGENT=linux%20%26%26%20docker&ENV=prod&TARGET_NODES=deploy-1&TARGET_NODES=deploy-2"
+        );
+        // Without definitions every value is passed as is
+        assert_eq!(
+            build_query(&parameters, &[]),
+            "AGENT=linux%20%26%26%20docker&ENV=prod&TARGET_NODES=deploy-1%2C%20deploy-2"
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Rebuilding builds with their parameters and replaying Pipeline builds.
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::JenkinsAdapter;
+use crate::integrations::IntegrationError;
+
+/// Action of builds started with parameters.
+const PARAMETERS_ACTION: &str = "hudson.model.ParametersAction";
+
+/// Action of Pipeline builds that can be replayed.
+const REPLAY_ACTION: &str = "org.jenkinsci.plugins.workflow.cps.replay.ReplayAction";
+
+/// Parameter values whose value Jenkins doesn't return.
+const HIDDEN_PARAMETER_CLASSES: [&str; 2] = [
+    "hudson.model.PasswordParameterValue",
+    "hudson.model.FileParameterValue",
+];
+
+impl JenkinsAdapter {
+    /// Triggers a job again with the parameters of one of its builds.
+    /// Returns the parameters used.
+    ///
+    /// Fails when the build has password or file parameters, which Jenkins
+    /// doesn't expose and therefore can't be passed on unchanged.
+    pub async fn rebuild_build(
+        &self,
+        job_name: &str,
+        build_number: u32,
+    ) -> Result<HashMap<String, String>, IntegrationError> {
+        let actions = self.build_actions(job_name, build_number).await?;
+        let parameters =
+            parse_parameters(&actions).map_err(|hidden| IntegrationError::ConfigError {
+                message: format!(
+                    "Build #{} of {} has parameters whose values Jenkins doesn't return ({}); \
+                     trigger it with parameters instead",
+                    build_number,
+                    job_name,
+                    hidden.join(", ")
+                ),
+            })?;
+
+        log::info!(
+            "Rebuilding {} #{} with {} parameter(s)",
+            job_name,
+            build_number,
+            parameters.len()
+        );
+        self.trigger_build(job_name, Some(parameters.clone()))
+            .await?;
+        Ok(parameters)
+    }
+
+    /// Replays a Pipeline build, with its original script or with `script`
+    /// in place of its main script.
+    pub async fn replay_pipeline(
+        &self,
+        job_name: &str,
+        build_number: u32,
+        script: Option<&str>,
+    ) -> Result<(), IntegrationError> {
+        let actions = self.build_actions(job_name, build_number).await?;
+        if !has_action(&actions, REPLAY_ACTION) {
+            return Err(IntegrationError::ConfigError {
+                message: format!(
+                    "Build #{} of {} can't be replayed (not a Pipeline build, or replay is \
+                     not permitted for this user)",
+                    build_number, job_name
+                ),
+            });
+        }
+
+        let endpoint = format!(
+            "/job/{}/{}/replay",
+            urlencoding::encode(job_name),
+            build_number
+        );
+        match script {
+            None => self.post(&format!("{}/rebuild", endpoint)).await,
+            Some(script) => {
+                let json = serde_json::json!({ "mainScript": script }).to_string();
+                self.post_form(
+                    &format!("{}/run", endpoint),
+                    &[("mainScript", script), ("json", &json)],
+                )
+                .await
+            }
+        }
+    }
+
+    /// Fetches the actions of a build with their parameters.
+    async fn build_actions(
+        &self,
+        job_name: &str,
+        build_number: u32,
+    ) -> Result<Vec<Value>, IntegrationError> {
+        let endpoint = format!(
+            "/job/{}/{}/api/json?tree=actions[_class,parameters[_class,name,value,label,labels]]",
+            urlencoding::encode(job_name),
+            build_number
+        );
+        let response: Value = self.get(&endpoint).await?;
+        Ok(response
+            .get("actions")
+            .and_then(|a| a.as_array())
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+fn has_action(actions: &[Value], class: &str) -> bool {
+    actions
+        .iter()
+        .any(|a| a.get("_class").and_then(|c| c.as_str()) == Some(class))
+}
+
+/// Reads the parameter values of a build, or the names of the parameters
+/// whose values are hidden.
+fn parse_parameters(actions: &[Value]) -> Result<HashMap<String, String>, Vec<String>> {
+    let values = actions
+        .iter()
+        .filter(|a| a.get("_class").and_then(|c| c.as_str()) == Some(PARAMETERS_ACTION))
+        .filter_map(|a| a.get("parameters").and_then(|p| p.as_array()))
+        .flatten();
+
+    let mut parameters = HashMap::new();
+    let mut hidden = Vec::new();
+    for value in values {
+        let Some(name) = value.get("name").and_then(|n| n.as_str()) else {
+            continue;
+        };
+        let class = value.get("_class").and_then(|c| c.as_str()).unwrap_or("");
+        if HIDDEN_PARAMETER_CLASSES.contains(&class) {
+            hidden.push(name.to_string());
+            continue;
+        }
+        // Label and node parameters report their selection as `label`/`labels`
+        let text = match value.get("value") {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Bool(b)) => Some(b.to_string()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        }
+        .or_else(|| Some(value.get("label")?.as_str()?.to_string()))
+        .or_else(|| {
+            let labels: Vec<&str> = value
+                .get("labels")?
+                .as_array()?
+                .iter()
+                .filter_map(|l| l.as_str())
+                .collect();
+            Some(labels.join(","))
+        })
+        .unwrap_or_default();
+        parameters.insert(name.to_string(), text);
+    }
+
+    if hidden.is_empty() {
+        Ok(parameters)
+    } else {
+        Err(hidden)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_parameters() {
+        let actions = [
+            json!({ "_class": "hudson.model.CauseAction" }),
+            json!({
+                "_class": "hudson.model.ParametersAction",
+                "parameters": [
+                    { "_class": "hudson.model.StringParameterValue", "name": "VERSION", "value": "1.4.2" },
+                    { "_class": "hudson.model.BooleanParameterValue", "name": "DRY_RUN", "value": false },
+                    { "_class": "org.jvnet.jenkins.plugins.nodelabelparameter.LabelParameterValue", "name": "AGENT", "label": "linux && docker" },
+                    { "_class": "org.jvnet.jenkins.plugins.nodelabelparameter.NodeParameterValue", "name": "TARGETS", "labels": ["deploy-1", "deploy-2"] }
+                ]
+            }),
+            json!({ "_class": REPLAY_ACTION }),
+        ];
+
+        let parameters = parse_parameters(&actions).unwrap();
+        assert_eq!(
+            parameters,
+            HashMap::from([
+                ("VERSION".to_string(), "1.4.2".to_string()),
+                ("DRY_RUN".to_string(), "false".to_string()),
+                ("AGENT".to_string(), "linux && docker".to_string()),
+                ("TARGETS".to_string(), "deploy-1,deploy-2".to_string()),
+            ])
+        );
+        assert!(has_action(&actions, REPLAY_ACTION));
+    }
+
+    #[test]
+    fn test_hidden_parameters() {
+        let actions = [json!({
+            "_class": "hudson.model.ParametersAction",
+            "parameters": [
+                { "_class": "hudson.model.StringParameterValue", "name": "VERSION", "value": "1.4.2" },
+                { "_class": "hudson.model.PasswordParameterValue", "name": "DEPLOY_TOKEN" }
+            ]
+        })];
+
+        assert_eq!(parse_parameters(&actions).unwrap_err(), ["DEPLOY_TOKEN"]);
+        assert!(!has_action(&actions, REPLAY_ACTION));
+    }
+}
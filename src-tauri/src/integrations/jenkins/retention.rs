@@ -0,0 +1,260 @@
+//! Build retention insight.
+//!
+//! Combines a job's build discarder (from `config.xml`) with the builds the
+//! controller still stores, so jobs that keep more history than intended, or
+//! have no discarder at all, stand out. Disk usage is only known when the
+//! Disk Usage plugin exports it on the job.
+
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::job_config::{parse_xml, Element};
+use super::types::{JenkinsBuildDiscarder, JenkinsRetentionInfo};
+use super::JenkinsAdapter;
+use crate::integrations::IntegrationError;
+use crate::types::Timestamp;
+
+/// Job as returned by the retention query.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRetentionJob {
+    #[serde(default)]
+    all_builds: Vec<RawStoredBuild>,
+    #[serde(default)]
+    actions: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawStoredBuild {
+    timestamp: i64,
+    #[serde(default)]
+    keep_log: bool,
+}
+
+impl JenkinsAdapter {
+    /// Fetches the build discarder and stored builds of a job.
+    pub async fn fetch_retention_info(
+        &self,
+        job_name: &str,
+    ) -> Result<JenkinsRetentionInfo, IntegrationError> {
+        let encoded_path = job_name
+            .trim_matches('/')
+            .split('/')
+            .map(|segment| urlencoding::encode(segment))
+            .collect::<Vec<_>>()
+            .join("/job/");
+
+        let job: RawRetentionJob = self
+            .get(&format!(
+                "/job/{}/api/json?tree=allBuilds[timestamp,keepLog],actions[diskUsage]",
+                encoded_path
+            ))
+            .await?;
+
+        // Reading config.xml needs more than read access, so the build counts
+        // are still worth returning without it
+        let mut config_warning = None;
+        let discarder = match self
+            .get_text(&format!("/job/{}/config.xml", encoded_path))
+            .await
+        {
+            Ok(xml) => match parse_xml(&xml) {
+                Ok(root) => build_discarder(&root),
+                Err(e) => {
+                    config_warning = Some(format!("config.xml could not be parsed: {}", e));
+                    None
+                }
+            },
+            Err(e) => {
+                config_warning = Some(format!("config.xml could not be read: {}", e));
+                None
+            }
+        };
+
+        let mut info = summarize(job_name, discarder, &job, Utc::now().timestamp_millis());
+        if let Some(warning) = config_warning {
+            info.warnings.insert(0, warning);
+        }
+        Ok(info)
+    }
+}
+
+/// Reads the log rotator of a job, configured either as the strategy of a
+/// `BuildDiscarderProperty` or, on older jobs, as a top-level `logRotator`.
+fn build_discarder(root: &Element) -> Option<JenkinsBuildDiscarder> {
+    let mut rotator = root.child("logRotator");
+    root.walk(&mut |element, parent| {
+        if rotator.is_none()
+            && element.name == "strategy"
+            && parent.name.ends_with("BuildDiscarderProperty")
+        {
+            rotator = Some(element);
+        }
+    });
+
+    let rotator = rotator?;
+
+    // Jenkins writes -1 for limits left empty
+    let limit = |name: &str| {
+        rotator
+            .child_text(name)?
+            .parse::<i64>()
+            .ok()
+            .filter(|n| *n >= 0)
+            .map(|n| n.min(u32::MAX as i64) as u32)
+    };
+    Some(JenkinsBuildDiscarder {
+        days_to_keep: limit("daysToKeep"),
+        num_to_keep: limit("numToKeep"),
+        artifact_days_to_keep: limit("artifactDaysToKeep"),
+        artifact_num_to_keep: limit("artifactNumToKeep"),
+    })
+}
+
+/// Disk usage in bytes reported by a job action, if any.
+fn disk_usage(actions: &[Value]) -> Option<f64> {
+    actions
+        .iter()
+        .find_map(|action| action["diskUsage"].as_f64())
+}
+
+fn summarize(
+    job_name: &str,
+    discarder: Option<JenkinsBuildDiscarder>,
+    job: &RawRetentionJob,
+    now_millis: i64,
+) -> JenkinsRetentionInfo {
+    let builds = &job.all_builds;
+    let kept_forever = builds.iter().filter(|b| b.keep_log).count() as u32;
+    let oldest = builds.iter().map(|b| b.timestamp).min();
+    let newest = builds.iter().map(|b| b.timestamp).max();
+
+    let mut warnings = Vec::new();
+    match &discarder {
+        None => warnings.push("No build discarder is configured".to_string()),
+        Some(discarder) => {
+            if discarder.days_to_keep.is_none() && discarder.num_to_keep.is_none() {
+                warnings.push("The build discarder keeps builds without limit".to_string());
+            }
+            let rotated = builds.len() as u32 - kept_forever;
+            if let Some(limit) = discarder.num_to_keep.filter(|limit| rotated > *limit) {
+                warnings.push(format!(
+                    "{} builds are stored, more than the {} the discarder keeps",
+                    rotated, limit
+                ));
+            }
+            let oldest_rotated = builds
+                .iter()
+                .filter(|b| !b.keep_log)
+                .map(|b| b.timestamp)
+                .min();
+            if let (Some(days), Some(oldest)) = (discarder.days_to_keep, oldest_rotated) {
+                let age = Duration::milliseconds(now_millis - oldest);
+                if age > Duration::days(days as i64) {
+                    warnings.push(format!(
+                        "The oldest build is {} days old, older than the {} days the discarder keeps",
+                        age.num_days(),
+                        days
+                    ));
+                }
+            }
+        }
+    }
+
+    JenkinsRetentionInfo {
+        job_name: job_name.to_string(),
+        discarder,
+        stored_builds: builds.len() as u32,
+        kept_forever,
+        oldest_build_at: oldest.and_then(Timestamp::from_epoch_millis),
+        newest_build_at: newest.and_then(Timestamp::from_epoch_millis),
+        disk_usage_bytes: disk_usage(&job.actions),
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const DAY: i64 = 24 * 60 * 60 * 1000;
+
+    #[test]
+    fn test_build_discarder() {
+        let pipeline = parse_xml(
+            r#"<?xml version='1.1' encoding='UTF-8'?>
+<flow-definition plugin="workflow-job">
+  <properties>
+    <jenkins.model.BuildDiscarderProperty>
+      <strategy class="hudson.tasks.LogRotator">
+        <daysToKeep>30</daysToKeep>
+        <numToKeep>-1</numToKeep>
+        <artifactDaysToKeep>-1</artifactDaysToKeep>
+        <artifactNumToKeep>5</artifactNumToKeep>
+      </strategy>
+    </jenkins.model.BuildDiscarderProperty>
+  </properties>
+</flow-definition>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            build_discarder(&pipeline),
+            Some(JenkinsBuildDiscarder {
+                days_to_keep: Some(30),
+                num_to_keep: None,
+                artifact_days_to_keep: None,
+                artifact_num_to_keep: Some(5),
+            })
+        );
+
+        let legacy =
+            parse_xml("<project><logRotator><numToKeep>10</numToKeep></logRotator></project>")
+                .unwrap();
+        assert_eq!(build_discarder(&legacy).unwrap().num_to_keep, Some(10));
+
+        let none = parse_xml("<project><properties/></project>").unwrap();
+        assert_eq!(build_discarder(&none), None);
+    }
+
+    #[test]
+    fn test_summarize() {
+        let now = 100 * DAY;
+        let job: RawRetentionJob = serde_json::from_value(json!({
+            "allBuilds": [
+                { "timestamp": now - DAY, "keepLog": false },
+                { "timestamp": now - 2 * DAY, "keepLog": false },
+                { "timestamp": now - 40 * DAY, "keepLog": false },
+                { "timestamp": now - 90 * DAY, "keepLog": true }
+            ],
+            "actions": [{}, { "diskUsage": 1048576 }]
+        }))
+        .unwrap();
+        let discarder = JenkinsBuildDiscarder {
+            days_to_keep: Some(30),
+            num_to_keep: Some(2),
+            artifact_days_to_keep: None,
+            artifact_num_to_keep: None,
+        };
+
+        let info = summarize("app", Some(discarder), &job, now);
+        assert_eq!(info.stored_builds, 4);
+        assert_eq!(info.kept_forever, 1);
+        assert_eq!(info.disk_usage_bytes, Some(1048576.0));
+        assert_eq!(
+            info.oldest_build_at,
+            Timestamp::from_epoch_millis(now - 90 * DAY)
+        );
+        // The build kept forever counts toward neither limit
+        assert_eq!(info.warnings.len(), 2);
+        assert!(info.warnings[0].contains("3 builds"));
+        assert!(info.warnings[1].contains("40 days old"));
+
+        let info = summarize("app", None, &RawRetentionJob::default(), now);
+        assert_eq!(info.stored_builds, 0);
+        assert_eq!(info.disk_usage_bytes, None);
+        assert_eq!(info.warnings, ["No build discarder is configured"]);
+    }
+}
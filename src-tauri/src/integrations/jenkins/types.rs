@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
+use crate::types::Timestamp;
+
 /// Jenkins job representation.
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
 pub struct JenkinsJob {
@@ -34,10 +36,131 @@ pub struct JenkinsBuild {
     pub number: u32,
     /// Build status
     pub status: JenkinsBuildStatus,
-    /// Build timestamp (Unix timestamp in milliseconds, as string to avoid i64 BigInt issues)
-    pub timestamp: String,
+    /// Build start time
+    pub timestamp: Timestamp,
     /// Build URL
     pub url: String,
     /// Build duration in milliseconds (None if still building, as string to avoid i64 BigInt issues)
     pub duration: Option<String>,
 }
+
+/// Jenkins credential metadata (never includes the secret).
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct JenkinsCredentialMetadata {
+    /// Credential ID referenced by jobs (e.g., "registry-push")
+    pub id: String,
+    /// Credential type (e.g., "Username with password", "Secret text")
+    pub type_name: String,
+    /// Display name (secrets masked by Jenkins)
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    /// Store the credential lives in: "system" or the folder path
+    pub store: String,
+    /// Credentials domain within the store ("global" for the unrestricted domain)
+    pub domain: String,
+}
+
+/// Kind of a job parameter.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JenkinsParameterKind {
+    String,
+    Text,
+    Boolean,
+    Choice,
+    Password,
+    /// Label expression selecting the agents to build on (Node and Label Parameter plugin)
+    Label,
+    /// Agent name(s) to build on (Node and Label Parameter plugin)
+    Node,
+    Other,
+}
+
+/// Parameter definition of a parameterized job.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct JenkinsParameterDefinition {
+    pub name: String,
+    pub kind: JenkinsParameterKind,
+    /// Definition type reported by Jenkins (e.g., "LabelParameterDefinition")
+    pub type_name: String,
+    pub description: Option<String>,
+    pub default_value: Option<String>,
+    /// Allowed values: the choices of a choice parameter, the agents a node
+    /// parameter allows, or the agent labels known to Jenkins for a label parameter
+    pub choices: Vec<String>,
+    /// Node parameters only: several agents may be selected (comma-separated
+    /// when triggering), starting one build per agent
+    pub multiple: bool,
+}
+
+/// Build trigger configured on a job.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct JenkinsJobTrigger {
+    /// Trigger type (e.g., "TimerTrigger", "SCMTrigger", "GitLabPushTrigger")
+    pub kind: String,
+    /// Cron schedule of timer and SCM polling triggers
+    pub spec: Option<String>,
+}
+
+/// Parameter declared in a job's configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct JenkinsJobConfigParameter {
+    pub name: String,
+    /// Definition type (e.g., "StringParameterDefinition")
+    pub type_name: String,
+    pub default_value: Option<String>,
+}
+
+/// Selected fields of a job's `config.xml`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct JenkinsJobConfigSummary {
+    /// Full job name (e.g., "team-a/deploy-api")
+    pub job_name: String,
+    /// Root element of the configuration (e.g., "flow-definition" for
+    /// pipelines, "project" for freestyle jobs)
+    pub job_type: String,
+    pub description: Option<String>,
+    pub disabled: bool,
+    /// Git remote URLs the job checks out
+    pub scm_urls: Vec<String>,
+    /// Branch specifiers (e.g., "*/main")
+    pub branches: Vec<String>,
+    /// Jenkinsfile path of pipelines loaded from SCM
+    pub script_path: Option<String>,
+    pub triggers: Vec<JenkinsJobTrigger>,
+    pub parameters: Vec<JenkinsJobConfigParameter>,
+    /// Label expression or agent the job is restricted to
+    pub assigned_node: Option<String>,
+    /// IDs of the credentials the configuration references (system or folder
+    /// stores)
+    pub credential_ids: Vec<String>,
+}
+
+/// Build discarder (log rotation) settings of a job. Unset limits keep
+/// builds or artifacts regardless of that limit.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct JenkinsBuildDiscarder {
+    pub days_to_keep: Option<u32>,
+    pub num_to_keep: Option<u32>,
+    pub artifact_days_to_keep: Option<u32>,
+    pub artifact_num_to_keep: Option<u32>,
+}
+
+/// Build retention of a job: its discarder settings and the builds it stores.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct JenkinsRetentionInfo {
+    pub job_name: String,
+    /// Discarder settings (None when none is configured or `config.xml`
+    /// couldn't be read)
+    pub discarder: Option<JenkinsBuildDiscarder>,
+    /// Builds stored on the controller
+    pub stored_builds: u32,
+    /// Builds marked "keep forever", which discarders skip
+    pub kept_forever: u32,
+    pub oldest_build_at: Option<Timestamp>,
+    pub newest_build_at: Option<Timestamp>,
+    /// Disk used by the job in bytes, when a disk usage plugin reports it
+    pub disk_usage_bytes: Option<f64>,
+    /// Storage concerns (e.g., no discarder, more builds than it keeps)
+    pub warnings: Vec<String>,
+}
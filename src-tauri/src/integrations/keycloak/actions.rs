@@ -0,0 +1,148 @@
+//! Required-actions emails.
+//!
+//! Keycloak emails a user a link to complete required actions (reset the
+//! password, verify the email address, ...), the same as "Credential Reset"
+//! in the admin console. The link expires after the given lifespan.
+
+use serde::Deserialize;
+use serde_json::json;
+
+use super::types::{KeycloakActionsEmail, KeycloakRequiredAction};
+use super::KeycloakAdapter;
+use crate::integrations::IntegrationError;
+
+/// Hours an emailed link stays valid by default (Keycloak's own default).
+pub const DEFAULT_ACTIONS_LIFESPAN_HOURS: u32 = 12;
+
+/// Longest lifespan accepted for an emailed link (30 days).
+const MAX_ACTIONS_LIFESPAN_HOURS: u32 = 720;
+
+/// User as returned by an exact username search.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UserRef {
+    id: String,
+    email: Option<String>,
+    #[serde(default)]
+    enabled: bool,
+}
+
+impl KeycloakAdapter {
+    /// Emails a user a link to complete `actions`.
+    pub async fn send_actions_email(
+        &self,
+        realm: &str,
+        username: &str,
+        actions: &[KeycloakRequiredAction],
+        lifespan_hours: u32,
+    ) -> Result<KeycloakActionsEmail, IntegrationError> {
+        if actions.is_empty() {
+            return Err(IntegrationError::ConfigError {
+                message: "No required action selected".to_string(),
+            });
+        }
+        let lifespan_seconds = lifespan_seconds(lifespan_hours)?;
+
+        let users: Vec<UserRef> = self
+            .get(&format!(
+                "/admin/realms/{}/users?username={}&exact=true",
+                urlencoding::encode(realm),
+                urlencoding::encode(username)
+            ))
+            .await?;
+        let user = users.into_iter().next().ok_or(IntegrationError::NotFound)?;
+        let email = recipient(username, &user)?;
+
+        self.put(
+            &format!(
+                "/admin/realms/{}/users/{}/execute-actions-email?lifespan={}",
+                urlencoding::encode(realm),
+                urlencoding::encode(&user.id),
+                lifespan_seconds
+            ),
+            &json!(actions),
+        )
+        .await?;
+
+        Ok(KeycloakActionsEmail {
+            realm: realm.to_string(),
+            username: username.to_string(),
+            email,
+            actions: actions.to_vec(),
+            lifespan_hours,
+        })
+    }
+}
+
+/// Validates a link lifespan and converts it to seconds.
+fn lifespan_seconds(lifespan_hours: u32) -> Result<u32, IntegrationError> {
+    if !(1..=MAX_ACTIONS_LIFESPAN_HOURS).contains(&lifespan_hours) {
+        return Err(IntegrationError::ConfigError {
+            message: format!(
+                "The link lifespan must be between 1 and {MAX_ACTIONS_LIFESPAN_HOURS} hours"
+            ),
+        });
+    }
+    Ok(lifespan_hours * 3600)
+}
+
+/// Address the email goes to; Keycloak rejects users without one and
+/// disabled users, with a less helpful message.
+fn recipient(username: &str, user: &UserRef) -> Result<String, IntegrationError> {
+    if !user.enabled {
+        return Err(IntegrationError::ConfigError {
+            message: format!("User {} is disabled", username),
+        });
+    }
+    user.email
+        .clone()
+        .filter(|email| !email.is_empty())
+        .ok_or_else(|| IntegrationError::ConfigError {
+            message: format!("User {} has no email address", username),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(email: Option<&str>, enabled: bool) -> UserRef {
+        UserRef {
+            id: "1".to_string(),
+            email: email.map(str::to_string),
+            enabled,
+        }
+    }
+
+    #[test]
+    fn test_recipient() {
+        assert_eq!(
+            recipient("alice", &user(Some("alice@example.com"), true)).unwrap(),
+            "alice@example.com"
+        );
+        assert!(recipient("alice", &user(Some("alice@example.com"), false)).is_err());
+        assert!(recipient("alice", &user(None, true)).is_err());
+        assert!(recipient("alice", &user(Some(""), true)).is_err());
+    }
+
+    #[test]
+    fn test_lifespan_seconds() {
+        assert_eq!(lifespan_seconds(12).unwrap(), 43_200);
+        assert_eq!(lifespan_seconds(720).unwrap(), 2_592_000);
+        assert!(lifespan_seconds(0).is_err());
+        assert!(lifespan_seconds(721).is_err());
+        assert!(lifespan_seconds(u32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_actions_serialize_as_keycloak_names() {
+        assert_eq!(
+            json!([
+                KeycloakRequiredAction::UpdatePassword,
+                KeycloakRequiredAction::VerifyEmail,
+                KeycloakRequiredAction::ConfigureTotp,
+            ]),
+            json!(["UPDATE_PASSWORD", "VERIFY_EMAIL", "CONFIGURE_TOTP"])
+        );
+    }
+}
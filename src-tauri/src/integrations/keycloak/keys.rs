@@ -0,0 +1,261 @@
+//! Realm signing and encryption keys.
+//!
+//! Reports each key's provider, algorithm and age so overdue rotations can
+//! be spotted. Keycloak doesn't expose when a key was created; for keys with
+//! a certificate (RSA, EC) the certificate's start of validity is used, as
+//! the generated providers issue it together with the key.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::types::{KeycloakRealmKey, KeycloakRealmKeys};
+use super::KeycloakAdapter;
+use crate::checks::certificates::parse_certificate;
+use crate::integrations::IntegrationError;
+use crate::types::Timestamp;
+
+/// Default maximum age of an active key before rotation is overdue.
+pub const DEFAULT_MAX_KEY_AGE_DAYS: u32 = 365;
+
+/// Response of `GET /admin/realms/{realm}/keys`.
+#[derive(Debug, Deserialize)]
+struct KeysMetadata {
+    /// Algorithm to key ID of the keys currently used by default
+    #[serde(default)]
+    active: HashMap<String, String>,
+    #[serde(default)]
+    keys: Vec<KeyMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyMetadata {
+    kid: String,
+    #[serde(default)]
+    algorithm: Option<String>,
+    #[serde(rename = "type", default)]
+    key_type: Option<String>,
+    #[serde(rename = "use", default)]
+    key_use: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+    /// ID of the key provider component
+    #[serde(default)]
+    provider_id: Option<String>,
+    #[serde(default)]
+    provider_priority: Option<i32>,
+    /// Base64 DER certificate
+    #[serde(default)]
+    certificate: Option<String>,
+    /// Expiry in milliseconds since the epoch (newer Keycloak versions)
+    #[serde(default)]
+    valid_to: Option<i64>,
+}
+
+/// A key provider component of the realm.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyProviderComponent {
+    id: String,
+    name: String,
+    /// Provider type (e.g., "rsa-generated", "hmac-generated")
+    provider_id: String,
+}
+
+impl KeycloakAdapter {
+    /// Fetches a realm's keys with their providers and ages.
+    ///
+    /// # Arguments
+    /// * `max_key_age_days` - Age after which an active key is reported as
+    ///   overdue for rotation
+    pub async fn fetch_realm_keys(
+        &self,
+        realm: &str,
+        max_key_age_days: u32,
+    ) -> Result<KeycloakRealmKeys, IntegrationError> {
+        let realm_path = format!("/admin/realms/{}", urlencoding::encode(realm));
+        let metadata: KeysMetadata = self.get(&format!("{}/keys", realm_path)).await?;
+
+        // Provider names are only cosmetic, so a failure here isn't fatal
+        let components: Vec<KeyProviderComponent> = match self
+            .get(&format!(
+                "{}/components?type=org.keycloak.keys.KeyProvider",
+                realm_path
+            ))
+            .await
+        {
+            Ok(components) => components,
+            Err(e) => {
+                log::warn!("Failed to fetch key providers of realm {}: {}", realm, e);
+                Vec::new()
+            }
+        };
+
+        Ok(build_report(
+            realm,
+            metadata,
+            &components,
+            max_key_age_days,
+            Utc::now(),
+        ))
+    }
+}
+
+/// Combines key metadata and provider components into a report.
+fn build_report(
+    realm: &str,
+    metadata: KeysMetadata,
+    components: &[KeyProviderComponent],
+    max_key_age_days: u32,
+    now: DateTime<Utc>,
+) -> KeycloakRealmKeys {
+    let mut keys: Vec<KeycloakRealmKey> = metadata
+        .keys
+        .into_iter()
+        .map(|key| {
+            let certificate = key
+                .certificate
+                .as_deref()
+                .and_then(|c| STANDARD.decode(c.trim()).ok())
+                .and_then(|der| parse_certificate(&der).ok());
+            let created_at = certificate
+                .as_ref()
+                .map(|c| Timestamp::from_datetime(c.not_before));
+            let expires_at = key
+                .valid_to
+                .and_then(Timestamp::from_epoch_millis)
+                .or(certificate
+                    .as_ref()
+                    .map(|c| Timestamp::from_datetime(c.not_after)));
+            let age_days = created_at.as_ref().map(|t| t.age_days(now));
+
+            let status = key.status.unwrap_or_else(|| "ACTIVE".to_string());
+            let rotation_overdue = status == "ACTIVE"
+                && age_days.is_some_and(|age| i64::from(age) > i64::from(max_key_age_days));
+            let component = key
+                .provider_id
+                .as_ref()
+                .and_then(|id| components.iter().find(|c| &c.id == id));
+
+            KeycloakRealmKey {
+                in_use: metadata.active.values().any(|kid| kid == &key.kid),
+                kid: key.kid,
+                algorithm: key.algorithm,
+                key_type: key.key_type,
+                key_use: key.key_use,
+                status,
+                provider_name: component.map(|c| c.name.clone()),
+                provider_type: component.map(|c| c.provider_id.clone()),
+                priority: key.provider_priority,
+                created_at,
+                expires_at,
+                age_days,
+                rotation_overdue,
+            }
+        })
+        .collect();
+
+    // Keys used by default first, then by priority
+    keys.sort_by(|a, b| {
+        b.in_use
+            .cmp(&a.in_use)
+            .then_with(|| b.priority.cmp(&a.priority))
+            .then_with(|| a.kid.cmp(&b.kid))
+    });
+
+    KeycloakRealmKeys {
+        realm: realm.to_string(),
+        max_key_age_days,
+        overdue_count: keys.iter().filter(|k| k.rotation_overdue).count() as u32,
+        keys,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag, content.len() as u8];
+        out.extend_from_slice(content);
+        out
+    }
+
+    /// Minimal certificate valid from `not_before` to 2030.
+    fn certificate(not_before: &[u8]) -> String {
+        let name = tlv(0x30, &[]);
+        let validity = tlv(
+            0x30,
+            &[tlv(0x17, not_before), tlv(0x17, b"300101000000Z")].concat(),
+        );
+        let tbs = [
+            tlv(0x02, &[1]),
+            tlv(0x30, &[]),
+            name.clone(),
+            validity,
+            name,
+        ]
+        .concat();
+        STANDARD.encode(tlv(0x30, &[tlv(0x30, &tbs), tlv(0x30, &[])].concat()))
+    }
+
+    #[test]
+    fn test_build_report() {
+        let metadata: KeysMetadata = serde_json::from_value(json!({
+            "active": { "RS256": "rsa-old", "HS512": "hmac" },
+            "keys": [
+                {
+                    "kid": "rsa-new", "algorithm": "RS256", "type": "RSA", "use": "SIG",
+                    "status": "PASSIVE", "providerId": "c2", "providerPriority": 50,
+                    "certificate": certificate(b"240101000000Z")
+                },
+                {
+                    "kid": "rsa-old", "algorithm": "RS256", "type": "RSA", "use": "SIG",
+                    "status": "ACTIVE", "providerId": "c1", "providerPriority": 100,
+                    "certificate": certificate(b"230101000000Z")
+                },
+                { "kid": "hmac", "algorithm": "HS512", "type": "OCT", "status": "ACTIVE" }
+            ]
+        }))
+        .unwrap();
+        let components = vec![KeyProviderComponent {
+            id: "c1".to_string(),
+            name: "rsa-generated".to_string(),
+            provider_id: "rsa-generated".to_string(),
+        }];
+        let now = DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let report = build_report("shop", metadata, &components, 365, now);
+        let summary: Vec<(&str, bool, Option<i32>, bool)> = report
+            .keys
+            .iter()
+            .map(|k| (k.kid.as_str(), k.in_use, k.age_days, k.rotation_overdue))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("rsa-old", true, Some(517), true),
+                ("hmac", true, None, false),
+                ("rsa-new", false, Some(152), false),
+            ]
+        );
+        assert_eq!(report.overdue_count, 1);
+        assert_eq!(
+            report.keys[0].provider_name.as_deref(),
+            Some("rsa-generated")
+        );
+        assert_eq!(
+            report.keys[0]
+                .expires_at
+                .as_ref()
+                .map(|t| t.rfc3339.as_str()),
+            Some("2030-01-01T00:00:00.000Z")
+        );
+    }
+}
@@ -2,16 +2,31 @@
 //!
 //! Implements the IntegrationAdapter trait for Keycloak API interactions.
 
+mod actions;
+mod keys;
 mod types;
-
-pub use types::{KeycloakClient, KeycloakRealm};
-
+mod users;
+
+pub use actions::DEFAULT_ACTIONS_LIFESPAN_HOURS;
+pub use keys::DEFAULT_MAX_KEY_AGE_DAYS;
+pub use types::{
+    KeycloakActionsEmail, KeycloakClient, KeycloakRealm, KeycloakRealmKeys, KeycloakRequiredAction,
+    KeycloakUserFileFormat, KeycloakUserImportResult,
+};
+pub use users::{export_users, parse_users};
+
+use crate::integrations::compat::ApiVersion;
+use crate::integrations::fixtures;
 use crate::integrations::{IntegrationAdapter, IntegrationError};
 use crate::types::IntegrationType;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::Value;
 
+/// First Keycloak version (Quarkus distribution) serving the API without the
+/// `/auth` context path.
+const WITHOUT_AUTH_PATH: ApiVersion = ApiVersion::new(17, 0);
+
 /// Keycloak integration adapter.
 ///
 /// Handles API calls to Keycloak instances using Basic Auth (username/password or service account token).
@@ -37,6 +52,25 @@ impl KeycloakAdapter {
         }
     }
 
+    /// Adapts requests to a pinned Keycloak version: releases before 17 serve
+    /// everything under `/auth`, which is appended to the base URL when missing.
+    pub fn with_api_version(mut self, api_version: Option<ApiVersion>) -> Self {
+        if !Self::compatibility_notes(api_version).is_empty() && !self.base_url.ends_with("/auth") {
+            self.base_url.push_str("/auth");
+        }
+        self
+    }
+
+    /// Describes how requests are adapted for a Keycloak version.
+    pub fn compatibility_notes(api_version: Option<ApiVersion>) -> Vec<String> {
+        match api_version {
+            Some(version) if version < WITHOUT_AUTH_PATH => {
+                vec!["Requests use the /auth context path (Keycloak 16 and older)".to_string()]
+            }
+            _ => Vec::new(),
+        }
+    }
+
     /// Builds the full API URL for a given endpoint.
     fn api_url(&self, endpoint: &str) -> String {
         format!("{}{}", self.base_url, endpoint)
@@ -59,10 +93,9 @@ impl KeycloakAdapter {
             .send()
             .await?;
 
-        let status = response.status();
+        let (status, body) = fixtures::capture("keycloak", &self.base_url, "GET", response).await?;
         if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            log::error!("Keycloak API error ({}): {}", status, error_text);
+            log::error!("Keycloak API error ({}): {}", status, body);
 
             // Handle permission errors gracefully (403/404 for admin endpoints)
             if status == 403 || status == 404 {
@@ -77,11 +110,11 @@ impl KeycloakAdapter {
 
             return Err(crate::integrations::errors::status_to_error(
                 status.as_u16(),
-                Some(error_text),
+                Some(body),
             ));
         }
 
-        response.json::<T>().await.map_err(|e| {
+        serde_json::from_str::<T>(&body).map_err(|e| {
             log::error!("Failed to parse Keycloak API response: {}", e);
             IntegrationError::ConfigError {
                 message: format!("Failed to parse response: {}", e),
@@ -89,6 +122,48 @@ impl KeycloakAdapter {
         })
     }
 
+    /// Makes an authenticated POST request with a JSON body to the Keycloak API.
+    /// The response body (empty for creations) is ignored.
+    async fn post(&self, endpoint: &str, body: &Value) -> Result<(), IntegrationError> {
+        self.send(reqwest::Method::POST, endpoint, body).await
+    }
+
+    /// Makes an authenticated PUT request with a JSON body to the Keycloak API.
+    /// The response body (usually empty) is ignored.
+    async fn put(&self, endpoint: &str, body: &Value) -> Result<(), IntegrationError> {
+        self.send(reqwest::Method::PUT, endpoint, body).await
+    }
+
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        endpoint: &str,
+        body: &Value,
+    ) -> Result<(), IntegrationError> {
+        let url = self.api_url(endpoint);
+        log::debug!("Keycloak API {}: {}", method, url);
+
+        let response = self
+            .client
+            .request(method, &url)
+            .basic_auth(&self.username, Some(&self.password))
+            .json(body)
+            .timeout(std::time::Duration::from_secs(30))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            log::error!("Keycloak API error ({}): {}", status, error_text);
+            return Err(crate::integrations::errors::status_to_error(
+                status.as_u16(),
+                Some(error_text),
+            ));
+        }
+        Ok(())
+    }
+
     /// Fetches all realms from Keycloak.
     ///
     /// Note: This requires admin access. If admin access is not available,
@@ -223,6 +298,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_api_url_legacy_version() {
+        let adapter = |version| {
+            KeycloakAdapter::new(
+                "https://keycloak.example.com/".to_string(),
+                "admin".to_string(),
+                "password".to_string(),
+            )
+            .with_api_version(Some(version))
+        };
+
+        assert_eq!(
+            adapter(ApiVersion::new(15, 1)).api_url("/admin/realms"),
+            "https://keycloak.example.com/auth/admin/realms"
+        );
+        assert_eq!(
+            adapter(ApiVersion::new(24, 0)).api_url("/admin/realms"),
+            "https://keycloak.example.com/admin/realms"
+        );
+    }
+
     #[test]
     fn test_api_url_trailing_slash() {
         let adapter = KeycloakAdapter::new(
@@ -2,6 +2,9 @@
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::BTreeMap;
+
+use crate::types::Timestamp;
 
 /// Keycloak realm representation.
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
@@ -22,3 +25,112 @@ pub struct KeycloakClient {
     /// Whether the client is enabled
     pub enabled: bool,
 }
+
+/// A realm key with its provider and age.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct KeycloakRealmKey {
+    /// Key ID
+    pub kid: String,
+    /// Algorithm (e.g., "RS256", "HS512")
+    pub algorithm: Option<String>,
+    /// Key type (e.g., "RSA", "EC", "OCT")
+    pub key_type: Option<String>,
+    /// "SIG" or "ENC"
+    pub key_use: Option<String>,
+    /// "ACTIVE", "PASSIVE" or "DISABLED"
+    pub status: String,
+    /// Whether the realm uses this key by default for its algorithm
+    pub in_use: bool,
+    /// Name of the key provider component
+    pub provider_name: Option<String>,
+    /// Key provider type (e.g., "rsa-generated")
+    pub provider_type: Option<String>,
+    /// Provider priority (highest is preferred)
+    pub priority: Option<i32>,
+    /// Creation time from the key certificate
+    pub created_at: Option<Timestamp>,
+    pub expires_at: Option<Timestamp>,
+    /// Whole days since creation, when known
+    pub age_days: Option<i32>,
+    /// Whether the key is active and older than the allowed age
+    pub rotation_overdue: bool,
+}
+
+/// Key rotation status of a realm.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct KeycloakRealmKeys {
+    pub realm: String,
+    /// Age after which an active key is overdue for rotation
+    pub max_key_age_days: u32,
+    pub keys: Vec<KeycloakRealmKey>,
+    /// Number of keys overdue for rotation
+    pub overdue_count: u32,
+}
+
+/// Realm user as exported and imported.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct KeycloakUser {
+    pub username: String,
+    pub email: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub enabled: bool,
+    pub email_verified: bool,
+    /// Custom user attributes (each may hold several values)
+    pub attributes: BTreeMap<String, Vec<String>>,
+}
+
+/// File format of exported and imported users.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum KeycloakUserFileFormat {
+    /// Array of user objects
+    Json,
+    /// Header row of field and attribute names, one user per line
+    Csv,
+}
+
+/// User that could not be imported.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct KeycloakUserImportFailure {
+    /// Username, or the record number when the record has none
+    pub username: String,
+    pub error: String,
+}
+
+/// Outcome of a user import.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct KeycloakUserImportResult {
+    pub realm: String,
+    /// Whether users were only checked, not created
+    pub dry_run: bool,
+    /// Users created (or that would be created in a dry run)
+    pub created: Vec<String>,
+    /// Users skipped because the realm already has them
+    pub existing: Vec<String>,
+    pub failed: Vec<KeycloakUserImportFailure>,
+}
+
+/// Required action a user is asked to complete by email.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum KeycloakRequiredAction {
+    /// Reset the password
+    UpdatePassword,
+    VerifyEmail,
+    /// Set up an authenticator app
+    ConfigureTotp,
+    UpdateProfile,
+}
+
+/// Required-actions email sent to a user.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct KeycloakActionsEmail {
+    pub realm: String,
+    pub username: String,
+    /// Address the email was sent to
+    pub email: String,
+    pub actions: Vec<KeycloakRequiredAction>,
+    /// Hours the link in the email stays valid
+    pub lifespan_hours: u32,
+}
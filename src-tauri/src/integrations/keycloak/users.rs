@@ -0,0 +1,534 @@
+//! Bulk user export and import.
+//!
+//! Users are exported as JSON or CSV and imported from either, e.g. to seed
+//! a test realm. Imported fields are matched to user fields by name
+//! (`username`, `email`, `firstName`, `lastName`, `enabled`, `emailVerified`,
+//! in camelCase or snake_case); every other field becomes a user attribute.
+//! An attribute mapping renames source fields first (an empty target drops
+//! the field), so files from other systems can be imported unchanged. In
+//! CSV, multiple values of an attribute are separated by `|`.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use super::types::{
+    KeycloakUser, KeycloakUserFileFormat, KeycloakUserImportFailure, KeycloakUserImportResult,
+};
+use super::KeycloakAdapter;
+use crate::integrations::IntegrationError;
+
+/// Users requested per page.
+const PAGE_SIZE: u32 = 100;
+
+/// Separator of multiple values in a CSV cell.
+const VALUE_SEPARATOR: char = '|';
+
+/// User fields, in CSV column order.
+const FIELDS: [&str; 6] = [
+    "username",
+    "email",
+    "firstName",
+    "lastName",
+    "enabled",
+    "emailVerified",
+];
+
+/// Fields of Keycloak's user representation (e.g., in realm exports) that
+/// are not imported unless mapped.
+const IGNORED_FIELDS: [&str; 13] = [
+    "id",
+    "createdTimestamp",
+    "totp",
+    "credentials",
+    "disableableCredentialTypes",
+    "requiredActions",
+    "realmRoles",
+    "clientRoles",
+    "groups",
+    "notBefore",
+    "access",
+    "federationLink",
+    "serviceAccountClientId",
+];
+
+/// Field names of a record with their values.
+type Record = BTreeMap<String, Vec<String>>;
+
+/// User as returned by `GET /admin/realms/:realm/users`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawUser {
+    username: String,
+    email: Option<String>,
+    first_name: Option<String>,
+    last_name: Option<String>,
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    email_verified: bool,
+    #[serde(default)]
+    attributes: BTreeMap<String, Vec<String>>,
+}
+
+impl From<RawUser> for KeycloakUser {
+    fn from(raw: RawUser) -> Self {
+        Self {
+            username: raw.username,
+            email: raw.email,
+            first_name: raw.first_name,
+            last_name: raw.last_name,
+            enabled: raw.enabled,
+            email_verified: raw.email_verified,
+            attributes: raw.attributes,
+        }
+    }
+}
+
+impl KeycloakAdapter {
+    /// Fetches every user of a realm with their attributes.
+    pub async fn fetch_users(&self, realm: &str) -> Result<Vec<KeycloakUser>, IntegrationError> {
+        let mut users = Vec::new();
+        let mut first = 0;
+        loop {
+            let page: Vec<RawUser> = self
+                .get(&format!(
+                    "/admin/realms/{}/users?briefRepresentation=false&first={}&max={}",
+                    urlencoding::encode(realm),
+                    first,
+                    PAGE_SIZE
+                ))
+                .await?;
+            let full_page = page.len() == PAGE_SIZE as usize;
+            users.extend(page.into_iter().map(KeycloakUser::from));
+            if !full_page {
+                break;
+            }
+            first += PAGE_SIZE;
+        }
+        Ok(users)
+    }
+
+    /// Creates users in a realm, skipping those it already has. A dry run
+    /// only checks which users exist.
+    pub async fn import_users(
+        &self,
+        realm: &str,
+        users: Vec<KeycloakUser>,
+        dry_run: bool,
+    ) -> Result<KeycloakUserImportResult, IntegrationError> {
+        let endpoint = format!("/admin/realms/{}/users", urlencoding::encode(realm));
+        let mut result = KeycloakUserImportResult {
+            realm: realm.to_string(),
+            dry_run,
+            created: Vec::new(),
+            existing: Vec::new(),
+            failed: Vec::new(),
+        };
+
+        let mut seen = HashSet::new();
+        for user in users {
+            // Keycloak stores usernames in lowercase
+            if !seen.insert(user.username.to_lowercase()) {
+                result.failed.push(KeycloakUserImportFailure {
+                    username: user.username,
+                    error: "Listed more than once".to_string(),
+                });
+                continue;
+            }
+
+            let outcome = if dry_run {
+                self.user_exists(realm, &user.username).await
+            } else {
+                match self.post(&endpoint, &user_body(&user)).await {
+                    Ok(()) => Ok(false),
+                    Err(IntegrationError::ApiError { status: 409, .. }) => Ok(true),
+                    Err(e) => Err(e),
+                }
+            };
+            match outcome {
+                Ok(false) => result.created.push(user.username),
+                Ok(true) => result.existing.push(user.username),
+                // Without admin access nothing else would succeed either
+                Err(e @ IntegrationError::AuthError { .. }) => return Err(e),
+                Err(e) => result.failed.push(KeycloakUserImportFailure {
+                    username: user.username,
+                    error: e.to_string(),
+                }),
+            }
+        }
+        Ok(result)
+    }
+
+    async fn user_exists(&self, realm: &str, username: &str) -> Result<bool, IntegrationError> {
+        let matches: Vec<Value> = self
+            .get(&format!(
+                "/admin/realms/{}/users?username={}&exact=true",
+                urlencoding::encode(realm),
+                urlencoding::encode(username)
+            ))
+            .await?;
+        Ok(!matches.is_empty())
+    }
+}
+
+fn user_body(user: &KeycloakUser) -> Value {
+    json!({
+        "username": user.username,
+        "email": user.email,
+        "firstName": user.first_name,
+        "lastName": user.last_name,
+        "enabled": user.enabled,
+        "emailVerified": user.email_verified,
+        "attributes": user.attributes,
+    })
+}
+
+/// Writes users as a JSON array or as CSV with a column per field and
+/// attribute.
+pub fn export_users(
+    users: &[KeycloakUser],
+    format: KeycloakUserFileFormat,
+) -> Result<String, String> {
+    match format {
+        KeycloakUserFileFormat::Json => serde_json::to_string_pretty(users)
+            .map_err(|e| format!("Failed to serialize users: {}", e)),
+        KeycloakUserFileFormat::Csv => Ok(users_to_csv(users)),
+    }
+}
+
+fn users_to_csv(users: &[KeycloakUser]) -> String {
+    let attributes: BTreeSet<&String> = users.iter().flat_map(|u| u.attributes.keys()).collect();
+
+    let header = FIELDS
+        .iter()
+        .copied()
+        .chain(attributes.iter().map(|a| a.as_str()))
+        .map(csv_cell)
+        .collect::<Vec<_>>()
+        .join(",");
+    let mut csv = header + "\n";
+    for user in users {
+        let optional = |value: &Option<String>| value.as_deref().unwrap_or_default().to_string();
+        let fields = [
+            user.username.clone(),
+            optional(&user.email),
+            optional(&user.first_name),
+            optional(&user.last_name),
+            user.enabled.to_string(),
+            user.email_verified.to_string(),
+        ];
+        let values = attributes.iter().map(|name| {
+            user.attributes
+                .get(*name)
+                .map(|values| values.join(&VALUE_SEPARATOR.to_string()))
+                .unwrap_or_default()
+        });
+        let row = fields
+            .into_iter()
+            .chain(values)
+            .map(|value| csv_cell(&value))
+            .collect::<Vec<_>>()
+            .join(",");
+        csv.push_str(&row);
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Reads users from a JSON or CSV file, renaming fields per
+/// `attribute_mapping` (source field to user field or attribute). Records
+/// that aren't valid users are returned as failures.
+pub fn parse_users(
+    contents: &str,
+    format: KeycloakUserFileFormat,
+    attribute_mapping: &BTreeMap<String, String>,
+) -> Result<(Vec<KeycloakUser>, Vec<KeycloakUserImportFailure>), String> {
+    let records = match format {
+        KeycloakUserFileFormat::Json => json_records(contents)?,
+        KeycloakUserFileFormat::Csv => csv_records(contents)?,
+    };
+
+    let mut users = Vec::new();
+    let mut failures = Vec::new();
+    for (index, record) in records.into_iter().enumerate() {
+        match record_to_user(record, attribute_mapping) {
+            Ok(user) => users.push(user),
+            Err(error) => failures.push(KeycloakUserImportFailure {
+                username: format!("record {}", index + 1),
+                error,
+            }),
+        }
+    }
+    Ok((users, failures))
+}
+
+/// Reads an array of user objects, or the `users` of a realm export.
+fn json_records(contents: &str) -> Result<Vec<Record>, String> {
+    let value: Value =
+        serde_json::from_str(contents).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let users = match &value {
+        Value::Array(users) => users,
+        Value::Object(realm) => match realm.get("users") {
+            Some(Value::Array(users)) => users,
+            _ => return Err("Expected an array of users or a realm export".to_string()),
+        },
+        _ => return Err("Expected an array of users or a realm export".to_string()),
+    };
+
+    // Scalars and arrays of scalars; nested objects are dropped
+    let scalars = |value: &Value| -> Vec<String> {
+        let items = match value {
+            Value::Array(items) => items.iter().collect(),
+            other => vec![other],
+        };
+        items
+            .into_iter()
+            .filter_map(|item| match item {
+                Value::String(s) => Some(s.clone()),
+                Value::Bool(_) | Value::Number(_) => Some(item.to_string()),
+                _ => None,
+            })
+            .collect()
+    };
+
+    Ok(users
+        .iter()
+        .filter_map(Value::as_object)
+        .map(|user| {
+            let mut record = Record::new();
+            for (name, value) in user {
+                match (name.as_str(), value) {
+                    ("attributes", Value::Object(attributes)) => {
+                        for (attribute, values) in attributes {
+                            record.insert(attribute.clone(), scalars(values));
+                        }
+                    }
+                    _ => {
+                        record.insert(name.clone(), scalars(value));
+                    }
+                }
+            }
+            record
+        })
+        .collect())
+}
+
+/// Reads CSV with a header row of field names.
+fn csv_records(contents: &str) -> Result<Vec<Record>, String> {
+    let mut rows = parse_csv(contents.trim_start_matches('\u{feff}'))?.into_iter();
+    let Some(header) = rows.next() else {
+        return Ok(Vec::new());
+    };
+    let header: Vec<String> = header.iter().map(|name| name.trim().to_string()).collect();
+
+    Ok(rows
+        .map(|row| {
+            header
+                .iter()
+                .zip(row)
+                .filter(|(name, _)| !name.is_empty())
+                .map(|(name, cell)| {
+                    let values = cell
+                        .split(VALUE_SEPARATOR)
+                        .filter(|v| !v.trim().is_empty())
+                        .map(str::to_string)
+                        .collect();
+                    (name.clone(), values)
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Splits CSV (RFC 4180: quoted fields, doubled quotes) into rows of cells,
+/// skipping blank lines.
+fn parse_csv(contents: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' => quoted = false,
+                _ => cell.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if cell.is_empty() => quoted = true,
+            ',' => row.push(std::mem::take(&mut cell)),
+            '\r' => {}
+            '\n' => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => cell.push(c),
+        }
+    }
+    if quoted {
+        return Err("Invalid CSV: unterminated quoted field".to_string());
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|cell| !cell.trim().is_empty()));
+    Ok(rows)
+}
+
+/// User field a name refers to, ignoring case and underscores.
+fn user_field(name: &str) -> Option<&'static str> {
+    let normalized = name.replace('_', "").to_lowercase();
+    FIELDS
+        .iter()
+        .copied()
+        .find(|field| field.to_lowercase() == normalized)
+}
+
+fn parse_bool(name: &str, value: &str) -> Result<bool, String> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "yes" | "1" => Ok(true),
+        "false" | "no" | "0" => Ok(false),
+        _ => Err(format!("Invalid {} value: {}", name, value)),
+    }
+}
+
+fn record_to_user(
+    record: Record,
+    attribute_mapping: &BTreeMap<String, String>,
+) -> Result<KeycloakUser, String> {
+    let mut user = KeycloakUser {
+        username: String::new(),
+        email: None,
+        first_name: None,
+        last_name: None,
+        enabled: true,
+        email_verified: false,
+        attributes: BTreeMap::new(),
+    };
+
+    for (name, values) in record {
+        let target = match attribute_mapping.get(&name) {
+            Some(target) => target.trim().to_string(),
+            None if IGNORED_FIELDS.contains(&name.as_str()) => continue,
+            None => name,
+        };
+        if target.is_empty() || values.is_empty() {
+            continue;
+        }
+        let value = values.join(&VALUE_SEPARATOR.to_string()).trim().to_string();
+        match user_field(&target) {
+            Some("username") => user.username = value.to_lowercase(),
+            Some("email") => user.email = Some(value),
+            Some("firstName") => user.first_name = Some(value),
+            Some("lastName") => user.last_name = Some(value),
+            Some("enabled") => user.enabled = parse_bool(&target, &value)?,
+            Some("emailVerified") => user.email_verified = parse_bool(&target, &value)?,
+            _ => user.attributes.entry(target).or_default().extend(values),
+        }
+    }
+
+    if user.username.is_empty() {
+        return Err("Missing username".to_string());
+    }
+    Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(username: &str, attributes: &[(&str, &[&str])]) -> KeycloakUser {
+        KeycloakUser {
+            username: username.to_string(),
+            email: Some(format!("{}@example.com", username)),
+            first_name: Some("Test, User".to_string()),
+            last_name: None,
+            enabled: true,
+            email_verified: false,
+            attributes: attributes
+                .iter()
+                .map(|(name, values)| {
+                    (
+                        name.to_string(),
+                        values.iter().map(|v| v.to_string()).collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let users = vec![
+            user("alice", &[("team", &["payments"]), ("roles", &["a", "b"])]),
+            user("bob", &[]),
+        ];
+        let csv = export_users(&users, KeycloakUserFileFormat::Csv).unwrap();
+        assert_eq!(
+            csv.lines().next(),
+            Some("username,email,firstName,lastName,enabled,emailVerified,roles,team")
+        );
+        assert!(csv.contains("alice,alice@example.com,\"Test, User\",,true,false,a|b,payments"));
+
+        let (parsed, failures) =
+            parse_users(&csv, KeycloakUserFileFormat::Csv, &BTreeMap::new()).unwrap();
+        assert!(failures.is_empty());
+        assert_eq!(parsed, users);
+    }
+
+    #[test]
+    fn test_parse_users_with_mapping() {
+        let csv = "login,mail,dept,password\r\nCarol,carol@example.com,ops,secret\r\n\r\n,nobody@example.com,ops,x\r\n";
+        let mapping = BTreeMap::from([
+            ("login".to_string(), "username".to_string()),
+            ("mail".to_string(), "email".to_string()),
+            ("dept".to_string(), "department".to_string()),
+            ("password".to_string(), String::new()),
+        ]);
+
+        let (users, failures) = parse_users(csv, KeycloakUserFileFormat::Csv, &mapping).unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].username, "carol");
+        assert_eq!(users[0].email.as_deref(), Some("carol@example.com"));
+        assert_eq!(
+            users[0].attributes,
+            BTreeMap::from([("department".to_string(), vec!["ops".to_string()])])
+        );
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].username, "record 2");
+    }
+
+    #[test]
+    fn test_parse_realm_export() {
+        let json = r#"{ "realm": "test", "users": [{
+            "id": "4f1c", "username": "dave", "firstName": "Dave", "enabled": false,
+            "attributes": { "locale": ["en"] },
+            "credentials": [{ "type": "password" }], "realmRoles": ["user"]
+        }] }"#;
+
+        let (users, failures) =
+            parse_users(json, KeycloakUserFileFormat::Json, &BTreeMap::new()).unwrap();
+        assert!(failures.is_empty());
+        assert_eq!(users[0].first_name.as_deref(), Some("Dave"));
+        assert!(!users[0].enabled);
+        assert_eq!(users[0].attributes.keys().collect::<Vec<_>>(), ["locale"]);
+
+        assert!(parse_csv("a,\"b").is_err());
+    }
+}
@@ -3,20 +3,26 @@
 //! Handles API calls to Kubernetes clusters using kubeconfig authentication.
 
 use crate::integrations::{IntegrationAdapter, IntegrationError};
-use crate::types::IntegrationType;
+use crate::types::{IntegrationType, Timestamp};
 use async_trait::async_trait;
+use k8s_openapi::api::apps::v1::Deployment;
 use k8s_openapi::api::core::v1::{Namespace, Pod, Service};
-use kube::{Api, Client, Config};
+use k8s_openapi::api::networking::v1::Ingress;
+use kube::api::{ListParams, Patch, PatchParams};
+use kube::{Api, Client};
 use std::path::PathBuf;
 
-use super::types::{K8sNamespace, K8sPod, K8sService, K8sServicePort};
+use super::client_cache;
+use super::types::{
+    K8sImageUpdate, K8sIngressHost, K8sNamespace, K8sPod, K8sService, K8sServicePort,
+};
 
 /// Kubernetes integration adapter.
 ///
 /// Handles API calls to Kubernetes clusters using kubeconfig file authentication.
 pub struct KubernetesAdapter {
     /// Kubernetes client
-    client: Client,
+    pub(super) client: Client,
     /// Kubeconfig path used for this adapter
     #[allow(dead_code)] // Used in get_base_url() trait method
     kubeconfig_path: PathBuf,
@@ -59,26 +65,9 @@ impl KubernetesAdapter {
             });
         }
 
-        // Load kubeconfig and create client
-        // Set KUBECONFIG environment variable temporarily for kube crate
-        std::env::set_var("KUBECONFIG", &expanded_path);
-
-        let config = Config::infer().await.map_err(|e| {
-            std::env::remove_var("KUBECONFIG");
-            IntegrationError::ConfigError {
-                message: format!("Failed to load kubeconfig: {}", e),
-            }
-        })?;
-
-        let client = Client::try_from(config).map_err(|e| {
-            std::env::remove_var("KUBECONFIG");
-            IntegrationError::ConfigError {
-                message: format!("Failed to create Kubernetes client: {}", e),
-            }
-        })?;
-
-        // Clear the environment variable after client creation
-        std::env::remove_var("KUBECONFIG");
+        // Reuses the client built from this kubeconfig unless the file (or
+        // a credential file it references) changed since
+        let client = client_cache::client_for(&expanded_path).await?;
 
         Ok(Self {
             client,
@@ -113,8 +102,7 @@ impl KubernetesAdapter {
                 .metadata
                 .creation_timestamp
                 .as_ref()
-                .map(|t| t.0.format("%+").to_string())
-                .unwrap_or_else(|| "Unknown".to_string());
+                .map(|t| Timestamp::from_datetime(t.0));
 
             result.push(K8sNamespace {
                 name,
@@ -277,6 +265,62 @@ impl KubernetesAdapter {
         Ok(result)
     }
 
+    /// Fetches the hosts routed by ingresses in a namespace.
+    ///
+    /// Hosts listed in an ingress TLS section get an `https://` URL.
+    pub async fn fetch_ingress_hosts(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<K8sIngressHost>, IntegrationError> {
+        log::debug!(
+            "Fetching Kubernetes ingress hosts in namespace: {}",
+            namespace
+        );
+
+        let api: Api<Ingress> = Api::namespaced(self.client.clone(), namespace);
+
+        let ingresses = api.list(&Default::default()).await.map_err(|e| {
+            log::error!("Failed to list ingresses in namespace {}: {}", namespace, e);
+            IntegrationError::NetworkError {
+                message: format!("Failed to list ingresses: {}", e),
+            }
+        })?;
+
+        let mut result = Vec::new();
+        for ingress in ingresses {
+            let name = ingress.metadata.name.clone().unwrap_or_default();
+            let Some(spec) = ingress.spec else {
+                continue;
+            };
+
+            let tls_hosts: Vec<String> = spec
+                .tls
+                .unwrap_or_default()
+                .into_iter()
+                .flat_map(|tls| tls.hosts.unwrap_or_default())
+                .collect();
+
+            for host in spec
+                .rules
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|rule| rule.host)
+            {
+                let tls = tls_hosts.contains(&host);
+                let scheme = if tls { "https" } else { "http" };
+                result.push(K8sIngressHost {
+                    namespace: namespace.to_string(),
+                    ingress: name.clone(),
+                    url: format!("{}://{}", scheme, host),
+                    host,
+                    tls,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Fetches detailed information for a specific pod.
     pub async fn fetch_pod_details(
         &self,
@@ -351,15 +395,151 @@ impl KubernetesAdapter {
             node,
         })
     }
+
+    /// Sets the image of one container in a deployment.
+    ///
+    /// Applies a JSON patch that first tests the container name at the patched
+    /// index, so a concurrent change to the container list fails the update
+    /// instead of changing the wrong container.
+    pub async fn set_deployment_image(
+        &self,
+        namespace: &str,
+        deployment: &str,
+        container: &str,
+        image: &str,
+    ) -> Result<K8sImageUpdate, IntegrationError> {
+        log::debug!(
+            "Setting image of {}/{} container {} to {}",
+            namespace,
+            deployment,
+            container,
+            image
+        );
+
+        let api: Api<Deployment> = Api::namespaced(self.client.clone(), namespace);
+
+        let current = api.get(deployment).await.map_err(|e| {
+            log::error!(
+                "Failed to get deployment {}/{}: {}",
+                namespace,
+                deployment,
+                e
+            );
+            if e.to_string().contains("NotFound") {
+                IntegrationError::NotFound
+            } else {
+                IntegrationError::NetworkError {
+                    message: format!("Failed to get deployment: {}", e),
+                }
+            }
+        })?;
+
+        let (patch, previous_image) = image_patch(&current, container, image)?;
+
+        api.patch(
+            deployment,
+            &PatchParams::default(),
+            &Patch::Json::<()>(patch),
+        )
+        .await
+        .map_err(|e| {
+            log::error!(
+                "Failed to patch deployment {}/{}: {}",
+                namespace,
+                deployment,
+                e
+            );
+            IntegrationError::NetworkError {
+                message: format!("Failed to patch deployment: {}", e),
+            }
+        })?;
+
+        log::info!(
+            "Updated {}/{} container {}: {:?} -> {}",
+            namespace,
+            deployment,
+            container,
+            previous_image,
+            image
+        );
+
+        Ok(K8sImageUpdate {
+            namespace: namespace.to_string(),
+            deployment: deployment.to_string(),
+            container: container.to_string(),
+            previous_image,
+            image: image.to_string(),
+        })
+    }
+}
+
+/// Builds the JSON patch replacing a container image.
+///
+/// Returns the patch and the container's current image.
+fn image_patch(
+    deployment: &Deployment,
+    container: &str,
+    image: &str,
+) -> Result<(json_patch::Patch, Option<String>), IntegrationError> {
+    let containers = deployment
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.template.spec.as_ref())
+        .map(|spec| spec.containers.as_slice())
+        .unwrap_or_default();
+
+    let index = containers
+        .iter()
+        .position(|c| c.name == container)
+        .ok_or_else(|| IntegrationError::ConfigError {
+            message: format!(
+                "Container '{}' not found (available: {})",
+                container,
+                containers
+                    .iter()
+                    .map(|c| c.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        })?;
+
+    let previous_image = containers[index].image.clone();
+    let path = format!("/spec/template/spec/containers/{}", index);
+    let patch = serde_json::json!([
+        { "op": "test", "path": format!("{}/name", path), "value": container },
+        { "op": "replace", "path": format!("{}/image", path), "value": image },
+    ]);
+
+    let patch = serde_json::from_value(patch).map_err(|e| IntegrationError::ConfigError {
+        message: format!("Failed to build image patch: {}", e),
+    })?;
+    Ok((patch, previous_image))
 }
 
 #[async_trait]
 impl IntegrationAdapter for KubernetesAdapter {
     async fn test_connection(&self) -> Result<(), IntegrationError> {
-        // Test connection by fetching API version or listing namespaces
+        // Test connection by listing namespaces
         log::debug!("Testing Kubernetes connection");
-        let _ = self.fetch_namespaces().await?;
-        Ok(())
+        let api: Api<Namespace> = Api::all(self.client.clone());
+        match api.list(&ListParams::default().limit(1)).await {
+            Ok(_) => Ok(()),
+            Err(kube::Error::Api(response)) if response.code == 401 => {
+                // Expired credentials may be refreshed without the kubeconfig
+                // changing (e.g., by an exec plugin), so rebuild on next use
+                client_cache::invalidate(&self.kubeconfig_path);
+                Err(IntegrationError::AuthError {
+                    message: format!(
+                        "Cluster rejected the credentials of {}: {}",
+                        self.kubeconfig_path.display(),
+                        response.message
+                    ),
+                })
+            }
+            Err(e) => Err(IntegrationError::NetworkError {
+                message: format!("Failed to list namespaces: {}", e),
+            }),
+        }
     }
 
     fn get_name(&self) -> &str {
@@ -380,6 +560,41 @@ impl IntegrationAdapter for KubernetesAdapter {
 mod tests {
     use super::*;
 
+    fn deployment(containers: &[(&str, &str)]) -> Deployment {
+        serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "api" },
+            "spec": {
+                "selector": {},
+                "template": {
+                    "spec": {
+                        "containers": containers
+                            .iter()
+                            .map(|(name, image)| serde_json::json!({ "name": name, "image": image }))
+                            .collect::<Vec<_>>()
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_image_patch_targets_container() {
+        let deployment = deployment(&[("sidecar", "envoy:1.30"), ("api", "registry/api:1.4.1")]);
+
+        let (patch, previous) = image_patch(&deployment, "api", "registry/api:1.4.2").unwrap();
+        assert_eq!(previous.as_deref(), Some("registry/api:1.4.1"));
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            serde_json::json!([
+                { "op": "test", "path": "/spec/template/spec/containers/1/name", "value": "api" },
+                { "op": "replace", "path": "/spec/template/spec/containers/1/image", "value": "registry/api:1.4.2" },
+            ])
+        );
+
+        assert!(image_patch(&deployment, "worker", "registry/worker:2").is_err());
+    }
+
     #[test]
     fn test_kubeconfig_path_expansion() {
         // Test that ~ expansion would work (can't test actual expansion without filesystem)
@@ -0,0 +1,500 @@
+//! Manifest application in sync waves.
+//!
+//! Multi-document manifests are applied in waves, similar to Argo CD sync
+//! waves: resources are ordered by their sync-wave annotation, then by kind
+//! (CRDs, namespaces, RBAC and storage, config, services, workloads, then
+//! everything else). Each wave is applied with server-side apply and must
+//! become ready before the next one starts, so e.g. a custom resource is only
+//! created once its CRD is established.
+
+use kube::api::{DynamicObject, Patch, PatchParams};
+use kube::core::{GroupVersionKind, TypeMeta};
+use kube::discovery::{pinned_kind, ApiResource, Scope};
+use kube::Api;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::adapter::KubernetesAdapter;
+use super::rollouts::FIELD_MANAGER;
+use crate::integrations::IntegrationError;
+
+/// Annotations setting a resource's sync wave (lower waves are applied
+/// first, default 0). The first one present wins.
+const SYNC_WAVE_ANNOTATIONS: &[&str] = &["ops-flow/sync-wave", "argocd.argoproj.io/sync-wave"];
+
+/// Delay between readiness checks of a wave.
+const READINESS_CHECK_INTERVAL: Duration = Duration::from_secs(3);
+
+/// A resource parsed from a manifest.
+#[derive(Debug, Clone)]
+pub struct ManifestResource {
+    pub kind: String,
+    pub name: String,
+    /// Namespace set in the manifest
+    pub namespace: Option<String>,
+    object: Value,
+}
+
+impl ManifestResource {
+    /// Container images of a workload's pod template (or of a pod).
+    pub fn images(&self) -> Vec<String> {
+        let spec = &self.object["spec"];
+        let pod_specs = [
+            spec,
+            &spec["template"]["spec"],
+            &spec["jobTemplate"]["spec"]["template"]["spec"],
+        ];
+        pod_specs
+            .iter()
+            .flat_map(|pod| ["initContainers", "containers"].map(|field| &pod[field]))
+            .filter_map(Value::as_array)
+            .flatten()
+            .filter_map(|container| container["image"].as_str())
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn describe(&self) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}/{}/{}", self.kind, namespace, self.name),
+            None => format!("{}/{}", self.kind, self.name),
+        }
+    }
+}
+
+/// Resources applied together before waiting for readiness.
+#[derive(Debug, Clone)]
+pub struct ApplyWave {
+    /// Sync-wave annotation value
+    pub sync_wave: i32,
+    pub resources: Vec<ManifestResource>,
+}
+
+impl ApplyWave {
+    /// Lists the wave's resources (e.g., "ConfigMap/shop/api, Deployment/shop/api").
+    pub fn describe(&self) -> String {
+        self.resources
+            .iter()
+            .map(ManifestResource::describe)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Splits multi-document YAML into waves in application order.
+pub fn plan_waves(manifests: &str) -> Result<Vec<ApplyWave>, IntegrationError> {
+    let mut ordered: Vec<(i32, u8, ManifestResource)> = Vec::new();
+    for (index, document) in serde_yaml::Deserializer::from_str(manifests).enumerate() {
+        let object = Value::deserialize(document).map_err(|e| IntegrationError::ConfigError {
+            message: format!("Invalid manifest document {}: {}", index + 1, e),
+        })?;
+        if object.is_null() {
+            continue;
+        }
+
+        let resource = parse_resource(object).map_err(|message| IntegrationError::ConfigError {
+            message: format!("Invalid manifest document {}: {}", index + 1, message),
+        })?;
+        let sync_wave =
+            sync_wave(&resource.object).map_err(|message| IntegrationError::ConfigError {
+                message: format!("{}: {}", resource.describe(), message),
+            })?;
+        ordered.push((sync_wave, kind_priority(&resource.kind), resource));
+    }
+
+    // Stable sort keeps the manifest order within a wave
+    ordered.sort_by_key(|(sync_wave, priority, _)| (*sync_wave, *priority));
+
+    let mut waves: Vec<(i32, u8, ApplyWave)> = Vec::new();
+    for (sync_wave, priority, resource) in ordered {
+        match waves.last_mut() {
+            Some((w, p, wave)) if *w == sync_wave && *p == priority => {
+                wave.resources.push(resource)
+            }
+            _ => waves.push((
+                sync_wave,
+                priority,
+                ApplyWave {
+                    sync_wave,
+                    resources: vec![resource],
+                },
+            )),
+        }
+    }
+    Ok(waves.into_iter().map(|(_, _, wave)| wave).collect())
+}
+
+fn parse_resource(object: Value) -> Result<ManifestResource, String> {
+    let text = |pointer: &str| {
+        object
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    if text("/apiVersion").is_none() {
+        return Err("'apiVersion' is required".to_string());
+    }
+    let kind = text("/kind").ok_or("'kind' is required")?;
+    let name = text("/metadata/name").ok_or("'metadata.name' is required")?;
+
+    Ok(ManifestResource {
+        kind,
+        name,
+        namespace: text("/metadata/namespace"),
+        object,
+    })
+}
+
+fn sync_wave(object: &Value) -> Result<i32, String> {
+    let annotations = &object["metadata"]["annotations"];
+    let Some(value) = SYNC_WAVE_ANNOTATIONS
+        .iter()
+        .find_map(|key| annotations[*key].as_str())
+    else {
+        return Ok(0);
+    };
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid sync wave '{}'", value))
+}
+
+/// Order of kinds within a sync wave.
+fn kind_priority(kind: &str) -> u8 {
+    match kind {
+        "CustomResourceDefinition" => 0,
+        "Namespace" => 1,
+        "ServiceAccount"
+        | "Role"
+        | "ClusterRole"
+        | "RoleBinding"
+        | "ClusterRoleBinding"
+        | "PriorityClass"
+        | "StorageClass"
+        | "PersistentVolume"
+        | "PersistentVolumeClaim"
+        | "ResourceQuota"
+        | "LimitRange" => 2,
+        "ConfigMap" | "Secret" => 3,
+        "Service" => 4,
+        "Deployment" | "StatefulSet" | "DaemonSet" | "ReplicaSet" | "Job" | "CronJob" | "Pod" => 5,
+        _ => 6,
+    }
+}
+
+/// Why a resource is not ready yet, or None once it is. Kinds without a
+/// readiness notion are ready once applied.
+fn unready_reason(kind: &str, object: &Value) -> Option<String> {
+    let status = &object["status"];
+    let count = |field: &str| status[field].as_i64().unwrap_or(0);
+    let desired = object["spec"]["replicas"].as_i64().unwrap_or(1);
+    let generation = object["metadata"]["generation"].as_i64().unwrap_or(0);
+    if status["observedGeneration"]
+        .as_i64()
+        .is_some_and(|observed| observed < generation)
+        || (status.is_null() && matches!(kind, "Deployment" | "StatefulSet" | "DaemonSet"))
+    {
+        return Some("update not observed yet".to_string());
+    }
+
+    match kind {
+        "CustomResourceDefinition" => {
+            (!has_condition(status, "Established")).then(|| "not established".to_string())
+        }
+        "Namespace" => {
+            (status["phase"].as_str() != Some("Active")).then(|| "namespace not active".to_string())
+        }
+        "Deployment" => replicas_reason(
+            desired,
+            count("updatedReplicas"),
+            count("availableReplicas"),
+        ),
+        "StatefulSet" => replicas_reason(desired, count("updatedReplicas"), count("readyReplicas")),
+        "DaemonSet" => replicas_reason(
+            count("desiredNumberScheduled"),
+            count("updatedNumberScheduled"),
+            count("numberReady"),
+        ),
+        "Job" => {
+            let completions = object["spec"]["completions"].as_i64().unwrap_or(1);
+            (count("succeeded") < completions)
+                .then(|| format!("{} of {} completions", count("succeeded"), completions))
+        }
+        _ => None,
+    }
+}
+
+fn replicas_reason(desired: i64, updated: i64, ready: i64) -> Option<String> {
+    if updated < desired {
+        Some(format!("{} of {} replicas updated", updated, desired))
+    } else if ready < desired {
+        Some(format!("{} of {} replicas ready", ready, desired))
+    } else {
+        None
+    }
+}
+
+fn has_condition(status: &Value, condition: &str) -> bool {
+    status["conditions"].as_array().is_some_and(|conditions| {
+        conditions
+            .iter()
+            .any(|c| c["type"] == condition && c["status"] == "True")
+    })
+}
+
+impl KubernetesAdapter {
+    /// Applies waves in order, waiting up to `readiness_timeout` for each
+    /// wave to become ready before applying the next.
+    ///
+    /// # Arguments
+    /// * `namespace` - Namespace of namespaced resources that don't set one
+    /// * `on_wave` - Called before each wave is applied with its index
+    pub async fn apply_waves(
+        &self,
+        namespace: Option<&str>,
+        waves: &[ApplyWave],
+        readiness_timeout: Duration,
+        mut on_wave: impl FnMut(usize, &ApplyWave),
+    ) -> Result<(), IntegrationError> {
+        let mut resources: HashMap<GroupVersionKind, (ApiResource, Scope)> = HashMap::new();
+
+        for (index, wave) in waves.iter().enumerate() {
+            on_wave(index, wave);
+
+            let mut applied = Vec::new();
+            for resource in &wave.resources {
+                let api = self
+                    .resource_api(&mut resources, resource, namespace)
+                    .await?;
+                let object: DynamicObject = serde_json::from_value(resource.object.clone())
+                    .map_err(|e| IntegrationError::ConfigError {
+                        message: format!("Invalid resource {}: {}", resource.describe(), e),
+                    })?;
+
+                api.patch(
+                    &resource.name,
+                    &PatchParams::apply(FIELD_MANAGER).force(),
+                    &Patch::Apply(&object),
+                )
+                .await
+                .map_err(|e| {
+                    log::error!("Failed to apply {}: {}", resource.describe(), e);
+                    IntegrationError::NetworkError {
+                        message: format!("Failed to apply {}: {}", resource.describe(), e),
+                    }
+                })?;
+                applied.push((api, resource));
+            }
+
+            self.wait_until_ready(&applied, readiness_timeout).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the API of a resource, discovering its kind on first use.
+    async fn resource_api(
+        &self,
+        resources: &mut HashMap<GroupVersionKind, (ApiResource, Scope)>,
+        resource: &ManifestResource,
+        namespace: Option<&str>,
+    ) -> Result<Api<DynamicObject>, IntegrationError> {
+        let type_meta = TypeMeta {
+            api_version: resource.object["apiVersion"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            kind: resource.kind.clone(),
+        };
+        let gvk =
+            GroupVersionKind::try_from(&type_meta).map_err(|e| IntegrationError::ConfigError {
+                message: format!("Invalid apiVersion of {}: {}", resource.describe(), e),
+            })?;
+
+        if !resources.contains_key(&gvk) {
+            let (api_resource, capabilities) =
+                pinned_kind(&self.client, &gvk).await.map_err(|e| {
+                    IntegrationError::ConfigError {
+                        message: format!(
+                            "Unknown resource kind {} ({}): {}",
+                            resource.kind, type_meta.api_version, e
+                        ),
+                    }
+                })?;
+            resources.insert(gvk.clone(), (api_resource, capabilities.scope));
+        }
+        let (api_resource, scope) = &resources[&gvk];
+
+        match scope {
+            Scope::Cluster => Ok(Api::all_with(self.client.clone(), api_resource)),
+            Scope::Namespaced => {
+                let namespace = resource.namespace.as_deref().or(namespace).ok_or_else(|| {
+                    IntegrationError::ConfigError {
+                        message: format!("No namespace set for {}", resource.describe()),
+                    }
+                })?;
+                Ok(Api::namespaced_with(
+                    self.client.clone(),
+                    namespace,
+                    api_resource,
+                ))
+            }
+        }
+    }
+
+    async fn wait_until_ready(
+        &self,
+        applied: &[(Api<DynamicObject>, &ManifestResource)],
+        timeout: Duration,
+    ) -> Result<(), IntegrationError> {
+        let deadline = Instant::now() + timeout;
+        let mut pending: Vec<&(Api<DynamicObject>, &ManifestResource)> = applied.iter().collect();
+
+        loop {
+            let mut reasons = Vec::new();
+            let mut still_pending = Vec::new();
+            for entry in pending {
+                let (api, resource) = entry;
+                let reason = match api.get(&resource.name).await {
+                    Ok(object) => {
+                        let value = serde_json::to_value(&object).unwrap_or_default();
+                        if resource.kind == "Job" && has_condition(&value["status"], "Failed") {
+                            return Err(IntegrationError::ConfigError {
+                                message: format!("{} failed", resource.describe()),
+                            });
+                        }
+                        unready_reason(&resource.kind, &value)
+                    }
+                    Err(e) => Some(e.to_string()),
+                };
+                if let Some(reason) = reason {
+                    reasons.push(format!("{}: {}", resource.describe(), reason));
+                    still_pending.push(entry);
+                }
+            }
+            pending = still_pending;
+
+            if pending.is_empty() {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(IntegrationError::ConfigError {
+                    message: format!("Wave not ready in time ({})", reasons.join("; ")),
+                });
+            }
+            tokio::time::sleep(READINESS_CHECK_INTERVAL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const MANIFESTS: &str = r#"
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: api
+---
+apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: api-config
+---
+apiVersion: batch/v1
+kind: Job
+metadata:
+  name: migrate
+  annotations:
+    argocd.argoproj.io/sync-wave: "-1"
+---
+apiVersion: v1
+kind: Namespace
+metadata:
+  name: shop
+---
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+metadata:
+  name: widgets.example.com
+---
+apiVersion: v1
+kind: Secret
+metadata:
+  name: api-secret
+"#;
+
+    #[test]
+    fn test_plan_waves_orders_by_annotation_then_kind() {
+        let waves = plan_waves(MANIFESTS).unwrap();
+        let summary: Vec<(i32, String)> = waves
+            .iter()
+            .map(|wave| (wave.sync_wave, wave.describe()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                (-1, "Job/migrate".to_string()),
+                (
+                    0,
+                    "CustomResourceDefinition/widgets.example.com".to_string()
+                ),
+                (0, "Namespace/shop".to_string()),
+                (0, "ConfigMap/api-config, Secret/api-secret".to_string()),
+                (0, "Deployment/api".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_waves_rejects_invalid_documents() {
+        assert!(plan_waves("kind: ConfigMap\nmetadata:\n  name: x").is_err());
+        assert!(plan_waves(
+            "apiVersion: v1\nkind: ConfigMap\nmetadata:\n  name: x\n  annotations:\n    ops-flow/sync-wave: soon"
+        )
+        .is_err());
+        assert!(plan_waves("---\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_images() {
+        let waves = plan_waves(
+            "apiVersion: batch/v1\nkind: CronJob\nmetadata:\n  name: report\nspec:\n  jobTemplate:\n    spec:\n      template:\n        spec:\n          initContainers: [{ name: init, image: 'busybox:1.36' }]\n          containers: [{ name: report, image: 'registry/report:2.0' }]",
+        )
+        .unwrap();
+        assert_eq!(
+            waves[0].resources[0].images(),
+            ["busybox:1.36", "registry/report:2.0"]
+        );
+    }
+
+    #[test]
+    fn test_unready_reason() {
+        let deployment = |updated: i64, available: i64| {
+            json!({
+                "metadata": { "generation": 3 },
+                "spec": { "replicas": 2 },
+                "status": {
+                    "observedGeneration": 3,
+                    "updatedReplicas": updated,
+                    "availableReplicas": available
+                }
+            })
+        };
+        assert_eq!(unready_reason("Deployment", &deployment(2, 2)), None);
+        assert_eq!(
+            unready_reason("Deployment", &deployment(2, 1)).as_deref(),
+            Some("1 of 2 replicas ready")
+        );
+
+        let crd =
+            json!({ "status": { "conditions": [{ "type": "Established", "status": "True" }] } });
+        assert_eq!(unready_reason("CustomResourceDefinition", &crd), None);
+        assert!(unready_reason("Namespace", &json!({ "status": {} })).is_some());
+        assert_eq!(unready_reason("ConfigMap", &json!({})), None);
+    }
+}
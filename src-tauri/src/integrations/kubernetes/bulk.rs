@@ -0,0 +1,202 @@
+//! Bulk pod actions.
+//!
+//! Applies one action to every pod matching a label selector, e.g. deleting
+//! all pods with `app=api` to restart them. A dry run only lists the pods the
+//! action would touch.
+
+use k8s_openapi::api::core::v1::Pod;
+use kube::api::{DeleteParams, ListParams, Patch, PatchParams};
+use kube::Api;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+use super::adapter::KubernetesAdapter;
+use super::types::{K8sBulkPodResult, K8sBulkPodTarget, K8sPodAction};
+use crate::integrations::IntegrationError;
+
+impl KubernetesAdapter {
+    /// Applies `action` to the pods in `namespace` matching `selector`.
+    ///
+    /// Failures on single pods are reported per pod and don't stop the
+    /// action on the others.
+    pub async fn bulk_pod_action(
+        &self,
+        namespace: &str,
+        selector: &str,
+        action: &K8sPodAction,
+        dry_run: bool,
+    ) -> Result<K8sBulkPodResult, IntegrationError> {
+        let selector = validate_selector(selector)?;
+        validate_action(action)?;
+
+        log::debug!(
+            "Bulk pod action {:?} on {} pods matching {} (dry run: {})",
+            action,
+            namespace,
+            selector,
+            dry_run
+        );
+
+        let api: Api<Pod> = Api::namespaced(self.client.clone(), namespace);
+        let pods = api
+            .list(&ListParams::default().labels(selector))
+            .await
+            .map_err(|e| {
+                log::error!(
+                    "Failed to list pods matching {} in {}: {}",
+                    selector,
+                    namespace,
+                    e
+                );
+                IntegrationError::NetworkError {
+                    message: format!("Failed to list pods: {}", e),
+                }
+            })?;
+
+        let mut targets: Vec<K8sBulkPodTarget> = pods.iter().map(target_of).collect();
+        targets.sort_by(|a, b| a.name.cmp(&b.name));
+
+        if !dry_run {
+            for target in &mut targets {
+                let result = match action {
+                    K8sPodAction::Delete => api
+                        .delete(&target.name, &DeleteParams::default())
+                        .await
+                        .map(|_| ()),
+                    K8sPodAction::Annotate { annotations } => api
+                        .patch(
+                            &target.name,
+                            &PatchParams::default(),
+                            &Patch::Merge(annotation_patch(annotations)),
+                        )
+                        .await
+                        .map(|_| ()),
+                };
+                if let Err(e) = result {
+                    log::warn!(
+                        "Bulk pod action failed on {}/{}: {}",
+                        namespace,
+                        target.name,
+                        e
+                    );
+                    target.error = Some(e.to_string());
+                }
+            }
+        }
+
+        Ok(K8sBulkPodResult {
+            namespace: namespace.to_string(),
+            selector: selector.to_string(),
+            action: action.clone(),
+            dry_run,
+            failed: targets.iter().filter(|t| t.error.is_some()).count() as u32,
+            pods: targets,
+        })
+    }
+}
+
+/// Rejects empty selectors, which would match every pod in the namespace.
+fn validate_selector(selector: &str) -> Result<&str, IntegrationError> {
+    let selector = selector.trim();
+    if selector.is_empty() {
+        return Err(IntegrationError::ConfigError {
+            message: "A label selector is required for bulk pod actions".to_string(),
+        });
+    }
+    Ok(selector)
+}
+
+fn validate_action(action: &K8sPodAction) -> Result<(), IntegrationError> {
+    match action {
+        K8sPodAction::Annotate { annotations } if annotations.is_empty() => {
+            Err(IntegrationError::ConfigError {
+                message: "No annotations to set".to_string(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn target_of(pod: &Pod) -> K8sBulkPodTarget {
+    let owner = pod
+        .metadata
+        .owner_references
+        .iter()
+        .flatten()
+        .find(|owner| owner.controller == Some(true))
+        .map(|owner| format!("{}/{}", owner.kind, owner.name));
+
+    K8sBulkPodTarget {
+        name: pod.metadata.name.clone().unwrap_or_default(),
+        status: pod
+            .status
+            .as_ref()
+            .and_then(|s| s.phase.clone())
+            .unwrap_or_else(|| "Unknown".to_string()),
+        owner,
+        error: None,
+    }
+}
+
+/// Merge patch setting the annotations; `None` values remove them.
+fn annotation_patch(annotations: &BTreeMap<String, Option<String>>) -> Value {
+    json!({ "metadata": { "annotations": annotations } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotation_patch_removes_null_values() {
+        let annotations = BTreeMap::from([
+            (
+                "ops-flow/restarted-at".to_string(),
+                Some("2024-05-01T10:00:00Z".to_string()),
+            ),
+            ("obsolete".to_string(), None),
+        ]);
+
+        assert_eq!(
+            annotation_patch(&annotations),
+            json!({ "metadata": { "annotations": {
+                "obsolete": null,
+                "ops-flow/restarted-at": "2024-05-01T10:00:00Z"
+            } } })
+        );
+    }
+
+    #[test]
+    fn test_target_of_reports_controller() {
+        let pod: Pod = serde_json::from_value(json!({
+            "metadata": {
+                "name": "api-7d9f8-x2k4p",
+                "ownerReferences": [{
+                    "apiVersion": "apps/v1", "kind": "ReplicaSet", "name": "api-7d9f8",
+                    "uid": "1", "controller": true
+                }]
+            },
+            "status": { "phase": "Running" }
+        }))
+        .unwrap();
+        let bare: Pod = serde_json::from_value(json!({ "metadata": { "name": "debug" } })).unwrap();
+
+        assert_eq!(
+            target_of(&pod).owner.as_deref(),
+            Some("ReplicaSet/api-7d9f8")
+        );
+        assert_eq!(target_of(&bare).owner, None);
+        assert_eq!(target_of(&bare).status, "Unknown");
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(validate_selector("  ").is_err());
+        assert_eq!(validate_selector(" app=api ").unwrap(), "app=api");
+        assert!(validate_action(&K8sPodAction::Annotate {
+            annotations: BTreeMap::new()
+        })
+        .is_err());
+        assert!(validate_action(&K8sPodAction::Delete).is_ok());
+    }
+}
@@ -0,0 +1,192 @@
+//! Kubernetes clients cached per kubeconfig.
+//!
+//! Building a client parses the kubeconfig and sets up TLS, so clients are
+//! shared by the commands using the same kubeconfig. Tools such as cloud
+//! CLIs or `kubelogin` rewrite the kubeconfig (or the certificate and token
+//! files it references) when they refresh credentials; the cached client is
+//! rebuilt as soon as any of those files changes, so refreshed credentials
+//! are used without restarting the app.
+
+use kube::config::{KubeConfigOptions, Kubeconfig};
+use kube::{Client, Config};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+
+use super::exec_auth;
+use crate::integrations::IntegrationError;
+
+/// Size and modification time of the files a client was built from.
+type FileStamps = Vec<(PathBuf, Option<(SystemTime, u64)>)>;
+
+struct CachedClient {
+    client: Client,
+    stamps: FileStamps,
+}
+
+static CLIENTS: LazyLock<Mutex<HashMap<PathBuf, CachedClient>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn clients() -> MutexGuard<'static, HashMap<PathBuf, CachedClient>> {
+    CLIENTS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+fn stamps(paths: Vec<PathBuf>) -> FileStamps {
+    paths
+        .into_iter()
+        .map(|path| {
+            let stamp = stamp(&path);
+            (path, stamp)
+        })
+        .collect()
+}
+
+fn unchanged(stamps: &FileStamps) -> bool {
+    stamps
+        .iter()
+        .all(|(path, stamp)| self::stamp(path) == *stamp)
+}
+
+/// The kubeconfig and the credential files it references (absolute, as
+/// `Kubeconfig::read_from` resolves them).
+fn referenced_files(kubeconfig_path: &Path, kubeconfig: &Kubeconfig) -> Vec<PathBuf> {
+    let clusters = kubeconfig
+        .clusters
+        .iter()
+        .filter_map(|named| named.cluster.as_ref()?.certificate_authority.clone());
+    let auth_files = kubeconfig
+        .auth_infos
+        .iter()
+        .filter_map(|named| named.auth_info.as_ref())
+        .flat_map(|auth| {
+            [
+                auth.client_certificate.clone(),
+                auth.client_key.clone(),
+                auth.token_file.clone(),
+            ]
+        })
+        .flatten();
+
+    let mut files = vec![kubeconfig_path.to_path_buf()];
+    for file in clusters.chain(auth_files).map(PathBuf::from) {
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    }
+    files
+}
+
+/// Returns the client for a kubeconfig, building it on first use and again
+/// whenever the kubeconfig or a credential file it references changed.
+pub(super) async fn client_for(kubeconfig_path: &Path) -> Result<Client, IntegrationError> {
+    let stale = match clients().get(kubeconfig_path) {
+        Some(cached) if unchanged(&cached.stamps) => return Ok(cached.client.clone()),
+        Some(_) => true,
+        None => false,
+    };
+    if stale {
+        log::info!(
+            "Kubeconfig {} changed, reloading Kubernetes credentials",
+            kubeconfig_path.display()
+        );
+    }
+
+    // Stamped before reading, so a rewrite while building triggers a rebuild
+    // on next use rather than going unnoticed
+    let config_stamp = stamp(kubeconfig_path);
+    let mut kubeconfig =
+        Kubeconfig::read_from(kubeconfig_path).map_err(|e| IntegrationError::ConfigError {
+            message: format!("Failed to load kubeconfig: {}", e),
+        })?;
+    let mut stamps = stamps(referenced_files(kubeconfig_path, &kubeconfig));
+    stamps[0].1 = config_stamp;
+    exec_auth::prepare(
+        &mut kubeconfig,
+        kubeconfig_path.parent().unwrap_or(Path::new("")),
+    )
+    .await?;
+
+    let config = Config::from_custom_kubeconfig(kubeconfig, &KubeConfigOptions::default())
+        .await
+        .map_err(|e| IntegrationError::ConfigError {
+            message: format!("Failed to load kubeconfig: {}", e),
+        })?;
+    let client = Client::try_from(config).map_err(|e| IntegrationError::ConfigError {
+        message: format!("Failed to create Kubernetes client: {}", e),
+    })?;
+
+    clients().insert(
+        kubeconfig_path.to_path_buf(),
+        CachedClient {
+            client: client.clone(),
+            stamps,
+        },
+    );
+    Ok(client)
+}
+
+/// Drops the cached client of a kubeconfig, so the next use reloads it even
+/// if no file changed (e.g., after the cluster rejected its credentials).
+pub(super) fn invalidate(kubeconfig_path: &Path) {
+    clients().remove(kubeconfig_path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_referenced_files() {
+        let kubeconfig = Kubeconfig::from_yaml(
+            r#"
+apiVersion: v1
+kind: Config
+clusters:
+- name: prod
+  cluster:
+    server: https://prod.example.com
+    certificate-authority: /etc/kube/ca.crt
+users:
+- name: admin
+  user:
+    client-certificate: /etc/kube/admin.crt
+    client-key: /etc/kube/admin.key
+- name: ci
+  user:
+    tokenFile: /etc/kube/ca.crt
+contexts: []
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            referenced_files(Path::new("/home/me/.kube/config"), &kubeconfig),
+            [
+                "/home/me/.kube/config",
+                "/etc/kube/ca.crt",
+                "/etc/kube/admin.crt",
+                "/etc/kube/admin.key",
+            ]
+            .map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn test_stamps_detect_changes() {
+        let path = std::env::temp_dir().join(format!("ops-flow-kubeconfig-{}", std::process::id()));
+        std::fs::write(&path, "a").unwrap();
+        let stamps = stamps(vec![path.clone()]);
+        assert!(unchanged(&stamps));
+
+        std::fs::write(&path, "refreshed").unwrap();
+        assert!(!unchanged(&stamps));
+        std::fs::remove_file(&path).unwrap();
+        assert!(!unchanged(&stamps));
+    }
+}
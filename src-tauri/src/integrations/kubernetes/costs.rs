@@ -0,0 +1,202 @@
+//! Cost estimation from resource requests.
+//!
+//! Estimates are request-based: each running or pending pod is charged for
+//! the CPU and memory its containers request, at the configured hourly
+//! prices. Actual usage, limits and node overhead are not taken into account.
+
+use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use kube::Api;
+use std::collections::BTreeMap;
+
+use super::adapter::KubernetesAdapter;
+use super::types::{K8sCostEstimate, K8sResourceRequests};
+use crate::integrations::IntegrationError;
+use crate::types::CostRate;
+
+/// Average number of hours in a month (365 * 24 / 12).
+pub const HOURS_PER_MONTH: f64 = 730.0;
+
+/// Bytes per GiB.
+const BYTES_PER_GIB: f64 = 1_073_741_824.0;
+
+impl KubernetesAdapter {
+    /// Sums the CPU and memory requests of the active pods in a namespace.
+    pub async fn fetch_resource_requests(
+        &self,
+        namespace: &str,
+    ) -> Result<K8sResourceRequests, IntegrationError> {
+        log::debug!("Fetching resource requests in namespace: {}", namespace);
+
+        let api: Api<Pod> = Api::namespaced(self.client.clone(), namespace);
+        let pods = api.list(&Default::default()).await.map_err(|e| {
+            log::error!("Failed to list pods in namespace {}: {}", namespace, e);
+            IntegrationError::NetworkError {
+                message: format!("Failed to list pods: {}", e),
+            }
+        })?;
+
+        let mut requests = K8sResourceRequests {
+            namespace: namespace.to_string(),
+            pods: 0,
+            pods_without_requests: 0,
+            cpu_cores: 0.0,
+            memory_gib: 0.0,
+        };
+        for pod in pods {
+            // Completed pods no longer hold their requests
+            let phase = pod.status.as_ref().and_then(|s| s.phase.as_deref());
+            if matches!(phase, Some("Succeeded") | Some("Failed")) {
+                continue;
+            }
+            let Some(spec) = pod.spec else {
+                continue;
+            };
+
+            let (mut cpu, mut memory) = (0.0, 0.0);
+            for container in &spec.containers {
+                let container_requests = container
+                    .resources
+                    .as_ref()
+                    .and_then(|r| r.requests.as_ref());
+                cpu += request(container_requests, "cpu");
+                memory += request(container_requests, "memory");
+            }
+
+            requests.pods += 1;
+            if cpu == 0.0 && memory == 0.0 {
+                requests.pods_without_requests += 1;
+            }
+            requests.cpu_cores += cpu;
+            requests.memory_gib += memory / BYTES_PER_GIB;
+        }
+
+        Ok(requests)
+    }
+}
+
+/// Reads a resource request in base units (cores or bytes), 0 when unset.
+fn request(requests: Option<&BTreeMap<String, Quantity>>, resource: &str) -> f64 {
+    requests
+        .and_then(|r| r.get(resource))
+        .and_then(|q| parse_quantity(&q.0))
+        .unwrap_or(0.0)
+}
+
+/// Parses a Kubernetes quantity (e.g., "250m", "1.5", "512Mi", "2G", "1e3").
+pub fn parse_quantity(quantity: &str) -> Option<f64> {
+    const BINARY: [(&str, f64); 6] = [
+        ("Ki", 1024.0),
+        ("Mi", 1_048_576.0),
+        ("Gi", 1_073_741_824.0),
+        ("Ti", 1_099_511_627_776.0),
+        ("Pi", 1_125_899_906_842_624.0),
+        ("Ei", 1_152_921_504_606_846_976.0),
+    ];
+    const DECIMAL: [(char, f64); 9] = [
+        ('n', 1e-9),
+        ('u', 1e-6),
+        ('m', 1e-3),
+        ('k', 1e3),
+        ('M', 1e6),
+        ('G', 1e9),
+        ('T', 1e12),
+        ('P', 1e15),
+        ('E', 1e18),
+    ];
+
+    let quantity = quantity.trim();
+    for (suffix, factor) in BINARY {
+        if let Some(number) = quantity.strip_suffix(suffix) {
+            return number.parse::<f64>().ok().map(|n| n * factor);
+        }
+    }
+    for (suffix, factor) in DECIMAL {
+        if let Some(number) = quantity.strip_suffix(suffix) {
+            return number.parse::<f64>().ok().map(|n| n * factor);
+        }
+    }
+    quantity.parse::<f64>().ok()
+}
+
+/// Prices resource requests at a rate for a month.
+pub fn estimate_monthly_cost(requests: &K8sResourceRequests, rate: &CostRate) -> K8sCostEstimate {
+    let cpu_monthly_cost = requests.cpu_cores * rate.cpu_hour_price * HOURS_PER_MONTH;
+    let memory_monthly_cost = requests.memory_gib * rate.memory_gb_hour_price * HOURS_PER_MONTH;
+    K8sCostEstimate {
+        requests: requests.clone(),
+        cpu_monthly_cost: round_cents(cpu_monthly_cost),
+        memory_monthly_cost: round_cents(memory_monthly_cost),
+        monthly_cost: round_cents(cpu_monthly_cost + memory_monthly_cost),
+        currency: rate.currency.clone(),
+    }
+}
+
+/// Picks the rate of an environment, falling back to the default rate.
+pub fn rate_for<'a>(rates: &'a [CostRate], environment_id: &str) -> Option<&'a CostRate> {
+    rates
+        .iter()
+        .find(|r| r.environment_id.as_deref() == Some(environment_id))
+        .or_else(|| rates.iter().find(|r| r.environment_id.is_none()))
+}
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quantity() {
+        let cases = [
+            ("250m", 0.25),
+            ("2", 2.0),
+            ("1.5", 1.5),
+            ("512Mi", 536_870_912.0),
+            ("1Gi", 1_073_741_824.0),
+            ("2G", 2e9),
+            ("1e3", 1000.0),
+            ("100000000n", 0.1),
+        ];
+        for (quantity, expected) in cases {
+            let parsed = parse_quantity(quantity).unwrap();
+            assert!((parsed - expected).abs() < 1e-9, "{}: {}", quantity, parsed);
+        }
+        assert_eq!(parse_quantity("lots"), None);
+    }
+
+    fn rate(id: &str, environment_id: Option<&str>) -> CostRate {
+        CostRate {
+            id: id.to_string(),
+            environment_id: environment_id.map(str::to_string),
+            cpu_hour_price: 0.04,
+            memory_gb_hour_price: 0.005,
+            currency: "USD".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rate_for() {
+        let rates = vec![rate("prod", Some("prod")), rate("default", None)];
+        assert_eq!(rate_for(&rates, "prod").unwrap().id, "prod");
+        assert_eq!(rate_for(&rates, "staging").unwrap().id, "default");
+        assert!(rate_for(&rates[..1], "staging").is_none());
+    }
+
+    #[test]
+    fn test_estimate_monthly_cost() {
+        let requests = K8sResourceRequests {
+            namespace: "shop-prod".to_string(),
+            pods: 4,
+            pods_without_requests: 1,
+            cpu_cores: 1.5,
+            memory_gib: 6.0,
+        };
+        let estimate = estimate_monthly_cost(&requests, &rate("default", None));
+        assert_eq!(estimate.cpu_monthly_cost, 43.8);
+        assert_eq!(estimate.memory_monthly_cost, 21.9);
+        assert_eq!(estimate.monthly_cost, 65.7);
+    }
+}
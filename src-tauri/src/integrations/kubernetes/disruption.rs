@@ -0,0 +1,350 @@
+//! Eviction safety checks.
+//!
+//! Before pods of a deployment are deleted or the deployment is scaled down,
+//! the PodDisruptionBudgets selecting its pods and its replica counts tell
+//! whether the action keeps the deployment's availability guarantees.
+
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use kube::api::ListParams;
+use kube::Api;
+use std::collections::BTreeMap;
+
+use super::adapter::KubernetesAdapter;
+use super::rollouts::unready_reason;
+use super::types::{K8sDisruptionBudget, K8sEvictionSafety};
+use crate::integrations::IntegrationError;
+
+impl KubernetesAdapter {
+    /// Checks whether deleting `delete_pods` pods of a deployment (one when
+    /// not given) or scaling it to `scale_to` replicas keeps the guarantees
+    /// of its PodDisruptionBudgets and leaves pods serving.
+    pub async fn check_eviction_safety(
+        &self,
+        namespace: &str,
+        deployment: &str,
+        delete_pods: Option<u32>,
+        scale_to: Option<u32>,
+    ) -> Result<K8sEvictionSafety, IntegrationError> {
+        log::debug!(
+            "Checking eviction safety of deployment {}/{}",
+            namespace,
+            deployment
+        );
+
+        let deployments: Api<Deployment> = Api::namespaced(self.client.clone(), namespace);
+        let found =
+            deployments
+                .get_opt(deployment)
+                .await
+                .map_err(|e| IntegrationError::NetworkError {
+                    message: format!("Failed to get deployment: {}", e),
+                })?;
+        let found = found.ok_or(IntegrationError::NotFound)?;
+
+        let budgets: Api<PodDisruptionBudget> = Api::namespaced(self.client.clone(), namespace);
+        let budgets = budgets.list(&ListParams::default()).await.map_err(|e| {
+            log::error!(
+                "Failed to list PodDisruptionBudgets in {}: {}",
+                namespace,
+                e
+            );
+            IntegrationError::NetworkError {
+                message: format!("Failed to list PodDisruptionBudgets: {}", e),
+            }
+        })?;
+
+        Ok(evaluate(
+            namespace,
+            deployment,
+            &found,
+            &budgets.items,
+            delete_pods.unwrap_or(1),
+            scale_to,
+        ))
+    }
+}
+
+fn evaluate(
+    namespace: &str,
+    name: &str,
+    deployment: &Deployment,
+    budgets: &[PodDisruptionBudget],
+    delete_pods: u32,
+    scale_to: Option<u32>,
+) -> K8sEvictionSafety {
+    let desired = deployment
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.replicas)
+        .unwrap_or(1)
+        .max(0) as u32;
+    let ready = deployment
+        .status
+        .as_ref()
+        .and_then(|status| status.ready_replicas)
+        .unwrap_or(0)
+        .max(0) as u32;
+    let labels = deployment
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.template.metadata.as_ref())
+        .and_then(|meta| meta.labels.clone())
+        .unwrap_or_default();
+
+    let covering: Vec<&PodDisruptionBudget> = budgets
+        .iter()
+        .filter(|budget| {
+            budget
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.selector.as_ref())
+                .is_some_and(|selector| selector_matches(selector, &labels))
+        })
+        .collect();
+    let budgets: Vec<K8sDisruptionBudget> = covering.iter().map(|b| budget_of(b)).collect();
+
+    let mut warnings = Vec::new();
+    if let Some(reason) = unready_reason(deployment) {
+        warnings.push(format!(
+            "Deployment {} is not fully ready: {}",
+            name, reason
+        ));
+    }
+    if desired == 1 {
+        warnings.push(format!(
+            "Deployment {} runs a single replica; deleting its pod interrupts service",
+            name
+        ));
+    }
+
+    let disruptions_allowed = if budgets.is_empty() {
+        warnings.push(format!(
+            "No PodDisruptionBudget covers the pods of {}",
+            name
+        ));
+        ready.saturating_sub(1)
+    } else {
+        for budget in budgets.iter().filter(|b| b.disruptions_allowed == 0) {
+            warnings.push(format!(
+                "PodDisruptionBudget {} allows no disruptions ({} healthy, {} required)",
+                budget.name, budget.current_healthy, budget.desired_healthy
+            ));
+        }
+        budgets
+            .iter()
+            .map(|b| b.disruptions_allowed)
+            .min()
+            .unwrap_or(0)
+    };
+
+    // Percentages hold at any size, so only counts raise the floor
+    let (min_safe_replicas, floor_budget) = covering
+        .iter()
+        .filter_map(
+            |budget| match budget.spec.as_ref()?.min_available.as_ref()? {
+                IntOrString::Int(count) => {
+                    Some(((*count).max(0) as u32, budget.metadata.name.clone()))
+                }
+                IntOrString::String(_) => None,
+            },
+        )
+        .max_by_key(|(count, _)| *count)
+        .filter(|(count, _)| *count > 1)
+        .unwrap_or((1, None));
+
+    let mut safe = delete_pods <= disruptions_allowed;
+    if delete_pods > disruptions_allowed {
+        warnings.push(format!(
+            "Deleting {} pod(s) exceeds the {} disruption(s) allowed",
+            delete_pods, disruptions_allowed
+        ));
+    }
+    if let Some(replicas) = scale_to.filter(|replicas| *replicas < desired) {
+        if replicas == 0 {
+            safe = false;
+            warnings.push(format!("Scaling {} to zero stops it", name));
+        } else if replicas < min_safe_replicas {
+            safe = false;
+            warnings.push(format!(
+                "Scaling to {} replica(s) goes below the {} required by PodDisruptionBudget {}",
+                replicas,
+                min_safe_replicas,
+                floor_budget.unwrap_or_default()
+            ));
+        }
+    }
+
+    K8sEvictionSafety {
+        namespace: namespace.to_string(),
+        deployment: name.to_string(),
+        desired_replicas: desired,
+        ready_replicas: ready,
+        budgets,
+        disruptions_allowed,
+        min_safe_replicas,
+        safe,
+        warnings,
+    }
+}
+
+fn budget_of(budget: &PodDisruptionBudget) -> K8sDisruptionBudget {
+    let spec = budget.spec.as_ref();
+    let status = budget.status.as_ref();
+    let count = |value: Option<i32>| value.unwrap_or(0).max(0) as u32;
+
+    K8sDisruptionBudget {
+        name: budget.metadata.name.clone().unwrap_or_default(),
+        min_available: spec
+            .and_then(|s| s.min_available.as_ref())
+            .map(format_int_or_string),
+        max_unavailable: spec
+            .and_then(|s| s.max_unavailable.as_ref())
+            .map(format_int_or_string),
+        current_healthy: count(status.map(|s| s.current_healthy)),
+        desired_healthy: count(status.map(|s| s.desired_healthy)),
+        // Budgets the controller hasn't evaluated yet allow nothing
+        disruptions_allowed: count(status.map(|s| s.disruptions_allowed)),
+    }
+}
+
+fn format_int_or_string(value: &IntOrString) -> String {
+    match value {
+        IntOrString::Int(count) => count.to_string(),
+        IntOrString::String(value) => value.clone(),
+    }
+}
+
+/// Whether a label selector selects pods with `labels`. An empty selector
+/// selects every pod.
+fn selector_matches(selector: &LabelSelector, labels: &BTreeMap<String, String>) -> bool {
+    let labels_match = selector
+        .match_labels
+        .iter()
+        .flatten()
+        .all(|(key, value)| labels.get(key) == Some(value));
+    let expressions_match = selector.match_expressions.iter().flatten().all(|req| {
+        let values = req.values.as_deref().unwrap_or_default();
+        let value = labels.get(&req.key);
+        match req.operator.as_str() {
+            "In" => value.is_some_and(|v| values.contains(v)),
+            "NotIn" => value.is_none_or(|v| !values.contains(v)),
+            "Exists" => value.is_some(),
+            "DoesNotExist" => value.is_none(),
+            _ => false,
+        }
+    });
+    labels_match && expressions_match
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn deployment(replicas: i32, ready: i32) -> Deployment {
+        serde_json::from_value(json!({
+            "metadata": { "name": "api" },
+            "spec": {
+                "replicas": replicas,
+                "selector": { "matchLabels": { "app": "api" } },
+                "template": { "metadata": { "labels": { "app": "api", "tier": "backend" } } }
+            },
+            "status": { "readyReplicas": ready, "updatedReplicas": ready }
+        }))
+        .unwrap()
+    }
+
+    fn budget(
+        name: &str,
+        selector: serde_json::Value,
+        min_available: i32,
+        allowed: i32,
+    ) -> PodDisruptionBudget {
+        serde_json::from_value(json!({
+            "metadata": { "name": name },
+            "spec": { "selector": selector, "minAvailable": min_available },
+            "status": {
+                "currentHealthy": 3, "desiredHealthy": min_available,
+                "disruptionsAllowed": allowed, "expectedPods": 3
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_selector_matches() {
+        let labels = BTreeMap::from([
+            ("app".to_string(), "api".to_string()),
+            ("tier".to_string(), "backend".to_string()),
+        ]);
+        let selector =
+            |value: serde_json::Value| -> LabelSelector { serde_json::from_value(value).unwrap() };
+
+        assert!(selector_matches(&selector(json!({})), &labels));
+        assert!(selector_matches(
+            &selector(json!({ "matchLabels": { "app": "api" } })),
+            &labels
+        ));
+        assert!(!selector_matches(
+            &selector(json!({ "matchLabels": { "app": "web" } })),
+            &labels
+        ));
+        assert!(selector_matches(
+            &selector(json!({ "matchExpressions": [
+                { "key": "tier", "operator": "In", "values": ["backend", "worker"] },
+                { "key": "canary", "operator": "DoesNotExist" }
+            ] })),
+            &labels
+        ));
+        assert!(!selector_matches(
+            &selector(json!({ "matchExpressions": [
+                { "key": "tier", "operator": "NotIn", "values": ["backend"] }
+            ] })),
+            &labels
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_with_budget() {
+        let budgets = [
+            budget("api-pdb", json!({ "matchLabels": { "app": "api" } }), 2, 1),
+            budget("web-pdb", json!({ "matchLabels": { "app": "web" } }), 1, 0),
+        ];
+
+        let one = evaluate("prod", "api", &deployment(3, 3), &budgets, 1, None);
+        assert!(one.safe);
+        assert_eq!(one.budgets.len(), 1);
+        assert_eq!(one.disruptions_allowed, 1);
+        assert_eq!(one.min_safe_replicas, 2);
+        assert!(one.warnings.is_empty());
+
+        let two = evaluate("prod", "api", &deployment(3, 3), &budgets, 2, Some(1));
+        assert!(!two.safe);
+        assert_eq!(
+            two.warnings,
+            [
+                "Deleting 2 pod(s) exceeds the 1 disruption(s) allowed",
+                "Scaling to 1 replica(s) goes below the 2 required by PodDisruptionBudget api-pdb",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_evaluate_without_budget() {
+        let single = evaluate("prod", "api", &deployment(1, 1), &[], 1, None);
+        assert!(!single.safe);
+        assert_eq!(single.disruptions_allowed, 0);
+        assert_eq!(single.warnings.len(), 3);
+
+        let scaled = evaluate("prod", "api", &deployment(3, 3), &[], 1, Some(0));
+        assert!(!scaled.safe);
+        assert_eq!(scaled.disruptions_allowed, 2);
+        assert_eq!(
+            scaled.warnings.last().unwrap(),
+            "Scaling api to zero stops it"
+        );
+    }
+}
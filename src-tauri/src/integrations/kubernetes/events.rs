@@ -0,0 +1,72 @@
+//! Namespace event streams.
+
+use futures::{future, Stream, StreamExt};
+use k8s_openapi::api::core::v1::Event;
+use kube::runtime::{watcher, WatchStreamExt};
+use kube::Api;
+
+use super::adapter::KubernetesAdapter;
+use super::types::K8sEvent;
+use crate::integrations::IntegrationError;
+use crate::types::Timestamp;
+
+impl KubernetesAdapter {
+    /// Watches the events of a namespace.
+    ///
+    /// The stream yields the events the cluster still retains, then every
+    /// event created or updated afterwards. Broken watches are resumed with
+    /// backoff; their errors are yielded without ending the stream.
+    pub fn watch_events(
+        &self,
+        namespace: &str,
+    ) -> impl Stream<Item = Result<K8sEvent, IntegrationError>> + Send + 'static {
+        log::debug!("Watching Kubernetes events in namespace: {}", namespace);
+
+        let api: Api<Event> = Api::namespaced(self.client.clone(), namespace);
+        let namespace = namespace.to_string();
+        watcher(api, watcher::Config::default())
+            .default_backoff()
+            .applied_objects()
+            .filter_map(move |result| {
+                let item = match result {
+                    Ok(event) => event_from(event, &namespace).map(Ok),
+                    Err(e) => Some(Err(IntegrationError::NetworkError {
+                        message: format!("Event watch failed: {}", e),
+                    })),
+                };
+                future::ready(item)
+            })
+    }
+}
+
+/// Converts an event; events without UID or any timestamp are dropped.
+fn event_from(event: Event, namespace: &str) -> Option<K8sEvent> {
+    let uid = event.metadata.uid.clone()?;
+    let last_seen = event
+        .last_timestamp
+        .map(|t| t.0)
+        .or_else(|| event.event_time.as_ref().map(|t| t.0))
+        .or_else(|| event.metadata.creation_timestamp.as_ref().map(|t| t.0))?;
+    let first_seen = event.first_timestamp.map_or(last_seen, |t| t.0);
+    let source = event
+        .reporting_component
+        .filter(|c| !c.is_empty())
+        .or_else(|| event.source.and_then(|s| s.component));
+
+    Some(K8sEvent {
+        uid,
+        namespace: event
+            .metadata
+            .namespace
+            .unwrap_or_else(|| namespace.to_string()),
+        object_kind: event.involved_object.kind.unwrap_or_default(),
+        object_name: event.involved_object.name.unwrap_or_default(),
+        r#type: event.type_.unwrap_or_else(|| "Normal".to_string()),
+        reason: event.reason.unwrap_or_default(),
+        message: event.message.unwrap_or_default(),
+        source,
+        count: event.count.map_or(1, |c| c.max(1) as u32),
+        first_seen: Timestamp::from_datetime(first_seen),
+        last_seen: Timestamp::from_datetime(last_seen),
+    })
+}
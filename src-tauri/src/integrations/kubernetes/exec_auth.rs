@@ -0,0 +1,344 @@
+//! Exec-based kubeconfig credentials.
+//!
+//! Kubeconfigs of managed clusters get their tokens from a credential
+//! plugin (`aws eks get-token`, `gke-gcloud-auth-plugin`, `kubelogin`). An
+//! app started from the desktop (Finder, a launcher) doesn't inherit the
+//! PATH of the user's shell, where such tools are installed, so the plugin
+//! is looked up on the login shell's PATH and common install locations, and
+//! runs with that PATH so it can find its own tools (e.g., `gcloud`). A
+//! missing plugin fails when the client is built, with `ExecPluginNotFound`,
+//! instead of on the first request with an opaque error.
+//!
+//! Starting the login shell blocks, so the PATH is computed once on the
+//! blocking pool rather than on the async task building the client.
+
+use kube::config::{ExecConfig, Kubeconfig};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::OnceCell;
+
+use crate::integrations::IntegrationError;
+
+/// Time allowed for the login shell to report its PATH.
+#[cfg(unix)]
+const SHELL_PATH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Install locations of command line tools that are often missing from the
+/// PATH of desktop apps.
+const EXTRA_DIRS: [&str; 5] = [
+    "/opt/homebrew/bin",
+    "/usr/local/bin",
+    "/snap/bin",
+    "~/.local/bin",
+    "~/google-cloud-sdk/bin",
+];
+
+/// How to install well-known plugins.
+const INSTALL_HINTS: [(&str, &str); 4] = [
+    ("aws", "Install the AWS CLI (https://aws.amazon.com/cli/)"),
+    (
+        "gke-gcloud-auth-plugin",
+        "Install it with `gcloud components install gke-gcloud-auth-plugin`",
+    ),
+    (
+        "kubelogin",
+        "Install kubelogin (https://azure.github.io/kubelogin/)",
+    ),
+    (
+        "aws-iam-authenticator",
+        "Install aws-iam-authenticator (https://github.com/kubernetes-sigs/aws-iam-authenticator)",
+    ),
+];
+
+/// PATH plugins are looked up on and run with.
+static SEARCH_PATH: OnceCell<String> = OnceCell::const_new();
+
+async fn search_path() -> &'static str {
+    SEARCH_PATH
+        .get_or_init(|| async {
+            tokio::task::spawn_blocking(compute_search_path)
+                .await
+                .unwrap_or_else(|e| {
+                    log::warn!("Failed to compute the credential plugin PATH: {e}");
+                    std::env::var("PATH").unwrap_or_default()
+                })
+        })
+        .await
+}
+
+fn compute_search_path() -> String {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    let shell_path = login_shell_path();
+    let process_path = std::env::var_os("PATH");
+    let home = dirs::home_dir();
+    let extra = EXTRA_DIRS.iter().map(|dir| match dir.strip_prefix("~/") {
+        Some(relative) => home.as_ref().map(|home| home.join(relative)),
+        None => Some(PathBuf::from(dir)),
+    });
+
+    let candidates = shell_path
+        .iter()
+        .flat_map(std::env::split_paths)
+        .chain(process_path.iter().flat_map(std::env::split_paths))
+        .chain(extra.flatten());
+    for dir in candidates {
+        if !dir.as_os_str().is_empty() && !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    std::env::join_paths(dirs)
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// PATH of the user's interactive login shell, which reads the profile and
+/// rc files tools are usually added to the PATH in. The shell is killed if
+/// it doesn't answer in time (e.g., a profile waiting for input).
+#[cfg(unix)]
+fn login_shell_path() -> Option<String> {
+    use std::io::Read;
+    use std::process::{Command, Stdio};
+    use std::sync::mpsc;
+
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+    let mut child = match Command::new(&shell)
+        .args(["-i", "-l", "-c", "printf '\\n%s' \"$PATH\""])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            log::warn!("Failed to start login shell {shell}: {e}");
+            return None;
+        }
+    };
+    let mut stdout = child.stdout.take()?;
+    let (done, result) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut output = String::new();
+        let _ = done.send(stdout.read_to_string(&mut output).map(|_| output));
+    });
+
+    let output = match result.recv_timeout(SHELL_PATH_TIMEOUT) {
+        Ok(output) => output.ok(),
+        Err(_) => {
+            log::warn!("Login shell did not report its PATH in time");
+            let _ = child.kill();
+            None
+        }
+    };
+    let status = child.wait().ok()?;
+
+    // Profiles may print, so the PATH is the last line
+    output
+        .filter(|_| status.success())?
+        .lines()
+        .last()
+        .map(str::to_string)
+        .filter(|path| !path.is_empty())
+}
+
+#[cfg(not(unix))]
+fn login_shell_path() -> Option<String> {
+    // Windows apps get the user's PATH from the registry
+    None
+}
+
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
+
+/// Finds a plugin: commands with a directory are taken relative to the
+/// kubeconfig's directory, bare names are looked up on `search_path`.
+fn resolve_command(command: &str, kubeconfig_dir: &Path, search_path: &str) -> Option<PathBuf> {
+    if command.contains(['/', '\\']) {
+        let path = kubeconfig_dir.join(command);
+        return is_executable(&path).then_some(path);
+    }
+
+    let extensions: &[&str] = if cfg!(windows) {
+        &["", ".exe", ".cmd", ".bat"]
+    } else {
+        &[""]
+    };
+    std::env::split_paths(search_path).find_map(|dir| {
+        extensions
+            .iter()
+            .map(|extension| dir.join(format!("{}{}", command, extension)))
+            .find(|path| is_executable(path))
+    })
+}
+
+fn missing_plugin(command: &str, search_path: &str) -> IntegrationError {
+    let name = Path::new(command)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(command);
+    let hint = INSTALL_HINTS
+        .iter()
+        .find(|(plugin, _)| *plugin == name)
+        .map(|(_, hint)| format!("{}. ", hint))
+        .unwrap_or_default();
+    IntegrationError::ExecPluginNotFound {
+        command: command.to_string(),
+        message: format!(
+            "{}The kubeconfig runs it for credentials; searched {}",
+            hint, search_path
+        ),
+    }
+}
+
+/// Resolves the credential plugin of the current context's user to a full
+/// path and sets the PATH it runs with (unless the kubeconfig sets one).
+pub(super) async fn prepare(
+    kubeconfig: &mut Kubeconfig,
+    kubeconfig_dir: &Path,
+) -> Result<(), IntegrationError> {
+    // Only computed when a plugin is used, as it starts a shell
+    if current_exec(kubeconfig).is_none_or(|exec| exec.command.is_none()) {
+        return Ok(());
+    }
+    prepare_with(kubeconfig, kubeconfig_dir, search_path().await)
+}
+
+/// Exec config of the current context's user, if it uses one.
+fn current_exec(kubeconfig: &mut Kubeconfig) -> Option<&mut ExecConfig> {
+    let user = kubeconfig
+        .current_context
+        .as_ref()
+        .and_then(|current| kubeconfig.contexts.iter().find(|c| &c.name == current))
+        .and_then(|named| named.context.as_ref())
+        .map(|context| context.user.clone())?;
+    kubeconfig
+        .auth_infos
+        .iter_mut()
+        .filter(|named| named.name == user)
+        .find_map(|named| named.auth_info.as_mut()?.exec.as_mut())
+}
+
+fn prepare_with(
+    kubeconfig: &mut Kubeconfig,
+    kubeconfig_dir: &Path,
+    search_path: &str,
+) -> Result<(), IntegrationError> {
+    let Some(exec) = current_exec(kubeconfig) else {
+        return Ok(());
+    };
+    let Some(command) = exec.command.clone() else {
+        return Ok(());
+    };
+
+    let resolved = resolve_command(&command, kubeconfig_dir, search_path)
+        .ok_or_else(|| missing_plugin(&command, search_path))?;
+    log::debug!(
+        "Kubeconfig credential plugin {} resolved to {}",
+        command,
+        resolved.display()
+    );
+    exec.command = Some(resolved.to_string_lossy().into_owned());
+
+    let env = exec.env.get_or_insert_with(Vec::new);
+    if !env
+        .iter()
+        .any(|var| var.get("name").map(String::as_str) == Some("PATH"))
+    {
+        env.push(HashMap::from([
+            ("name".to_string(), "PATH".to_string()),
+            ("value".to_string(), search_path.to_string()),
+        ]));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kubeconfig(command: &str) -> Kubeconfig {
+        Kubeconfig::from_yaml(&format!(
+            r#"
+apiVersion: v1
+kind: Config
+current-context: eks
+contexts:
+- name: eks
+  context: {{ cluster: eks, user: eks-user }}
+clusters:
+- name: eks
+  cluster: {{ server: "https://eks.example.com" }}
+users:
+- name: other
+  user:
+    exec: {{ apiVersion: client.authentication.k8s.io/v1beta1, command: not-used }}
+- name: eks-user
+  user:
+    exec:
+      apiVersion: client.authentication.k8s.io/v1beta1
+      command: {}
+      args: [eks, get-token, --cluster-name, prod]
+"#,
+            command
+        ))
+        .unwrap()
+    }
+
+    fn exec_of(kubeconfig: &Kubeconfig) -> &kube::config::ExecConfig {
+        kubeconfig.auth_infos[1]
+            .auth_info
+            .as_ref()
+            .unwrap()
+            .exec
+            .as_ref()
+            .unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_prepare_resolves_plugin() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("ops-flow-exec-auth-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let plugin = dir.join("aws");
+        std::fs::write(&plugin, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&plugin, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let search_path = format!("/nonexistent:{}", dir.display());
+
+        let mut config = kubeconfig("aws");
+        prepare_with(&mut config, Path::new("/"), &search_path).unwrap();
+        let exec = exec_of(&config);
+        assert_eq!(exec.command.as_deref(), plugin.to_str());
+        assert_eq!(
+            exec.env.as_ref().unwrap()[0].get("value"),
+            Some(&search_path)
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_prepare_reports_missing_plugin() {
+        let mut config = kubeconfig("gke-gcloud-auth-plugin");
+        let error = prepare_with(&mut config, Path::new("/"), "/nonexistent").unwrap_err();
+        match error {
+            IntegrationError::ExecPluginNotFound { command, message } => {
+                assert_eq!(command, "gke-gcloud-auth-plugin");
+                assert!(message.starts_with("Install it with `gcloud components install"));
+            }
+            other => panic!("Expected ExecPluginNotFound, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Container image inventory.
+//!
+//! Aggregates the images of running pods, so questions like "where is
+//! `api:1.2` still running?" can be answered across namespaces.
+
+use k8s_openapi::api::core::v1::Pod;
+use kube::Api;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::adapter::KubernetesAdapter;
+use super::types::K8sImageUsage;
+use crate::integrations::IntegrationError;
+
+impl KubernetesAdapter {
+    /// Lists the images of the running pods in some namespaces, or in the
+    /// whole cluster when `namespaces` is empty, sorted by image.
+    pub async fn fetch_image_inventory(
+        &self,
+        namespaces: &[String],
+    ) -> Result<Vec<K8sImageUsage>, IntegrationError> {
+        log::debug!("Fetching image inventory in namespaces: {:?}", namespaces);
+
+        let list = |api: Api<Pod>, scope: String| async move {
+            api.list(&Default::default()).await.map_err(|e| {
+                log::error!("Failed to list pods in {}: {}", scope, e);
+                IntegrationError::NetworkError {
+                    message: format!("Failed to list pods: {}", e),
+                }
+            })
+        };
+
+        let mut pods = Vec::new();
+        if namespaces.is_empty() {
+            pods.extend(list(Api::all(self.client.clone()), "all namespaces".to_string()).await?);
+        } else {
+            for namespace in namespaces {
+                let api = Api::namespaced(self.client.clone(), namespace);
+                pods.extend(list(api, format!("namespace {}", namespace)).await?);
+            }
+        }
+
+        Ok(aggregate_images(&pods))
+    }
+}
+
+/// Counts the containers of running pods per image.
+fn aggregate_images(pods: &[Pod]) -> Vec<K8sImageUsage> {
+    let mut usage: BTreeMap<String, K8sImageUsage> = BTreeMap::new();
+    let mut namespaces: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut digests: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for pod in pods {
+        let Some(status) = &pod.status else {
+            continue;
+        };
+        if status.phase.as_deref() != Some("Running") {
+            continue;
+        }
+        let namespace = pod.metadata.namespace.clone().unwrap_or_default();
+        let mut pod_images = BTreeSet::new();
+
+        for container in status.container_statuses.iter().flatten() {
+            if container
+                .state
+                .as_ref()
+                .and_then(|s| s.running.as_ref())
+                .is_none()
+            {
+                continue;
+            }
+            // The spec holds the image as written; the status may resolve it
+            // (e.g., "docker.io/library/nginx:1.25" for "nginx:1.25")
+            let image = pod
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.containers.iter().find(|c| c.name == container.name))
+                .and_then(|c| c.image.clone())
+                .unwrap_or_else(|| container.image.clone());
+
+            let entry = usage.entry(image.clone()).or_insert_with(|| K8sImageUsage {
+                image: image.clone(),
+                digests: Vec::new(),
+                pods: 0,
+                containers: 0,
+                namespaces: Vec::new(),
+            });
+            entry.containers += 1;
+            if pod_images.insert(image.clone()) {
+                entry.pods += 1;
+            }
+            namespaces
+                .entry(image.clone())
+                .or_default()
+                .insert(namespace.clone());
+            if let Some(digest) = digest_of(&container.image_id) {
+                digests.entry(image).or_default().insert(digest.to_string());
+            }
+        }
+    }
+
+    usage
+        .into_values()
+        .map(|mut entry| {
+            entry.namespaces = namespaces
+                .remove(&entry.image)
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            entry.digests = digests
+                .remove(&entry.image)
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            entry
+        })
+        .collect()
+}
+
+/// Digest of an image ID like `docker-pullable://nginx@sha256:...`.
+fn digest_of(image_id: &str) -> Option<&str> {
+    image_id
+        .rsplit_once('@')
+        .map(|(_, digest)| digest)
+        .or_else(|| image_id.strip_prefix("sha256:").map(|_| image_id))
+        .filter(|digest| !digest.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pod(namespace: &str, phase: &str, containers: &[(&str, &str, bool)]) -> Pod {
+        serde_json::from_value(json!({
+            "metadata": { "name": "pod", "namespace": namespace },
+            "spec": {
+                "containers": containers
+                    .iter()
+                    .map(|(name, image, _)| json!({ "name": name, "image": image }))
+                    .collect::<Vec<_>>()
+            },
+            "status": {
+                "phase": phase,
+                "containerStatuses": containers
+                    .iter()
+                    .map(|(name, image, running)| json!({
+                        "name": name,
+                        "image": format!("docker.io/library/{image}"),
+                        "imageID": format!("docker.io/library/{image}@sha256:{name}"),
+                        "ready": *running,
+                        "restartCount": 0,
+                        "state": if *running {
+                            json!({ "running": {} })
+                        } else {
+                            json!({ "waiting": { "reason": "CrashLoopBackOff" } })
+                        }
+                    }))
+                    .collect::<Vec<_>>()
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_aggregate_images_counts_running_containers() {
+        let pods = vec![
+            pod(
+                "shop-prod",
+                "Running",
+                &[("api", "api:1.2", true), ("proxy", "envoy:1.30", true)],
+            ),
+            pod("shop-prod", "Running", &[("api", "api:1.2", true)]),
+            pod("shop-staging", "Running", &[("api", "api:1.2", true)]),
+            pod("shop-staging", "Running", &[("api", "api:1.3", false)]),
+            pod("jobs", "Succeeded", &[("job", "migrate:1.0", true)]),
+        ];
+
+        let images = aggregate_images(&pods);
+
+        assert_eq!(
+            images,
+            vec![
+                K8sImageUsage {
+                    image: "api:1.2".to_string(),
+                    digests: vec!["sha256:api".to_string()],
+                    pods: 3,
+                    containers: 3,
+                    namespaces: vec!["shop-prod".to_string(), "shop-staging".to_string()],
+                },
+                K8sImageUsage {
+                    image: "envoy:1.30".to_string(),
+                    digests: vec!["sha256:proxy".to_string()],
+                    pods: 1,
+                    containers: 1,
+                    namespaces: vec!["shop-prod".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_digest_of() {
+        assert_eq!(
+            digest_of("docker-pullable://nginx@sha256:abc"),
+            Some("sha256:abc")
+        );
+        assert_eq!(digest_of("sha256:abc"), Some("sha256:abc"));
+        assert_eq!(digest_of(""), None);
+    }
+}
@@ -3,7 +3,24 @@
 //! Implements the IntegrationAdapter trait for Kubernetes API interactions.
 
 mod adapter;
+mod apply;
+mod bulk;
+mod client_cache;
+mod costs;
+mod disruption;
+mod events;
+mod exec_auth;
+mod images;
+mod revisions;
+mod rollouts;
 mod types;
+mod workloads;
 
 pub use adapter::KubernetesAdapter;
-pub use types::{K8sNamespace, K8sPod, K8sService};
+pub use apply::{plan_waves, ApplyWave};
+pub use costs::{estimate_monthly_cost, rate_for};
+pub use types::{
+    K8sBlueGreenSwitch, K8sBulkPodResult, K8sCanaryStatus, K8sDeploymentRevision,
+    K8sDeploymentState, K8sEnvironmentCost, K8sEvent, K8sEvictionSafety, K8sImageUpdate,
+    K8sImageUsage, K8sIngressHost, K8sNamespace, K8sPod, K8sPodAction, K8sRollback, K8sService,
+};
@@ -0,0 +1,411 @@
+//! Deployment revision history and rollback.
+//!
+//! Kubernetes keeps old ReplicaSets of a deployment (up to
+//! `revisionHistoryLimit`), each annotated with the revision it was created
+//! for. Rolling back copies the pod template of an older ReplicaSet onto the
+//! deployment, the same way `kubectl rollout undo` does.
+
+use k8s_openapi::api::apps::v1::{Deployment, ReplicaSet};
+use k8s_openapi::api::core::v1::PodTemplateSpec;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use kube::api::{ListParams, Patch, PatchParams};
+use kube::Api;
+
+use super::adapter::KubernetesAdapter;
+use super::rollouts::get_deployment;
+use super::types::{K8sContainerImage, K8sDeploymentRevision, K8sRollback};
+use crate::integrations::IntegrationError;
+use crate::types::Timestamp;
+
+/// Annotation holding the revision of a deployment and its ReplicaSets.
+const REVISION_ANNOTATION: &str = "deployment.kubernetes.io/revision";
+
+/// Annotation describing why a revision was created.
+const CHANGE_CAUSE_ANNOTATION: &str = "kubernetes.io/change-cause";
+
+/// Label the deployment controller adds to ReplicaSet pod templates.
+const POD_TEMPLATE_HASH_LABEL: &str = "pod-template-hash";
+
+impl KubernetesAdapter {
+    /// Lists the revisions of a deployment, newest first.
+    pub async fn fetch_deployment_revisions(
+        &self,
+        namespace: &str,
+        deployment: &str,
+    ) -> Result<Vec<K8sDeploymentRevision>, IntegrationError> {
+        log::debug!("Fetching revisions of {}/{}", namespace, deployment);
+
+        let api: Api<Deployment> = Api::namespaced(self.client.clone(), namespace);
+        let current = get_deployment(&api, namespace, deployment).await?;
+        let replica_sets = self.owned_replica_sets(namespace, &current).await?;
+
+        Ok(revisions(&current, &replica_sets))
+    }
+
+    /// Rolls a deployment back to `to_revision`, or to the revision before
+    /// the current one when `None`.
+    pub async fn rollback_deployment(
+        &self,
+        namespace: &str,
+        deployment: &str,
+        to_revision: Option<u32>,
+    ) -> Result<K8sRollback, IntegrationError> {
+        log::debug!(
+            "Rolling back {}/{} to revision {:?}",
+            namespace,
+            deployment,
+            to_revision
+        );
+
+        let api: Api<Deployment> = Api::namespaced(self.client.clone(), namespace);
+        let current = get_deployment(&api, namespace, deployment).await?;
+        let replica_sets = self.owned_replica_sets(namespace, &current).await?;
+        let from_revision = revision_of(&current.metadata);
+
+        let target = pick_target(&replica_sets, from_revision, to_revision)?;
+        let to_revision = revision_of(&target.metadata).unwrap_or_default();
+        let template = rollback_template(target)?;
+        let containers = template_containers(&template);
+        let patch = rollback_patch(&current, template, to_revision)?;
+
+        api.patch(
+            deployment,
+            &PatchParams::default(),
+            &Patch::Json::<()>(patch),
+        )
+        .await
+        .map_err(|e| {
+            log::error!(
+                "Failed to roll back deployment {}/{}: {}",
+                namespace,
+                deployment,
+                e
+            );
+            IntegrationError::NetworkError {
+                message: format!("Failed to roll back deployment: {}", e),
+            }
+        })?;
+
+        log::info!(
+            "Rolled back {}/{} from revision {:?} to {}",
+            namespace,
+            deployment,
+            from_revision,
+            to_revision
+        );
+
+        Ok(K8sRollback {
+            namespace: namespace.to_string(),
+            deployment: deployment.to_string(),
+            from_revision,
+            to_revision,
+            containers,
+        })
+    }
+
+    /// Lists the ReplicaSets owned by a deployment.
+    async fn owned_replica_sets(
+        &self,
+        namespace: &str,
+        deployment: &Deployment,
+    ) -> Result<Vec<ReplicaSet>, IntegrationError> {
+        let selector = deployment
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.selector.match_labels.as_ref())
+            .map(|labels| {
+                labels
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, v))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .unwrap_or_default();
+
+        let api: Api<ReplicaSet> = Api::namespaced(self.client.clone(), namespace);
+        let replica_sets = api
+            .list(&ListParams::default().labels(&selector))
+            .await
+            .map_err(|e| {
+                log::error!(
+                    "Failed to list ReplicaSets in namespace {}: {}",
+                    namespace,
+                    e
+                );
+                IntegrationError::NetworkError {
+                    message: format!("Failed to list ReplicaSets: {}", e),
+                }
+            })?;
+
+        let uid = deployment.metadata.uid.as_deref();
+        Ok(replica_sets
+            .into_iter()
+            .filter(|rs| {
+                rs.metadata
+                    .owner_references
+                    .iter()
+                    .flatten()
+                    .any(|owner| owner.kind == "Deployment" && Some(owner.uid.as_str()) == uid)
+            })
+            .collect())
+    }
+}
+
+fn revision_of(metadata: &ObjectMeta) -> Option<u32> {
+    metadata
+        .annotations
+        .as_ref()?
+        .get(REVISION_ANNOTATION)?
+        .parse()
+        .ok()
+}
+
+fn template_containers(template: &PodTemplateSpec) -> Vec<K8sContainerImage> {
+    template
+        .spec
+        .as_ref()
+        .map(|pod| {
+            pod.containers
+                .iter()
+                .map(|c| K8sContainerImage {
+                    name: c.name.clone(),
+                    image: c.image.clone().unwrap_or_default(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Builds the revision list of a deployment from its ReplicaSets.
+fn revisions(deployment: &Deployment, replica_sets: &[ReplicaSet]) -> Vec<K8sDeploymentRevision> {
+    let current = revision_of(&deployment.metadata);
+    let mut revisions: Vec<K8sDeploymentRevision> = replica_sets
+        .iter()
+        .filter_map(|rs| {
+            let revision = revision_of(&rs.metadata)?;
+            Some(K8sDeploymentRevision {
+                revision,
+                replica_set: rs.metadata.name.clone().unwrap_or_default(),
+                change_cause: rs
+                    .metadata
+                    .annotations
+                    .as_ref()
+                    .and_then(|a| a.get(CHANGE_CAUSE_ANNOTATION))
+                    .cloned(),
+                containers: rs
+                    .spec
+                    .as_ref()
+                    .and_then(|spec| spec.template.as_ref())
+                    .map(template_containers)
+                    .unwrap_or_default(),
+                created_at: rs
+                    .metadata
+                    .creation_timestamp
+                    .as_ref()
+                    .map(|t| Timestamp::from_datetime(t.0)),
+                replicas: rs.status.as_ref().map_or(0, |s| s.replicas.max(0) as u32),
+                current: Some(revision) == current,
+            })
+        })
+        .collect();
+    revisions.sort_by_key(|r| std::cmp::Reverse(r.revision));
+    revisions
+}
+
+/// Picks the ReplicaSet to roll back to.
+fn pick_target(
+    replica_sets: &[ReplicaSet],
+    current: Option<u32>,
+    to_revision: Option<u32>,
+) -> Result<&ReplicaSet, IntegrationError> {
+    if to_revision.is_some() && to_revision == current {
+        return Err(IntegrationError::ConfigError {
+            message: format!(
+                "Revision {} is already the current revision",
+                to_revision.unwrap_or_default()
+            ),
+        });
+    }
+
+    let with_revision = replica_sets
+        .iter()
+        .filter_map(|rs| revision_of(&rs.metadata).map(|revision| (revision, rs)));
+    let target = match to_revision {
+        Some(wanted) => with_revision
+            .filter(|(revision, _)| *revision == wanted)
+            .map(|(_, rs)| rs)
+            .next(),
+        // The newest revision older than the current one
+        None => with_revision
+            .filter(|(revision, _)| current.is_none_or(|current| *revision < current))
+            .max_by_key(|(revision, _)| *revision)
+            .map(|(_, rs)| rs),
+    };
+
+    target.ok_or_else(|| IntegrationError::ConfigError {
+        message: match to_revision {
+            Some(wanted) => format!("Revision {} not found in the deployment history", wanted),
+            None => "No previous revision to roll back to".to_string(),
+        },
+    })
+}
+
+/// Pod template of a ReplicaSet, without the label the controller adds.
+fn rollback_template(replica_set: &ReplicaSet) -> Result<PodTemplateSpec, IntegrationError> {
+    let mut template = replica_set
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.template.clone())
+        .ok_or_else(|| IntegrationError::ConfigError {
+            message: format!(
+                "ReplicaSet {} has no pod template",
+                replica_set.metadata.name.as_deref().unwrap_or_default()
+            ),
+        })?;
+    if let Some(labels) = template.metadata.as_mut().and_then(|m| m.labels.as_mut()) {
+        labels.remove(POD_TEMPLATE_HASH_LABEL);
+    }
+    Ok(template)
+}
+
+/// JSON patch replacing the pod template and recording the change cause.
+///
+/// The resource version test makes the rollback fail instead of overwriting
+/// a change made since the deployment was read.
+fn rollback_patch(
+    deployment: &Deployment,
+    template: PodTemplateSpec,
+    to_revision: u32,
+) -> Result<json_patch::Patch, IntegrationError> {
+    let mut patch = vec![
+        serde_json::json!({
+            "op": "test",
+            "path": "/metadata/resourceVersion",
+            "value": deployment.metadata.resource_version,
+        }),
+        serde_json::json!({ "op": "replace", "path": "/spec/template", "value": template }),
+    ];
+    let cause = format!("Rolled back to revision {}", to_revision);
+    if deployment.metadata.annotations.is_some() {
+        patch.push(serde_json::json!({
+            "op": "add",
+            "path": "/metadata/annotations/kubernetes.io~1change-cause",
+            "value": cause,
+        }));
+    } else {
+        patch.push(serde_json::json!({
+            "op": "add",
+            "path": "/metadata/annotations",
+            "value": { CHANGE_CAUSE_ANNOTATION: cause },
+        }));
+    }
+
+    serde_json::from_value(serde_json::Value::Array(patch)).map_err(|e| {
+        IntegrationError::ConfigError {
+            message: format!("Failed to build rollback patch: {}", e),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn replica_set(name: &str, revision: u32, image: &str) -> ReplicaSet {
+        serde_json::from_value(json!({
+            "metadata": {
+                "name": name,
+                "annotations": {
+                    "deployment.kubernetes.io/revision": revision.to_string(),
+                    "kubernetes.io/change-cause": format!("deploy {image}"),
+                },
+            },
+            "spec": {
+                "selector": { "matchLabels": { "app": "api" } },
+                "template": {
+                    "metadata": { "labels": { "app": "api", "pod-template-hash": name } },
+                    "spec": { "containers": [{ "name": "api", "image": image }] },
+                },
+            },
+            "status": { "replicas": if revision == 3 { 2 } else { 0 } },
+        }))
+        .unwrap()
+    }
+
+    fn history() -> (Deployment, Vec<ReplicaSet>) {
+        let deployment = serde_json::from_value(json!({
+            "metadata": {
+                "name": "api",
+                "resourceVersion": "42",
+                "annotations": { "deployment.kubernetes.io/revision": "3" },
+            },
+        }))
+        .unwrap();
+        let replica_sets = vec![
+            replica_set("api-a", 1, "api:1.0"),
+            replica_set("api-c", 3, "api:1.2"),
+            replica_set("api-b", 2, "api:1.1"),
+        ];
+        (deployment, replica_sets)
+    }
+
+    #[test]
+    fn test_revisions_newest_first() {
+        let (deployment, replica_sets) = history();
+        let revisions = revisions(&deployment, &replica_sets);
+
+        assert_eq!(
+            revisions.iter().map(|r| r.revision).collect::<Vec<_>>(),
+            vec![3, 2, 1]
+        );
+        assert!(revisions[0].current);
+        assert!(!revisions[1].current);
+        assert_eq!(revisions[0].replicas, 2);
+        assert_eq!(revisions[1].change_cause.as_deref(), Some("deploy api:1.1"));
+        assert_eq!(revisions[2].containers[0].image, "api:1.0");
+    }
+
+    #[test]
+    fn test_pick_target() {
+        let (_, replica_sets) = history();
+        let name = |rs: &ReplicaSet| rs.metadata.name.clone().unwrap();
+
+        assert_eq!(
+            name(pick_target(&replica_sets, Some(3), None).unwrap()),
+            "api-b"
+        );
+        assert_eq!(
+            name(pick_target(&replica_sets, Some(3), Some(1)).unwrap()),
+            "api-a"
+        );
+        assert!(pick_target(&replica_sets, Some(3), Some(3)).is_err());
+        assert!(pick_target(&replica_sets, Some(3), Some(7)).is_err());
+        assert!(pick_target(&replica_sets, Some(1), None).is_err());
+    }
+
+    #[test]
+    fn test_rollback_template_drops_hash_label() {
+        let (_, replica_sets) = history();
+        let template = rollback_template(&replica_sets[0]).unwrap();
+        let labels = template.metadata.unwrap().labels.unwrap();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels.get("app").map(String::as_str), Some("api"));
+    }
+
+    #[test]
+    fn test_rollback_patch() {
+        let (deployment, replica_sets) = history();
+        let template = rollback_template(&replica_sets[1]).unwrap();
+        let patch =
+            serde_json::to_value(rollback_patch(&deployment, template, 1).unwrap()).unwrap();
+
+        assert_eq!(patch[0]["value"], "42");
+        assert_eq!(patch[1]["path"], "/spec/template");
+        assert_eq!(
+            patch[2]["path"],
+            "/metadata/annotations/kubernetes.io~1change-cause"
+        );
+        assert_eq!(patch[2]["value"], "Rolled back to revision 1");
+    }
+}
@@ -0,0 +1,671 @@
+//! Progressive rollout helpers.
+//!
+//! Canaries use native label-based traffic splitting: the canary deployment
+//! runs pods with the same labels the stable deployment's Service selects, so
+//! traffic is shared in proportion to the number of ready pods. The canary is
+//! told apart by an extra `track: canary` label.
+//!
+//! Blue-green switches flip the `color` label in a Service selector between
+//! two deployments that otherwise run with identical labels.
+
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::{Endpoints, Service};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use kube::api::{DeleteParams, ListParams, Patch, PatchParams};
+use kube::Api;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use super::adapter::KubernetesAdapter;
+use super::types::{K8sBlueGreenSwitch, K8sCanaryStatus, K8sImageUpdate};
+use crate::integrations::IntegrationError;
+
+/// Label added to canary pods (and the canary selector).
+const TRACK_LABEL: &str = "track";
+
+/// Annotation linking a canary deployment to its stable deployment.
+const CANARY_OF_ANNOTATION: &str = "ops-flow/canary-of";
+
+/// Service selector label switched between blue and green deployments.
+const COLOR_LABEL: &str = "color";
+
+/// Health checks run after a blue-green switch before it is considered good.
+const POST_SWITCH_CHECKS: u32 = 6;
+
+/// Delay between post-switch health checks.
+const POST_SWITCH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Field manager used for server-side apply.
+pub(super) const FIELD_MANAGER: &str = "ops-flow";
+
+/// Name of the canary deployment for a stable deployment.
+pub fn canary_name(deployment: &str) -> String {
+    format!("{}-canary", deployment)
+}
+
+impl KubernetesAdapter {
+    /// Creates or updates the canary of a deployment.
+    ///
+    /// The canary copies the stable pod template with `image` on the first
+    /// container and gets enough replicas to receive roughly `percent` of the
+    /// traffic.
+    pub async fn start_canary(
+        &self,
+        namespace: &str,
+        deployment: &str,
+        image: &str,
+        percent: u32,
+    ) -> Result<K8sCanaryStatus, IntegrationError> {
+        log::debug!(
+            "Starting canary of {}/{} with {} at {}%",
+            namespace,
+            deployment,
+            image,
+            percent
+        );
+
+        let api: Api<Deployment> = Api::namespaced(self.client.clone(), namespace);
+        let stable = get_deployment(&api, namespace, deployment).await?;
+        let (canary, status) = build_canary(&stable, namespace, deployment, image, percent)?;
+
+        api.patch(
+            &status.canary_deployment,
+            &PatchParams::apply(FIELD_MANAGER).force(),
+            &Patch::Apply(&canary),
+        )
+        .await
+        .map_err(|e| {
+            log::error!(
+                "Failed to apply canary {}/{}: {}",
+                namespace,
+                status.canary_deployment,
+                e
+            );
+            IntegrationError::NetworkError {
+                message: format!("Failed to apply canary deployment: {}", e),
+            }
+        })?;
+
+        log::info!(
+            "Canary {}/{} running {} with {} of {} replicas",
+            namespace,
+            status.canary_deployment,
+            image,
+            status.canary_replicas,
+            status.canary_replicas + status.stable_replicas
+        );
+        Ok(status)
+    }
+
+    /// Rolls the canary's images out to the stable deployment and removes the canary.
+    pub async fn promote_canary(
+        &self,
+        namespace: &str,
+        deployment: &str,
+    ) -> Result<Vec<K8sImageUpdate>, IntegrationError> {
+        log::debug!("Promoting canary of {}/{}", namespace, deployment);
+
+        let api: Api<Deployment> = Api::namespaced(self.client.clone(), namespace);
+        let canary = get_deployment(&api, namespace, &canary_name(deployment)).await?;
+        let stable = get_deployment(&api, namespace, deployment).await?;
+
+        let stable_images = container_images(&stable);
+        let updates: Vec<K8sImageUpdate> = container_images(&canary)
+            .into_iter()
+            .filter_map(|(container, image)| {
+                let image = image?;
+                let previous_image = stable_images
+                    .iter()
+                    .find(|(name, _)| *name == container)?
+                    .1
+                    .clone();
+                (previous_image.as_deref() != Some(image.as_str())).then(|| K8sImageUpdate {
+                    namespace: namespace.to_string(),
+                    deployment: deployment.to_string(),
+                    container,
+                    previous_image,
+                    image,
+                })
+            })
+            .collect();
+
+        if !updates.is_empty() {
+            // Strategic merge patches merge the container list by name.
+            let patch = serde_json::json!({
+                "spec": { "template": { "spec": { "containers": updates
+                    .iter()
+                    .map(|u| serde_json::json!({ "name": u.container, "image": u.image }))
+                    .collect::<Vec<_>>() } } }
+            });
+            api.patch(
+                deployment,
+                &PatchParams::default(),
+                &Patch::Strategic(patch),
+            )
+            .await
+            .map_err(|e| {
+                log::error!(
+                    "Failed to promote canary of {}/{}: {}",
+                    namespace,
+                    deployment,
+                    e
+                );
+                IntegrationError::NetworkError {
+                    message: format!("Failed to update stable deployment: {}", e),
+                }
+            })?;
+        }
+
+        self.delete_canary(&api, namespace, deployment).await?;
+        log::info!("Promoted canary of {}/{}", namespace, deployment);
+        Ok(updates)
+    }
+
+    /// Removes the canary of a deployment, sending all traffic back to stable pods.
+    pub async fn abort_canary(
+        &self,
+        namespace: &str,
+        deployment: &str,
+    ) -> Result<(), IntegrationError> {
+        log::debug!("Aborting canary of {}/{}", namespace, deployment);
+
+        let api: Api<Deployment> = Api::namespaced(self.client.clone(), namespace);
+        self.delete_canary(&api, namespace, deployment).await?;
+        log::info!("Aborted canary of {}/{}", namespace, deployment);
+        Ok(())
+    }
+
+    async fn delete_canary(
+        &self,
+        api: &Api<Deployment>,
+        namespace: &str,
+        deployment: &str,
+    ) -> Result<(), IntegrationError> {
+        let name = canary_name(deployment);
+        api.delete(&name, &DeleteParams::foreground())
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                log::error!("Failed to delete canary {}/{}: {}", namespace, name, e);
+                if e.to_string().contains("NotFound") {
+                    IntegrationError::NotFound
+                } else {
+                    IntegrationError::NetworkError {
+                        message: format!("Failed to delete canary deployment: {}", e),
+                    }
+                }
+            })
+    }
+}
+
+impl KubernetesAdapter {
+    /// Points a Service at the `target_color` deployment.
+    ///
+    /// The target deployments must be fully ready before the selector changes.
+    /// Afterwards the Service endpoints and the target deployments are checked
+    /// for a short period; if any check fails, the previous color is restored.
+    pub async fn switch_blue_green(
+        &self,
+        namespace: &str,
+        service: &str,
+        target_color: &str,
+    ) -> Result<K8sBlueGreenSwitch, IntegrationError> {
+        log::debug!(
+            "Switching service {}/{} to {}",
+            namespace,
+            service,
+            target_color
+        );
+
+        if target_color != "blue" && target_color != "green" {
+            return Err(IntegrationError::ConfigError {
+                message: format!(
+                    "Target color must be 'blue' or 'green', got '{}'",
+                    target_color
+                ),
+            });
+        }
+
+        let services: Api<Service> = Api::namespaced(self.client.clone(), namespace);
+        let current = services.get(service).await.map_err(|e| {
+            log::error!("Failed to get service {}/{}: {}", namespace, service, e);
+            if e.to_string().contains("NotFound") {
+                IntegrationError::NotFound
+            } else {
+                IntegrationError::NetworkError {
+                    message: format!("Failed to get service: {}", e),
+                }
+            }
+        })?;
+
+        let selector = current
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.selector.clone())
+            .unwrap_or_default();
+        let previous_color =
+            selector
+                .get(COLOR_LABEL)
+                .cloned()
+                .ok_or_else(|| IntegrationError::ConfigError {
+                    message: format!(
+                        "Service {} does not select pods by the '{}' label",
+                        service, COLOR_LABEL
+                    ),
+                })?;
+        let target_selector = with_color(&selector, target_color);
+
+        let deployments = self.check_target_ready(namespace, &target_selector).await?;
+
+        if previous_color != target_color {
+            self.set_service_color(namespace, service, target_color)
+                .await?;
+            log::info!(
+                "Switched service {}/{} from {} to {}",
+                namespace,
+                service,
+                previous_color,
+                target_color
+            );
+
+            if let Err(e) = self
+                .verify_switch(namespace, service, &target_selector)
+                .await
+            {
+                log::warn!(
+                    "Post-switch check of {}/{} failed, reverting to {}: {}",
+                    namespace,
+                    service,
+                    previous_color,
+                    e
+                );
+                self.set_service_color(namespace, service, &previous_color)
+                    .await?;
+                return Err(IntegrationError::ApiError {
+                    status: 503,
+                    message: format!(
+                        "Switch to {} failed health checks and was reverted to {}: {}",
+                        target_color, previous_color, e
+                    ),
+                });
+            }
+        }
+
+        Ok(K8sBlueGreenSwitch {
+            namespace: namespace.to_string(),
+            service: service.to_string(),
+            previous_color,
+            active_color: target_color.to_string(),
+            deployments,
+        })
+    }
+
+    /// Returns the deployments behind `selector`, failing unless all are fully ready.
+    async fn check_target_ready(
+        &self,
+        namespace: &str,
+        selector: &BTreeMap<String, String>,
+    ) -> Result<Vec<String>, IntegrationError> {
+        let api: Api<Deployment> = Api::namespaced(self.client.clone(), namespace);
+        let deployments = api.list(&ListParams::default()).await.map_err(|e| {
+            log::error!("Failed to list deployments in {}: {}", namespace, e);
+            IntegrationError::NetworkError {
+                message: format!("Failed to list deployments: {}", e),
+            }
+        })?;
+
+        let targets: Vec<&Deployment> = deployments
+            .items
+            .iter()
+            .filter(|d| selects_deployment(selector, d))
+            .collect();
+        if targets.is_empty() {
+            return Err(IntegrationError::ConfigError {
+                message: format!(
+                    "No deployment matches selector {}",
+                    format_selector(selector)
+                ),
+            });
+        }
+
+        for deployment in &targets {
+            if let Some(reason) = unready_reason(deployment) {
+                return Err(IntegrationError::ConfigError {
+                    message: format!(
+                        "Deployment {} is not ready: {}",
+                        deployment.metadata.name.as_deref().unwrap_or_default(),
+                        reason
+                    ),
+                });
+            }
+        }
+
+        Ok(targets
+            .iter()
+            .filter_map(|d| d.metadata.name.clone())
+            .collect())
+    }
+
+    /// Repeatedly checks the Service endpoints and target deployments.
+    async fn verify_switch(
+        &self,
+        namespace: &str,
+        service: &str,
+        selector: &BTreeMap<String, String>,
+    ) -> Result<(), IntegrationError> {
+        let endpoints: Api<Endpoints> = Api::namespaced(self.client.clone(), namespace);
+
+        for _ in 0..POST_SWITCH_CHECKS {
+            tokio::time::sleep(POST_SWITCH_CHECK_INTERVAL).await;
+
+            self.check_target_ready(namespace, selector).await?;
+
+            let current =
+                endpoints
+                    .get(service)
+                    .await
+                    .map_err(|e| IntegrationError::NetworkError {
+                        message: format!("Failed to get service endpoints: {}", e),
+                    })?;
+            let ready = current
+                .subsets
+                .unwrap_or_default()
+                .iter()
+                .map(|s| s.addresses.as_ref().map_or(0, Vec::len))
+                .sum::<usize>();
+            if ready == 0 {
+                return Err(IntegrationError::ConfigError {
+                    message: format!("Service {} has no ready endpoints", service),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    async fn set_service_color(
+        &self,
+        namespace: &str,
+        service: &str,
+        color: &str,
+    ) -> Result<(), IntegrationError> {
+        let api: Api<Service> = Api::namespaced(self.client.clone(), namespace);
+        let patch = serde_json::json!({ "spec": { "selector": { COLOR_LABEL: color } } });
+
+        api.patch(service, &PatchParams::default(), &Patch::Merge(patch))
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                log::error!("Failed to patch service {}/{}: {}", namespace, service, e);
+                IntegrationError::NetworkError {
+                    message: format!("Failed to update service selector: {}", e),
+                }
+            })
+    }
+}
+
+pub(super) async fn get_deployment(
+    api: &Api<Deployment>,
+    namespace: &str,
+    name: &str,
+) -> Result<Deployment, IntegrationError> {
+    api.get(name).await.map_err(|e| {
+        log::error!("Failed to get deployment {}/{}: {}", namespace, name, e);
+        if e.to_string().contains("NotFound") {
+            IntegrationError::NotFound
+        } else {
+            IntegrationError::NetworkError {
+                message: format!("Failed to get deployment: {}", e),
+            }
+        }
+    })
+}
+
+/// Returns `(container, image)` pairs of a deployment's pod template.
+fn container_images(deployment: &Deployment) -> Vec<(String, Option<String>)> {
+    deployment
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.template.spec.as_ref())
+        .map(|spec| {
+            spec.containers
+                .iter()
+                .map(|c| (c.name.clone(), c.image.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns `selector` with the color label set to `color`.
+fn with_color(selector: &BTreeMap<String, String>, color: &str) -> BTreeMap<String, String> {
+    let mut selector = selector.clone();
+    selector.insert(COLOR_LABEL.to_string(), color.to_string());
+    selector
+}
+
+/// Whether the pods of a deployment are selected by `selector`.
+fn selects_deployment(selector: &BTreeMap<String, String>, deployment: &Deployment) -> bool {
+    let labels = deployment
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.template.metadata.as_ref())
+        .and_then(|meta| meta.labels.as_ref());
+
+    labels.is_some_and(|labels| {
+        selector
+            .iter()
+            .all(|(key, value)| labels.get(key) == Some(value))
+    })
+}
+
+/// Explains why a deployment is not fully ready, if it isn't.
+pub(super) fn unready_reason(deployment: &Deployment) -> Option<String> {
+    let desired = deployment
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.replicas)
+        .unwrap_or(1);
+    let status = deployment.status.clone().unwrap_or_default();
+    let ready = status.ready_replicas.unwrap_or(0);
+    let updated = status.updated_replicas.unwrap_or(0);
+
+    if desired == 0 {
+        Some("scaled to zero".to_string())
+    } else if ready < desired {
+        Some(format!("{} of {} replicas ready", ready, desired))
+    } else if updated < desired {
+        Some(format!("{} of {} replicas updated", updated, desired))
+    } else {
+        None
+    }
+}
+
+fn format_selector(selector: &BTreeMap<String, String>) -> String {
+    selector
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Number of canary replicas giving the canary about `percent` of the traffic
+/// next to `stable` replicas (at least one).
+pub fn canary_replicas(stable: u32, percent: u32) -> u32 {
+    let percent = percent.clamp(1, 99);
+    (stable * percent).div_ceil(100 - percent).max(1)
+}
+
+/// Builds the canary deployment from the stable one.
+fn build_canary(
+    stable: &Deployment,
+    namespace: &str,
+    deployment: &str,
+    image: &str,
+    percent: u32,
+) -> Result<(Deployment, K8sCanaryStatus), IntegrationError> {
+    if !(1..=99).contains(&percent) {
+        return Err(IntegrationError::ConfigError {
+            message: format!("Canary percent must be between 1 and 99, got {}", percent),
+        });
+    }
+
+    let mut spec = stable
+        .spec
+        .clone()
+        .ok_or_else(|| IntegrationError::ConfigError {
+            message: format!("Deployment {} has no spec", deployment),
+        })?;
+
+    let stable_replicas = spec.replicas.unwrap_or(1).max(0) as u32;
+    if stable_replicas == 0 {
+        return Err(IntegrationError::ConfigError {
+            message: format!("Deployment {} is scaled to zero", deployment),
+        });
+    }
+    let replicas = canary_replicas(stable_replicas, percent);
+
+    let container = spec
+        .template
+        .spec
+        .as_mut()
+        .and_then(|pod| pod.containers.first_mut())
+        .ok_or_else(|| IntegrationError::ConfigError {
+            message: format!("Deployment {} has no containers", deployment),
+        })?;
+    container.image = Some(image.to_string());
+
+    spec.replicas = Some(replicas as i32);
+    spec.selector
+        .match_labels
+        .get_or_insert_with(BTreeMap::new)
+        .insert(TRACK_LABEL.to_string(), "canary".to_string());
+    spec.template
+        .metadata
+        .get_or_insert_with(Default::default)
+        .labels
+        .get_or_insert_with(BTreeMap::new)
+        .insert(TRACK_LABEL.to_string(), "canary".to_string());
+
+    let mut labels = stable.metadata.labels.clone().unwrap_or_default();
+    labels.insert(TRACK_LABEL.to_string(), "canary".to_string());
+    let name = canary_name(deployment);
+
+    let canary = Deployment {
+        metadata: ObjectMeta {
+            name: Some(name.clone()),
+            namespace: Some(namespace.to_string()),
+            labels: Some(labels),
+            annotations: Some(BTreeMap::from([(
+                CANARY_OF_ANNOTATION.to_string(),
+                deployment.to_string(),
+            )])),
+            ..Default::default()
+        },
+        spec: Some(spec),
+        status: None,
+    };
+
+    let total = stable_replicas + replicas;
+    let status = K8sCanaryStatus {
+        namespace: namespace.to_string(),
+        deployment: deployment.to_string(),
+        canary_deployment: name,
+        image: image.to_string(),
+        stable_replicas,
+        canary_replicas: replicas,
+        traffic_percent: (replicas * 100 + total / 2) / total,
+    };
+    Ok((canary, status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stable(replicas: i32) -> Deployment {
+        serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "api", "labels": { "app": "api" }, "resourceVersion": "42" },
+            "spec": {
+                "replicas": replicas,
+                "selector": { "matchLabels": { "app": "api" } },
+                "template": {
+                    "metadata": { "labels": { "app": "api" } },
+                    "spec": { "containers": [{ "name": "api", "image": "registry/api:1.4.1" }] }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_canary_replicas() {
+        assert_eq!(canary_replicas(9, 10), 1);
+        assert_eq!(canary_replicas(4, 20), 1);
+        assert_eq!(canary_replicas(3, 50), 3);
+        assert_eq!(canary_replicas(1, 5), 1);
+    }
+
+    #[test]
+    fn test_build_canary() {
+        let (canary, status) =
+            build_canary(&stable(9), "shop", "api", "registry/api:1.4.2", 10).unwrap();
+
+        assert_eq!(canary.metadata.name.as_deref(), Some("api-canary"));
+        assert_eq!(canary.metadata.resource_version, None);
+        let spec = canary.spec.unwrap();
+        assert_eq!(spec.replicas, Some(1));
+        assert_eq!(
+            spec.selector
+                .match_labels
+                .unwrap()
+                .get("track")
+                .map(String::as_str),
+            Some("canary")
+        );
+        let template_labels = spec.template.metadata.unwrap().labels.unwrap();
+        assert_eq!(template_labels.get("app").map(String::as_str), Some("api"));
+        assert_eq!(
+            spec.template.spec.unwrap().containers[0].image.as_deref(),
+            Some("registry/api:1.4.2")
+        );
+        assert_eq!((status.stable_replicas, status.traffic_percent), (9, 10));
+
+        assert!(build_canary(&stable(9), "shop", "api", "registry/api:1.4.2", 100).is_err());
+        assert!(build_canary(&stable(0), "shop", "api", "registry/api:1.4.2", 10).is_err());
+    }
+
+    fn colored(color: &str, replicas: i32, ready: i32) -> Deployment {
+        serde_json::from_value(serde_json::json!({
+            "metadata": { "name": format!("api-{color}") },
+            "spec": {
+                "replicas": replicas,
+                "selector": { "matchLabels": { "app": "api", "color": color } },
+                "template": { "metadata": { "labels": { "app": "api", "color": color } } }
+            },
+            "status": { "readyReplicas": ready, "updatedReplicas": replicas }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_blue_green_target_selection() {
+        let selector = BTreeMap::from([
+            ("app".to_string(), "api".to_string()),
+            ("color".to_string(), "blue".to_string()),
+        ]);
+        let target = with_color(&selector, "green");
+        assert_eq!(format_selector(&target), "app=api,color=green");
+
+        assert!(selects_deployment(&target, &colored("green", 3, 3)));
+        assert!(!selects_deployment(&target, &colored("blue", 3, 3)));
+    }
+
+    #[test]
+    fn test_unready_reason() {
+        assert_eq!(unready_reason(&colored("green", 3, 3)), None);
+        assert_eq!(
+            unready_reason(&colored("green", 3, 1)).as_deref(),
+            Some("1 of 3 replicas ready")
+        );
+        assert!(unready_reason(&colored("green", 0, 0)).is_some());
+    }
+}
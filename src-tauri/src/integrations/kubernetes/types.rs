@@ -2,6 +2,9 @@
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::BTreeMap;
+
+use crate::types::Timestamp;
 
 /// Kubernetes namespace representation.
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
@@ -10,8 +13,8 @@ pub struct K8sNamespace {
     pub name: String,
     /// Namespace status (e.g., "Active", "Terminating")
     pub status: String,
-    /// Creation timestamp (ISO 8601 format)
-    pub created_at: String,
+    /// Creation time (None if not reported)
+    pub created_at: Option<Timestamp>,
 }
 
 /// Kubernetes pod representation.
@@ -56,3 +59,270 @@ pub struct K8sServicePort {
     /// Protocol (e.g., "TCP", "UDP")
     pub protocol: String,
 }
+
+/// Host routed by a Kubernetes ingress.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sIngressHost {
+    /// Namespace of the ingress
+    pub namespace: String,
+    /// Ingress name
+    pub ingress: String,
+    /// Host name (e.g., "shop.example.com")
+    pub host: String,
+    /// Whether the host is served over TLS
+    pub tls: bool,
+    /// URL of the host (e.g., "https://shop.example.com")
+    pub url: String,
+}
+
+/// Result of changing a deployment container's image.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sImageUpdate {
+    /// Namespace of the deployment
+    pub namespace: String,
+    /// Deployment name
+    pub deployment: String,
+    /// Container whose image was changed
+    pub container: String,
+    /// Image before the update (if set)
+    pub previous_image: Option<String>,
+    /// Image after the update
+    pub image: String,
+}
+
+/// Canary deployment running next to a stable deployment.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sCanaryStatus {
+    /// Namespace of both deployments
+    pub namespace: String,
+    /// Stable deployment name
+    pub deployment: String,
+    /// Canary deployment name (e.g., "api-canary")
+    pub canary_deployment: String,
+    /// Image run by the canary
+    pub image: String,
+    /// Replicas of the stable deployment
+    pub stable_replicas: u32,
+    /// Replicas of the canary deployment
+    pub canary_replicas: u32,
+    /// Approximate share of traffic served by the canary (0-100)
+    pub traffic_percent: u32,
+}
+
+/// Result of a blue-green Service switch.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sBlueGreenSwitch {
+    /// Namespace of the Service
+    pub namespace: String,
+    /// Service name
+    pub service: String,
+    /// Color selected before the switch
+    pub previous_color: String,
+    /// Color selected now ("blue" or "green")
+    pub active_color: String,
+    /// Deployments now receiving traffic
+    pub deployments: Vec<String>,
+}
+
+/// CPU and memory requested by the active pods of a namespace.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct K8sResourceRequests {
+    /// Namespace name
+    pub namespace: String,
+    /// Running or pending pods
+    pub pods: u32,
+    /// Pods without CPU or memory requests (not priced)
+    pub pods_without_requests: u32,
+    /// Requested CPU in cores
+    pub cpu_cores: f64,
+    /// Requested memory in GiB
+    pub memory_gib: f64,
+}
+
+/// Image of a container in a pod template.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sContainerImage {
+    pub name: String,
+    pub image: String,
+}
+
+/// Deployed version of a deployment.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sDeploymentState {
+    pub name: String,
+    /// Containers of the pod template, in order
+    pub containers: Vec<K8sContainerImage>,
+    /// Value of the `helm.sh/chart` label (e.g., "api-1.4.2")
+    pub chart: Option<String>,
+    /// Version part of the chart label
+    pub chart_version: Option<String>,
+    pub replicas: u32,
+    pub ready_replicas: u32,
+}
+
+/// A revision of a deployment, backed by one of its ReplicaSets.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sDeploymentRevision {
+    /// Value of the `deployment.kubernetes.io/revision` annotation
+    pub revision: u32,
+    pub replica_set: String,
+    /// Value of the `kubernetes.io/change-cause` annotation
+    pub change_cause: Option<String>,
+    /// Containers of the revision's pod template, in order
+    pub containers: Vec<K8sContainerImage>,
+    pub created_at: Option<Timestamp>,
+    pub replicas: u32,
+    /// Whether the deployment currently runs this revision
+    pub current: bool,
+}
+
+/// Result of rolling a deployment back to an earlier revision.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sRollback {
+    pub namespace: String,
+    pub deployment: String,
+    /// Revision running before the rollback
+    pub from_revision: Option<u32>,
+    /// Revision whose pod template was restored
+    pub to_revision: u32,
+    /// Containers of the restored pod template
+    pub containers: Vec<K8sContainerImage>,
+}
+
+/// A container image running in the cluster.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sImageUsage {
+    /// Image reference as written in the pod specs (e.g., "registry.example.com/team/api:1.2")
+    pub image: String,
+    /// Digests the running containers resolved the image to
+    pub digests: Vec<String>,
+    /// Running pods with a container of this image
+    pub pods: u32,
+    /// Running containers of this image
+    pub containers: u32,
+    /// Namespaces the image runs in, sorted
+    pub namespaces: Vec<String>,
+}
+
+/// Monthly cost estimate of a namespace's resource requests.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct K8sCostEstimate {
+    pub requests: K8sResourceRequests,
+    pub cpu_monthly_cost: f64,
+    pub memory_monthly_cost: f64,
+    pub monthly_cost: f64,
+    /// Currency of the rate used (e.g., "USD")
+    pub currency: String,
+}
+
+/// Cost estimate of an environment's namespace on one cluster.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct K8sEnvironmentCost {
+    pub environment_id: String,
+    pub environment_name: String,
+    /// Kubernetes integration (cluster) the namespace was read from
+    pub integration_id: String,
+    pub integration_name: String,
+    pub estimate: Option<K8sCostEstimate>,
+    /// Why the namespace could not be estimated
+    pub error: Option<String>,
+}
+
+/// Action applied to every pod matching a label selector.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum K8sPodAction {
+    /// Delete the pods; pods owned by a controller are recreated
+    Delete,
+    /// Set annotations on the pods; a null value removes the annotation
+    Annotate {
+        annotations: BTreeMap<String, Option<String>>,
+    },
+}
+
+/// A pod affected by a bulk action.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sBulkPodTarget {
+    /// Pod name
+    pub name: String,
+    /// Pod phase (e.g., "Running")
+    pub status: String,
+    /// Controlling owner (e.g., "ReplicaSet/api-7d9f8"); deleted pods without
+    /// one are not recreated
+    pub owner: Option<String>,
+    /// Why the action failed on this pod
+    pub error: Option<String>,
+}
+
+/// Result (or dry-run preview) of a bulk pod action.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sBulkPodResult {
+    pub namespace: String,
+    pub selector: String,
+    pub action: K8sPodAction,
+    /// Whether the pods were only listed
+    pub dry_run: bool,
+    pub pods: Vec<K8sBulkPodTarget>,
+    /// Pods the action failed on
+    pub failed: u32,
+}
+
+/// Kubernetes event (`core/v1` Event) about an object in a namespace.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sEvent {
+    /// Event UID; repeated occurrences share it and increase `count`
+    pub uid: String,
+    pub namespace: String,
+    /// Kind of the object the event is about (e.g., "Pod")
+    pub object_kind: String,
+    /// Name of the object the event is about
+    pub object_name: String,
+    /// Event type ("Normal" or "Warning")
+    pub r#type: String,
+    /// Machine-readable reason (e.g., "BackOff", "Scheduled")
+    pub reason: String,
+    pub message: String,
+    /// Component that reported the event (e.g., "kubelet")
+    pub source: Option<String>,
+    /// Number of occurrences
+    pub count: u32,
+    /// First occurrence
+    pub first_seen: Timestamp,
+    /// Most recent occurrence
+    pub last_seen: Timestamp,
+}
+
+/// PodDisruptionBudget covering the pods of a deployment.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sDisruptionBudget {
+    pub name: String,
+    /// Pods that must stay available (a count or a percentage, e.g., "50%")
+    pub min_available: Option<String>,
+    /// Pods that may be unavailable (a count or a percentage)
+    pub max_unavailable: Option<String>,
+    /// Healthy pods the budget currently counts
+    pub current_healthy: u32,
+    /// Healthy pods the budget requires
+    pub desired_healthy: u32,
+    /// Evictions the budget currently allows
+    pub disruptions_allowed: u32,
+}
+
+/// Whether pods of a deployment can be deleted or the deployment scaled down
+/// without breaking its availability guarantees.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct K8sEvictionSafety {
+    pub namespace: String,
+    pub deployment: String,
+    pub desired_replicas: u32,
+    pub ready_replicas: u32,
+    pub budgets: Vec<K8sDisruptionBudget>,
+    /// Pods that can be deleted now (the budgets' allowance, or all ready
+    /// pods but one when no budget covers the deployment)
+    pub disruptions_allowed: u32,
+    /// Fewest replicas that keep the budgets' minimum available
+    pub min_safe_replicas: u32,
+    /// Whether the checked action keeps the guarantees
+    pub safe: bool,
+    pub warnings: Vec<String>,
+}
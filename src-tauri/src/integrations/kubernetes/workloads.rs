@@ -0,0 +1,131 @@
+//! Deployed workload versions.
+
+use k8s_openapi::api::apps::v1::Deployment;
+use kube::Api;
+
+use super::adapter::KubernetesAdapter;
+use super::types::{K8sContainerImage, K8sDeploymentState};
+use crate::integrations::IntegrationError;
+
+/// Label Helm sets to `<chart name>-<chart version>`.
+const HELM_CHART_LABEL: &str = "helm.sh/chart";
+
+impl KubernetesAdapter {
+    /// Lists the deployments of a namespace with their container images and
+    /// Helm chart.
+    pub async fn fetch_deployment_states(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<K8sDeploymentState>, IntegrationError> {
+        log::debug!("Fetching deployment states in namespace: {}", namespace);
+
+        let api: Api<Deployment> = Api::namespaced(self.client.clone(), namespace);
+        let deployments = api.list(&Default::default()).await.map_err(|e| {
+            log::error!(
+                "Failed to list deployments in namespace {}: {}",
+                namespace,
+                e
+            );
+            IntegrationError::NetworkError {
+                message: format!("Failed to list deployments: {}", e),
+            }
+        })?;
+
+        Ok(deployments.into_iter().map(deployment_state).collect())
+    }
+}
+
+fn deployment_state(deployment: Deployment) -> K8sDeploymentState {
+    let chart = deployment
+        .metadata
+        .labels
+        .as_ref()
+        .and_then(|labels| labels.get(HELM_CHART_LABEL))
+        .cloned();
+    let containers = deployment
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.template.spec.as_ref())
+        .map(|pod| {
+            pod.containers
+                .iter()
+                .map(|c| K8sContainerImage {
+                    name: c.name.clone(),
+                    image: c.image.clone().unwrap_or_default(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let status = deployment.status.as_ref();
+
+    K8sDeploymentState {
+        name: deployment.metadata.name.unwrap_or_default(),
+        containers,
+        chart_version: chart.as_deref().and_then(chart_version).map(str::to_string),
+        chart,
+        replicas: deployment
+            .spec
+            .as_ref()
+            .and_then(|s| s.replicas)
+            .unwrap_or(1)
+            .max(0) as u32,
+        ready_replicas: status.and_then(|s| s.ready_replicas).unwrap_or(0).max(0) as u32,
+    }
+}
+
+/// Version part of a `helm.sh/chart` label (e.g., "1.4.2-rc.1" of
+/// "checkout-api-1.4.2-rc.1"): everything after the first '-' followed by
+/// a digit.
+fn chart_version(chart: &str) -> Option<&str> {
+    chart
+        .match_indices('-')
+        .map(|(index, _)| &chart[index + 1..])
+        .find(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_chart_version() {
+        assert_eq!(chart_version("checkout-api-1.4.2"), Some("1.4.2"));
+        assert_eq!(chart_version("api-1.4.2-rc.1"), Some("1.4.2-rc.1"));
+        assert_eq!(chart_version("web2-0.1.0"), Some("0.1.0"));
+        assert_eq!(chart_version("checkout"), None);
+    }
+
+    #[test]
+    fn test_deployment_state() {
+        let deployment: Deployment = serde_json::from_value(json!({
+            "metadata": {
+                "name": "api",
+                "labels": { "helm.sh/chart": "api-1.4.2" }
+            },
+            "spec": {
+                "replicas": 3,
+                "selector": {},
+                "template": {
+                    "spec": {
+                        "containers": [
+                            { "name": "api", "image": "registry.example.com/team/api:1.4.2" },
+                            { "name": "proxy", "image": "envoy:1.30" }
+                        ]
+                    }
+                }
+            },
+            "status": { "readyReplicas": 2 }
+        }))
+        .unwrap();
+
+        let state = deployment_state(deployment);
+
+        assert_eq!(state.name, "api");
+        assert_eq!(state.chart.as_deref(), Some("api-1.4.2"));
+        assert_eq!(state.chart_version.as_deref(), Some("1.4.2"));
+        assert_eq!(state.replicas, 3);
+        assert_eq!(state.ready_replicas, 2);
+        assert_eq!(state.containers[1].image, "envoy:1.30");
+    }
+}
@@ -3,11 +3,19 @@
 //! Provides the base trait and infrastructure for integration adapters
 //! that connect to external services (GitLab, Jenkins, Kubernetes, etc.).
 
+pub mod builds;
+pub mod compat;
+#[cfg(test)]
+mod contract_tests;
+pub mod demo;
 pub mod errors;
+pub mod fixtures;
 pub mod gitlab;
+pub mod harbor;
 pub mod jenkins;
 pub mod keycloak;
 pub mod kubernetes;
+pub mod oci;
 pub mod registry;
 pub mod sonarqube;
 
@@ -134,13 +142,17 @@ pub fn create_adapter(
                     message: "Keycloak integration requires a password or token".to_string(),
                 })?;
 
+            let api_version = compat::pinned_version(integration)
+                .map_err(|message| IntegrationError::ConfigError { message })?;
             let adapter = keycloak::KeycloakAdapter::new(
                 integration.base_url.clone(),
                 username.clone(),
                 password.clone(),
-            );
+            )
+            .with_api_version(api_version);
             Ok(Box::new(adapter))
         }
+        IntegrationType::Demo => Ok(Box::new(demo::DemoAdapter::new(integration))),
         IntegrationType::Kubernetes => {
             // Kubernetes adapter creation is async and handled directly in command layer
             // This function is synchronous, so we return an error here
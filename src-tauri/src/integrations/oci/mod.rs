@@ -0,0 +1,326 @@
+//! OCI distribution (registry v2) client.
+//!
+//! Resolves tags to digests and reads artifacts attached to an image through
+//! the referrers API. Registries using token authentication are handled by
+//! answering their `WWW-Authenticate: Bearer` challenge.
+
+use crate::integrations::IntegrationError;
+use reqwest::header::{ACCEPT, WWW_AUTHENTICATE};
+use reqwest::{Client, Response, StatusCode};
+use serde_json::Value;
+use std::sync::{Mutex, PoisonError};
+
+/// Registry serving images without a registry host (Docker Hub).
+pub const DEFAULT_REGISTRY: &str = "registry-1.docker.io";
+
+/// Manifest media types accepted when resolving a tag.
+const MANIFEST_TYPES: &str = "application/vnd.oci.image.index.v1+json, \
+    application/vnd.oci.image.manifest.v1+json, \
+    application/vnd.docker.distribution.manifest.list.v2+json, \
+    application/vnd.docker.distribution.manifest.v2+json";
+
+/// Descriptor of an artifact referring to an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OciReferrer {
+    pub digest: String,
+    /// Artifact type (e.g., "application/spdx+json")
+    pub artifact_type: Option<String>,
+}
+
+/// Client for one registry.
+pub struct OciRegistry {
+    /// Registry base URL (e.g., "https://ghcr.io")
+    base_url: String,
+    /// Username and password for Basic Auth or token requests
+    credentials: Option<(String, String)>,
+    /// Bearer token obtained from the registry's token service
+    token: Mutex<Option<String>>,
+    /// HTTP client for API requests
+    client: Client,
+}
+
+impl OciRegistry {
+    /// Creates a client for a registry host (with port, if any).
+    pub fn new(registry: &str, credentials: Option<(String, String)>) -> Self {
+        let scheme = if registry.starts_with("localhost") || registry.starts_with("127.0.0.1") {
+            "http"
+        } else {
+            "https"
+        };
+        Self {
+            base_url: format!("{}://{}", scheme, registry),
+            credentials,
+            token: Mutex::new(None),
+            client: Client::new(),
+        }
+    }
+
+    /// Sends a GET request, answering a bearer challenge once if needed.
+    async fn send(
+        &self,
+        name: &str,
+        path: &str,
+        accept: &str,
+    ) -> Result<Response, IntegrationError> {
+        let url = format!("{}/v2/{}{}", self.base_url, name, path);
+        log::debug!("OCI registry GET: {}", url);
+
+        let mut response = self.request(&url, accept).send().await?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            let challenge = response
+                .headers()
+                .get(WWW_AUTHENTICATE)
+                .and_then(|h| h.to_str().ok())
+                .and_then(parse_bearer_challenge);
+            if let Some((realm, service, scope)) = challenge {
+                let scope = scope.unwrap_or_else(|| format!("repository:{}:pull", name));
+                let token = self.fetch_token(&realm, service.as_deref(), &scope).await?;
+                *self.token.lock().unwrap_or_else(PoisonError::into_inner) = Some(token);
+                response = self.request(&url, accept).send().await?;
+            }
+        }
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            log::error!("OCI registry error ({}): {}", status, error_text);
+            return Err(crate::integrations::errors::status_to_error(
+                status.as_u16(),
+                Some(error_text),
+            ));
+        }
+        Ok(response)
+    }
+
+    /// Builds an authenticated request.
+    fn request(&self, url: &str, accept: &str) -> reqwest::RequestBuilder {
+        let request = self
+            .client
+            .get(url)
+            .header(ACCEPT, accept)
+            .timeout(std::time::Duration::from_secs(30));
+        let token = self
+            .token
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        match (token, &self.credentials) {
+            (Some(token), _) => request.bearer_auth(token),
+            (None, Some((username, password))) => request.basic_auth(username, Some(password)),
+            (None, None) => request,
+        }
+    }
+
+    /// Requests a pull token from the registry's token service.
+    async fn fetch_token(
+        &self,
+        realm: &str,
+        service: Option<&str>,
+        scope: &str,
+    ) -> Result<String, IntegrationError> {
+        let mut query = vec![("scope", scope)];
+        if let Some(service) = service {
+            query.push(("service", service));
+        }
+        let mut request = self
+            .client
+            .get(realm)
+            .query(&query)
+            .timeout(std::time::Duration::from_secs(30));
+        if let Some((username, password)) = &self.credentials {
+            request = request.basic_auth(username, Some(password));
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(IntegrationError::AuthError {
+                message: format!("Registry token request failed: {}", response.status()),
+            });
+        }
+        let body: Value = response.json().await?;
+        body.get("token")
+            .or_else(|| body.get("access_token"))
+            .and_then(|t| t.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| IntegrationError::AuthError {
+                message: "Registry token response has no token".to_string(),
+            })
+    }
+
+    /// Resolves a tag to its manifest digest (digests are returned as is).
+    pub async fn resolve_digest(
+        &self,
+        name: &str,
+        reference: &str,
+    ) -> Result<String, IntegrationError> {
+        if reference.contains(':') {
+            return Ok(reference.to_string());
+        }
+        let response = self
+            .send(name, &format!("/manifests/{}", reference), MANIFEST_TYPES)
+            .await?;
+        response
+            .headers()
+            .get("docker-content-digest")
+            .and_then(|h| h.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| IntegrationError::ConfigError {
+                message: format!("Registry returned no digest for {}:{}", name, reference),
+            })
+    }
+
+    /// Lists artifacts referring to an image digest.
+    pub async fn fetch_referrers(
+        &self,
+        name: &str,
+        digest: &str,
+    ) -> Result<Vec<OciReferrer>, IntegrationError> {
+        let index: Value = self
+            .send(
+                name,
+                &format!("/referrers/{}", digest),
+                "application/vnd.oci.image.index.v1+json",
+            )
+            .await?
+            .json()
+            .await?;
+        Ok(parse_referrers(&index))
+    }
+
+    /// Fetches a manifest by digest.
+    pub async fn fetch_manifest(
+        &self,
+        name: &str,
+        digest: &str,
+    ) -> Result<Value, IntegrationError> {
+        Ok(self
+            .send(name, &format!("/manifests/{}", digest), MANIFEST_TYPES)
+            .await?
+            .json()
+            .await?)
+    }
+
+    /// Fetches a blob, failing when it is larger than `max_bytes`.
+    pub async fn fetch_blob(
+        &self,
+        name: &str,
+        digest: &str,
+        max_bytes: usize,
+    ) -> Result<Vec<u8>, IntegrationError> {
+        let response = self
+            .send(name, &format!("/blobs/{}", digest), "*/*")
+            .await?;
+        if response
+            .content_length()
+            .is_some_and(|len| len > max_bytes as u64)
+        {
+            return Err(IntegrationError::ConfigError {
+                message: format!("Blob {} is larger than {} bytes", digest, max_bytes),
+            });
+        }
+        let bytes = response.bytes().await?;
+        if bytes.len() > max_bytes {
+            return Err(IntegrationError::ConfigError {
+                message: format!("Blob {} is larger than {} bytes", digest, max_bytes),
+            });
+        }
+        Ok(bytes.to_vec())
+    }
+}
+
+/// Reads the realm, service and scope of a `Bearer` challenge.
+fn parse_bearer_challenge(header: &str) -> Option<(String, Option<String>, Option<String>)> {
+    let params = header.strip_prefix("Bearer ")?;
+    let mut realm = None;
+    let mut service = None;
+    let mut scope = None;
+
+    // Values are quoted and may contain commas (e.g., scope "...:pull,push")
+    let mut rest = params.trim();
+    while let Some((key, after)) = rest.split_once('=') {
+        let after = after.trim_start();
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"')?;
+                (&quoted[..end], &quoted[end + 1..])
+            }
+            None => after.split_once(',').unwrap_or((after, "")),
+        };
+        match key.trim() {
+            "realm" => realm = Some(value.to_string()),
+            "service" => service = Some(value.to_string()),
+            "scope" => scope = Some(value.to_string()),
+            _ => {}
+        }
+        rest = remaining.trim_start_matches([',', ' ']);
+    }
+    Some((realm?, service, scope))
+}
+
+/// Reads the descriptors of a referrers index.
+fn parse_referrers(index: &Value) -> Vec<OciReferrer> {
+    index
+        .get("manifests")
+        .and_then(|m| m.as_array())
+        .map(|manifests| {
+            manifests
+                .iter()
+                .filter_map(|m| {
+                    Some(OciReferrer {
+                        digest: m.get("digest")?.as_str()?.to_string(),
+                        artifact_type: m
+                            .get("artifactType")
+                            .and_then(|t| t.as_str())
+                            .map(str::to_string),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_bearer_challenge() {
+        let (realm, service, scope) = parse_bearer_challenge(
+            r#"Bearer realm="https://ghcr.io/token",service="ghcr.io",scope="repository:team/api:pull""#,
+        )
+        .unwrap();
+        assert_eq!(realm, "https://ghcr.io/token");
+        assert_eq!(service.as_deref(), Some("ghcr.io"));
+        assert_eq!(scope.as_deref(), Some("repository:team/api:pull"));
+
+        let (_, service, scope) =
+            parse_bearer_challenge(r#"Bearer realm="https://auth.example.com/token""#).unwrap();
+        assert_eq!((service, scope), (None, None));
+        assert!(parse_bearer_challenge(r#"Basic realm="registry""#).is_none());
+    }
+
+    #[test]
+    fn test_parse_referrers() {
+        let index = json!({
+            "manifests": [
+                { "digest": "sha256:a", "artifactType": "application/spdx+json" },
+                { "digest": "sha256:b" },
+                { "artifactType": "application/vnd.cyclonedx+json" }
+            ]
+        });
+        assert_eq!(
+            parse_referrers(&index),
+            [
+                OciReferrer {
+                    digest: "sha256:a".to_string(),
+                    artifact_type: Some("application/spdx+json".to_string()),
+                },
+                OciReferrer {
+                    digest: "sha256:b".to_string(),
+                    artifact_type: None,
+                },
+            ]
+        );
+    }
+}
@@ -2,10 +2,14 @@
 //!
 //! Implements the IntegrationAdapter trait for SonarQube API interactions.
 
+mod profiles;
 mod types;
 
-pub use types::{SonarQubeMetrics, SonarQubeProject};
+pub use types::{
+    SonarQubeMetrics, SonarQubeProfileDrift, SonarQubeProject, SonarQubeQualityProfile,
+};
 
+use crate::integrations::fixtures;
 use crate::integrations::{IntegrationAdapter, IntegrationError};
 use crate::types::IntegrationType;
 use async_trait::async_trait;
@@ -55,17 +59,17 @@ impl SonarQubeAdapter {
             .send()
             .await?;
 
-        let status = response.status();
+        let (status, body) =
+            fixtures::capture("sonarqube", &self.base_url, "GET", response).await?;
         if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            log::error!("SonarQube API error ({}): {}", status, error_text);
+            log::error!("SonarQube API error ({}): {}", status, body);
             return Err(crate::integrations::errors::status_to_error(
                 status.as_u16(),
-                Some(error_text),
+                Some(body),
             ));
         }
 
-        response.json::<T>().await.map_err(|e| {
+        serde_json::from_str::<T>(&body).map_err(|e| {
             log::error!("Failed to parse SonarQube API response: {}", e);
             IntegrationError::ConfigError {
                 message: format!("Failed to parse response: {}", e),
@@ -0,0 +1,257 @@
+//! Quality profile drift.
+//!
+//! Projects are analyzed with one quality profile per language. Governance
+//! usually designates a "golden" profile per language; the profile each
+//! project uses is compared against it with `/qualityprofiles/compare`, which
+//! lists the rules active on one side only and those activated differently.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::types::{
+    SonarQubeProfileDrift, SonarQubeQualityProfile, SonarQubeRuleDifference,
+    SonarQubeRuleDifferenceKind,
+};
+use super::SonarQubeAdapter;
+use crate::integrations::IntegrationError;
+
+/// Quality profile as returned by `GET /qualityprofiles/search`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawProfile {
+    key: String,
+    name: String,
+    language: String,
+    #[serde(default)]
+    language_name: Option<String>,
+    #[serde(default)]
+    is_default: bool,
+    #[serde(default)]
+    parent_name: Option<String>,
+    #[serde(default)]
+    active_rule_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawProfileList {
+    profiles: Vec<RawProfile>,
+}
+
+/// Result of `GET /qualityprofiles/compare` (left is the golden profile).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawComparison {
+    #[serde(default)]
+    in_left: Vec<RawComparedRule>,
+    #[serde(default)]
+    in_right: Vec<RawComparedRule>,
+    #[serde(default)]
+    modified: Vec<RawComparedRule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawComparedRule {
+    key: String,
+    #[serde(default)]
+    name: String,
+    severity: Option<String>,
+    /// Activation in the left profile (modified rules only)
+    left: Option<Value>,
+    /// Activation in the right profile (modified rules only)
+    right: Option<Value>,
+}
+
+impl From<RawProfile> for SonarQubeQualityProfile {
+    fn from(raw: RawProfile) -> Self {
+        Self {
+            language_name: raw.language_name.unwrap_or_else(|| raw.language.clone()),
+            key: raw.key,
+            name: raw.name,
+            language: raw.language,
+            is_default: raw.is_default,
+            parent_name: raw.parent_name,
+            active_rule_count: raw.active_rule_count,
+        }
+    }
+}
+
+impl SonarQubeAdapter {
+    /// Fetches quality profiles: those a project uses (one per language)
+    /// when `project_key` is given, all of them otherwise.
+    pub async fn fetch_quality_profiles(
+        &self,
+        project_key: Option<&str>,
+    ) -> Result<Vec<SonarQubeQualityProfile>, IntegrationError> {
+        let endpoint = match project_key {
+            Some(key) => format!(
+                "/qualityprofiles/search?project={}",
+                urlencoding::encode(key)
+            ),
+            None => "/qualityprofiles/search".to_string(),
+        };
+        let list: RawProfileList = self.get(&endpoint).await?;
+        Ok(list.profiles.into_iter().map(Into::into).collect())
+    }
+
+    /// Compares the profiles of projects against the golden profiles.
+    ///
+    /// # Arguments
+    /// * `golden_profile_keys` - Golden profile of each language, at most one
+    ///   per language
+    /// * `project_keys` - Projects to check
+    pub async fn check_profile_drift(
+        &self,
+        golden_profile_keys: &[String],
+        project_keys: &[String],
+    ) -> Result<Vec<SonarQubeProfileDrift>, IntegrationError> {
+        let all_profiles = self.fetch_quality_profiles(None).await?;
+        let mut golden_profiles: Vec<SonarQubeQualityProfile> = Vec::new();
+        for key in golden_profile_keys {
+            let profile = all_profiles.iter().find(|p| &p.key == key).ok_or_else(|| {
+                IntegrationError::ConfigError {
+                    message: format!("Golden profile not found: {}", key),
+                }
+            })?;
+            if let Some(other) = golden_profiles
+                .iter()
+                .find(|g| g.language == profile.language)
+            {
+                return Err(IntegrationError::ConfigError {
+                    message: format!(
+                        "Golden profiles {} and {} are both for {}",
+                        other.name, profile.name, profile.language_name
+                    ),
+                });
+            }
+            golden_profiles.push(profile.clone());
+        }
+
+        let mut drifts = Vec::new();
+        for project_key in project_keys {
+            for profile in self.fetch_quality_profiles(Some(project_key)).await? {
+                let golden = golden_profiles
+                    .iter()
+                    .find(|g| g.language == profile.language)
+                    .cloned();
+                let mut drift = SonarQubeProfileDrift {
+                    project_key: project_key.clone(),
+                    language: profile.language.clone(),
+                    profile,
+                    golden_profile: golden,
+                    differences: Vec::new(),
+                    error: None,
+                };
+                if let Some(golden) = drift
+                    .golden_profile
+                    .as_ref()
+                    .filter(|g| g.key != drift.profile.key)
+                {
+                    match self.compare_profiles(&golden.key, &drift.profile.key).await {
+                        Ok(differences) => drift.differences = differences,
+                        Err(e) => drift.error = Some(e.to_string()),
+                    }
+                }
+                drifts.push(drift);
+            }
+        }
+        Ok(drifts)
+    }
+
+    /// Lists the rules activated differently in `profile_key` than in
+    /// `golden_key`.
+    async fn compare_profiles(
+        &self,
+        golden_key: &str,
+        profile_key: &str,
+    ) -> Result<Vec<SonarQubeRuleDifference>, IntegrationError> {
+        let comparison: RawComparison = self
+            .get(&format!(
+                "/qualityprofiles/compare?leftKey={}&rightKey={}",
+                urlencoding::encode(golden_key),
+                urlencoding::encode(profile_key)
+            ))
+            .await?;
+        Ok(differences(comparison))
+    }
+}
+
+fn activation_severity(activation: Option<&Value>) -> Option<String> {
+    activation?["severity"].as_str().map(str::to_string)
+}
+
+fn differences(comparison: RawComparison) -> Vec<SonarQubeRuleDifference> {
+    let missing = comparison
+        .in_left
+        .into_iter()
+        .map(|rule| SonarQubeRuleDifference {
+            rule_key: rule.key,
+            name: rule.name,
+            kind: SonarQubeRuleDifferenceKind::Missing,
+            golden_severity: rule.severity,
+            profile_severity: None,
+        });
+    let extra = comparison
+        .in_right
+        .into_iter()
+        .map(|rule| SonarQubeRuleDifference {
+            rule_key: rule.key,
+            name: rule.name,
+            kind: SonarQubeRuleDifferenceKind::Extra,
+            golden_severity: None,
+            profile_severity: rule.severity,
+        });
+    let modified = comparison
+        .modified
+        .into_iter()
+        .map(|rule| SonarQubeRuleDifference {
+            golden_severity: activation_severity(rule.left.as_ref()),
+            profile_severity: activation_severity(rule.right.as_ref()),
+            rule_key: rule.key,
+            name: rule.name,
+            kind: SonarQubeRuleDifferenceKind::Modified,
+        });
+
+    let mut differences: Vec<_> = missing.chain(extra).chain(modified).collect();
+    differences.sort_by(|a, b| a.rule_key.cmp(&b.rule_key));
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_differences() {
+        let comparison: RawComparison = serde_json::from_value(json!({
+            "left": { "key": "golden", "name": "Company way" },
+            "right": { "key": "legacy", "name": "Legacy" },
+            "inLeft": [{ "key": "java:S2068", "name": "Hard-coded passwords", "severity": "BLOCKER" }],
+            "inRight": [{ "key": "java:S1135", "name": "TODO tags", "severity": "INFO" }],
+            "modified": [{
+                "key": "java:S138",
+                "name": "Methods should not have too many lines",
+                "left": { "severity": "MAJOR", "params": { "max": "75" } },
+                "right": { "severity": "MINOR", "params": { "max": "200" } }
+            }],
+            "same": [{ "key": "java:S100", "name": "Method names", "severity": "MINOR" }]
+        }))
+        .unwrap();
+
+        let differences = differences(comparison);
+        assert_eq!(
+            differences
+                .iter()
+                .map(|d| (d.rule_key.as_str(), d.kind))
+                .collect::<Vec<_>>(),
+            [
+                ("java:S1135", SonarQubeRuleDifferenceKind::Extra),
+                ("java:S138", SonarQubeRuleDifferenceKind::Modified),
+                ("java:S2068", SonarQubeRuleDifferenceKind::Missing),
+            ]
+        );
+        assert_eq!(differences[1].golden_severity.as_deref(), Some("MAJOR"));
+        assert_eq!(differences[1].profile_severity.as_deref(), Some("MINOR"));
+        assert_eq!(differences[2].profile_severity, None);
+    }
+}
@@ -28,3 +28,61 @@ pub struct SonarQubeMetrics {
     /// Technical debt in minutes (as string to avoid i64 BigInt issues)
     pub technical_debt: Option<String>,
 }
+
+/// Quality profile (the rule set a project is analyzed with, per language).
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SonarQubeQualityProfile {
+    /// Profile key (unique identifier)
+    pub key: String,
+    pub name: String,
+    /// Language key (e.g., "java", "js")
+    pub language: String,
+    /// Language name (e.g., "Java")
+    pub language_name: String,
+    /// Whether this is the default profile of its language
+    pub is_default: bool,
+    /// Name of the profile this one inherits rules from
+    pub parent_name: Option<String>,
+    pub active_rule_count: u32,
+}
+
+/// How a rule differs from the golden profile.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SonarQubeRuleDifferenceKind {
+    /// Active in the golden profile only
+    Missing,
+    /// Active in the project's profile only
+    Extra,
+    /// Active in both with another severity or parameters
+    Modified,
+}
+
+/// Rule activated differently in a project's profile than in the golden one.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SonarQubeRuleDifference {
+    /// Rule key (e.g., "java:S1481")
+    pub rule_key: String,
+    pub name: String,
+    pub kind: SonarQubeRuleDifferenceKind,
+    /// Severity in the golden profile (None when not active there)
+    pub golden_severity: Option<String>,
+    /// Severity in the project's profile (None when not active there)
+    pub profile_severity: Option<String>,
+}
+
+/// Drift of a project's quality profile for one language from the golden
+/// profile of that language.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SonarQubeProfileDrift {
+    pub project_key: String,
+    pub language: String,
+    /// Profile the project uses for the language
+    pub profile: SonarQubeQualityProfile,
+    /// Golden profile of the language (None when none was designated)
+    pub golden_profile: Option<SonarQubeQualityProfile>,
+    /// Rules that differ, empty when the profiles match
+    pub differences: Vec<SonarQubeRuleDifference>,
+    /// Why the profiles couldn't be compared
+    pub error: Option<String>,
+}
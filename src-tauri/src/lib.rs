@@ -4,11 +4,37 @@
 //! Command implementations are organized in the `commands` module,
 //! and shared types are in the `types` module.
 
+mod app_lock;
 pub mod bindings;
+mod build_failures;
+mod checks;
 mod commands;
+mod config_history;
+mod event_recorder;
+mod feature_flags;
+mod flow_engine;
+mod initial_sync;
 mod integrations;
+mod leak_scan;
+mod linkage;
+mod metrics;
+#[cfg(desktop)]
+mod notification_tray;
+mod onboarding;
+mod ownership;
+mod polling_cost;
+mod project_inventory;
+mod refresh;
+mod reports;
+mod saved_filters;
+mod secrets;
+mod tag_watcher;
+mod task_pool;
 mod types;
+mod updates;
 mod utils;
+mod webhooks;
+mod windows;
 
 use tauri::Manager;
 
@@ -101,6 +127,15 @@ pub fn run() {
                 app.package_info().name
             );
 
+            // `--record[=<dir>]` saves API responses as contract test fixtures
+            if let Some(dir) = integrations::fixtures::record_flag(std::env::args()) {
+                let dir = match dir {
+                    Some(dir) => dir,
+                    None => app.path().app_data_dir()?.join("fixtures"),
+                };
+                integrations::fixtures::enable_recording(dir);
+            }
+
             // Set up global shortcut plugin (without any shortcuts - we register them separately)
             #[cfg(desktop)]
             {
@@ -130,11 +165,53 @@ pub fn run() {
                 // Non-fatal: app can still run without quick pane
             }
 
+            // Restore the app lock (starts locked when enabled)
+            let preferences = commands::preferences::read_preferences(app.handle());
+            app_lock::init(
+                app.handle(),
+                preferences.as_ref().and_then(|p| p.app_lock_idle_minutes),
+            );
+
+            // List the actions of recent notifications in the tray
+            #[cfg(desktop)]
+            if let Err(e) = notification_tray::init(app.handle()) {
+                log::error!("Failed to create the notification tray: {e}");
+            }
+
+            // Serve internal metrics when a port is configured
+            metrics::init(app.handle(), preferences.and_then(|p| p.metrics_port));
+
+            // Check TLS certificate expiry in the background (daily)
+            checks::certificates::start_daily_checks(app.handle());
+
+            // Generate scheduled reports in the background
+            reports::start_scheduled_reports(app.handle());
+
+            // Re-evaluate saved filters that notify on changes
+            saved_filters::start_watching(app.handle());
+
+            // Check the update channel and apply the update policy
+            updates::start_scheduled_checks(app.handle());
+
+            // Record the events of the namespaces of enabled event recordings
+            event_recorder::start_recording(app.handle());
+
+            // Snapshot the GitLab project inventories daily and report changes
+            project_inventory::start_scheduled_snapshots(app.handle());
+
+            // Continue unfinished initial syncs of large integrations
+            initial_sync::start_background_sync(app.handle());
+
             // NOTE: Application menu is built from JavaScript for i18n support
             // See src/lib/menu.ts for the menu implementation
 
             Ok(())
         })
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::Destroyed = event {
+                windows::forget(window.label());
+            }
+        })
         .invoke_handler(builder.invoke_handler())
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,359 @@
+//! GitLab-to-Jenkins linkage detection.
+//!
+//! Matches the Git remotes in Jenkins job configurations against the URLs of
+//! GitLab projects and proposes a mapping (`repo_id` = GitLab project ID,
+//! `job_id` = Jenkins job name) for every job building a GitLab project. HTTPS
+//! and SSH remotes of a project match alike, so
+//! `git@gitlab.example.com:platform/api.git` links to
+//! `https://gitlab.example.com/platform/api`. Proposals can be saved as
+//! mappings right away.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::commands::config::{load_mappings, save_mappings};
+use crate::commands::gitlab::gitlab_adapter;
+use crate::commands::jenkins::jenkins_adapter;
+use crate::integrations::gitlab::GitLabProject;
+use crate::integrations::jenkins::JenkinsJobConfigSummary;
+use crate::types::{Mapping, Ownership};
+
+/// Job configurations fetched at the same time.
+const MAX_CONCURRENT_FETCHES: usize = 8;
+
+/// How a proposed link relates to the existing mappings.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkageStatus {
+    /// No mapping links the job yet
+    New,
+    /// A mapping already links the job to the project
+    Mapped,
+    /// The job is mapped to another repository
+    Conflict,
+}
+
+/// A Jenkins job building a GitLab project.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct LinkageProposal {
+    pub gitlab_project_id: u32,
+    /// Project path from its URL (e.g., "platform/api")
+    pub gitlab_project_path: String,
+    pub jenkins_job: String,
+    /// Remote URL of the job that matched
+    pub scm_url: String,
+    pub status: LinkageStatus,
+    /// Mapping linking the job (the matching one, or the conflicting one)
+    pub mapping_id: Option<String>,
+}
+
+/// Result of matching a Jenkins instance against a GitLab instance.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct LinkageReport {
+    pub proposals: Vec<LinkageProposal>,
+    /// Jobs with Git remotes that match no GitLab project
+    pub unmatched_jobs: Vec<String>,
+    /// Jobs without Git remotes
+    pub jobs_without_scm: u32,
+    /// Mappings created for new proposals
+    pub created: u32,
+    /// Jobs whose configuration couldn't be read
+    pub errors: Vec<String>,
+}
+
+/// Matches the jobs of a Jenkins integration to the projects of a GitLab
+/// integration. With `create_mappings`, new proposals are saved as mappings.
+pub async fn detect(
+    app: &AppHandle,
+    gitlab_integration_id: &str,
+    jenkins_integration_id: &str,
+    create_mappings: bool,
+) -> Result<LinkageReport, String> {
+    let projects = gitlab_adapter(app, gitlab_integration_id)
+        .await?
+        .fetch_projects()
+        .await
+        .map_err(|e| format!("Failed to fetch GitLab projects: {e}"))?;
+    let jenkins = Arc::new(jenkins_adapter(app, jenkins_integration_id).await?);
+    let jobs = jenkins
+        .fetch_jobs()
+        .await
+        .map_err(|e| format!("Failed to fetch Jenkins jobs: {e}"))?;
+
+    let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_FETCHES));
+    let mut fetches = JoinSet::new();
+    for job in jobs {
+        let jenkins = Arc::clone(&jenkins);
+        let permits = Arc::clone(&permits);
+        fetches.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            let summary = jenkins.fetch_job_config_summary(&job.name).await;
+            (job.name, summary)
+        });
+    }
+
+    let mut summaries = Vec::new();
+    let mut errors = Vec::new();
+    while let Some(joined) = fetches.join_next().await {
+        match joined {
+            Ok((_, Ok(summary))) => summaries.push(summary),
+            Ok((job, Err(e))) => errors.push(format!("{job}: {e}")),
+            Err(e) => errors.push(format!("Configuration fetch failed: {e}")),
+        }
+    }
+    summaries.sort_by(|a, b| a.job_name.cmp(&b.job_name));
+    errors.sort();
+
+    let mut mappings = load_mappings(app.clone()).await?;
+    let mut report = match_jobs(&projects, &summaries, &mappings);
+    report.errors = errors;
+
+    if create_mappings {
+        for proposal in report
+            .proposals
+            .iter_mut()
+            .filter(|p| p.status == LinkageStatus::New)
+        {
+            let mapping = Mapping {
+                id: mapping_id(proposal.gitlab_project_id, &proposal.jenkins_job),
+                repo_id: Some(proposal.gitlab_project_id.to_string()),
+                job_id: Some(proposal.jenkins_job.clone()),
+                namespace: None,
+                service_name: None,
+                project_id: None,
+                environment_id: None,
+                ownership: Ownership::default(),
+            };
+            proposal.status = LinkageStatus::Mapped;
+            proposal.mapping_id = Some(mapping.id.clone());
+            mappings.push(mapping);
+            report.created += 1;
+        }
+        if report.created > 0 {
+            save_mappings(app.clone(), mappings).await?;
+            log::info!("Created {} GitLab-Jenkins mappings", report.created);
+        }
+    }
+    Ok(report)
+}
+
+/// Matches job remotes to projects and compares the links with the mappings.
+fn match_jobs(
+    projects: &[GitLabProject],
+    summaries: &[JenkinsJobConfigSummary],
+    mappings: &[Mapping],
+) -> LinkageReport {
+    let projects_by_key: HashMap<String, &GitLabProject> = projects
+        .iter()
+        .filter_map(|p| Some((repository_key(&p.web_url)?, p)))
+        .collect();
+
+    let mut report = LinkageReport {
+        proposals: Vec::new(),
+        unmatched_jobs: Vec::new(),
+        jobs_without_scm: 0,
+        created: 0,
+        errors: Vec::new(),
+    };
+    for summary in summaries {
+        if summary.scm_urls.is_empty() {
+            report.jobs_without_scm += 1;
+            continue;
+        }
+        let matches: Vec<(&String, &GitLabProject)> = summary
+            .scm_urls
+            .iter()
+            .filter_map(|url| Some((url, *projects_by_key.get(&repository_key(url)?)?)))
+            .collect();
+        if matches.is_empty() {
+            report.unmatched_jobs.push(summary.job_name.clone());
+            continue;
+        }
+
+        let job_mappings: Vec<&Mapping> = mappings
+            .iter()
+            .filter(|m| m.job_id.as_deref() == Some(summary.job_name.as_str()))
+            .collect();
+        for (scm_url, project) in matches {
+            let project_id = project.id.to_string();
+            let (status, mapping_id) = match job_mappings
+                .iter()
+                .find(|m| m.repo_id.as_deref() == Some(project_id.as_str()))
+            {
+                Some(mapping) => (LinkageStatus::Mapped, Some(mapping.id.clone())),
+                None => match job_mappings.iter().find(|m| m.repo_id.is_some()) {
+                    Some(other) => (LinkageStatus::Conflict, Some(other.id.clone())),
+                    None => (LinkageStatus::New, None),
+                },
+            };
+            report.proposals.push(LinkageProposal {
+                gitlab_project_id: project.id,
+                gitlab_project_path: repository_key(&project.web_url)
+                    .and_then(|key| key.split_once('/').map(|(_, path)| path.to_string()))
+                    .unwrap_or_else(|| project.path.clone()),
+                jenkins_job: summary.job_name.clone(),
+                scm_url: scm_url.clone(),
+                status,
+                mapping_id,
+            });
+        }
+    }
+    report
+}
+
+/// Host and path identifying a repository in an HTTPS, SSH or scp-like Git
+/// URL, or a GitLab project URL (e.g., "gitlab.example.com/platform/api").
+fn repository_key(url: &str) -> Option<String> {
+    let url = url.trim();
+    let (rest, scp_like) = match url.split_once("://") {
+        Some((_, rest)) => (rest, false),
+        None => (url, true),
+    };
+    // Drop the user of SSH remotes (git@host)
+    let rest = match rest.split_once('@') {
+        Some((user, host_and_path)) if !user.contains('/') => host_and_path,
+        _ => rest,
+    };
+    let (host, path) = if scp_like {
+        rest.split_once(':')?
+    } else {
+        rest.split_once('/')?
+    };
+    // Drop ports (ssh://git@host:2222/..., https://host:8443/...)
+    let host = host.split(':').next().unwrap_or(host);
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path).trim_matches('/');
+    if host.is_empty() || path.is_empty() {
+        return None;
+    }
+    Some(format!("{host}/{path}").to_lowercase())
+}
+
+/// ID of a mapping created for a link.
+fn mapping_id(project_id: u32, job: &str) -> String {
+    let job: String = job
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("gitlab-{project_id}-jenkins-{job}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(id: u32, path: &str) -> GitLabProject {
+        GitLabProject {
+            id,
+            name: path.rsplit('/').next().unwrap().to_string(),
+            path: path.rsplit('/').next().unwrap().to_string(),
+            web_url: format!("https://gitlab.example.com/{path}"),
+        }
+    }
+
+    fn summary(job: &str, scm_urls: &[&str]) -> JenkinsJobConfigSummary {
+        JenkinsJobConfigSummary {
+            job_name: job.to_string(),
+            job_type: "flow-definition".to_string(),
+            description: None,
+            disabled: false,
+            scm_urls: scm_urls.iter().map(|u| u.to_string()).collect(),
+            branches: Vec::new(),
+            script_path: None,
+            triggers: Vec::new(),
+            parameters: Vec::new(),
+            assigned_node: None,
+            credential_ids: Vec::new(),
+        }
+    }
+
+    fn mapping(id: &str, repo_id: &str, job_id: &str) -> Mapping {
+        Mapping {
+            id: id.to_string(),
+            repo_id: Some(repo_id.to_string()),
+            job_id: Some(job_id.to_string()),
+            namespace: None,
+            service_name: None,
+            project_id: None,
+            environment_id: None,
+            ownership: Ownership::default(),
+        }
+    }
+
+    #[test]
+    fn test_repository_key() {
+        let key = Some("gitlab.example.com/platform/api".to_string());
+        assert_eq!(
+            repository_key("https://gitlab.example.com/platform/api"),
+            key
+        );
+        assert_eq!(
+            repository_key("https://gitlab.example.com/Platform/API.git"),
+            key
+        );
+        assert_eq!(
+            repository_key("git@gitlab.example.com:platform/api.git"),
+            key
+        );
+        assert_eq!(
+            repository_key("ssh://git@gitlab.example.com:2222/platform/api.git"),
+            key
+        );
+        assert_eq!(
+            repository_key("https://ci-bot@gitlab.example.com:8443/platform/api/"),
+            key
+        );
+        assert_eq!(repository_key("not a url"), None);
+    }
+
+    #[test]
+    fn test_match_jobs() {
+        let projects = [project(7, "platform/api"), project(8, "platform/web")];
+        let summaries = [
+            summary("api/deploy", &["git@gitlab.example.com:platform/api.git"]),
+            summary(
+                "web/build",
+                &["https://gitlab.example.com/platform/web.git"],
+            ),
+            summary("legacy", &["https://github.com/acme/legacy.git"]),
+            summary("nightly-cleanup", &[]),
+            summary("api/release", &["https://gitlab.example.com/platform/api"]),
+        ];
+        let mappings = [
+            mapping("m1", "7", "api/deploy"),
+            mapping("m2", "99", "api/release"),
+        ];
+
+        let report = match_jobs(&projects, &summaries, &mappings);
+
+        let statuses: Vec<(&str, LinkageStatus)> = report
+            .proposals
+            .iter()
+            .map(|p| (p.jenkins_job.as_str(), p.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("api/deploy", LinkageStatus::Mapped),
+                ("web/build", LinkageStatus::New),
+                ("api/release", LinkageStatus::Conflict),
+            ]
+        );
+        assert_eq!(report.proposals[1].gitlab_project_path, "platform/web");
+        assert_eq!(report.proposals[2].mapping_id.as_deref(), Some("m2"));
+        assert_eq!(report.unmatched_jobs, ["legacy"]);
+        assert_eq!(report.jobs_without_scm, 1);
+        assert_eq!(mapping_id(8, "web/build"), "gitlab-8-jenkins-web-build");
+    }
+}
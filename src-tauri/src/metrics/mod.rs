@@ -0,0 +1,337 @@
+//! Internal metrics.
+//!
+//! Counts background polls, times integration commands, reports the load of
+//! the background task pool and keeps the result of the last connection test
+//! of each integration. When a metrics port is set in the preferences, they
+//! are served in the Prometheus text format at
+//! `http://127.0.0.1:<port>/metrics` (see `server`).
+
+mod server;
+
+pub use server::{configure, init, status, MetricsEndpointStatus};
+
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::task_pool::{self, InteractiveGuard};
+use crate::types::Integration;
+
+/// Upper bounds of the command duration histogram buckets.
+const LATENCY_BUCKETS_SECONDS: [f64; 10] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+#[derive(Debug, Default)]
+struct PollCounts {
+    successes: u64,
+    failures: u64,
+    last_success: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default)]
+struct Latency {
+    /// Observations per bucket (not cumulative)
+    buckets: [u64; LATENCY_BUCKETS_SECONDS.len()],
+    count: u64,
+    sum_seconds: f64,
+}
+
+#[derive(Debug)]
+struct IntegrationHealth {
+    name: String,
+    integration_type: String,
+    up: bool,
+    checked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    polls: BTreeMap<&'static str, PollCounts>,
+    commands: BTreeMap<&'static str, Latency>,
+    integrations: BTreeMap<String, IntegrationHealth>,
+}
+
+static REGISTRY: LazyLock<Mutex<Registry>> = LazyLock::new(|| Mutex::new(Registry::default()));
+
+fn registry() -> MutexGuard<'static, Registry> {
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Records the outcome of one run of a background poller (e.g. "tag_watch").
+pub fn record_poll(poller: &'static str, ok: bool) {
+    registry().record_poll(poller, ok, Utc::now());
+}
+
+/// Records the result of a connection test.
+pub fn record_integration_health(integration: &Integration, up: bool) {
+    registry().integrations.insert(
+        integration.id.clone(),
+        IntegrationHealth {
+            name: integration.name.clone(),
+            integration_type: format!("{:?}", integration.integration_type).to_lowercase(),
+            up,
+            checked_at: Utc::now(),
+        },
+    );
+}
+
+/// Renders all metrics in the Prometheus text format.
+pub fn render() -> String {
+    let mut out = registry().render();
+    render_task_pool(&mut out, &task_pool::status());
+    out
+}
+
+fn render_task_pool(out: &mut String, pool: &task_pool::TaskPoolStatus) {
+    header(
+        out,
+        "ops_flow_background_tasks",
+        "gauge",
+        "Background tasks by state and priority.",
+    );
+    let _ = writeln!(
+        out,
+        "ops_flow_background_tasks{{state=\"running\"}} {}",
+        pool.running
+    );
+    let _ = writeln!(
+        out,
+        "ops_flow_background_tasks{{state=\"queued\",priority=\"poller\"}} {}",
+        pool.queued_pollers
+    );
+    let _ = writeln!(
+        out,
+        "ops_flow_background_tasks{{state=\"queued\",priority=\"analytics\"}} {}",
+        pool.queued_analytics
+    );
+}
+
+/// Records how long a command took when dropped. While it lives, background
+/// tasks make way for the command (see `task_pool`).
+///
+/// Create it at the top of a command: `let _timer = CommandTimer::start("name");`
+pub struct CommandTimer {
+    command: &'static str,
+    started: Instant,
+    _interactive: InteractiveGuard,
+}
+
+impl CommandTimer {
+    pub fn start(command: &'static str) -> Self {
+        Self {
+            command,
+            started: Instant::now(),
+            _interactive: InteractiveGuard::start(),
+        }
+    }
+}
+
+impl Drop for CommandTimer {
+    fn drop(&mut self) {
+        registry().observe_command(self.command, self.started.elapsed());
+    }
+}
+
+impl Registry {
+    fn record_poll(&mut self, poller: &'static str, ok: bool, now: DateTime<Utc>) {
+        let counts = self.polls.entry(poller).or_default();
+        if ok {
+            counts.successes += 1;
+            counts.last_success = Some(now);
+        } else {
+            counts.failures += 1;
+        }
+    }
+
+    fn observe_command(&mut self, command: &'static str, duration: Duration) {
+        let latency = self.commands.entry(command).or_default();
+        let seconds = duration.as_secs_f64();
+        if let Some(bucket) = LATENCY_BUCKETS_SECONDS.iter().position(|le| seconds <= *le) {
+            latency.buckets[bucket] += 1;
+        }
+        latency.count += 1;
+        latency.sum_seconds += seconds;
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "ops_flow_polls_total",
+            "counter",
+            "Background polls by poller and result.",
+        );
+        for (poller, counts) in &self.polls {
+            let poller = escape_label(poller);
+            let _ = writeln!(
+                out,
+                "ops_flow_polls_total{{poller=\"{poller}\",result=\"success\"}} {}",
+                counts.successes
+            );
+            let _ = writeln!(
+                out,
+                "ops_flow_polls_total{{poller=\"{poller}\",result=\"failure\"}} {}",
+                counts.failures
+            );
+        }
+        header(
+            &mut out,
+            "ops_flow_poll_last_success_timestamp_seconds",
+            "gauge",
+            "Unix time of the last successful poll.",
+        );
+        for (poller, counts) in &self.polls {
+            if let Some(last) = counts.last_success {
+                let _ = writeln!(
+                    out,
+                    "ops_flow_poll_last_success_timestamp_seconds{{poller=\"{}\"}} {}",
+                    escape_label(poller),
+                    last.timestamp()
+                );
+            }
+        }
+
+        header(
+            &mut out,
+            "ops_flow_command_duration_seconds",
+            "histogram",
+            "Duration of integration commands.",
+        );
+        for (command, latency) in &self.commands {
+            let command = escape_label(command);
+            let mut cumulative = 0;
+            for (le, count) in LATENCY_BUCKETS_SECONDS.iter().zip(latency.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "ops_flow_command_duration_seconds_bucket{{command=\"{command}\",le=\"{le}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "ops_flow_command_duration_seconds_bucket{{command=\"{command}\",le=\"+Inf\"}} {}",
+                latency.count
+            );
+            let _ = writeln!(
+                out,
+                "ops_flow_command_duration_seconds_sum{{command=\"{command}\"}} {}",
+                latency.sum_seconds
+            );
+            let _ = writeln!(
+                out,
+                "ops_flow_command_duration_seconds_count{{command=\"{command}\"}} {}",
+                latency.count
+            );
+        }
+
+        header(
+            &mut out,
+            "ops_flow_integration_up",
+            "gauge",
+            "Whether the last connection test of an integration succeeded.",
+        );
+        for (id, health) in &self.integrations {
+            let _ = writeln!(
+                out,
+                "ops_flow_integration_up{{{}}} {}",
+                integration_labels(id, health),
+                u8::from(health.up)
+            );
+        }
+        header(
+            &mut out,
+            "ops_flow_integration_last_check_timestamp_seconds",
+            "gauge",
+            "Unix time of the last connection test of an integration.",
+        );
+        for (id, health) in &self.integrations {
+            let _ = writeln!(
+                out,
+                "ops_flow_integration_last_check_timestamp_seconds{{{}}} {}",
+                integration_labels(id, health),
+                health.checked_at.timestamp()
+            );
+        }
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn integration_labels(id: &str, health: &IntegrationHealth) -> String {
+    format!(
+        "integration=\"{}\",name=\"{}\",type=\"{}\"",
+        escape_label(id),
+        escape_label(&health.name),
+        escape_label(&health.integration_type)
+    )
+}
+
+/// Escapes a label value as required by the text format.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_polls_and_commands() {
+        let mut registry = Registry::default();
+        let now = DateTime::from_timestamp(1_714_557_600, 0).unwrap();
+        registry.record_poll("tag_watch", true, now);
+        registry.record_poll("tag_watch", false, now);
+        registry.observe_command("fetch_gitlab_projects", Duration::from_millis(80));
+        registry.observe_command("fetch_gitlab_projects", Duration::from_secs(3));
+
+        let text = registry.render();
+
+        assert!(text.contains("ops_flow_polls_total{poller=\"tag_watch\",result=\"success\"} 1\n"));
+        assert!(text.contains("ops_flow_polls_total{poller=\"tag_watch\",result=\"failure\"} 1\n"));
+        assert!(text.contains(
+            "ops_flow_poll_last_success_timestamp_seconds{poller=\"tag_watch\"} 1714557600\n"
+        ));
+        assert!(text.contains("# TYPE ops_flow_command_duration_seconds histogram\n"));
+        assert!(text.contains(
+            "ops_flow_command_duration_seconds_bucket{command=\"fetch_gitlab_projects\",le=\"0.05\"} 0\n"
+        ));
+        assert!(text.contains(
+            "ops_flow_command_duration_seconds_bucket{command=\"fetch_gitlab_projects\",le=\"0.1\"} 1\n"
+        ));
+        assert!(text.contains(
+            "ops_flow_command_duration_seconds_bucket{command=\"fetch_gitlab_projects\",le=\"5\"} 2\n"
+        ));
+        assert!(text.contains(
+            "ops_flow_command_duration_seconds_count{command=\"fetch_gitlab_projects\"} 2\n"
+        ));
+    }
+
+    #[test]
+    fn test_render_integration_health() {
+        let mut registry = Registry::default();
+        registry.integrations.insert(
+            "gitlab-main".to_string(),
+            IntegrationHealth {
+                name: "GitLab \"main\"".to_string(),
+                integration_type: "gitlab".to_string(),
+                up: false,
+                checked_at: DateTime::from_timestamp(1_714_557_600, 0).unwrap(),
+            },
+        );
+
+        let text = registry.render();
+
+        assert!(text.contains(
+            "ops_flow_integration_up{integration=\"gitlab-main\",name=\"GitLab \\\"main\\\"\",type=\"gitlab\"} 0\n"
+        ));
+    }
+}
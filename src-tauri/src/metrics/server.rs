@@ -0,0 +1,192 @@
+//! Metrics endpoint.
+//!
+//! Serves `GET /metrics` on the loopback interface only, so Prometheus has to
+//! run on the same machine (or scrape through a tunnel). While it runs, the
+//! connection of every integration is tested periodically to keep
+//! `ops_flow_integration_up` current.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::net::Ipv4Addr;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+use crate::commands::config::{check_integration_connection, load_integrations};
+use crate::webhooks::http;
+
+/// Time allowed for a scraper to send its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often integration connections are tested while the endpoint runs.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Current state of the metrics endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MetricsEndpointStatus {
+    pub running: bool,
+    /// Scrape URL (e.g., "http://127.0.0.1:9464/metrics")
+    pub url: Option<String>,
+    /// Scrapes served since the endpoint started
+    pub scrapes: u32,
+}
+
+/// The running endpoint.
+struct Endpoint {
+    /// Port from the preferences
+    port: u16,
+    status: MetricsEndpointStatus,
+    shutdown: oneshot::Sender<()>,
+}
+
+static ENDPOINT: LazyLock<Mutex<Option<Endpoint>>> = LazyLock::new(|| Mutex::new(None));
+
+fn endpoint() -> MutexGuard<'static, Option<Endpoint>> {
+    ENDPOINT.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Starts the endpoint at startup when a port is configured.
+pub fn init(app: &AppHandle, port: Option<u16>) {
+    if port.is_none() {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = configure(&app, port).await {
+            log::warn!("Metrics endpoint not started: {e}");
+        }
+    });
+}
+
+/// Starts, restarts or stops the endpoint to match the configured port.
+pub async fn configure(app: &AppHandle, port: Option<u16>) -> Result<(), String> {
+    if endpoint().as_ref().map(|e| e.port) == port {
+        return Ok(());
+    }
+    stop();
+    let Some(port) = port else {
+        return Ok(());
+    };
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .await
+        .map_err(|e| format!("Failed to serve metrics on 127.0.0.1:{port}: {e}"))?;
+    let url = match listener.local_addr() {
+        Ok(address) => format!("http://{address}/metrics"),
+        Err(_) => format!("http://127.0.0.1:{port}/metrics"),
+    };
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    *endpoint() = Some(Endpoint {
+        port,
+        status: MetricsEndpointStatus {
+            running: true,
+            url: Some(url.clone()),
+            scrapes: 0,
+        },
+        shutdown: shutdown_tx,
+    });
+
+    log::info!("Serving metrics at {url}");
+    tauri::async_runtime::spawn(serve(app.clone(), listener, shutdown_rx));
+    Ok(())
+}
+
+fn stop() {
+    if let Some(running) = endpoint().take() {
+        let _ = running.shutdown.send(());
+        log::info!("Metrics endpoint stopped");
+    }
+}
+
+/// Returns the endpoint state.
+pub fn status() -> MetricsEndpointStatus {
+    match endpoint().as_ref() {
+        Some(running) => running.status.clone(),
+        None => MetricsEndpointStatus {
+            running: false,
+            url: None,
+            scrapes: 0,
+        },
+    }
+}
+
+async fn serve(app: AppHandle, listener: TcpListener, mut shutdown: oneshot::Receiver<()>) {
+    let mut health_checks = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            _ = health_checks.tick() => {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move { check_integrations(&app).await });
+            }
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tauri::async_runtime::spawn(handle_connection(stream));
+                }
+                Err(e) => log::warn!("Failed to accept metrics connection: {e}"),
+            },
+        }
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream) {
+    let (status, body) =
+        match tokio::time::timeout(REQUEST_TIMEOUT, http::read_request(&mut stream)).await {
+            Ok(Ok(request)) => respond(&request.method, &request.path),
+            Ok(Err(response)) => (response.status, response.body.to_string()),
+            Err(_) => (400, "Timed out reading request\n".to_string()),
+        };
+
+    if let Err(e) = http::write_text(&mut stream, status, CONTENT_TYPE, &body).await {
+        log::debug!("Failed to write metrics response: {e}");
+    }
+}
+
+fn respond(method: &str, path: &str) -> (u16, String) {
+    if path != "/metrics" {
+        return (404, "Not found, scrape /metrics\n".to_string());
+    }
+    if method != "GET" {
+        return (405, "Only GET is supported\n".to_string());
+    }
+    if let Some(running) = endpoint().as_mut() {
+        running.status.scrapes += 1;
+    }
+    (200, super::render())
+}
+
+/// Tests every integration connection, recording the results.
+async fn check_integrations(app: &AppHandle) {
+    let integrations = match load_integrations(app.clone()).await {
+        Ok(integrations) => integrations,
+        Err(e) => {
+            log::warn!("Failed to load integrations for health checks: {e}");
+            return;
+        }
+    };
+    for integration in integrations {
+        if let Err(e) = check_integration_connection(app, &integration.id).await {
+            log::debug!("Health check of {} failed: {e}", integration.name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_respond() {
+        assert_eq!(respond("GET", "/").0, 404);
+        assert_eq!(respond("POST", "/metrics").0, 405);
+
+        let (status, body) = respond("GET", "/metrics");
+        assert_eq!(status, 200);
+        assert!(body.contains("# TYPE ops_flow_polls_total counter"));
+    }
+}
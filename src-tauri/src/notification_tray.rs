@@ -0,0 +1,124 @@
+//! Tray menu listing the actions of recent notifications.
+//!
+//! Native notifications can't show buttons on every platform, and the
+//! backends don't report presses where they can. The tray menu lists each
+//! recent actionable notification with its actions, so they can be run
+//! without opening the app.
+
+use tauri::menu::{IsMenuItem, Menu, MenuItem, Submenu};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager, Wry};
+
+use crate::commands::notifications::{self, ActionableNotification};
+
+const TRAY_ID: &str = "notifications";
+
+/// Prefix of the menu item IDs of notification actions.
+const ACTION_ITEM_PREFIX: &str = "notification:";
+
+/// Number of notifications listed in the tray menu.
+const MAX_TRAY_NOTIFICATIONS: usize = 10;
+
+/// Creates the tray icon with the current notifications.
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let menu = build_menu(app, &notifications::recent_notifications())?;
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Ops Flow notifications")
+        .menu(&menu)
+        .show_menu_on_left_click(true)
+        .on_menu_event(|app, event| {
+            let Some((notification_id, action_id)) = parse_action_item(event.id().as_ref()) else {
+                return;
+            };
+            let app = app.clone();
+            let action_id = action_id.to_string();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = notifications::run_action(&app, notification_id, &action_id).await {
+                    log::warn!("Failed to run notification action {action_id}: {e}");
+                }
+            });
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Rebuilds the tray menu from the given notifications, newest first.
+pub fn refresh(app: &AppHandle, notifications: &[ActionableNotification]) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let result = build_menu(app, notifications).and_then(|menu| tray.set_menu(Some(menu)));
+    if let Err(e) = result {
+        log::warn!("Failed to update the notification tray menu: {e}");
+    }
+}
+
+fn build_menu(
+    app: &AppHandle,
+    notifications: &[ActionableNotification],
+) -> tauri::Result<Menu<Wry>> {
+    let menu = Menu::new(app)?;
+    if notifications.is_empty() {
+        menu.append(&MenuItem::new(
+            app,
+            "No recent notifications",
+            false,
+            None::<&str>,
+        )?)?;
+    }
+    for notification in notifications.iter().take(MAX_TRAY_NOTIFICATIONS) {
+        let items = notification
+            .actions
+            .iter()
+            .map(|action| {
+                MenuItem::with_id(
+                    app,
+                    action_item_id(notification.id, &action.id),
+                    &action.label,
+                    true,
+                    None::<&str>,
+                )
+            })
+            .collect::<tauri::Result<Vec<_>>>()?;
+        let items: Vec<&dyn IsMenuItem<Wry>> = items
+            .iter()
+            .map(|item| item as &dyn IsMenuItem<Wry>)
+            .collect();
+        menu.append(&Submenu::with_items(
+            app,
+            &notification.title,
+            true,
+            &items,
+        )?)?;
+    }
+    Ok(menu)
+}
+
+fn action_item_id(notification_id: u32, action_id: &str) -> String {
+    format!("{ACTION_ITEM_PREFIX}{notification_id}:{action_id}")
+}
+
+/// Splits a menu item ID into the notification and action IDs. Returns
+/// `None` for items that aren't notification actions.
+fn parse_action_item(item_id: &str) -> Option<(u32, &str)> {
+    let (notification_id, action_id) = item_id.strip_prefix(ACTION_ITEM_PREFIX)?.split_once(':')?;
+    Some((notification_id.parse().ok()?, action_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_item_ids_round_trip() {
+        let id = action_item_id(12, "retry:all");
+        assert_eq!(id, "notification:12:retry:all");
+        assert_eq!(parse_action_item(&id), Some((12, "retry:all")));
+
+        assert_eq!(parse_action_item("quit"), None);
+        assert_eq!(parse_action_item("notification:abc:open"), None);
+    }
+}
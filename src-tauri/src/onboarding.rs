@@ -0,0 +1,286 @@
+//! First-run guided setup.
+//!
+//! Onboarding walks through the steps needed before the app is useful: add
+//! integrations, check that they connect, then create an example project
+//! with a mapping to start from. Progress is stored in
+//! `<app data>/onboarding.json`, so setup resumes where it stopped after a
+//! restart. Steps complete in order; the current step can be retried until
+//! it succeeds, and a completed step can be run again (e.g., to re-test
+//! connections after fixing credentials).
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::config::{
+    check_integration_connection, load_environments, load_integrations, load_mappings,
+    load_projects, save_environments, save_mappings, save_projects,
+};
+use crate::types::{Environment, Mapping, Ownership, Project, Timestamp};
+
+/// ID of the project created by the example project step.
+const EXAMPLE_PROJECT_ID: &str = "example-project";
+
+/// Onboarding steps, in order.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    /// At least one integration is configured
+    AddIntegrations,
+    /// At least one integration connects
+    TestConnections,
+    /// An example project, environment and mapping exist
+    CreateExampleProject,
+}
+
+impl OnboardingStep {
+    const ALL: [OnboardingStep; 3] = [
+        OnboardingStep::AddIntegrations,
+        OnboardingStep::TestConnections,
+        OnboardingStep::CreateExampleProject,
+    ];
+}
+
+/// Connection test result of one integration.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingConnectionResult {
+    pub integration_id: String,
+    pub integration_name: String,
+    pub connected: bool,
+    pub error: Option<String>,
+}
+
+/// Onboarding progress.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingState {
+    pub completed_steps: Vec<OnboardingStep>,
+    /// Next step to complete; `None` once onboarding is finished
+    pub current_step: Option<OnboardingStep>,
+    /// Results of the last connection test
+    pub connection_results: Vec<OnboardingConnectionResult>,
+    /// Project created by the example project step
+    pub example_project_id: Option<String>,
+    pub updated_at: Option<Timestamp>,
+}
+
+impl OnboardingState {
+    fn new() -> Self {
+        Self {
+            current_step: Some(OnboardingStep::ALL[0]),
+            ..Self::default()
+        }
+    }
+
+    /// Checks that `step` can run: it is the current step or was completed.
+    fn check_can_run(&self, step: OnboardingStep) -> Result<(), String> {
+        if self.current_step == Some(step) || self.completed_steps.contains(&step) {
+            return Ok(());
+        }
+        match self.current_step {
+            Some(current) => Err(format!("Complete the {current:?} step before {step:?}")),
+            None => Err("Onboarding is already finished".to_string()),
+        }
+    }
+
+    /// Marks `step` completed and moves to the first step not completed yet.
+    fn complete(&mut self, step: OnboardingStep) {
+        if !self.completed_steps.contains(&step) {
+            self.completed_steps.push(step);
+        }
+        self.current_step = OnboardingStep::ALL
+            .into_iter()
+            .find(|s| !self.completed_steps.contains(s));
+        self.updated_at = Some(Timestamp::from_datetime(Utc::now()));
+    }
+}
+
+/// Returns the onboarding progress, starting it on first run.
+pub fn state(app: &AppHandle) -> Result<OnboardingState, String> {
+    let path = state_path(app)?;
+    if !path.exists() {
+        return Ok(OnboardingState::new());
+    }
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read onboarding state: {e}"))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse onboarding state: {e}"))
+}
+
+/// Runs an onboarding step and returns the updated progress. A step that
+/// fails leaves the progress where it was (connection results are kept, so
+/// the failures can be shown).
+pub async fn complete_step(
+    app: &AppHandle,
+    step: OnboardingStep,
+) -> Result<OnboardingState, String> {
+    let mut state = state(app)?;
+    state.check_can_run(step)?;
+    log::info!("Running onboarding step {step:?}");
+
+    match step {
+        OnboardingStep::AddIntegrations => {
+            if load_integrations(app.clone()).await?.is_empty() {
+                return Err("Add at least one integration to continue".to_string());
+            }
+        }
+        OnboardingStep::TestConnections => {
+            state.connection_results = test_connections(app).await?;
+            if !state.connection_results.iter().any(|r| r.connected) {
+                save_state(app, &state)?;
+                return Err("None of the integrations could connect".to_string());
+            }
+        }
+        OnboardingStep::CreateExampleProject => {
+            state.example_project_id = Some(create_example_project(app).await?);
+        }
+    }
+
+    state.complete(step);
+    save_state(app, &state)?;
+    Ok(state)
+}
+
+async fn test_connections(app: &AppHandle) -> Result<Vec<OnboardingConnectionResult>, String> {
+    let mut results = Vec::new();
+    for integration in load_integrations(app.clone()).await? {
+        let result = check_integration_connection(app, &integration.id).await;
+        if let Err(e) = &result {
+            log::warn!(
+                "Onboarding connection test of {} failed: {e}",
+                integration.name
+            );
+        }
+        results.push(OnboardingConnectionResult {
+            integration_id: integration.id,
+            integration_name: integration.name,
+            connected: result.is_ok(),
+            error: result.err(),
+        });
+    }
+    Ok(results)
+}
+
+/// Creates the example project with a `dev` environment and a mapping,
+/// unless it already exists. Returns the project ID.
+async fn create_example_project(app: &AppHandle) -> Result<String, String> {
+    let mut projects = load_projects(app.clone()).await?;
+    if projects.iter().any(|p| p.id == EXAMPLE_PROJECT_ID) {
+        return Ok(EXAMPLE_PROJECT_ID.to_string());
+    }
+
+    let (project, environment, mapping) = example_project();
+    let mut environments = load_environments(app.clone()).await?;
+    let mut mappings = load_mappings(app.clone()).await?;
+    projects.push(project);
+    environments.push(environment);
+    mappings.push(mapping);
+
+    save_projects(app.clone(), projects).await?;
+    save_environments(app.clone(), environments).await?;
+    save_mappings(app.clone(), mappings).await?;
+    log::info!("Created the example project");
+    Ok(EXAMPLE_PROJECT_ID.to_string())
+}
+
+fn example_project() -> (Project, Environment, Mapping) {
+    let environment_id = format!("{EXAMPLE_PROJECT_ID}-dev");
+    let project = Project {
+        id: EXAMPLE_PROJECT_ID.to_string(),
+        name: "Example project".to_string(),
+        description: Some(
+            "Created during setup. Point its mapping at a real repository, job and \
+             namespace, or delete it."
+                .to_string(),
+        ),
+        environments: vec![environment_id.clone()],
+        ownership: Ownership::default(),
+    };
+    let environment = Environment {
+        id: environment_id.clone(),
+        name: "dev".to_string(),
+        namespace: Some("default".to_string()),
+        project_id: EXAMPLE_PROJECT_ID.to_string(),
+        variables: Default::default(),
+    };
+    let mapping = Mapping {
+        id: format!("{EXAMPLE_PROJECT_ID}-mapping"),
+        repo_id: None,
+        job_id: None,
+        namespace: Some("default".to_string()),
+        service_name: Some("example-service".to_string()),
+        project_id: Some(EXAMPLE_PROJECT_ID.to_string()),
+        environment_id: Some(environment_id),
+        ownership: Ownership::default(),
+    };
+    (project, environment, mapping)
+}
+
+fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    Ok(app_data_dir.join("onboarding.json"))
+}
+
+fn save_state(app: &AppHandle, state: &OnboardingState) -> Result<(), String> {
+    let path = state_path(app)?;
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize onboarding state: {e}"))?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {e}"))?;
+    }
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json)
+        .map_err(|e| format!("Failed to write onboarding state: {e}"))?;
+    if let Err(rename_err) = std::fs::rename(&temp_path, &path) {
+        if let Err(remove_err) = std::fs::remove_file(&temp_path) {
+            log::warn!("Failed to remove temp file after rename failure: {remove_err}");
+        }
+        return Err(format!("Failed to finalize onboarding state: {rename_err}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_complete_in_order() {
+        let mut state = OnboardingState::new();
+        assert_eq!(state.current_step, Some(OnboardingStep::AddIntegrations));
+        assert!(state
+            .check_can_run(OnboardingStep::CreateExampleProject)
+            .is_err());
+
+        state.complete(OnboardingStep::AddIntegrations);
+        state.complete(OnboardingStep::TestConnections);
+        assert_eq!(
+            state.current_step,
+            Some(OnboardingStep::CreateExampleProject)
+        );
+        // Completed steps can run again
+        assert!(state.check_can_run(OnboardingStep::TestConnections).is_ok());
+
+        state.complete(OnboardingStep::CreateExampleProject);
+        assert_eq!(state.current_step, None);
+        assert_eq!(state.completed_steps.len(), 3);
+    }
+
+    #[test]
+    fn test_example_project_is_consistent() {
+        let (project, environment, mapping) = example_project();
+        assert_eq!(project.environments, std::slice::from_ref(&environment.id));
+        assert_eq!(environment.project_id, project.id);
+        assert_eq!(mapping.project_id.as_ref(), Some(&project.id));
+        assert_eq!(mapping.environment_id.as_ref(), Some(&environment.id));
+    }
+}
@@ -0,0 +1,175 @@
+//! Service ownership lookup.
+//!
+//! Projects and mappings carry an [`Ownership`]; a service's owner is its
+//! mapping's, with unset fields taken from its project. Notifications about
+//! a service are routed to the resulting Slack channel and on-call rotation.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+
+use crate::commands::config::{load_mappings, load_projects};
+use crate::types::{Mapping, Ownership, Project};
+
+/// Service a notification is about.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceRef {
+    pub project_id: String,
+    /// Environment, when the service's mapping is environment-specific
+    pub environment_id: Option<String>,
+    /// Kubernetes service name; the project's owner is used when unset
+    pub service_name: Option<String>,
+}
+
+/// Resolves the owner of a service from the projects and mappings.
+///
+/// Returns None when neither the project nor the mapping has an owner.
+pub fn resolve(
+    projects: &[Project],
+    mappings: &[Mapping],
+    service: &ServiceRef,
+) -> Option<Ownership> {
+    let project = projects
+        .iter()
+        .find(|p| p.id == service.project_id)
+        .map(|p| p.ownership.clone())
+        .unwrap_or_default();
+
+    let mapping = service.service_name.as_deref().and_then(|name| {
+        mappings.iter().find(|m| {
+            m.project_id.as_deref() == Some(service.project_id.as_str())
+                && m.service_name.as_deref() == Some(name)
+                && (service.environment_id.is_none()
+                    || m.environment_id.is_none()
+                    || m.environment_id == service.environment_id)
+        })
+    });
+
+    let ownership = match mapping {
+        Some(mapping) => mapping.ownership.clone().or(&project),
+        None => project,
+    };
+    (!ownership.is_empty()).then_some(ownership)
+}
+
+/// Resolves the owner of a service from the saved configuration.
+pub async fn lookup(app: &AppHandle, service: &ServiceRef) -> Result<Option<Ownership>, String> {
+    let projects = load_projects(app.clone()).await?;
+    let mappings = load_mappings(app.clone()).await?;
+    Ok(resolve(&projects, &mappings, service))
+}
+
+/// One-line summary of an owner (e.g., "checkout · #checkout-alerts").
+pub fn summary(ownership: &Ownership) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    parts.extend(ownership.owner_team.clone());
+    parts.extend(ownership.slack_channel.clone());
+    if let Some(on_call) = &ownership.on_call {
+        parts.push(format!(
+            "on-call {:?} {}",
+            on_call.provider, on_call.rotation_id
+        ));
+    }
+    parts.join(" · ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{OnCallProvider, OnCallRotation};
+
+    fn ownership(team: &str, channel: Option<&str>) -> Ownership {
+        Ownership {
+            owner_team: Some(team.to_string()),
+            slack_channel: channel.map(str::to_string),
+            on_call: None,
+        }
+    }
+
+    fn project() -> Project {
+        Project {
+            id: "checkout".to_string(),
+            name: "Checkout".to_string(),
+            description: None,
+            environments: Vec::new(),
+            ownership: Ownership {
+                on_call: Some(OnCallRotation {
+                    provider: OnCallProvider::PagerDuty,
+                    rotation_id: "PD12345".to_string(),
+                }),
+                ..ownership("checkout", Some("#checkout"))
+            },
+        }
+    }
+
+    fn mapping(service: &str, environment_id: Option<&str>, ownership: Ownership) -> Mapping {
+        Mapping {
+            id: format!("{service}-mapping"),
+            repo_id: None,
+            job_id: None,
+            namespace: None,
+            service_name: Some(service.to_string()),
+            project_id: Some("checkout".to_string()),
+            environment_id: environment_id.map(str::to_string),
+            ownership,
+        }
+    }
+
+    fn service(name: Option<&str>, environment_id: Option<&str>) -> ServiceRef {
+        ServiceRef {
+            project_id: "checkout".to_string(),
+            environment_id: environment_id.map(str::to_string),
+            service_name: name.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_mapping_overrides_project() {
+        let mappings = [
+            mapping("payments", Some("prod"), ownership("payments", None)),
+            mapping("cart", None, Ownership::default()),
+        ];
+        let projects = [project()];
+
+        let payments = resolve(
+            &projects,
+            &mappings,
+            &service(Some("payments"), Some("prod")),
+        );
+        assert_eq!(
+            payments,
+            Some(Ownership {
+                owner_team: Some("payments".to_string()),
+                ..project().ownership
+            })
+        );
+
+        // No owner on the mapping, or a mapping of another environment
+        let cart = resolve(&projects, &mappings, &service(Some("cart"), Some("prod")));
+        assert_eq!(cart, Some(project().ownership));
+        let staging = resolve(
+            &projects,
+            &mappings,
+            &service(Some("payments"), Some("staging")),
+        );
+        assert_eq!(staging, Some(project().ownership));
+    }
+
+    #[test]
+    fn test_no_owner() {
+        let mut project = project();
+        project.ownership = Ownership::default();
+
+        assert_eq!(resolve(&[project], &[], &service(None, None)), None);
+        assert_eq!(resolve(&[], &[], &service(Some("cart"), None)), None);
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(
+            summary(&project().ownership),
+            "checkout · #checkout · on-call PagerDuty PD12345"
+        );
+    }
+}
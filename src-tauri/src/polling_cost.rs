@@ -0,0 +1,330 @@
+//! Polling cost estimate.
+//!
+//! Adds up the requests the background pollers send per hour with the
+//! current configuration (tag watches, notifying saved filters, event
+//! recordings and uptime targets) and compares each integration's load with
+//! its known API rate limit, so intervals can be tuned before the poller
+//! runs into 429s. Estimates assume one page per list request; daily jobs
+//! (inventory snapshots, certificate checks, reports) and on-demand
+//! refreshes are not counted.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashSet;
+use tauri::AppHandle;
+
+use crate::commands::config::{
+    load_event_recordings, load_integrations, load_saved_filters, load_tag_watches,
+    load_uptime_targets,
+};
+use crate::saved_filters;
+use crate::tag_watcher;
+use crate::types::{Integration, IntegrationType};
+
+/// Authenticated API requests per minute allowed per user on GitLab.com.
+const GITLAB_COM_REQUESTS_PER_MINUTE: u32 = 2_000;
+
+/// Share of a rate limit (percent) above which polling is reported, leaving
+/// room for interactive use of the same token.
+const LIMIT_WARNING_PERCENT: f64 = 50.0;
+
+/// Load (requests per hour) above which an integration without a known
+/// limit is reported: one request per second on average.
+const HIGH_LOAD_PER_HOUR: f64 = 3_600.0;
+
+/// Requests sent by one background poller.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct PollingSource {
+    /// "tag_watch", "saved_filter", "event_recording" or "uptime"
+    pub poller: String,
+    /// Name of the watch, filter, recording or target
+    pub name: String,
+    /// Integration called; None for environment URLs
+    pub integration_id: Option<String>,
+    pub requests_per_hour: f64,
+    /// Long-lived streams held open
+    pub streams: u32,
+}
+
+/// Published API rate limit of a service.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct KnownRateLimit {
+    pub description: String,
+    pub requests_per_minute: u32,
+}
+
+/// Polling load on one integration.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct IntegrationPollingCost {
+    pub integration_id: String,
+    pub name: String,
+    pub integration_type: IntegrationType,
+    pub requests_per_hour: f64,
+    pub streams: u32,
+    pub rate_limit: Option<KnownRateLimit>,
+    /// Share of the rate limit used, in percent
+    pub limit_usage_percent: Option<f64>,
+}
+
+/// Estimated polling load of the current configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct PollingCostEstimate {
+    /// Integrations polled, busiest first
+    pub integrations: Vec<IntegrationPollingCost>,
+    pub sources: Vec<PollingSource>,
+    /// Requests to environment URLs (uptime probes)
+    pub endpoint_requests_per_hour: f64,
+    pub total_requests_per_hour: f64,
+    pub warnings: Vec<String>,
+}
+
+/// Estimates the polling load of the saved configuration.
+pub async fn estimate(app: &AppHandle) -> Result<PollingCostEstimate, String> {
+    let mut sources = Vec::new();
+    let mut warnings = Vec::new();
+
+    for watch in load_tag_watches(app.clone()).await? {
+        sources.push(PollingSource {
+            poller: "tag_watch".to_string(),
+            name: watch.name,
+            integration_id: Some(watch.integration_id),
+            requests_per_hour: per_hour(
+                watch
+                    .interval_seconds
+                    .max(tag_watcher::MIN_INTERVAL_SECONDS),
+            ),
+            streams: 0,
+        });
+    }
+
+    // Filters share fetched records, so a target read by several filters
+    // is counted once
+    let filter_interval = saved_filters::WATCH_INTERVAL.as_secs() as u32;
+    let mut reads_seen = HashSet::new();
+    for filter in load_saved_filters(app.clone()).await? {
+        if !filter.notify {
+            continue;
+        }
+        let reads = match saved_filters::polled_reads(app, &filter).await {
+            Ok(reads) => reads,
+            Err(e) => {
+                warnings.push(format!(
+                    "Saved filter {} is not counted: {}",
+                    filter.name, e
+                ));
+                continue;
+            }
+        };
+        let new_reads = reads
+            .into_iter()
+            .filter(|read| {
+                reads_seen.insert((filter.integration_id.clone(), filter.resource, read.clone()))
+            })
+            .count();
+        sources.push(PollingSource {
+            poller: "saved_filter".to_string(),
+            name: filter.name,
+            integration_id: Some(filter.integration_id),
+            requests_per_hour: new_reads as f64 * per_hour(filter_interval),
+            streams: 0,
+        });
+    }
+
+    for recording in load_event_recordings(app.clone()).await? {
+        if !recording.enabled {
+            continue;
+        }
+        sources.push(PollingSource {
+            poller: "event_recording".to_string(),
+            name: recording.name,
+            integration_id: Some(recording.integration_id),
+            requests_per_hour: 0.0,
+            streams: recording.namespaces.len() as u32,
+        });
+    }
+
+    for target in load_uptime_targets(app.clone()).await? {
+        sources.push(PollingSource {
+            poller: "uptime".to_string(),
+            name: target.name,
+            integration_id: None,
+            requests_per_hour: per_hour(target.interval_seconds.max(1)),
+            streams: 0,
+        });
+    }
+
+    let integrations = load_integrations(app.clone()).await?;
+    let mut estimate = summarize(&integrations, sources);
+    warnings.append(&mut estimate.warnings);
+    estimate.warnings = warnings;
+    Ok(estimate)
+}
+
+fn per_hour(interval_seconds: u32) -> f64 {
+    3_600.0 / f64::from(interval_seconds)
+}
+
+/// Rate limit published for the service an integration points at.
+fn known_rate_limit(integration: &Integration) -> Option<KnownRateLimit> {
+    let host = reqwest::Url::parse(&integration.base_url)
+        .ok()?
+        .host_str()?
+        .to_lowercase();
+    match integration.integration_type {
+        IntegrationType::GitLab if host == "gitlab.com" => Some(KnownRateLimit {
+            description: "GitLab.com authenticated API requests per user".to_string(),
+            requests_per_minute: GITLAB_COM_REQUESTS_PER_MINUTE,
+        }),
+        _ => None,
+    }
+}
+
+/// Totals the sources per integration and checks them against rate limits.
+fn summarize(integrations: &[Integration], sources: Vec<PollingSource>) -> PollingCostEstimate {
+    let mut warnings = Vec::new();
+    let mut costs: Vec<IntegrationPollingCost> = Vec::new();
+    let mut endpoint_requests_per_hour = 0.0;
+
+    for source in &sources {
+        let Some(integration_id) = &source.integration_id else {
+            endpoint_requests_per_hour += source.requests_per_hour;
+            continue;
+        };
+        if let Some(cost) = costs
+            .iter_mut()
+            .find(|c| &c.integration_id == integration_id)
+        {
+            cost.requests_per_hour += source.requests_per_hour;
+            cost.streams += source.streams;
+            continue;
+        }
+        let Some(integration) = integrations.iter().find(|i| &i.id == integration_id) else {
+            warnings.push(format!(
+                "{} {} uses unknown integration {}",
+                source.poller, source.name, integration_id
+            ));
+            continue;
+        };
+        costs.push(IntegrationPollingCost {
+            integration_id: integration.id.clone(),
+            name: integration.name.clone(),
+            integration_type: integration.integration_type.clone(),
+            requests_per_hour: source.requests_per_hour,
+            streams: source.streams,
+            rate_limit: known_rate_limit(integration),
+            limit_usage_percent: None,
+        });
+    }
+
+    for cost in &mut costs {
+        match &cost.rate_limit {
+            Some(limit) => {
+                let per_minute = cost.requests_per_hour / 60.0;
+                let usage = per_minute / f64::from(limit.requests_per_minute) * 100.0;
+                cost.limit_usage_percent = Some(usage);
+                if usage >= LIMIT_WARNING_PERCENT {
+                    warnings.push(format!(
+                        "{} polling uses {:.0}% of the {} limit ({} per minute); raise poll intervals",
+                        cost.name, usage, limit.description, limit.requests_per_minute
+                    ));
+                }
+            }
+            None if cost.requests_per_hour >= HIGH_LOAD_PER_HOUR => warnings.push(format!(
+                "{} is polled {:.0} times per hour; a rate limit set on the instance may throttle it",
+                cost.name, cost.requests_per_hour
+            )),
+            None => {}
+        }
+    }
+    costs.sort_by(|a, b| b.requests_per_hour.total_cmp(&a.requests_per_hour));
+
+    let total_requests_per_hour =
+        costs.iter().map(|c| c.requests_per_hour).sum::<f64>() + endpoint_requests_per_hour;
+    PollingCostEstimate {
+        integrations: costs,
+        sources,
+        endpoint_requests_per_hour,
+        total_requests_per_hour,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn integration(id: &str, base_url: &str) -> Integration {
+        Integration {
+            id: id.to_string(),
+            integration_type: IntegrationType::GitLab,
+            name: id.to_string(),
+            base_url: base_url.to_string(),
+            credentials_ref: None,
+            api_version: None,
+        }
+    }
+
+    fn source(integration_id: Option<&str>, requests_per_hour: f64) -> PollingSource {
+        PollingSource {
+            poller: "tag_watch".to_string(),
+            name: "watch".to_string(),
+            integration_id: integration_id.map(str::to_string),
+            requests_per_hour,
+            streams: 0,
+        }
+    }
+
+    #[test]
+    fn test_summarize_totals_per_integration() {
+        let integrations = [
+            integration("cloud", "https://gitlab.com"),
+            integration("self", "https://gitlab.example.com"),
+        ];
+        let estimate = summarize(
+            &integrations,
+            vec![
+                source(Some("self"), 120.0),
+                source(Some("cloud"), 30.0),
+                source(Some("self"), 12.0),
+                source(None, 60.0),
+            ],
+        );
+
+        assert_eq!(estimate.integrations[0].integration_id, "self");
+        assert_eq!(estimate.integrations[0].requests_per_hour, 132.0);
+        assert_eq!(estimate.integrations[0].rate_limit, None);
+        assert!(estimate.integrations[1].rate_limit.is_some());
+        assert_eq!(estimate.endpoint_requests_per_hour, 60.0);
+        assert_eq!(estimate.total_requests_per_hour, 222.0);
+        assert!(estimate.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_summarize_warns_near_rate_limits() {
+        let integrations = [
+            integration("cloud", "https://GitLab.com/"),
+            integration("self", "https://gitlab.example.com"),
+        ];
+        let estimate = summarize(
+            &integrations,
+            vec![
+                // 1,200 per minute
+                source(Some("cloud"), 72_000.0),
+                source(Some("self"), 7_200.0),
+                source(Some("gone"), 10.0),
+            ],
+        );
+
+        assert_eq!(estimate.integrations[0].limit_usage_percent, Some(60.0));
+        assert_eq!(estimate.warnings.len(), 3);
+        assert!(estimate.warnings[0].contains("unknown integration gone"));
+        assert!(estimate.warnings[1].contains("60%"));
+        assert!(estimate.warnings[2].contains("7200 times per hour"));
+    }
+
+    #[test]
+    fn test_per_hour() {
+        assert_eq!(per_hour(300), 12.0);
+        assert_eq!(per_hour(30), 120.0);
+    }
+}
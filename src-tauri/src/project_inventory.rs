@@ -0,0 +1,539 @@
+//! GitLab project inventory snapshots.
+//!
+//! Once a day every GitLab integration's project list (IDs, names, default
+//! branches, visibility, archived flag) is written to
+//! `<app data>/project_inventory/<integration id>/<snapshot id>.json`. Each
+//! new snapshot is diffed against the previous one; renamed, archived, new
+//! and removed projects emit a `project-inventory-changed` event and raise a
+//! native notification.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::config::load_integrations;
+use crate::commands::gitlab::gitlab_adapter;
+use crate::commands::notifications::send_native_notification;
+use crate::integrations::gitlab::GitLabInventoryProject;
+use crate::metrics;
+use crate::task_pool::{self, Priority};
+use crate::types::{IntegrationType, Timestamp};
+use crate::windows;
+
+/// Event emitted with a `ProjectInventoryDiff` when a scheduled snapshot
+/// differs from the previous one.
+pub const PROJECT_INVENTORY_CHANGED_EVENT: &str = "project-inventory-changed";
+
+/// Snapshots kept per integration; older ones are removed.
+const MAX_SNAPSHOTS: usize = 60;
+
+/// Delay before the first scheduled snapshot after startup.
+const STARTUP_DELAY: Duration = Duration::from_secs(180);
+
+/// Interval between scheduled snapshots.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A recorded project inventory.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ProjectInventorySnapshot {
+    /// Snapshot identifier (sortable by time)
+    pub id: String,
+    pub integration_id: String,
+    pub taken_at: Timestamp,
+    pub projects: Vec<GitLabInventoryProject>,
+}
+
+/// A recorded project inventory without its projects.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ProjectInventorySnapshotInfo {
+    pub id: String,
+    pub integration_id: String,
+    pub taken_at: Timestamp,
+    pub project_count: u32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectChangeKind {
+    Added,
+    Removed,
+    Renamed,
+    Archived,
+    Unarchived,
+    DefaultBranchChanged,
+    VisibilityChanged,
+}
+
+/// A change of one project between two snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ProjectInventoryChange {
+    pub project_id: u32,
+    /// Full path in the newer snapshot (the older one for removed projects)
+    pub path: String,
+    pub kind: ProjectChangeKind,
+    /// Previous value (the path for renames)
+    pub from: Option<String>,
+    /// New value (the path for renames)
+    pub to: Option<String>,
+}
+
+/// Changes between two snapshots of an integration.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ProjectInventoryDiff {
+    pub integration_id: String,
+    pub from: ProjectInventorySnapshotInfo,
+    pub to: ProjectInventorySnapshotInfo,
+    pub changes: Vec<ProjectInventoryChange>,
+}
+
+impl ProjectInventoryDiff {
+    /// Short description of the changes (e.g., "2 new, 1 renamed").
+    pub fn summary(&self) -> String {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for change in &self.changes {
+            let label = match change.kind {
+                ProjectChangeKind::Added => "new",
+                ProjectChangeKind::Removed => "removed",
+                ProjectChangeKind::Renamed => "renamed",
+                ProjectChangeKind::Archived => "archived",
+                ProjectChangeKind::Unarchived => "unarchived",
+                ProjectChangeKind::DefaultBranchChanged => "default branch changed",
+                ProjectChangeKind::VisibilityChanged => "visibility changed",
+            };
+            *counts.entry(label).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(label, count)| format!("{count} {label}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Starts a background task snapshotting every GitLab integration shortly
+/// after startup and then daily.
+pub fn start_scheduled_snapshots(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        loop {
+            let result =
+                task_pool::run(Priority::Poller, "project_inventory", snapshot_all(&app)).await;
+            metrics::record_poll("project_inventory", result.is_ok());
+            if let Err(e) = result {
+                log::warn!("Project inventory snapshot failed: {e}");
+            }
+            tokio::time::sleep(SNAPSHOT_INTERVAL).await;
+        }
+    });
+}
+
+/// Snapshots every GitLab integration and reports the changes since the
+/// previous snapshots.
+async fn snapshot_all(app: &AppHandle) -> Result<(), String> {
+    let integrations = load_integrations(app.clone()).await?;
+    let mut failures = Vec::new();
+    for integration in integrations
+        .iter()
+        .filter(|i| i.integration_type == IntegrationType::GitLab)
+    {
+        match snapshot_and_diff(app, &integration.id).await {
+            Ok(Some(diff)) => report(app, &integration.name, &diff).await,
+            Ok(None) => {}
+            Err(e) => {
+                log::warn!("Failed to snapshot projects of {}: {e}", integration.name);
+                failures.push(integration.name.clone());
+            }
+        }
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("No snapshot for {}", failures.join(", ")))
+    }
+}
+
+/// Takes a snapshot and diffs it against the previous one, if there is one
+/// and anything changed.
+async fn snapshot_and_diff(
+    app: &AppHandle,
+    integration_id: &str,
+) -> Result<Option<ProjectInventoryDiff>, String> {
+    let previous = list_snapshots(app, integration_id)?.pop();
+    let snapshot = take_snapshot(app, integration_id).await?;
+    let Some(previous) = previous else {
+        return Ok(None);
+    };
+    let diff = diff_snapshots(
+        &read_snapshot(app, integration_id, &previous.id)?,
+        &snapshot,
+    );
+    Ok((!diff.changes.is_empty()).then_some(diff))
+}
+
+async fn report(app: &AppHandle, integration_name: &str, diff: &ProjectInventoryDiff) {
+    log::info!(
+        "GitLab projects of {integration_name} changed: {}",
+        diff.summary()
+    );
+    windows::broadcast(app, PROJECT_INVENTORY_CHANGED_EVENT, diff);
+    let title = format!("GitLab projects of {integration_name} changed");
+    if let Err(e) = send_native_notification(app.clone(), title, Some(diff.summary())).await {
+        log::warn!("Failed to notify about project inventory changes: {e}");
+    }
+}
+
+/// Fetches the project inventory of a GitLab integration and records it.
+pub async fn take_snapshot(
+    app: &AppHandle,
+    integration_id: &str,
+) -> Result<ProjectInventorySnapshot, String> {
+    let projects = gitlab_adapter(app, integration_id)
+        .await?
+        .fetch_project_inventory()
+        .await
+        .map_err(|e| format!("Failed to fetch GitLab projects: {}", e))?;
+    record_snapshot(app, integration_id, projects)
+}
+
+/// Records projects fetched elsewhere (e.g., by the initial sync) as the
+/// newest snapshot of an integration.
+pub fn record_snapshot(
+    app: &AppHandle,
+    integration_id: &str,
+    projects: Vec<GitLabInventoryProject>,
+) -> Result<ProjectInventorySnapshot, String> {
+    let now = Utc::now();
+    let snapshot = ProjectInventorySnapshot {
+        id: snapshot_id(now),
+        integration_id: integration_id.to_string(),
+        taken_at: Timestamp::from_datetime(now),
+        projects,
+    };
+    let dir = snapshot_dir(app, integration_id)?;
+    write_snapshot(&dir, &snapshot)?;
+    log::debug!(
+        "Recorded {} GitLab project(s) of {integration_id}",
+        snapshot.projects.len()
+    );
+
+    let ids = snapshot_ids(&dir)?;
+    for id in ids.iter().take(ids.len().saturating_sub(MAX_SNAPSHOTS)) {
+        if let Err(e) = std::fs::remove_file(dir.join(format!("{id}.json"))) {
+            log::warn!("Failed to remove old project inventory snapshot {id}: {e}");
+        }
+    }
+    Ok(snapshot)
+}
+
+/// Lists the snapshots of an integration, oldest first.
+pub fn list_snapshots(
+    app: &AppHandle,
+    integration_id: &str,
+) -> Result<Vec<ProjectInventorySnapshotInfo>, String> {
+    let dir = snapshot_dir(app, integration_id)?;
+    snapshot_ids(&dir)?
+        .iter()
+        .map(|id| Ok(snapshot_info(&read_snapshot_file(&dir, id)?)))
+        .collect()
+}
+
+/// Diffs two snapshots of an integration; `to: None` means the latest.
+pub fn diff(
+    app: &AppHandle,
+    integration_id: &str,
+    from: &str,
+    to: Option<&str>,
+) -> Result<ProjectInventoryDiff, String> {
+    let to = match to {
+        Some(id) => id.to_string(),
+        None => list_snapshots(app, integration_id)?
+            .pop()
+            .map(|s| s.id)
+            .ok_or_else(|| format!("No project inventory recorded for {integration_id}"))?,
+    };
+    let old = read_snapshot(app, integration_id, from)?;
+    let new = read_snapshot(app, integration_id, &to)?;
+    Ok(diff_snapshots(&old, &new))
+}
+
+/// Changes from `old` to `new`, ordered by project ID.
+fn diff_snapshots(
+    old: &ProjectInventorySnapshot,
+    new: &ProjectInventorySnapshot,
+) -> ProjectInventoryDiff {
+    let old_projects: BTreeMap<u32, &GitLabInventoryProject> =
+        old.projects.iter().map(|p| (p.id, p)).collect();
+    let new_projects: BTreeMap<u32, &GitLabInventoryProject> =
+        new.projects.iter().map(|p| (p.id, p)).collect();
+
+    let mut changes = Vec::new();
+    let mut ids: Vec<u32> = old_projects
+        .keys()
+        .chain(new_projects.keys())
+        .copied()
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+    for id in ids {
+        match (old_projects.get(&id), new_projects.get(&id)) {
+            (None, Some(project)) => {
+                changes.push(change(project, ProjectChangeKind::Added, None, None))
+            }
+            (Some(project), None) => {
+                changes.push(change(project, ProjectChangeKind::Removed, None, None))
+            }
+            (Some(before), Some(after)) => changes.extend(project_changes(before, after)),
+            (None, None) => {}
+        }
+    }
+
+    ProjectInventoryDiff {
+        integration_id: new.integration_id.clone(),
+        from: snapshot_info(old),
+        to: snapshot_info(new),
+        changes,
+    }
+}
+
+fn project_changes(
+    before: &GitLabInventoryProject,
+    after: &GitLabInventoryProject,
+) -> Vec<ProjectInventoryChange> {
+    let mut changes = Vec::new();
+    if before.path_with_namespace != after.path_with_namespace {
+        changes.push(change(
+            after,
+            ProjectChangeKind::Renamed,
+            Some(before.path_with_namespace.clone()),
+            Some(after.path_with_namespace.clone()),
+        ));
+    }
+    if before.archived != after.archived {
+        let kind = if after.archived {
+            ProjectChangeKind::Archived
+        } else {
+            ProjectChangeKind::Unarchived
+        };
+        changes.push(change(after, kind, None, None));
+    }
+    if before.default_branch != after.default_branch {
+        changes.push(change(
+            after,
+            ProjectChangeKind::DefaultBranchChanged,
+            before.default_branch.clone(),
+            after.default_branch.clone(),
+        ));
+    }
+    if before.visibility != after.visibility {
+        changes.push(change(
+            after,
+            ProjectChangeKind::VisibilityChanged,
+            Some(before.visibility.clone()),
+            Some(after.visibility.clone()),
+        ));
+    }
+    changes
+}
+
+fn change(
+    project: &GitLabInventoryProject,
+    kind: ProjectChangeKind,
+    from: Option<String>,
+    to: Option<String>,
+) -> ProjectInventoryChange {
+    ProjectInventoryChange {
+        project_id: project.id,
+        path: project.path_with_namespace.clone(),
+        kind,
+        from,
+        to,
+    }
+}
+
+fn snapshot_info(snapshot: &ProjectInventorySnapshot) -> ProjectInventorySnapshotInfo {
+    ProjectInventorySnapshotInfo {
+        id: snapshot.id.clone(),
+        integration_id: snapshot.integration_id.clone(),
+        taken_at: snapshot.taken_at.clone(),
+        project_count: snapshot.projects.len() as u32,
+    }
+}
+
+fn snapshot_id(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%S%3fZ").to_string()
+}
+
+fn snapshot_dir(app: &AppHandle, integration_id: &str) -> Result<PathBuf, String> {
+    if integration_id.is_empty() || integration_id.contains(['/', '\\']) || integration_id == ".." {
+        return Err(format!("Invalid integration ID: {integration_id}"));
+    }
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    Ok(app_data_dir.join("project_inventory").join(integration_id))
+}
+
+fn snapshot_ids(dir: &Path) -> Result<Vec<String>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read project inventory directory: {e}"))?;
+
+    let mut ids: Vec<String> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_suffix(".json").map(str::to_string)
+        })
+        .collect();
+    ids.sort();
+    Ok(ids)
+}
+
+fn read_snapshot(
+    app: &AppHandle,
+    integration_id: &str,
+    id: &str,
+) -> Result<ProjectInventorySnapshot, String> {
+    read_snapshot_file(&snapshot_dir(app, integration_id)?, id)
+}
+
+fn read_snapshot_file(dir: &Path, id: &str) -> Result<ProjectInventorySnapshot, String> {
+    let path = dir.join(format!("{id}.json"));
+    if id.contains(['/', '\\']) || !path.exists() {
+        return Err(format!("Project inventory snapshot not found: {id}"));
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read project inventory snapshot {id}: {e}"))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse project inventory snapshot {id}: {e}"))
+}
+
+fn write_snapshot(dir: &Path, snapshot: &ProjectInventorySnapshot) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create project inventory directory: {e}"))?;
+    let json = serde_json::to_string(snapshot)
+        .map_err(|e| format!("Failed to serialize project inventory snapshot: {e}"))?;
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let path = dir.join(format!("{}.json", snapshot.id));
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json)
+        .map_err(|e| format!("Failed to write project inventory snapshot: {e}"))?;
+    if let Err(rename_err) = std::fs::rename(&temp_path, &path) {
+        if let Err(remove_err) = std::fs::remove_file(&temp_path) {
+            log::warn!("Failed to remove temp file after rename failure: {remove_err}");
+        }
+        return Err(format!(
+            "Failed to finalize project inventory snapshot: {rename_err}"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(id: u32, path: &str) -> GitLabInventoryProject {
+        GitLabInventoryProject {
+            id,
+            name: path.rsplit('/').next().unwrap_or(path).to_string(),
+            path_with_namespace: path.to_string(),
+            default_branch: Some("main".to_string()),
+            visibility: "private".to_string(),
+            archived: false,
+            web_url: format!("https://gitlab.example.com/{path}"),
+        }
+    }
+
+    fn snapshot(id: &str, projects: Vec<GitLabInventoryProject>) -> ProjectInventorySnapshot {
+        ProjectInventorySnapshot {
+            id: id.to_string(),
+            integration_id: "gitlab".to_string(),
+            taken_at: Timestamp::parse("2024-05-01T10:00:00Z").unwrap(),
+            projects,
+        }
+    }
+
+    #[test]
+    fn test_diff_detects_added_removed_and_renamed() {
+        let old = snapshot(
+            "a",
+            vec![project(1, "platform/api"), project(2, "platform/legacy")],
+        );
+        let new = snapshot(
+            "b",
+            vec![
+                project(1, "platform/checkout-api"),
+                project(3, "platform/web"),
+            ],
+        );
+
+        let diff = diff_snapshots(&old, &new);
+
+        assert_eq!(diff.from.project_count, 2);
+        assert_eq!(
+            diff.changes,
+            vec![
+                ProjectInventoryChange {
+                    project_id: 1,
+                    path: "platform/checkout-api".to_string(),
+                    kind: ProjectChangeKind::Renamed,
+                    from: Some("platform/api".to_string()),
+                    to: Some("platform/checkout-api".to_string()),
+                },
+                change(
+                    &project(2, "platform/legacy"),
+                    ProjectChangeKind::Removed,
+                    None,
+                    None
+                ),
+                change(
+                    &project(3, "platform/web"),
+                    ProjectChangeKind::Added,
+                    None,
+                    None
+                ),
+            ]
+        );
+        assert_eq!(diff.summary(), "1 new, 1 removed, 1 renamed");
+    }
+
+    #[test]
+    fn test_diff_detects_archive_branch_and_visibility_changes() {
+        let mut changed = project(1, "platform/api");
+        changed.archived = true;
+        changed.default_branch = Some("trunk".to_string());
+        changed.visibility = "internal".to_string();
+
+        let diff = diff_snapshots(
+            &snapshot("a", vec![project(1, "platform/api")]),
+            &snapshot("b", vec![changed]),
+        );
+
+        let kinds: Vec<ProjectChangeKind> = diff.changes.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ProjectChangeKind::Archived,
+                ProjectChangeKind::DefaultBranchChanged,
+                ProjectChangeKind::VisibilityChanged,
+            ]
+        );
+        assert_eq!(diff.changes[1].to.as_deref(), Some("trunk"));
+    }
+
+    #[test]
+    fn test_diff_of_identical_snapshots_is_empty() {
+        let projects = vec![project(1, "platform/api")];
+        let diff = diff_snapshots(&snapshot("a", projects.clone()), &snapshot("b", projects));
+        assert!(diff.changes.is_empty());
+    }
+}
@@ -0,0 +1,405 @@
+//! Aggregate project refresh.
+//!
+//! Refreshing a project fetches, at once, everything its mappings point at:
+//! the pipelines of mapped GitLab repositories, the builds of mapped Jenkins
+//! jobs and the pods of its namespaces. Mappings don't name an integration,
+//! so each target is fetched from every integration of its type. Results
+//! are streamed as `refresh-result` events as each fetch completes, followed
+//! by one `refresh-completed` event, so one slow integration doesn't hold
+//! back the others. A refresh requested while one of the same project is
+//! running (e.g., from a second window) joins the running one.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+use tauri::AppHandle;
+
+use crate::commands::config::{load_environments, load_integrations, load_mappings};
+use crate::commands::gitlab::gitlab_adapter;
+use crate::commands::jenkins::jenkins_adapter;
+use crate::commands::kubernetes::kubernetes_adapter;
+use crate::integrations::gitlab::GitLabPipeline;
+use crate::integrations::jenkins::JenkinsBuild;
+use crate::integrations::kubernetes::K8sPod;
+use crate::task_pool::InteractiveGuard;
+use crate::types::{Environment, Integration, IntegrationType, Mapping};
+use crate::windows;
+
+/// Event emitted with a `RefreshResult` as each fetch of a refresh completes.
+pub const REFRESH_RESULT_EVENT: &str = "refresh-result";
+
+/// Event emitted with a `RefreshCompleted` once every fetch of a refresh is done.
+pub const REFRESH_COMPLETED_EVENT: &str = "refresh-completed";
+
+static NEXT_REFRESH: AtomicU32 = AtomicU32::new(1);
+
+/// Refreshes running, by project ID.
+static RUNNING: LazyLock<Mutex<HashMap<String, RefreshStarted>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn running() -> MutexGuard<'static, HashMap<String, RefreshStarted>> {
+    RUNNING.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RefreshSourceKind {
+    GitlabPipelines,
+    JenkinsBuilds,
+    K8sPods,
+}
+
+/// One fetch of a refresh.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct RefreshSource {
+    /// Identifier of the fetch within the refresh (e.g., "gitlab_pipelines:gitlab:42")
+    pub id: String,
+    pub kind: RefreshSourceKind,
+    pub integration_id: String,
+    /// GitLab project ID, Jenkins job name or Kubernetes namespace
+    pub target: String,
+}
+
+/// Data fetched from a source.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(tag = "kind", content = "items", rename_all = "snake_case")]
+pub enum RefreshData {
+    GitlabPipelines(Vec<GitLabPipeline>),
+    JenkinsBuilds(Vec<JenkinsBuild>),
+    K8sPods(Vec<K8sPod>),
+}
+
+/// Outcome of one fetch of a refresh.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct RefreshResult {
+    pub refresh_id: String,
+    pub source: RefreshSource,
+    /// Fetched data; None when the fetch failed
+    pub data: Option<RefreshData>,
+    pub error: Option<String>,
+    pub duration_ms: u32,
+}
+
+/// A refresh that has been started.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct RefreshStarted {
+    pub refresh_id: String,
+    pub project_id: String,
+    /// Fetches running; one `refresh-result` event follows for each
+    pub sources: Vec<RefreshSource>,
+}
+
+/// Summary of a finished refresh.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct RefreshCompleted {
+    pub refresh_id: String,
+    pub project_id: String,
+    pub succeeded: u32,
+    pub failed: u32,
+    pub duration_ms: u32,
+}
+
+/// Starts fetching everything mapped to a project in the background and
+/// returns the fetches that were started, or those of the project's running
+/// refresh.
+pub async fn refresh_all(app: &AppHandle, project_id: &str) -> Result<RefreshStarted, String> {
+    let environments = load_environments(app.clone()).await?;
+    let mappings = load_mappings(app.clone()).await?;
+    let integrations = load_integrations(app.clone()).await?;
+
+    let sources = plan_sources(project_id, &environments, &mappings, &integrations);
+    let refresh_id = format!(
+        "refresh-{}-{}",
+        Utc::now().timestamp_millis(),
+        NEXT_REFRESH.fetch_add(1, Ordering::Relaxed)
+    );
+    let started = RefreshStarted {
+        refresh_id: refresh_id.clone(),
+        project_id: project_id.to_string(),
+        sources: sources.clone(),
+    };
+
+    match running().entry(project_id.to_string()) {
+        Entry::Occupied(entry) => {
+            log::debug!(
+                "Project {project_id} is already refreshing ({})",
+                entry.get().refresh_id
+            );
+            return Ok(entry.get().clone());
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(started.clone());
+        }
+    }
+
+    log::info!(
+        "Refreshing project {project_id} from {} source(s) ({refresh_id})",
+        sources.len()
+    );
+
+    let app = app.clone();
+    let project_id = project_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        // Background polling makes way until the refresh is done
+        let _interactive = InteractiveGuard::start();
+        let began = Instant::now();
+
+        let fetches: Vec<_> = sources
+            .into_iter()
+            .map(|source| {
+                let app = app.clone();
+                let refresh_id = refresh_id.clone();
+                tauri::async_runtime::spawn(async move {
+                    let result = fetch_source(&app, refresh_id, source).await;
+                    windows::broadcast(&app, REFRESH_RESULT_EVENT, &result);
+                    result.error.is_none()
+                })
+            })
+            .collect();
+
+        let (mut succeeded, mut failed) = (0, 0);
+        for fetch in fetches {
+            match fetch.await {
+                Ok(true) => succeeded += 1,
+                _ => failed += 1,
+            }
+        }
+
+        let completed = RefreshCompleted {
+            refresh_id,
+            project_id,
+            succeeded,
+            failed,
+            duration_ms: elapsed_ms(began),
+        };
+        log::debug!(
+            "Refresh {} finished: {succeeded} succeeded, {failed} failed",
+            completed.refresh_id
+        );
+        running().remove(&completed.project_id);
+        windows::broadcast(&app, REFRESH_COMPLETED_EVENT, &completed);
+    });
+
+    Ok(started)
+}
+
+async fn fetch_source(app: &AppHandle, refresh_id: String, source: RefreshSource) -> RefreshResult {
+    let began = Instant::now();
+    let outcome = fetch_data(app, &source).await;
+    if let Err(e) = &outcome {
+        log::warn!("Refresh of {} failed: {e}", source.id);
+    }
+    let (data, error) = match outcome {
+        Ok(data) => (Some(data), None),
+        Err(e) => (None, Some(e)),
+    };
+    RefreshResult {
+        refresh_id,
+        source,
+        data,
+        error,
+        duration_ms: elapsed_ms(began),
+    }
+}
+
+async fn fetch_data(app: &AppHandle, source: &RefreshSource) -> Result<RefreshData, String> {
+    match source.kind {
+        RefreshSourceKind::GitlabPipelines => {
+            let project_id: u32 = source
+                .target
+                .parse()
+                .map_err(|_| format!("Invalid GitLab project ID: {}", source.target))?;
+            gitlab_adapter(app, &source.integration_id)
+                .await?
+                .fetch_pipelines(project_id)
+                .await
+                .map(RefreshData::GitlabPipelines)
+                .map_err(|e| format!("Failed to fetch pipelines: {}", e))
+        }
+        RefreshSourceKind::JenkinsBuilds => jenkins_adapter(app, &source.integration_id)
+            .await?
+            .fetch_builds(&source.target)
+            .await
+            .map(RefreshData::JenkinsBuilds)
+            .map_err(|e| format!("Failed to fetch builds: {}", e)),
+        RefreshSourceKind::K8sPods => kubernetes_adapter(app, &source.integration_id)
+            .await?
+            .fetch_pods(&source.target)
+            .await
+            .map(RefreshData::K8sPods)
+            .map_err(|e| format!("Failed to fetch pods: {}", e)),
+    }
+}
+
+/// Lists the fetches refreshing a project: each GitLab repository, Jenkins
+/// job and namespace mapped to the project or its environments, from every
+/// integration of the matching type.
+fn plan_sources(
+    project_id: &str,
+    environments: &[Environment],
+    mappings: &[Mapping],
+    integrations: &[Integration],
+) -> Vec<RefreshSource> {
+    let project_environments: Vec<&Environment> = environments
+        .iter()
+        .filter(|e| e.project_id == project_id)
+        .collect();
+    let project_mappings: Vec<&Mapping> = mappings
+        .iter()
+        .filter(|m| {
+            m.project_id.as_deref() == Some(project_id)
+                || m.environment_id
+                    .as_deref()
+                    .is_some_and(|id| project_environments.iter().any(|e| e.id == id))
+        })
+        .collect();
+
+    let mut targets: Vec<(RefreshSourceKind, String)> = Vec::new();
+    for mapping in &project_mappings {
+        if let Some(repo_id) = mapping.repo_id.as_deref() {
+            targets.push((
+                RefreshSourceKind::GitlabPipelines,
+                repo_id.trim().to_string(),
+            ));
+        }
+        if let Some(job_id) = mapping.job_id.as_deref() {
+            targets.push((RefreshSourceKind::JenkinsBuilds, job_id.trim().to_string()));
+        }
+        if let Some(namespace) = mapping.namespace.as_deref() {
+            targets.push((RefreshSourceKind::K8sPods, namespace.trim().to_string()));
+        }
+    }
+    for environment in &project_environments {
+        if let Some(namespace) = environment.namespace.as_deref() {
+            targets.push((RefreshSourceKind::K8sPods, namespace.trim().to_string()));
+        }
+    }
+    targets.retain(|(_, target)| !target.is_empty());
+
+    let mut sources = Vec::new();
+    for (kind, target) in targets {
+        let integration_type = match kind {
+            RefreshSourceKind::GitlabPipelines => IntegrationType::GitLab,
+            RefreshSourceKind::JenkinsBuilds => IntegrationType::Jenkins,
+            RefreshSourceKind::K8sPods => IntegrationType::Kubernetes,
+        };
+        for integration in integrations
+            .iter()
+            .filter(|i| i.integration_type == integration_type)
+        {
+            let id = format!("{}:{}:{target}", kind_name(kind), integration.id);
+            if sources.iter().any(|s: &RefreshSource| s.id == id) {
+                continue;
+            }
+            sources.push(RefreshSource {
+                id,
+                kind,
+                integration_id: integration.id.clone(),
+                target: target.clone(),
+            });
+        }
+    }
+    sources
+}
+
+fn kind_name(kind: RefreshSourceKind) -> &'static str {
+    match kind {
+        RefreshSourceKind::GitlabPipelines => "gitlab_pipelines",
+        RefreshSourceKind::JenkinsBuilds => "jenkins_builds",
+        RefreshSourceKind::K8sPods => "k8s_pods",
+    }
+}
+
+fn elapsed_ms(since: Instant) -> u32 {
+    u32::try_from(since.elapsed().as_millis()).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn integration(id: &str, integration_type: IntegrationType) -> Integration {
+        Integration {
+            id: id.to_string(),
+            integration_type,
+            name: id.to_string(),
+            base_url: "https://example.com".to_string(),
+            credentials_ref: None,
+            api_version: None,
+        }
+    }
+
+    fn environment(id: &str, project_id: &str, namespace: Option<&str>) -> Environment {
+        Environment {
+            id: id.to_string(),
+            name: id.to_string(),
+            namespace: namespace.map(str::to_string),
+            project_id: project_id.to_string(),
+            variables: BTreeMap::new(),
+        }
+    }
+
+    fn mapping(
+        project_id: Option<&str>,
+        environment_id: Option<&str>,
+        repo_id: Option<&str>,
+        job_id: Option<&str>,
+    ) -> Mapping {
+        Mapping {
+            id: "m".to_string(),
+            repo_id: repo_id.map(str::to_string),
+            job_id: job_id.map(str::to_string),
+            namespace: None,
+            service_name: None,
+            project_id: project_id.map(str::to_string),
+            environment_id: environment_id.map(str::to_string),
+            ownership: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_plan_sources_covers_mappings_and_namespaces() {
+        let environments = vec![
+            environment("shop-prod", "shop", Some("shop-prod")),
+            environment("blog-prod", "blog", Some("blog-prod")),
+        ];
+        let mappings = vec![
+            mapping(Some("shop"), None, Some("42"), None),
+            mapping(None, Some("shop-prod"), Some("42"), Some("shop-deploy")),
+            mapping(Some("blog"), None, Some("7"), None),
+        ];
+        let integrations = vec![
+            integration("gitlab", IntegrationType::GitLab),
+            integration("jenkins", IntegrationType::Jenkins),
+            integration("k8s-a", IntegrationType::Kubernetes),
+            integration("k8s-b", IntegrationType::Kubernetes),
+        ];
+
+        let ids: Vec<String> = plan_sources("shop", &environments, &mappings, &integrations)
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+
+        assert_eq!(
+            ids,
+            vec![
+                "gitlab_pipelines:gitlab:42",
+                "jenkins_builds:jenkins:shop-deploy",
+                "k8s_pods:k8s-a:shop-prod",
+                "k8s_pods:k8s-b:shop-prod",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_sources_of_unknown_project_is_empty() {
+        let integrations = vec![integration("gitlab", IntegrationType::GitLab)];
+        assert!(plan_sources("missing", &[], &[], &integrations).is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+//! Compliance report.
+//!
+//! Summarizes the results of the last certificate check and how the uptime
+//! targets did against the uptime objective. Nothing is checked while
+//! rendering; the report shows what the monitors last recorded.
+
+use tauri::AppHandle;
+
+use super::html::{Cell, Document, Tone};
+use crate::checks::certificates::{self, CertificateInfo, ALERT_THRESHOLDS_DAYS};
+use crate::checks::uptime;
+
+/// Uptime share a target must reach to be compliant.
+const UPTIME_OBJECTIVE_PERCENT: f64 = 99.0;
+
+pub async fn render(app: &AppHandle, doc: &mut Document) -> Result<(), String> {
+    let certificates = certificates::list(app)?;
+    let targets = uptime::status(app).await?.targets;
+
+    let states: Vec<Cell> = certificates.iter().map(certificate_state).collect();
+    let count = |tone: Tone| states.iter().filter(|s| s.tone == tone).count();
+    let measured: Vec<f64> = targets.iter().filter_map(|t| t.uptime_percent).collect();
+    let meeting = measured
+        .iter()
+        .filter(|p| **p >= UPTIME_OBJECTIVE_PERCENT)
+        .count();
+
+    doc.metrics(&[
+        ("Certificates OK", count(Tone::Ok).to_string()),
+        ("Expiring soon", count(Tone::Warn).to_string()),
+        ("Expired or unreachable", count(Tone::Bad).to_string()),
+        (
+            "Targets meeting uptime objective",
+            format!("{meeting}/{}", measured.len()),
+        ),
+    ]);
+
+    doc.heading("TLS Certificates");
+    if certificates.is_empty() {
+        doc.note("No certificate check results yet.");
+    } else {
+        let rows = certificates
+            .iter()
+            .zip(states)
+            .map(|(cert, state)| {
+                vec![
+                    cert.host.as_str().into(),
+                    cert.sources.join(", ").into(),
+                    cert.issuer.as_deref().unwrap_or("-").into(),
+                    cert.expires_at.as_deref().unwrap_or("-").into(),
+                    state,
+                    cert.checked_at.as_str().into(),
+                ]
+            })
+            .collect();
+        doc.table(
+            &["Host", "Used by", "Issuer", "Expires", "Status", "Checked"],
+            rows,
+        );
+    }
+
+    doc.heading("Uptime");
+    doc.paragraph(&format!(
+        "Objective: {UPTIME_OBJECTIVE_PERCENT}% of checks successful, over the last day of checks."
+    ));
+    if targets.is_empty() {
+        doc.note("No uptime targets configured.");
+    } else {
+        let rows = targets
+            .iter()
+            .map(|target| {
+                let uptime = match target.uptime_percent {
+                    Some(percent) if percent >= UPTIME_OBJECTIVE_PERCENT => {
+                        Cell::new(format!("{percent:.2}%"), Tone::Ok)
+                    }
+                    Some(percent) => Cell::new(format!("{percent:.2}%"), Tone::Bad),
+                    None => "Not checked".into(),
+                };
+                vec![
+                    target.name.as_str().into(),
+                    target.url.as_str().into(),
+                    uptime,
+                    target.history.len().to_string().into(),
+                ]
+            })
+            .collect();
+        doc.table(&["Target", "URL", "Uptime", "Checks"], rows);
+    }
+    Ok(())
+}
+
+/// Status cell of a certificate: expired or unreadable certificates are bad,
+/// ones within the first alert threshold a warning.
+fn certificate_state(cert: &CertificateInfo) -> Cell {
+    if let Some(error) = &cert.error {
+        return Cell::new(format!("Unreachable: {error}"), Tone::Bad);
+    }
+    match cert.days_remaining {
+        Some(days) if days < 0 => Cell::new("Expired", Tone::Bad),
+        Some(days) if i64::from(days) <= ALERT_THRESHOLDS_DAYS[0] => {
+            Cell::new(format!("Expires in {days} days"), Tone::Warn)
+        }
+        Some(days) => Cell::new(format!("{days} days left"), Tone::Ok),
+        None => Cell::new("Unknown", Tone::Warn),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cert(days_remaining: Option<i32>, error: Option<&str>) -> CertificateInfo {
+        CertificateInfo {
+            host: "api.example.com".to_string(),
+            sources: vec!["integration: GitLab".to_string()],
+            subject: None,
+            issuer: None,
+            expires_at: None,
+            days_remaining,
+            checked_at: "2024-05-01T10:00:00Z".to_string(),
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_certificate_state() {
+        assert_eq!(certificate_state(&cert(Some(90), None)).tone, Tone::Ok);
+        assert_eq!(
+            certificate_state(&cert(Some(12), None)),
+            Cell::new("Expires in 12 days", Tone::Warn)
+        );
+        assert_eq!(certificate_state(&cert(Some(-1), None)).tone, Tone::Bad);
+        assert_eq!(
+            certificate_state(&cert(None, Some("Connection failed"))).text,
+            "Unreachable: Connection failed"
+        );
+    }
+}
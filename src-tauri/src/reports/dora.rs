@@ -0,0 +1,269 @@
+//! DORA metrics report.
+//!
+//! Computed from GitLab deployments of every mapping that has a repository
+//! and an environment:
+//! - deployment frequency: successful deployments per week
+//! - lead time for changes: commit time to deployment finish (median)
+//! - change failure rate: failed deployments / finished deployments
+//! - time to restore: failed deployment to the next successful one (median)
+
+use chrono::{Duration, Utc};
+use tauri::AppHandle;
+
+use super::html::{Cell, Document, Tone};
+use crate::commands::config::{load_environments, load_mappings};
+use crate::commands::gitlab::gitlab_adapter;
+use crate::integrations::gitlab::GitLabDeployment;
+use crate::types::{ReportDefinition, Timestamp};
+
+/// Deployment outcomes of one or more environments.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct DoraSample {
+    successes: u32,
+    failures: u32,
+    lead_times_hours: Vec<f64>,
+    restore_times_hours: Vec<f64>,
+}
+
+impl DoraSample {
+    fn from_deployments(deployments: &[GitLabDeployment]) -> Self {
+        let mut deployments: Vec<&GitLabDeployment> = deployments.iter().collect();
+        deployments.sort_by_key(|d| d.created_at.datetime());
+
+        let hours = |from: &Timestamp, to: &Timestamp| {
+            (to.datetime() - from.datetime()).num_seconds() as f64 / 3600.0
+        };
+        let mut sample = Self::default();
+        let mut failed_since: Option<&Timestamp> = None;
+        for deployment in deployments {
+            match deployment.status.as_str() {
+                "success" => {
+                    sample.successes += 1;
+                    let finished = deployment.finished_at.as_ref();
+                    if let (Some(commit), Some(finished)) =
+                        (&deployment.commit_created_at, finished)
+                    {
+                        sample.lead_times_hours.push(hours(commit, finished));
+                    }
+                    if let (Some(failed), Some(finished)) = (failed_since.take(), finished) {
+                        sample.restore_times_hours.push(hours(failed, finished));
+                    }
+                }
+                "failed" => {
+                    sample.failures += 1;
+                    failed_since.get_or_insert(&deployment.created_at);
+                }
+                _ => {}
+            }
+        }
+        sample
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.successes += other.successes;
+        self.failures += other.failures;
+        self.lead_times_hours.extend(other.lead_times_hours);
+        self.restore_times_hours.extend(other.restore_times_hours);
+    }
+
+    fn per_week(&self, period_days: u32) -> f64 {
+        f64::from(self.successes) * 7.0 / f64::from(period_days.max(1))
+    }
+
+    fn failure_rate_percent(&self) -> Option<f64> {
+        let finished = self.successes + self.failures;
+        (finished > 0).then(|| f64::from(self.failures) * 100.0 / f64::from(finished))
+    }
+}
+
+fn median(values: &[f64]) -> Option<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        n if n % 2 == 0 => Some((sorted[mid - 1] + sorted[mid]) / 2.0),
+        _ => Some(sorted[mid]),
+    }
+}
+
+fn format_hours(hours: Option<f64>) -> String {
+    match hours {
+        None => "-".to_string(),
+        Some(h) if h < 48.0 => format!("{h:.1} h"),
+        Some(h) => format!("{:.1} d", h / 24.0),
+    }
+}
+
+pub async fn render(
+    app: &AppHandle,
+    doc: &mut Document,
+    definition: &ReportDefinition,
+) -> Result<(), String> {
+    let Some(integration_id) = &definition.integration_id else {
+        doc.note("Select a GitLab integration in the report settings to compute DORA metrics.");
+        return Ok(());
+    };
+    let adapter = gitlab_adapter(app, integration_id).await?;
+    let environments = load_environments(app.clone()).await?;
+    let mappings = load_mappings(app.clone()).await?;
+
+    // (project ID, environment name) of every mapping, once
+    let mut targets: Vec<(u32, String)> = Vec::new();
+    for mapping in &mappings {
+        let project_id = mapping
+            .repo_id
+            .as_deref()
+            .and_then(|r| r.trim().parse().ok());
+        let environment = mapping
+            .environment_id
+            .as_deref()
+            .and_then(|id| environments.iter().find(|e| e.id == id));
+        if let (Some(project_id), Some(environment)) = (project_id, environment) {
+            if !targets.contains(&(project_id, environment.name.clone())) {
+                targets.push((project_id, environment.name.clone()));
+            }
+        }
+    }
+
+    let since =
+        Timestamp::from_datetime(Utc::now() - Duration::days(definition.period_days.into()));
+    doc.paragraph(&format!(
+        "GitLab deployments since {} ({} days).",
+        since, definition.period_days
+    ));
+    if targets.is_empty() {
+        doc.note("No mappings with a GitLab project ID and an environment.");
+        return Ok(());
+    }
+
+    let mut total = DoraSample::default();
+    let mut rows = Vec::new();
+    for (project_id, environment) in targets {
+        let deployments = adapter
+            .fetch_deployments(project_id, &environment, &since)
+            .await
+            .map_err(|e| format!("Failed to fetch deployments of project {project_id}: {e}"))?;
+        let sample = DoraSample::from_deployments(&deployments);
+        rows.push(sample_row(
+            &format!("{project_id} / {environment}"),
+            &sample,
+            definition.period_days,
+        ));
+        total.merge(sample);
+    }
+
+    doc.metrics(&[
+        (
+            "Deployments per week",
+            format!("{:.1}", total.per_week(definition.period_days)),
+        ),
+        (
+            "Lead time for changes",
+            format_hours(median(&total.lead_times_hours)),
+        ),
+        (
+            "Change failure rate",
+            total
+                .failure_rate_percent()
+                .map_or("-".to_string(), |p| format!("{p:.0}%")),
+        ),
+        (
+            "Time to restore",
+            format_hours(median(&total.restore_times_hours)),
+        ),
+    ]);
+    doc.heading("By project and environment");
+    doc.table(
+        &[
+            "Project / environment",
+            "Deployments",
+            "Per week",
+            "Lead time",
+            "Failure rate",
+            "Time to restore",
+        ],
+        rows,
+    );
+    Ok(())
+}
+
+fn sample_row(label: &str, sample: &DoraSample, period_days: u32) -> Vec<Cell> {
+    // DORA's high performers stay at or below 15%
+    let failure_rate = match sample.failure_rate_percent() {
+        None => "-".into(),
+        Some(p) if p > 15.0 => Cell::new(format!("{p:.0}%"), Tone::Bad),
+        Some(p) => Cell::new(format!("{p:.0}%"), Tone::Ok),
+    };
+    vec![
+        label.into(),
+        sample.successes.to_string().into(),
+        format!("{:.1}", sample.per_week(period_days)).into(),
+        format_hours(median(&sample.lead_times_hours)).into(),
+        failure_rate,
+        format_hours(median(&sample.restore_times_hours)).into(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment(
+        status: &str,
+        created: &str,
+        finished: Option<&str>,
+        commit: Option<&str>,
+    ) -> GitLabDeployment {
+        GitLabDeployment {
+            id: 1,
+            status: status.to_string(),
+            environment: "production".to_string(),
+            sha: "a1b2c3".to_string(),
+            commit_created_at: commit.and_then(Timestamp::parse),
+            created_at: Timestamp::parse(created).unwrap(),
+            finished_at: finished.and_then(Timestamp::parse),
+        }
+    }
+
+    #[test]
+    fn test_sample_from_deployments() {
+        let deployments = vec![
+            deployment(
+                "success",
+                "2024-05-01T10:00:00Z",
+                Some("2024-05-01T10:10:00Z"),
+                Some("2024-05-01T08:10:00Z"),
+            ),
+            deployment("failed", "2024-05-02T10:00:00Z", None, None),
+            deployment("failed", "2024-05-02T11:00:00Z", None, None),
+            deployment(
+                "success",
+                "2024-05-02T13:30:00Z",
+                Some("2024-05-02T14:00:00Z"),
+                Some("2024-05-02T10:00:00Z"),
+            ),
+            deployment("running", "2024-05-03T10:00:00Z", None, None),
+        ];
+
+        let sample = DoraSample::from_deployments(&deployments);
+
+        assert_eq!(sample.successes, 2);
+        assert_eq!(sample.failures, 2);
+        assert_eq!(sample.lead_times_hours, vec![2.0, 4.0]);
+        // Restored 4h after the first of the two failures
+        assert_eq!(sample.restore_times_hours, vec![4.0]);
+        assert_eq!(sample.failure_rate_percent(), Some(50.0));
+        assert_eq!(sample.per_week(14), 1.0);
+    }
+
+    #[test]
+    fn test_median_and_format() {
+        assert_eq!(median(&[]), None);
+        assert_eq!(median(&[3.0, 1.0, 2.0]), Some(2.0));
+        assert_eq!(median(&[4.0, 1.0, 2.0, 3.0]), Some(2.5));
+        assert_eq!(format_hours(Some(5.0)), "5.0 h");
+        assert_eq!(format_hours(Some(72.0)), "3.0 d");
+        assert_eq!(format_hours(None), "-");
+    }
+}
@@ -0,0 +1,182 @@
+//! Standalone HTML documents.
+//!
+//! Reports are single files with inline styles so they can be mailed or
+//! attached to a ticket and still render without the app.
+
+use std::fmt::Write;
+
+const STYLE: &str = "\
+body { font-family: -apple-system, 'Segoe UI', Roboto, sans-serif; color: #1f2328; margin: 2rem auto; max-width: 1100px; padding: 0 1rem; }
+h1 { margin-bottom: 0.25rem; }
+h2 { margin-top: 2rem; border-bottom: 1px solid #d0d7de; padding-bottom: 0.25rem; }
+.meta, .empty { color: #656d76; }
+table { border-collapse: collapse; width: 100%; margin: 0.75rem 0; font-size: 0.9rem; }
+th, td { border: 1px solid #d0d7de; padding: 0.35rem 0.6rem; text-align: left; vertical-align: top; }
+th { background: #f6f8fa; }
+.metrics { display: flex; flex-wrap: wrap; gap: 0.75rem; margin: 0.75rem 0; }
+.metric { border: 1px solid #d0d7de; border-radius: 6px; padding: 0.5rem 0.9rem; min-width: 9rem; }
+.metric .value { font-size: 1.4rem; font-weight: 600; }
+.metric .label { color: #656d76; font-size: 0.8rem; }
+.ok { color: #1a7f37; } .warn { color: #9a6700; } .bad { color: #cf222e; }
+@media print { body { margin: 0; } h2 { break-after: avoid; } tr { break-inside: avoid; } }
+";
+
+/// Escapes text for use in HTML content and attribute values.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Severity of a table cell, rendered as a color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tone {
+    Plain,
+    Ok,
+    Warn,
+    Bad,
+}
+
+/// Table cell text with a tone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub text: String,
+    pub tone: Tone,
+}
+
+impl Cell {
+    pub fn new(text: impl Into<String>, tone: Tone) -> Self {
+        Self {
+            text: text.into(),
+            tone,
+        }
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        Self::new(text, Tone::Plain)
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Self::new(text, Tone::Plain)
+    }
+}
+
+/// Report document under construction.
+pub struct Document {
+    title: String,
+    body: String,
+}
+
+impl Document {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            body: String::new(),
+        }
+    }
+
+    pub fn heading(&mut self, text: &str) {
+        let _ = writeln!(self.body, "<h2>{}</h2>", escape(text));
+    }
+
+    pub fn paragraph(&mut self, text: &str) {
+        let _ = writeln!(self.body, "<p>{}</p>", escape(text));
+    }
+
+    /// Greyed-out note, e.g. for sections without data.
+    pub fn note(&mut self, text: &str) {
+        let _ = writeln!(self.body, "<p class=\"empty\">{}</p>", escape(text));
+    }
+
+    /// Row of headline numbers as (label, value) pairs.
+    pub fn metrics(&mut self, metrics: &[(&str, String)]) {
+        self.body.push_str("<div class=\"metrics\">\n");
+        for (label, value) in metrics {
+            let _ = writeln!(
+                self.body,
+                "<div class=\"metric\"><div class=\"value\">{}</div><div class=\"label\">{}</div></div>",
+                escape(value),
+                escape(label)
+            );
+        }
+        self.body.push_str("</div>\n");
+    }
+
+    pub fn table(&mut self, headers: &[&str], rows: Vec<Vec<Cell>>) {
+        self.body.push_str("<table>\n<tr>");
+        for header in headers {
+            let _ = write!(self.body, "<th>{}</th>", escape(header));
+        }
+        self.body.push_str("</tr>\n");
+        for row in rows {
+            self.body.push_str("<tr>");
+            for cell in row {
+                let class = match cell.tone {
+                    Tone::Plain => "",
+                    Tone::Ok => " class=\"ok\"",
+                    Tone::Warn => " class=\"warn\"",
+                    Tone::Bad => " class=\"bad\"",
+                };
+                let _ = write!(self.body, "<td{class}>{}</td>", escape(&cell.text));
+            }
+            self.body.push_str("</tr>\n");
+        }
+        self.body.push_str("</table>\n");
+    }
+
+    /// Renders the complete page; `generated_at` is shown under the title.
+    pub fn finish(self, generated_at: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n<p class=\"meta\">Generated {generated_at} by Ops Flow</p>\n{body}</body>\n</html>\n",
+            title = escape(&self.title),
+            generated_at = escape(generated_at),
+            body = self.body
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_document_renders_escaped_table() {
+        let mut doc = Document::new("Prod <weekly>");
+        doc.heading("Certificates");
+        doc.table(
+            &["Host", "Status"],
+            vec![vec![
+                "api.example.com".into(),
+                Cell::new("Expired", Tone::Bad),
+            ]],
+        );
+
+        let html = doc.finish("2024-05-01T10:00:00.000Z");
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Prod &lt;weekly&gt;</title>"));
+        assert!(html.contains("<tr><td>api.example.com</td><td class=\"bad\">Expired</td></tr>"));
+        assert!(html.contains("Generated 2024-05-01T10:00:00.000Z"));
+    }
+}
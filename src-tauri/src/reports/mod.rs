@@ -0,0 +1,257 @@
+//! Reports.
+//!
+//! Renders environment overviews, compliance results and DORA metrics as
+//! standalone HTML pages (optionally printed to PDF) for sharing outside the
+//! app. Definitions live in `report_definitions.yaml`; generated files are
+//! written to `<app data>/reports/<report id>-<time>.html|pdf`. Reports with
+//! an `interval_hours` are also generated by a background task.
+
+mod compliance;
+mod dora;
+mod html;
+mod overview;
+mod pdf;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::config::load_report_definitions;
+use crate::metrics;
+use crate::task_pool::{self, Priority};
+use crate::types::{ReportDefinition, ReportFormat, ReportKind, Timestamp};
+
+/// Generations kept per report; older files are removed.
+const MAX_GENERATIONS: usize = 20;
+
+/// Delay before the first scheduled check after startup.
+const STARTUP_DELAY: Duration = Duration::from_secs(120);
+
+/// How often scheduled reports are checked for being due.
+const SCHEDULE_TICK: Duration = Duration::from_secs(5 * 60);
+
+const FILE_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// A generated report file.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GeneratedReport {
+    pub report_id: String,
+    /// File name within the reports directory
+    pub file_name: String,
+    pub format: ReportFormat,
+    pub generated_at: Timestamp,
+}
+
+fn reports_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    let dir = app_data_dir.join("reports");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create reports directory: {e}"))?;
+    Ok(dir)
+}
+
+/// Renders a report and writes it to the reports directory.
+pub async fn generate(
+    app: &AppHandle,
+    definition: &ReportDefinition,
+) -> Result<GeneratedReport, String> {
+    log::info!(
+        "Generating report {} ({:?})",
+        definition.id,
+        definition.kind
+    );
+    let now = Utc::now();
+
+    let mut doc = html::Document::new(&definition.name);
+    match definition.kind {
+        ReportKind::EnvironmentOverview => overview::render(app, &mut doc, definition).await?,
+        ReportKind::Compliance => compliance::render(app, &mut doc).await?,
+        ReportKind::Dora => dora::render(app, &mut doc, definition).await?,
+    }
+    let generated_at = Timestamp::from_datetime(now);
+    let page = doc.finish(&generated_at.rfc3339);
+
+    let dir = reports_dir(app)?;
+    let stem = format!("{}-{}", definition.id, now.format(FILE_TIME_FORMAT));
+    let html_path = dir.join(format!("{stem}.html"));
+    std::fs::write(&html_path, page).map_err(|e| format!("Failed to write report: {e}"))?;
+
+    let file_name = match definition.format {
+        ReportFormat::Html => format!("{stem}.html"),
+        ReportFormat::Pdf => {
+            let pdf_path = dir.join(format!("{stem}.pdf"));
+            tokio::task::spawn_blocking(move || pdf::print_to_pdf(&html_path, &pdf_path))
+                .await
+                .map_err(|e| format!("PDF rendering task failed: {e}"))?
+                .map_err(|e| format!("{e} (the HTML report was saved)"))?;
+            format!("{stem}.pdf")
+        }
+    };
+
+    prune(app, &definition.id);
+    Ok(GeneratedReport {
+        report_id: definition.id.clone(),
+        file_name,
+        format: definition.format,
+        generated_at,
+    })
+}
+
+/// Lists generated report files, newest first.
+pub fn list(app: &AppHandle) -> Result<Vec<GeneratedReport>, String> {
+    let entries = std::fs::read_dir(reports_dir(app)?)
+        .map_err(|e| format!("Failed to read reports directory: {e}"))?;
+
+    let mut reports: Vec<GeneratedReport> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|name| parse_file_name(&name))
+        .collect();
+    reports.sort_by(|a, b| {
+        b.generated_at
+            .rfc3339
+            .cmp(&a.generated_at.rfc3339)
+            .then_with(|| a.file_name.cmp(&b.file_name))
+    });
+    Ok(reports)
+}
+
+/// Returns the path of a generated report file.
+pub fn path_of(app: &AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    // Only names produced by `generate`, so nothing outside the directory
+    if parse_file_name(file_name).is_none() || file_name.contains(['/', '\\']) {
+        return Err(format!("Invalid report file name: {file_name}"));
+    }
+    let path = reports_dir(app)?.join(file_name);
+    if !path.exists() {
+        return Err(format!("Report not found: {file_name}"));
+    }
+    Ok(path)
+}
+
+/// Starts a background task generating reports whose interval has elapsed.
+pub fn start_scheduled_reports(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        loop {
+            if let Err(e) = generate_due_reports(&app).await {
+                log::warn!("Scheduled reports failed: {e}");
+            }
+            tokio::time::sleep(SCHEDULE_TICK).await;
+        }
+    });
+}
+
+async fn generate_due_reports(app: &AppHandle) -> Result<(), String> {
+    let definitions = load_report_definitions(app.clone()).await?;
+    let generated = list(app)?;
+    let now = Utc::now();
+
+    for definition in definitions {
+        let Some(interval_hours) = definition.interval_hours else {
+            continue;
+        };
+        let last = generated
+            .iter()
+            .find(|r| r.report_id == definition.id)
+            .map(|r| r.generated_at.datetime());
+        if is_due(last, interval_hours, now) {
+            let key = definition.integration_id.as_deref().unwrap_or("reports");
+            let result = task_pool::run(Priority::Analytics, key, generate(app, &definition)).await;
+            metrics::record_poll("scheduled_reports", result.is_ok());
+            if let Err(e) = result {
+                log::warn!("Failed to generate scheduled report {}: {e}", definition.id);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_due(last: Option<DateTime<Utc>>, interval_hours: u32, now: DateTime<Utc>) -> bool {
+    last.is_none_or(|last| now - last >= chrono::Duration::hours(interval_hours.max(1).into()))
+}
+
+/// Reads `<report id>-<time>.<html|pdf>`.
+fn parse_file_name(name: &str) -> Option<GeneratedReport> {
+    let (stem, format) = if let Some(stem) = name.strip_suffix(".html") {
+        (stem, ReportFormat::Html)
+    } else {
+        (name.strip_suffix(".pdf")?, ReportFormat::Pdf)
+    };
+    let (report_id, time) = stem.rsplit_once('-')?;
+    let time = NaiveDateTime::parse_from_str(time, FILE_TIME_FORMAT).ok()?;
+
+    Some(GeneratedReport {
+        report_id: report_id.to_string(),
+        file_name: name.to_string(),
+        format,
+        generated_at: Timestamp::from_datetime(time.and_utc()),
+    })
+}
+
+/// Removes all but the newest `MAX_GENERATIONS` generations of a report.
+fn prune(app: &AppHandle, report_id: &str) {
+    let reports = match list(app) {
+        Ok(reports) => reports,
+        Err(e) => {
+            log::warn!("Failed to list reports for cleanup: {e}");
+            return;
+        }
+    };
+    // Newest first, so everything older than the last kept generation goes
+    let mine: Vec<&GeneratedReport> = reports
+        .iter()
+        .filter(|r| r.report_id == report_id)
+        .collect();
+    let mut generations: Vec<&str> = mine
+        .iter()
+        .map(|r| r.generated_at.rfc3339.as_str())
+        .collect();
+    generations.dedup();
+    let Some(oldest_kept) = generations.get(MAX_GENERATIONS - 1).copied() else {
+        return;
+    };
+
+    for report in mine {
+        if report.generated_at.rfc3339.as_str() >= oldest_kept {
+            continue;
+        }
+        if let Ok(path) = path_of(app, &report.file_name) {
+            if let Err(e) = std::fs::remove_file(&path) {
+                log::warn!("Failed to remove old report {path:?}: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_name() {
+        let report = parse_file_name("weekly-dora-20240501T100000Z.pdf").unwrap();
+
+        assert_eq!(report.report_id, "weekly-dora");
+        assert_eq!(report.format, ReportFormat::Pdf);
+        assert_eq!(report.generated_at.rfc3339, "2024-05-01T10:00:00.000Z");
+        assert!(parse_file_name("weekly-dora-20240501T100000Z.html.tmp").is_none());
+        assert!(parse_file_name("notes.html").is_none());
+    }
+
+    #[test]
+    fn test_is_due() {
+        let now = Timestamp::parse("2024-05-02T10:00:00Z").unwrap().datetime();
+        let hours_ago = |h: i64| Some(now - chrono::Duration::hours(h));
+
+        assert!(is_due(None, 24, now));
+        assert!(is_due(hours_ago(24), 24, now));
+        assert!(!is_due(hours_ago(23), 24, now));
+    }
+}
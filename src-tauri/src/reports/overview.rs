@@ -0,0 +1,308 @@
+//! Environment overview report.
+
+use tauri::AppHandle;
+
+use super::html::{Cell, Document, Tone};
+use crate::checks::deploy_freeze::{self, ProjectFreezes};
+use crate::checks::uptime::{self, UptimeTargetStatus};
+use crate::commands::config::{
+    load_environments, load_mappings, load_projects, load_uptime_targets,
+};
+use crate::ownership;
+use crate::types::{Environment, Mapping, Project, ReportDefinition, Timestamp, UptimeTarget};
+
+/// Adds one table per project listing its environments, what is mapped to
+/// them and who owns it, the state of their uptime targets and their GitLab deploy freezes.
+/// Freezes are read from the report's GitLab integration, or from every
+/// GitLab integration when none is selected.
+pub async fn render(
+    app: &AppHandle,
+    doc: &mut Document,
+    definition: &ReportDefinition,
+) -> Result<(), String> {
+    let projects = load_projects(app.clone()).await?;
+    let environments = load_environments(app.clone()).await?;
+    let mappings = load_mappings(app.clone()).await?;
+    let targets = load_uptime_targets(app.clone()).await?;
+    let statuses = uptime::status(app).await?.targets;
+    let environment_ids: Vec<&str> = environments.iter().map(|e| e.id.as_str()).collect();
+    let freezes = deploy_freeze::fetch_freezes(
+        app,
+        &deploy_freeze::mapped_projects(&environment_ids, &[], &mappings),
+        definition.integration_id.as_deref(),
+    )
+    .await?;
+
+    doc.metrics(&[
+        ("Projects", projects.len().to_string()),
+        ("Environments", environments.len().to_string()),
+        ("Mappings", mappings.len().to_string()),
+        ("Uptime targets", targets.len().to_string()),
+    ]);
+
+    if projects.is_empty() {
+        doc.note("No projects configured.");
+    }
+    for project in &projects {
+        doc.heading(&project.name);
+        if let Some(description) = &project.description {
+            doc.paragraph(description);
+        }
+        if !project.ownership.is_empty() {
+            doc.paragraph(&format!(
+                "Owner: {}",
+                ownership::summary(&project.ownership)
+            ));
+        }
+
+        let rows: Vec<Vec<Cell>> = environments
+            .iter()
+            .filter(|env| env.project_id == project.id)
+            .map(|env| environment_row(project, env, &mappings, &targets, &statuses, &freezes))
+            .collect();
+        if rows.is_empty() {
+            doc.note("No environments.");
+        } else {
+            doc.table(
+                &[
+                    "Environment",
+                    "Namespace",
+                    "Repositories",
+                    "Jenkins jobs",
+                    "Services",
+                    "Owners",
+                    "Uptime",
+                    "Deploy freeze",
+                ],
+                rows,
+            );
+        }
+    }
+    Ok(())
+}
+
+fn environment_row(
+    project: &Project,
+    env: &Environment,
+    mappings: &[Mapping],
+    targets: &[UptimeTarget],
+    statuses: &[UptimeTargetStatus],
+    freezes: &ProjectFreezes,
+) -> Vec<Cell> {
+    let mapped: Vec<&Mapping> = mappings
+        .iter()
+        .filter(|m| m.environment_id.as_deref() == Some(env.id.as_str()))
+        .collect();
+    let list = |field: fn(&Mapping) -> Option<&String>| {
+        let mut values: Vec<&str> = mapped
+            .iter()
+            .filter_map(|m| field(m))
+            .map(String::as_str)
+            .collect();
+        values.sort_unstable();
+        values.dedup();
+        if values.is_empty() {
+            "-".to_string()
+        } else {
+            values.join(", ")
+        }
+    };
+
+    vec![
+        env.name.as_str().into(),
+        env.namespace.as_deref().unwrap_or("-").into(),
+        list(|m| m.repo_id.as_ref()).into(),
+        list(|m| m.job_id.as_ref()).into(),
+        list(|m| m.service_name.as_ref()).into(),
+        owners_cell(project, &mapped),
+        uptime_cell(&env.id, targets, statuses),
+        freeze_cell(
+            &deploy_freeze::mapped_projects(&[&env.id], &[], mappings),
+            freezes,
+        ),
+    ]
+}
+
+/// Lists the owning teams of an environment's services, falling back to
+/// the project's team.
+fn owners_cell(project: &Project, mapped: &[&Mapping]) -> Cell {
+    let mut teams: Vec<String> = mapped
+        .iter()
+        .filter_map(|m| m.ownership.clone().or(&project.ownership).owner_team)
+        .collect();
+    teams.extend(
+        project
+            .ownership
+            .owner_team
+            .clone()
+            .filter(|_| mapped.is_empty()),
+    );
+    teams.sort_unstable();
+    teams.dedup();
+    if teams.is_empty() {
+        "-".into()
+    } else {
+        teams.join(", ").into()
+    }
+}
+
+/// Summarizes the freeze periods of an environment's GitLab projects: the
+/// freezes in effect, else the next one to start.
+fn freeze_cell(projects: &[u32], freezes: &ProjectFreezes) -> Cell {
+    if projects.is_empty() {
+        return "-".into();
+    }
+
+    let mut active = Vec::new();
+    let mut next = None;
+    let mut unknown = false;
+    for project_id in projects {
+        match freezes.get(project_id) {
+            Some(Ok(periods)) => {
+                for period in periods {
+                    if period.active {
+                        active.push(deploy_freeze::describe_active(*project_id, period));
+                    } else if let Some(starts_at) = &period.starts_at {
+                        // Same format and zone, so the text orders by time
+                        if next.is_none_or(|n: &Timestamp| starts_at.rfc3339 < n.rfc3339) {
+                            next = Some(starts_at);
+                        }
+                    }
+                }
+            }
+            Some(Err(_)) | None => unknown = true,
+        }
+    }
+
+    if !active.is_empty() {
+        Cell::new(active.join("; "), Tone::Bad)
+    } else if unknown {
+        Cell::new("unknown", Tone::Warn)
+    } else if let Some(starts_at) = next {
+        format!("next from {starts_at}").into()
+    } else {
+        Cell::new("none", Tone::Ok)
+    }
+}
+
+/// Summarizes the uptime targets of an environment, e.g. "2/2 up, 99.8%".
+fn uptime_cell(
+    environment_id: &str,
+    targets: &[UptimeTarget],
+    statuses: &[UptimeTargetStatus],
+) -> Cell {
+    let statuses: Vec<&UptimeTargetStatus> = targets
+        .iter()
+        .filter(|t| t.environment_id.as_deref() == Some(environment_id))
+        .filter_map(|t| statuses.iter().find(|s| s.target_id == t.id))
+        .collect();
+    if statuses.is_empty() {
+        return "-".into();
+    }
+
+    let checked: Vec<bool> = statuses.iter().filter_map(|s| s.up).collect();
+    if checked.is_empty() {
+        return format!("{} not checked yet", statuses.len()).into();
+    }
+    let up = checked.iter().filter(|up| **up).count();
+    let percents: Vec<f64> = statuses.iter().filter_map(|s| s.uptime_percent).collect();
+    let mut text = format!("{up}/{} up", checked.len());
+    if !percents.is_empty() {
+        let worst = percents.iter().copied().fold(f64::INFINITY, f64::min);
+        text.push_str(&format!(", {worst:.1}%"));
+    }
+
+    let tone = if up == checked.len() {
+        Tone::Ok
+    } else {
+        Tone::Bad
+    };
+    Cell::new(text, tone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::gitlab::GitLabFreezePeriod;
+
+    fn target(id: &str, environment_id: &str) -> UptimeTarget {
+        UptimeTarget {
+            id: id.to_string(),
+            environment_id: Some(environment_id.to_string()),
+            name: id.to_string(),
+            url: format!("https://{id}.example.com"),
+            interval_seconds: 60,
+            timeout_seconds: 10,
+            expected_status: None,
+        }
+    }
+
+    fn status(id: &str, up: Option<bool>, uptime_percent: Option<f64>) -> UptimeTargetStatus {
+        UptimeTargetStatus {
+            target_id: id.to_string(),
+            name: id.to_string(),
+            url: format!("https://{id}.example.com"),
+            up,
+            consecutive_failures: 0,
+            uptime_percent,
+            history: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_uptime_cell() {
+        let targets = vec![target("api", "prod"), target("web", "prod")];
+
+        let all_up = uptime_cell(
+            "prod",
+            &targets,
+            &[
+                status("api", Some(true), Some(99.95)),
+                status("web", Some(true), Some(99.5)),
+            ],
+        );
+        assert_eq!(all_up, Cell::new("2/2 up, 99.5%", Tone::Ok));
+
+        let one_down = uptime_cell(
+            "prod",
+            &targets,
+            &[status("api", Some(false), None), status("web", None, None)],
+        );
+        assert_eq!(one_down, Cell::new("0/1 up", Tone::Bad));
+
+        assert_eq!(uptime_cell("staging", &targets, &[]).text, "-");
+    }
+
+    #[test]
+    fn test_freeze_cell() {
+        let period = |active: bool, starts_at: &str| GitLabFreezePeriod {
+            id: 1,
+            freeze_start: "0 23 * * 5".to_string(),
+            freeze_end: "0 7 * * 1".to_string(),
+            cron_timezone: "UTC".to_string(),
+            active,
+            starts_at: Some(Timestamp::parse(starts_at).unwrap()),
+            ends_at: Some(Timestamp::parse("2024-05-06T07:00:00Z").unwrap()),
+        };
+        let freezes = ProjectFreezes::from([
+            (1, Ok(vec![period(false, "2024-05-10T23:00:00Z")])),
+            (2, Ok(vec![period(false, "2024-05-03T23:00:00Z")])),
+            (3, Ok(vec![period(true, "2024-05-03T23:00:00Z")])),
+            (4, Err("Network error".to_string())),
+        ]);
+
+        assert_eq!(
+            freeze_cell(&[1, 2], &freezes),
+            Cell::new("next from 2024-05-03T23:00:00.000Z", Tone::Plain)
+        );
+        assert_eq!(
+            freeze_cell(&[1, 3, 4], &freezes),
+            Cell::new(
+                "project 3 is frozen until 2024-05-06T07:00:00.000Z",
+                Tone::Bad
+            )
+        );
+        assert_eq!(freeze_cell(&[1, 4], &freezes).text, "unknown");
+        assert_eq!(freeze_cell(&[], &freezes).text, "-");
+    }
+}
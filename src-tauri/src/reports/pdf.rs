@@ -0,0 +1,65 @@
+//! PDF rendering through a headless browser.
+//!
+//! Prints the HTML report with Chrome, Chromium or Edge, whichever is found
+//! first. `OPS_FLOW_BROWSER` overrides the search with an explicit binary.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Environment variable naming the browser binary to print with.
+const BROWSER_ENV: &str = "OPS_FLOW_BROWSER";
+
+fn browser_candidates() -> Vec<PathBuf> {
+    if let Ok(browser) = std::env::var(BROWSER_ENV) {
+        return vec![PathBuf::from(browser)];
+    }
+
+    let paths: &[&str] = if cfg!(target_os = "macos") {
+        &[
+            "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+            "/Applications/Chromium.app/Contents/MacOS/Chromium",
+            "/Applications/Microsoft Edge.app/Contents/MacOS/Microsoft Edge",
+        ]
+    } else if cfg!(target_os = "windows") {
+        &[
+            r"C:\Program Files\Google\Chrome\Application\chrome.exe",
+            r"C:\Program Files (x86)\Google\Chrome\Application\chrome.exe",
+            r"C:\Program Files (x86)\Microsoft\Edge\Application\msedge.exe",
+        ]
+    } else {
+        &[
+            "/usr/bin/google-chrome",
+            "/usr/bin/google-chrome-stable",
+            "/usr/bin/chromium",
+            "/usr/bin/chromium-browser",
+            "/usr/bin/microsoft-edge",
+        ]
+    };
+    paths.iter().map(PathBuf::from).collect()
+}
+
+/// Prints `html_path` to `pdf_path`. Blocks until the browser exits.
+pub fn print_to_pdf(html_path: &Path, pdf_path: &Path) -> Result<(), String> {
+    let browser = browser_candidates()
+        .into_iter()
+        .find(|path| path.exists())
+        .ok_or_else(|| {
+            format!("PDF export needs Chrome, Chromium or Edge; set {BROWSER_ENV} to its path")
+        })?;
+    log::debug!("Printing {html_path:?} to PDF with {browser:?}");
+
+    let output = Command::new(&browser)
+        .arg("--headless")
+        .arg("--disable-gpu")
+        .arg("--no-pdf-header-footer")
+        .arg(format!("--print-to-pdf={}", pdf_path.display()))
+        .arg(html_path)
+        .output()
+        .map_err(|e| format!("Failed to start {}: {e}", browser.display()))?;
+
+    if !output.status.success() || !pdf_path.exists() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("PDF rendering failed: {}", stderr.trim()));
+    }
+    Ok(())
+}
@@ -0,0 +1,377 @@
+//! Saved filters.
+//!
+//! Evaluates saved filters against resources read from the integrations.
+//! Resources are cached for `CACHE_TTL`, so filters over the same projects,
+//! jobs or namespaces share requests. The matches of each filter are kept in
+//! memory to detect changes: when they change, `saved-filter-changed` is
+//! emitted and filters with `notify` set send a native notification. Those
+//! filters are also re-evaluated in the background.
+
+mod records;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::commands::config::{load_environments, load_mappings, load_saved_filters};
+use crate::commands::notifications::send_native_notification;
+use crate::flow_engine::glob_matches;
+use crate::metrics;
+use crate::task_pool::{self, Priority};
+use crate::types::{FilterCondition, FilterOperator, SavedFilter, SavedFilterResource, Timestamp};
+use crate::windows;
+
+/// Event emitted with a `SavedFilterResult` when a filter's matches change.
+pub const SAVED_FILTER_CHANGED_EVENT: &str = "saved-filter-changed";
+
+/// How long fetched resources are reused.
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Delay before the first background evaluation after startup.
+const STARTUP_DELAY: Duration = Duration::from_secs(60);
+
+/// How often filters with `notify` set are re-evaluated.
+pub(crate) const WATCH_INTERVAL: Duration = Duration::from_secs(2 * 60);
+
+/// A resource matched by a filter.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FilterMatch {
+    /// Identifies the resource across evaluations (e.g., "prod/api-7d9f")
+    pub key: String,
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Result of evaluating a saved filter.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SavedFilterResult {
+    pub filter_id: String,
+    pub evaluated_at: Timestamp,
+    pub matches: Vec<FilterMatch>,
+    /// Keys matching now that did not at the previous evaluation
+    pub added: Vec<String>,
+    /// Keys that matched at the previous evaluation and no longer do
+    pub removed: Vec<String>,
+    /// Targets that could not be read; changes are not tracked while any fail
+    pub errors: Vec<String>,
+}
+
+/// Records per (integration, resource, target), with their fetch time.
+type Cache = HashMap<(String, SavedFilterResource, String), (Instant, Vec<FilterMatch>)>;
+
+static CACHE: LazyLock<Mutex<Cache>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Matched keys of the previous evaluation, per filter.
+static PREVIOUS: LazyLock<Mutex<HashMap<String, BTreeSet<String>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn cache() -> MutexGuard<'static, Cache> {
+    CACHE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn previous() -> MutexGuard<'static, HashMap<String, BTreeSet<String>>> {
+    PREVIOUS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Evaluates a filter, reporting changes since its previous evaluation.
+pub async fn evaluate(app: &AppHandle, filter: &SavedFilter) -> Result<SavedFilterResult, String> {
+    let targets = resolve_targets(app, filter).await?;
+    let reads = reads(filter.resource, &targets);
+
+    let mut matches: Vec<FilterMatch> = Vec::new();
+    let mut errors = Vec::new();
+    for read in reads {
+        match cached_records(app, filter, read).await {
+            Ok(records) => matches.extend(
+                records
+                    .into_iter()
+                    .filter(|r| is_selected(filter.resource, &targets, r))
+                    .filter(|r| filter.conditions.iter().all(|c| matches_condition(r, c))),
+            ),
+            Err(e) if read.is_empty() => errors.push(e),
+            Err(e) => errors.push(format!("{read}: {e}")),
+        }
+    }
+    matches.sort_by(|a, b| a.key.cmp(&b.key));
+    matches.dedup_by(|a, b| a.key == b.key);
+
+    let keys: BTreeSet<String> = matches.iter().map(|m| m.key.clone()).collect();
+    let (added, removed) = if errors.is_empty() {
+        let last = previous().insert(filter.id.clone(), keys.clone());
+        diff_keys(last.as_ref(), &keys)
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let result = SavedFilterResult {
+        filter_id: filter.id.clone(),
+        evaluated_at: Timestamp::from_datetime(Utc::now()),
+        matches,
+        added,
+        removed,
+        errors,
+    };
+    if !result.added.is_empty() || !result.removed.is_empty() {
+        report_change(app, filter, &result).await;
+    }
+    Ok(result)
+}
+
+/// Starts a background task re-evaluating filters with `notify` set.
+pub fn start_watching(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        loop {
+            match load_saved_filters(app.clone()).await {
+                Ok(filters) => {
+                    for filter in filters.iter().filter(|f| f.notify) {
+                        let result = task_pool::run(
+                            Priority::Poller,
+                            &filter.integration_id,
+                            evaluate(&app, filter),
+                        )
+                        .await;
+                        let ok = result.as_ref().is_ok_and(|r| r.errors.is_empty());
+                        metrics::record_poll("saved_filters", ok);
+                        if let Err(e) = result {
+                            log::warn!("Saved filter {} failed: {e}", filter.name);
+                        }
+                    }
+                }
+                Err(e) => log::warn!("Failed to load saved filters: {e}"),
+            }
+            tokio::time::sleep(WATCH_INTERVAL).await;
+        }
+    });
+}
+
+/// Targets read from the API when a filter is evaluated ("" for all Jenkins jobs).
+pub(crate) async fn polled_reads(
+    app: &AppHandle,
+    filter: &SavedFilter,
+) -> Result<Vec<String>, String> {
+    let targets = resolve_targets(app, filter).await?;
+    Ok(reads(filter.resource, &targets)
+        .into_iter()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Jenkins lists all jobs at once; they are narrowed down to the targets
+/// after reading.
+fn reads(resource: SavedFilterResource, targets: &[String]) -> Vec<&str> {
+    match resource {
+        SavedFilterResource::JenkinsJobs => vec![""],
+        _ => targets.iter().map(String::as_str).collect(),
+    }
+}
+
+/// Targets given on the filter plus the ones mapped to its environment.
+async fn resolve_targets(app: &AppHandle, filter: &SavedFilter) -> Result<Vec<String>, String> {
+    let mut targets: Vec<String> = filter
+        .targets
+        .iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    if let Some(environment_id) = &filter.environment_id {
+        let environments = load_environments(app.clone()).await?;
+        let environment = environments
+            .iter()
+            .find(|e| &e.id == environment_id)
+            .ok_or_else(|| format!("Environment not found: {environment_id}"))?;
+        let mappings = load_mappings(app.clone()).await?;
+        let mapped = mappings
+            .iter()
+            .filter(|m| m.environment_id.as_ref() == Some(environment_id));
+        match filter.resource {
+            SavedFilterResource::GitlabPipelines => {
+                targets.extend(mapped.filter_map(|m| m.repo_id.clone()));
+            }
+            SavedFilterResource::JenkinsJobs => {
+                targets.extend(mapped.filter_map(|m| m.job_id.clone()));
+            }
+            SavedFilterResource::K8sPods => {
+                targets.extend(mapped.filter_map(|m| m.namespace.clone()));
+                targets.extend(environment.namespace.clone());
+            }
+        }
+    }
+    targets.sort();
+    targets.dedup();
+
+    if targets.is_empty() && filter.resource != SavedFilterResource::JenkinsJobs {
+        return Err(format!(
+            "Saved filter {} has no projects or namespaces to read",
+            filter.name
+        ));
+    }
+    Ok(targets)
+}
+
+/// Records of a target, fetched at most once per `CACHE_TTL`.
+async fn cached_records(
+    app: &AppHandle,
+    filter: &SavedFilter,
+    target: &str,
+) -> Result<Vec<FilterMatch>, String> {
+    let key = (
+        filter.integration_id.clone(),
+        filter.resource,
+        target.to_string(),
+    );
+    if let Some((fetched_at, records)) = cache().get(&key) {
+        if fetched_at.elapsed() < CACHE_TTL {
+            return Ok(records.clone());
+        }
+    }
+
+    let records = records::fetch(app, filter.resource, &filter.integration_id, target).await?;
+    cache().insert(key, (Instant::now(), records.clone()));
+    Ok(records)
+}
+
+/// Jenkins jobs are read all at once; keeps the targeted ones (all without targets).
+fn is_selected(resource: SavedFilterResource, targets: &[String], record: &FilterMatch) -> bool {
+    resource != SavedFilterResource::JenkinsJobs
+        || targets.is_empty()
+        || targets.contains(&record.key)
+}
+
+/// Compares field values case-insensitively; missing fields are empty.
+fn matches_condition(record: &FilterMatch, condition: &FilterCondition) -> bool {
+    let actual = record
+        .fields
+        .get(&condition.field)
+        .map(|v| v.to_lowercase())
+        .unwrap_or_default();
+    let expected = condition.value.trim().to_lowercase();
+    match condition.operator {
+        FilterOperator::Equals => actual == expected,
+        FilterOperator::NotEquals => actual != expected,
+        FilterOperator::Contains => actual.contains(&expected),
+        FilterOperator::Matches => glob_matches(&expected, &actual),
+        FilterOperator::OneOf => expected.split(',').any(|v| v.trim() == actual),
+    }
+}
+
+/// Keys added and removed since the previous evaluation; nothing on the first.
+fn diff_keys(
+    previous: Option<&BTreeSet<String>>,
+    current: &BTreeSet<String>,
+) -> (Vec<String>, Vec<String>) {
+    let Some(previous) = previous else {
+        return (Vec::new(), Vec::new());
+    };
+    (
+        current.difference(previous).cloned().collect(),
+        previous.difference(current).cloned().collect(),
+    )
+}
+
+async fn report_change(app: &AppHandle, filter: &SavedFilter, result: &SavedFilterResult) {
+    log::info!(
+        "Saved filter {} changed: {} added, {} removed",
+        filter.name,
+        result.added.len(),
+        result.removed.len()
+    );
+    windows::broadcast(app, SAVED_FILTER_CHANGED_EVENT, result);
+    if filter.notify {
+        let body = format!(
+            "{} now match ({} new, {} gone)",
+            result.matches.len(),
+            result.added.len(),
+            result.removed.len()
+        );
+        if let Err(e) = send_native_notification(app.clone(), filter.name.clone(), Some(body)).await
+        {
+            log::warn!("Failed to send saved filter notification: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pod(key: &str, status: &str) -> FilterMatch {
+        FilterMatch {
+            key: key.to_string(),
+            fields: BTreeMap::from([
+                ("name".to_string(), key.to_string()),
+                ("status".to_string(), status.to_string()),
+            ]),
+        }
+    }
+
+    fn condition(field: &str, operator: FilterOperator, value: &str) -> FilterCondition {
+        FilterCondition {
+            field: field.to_string(),
+            operator,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_matches_condition() {
+        let record = pod("api-7d9f", "Running");
+
+        assert!(matches_condition(
+            &record,
+            &condition("status", FilterOperator::Equals, "running")
+        ));
+        assert!(!matches_condition(
+            &record,
+            &condition("status", FilterOperator::NotEquals, "Running")
+        ));
+        assert!(matches_condition(
+            &record,
+            &condition("name", FilterOperator::Matches, "api-*")
+        ));
+        assert!(matches_condition(
+            &record,
+            &condition("status", FilterOperator::OneOf, "Pending, Running")
+        ));
+        assert!(matches_condition(
+            &record,
+            &condition("name", FilterOperator::Contains, "7D9")
+        ));
+        // Missing fields compare as empty
+        assert!(matches_condition(
+            &record,
+            &condition("node", FilterOperator::Equals, "")
+        ));
+    }
+
+    #[test]
+    fn test_diff_keys() {
+        let keys = |k: &[&str]| k.iter().map(|s| s.to_string()).collect::<BTreeSet<_>>();
+
+        assert_eq!(diff_keys(None, &keys(&["a"])), (vec![], vec![]));
+        assert_eq!(
+            diff_keys(Some(&keys(&["a", "b"])), &keys(&["b", "c"])),
+            (vec!["c".to_string()], vec!["a".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_is_selected_narrows_jenkins_jobs() {
+        let jobs = SavedFilterResource::JenkinsJobs;
+        let targets = vec!["deploy/api".to_string()];
+
+        assert!(is_selected(jobs, &targets, &pod("deploy/api", "failed")));
+        assert!(!is_selected(jobs, &targets, &pod("deploy/web", "failed")));
+        assert!(is_selected(jobs, &[], &pod("deploy/web", "failed")));
+        assert!(is_selected(
+            SavedFilterResource::K8sPods,
+            &targets,
+            &pod("prod/api-7d9f", "Running")
+        ));
+    }
+}
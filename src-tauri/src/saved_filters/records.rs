@@ -0,0 +1,154 @@
+//! Resources as flat records of named fields that filter conditions compare.
+
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+use super::FilterMatch;
+use crate::commands::gitlab::gitlab_adapter;
+use crate::commands::jenkins::jenkins_adapter;
+use crate::commands::kubernetes::kubernetes_adapter;
+use crate::integrations::gitlab::GitLabPipeline;
+use crate::integrations::jenkins::JenkinsJob;
+use crate::integrations::kubernetes::K8sPod;
+use crate::types::SavedFilterResource;
+
+/// Fetches the records of one target: a GitLab project ID, a namespace, or
+/// (for Jenkins, whose jobs are listed at once) an empty target for all jobs.
+pub async fn fetch(
+    app: &AppHandle,
+    resource: SavedFilterResource,
+    integration_id: &str,
+    target: &str,
+) -> Result<Vec<FilterMatch>, String> {
+    match resource {
+        SavedFilterResource::GitlabPipelines => {
+            let project_id: u32 = target
+                .parse()
+                .map_err(|_| format!("Invalid GitLab project ID: {target}"))?;
+            let pipelines = gitlab_adapter(app, integration_id)
+                .await?
+                .fetch_pipelines(project_id)
+                .await
+                .map_err(|e| format!("Failed to fetch pipelines: {e}"))?;
+            Ok(pipelines
+                .iter()
+                .map(|p| pipeline_record(project_id, p))
+                .collect())
+        }
+        SavedFilterResource::JenkinsJobs => {
+            let jobs = jenkins_adapter(app, integration_id)
+                .await?
+                .fetch_jobs()
+                .await
+                .map_err(|e| format!("Failed to fetch jobs: {e}"))?;
+            Ok(jobs.iter().map(job_record).collect())
+        }
+        SavedFilterResource::K8sPods => {
+            let pods = kubernetes_adapter(app, integration_id)
+                .await?
+                .fetch_pods(target)
+                .await
+                .map_err(|e| format!("Failed to fetch pods: {e}"))?;
+            Ok(pods.iter().map(pod_record).collect())
+        }
+    }
+}
+
+fn record<const N: usize>(key: String, fields: [(&str, String); N]) -> FilterMatch {
+    FilterMatch {
+        key,
+        fields: fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect::<BTreeMap<_, _>>(),
+    }
+}
+
+fn pipeline_record(project_id: u32, pipeline: &GitLabPipeline) -> FilterMatch {
+    record(
+        format!("{project_id}#{}", pipeline.id),
+        [
+            ("id", pipeline.id.to_string()),
+            ("project_id", project_id.to_string()),
+            ("status", pipeline.status.clone()),
+            ("ref", pipeline.r#ref.clone()),
+            ("created_at", pipeline.created_at.rfc3339.clone()),
+        ],
+    )
+}
+
+fn job_record(job: &JenkinsJob) -> FilterMatch {
+    record(
+        job.name.clone(),
+        [
+            ("name", job.name.clone()),
+            ("color", job.color.clone()),
+            ("status", job_status(&job.color).to_string()),
+        ],
+    )
+}
+
+fn pod_record(pod: &K8sPod) -> FilterMatch {
+    record(
+        format!("{}/{}", pod.namespace, pod.name),
+        [
+            ("name", pod.name.clone()),
+            ("namespace", pod.namespace.clone()),
+            ("status", pod.status.clone()),
+            ("node", pod.node.clone().unwrap_or_default()),
+            ("containers", pod.containers.join(",")),
+        ],
+    )
+}
+
+/// Status of a job's last build from its ball color (e.g., "red_anime").
+fn job_status(color: &str) -> &str {
+    if color.ends_with("_anime") {
+        return "building";
+    }
+    match color {
+        "blue" | "green" => "success",
+        "red" => "failed",
+        "yellow" => "unstable",
+        "aborted" => "aborted",
+        "disabled" => "disabled",
+        "notbuilt" | "grey" => "not_built",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_record() {
+        let job = |color: &str| JenkinsJob {
+            name: "deploy/api".to_string(),
+            url: "https://jenkins.example.com/job/deploy/job/api/".to_string(),
+            color: color.to_string(),
+        };
+
+        let record = job_record(&job("red"));
+        assert_eq!(record.key, "deploy/api");
+        assert_eq!(record.fields["status"], "failed");
+        assert_eq!(job_record(&job("blue_anime")).fields["status"], "building");
+        assert_eq!(job_record(&job("notbuilt")).fields["status"], "not_built");
+    }
+
+    #[test]
+    fn test_pod_record() {
+        let pod = K8sPod {
+            name: "api-7d9f".to_string(),
+            namespace: "prod".to_string(),
+            status: "CrashLoopBackOff".to_string(),
+            containers: vec!["api".to_string(), "sidecar".to_string()],
+            node: None,
+        };
+
+        let record = pod_record(&pod);
+        assert_eq!(record.key, "prod/api-7d9f");
+        assert_eq!(record.fields["containers"], "api,sidecar");
+        assert_eq!(record.fields["node"], "");
+    }
+}
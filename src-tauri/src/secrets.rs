@@ -0,0 +1,217 @@
+//! Secret references in configuration.
+//!
+//! Config values that hold secrets (webhook secrets, smoke test headers) may
+//! name a secret instead of containing it: `keyring:<name>` reads a secret
+//! saved in the OS keyring, `vault:<path>#<field>` reads a field of a
+//! HashiCorp Vault secret. References are resolved each time the value is
+//! used, so secrets never land in YAML. Values without a known scheme are
+//! used as they are.
+
+use async_trait::async_trait;
+use keyring::Entry;
+use serde_json::Value;
+use std::time::Duration;
+
+/// Keyring service holding saved secrets.
+const KEYRING_SERVICE: &str = "ops-flow";
+
+/// Prefix of keyring entries holding secrets, keeping them apart from
+/// integration credentials.
+const KEYRING_PREFIX: &str = "secret:";
+
+/// Keyring secret holding the Vault token when `VAULT_TOKEN` is not set.
+const VAULT_TOKEN_SECRET: &str = "vault-token";
+
+/// Field read from a Vault secret when the reference names none.
+const DEFAULT_VAULT_FIELD: &str = "value";
+
+/// Source of secrets for references with its scheme.
+#[async_trait]
+trait SecretBackend: Send + Sync {
+    /// Scheme of references (e.g., "keyring" for `keyring:<name>`).
+    fn scheme(&self) -> &'static str;
+
+    /// Reads the secret `reference` names (the part after the scheme).
+    async fn resolve(&self, reference: &str) -> Result<String, String>;
+}
+
+static BACKENDS: [&dyn SecretBackend; 2] = [&KeyringBackend, &VaultBackend];
+
+/// Splits a value into a backend and the reference it resolves, if the value
+/// is a secret reference.
+fn parse(value: &str) -> Option<(&'static dyn SecretBackend, &str)> {
+    let (scheme, reference) = value.trim().split_once(':')?;
+    let backend = BACKENDS.iter().find(|b| b.scheme() == scheme)?;
+    Some((*backend, reference.trim()))
+}
+
+/// Expands a config value: secret references are replaced by the secret,
+/// other values are returned unchanged.
+pub async fn resolve(value: &str) -> Result<String, String> {
+    let Some((backend, reference)) = parse(value) else {
+        return Ok(value.to_string());
+    };
+    if reference.is_empty() {
+        return Err(format!(
+            "Secret reference '{value}' names no {} secret",
+            backend.scheme()
+        ));
+    }
+    backend
+        .resolve(reference)
+        .await
+        .map_err(|e| format!("Failed to resolve secret '{value}': {e}"))
+}
+
+fn keyring_entry(name: &str) -> Result<Entry, String> {
+    Entry::new(KEYRING_SERVICE, &format!("{KEYRING_PREFIX}{name}"))
+        .map_err(|e| format!("Failed to access keyring: {e}"))
+}
+
+/// Saves a secret in the OS keyring for `keyring:<name>` references.
+pub fn save_keyring_secret(name: &str, value: &str) -> Result<(), String> {
+    keyring_entry(name)?
+        .set_password(value)
+        .map_err(|e| format!("Failed to save secret: {e}"))
+}
+
+/// Reads a secret saved in the OS keyring, or `None` if there is none.
+pub fn read_keyring_secret(name: &str) -> Result<Option<String>, String> {
+    match keyring_entry(name)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read keyring: {e}")),
+    }
+}
+
+/// Deletes a secret saved in the OS keyring. Missing secrets are ignored.
+pub fn delete_keyring_secret(name: &str) -> Result<(), String> {
+    match keyring_entry(name)?.delete_password() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to delete secret: {e}")),
+    }
+}
+
+/// Secrets saved in the OS keyring.
+struct KeyringBackend;
+
+#[async_trait]
+impl SecretBackend for KeyringBackend {
+    fn scheme(&self) -> &'static str {
+        "keyring"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, String> {
+        let name = reference.to_string();
+        tokio::task::spawn_blocking(move || match keyring_entry(&name)?.get_password() {
+            Ok(secret) => Ok(secret),
+            Err(keyring::Error::NoEntry) => Err(format!("no keyring secret named '{name}'")),
+            Err(e) => Err(format!("Failed to read keyring: {e}")),
+        })
+        .await
+        .map_err(|e| format!("Keyring task failed: {e}"))?
+    }
+}
+
+/// Secrets of a HashiCorp Vault server at `VAULT_ADDR`, read with the token
+/// in `VAULT_TOKEN` or the `vault-token` keyring secret.
+struct VaultBackend;
+
+#[async_trait]
+impl SecretBackend for VaultBackend {
+    fn scheme(&self) -> &'static str {
+        "vault"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, String> {
+        let address =
+            std::env::var("VAULT_ADDR").map_err(|_| "VAULT_ADDR is not set".to_string())?;
+        let token = match std::env::var("VAULT_TOKEN") {
+            Ok(token) => token,
+            Err(_) => KeyringBackend.resolve(VAULT_TOKEN_SECRET).await?,
+        };
+        let (path, field) = reference
+            .split_once('#')
+            .unwrap_or((reference, DEFAULT_VAULT_FIELD));
+
+        let url = format!(
+            "{}/v1/{}",
+            address.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        );
+        let response = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| format!("Failed to initialize HTTP client: {e}"))?
+            .get(&url)
+            .header("X-Vault-Token", token)
+            .send()
+            .await
+            .map_err(|e| format!("Vault request failed: {e}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Vault returned {status} for {path}"));
+        }
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Invalid Vault response: {e}"))?;
+        vault_field(&body, field).ok_or_else(|| format!("{path} has no field '{field}'"))
+    }
+}
+
+/// Reads a field of a Vault secret: KV version 2 nests the fields in
+/// `data.data`, version 1 in `data`.
+fn vault_field(body: &Value, field: &str) -> Option<String> {
+    let data = &body["data"];
+    let fields = if data["data"].is_object() && data["metadata"].is_object() {
+        &data["data"]
+    } else {
+        data
+    };
+    match &fields[field] {
+        Value::String(value) => Some(value.clone()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("keyring:webhook-secret").map(|(b, r)| (b.scheme(), r)),
+            Some(("keyring", "webhook-secret"))
+        );
+        assert_eq!(
+            parse(" vault:secret/data/ops#token ").map(|(b, r)| (b.scheme(), r)),
+            Some(("vault", "secret/data/ops#token"))
+        );
+        assert!(parse("Bearer abc:def").is_none());
+        assert!(parse("s3cret").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_literal() {
+        assert_eq!(resolve("Bearer abc:def").await.unwrap(), "Bearer abc:def");
+        assert!(resolve("keyring:").await.is_err());
+    }
+
+    #[test]
+    fn test_vault_field() {
+        let kv2 = json!({ "data": {
+            "data": { "token": "t0ken", "port": 8443 },
+            "metadata": { "version": 3 }
+        } });
+        assert_eq!(vault_field(&kv2, "token").as_deref(), Some("t0ken"));
+        assert_eq!(vault_field(&kv2, "port").as_deref(), Some("8443"));
+        assert_eq!(vault_field(&kv2, "missing"), None);
+
+        let kv1 = json!({ "data": { "value": "s3cret" } });
+        assert_eq!(vault_field(&kv1, "value").as_deref(), Some("s3cret"));
+    }
+}
@@ -0,0 +1,485 @@
+//! GitLab tag watcher.
+//!
+//! While running, the watcher polls the projects of every tag watch
+//! (`tag_watches.yaml`) at its interval and records tags matching the watch's
+//! pattern in `<app data>/tag_watch_state.json`. The first poll of a watch
+//! only records the existing tags; tags that appear afterwards are reported:
+//! a `tag-detected` event is emitted, a native notification is sent and flows
+//! are started as if GitLab had delivered a `tag_push` webhook.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use specta::Type;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::sync::oneshot;
+
+use crate::commands::config::load_tag_watches;
+use crate::commands::flows::read_saved_flows;
+use crate::commands::gitlab::gitlab_adapter;
+use crate::commands::notifications::send_native_notification;
+use crate::flow_engine::{self, glob_matches, WebhookEvent};
+use crate::integrations::gitlab::GitLabTag;
+use crate::metrics;
+use crate::task_pool::{self, Priority};
+use crate::types::TagWatch;
+use crate::windows;
+
+/// Event emitted with a `DetectedTag` for every new tag.
+pub const TAG_DETECTED_EVENT: &str = "tag-detected";
+
+/// Detected tags kept per watch.
+const MAX_DETECTED: usize = 50;
+
+/// How often the watcher looks for watches that are due.
+const TICK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Shortest interval between polls of a watch.
+pub(crate) const MIN_INTERVAL_SECONDS: u32 = 30;
+
+/// A new tag found by a watch.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct DetectedTag {
+    pub watch_id: String,
+    pub project_id: u32,
+    pub tag: String,
+    /// Commit SHA the tag points to
+    pub commit_sha: String,
+    /// Detection timestamp (RFC 3339)
+    pub detected_at: String,
+    /// IDs of the flow runs started for the tag
+    pub run_ids: Vec<String>,
+}
+
+/// Current state of a watch.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct TagWatchStatus {
+    pub watch_id: String,
+    pub name: String,
+    /// Last poll timestamp (RFC 3339)
+    pub last_checked_at: Option<String>,
+    /// Why the last poll failed
+    pub last_error: Option<String>,
+    /// Tags detected, newest first
+    pub detected: Vec<DetectedTag>,
+}
+
+/// Current state of the tag watcher.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TagWatcherStatus {
+    pub running: bool,
+    /// Start timestamp (RFC 3339)
+    pub started_at: Option<String>,
+    pub watches: Vec<TagWatchStatus>,
+}
+
+/// Recorded state of a watch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct WatchState {
+    /// Project and pattern the seen tags belong to; a changed watch starts over
+    baseline: Option<String>,
+    /// Matching tag names returned by the last poll
+    seen: BTreeSet<String>,
+    last_checked_at: Option<String>,
+    last_error: Option<String>,
+    /// Newest last
+    detected: VecDeque<DetectedTag>,
+}
+
+impl WatchState {
+    /// Records the matching tags of a poll and returns the new ones, oldest
+    /// first. The first poll of a watch only records the existing tags.
+    fn observe(&mut self, watch: &TagWatch, tags: Vec<GitLabTag>) -> Vec<GitLabTag> {
+        let baseline = baseline_key(watch);
+        let first_poll = self.baseline.as_deref() != Some(baseline.as_str());
+
+        let matching: Vec<GitLabTag> = tags
+            .into_iter()
+            .filter(|t| glob_matches(&watch.pattern, &t.name))
+            .collect();
+        let mut new_tags: Vec<GitLabTag> = if first_poll {
+            Vec::new()
+        } else {
+            matching
+                .iter()
+                .filter(|t| !self.seen.contains(&t.name))
+                .cloned()
+                .collect()
+        };
+        // Tags arrive most recently updated first
+        new_tags.reverse();
+
+        self.baseline = Some(baseline);
+        self.seen = matching.into_iter().map(|t| t.name).collect();
+        new_tags
+    }
+
+    fn record(&mut self, tag: DetectedTag) {
+        self.detected.push_back(tag);
+        while self.detected.len() > MAX_DETECTED {
+            self.detected.pop_front();
+        }
+    }
+
+    fn is_due(&self, watch: &TagWatch, now: DateTime<Utc>) -> bool {
+        let Some(last) = &self.last_checked_at else {
+            return true;
+        };
+        match DateTime::parse_from_rfc3339(last) {
+            Ok(checked_at) => {
+                now.signed_duration_since(checked_at).num_seconds()
+                    >= i64::from(watch.interval_seconds.max(MIN_INTERVAL_SECONDS))
+            }
+            Err(_) => true,
+        }
+    }
+
+    fn status(&self, watch: &TagWatch) -> TagWatchStatus {
+        TagWatchStatus {
+            watch_id: watch.id.clone(),
+            name: watch.name.clone(),
+            last_checked_at: self.last_checked_at.clone(),
+            last_error: self.last_error.clone(),
+            detected: self.detected.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+fn baseline_key(watch: &TagWatch) -> String {
+    format!(
+        "{}/{}/{}",
+        watch.integration_id, watch.project_id, watch.pattern
+    )
+}
+
+/// The running watcher.
+struct Watcher {
+    started_at: String,
+    shutdown: oneshot::Sender<()>,
+}
+
+static WATCHER: LazyLock<Mutex<Option<Watcher>>> = LazyLock::new(|| Mutex::new(None));
+
+/// State per watch ID; `None` until loaded from disk.
+static STATE: LazyLock<Mutex<Option<HashMap<String, WatchState>>>> =
+    LazyLock::new(|| Mutex::new(None));
+
+fn watcher() -> MutexGuard<'static, Option<Watcher>> {
+    WATCHER.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Runs `f` on the state, loading it from disk on first use.
+fn with_state<R>(app: &AppHandle, f: impl FnOnce(&mut HashMap<String, WatchState>) -> R) -> R {
+    let mut state = STATE.lock().unwrap_or_else(PoisonError::into_inner);
+    let state = state.get_or_insert_with(|| {
+        load_state(app).unwrap_or_else(|e| {
+            log::warn!("Failed to load tag watch state, starting empty: {e}");
+            HashMap::new()
+        })
+    });
+    f(state)
+}
+
+/// Starts the watcher. Fails if it is already running.
+pub fn start(app: &AppHandle) -> Result<(), String> {
+    let mut watcher = watcher();
+    if watcher.is_some() {
+        return Err("Tag watcher is already running".to_string());
+    }
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    *watcher = Some(Watcher {
+        started_at: Utc::now().to_rfc3339(),
+        shutdown: shutdown_tx,
+    });
+
+    log::info!("Tag watcher started");
+    tauri::async_runtime::spawn(watch_loop(app.clone(), shutdown_rx));
+    Ok(())
+}
+
+/// Stops the watcher if it is running.
+pub fn stop() {
+    if let Some(running) = watcher().take() {
+        let _ = running.shutdown.send(());
+        log::info!("Tag watcher stopped");
+    }
+}
+
+/// Returns the watcher state with the detected tags of every watch.
+pub async fn status(app: &AppHandle) -> Result<TagWatcherStatus, String> {
+    let watches = load_tag_watches(app.clone()).await?;
+    let started_at = watcher().as_ref().map(|w| w.started_at.clone());
+
+    let watches = with_state(app, |state| {
+        watches
+            .iter()
+            .map(|watch| {
+                state
+                    .get(&watch.id)
+                    .cloned()
+                    .unwrap_or_default()
+                    .status(watch)
+            })
+            .collect()
+    });
+
+    Ok(TagWatcherStatus {
+        running: started_at.is_some(),
+        started_at,
+        watches,
+    })
+}
+
+async fn watch_loop(app: AppHandle, mut shutdown: oneshot::Receiver<()>) {
+    loop {
+        match load_tag_watches(app.clone()).await {
+            Ok(watches) => poll_due_watches(&app, watches).await,
+            Err(e) => log::warn!("Failed to load tag watches: {e}"),
+        }
+
+        tokio::select! {
+            _ = &mut shutdown => break,
+            _ = tokio::time::sleep(TICK_INTERVAL) => {}
+        }
+    }
+}
+
+/// Polls every watch whose interval has elapsed.
+async fn poll_due_watches(app: &AppHandle, watches: Vec<TagWatch>) {
+    let now = Utc::now();
+    let due: Vec<TagWatch> = with_state(app, |state| {
+        watches
+            .into_iter()
+            .filter(|w| state.get(&w.id).is_none_or(|s| s.is_due(w, now)))
+            .collect()
+    });
+    if due.is_empty() {
+        return;
+    }
+
+    for watch in due {
+        let result = task_pool::run(
+            Priority::Poller,
+            &watch.integration_id,
+            fetch_tags(app, &watch),
+        )
+        .await;
+        metrics::record_poll("tag_watch", result.is_ok());
+        let checked_at = Utc::now().to_rfc3339();
+        let new_tags = with_state(app, |state| {
+            let entry = state.entry(watch.id.clone()).or_default();
+            entry.last_checked_at = Some(checked_at);
+            match result {
+                Ok(tags) => {
+                    entry.last_error = None;
+                    entry.observe(&watch, tags)
+                }
+                Err(e) => {
+                    log::warn!("Tag watch {} failed: {e}", watch.name);
+                    entry.last_error = Some(e);
+                    Vec::new()
+                }
+            }
+        });
+
+        for tag in new_tags {
+            let detected = report(app, &watch, &tag).await;
+            with_state(app, |state| {
+                state.entry(watch.id.clone()).or_default().record(detected);
+            });
+        }
+    }
+
+    if let Err(e) = save_state(app) {
+        log::warn!("Failed to save tag watch state: {e}");
+    }
+}
+
+async fn fetch_tags(app: &AppHandle, watch: &TagWatch) -> Result<Vec<GitLabTag>, String> {
+    let adapter = gitlab_adapter(app, &watch.integration_id).await?;
+    // The literal start of the pattern narrows the search on the server
+    let prefix = watch.pattern.split('*').next().filter(|p| !p.is_empty());
+    adapter
+        .fetch_tags(watch.project_id, prefix)
+        .await
+        .map_err(|e| format!("Failed to fetch tags: {e}"))
+}
+
+/// Emits, notifies and starts flows for a new tag.
+async fn report(app: &AppHandle, watch: &TagWatch, tag: &GitLabTag) -> DetectedTag {
+    log::info!(
+        "Tag watch {} found new tag {} ({})",
+        watch.name,
+        tag.name,
+        tag.commit.id
+    );
+
+    let mut run_ids = Vec::new();
+    if watch.trigger_flows {
+        match read_saved_flows(app) {
+            Ok(flows) => {
+                let event = tag_push_event(watch, tag);
+                run_ids = flow_engine::trigger_flows(app, &flows, &event)
+                    .into_iter()
+                    .map(|run| run.id)
+                    .collect();
+            }
+            Err(e) => log::error!("Failed to load flows for tag {}: {e}", tag.name),
+        }
+    }
+
+    let detected = DetectedTag {
+        watch_id: watch.id.clone(),
+        project_id: watch.project_id,
+        tag: tag.name.clone(),
+        commit_sha: tag.commit.id.clone(),
+        detected_at: Utc::now().to_rfc3339(),
+        run_ids,
+    };
+
+    windows::broadcast(app, TAG_DETECTED_EVENT, &detected);
+    if watch.notify {
+        let mut body = watch.name.clone();
+        // Lightweight tags have no creation time of their own
+        if let Some(created_at) = tag.created_at.as_ref().or(tag.commit.created_at.as_ref()) {
+            body = format!("{body}, tagged {}", created_at.relative_to(Utc::now()));
+        }
+        if !detected.run_ids.is_empty() {
+            body = format!("{body} (started {} flow run(s))", detected.run_ids.len());
+        }
+        if let Err(e) =
+            send_native_notification(app.clone(), format!("New tag {}", tag.name), Some(body)).await
+        {
+            log::warn!("Failed to send tag notification: {e}");
+        }
+    }
+    detected
+}
+
+/// Builds the event a GitLab `tag_push` webhook would deliver for the tag.
+fn tag_push_event(watch: &TagWatch, tag: &GitLabTag) -> WebhookEvent {
+    WebhookEvent {
+        source: "gitlab".to_string(),
+        event: "tag_push".to_string(),
+        payload: json!({
+            "object_kind": "tag_push",
+            "ref": format!("refs/tags/{}", tag.name),
+            "checkout_sha": tag.commit.id,
+            "message": tag.message,
+            "project_id": watch.project_id,
+            "project": { "id": watch.project_id },
+            "tag_watch": watch.id,
+        }),
+    }
+}
+
+fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    Ok(app_data_dir.join("tag_watch_state.json"))
+}
+
+fn load_state(app: &AppHandle) -> Result<HashMap<String, WatchState>, String> {
+    let path = state_path(app)?;
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let contents =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read state: {e}"))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse state: {e}"))
+}
+
+fn save_state(app: &AppHandle) -> Result<(), String> {
+    let path = state_path(app)?;
+    let json = with_state(app, |state| serde_json::to_string(state))
+        .map_err(|e| format!("Failed to serialize state: {e}"))?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {e}"))?;
+    }
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json).map_err(|e| format!("Failed to write state: {e}"))?;
+    if let Err(rename_err) = std::fs::rename(&temp_path, &path) {
+        if let Err(remove_err) = std::fs::remove_file(&temp_path) {
+            log::warn!("Failed to remove temp file after rename failure: {remove_err}");
+        }
+        return Err(format!("Failed to finalize state: {rename_err}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::gitlab::GitLabTagCommit;
+
+    fn watch(pattern: &str) -> TagWatch {
+        TagWatch {
+            id: "api-releases".to_string(),
+            name: "API releases".to_string(),
+            integration_id: "gitlab".to_string(),
+            project_id: 42,
+            pattern: pattern.to_string(),
+            interval_seconds: 300,
+            notify: true,
+            trigger_flows: true,
+        }
+    }
+
+    fn tags(names: &[&str]) -> Vec<GitLabTag> {
+        names
+            .iter()
+            .map(|name| GitLabTag {
+                name: name.to_string(),
+                message: None,
+                commit: GitLabTagCommit {
+                    id: format!("sha-{name}"),
+                    title: None,
+                    created_at: None,
+                },
+                created_at: None,
+            })
+            .collect()
+    }
+
+    fn names(tags: &[GitLabTag]) -> Vec<&str> {
+        tags.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_observe() {
+        let mut state = WatchState::default();
+        let v = watch("v*");
+
+        // The first poll only records existing tags
+        assert!(state.observe(&v, tags(&["v1.0.0", "nightly"])).is_empty());
+        assert_eq!(
+            names(&state.observe(&v, tags(&["v1.2.0", "v1.1.0", "v1.0.0", "nightly-2"]))),
+            ["v1.1.0", "v1.2.0"]
+        );
+        assert!(state.observe(&v, tags(&["v1.2.0", "v1.1.0"])).is_empty());
+
+        // A changed pattern starts over
+        assert!(state
+            .observe(&watch("release-*"), tags(&["release-1"]))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_tag_push_event_matches_webhook_trigger() {
+        let event = tag_push_event(&watch("v*"), &tags(&["v1.4.2"])[0]);
+        assert_eq!(event.payload["ref"], "refs/tags/v1.4.2");
+        assert_eq!(event.payload["checkout_sha"], "sha-v1.4.2");
+        assert_eq!(event.event, "tag_push");
+    }
+}
@@ -0,0 +1,294 @@
+//! Prioritized pool for background work.
+//!
+//! Background tasks wait for one of `WORKERS` slots before they run. Pollers
+//! go before analytics (scheduled reports and other bulk work), and within a
+//! priority the key (usually an integration ID) with the fewest running tasks
+//! goes first, so one busy integration can't hold every slot. While a
+//! user-initiated command runs (see `CommandTimer`), pollers are held to
+//! `BUSY_WORKERS` slots and analytics wait, so background sync never slows
+//! down a click.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use tokio::sync::oneshot;
+
+/// Background tasks running at once.
+const WORKERS: usize = 6;
+
+/// Slots analytics may fill, keeping the rest free for pollers.
+const ANALYTICS_WORKERS: usize = 4;
+
+/// Slots pollers may fill while user-initiated commands run.
+const BUSY_WORKERS: usize = 2;
+
+/// Tasks of one key running at once.
+const MAX_PER_KEY: usize = 2;
+
+/// Urgency of background work, most urgent first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Scheduled polling (tag watches, saved filters, certificates, ...)
+    Poller,
+    /// Bulk work whose results nobody waits for (scheduled reports, ...)
+    Analytics,
+}
+
+/// Current load of the pool.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct TaskPoolStatus {
+    /// Background tasks running
+    pub running: u32,
+    /// Pollers waiting for a slot
+    pub queued_pollers: u32,
+    /// Analytics tasks waiting for a slot
+    pub queued_analytics: u32,
+    /// User-initiated commands running
+    pub interactive: u32,
+}
+
+struct Waiter {
+    priority: Priority,
+    key: String,
+    ready: oneshot::Sender<()>,
+}
+
+#[derive(Default)]
+struct Pool {
+    running: usize,
+    running_by_key: HashMap<String, usize>,
+    interactive: usize,
+    /// Waiting tasks, oldest first
+    queue: VecDeque<Waiter>,
+}
+
+impl Pool {
+    /// Slots tasks of a priority may fill right now.
+    fn limit(&self, priority: Priority) -> usize {
+        match (priority, self.interactive > 0) {
+            (Priority::Poller, false) => WORKERS,
+            (Priority::Poller, true) => BUSY_WORKERS,
+            (Priority::Analytics, false) => ANALYTICS_WORKERS,
+            (Priority::Analytics, true) => 0,
+        }
+    }
+
+    /// Index of the waiter to admit next: the most urgent priority with a
+    /// free slot, then the key with the fewest running tasks, then the oldest.
+    fn next(&self) -> Option<usize> {
+        self.queue
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| self.running < self.limit(w.priority))
+            .map(|(index, w)| (index, w, self.running_on(&w.key)))
+            .filter(|(_, _, running)| *running < MAX_PER_KEY)
+            .min_by_key(|(index, w, running)| (w.priority, *running, *index))
+            .map(|(index, _, _)| index)
+    }
+
+    fn running_on(&self, key: &str) -> usize {
+        self.running_by_key.get(key).copied().unwrap_or(0)
+    }
+
+    /// Starts waiting tasks while slots are free.
+    fn dispatch(&mut self) {
+        self.queue.retain(|w| !w.ready.is_closed());
+        while let Some(index) = self.next() {
+            let Some(waiter) = self.queue.remove(index) else {
+                break;
+            };
+            // The task may have been dropped while waiting
+            if waiter.ready.send(()).is_ok() {
+                self.running += 1;
+                *self.running_by_key.entry(waiter.key).or_default() += 1;
+            }
+        }
+    }
+
+    fn release(&mut self, key: &str) {
+        self.running = self.running.saturating_sub(1);
+        if let Some(count) = self.running_by_key.get_mut(key) {
+            *count -= 1;
+            if *count == 0 {
+                self.running_by_key.remove(key);
+            }
+        }
+    }
+}
+
+static POOL: LazyLock<Mutex<Pool>> = LazyLock::new(|| Mutex::new(Pool::default()));
+
+fn pool() -> MutexGuard<'static, Pool> {
+    POOL.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A place in the queue, then a slot of the pool once admitted. Freed when
+/// dropped, even if the task is dropped while waiting.
+struct Slot {
+    key: String,
+    /// Admission signal while still waiting
+    waiting: Option<oneshot::Receiver<()>>,
+}
+
+impl Slot {
+    async fn admitted(&mut self) {
+        if let Some(waiting) = self.waiting.as_mut() {
+            // The sender is only dropped unsent when the pool discards the
+            // waiter, which it doesn't while this receiver is open
+            let _ = waiting.await;
+            self.waiting = None;
+        }
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        let held = match self.waiting.as_mut() {
+            None => true,
+            Some(waiting) => {
+                waiting.close();
+                waiting.try_recv().is_ok()
+            }
+        };
+        let mut pool = pool();
+        if held {
+            pool.release(&self.key);
+        }
+        pool.dispatch();
+    }
+}
+
+/// Runs `task` once a slot is free for its priority and key.
+pub async fn run<F: Future>(priority: Priority, key: &str, task: F) -> F::Output {
+    let (ready, waiting) = oneshot::channel();
+    let mut slot = Slot {
+        key: key.to_string(),
+        waiting: Some(waiting),
+    };
+    {
+        let mut pool = pool();
+        pool.queue.push_back(Waiter {
+            priority,
+            key: key.to_string(),
+            ready,
+        });
+        pool.dispatch();
+    }
+    slot.admitted().await;
+    task.await
+}
+
+/// Marks a user-initiated command as running until dropped.
+pub struct InteractiveGuard(());
+
+impl InteractiveGuard {
+    pub fn start() -> Self {
+        pool().interactive += 1;
+        Self(())
+    }
+}
+
+impl Drop for InteractiveGuard {
+    fn drop(&mut self) {
+        let mut pool = pool();
+        pool.interactive = pool.interactive.saturating_sub(1);
+        pool.dispatch();
+    }
+}
+
+/// Returns the current load of the pool.
+pub fn status() -> TaskPoolStatus {
+    let pool = pool();
+    let queued =
+        |priority: Priority| pool.queue.iter().filter(|w| w.priority == priority).count() as u32;
+    TaskPoolStatus {
+        running: pool.running as u32,
+        queued_pollers: queued(Priority::Poller),
+        queued_analytics: queued(Priority::Analytics),
+        interactive: pool.interactive as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enqueue(pool: &mut Pool, priority: Priority, key: &str) -> oneshot::Receiver<()> {
+        let (ready, admitted) = oneshot::channel();
+        pool.queue.push_back(Waiter {
+            priority,
+            key: key.to_string(),
+            ready,
+        });
+        admitted
+    }
+
+    fn admitted(receiver: &mut oneshot::Receiver<()>) -> bool {
+        receiver.try_recv().is_ok()
+    }
+
+    #[test]
+    fn test_priority_and_fairness() {
+        let mut pool = Pool::default();
+        let mut analytics = enqueue(&mut pool, Priority::Analytics, "reports");
+        let mut busy: Vec<_> = (0..4)
+            .map(|_| enqueue(&mut pool, Priority::Poller, "gitlab"))
+            .collect();
+        let mut jenkins = enqueue(&mut pool, Priority::Poller, "jenkins");
+        pool.dispatch();
+
+        // Two per key, pollers first, then analytics up to its limit
+        assert_eq!(busy.iter_mut().filter_map(|r| r.try_recv().ok()).count(), 2);
+        assert!(admitted(&mut jenkins));
+        assert!(admitted(&mut analytics));
+        assert_eq!(pool.running, 4);
+        assert_eq!(pool.queue.len(), 2);
+
+        pool.release("gitlab");
+        pool.dispatch();
+        assert_eq!(busy.iter_mut().filter_map(|r| r.try_recv().ok()).count(), 1);
+        assert_eq!(pool.queue.len(), 1);
+    }
+
+    #[test]
+    fn test_interactive_holds_background_work() {
+        let mut pool = Pool {
+            interactive: 1,
+            ..Pool::default()
+        };
+        let mut analytics = enqueue(&mut pool, Priority::Analytics, "reports");
+        let mut pollers: Vec<_> = ["a", "b", "c"]
+            .iter()
+            .map(|key| enqueue(&mut pool, Priority::Poller, key))
+            .collect();
+        pool.dispatch();
+
+        assert_eq!(
+            pollers.iter_mut().filter_map(|r| r.try_recv().ok()).count(),
+            2
+        );
+        assert!(!admitted(&mut analytics));
+
+        pool.interactive = 0;
+        pool.dispatch();
+        assert_eq!(
+            pollers.iter_mut().filter_map(|r| r.try_recv().ok()).count(),
+            1
+        );
+        assert!(admitted(&mut analytics));
+    }
+
+    #[test]
+    fn test_dropped_waiters_are_skipped() {
+        let mut pool = Pool::default();
+        drop(enqueue(&mut pool, Priority::Poller, "gitlab"));
+        let mut waiting = enqueue(&mut pool, Priority::Poller, "gitlab");
+        pool.dispatch();
+
+        assert!(admitted(&mut waiting));
+        assert_eq!(pool.running, 1);
+        assert!(pool.queue.is_empty());
+    }
+}
@@ -1,8 +1,10 @@
 //! Shared types and validation functions for the Tauri application.
 
 mod project;
+mod timestamp;
 
 pub use project::*;
+pub use timestamp::*;
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -37,6 +39,77 @@ pub struct AppPreferences {
     /// User's preferred language (e.g., "en", "es", "de")
     /// If None, uses system locale detection
     pub language: Option<String>,
+    /// Minutes without activity before the app locks (requires a lock passphrase)
+    /// If None, the app never locks automatically
+    pub app_lock_idle_minutes: Option<u32>,
+    /// Local port serving internal metrics in the Prometheus text format
+    /// If None, the metrics endpoint is disabled
+    pub metrics_port: Option<u16>,
+    /// Release channel to update from
+    /// If None, the stable channel is used
+    pub update_channel: Option<UpdateChannel>,
+    /// Days a release must have been published before it is offered
+    /// If None, releases are offered as soon as they are published
+    pub update_defer_days: Option<u32>,
+    /// Local hours during which updates are installed without asking
+    /// If None, available updates are only announced
+    pub update_install_window: Option<UpdateInstallWindow>,
+    /// Experimental subsystems switched on or off
+    /// If None, every flag has its default
+    pub feature_flags: Option<FeatureFlags>,
+}
+
+/// Switches for experimental subsystems, so they can ship turned off and be
+/// enabled per user. Flags missing from the preferences take their default.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(default)]
+pub struct FeatureFlags {
+    /// Kubernetes watch streams (the event recorder)
+    pub watch_streams: bool,
+    /// Local webhook receiver
+    pub webhook_receiver: bool,
+    /// Flow runs, however they are started
+    pub flow_engine: bool,
+}
+
+impl Default for FeatureFlags {
+    fn default() -> Self {
+        Self {
+            watch_streams: true,
+            webhook_receiver: true,
+            flow_engine: true,
+        }
+    }
+}
+
+/// Release channel the app updates from.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+/// Daily window of local hours, e.g. 22 to 6 for overnight.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct UpdateInstallWindow {
+    /// First hour of the window (0-23)
+    pub start_hour: u8,
+    /// Hour the window ends (0-23, exclusive)
+    pub end_hour: u8,
+}
+
+impl UpdateInstallWindow {
+    /// Whether the given local hour falls inside the window.
+    pub fn contains(&self, hour: u32) -> bool {
+        let (start, end) = (u32::from(self.start_hour), u32::from(self.end_hour));
+        if start <= end {
+            (start..end).contains(&hour)
+        } else {
+            hour >= start || hour < end
+        }
+    }
 }
 
 impl Default for AppPreferences {
@@ -45,6 +118,12 @@ impl Default for AppPreferences {
             theme: "system".to_string(),
             quick_pane_shortcut: None, // None means use default
             language: None,            // None means use system locale
+            app_lock_idle_minutes: None,
+            metrics_port: None,
+            update_channel: None,
+            update_defer_days: None,
+            update_install_window: None,
+            feature_flags: None,
         }
     }
 }
@@ -117,6 +196,17 @@ pub fn validate_string_input(input: &str, max_len: usize, field_name: &str) -> R
     Ok(())
 }
 
+/// Validates an update install window.
+pub fn validate_update_install_window(window: &UpdateInstallWindow) -> Result<(), String> {
+    if window.start_hour > 23 || window.end_hour > 23 {
+        return Err("Invalid update window: hours must be between 0 and 23".to_string());
+    }
+    if window.start_hour == window.end_hour {
+        return Err("Invalid update window: start and end hours must differ".to_string());
+    }
+    Ok(())
+}
+
 /// Validates theme value.
 pub fn validate_theme(theme: &str) -> Result<(), String> {
     match theme {
@@ -4,6 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::BTreeMap;
 
 // ============================================================================
 // Project
@@ -20,6 +21,10 @@ pub struct Project {
     pub description: Option<String>,
     /// List of environment IDs associated with this project
     pub environments: Vec<String>,
+    /// Team owning the project, used for every service without an owner of
+    /// its own
+    #[serde(default)]
+    pub ownership: Ownership,
 }
 
 // ============================================================================
@@ -37,6 +42,10 @@ pub struct Environment {
     pub namespace: Option<String>,
     /// ID of the project this environment belongs to
     pub project_id: String,
+    /// Non-secret settings (e.g., domain, replica defaults, image registry),
+    /// available to flows run in this environment as `{{ env.<key> }}`
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
 }
 
 // ============================================================================
@@ -52,6 +61,8 @@ pub enum IntegrationType {
     Kubernetes,
     SonarQube,
     Keycloak,
+    /// Built-in synthetic data, for evaluating the app without credentials
+    Demo,
 }
 
 /// Integration configuration (does not contain credentials)
@@ -69,6 +80,9 @@ pub struct Integration {
     /// Reference to credentials stored in OS keyring
     /// This is the key used to retrieve credentials from keyring
     pub credentials_ref: Option<String>,
+    /// Remote API version to assume (e.g., "13.12") instead of detecting it
+    /// For self-hosted instances that can't report their version
+    pub api_version: Option<String>,
 }
 
 // ============================================================================
@@ -93,6 +107,444 @@ pub struct Mapping {
     pub project_id: Option<String>,
     /// Environment ID this mapping belongs to
     pub environment_id: Option<String>,
+    /// Owner of the mapped service; unset fields fall back to the project's
+    #[serde(default)]
+    pub ownership: Ownership,
+}
+
+// ============================================================================
+// Ownership
+// ============================================================================
+
+/// Who owns a project or service and how to reach them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct Ownership {
+    /// Owning team (e.g., "checkout")
+    pub owner_team: Option<String>,
+    /// Slack channel notifications go to (e.g., "#checkout-alerts")
+    pub slack_channel: Option<String>,
+    /// On-call rotation paged for incidents
+    pub on_call: Option<OnCallRotation>,
+}
+
+impl Ownership {
+    /// Whether no field is set.
+    pub fn is_empty(&self) -> bool {
+        self.owner_team.is_none() && self.slack_channel.is_none() && self.on_call.is_none()
+    }
+
+    /// Fills the unset fields from `fallback`.
+    pub fn or(self, fallback: &Ownership) -> Ownership {
+        Ownership {
+            owner_team: self.owner_team.or_else(|| fallback.owner_team.clone()),
+            slack_channel: self
+                .slack_channel
+                .or_else(|| fallback.slack_channel.clone()),
+            on_call: self.on_call.or_else(|| fallback.on_call.clone()),
+        }
+    }
+}
+
+/// Reference to an on-call rotation in a paging service.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct OnCallRotation {
+    pub provider: OnCallProvider,
+    /// Schedule or rotation ID in the provider (e.g., "PD12345")
+    pub rotation_id: String,
+}
+
+/// Paging service an on-call rotation lives in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OnCallProvider {
+    PagerDuty,
+    Opsgenie,
+    Other,
+}
+
+// ============================================================================
+// Smoke Test
+// ============================================================================
+
+/// HTTP check run against an environment after a deployment.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SmokeTest {
+    /// Unique identifier for the smoke test
+    pub id: String,
+    /// Environment ID this check belongs to
+    pub environment_id: String,
+    /// Human-readable check name
+    pub name: String,
+    /// URL to request with GET
+    pub url: String,
+    /// Expected HTTP status code
+    #[serde(default = "default_expected_status")]
+    pub expected_status: u16,
+    /// Text the response body must contain (if any)
+    pub expected_body: Option<String>,
+    /// Timeout per attempt in seconds
+    #[serde(default = "default_smoke_test_timeout")]
+    pub timeout_seconds: u32,
+    /// Additional attempts after a failed one
+    #[serde(default)]
+    pub retries: u32,
+    /// Request headers (e.g., "Authorization"); values may be secret
+    /// references such as "keyring:smoke-token"
+    #[serde(default)]
+    pub headers: std::collections::BTreeMap<String, String>,
+}
+
+// ============================================================================
+// Uptime Target
+// ============================================================================
+
+/// Environment URL probed on a schedule by the uptime monitor.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct UptimeTarget {
+    /// Unique identifier for the target
+    pub id: String,
+    /// Environment ID this URL belongs to
+    pub environment_id: Option<String>,
+    /// Human-readable target name
+    pub name: String,
+    /// URL to request with GET
+    pub url: String,
+    /// Seconds between checks
+    #[serde(default = "default_uptime_interval")]
+    pub interval_seconds: u32,
+    /// Timeout per check in seconds
+    #[serde(default = "default_smoke_test_timeout")]
+    pub timeout_seconds: u32,
+    /// Status that counts as up; any status below 400 when not set
+    pub expected_status: Option<u16>,
+}
+
+fn default_uptime_interval() -> u32 {
+    60
+}
+
+fn default_expected_status() -> u16 {
+    200
+}
+
+fn default_smoke_test_timeout() -> u32 {
+    10
+}
+
+// ============================================================================
+// Quick Pane Macros
+// ============================================================================
+
+/// Action run from the quick pane. Each action runs as a `quick_action` flow node.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum QuickAction {
+    /// Trigger a Jenkins job
+    #[serde(rename_all = "camelCase")]
+    TriggerJenkinsBuild {
+        integration_id: String,
+        job_name: String,
+        #[serde(default)]
+        parameters: std::collections::HashMap<String, String>,
+    },
+    /// Trigger a GitLab pipeline on a ref
+    #[serde(rename_all = "camelCase")]
+    TriggerGitlabPipeline {
+        integration_id: String,
+        project_id: u32,
+        #[serde(rename = "ref")]
+        ref_name: String,
+    },
+    /// Run the smoke tests of an environment
+    #[serde(rename_all = "camelCase")]
+    RunSmokeTests { environment_id: String },
+    /// Send a native notification
+    Notify { title: String, body: Option<String> },
+}
+
+/// Named sequence of quick actions recorded in the quick pane.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct QuickPaneMacro {
+    /// Unique identifier for the macro
+    pub id: String,
+    /// Human-readable macro name
+    pub name: String,
+    /// Actions replayed in order
+    pub steps: Vec<QuickAction>,
+    /// Creation timestamp (RFC 3339)
+    pub created_at: String,
+}
+
+// ============================================================================
+// Cost Rates
+// ============================================================================
+
+/// Hourly prices used to estimate Kubernetes costs from resource requests.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct CostRate {
+    /// Unique identifier for the rate
+    pub id: String,
+    /// Environment this rate applies to; the default rate when not set
+    pub environment_id: Option<String>,
+    /// Price of one requested CPU core per hour
+    pub cpu_hour_price: f64,
+    /// Price of one requested GiB of memory per hour
+    pub memory_gb_hour_price: f64,
+    /// Currency code (e.g., "USD", "EUR")
+    #[serde(default = "default_currency")]
+    pub currency: String,
+}
+
+fn default_currency() -> String {
+    "USD".to_string()
+}
+
+// ============================================================================
+// Image Scanners
+// ============================================================================
+
+/// Harbor instance whose vulnerability scans gate deployments of its images.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ImageScanner {
+    /// Unique identifier for the scanner
+    pub id: String,
+    /// Human-readable scanner name
+    pub name: String,
+    /// Harbor URL (e.g., "https://harbor.example.com")
+    pub base_url: String,
+    /// Registry hosts whose images are checked; the host of `base_url` when empty
+    #[serde(default)]
+    pub registry_hosts: Vec<String>,
+    /// Keyring entry holding the scanner credentials (defaults to the scanner ID)
+    /// Anonymous access is used when no credentials are stored
+    pub credentials_ref: Option<String>,
+    /// Critical vulnerabilities allowed before deploys are blocked
+    #[serde(default)]
+    pub max_critical: u32,
+    /// Block deploys of images without a successful scan
+    #[serde(default)]
+    pub block_unscanned: bool,
+}
+
+// ============================================================================
+// Desired State
+// ============================================================================
+
+/// What should be running in an environment's namespace.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct DesiredState {
+    /// Environment whose namespace is compared
+    pub environment_id: String,
+    /// Kubernetes integration of the cluster running the environment
+    pub integration_id: String,
+    /// Expected services; deployments not listed are ignored
+    #[serde(default)]
+    pub services: Vec<DesiredService>,
+}
+
+/// Expected version of a deployed service. Unset fields are not compared.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct DesiredService {
+    /// Deployment name
+    pub name: String,
+    /// Container to compare; the first container when not set
+    pub container: Option<String>,
+    /// Image without its tag (e.g., "registry.example.com/team/api")
+    pub image: Option<String>,
+    /// Image tag (e.g., "1.4.2")
+    pub tag: Option<String>,
+    /// Helm chart version, from the deployment's `helm.sh/chart` label
+    pub chart_version: Option<String>,
+}
+
+// ============================================================================
+// Tag Watches
+// ============================================================================
+
+/// GitLab project polled for new tags matching a pattern.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct TagWatch {
+    /// Unique identifier for the watch
+    pub id: String,
+    /// Human-readable watch name
+    pub name: String,
+    /// GitLab integration to poll
+    pub integration_id: String,
+    pub project_id: u32,
+    /// Tag name pattern; `*` matches any characters (e.g., "v*")
+    #[serde(default = "default_tag_pattern")]
+    pub pattern: String,
+    /// Seconds between polls
+    #[serde(default = "default_tag_watch_interval")]
+    pub interval_seconds: u32,
+    /// Send a native notification for new tags
+    #[serde(default = "default_true")]
+    pub notify: bool,
+    /// Start flows whose webhook trigger matches a GitLab `tag_push` event
+    #[serde(default = "default_true")]
+    pub trigger_flows: bool,
+}
+
+fn default_tag_pattern() -> String {
+    "*".to_string()
+}
+
+fn default_tag_watch_interval() -> u32 {
+    300
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// ============================================================================
+// Event Recordings
+// ============================================================================
+
+/// Kubernetes namespaces whose events are recorded for later queries.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct EventRecording {
+    /// Unique identifier for the recording
+    pub id: String,
+    /// Human-readable recording name
+    pub name: String,
+    /// Kubernetes integration to watch
+    pub integration_id: String,
+    pub namespaces: Vec<String>,
+    /// Days recorded events are kept
+    #[serde(default = "default_event_retention")]
+    pub retention_days: u32,
+    /// Record events; disabled recordings keep their history until it expires
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_event_retention() -> u32 {
+    14
+}
+
+// ============================================================================
+// Failure Rules
+// ============================================================================
+
+/// Log pattern that classifies a failed build.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FailureRule {
+    /// Unique identifier for the rule
+    pub id: String,
+    /// Failure category reported for matching builds (e.g., "out_of_memory")
+    pub category: String,
+    /// Regular expression matched against each log line (e.g., "npm ERR!")
+    pub pattern: String,
+}
+
+// ============================================================================
+// Reports
+// ============================================================================
+
+/// Content of a report.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportKind {
+    /// Projects, environments, their mappings and uptime
+    EnvironmentOverview,
+    /// Certificate expiry and uptime objectives
+    Compliance,
+    /// DORA metrics from GitLab deployments
+    Dora,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Html,
+    /// HTML printed by a headless Chrome, Chromium or Edge
+    Pdf,
+}
+
+/// Report generated on demand or on a schedule.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ReportDefinition {
+    /// Unique identifier for the report
+    pub id: String,
+    /// Report title
+    pub name: String,
+    pub kind: ReportKind,
+    #[serde(default)]
+    pub format: ReportFormat,
+    /// Days of deployments covered by DORA metrics
+    #[serde(default = "default_report_period")]
+    pub period_days: u32,
+    /// GitLab integration whose deployments feed DORA metrics
+    pub integration_id: Option<String>,
+    /// Hours between scheduled generations; only generated on demand when not set
+    pub interval_hours: Option<u32>,
+}
+
+fn default_report_period() -> u32 {
+    30
+}
+
+// ============================================================================
+// Saved Filters
+// ============================================================================
+
+/// Resources a saved filter selects from.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SavedFilterResource {
+    /// Fields: id, project_id, status, ref, created_at
+    GitlabPipelines,
+    /// Fields: name, color, status
+    JenkinsJobs,
+    /// Fields: name, namespace, status, node, containers
+    K8sPods,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOperator {
+    #[default]
+    Equals,
+    NotEquals,
+    Contains,
+    /// `*` matches any characters
+    Matches,
+    /// Comma-separated list of values
+    OneOf,
+}
+
+/// Condition on one field of a resource.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct FilterCondition {
+    pub field: String,
+    #[serde(default)]
+    pub operator: FilterOperator,
+    pub value: String,
+}
+
+/// Named query over the resources of an integration
+/// (e.g., "failing pipelines in prod projects").
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SavedFilter {
+    /// Unique identifier for the filter
+    pub id: String,
+    /// Human-readable filter name
+    pub name: String,
+    pub resource: SavedFilterResource,
+    pub integration_id: String,
+    /// GitLab project IDs, Jenkins job names or namespaces to read
+    #[serde(default)]
+    pub targets: Vec<String>,
+    /// Also read the projects, jobs or namespace mapped to this environment
+    pub environment_id: Option<String>,
+    /// Conditions that must all match
+    #[serde(default)]
+    pub conditions: Vec<FilterCondition>,
+    /// Re-evaluate in the background and notify when the results change
+    #[serde(default)]
+    pub notify: bool,
 }
 
 // ============================================================================
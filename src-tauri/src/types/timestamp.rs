@@ -0,0 +1,184 @@
+//! Normalized timestamps.
+//!
+//! Integrations report time in different formats: Jenkins uses epoch
+//! milliseconds, GitLab ISO 8601 with an offset, Kubernetes and Keycloak
+//! RFC 3339. Adapters convert them to `Timestamp` so the frontend gets the
+//! same representation everywhere.
+
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, TimeDelta, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use specta::Type;
+use std::fmt;
+
+/// A point in time, normalized to UTC.
+///
+/// Deserializes from RFC 3339 / ISO 8601 strings, epoch milliseconds (number
+/// or string) and its own serialized form, so API response types can use it
+/// directly.
+#[derive(Debug, Clone, Serialize, Type, PartialEq, Eq, Hash)]
+pub struct Timestamp {
+    /// RFC 3339 in UTC with millisecond precision (e.g., "2024-05-01T10:00:00.000Z")
+    pub rfc3339: String,
+    /// Unix timestamp in milliseconds (as string to avoid i64 BigInt issues)
+    pub epoch_millis: String,
+}
+
+impl Timestamp {
+    pub fn from_datetime(time: DateTime<Utc>) -> Self {
+        Self {
+            rfc3339: time.to_rfc3339_opts(SecondsFormat::Millis, true),
+            epoch_millis: time.timestamp_millis().to_string(),
+        }
+    }
+
+    /// Returns None if the value is out of chrono's range.
+    pub fn from_epoch_millis(millis: i64) -> Option<Self> {
+        DateTime::from_timestamp_millis(millis).map(Self::from_datetime)
+    }
+
+    /// Parses RFC 3339 / ISO 8601 timestamps and epoch milliseconds.
+    ///
+    /// Timestamps without an offset (e.g., "2024-05-01 10:00:00 UTC" from
+    /// older GitLab endpoints) are taken as UTC.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Ok(millis) = value.parse::<i64>() {
+            return Self::from_epoch_millis(millis);
+        }
+        if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+            return Some(Self::from_datetime(time.with_timezone(&Utc)));
+        }
+
+        let naive = value.strip_suffix(" UTC").unwrap_or(value);
+        ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(naive, format).ok())
+            .map(|time| Self::from_datetime(time.and_utc()))
+    }
+
+    pub fn datetime(&self) -> DateTime<Utc> {
+        // Both fields are always written from the same DateTime
+        self.epoch_millis
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_millis)
+            .unwrap_or_default()
+    }
+
+    /// Time since this timestamp; negative if it is after `now`.
+    pub fn elapsed(&self, now: DateTime<Utc>) -> TimeDelta {
+        now.signed_duration_since(self.datetime())
+    }
+
+    /// Whole days since this timestamp; negative if it is after `now`.
+    pub fn age_days(&self, now: DateTime<Utc>) -> i32 {
+        let days = self.elapsed(now).num_days();
+        i32::try_from(days).unwrap_or(if days < 0 { i32::MIN } else { i32::MAX })
+    }
+
+    /// Describes this timestamp relative to `now` in its largest whole unit
+    /// (e.g., "just now", "5 minutes ago", "in 2 days").
+    pub fn relative_to(&self, now: DateTime<Utc>) -> String {
+        let seconds = self.elapsed(now).num_seconds();
+        let (amount, unit) = match seconds.unsigned_abs() {
+            s if s < 60 => return "just now".to_string(),
+            s if s < 3_600 => (s / 60, "minute"),
+            s if s < 86_400 => (s / 3_600, "hour"),
+            s if s < 30 * 86_400 => (s / 86_400, "day"),
+            s if s < 365 * 86_400 => (s / (30 * 86_400), "month"),
+            s => (s / (365 * 86_400), "year"),
+        };
+        let plural = if amount == 1 { "" } else { "s" };
+
+        if seconds >= 0 {
+            format!("{amount} {unit}{plural} ago")
+        } else {
+            format!("in {amount} {unit}{plural}")
+        }
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.rfc3339)
+    }
+}
+
+/// Formats `Timestamp` accepts when deserializing.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawTimestamp {
+    Normalized { epoch_millis: String },
+    Millis(i64),
+    Text(String),
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let parsed = match RawTimestamp::deserialize(deserializer)? {
+            RawTimestamp::Normalized { epoch_millis } => Self::parse(&epoch_millis),
+            RawTimestamp::Millis(millis) => Self::from_epoch_millis(millis),
+            RawTimestamp::Text(text) => Self::parse(&text),
+        };
+        parsed.ok_or_else(|| serde::de::Error::custom("invalid timestamp"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_normalizes_formats() {
+        let expected = Timestamp {
+            rfc3339: "2024-05-01T08:00:00.000Z".to_string(),
+            epoch_millis: "1714550400000".to_string(),
+        };
+        for value in [
+            "1714550400000",
+            "2024-05-01T08:00:00Z",
+            "2024-05-01T10:00:00.000+02:00",
+            "2024-05-01 08:00:00 UTC",
+            "2024-05-01T08:00:00",
+        ] {
+            assert_eq!(Timestamp::parse(value).as_ref(), Some(&expected), "{value}");
+        }
+        assert_eq!(Timestamp::parse("yesterday"), None);
+    }
+
+    #[test]
+    fn test_deserialize_round_trips() {
+        let from_millis: Timestamp = serde_json::from_value(json!(1714550400000_i64)).unwrap();
+        let from_text: Timestamp = serde_json::from_value(json!("2024-05-01T08:00:00Z")).unwrap();
+        let round_trip: Timestamp =
+            serde_json::from_value(serde_json::to_value(&from_text).unwrap()).unwrap();
+
+        assert_eq!(from_millis, from_text);
+        assert_eq!(round_trip, from_text);
+        assert!(serde_json::from_value::<Timestamp>(json!("soon")).is_err());
+    }
+
+    #[test]
+    fn test_relative_to() {
+        let now = at("2024-05-10T12:00:00Z");
+        let describe = |rfc3339: &str| Timestamp::from_datetime(at(rfc3339)).relative_to(now);
+
+        assert_eq!(describe("2024-05-10T11:59:30Z"), "just now");
+        assert_eq!(describe("2024-05-10T11:55:00Z"), "5 minutes ago");
+        assert_eq!(describe("2024-05-10T11:00:00Z"), "1 hour ago");
+        assert_eq!(describe("2024-05-01T12:00:00Z"), "9 days ago");
+        assert_eq!(describe("2024-05-12T13:00:00Z"), "in 2 days");
+        assert_eq!(describe("2022-01-01T00:00:00Z"), "2 years ago");
+        assert_eq!(
+            Timestamp::from_datetime(at("2024-05-01T12:00:00Z")).age_days(now),
+            9
+        );
+    }
+}
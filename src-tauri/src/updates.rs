@@ -0,0 +1,357 @@
+//! App updates.
+//!
+//! Releases come from the stable channel (GitHub's latest release) or the
+//! beta channel (the rolling `beta` release), as chosen in the preferences.
+//! While the app runs, the channel is checked every few hours. A release
+//! published less than `update_defer_days` ago is held back; otherwise it is
+//! installed when the local time enters `update_install_window`, or announced
+//! with an `update-available` event and a native notification when no window
+//! is set. The Tauri updater plugin downloads and verifies the bundles.
+
+use chrono::{DateTime, Days, Local, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::notifications::send_native_notification;
+use crate::commands::preferences::read_preferences;
+use crate::metrics;
+use crate::task_pool::{self, Priority};
+use crate::types::{AppPreferences, UpdateChannel};
+use crate::windows;
+
+/// Event emitted with an `UpdateCheck` when a new release can be installed.
+pub const UPDATE_AVAILABLE_EVENT: &str = "update-available";
+
+/// Event emitted with the version once an update is installed (a restart
+/// applies it).
+pub const UPDATE_INSTALLED_EVENT: &str = "update-installed";
+
+const STABLE_ENDPOINT: &str =
+    "https://github.com/hoan02/ops-flow/releases/latest/download/latest.json";
+const BETA_ENDPOINT: &str = "https://github.com/hoan02/ops-flow/releases/download/beta/latest.json";
+
+/// How often the channel is checked in the background.
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// How often the scheduler wakes up to check or to catch the install window.
+const TICK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// A release newer than the running version.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct AvailableUpdate {
+    pub version: String,
+    /// Release notes (Markdown)
+    pub notes: Option<String>,
+    /// Publication timestamp (RFC 3339)
+    pub published_at: Option<String>,
+    /// When the deferral ends (RFC 3339), if the release is still held back
+    pub deferred_until: Option<String>,
+}
+
+/// Result of checking the update channel.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct UpdateCheck {
+    pub channel: UpdateChannel,
+    pub current_version: String,
+    /// Check timestamp (RFC 3339)
+    pub checked_at: String,
+    pub update: Option<AvailableUpdate>,
+}
+
+#[derive(Debug, Default)]
+struct SchedulerState {
+    last_check: Option<DateTime<Utc>>,
+    /// Version already announced, so it is announced once
+    announced: Option<String>,
+    /// Version waiting for the install window
+    pending: Option<String>,
+}
+
+static SCHEDULER: LazyLock<Mutex<SchedulerState>> =
+    LazyLock::new(|| Mutex::new(SchedulerState::default()));
+
+fn scheduler() -> MutexGuard<'static, SchedulerState> {
+    SCHEDULER.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Checks the configured channel for a newer release.
+pub async fn check(app: &AppHandle) -> Result<UpdateCheck, String> {
+    let preferences = read_preferences(app).unwrap_or_default();
+    let channel = preferences.update_channel.unwrap_or_default();
+    let release = fetch_release(app, channel).await?;
+
+    let now = Utc::now();
+    Ok(UpdateCheck {
+        channel,
+        current_version: app.package_info().version.to_string(),
+        checked_at: now.to_rfc3339(),
+        update: release.map(|release| AvailableUpdate {
+            deferred_until: deferred_until(
+                release.published_at,
+                preferences.update_defer_days,
+                now,
+            )
+            .map(|until| until.to_rfc3339()),
+            version: release.version,
+            notes: release.notes,
+            published_at: release.published_at.map(|at| at.to_rfc3339()),
+        }),
+    })
+}
+
+/// Downloads and installs `version` from the configured channel.
+///
+/// Fails when the channel now offers another release or when the release is
+/// still deferred. Returns the installed version; the app has to restart to
+/// run it.
+pub async fn install(app: &AppHandle, version: &str) -> Result<String, String> {
+    let preferences = read_preferences(app).unwrap_or_default();
+    let channel = preferences.update_channel.unwrap_or_default();
+    let version =
+        download_and_install(app, channel, version, preferences.update_defer_days).await?;
+
+    log::info!("Installed update {version}");
+    windows::broadcast(app, UPDATE_INSTALLED_EVENT, &version);
+    Ok(version)
+}
+
+/// Starts checking for updates in the background.
+pub fn start_scheduled_checks(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut ticks = tokio::time::interval(TICK_INTERVAL);
+        loop {
+            ticks.tick().await;
+            run_scheduled(&app).await;
+        }
+    });
+}
+
+async fn run_scheduled(app: &AppHandle) {
+    let preferences = read_preferences(app).unwrap_or_default();
+    let now = Utc::now();
+
+    let check_due = scheduler()
+        .last_check
+        .is_none_or(|last| (now - last).to_std().is_ok_and(|e| e >= CHECK_INTERVAL));
+    if check_due {
+        scheduler().last_check = Some(now);
+        let result = task_pool::run(Priority::Poller, "update_check", check(app)).await;
+        metrics::record_poll("update_check", result.is_ok());
+        match result {
+            Ok(update_check) => handle_check(app, &preferences, update_check).await,
+            Err(e) => log::warn!("Update check failed: {e}"),
+        }
+    }
+
+    let in_window = preferences
+        .update_install_window
+        .is_some_and(|window| window.contains(Local::now().hour()));
+    let pending = scheduler().pending.clone();
+    if let (true, Some(version)) = (in_window, pending) {
+        log::info!("Installing update {version} in the install window");
+        let result = install(app, &version).await;
+        let mut state = scheduler();
+        state.pending = None;
+        if let Err(e) = result {
+            log::warn!("Scheduled update install failed: {e}");
+            // Check again on the next tick, which queues the release again
+            // if it can still be installed
+            state.last_check = None;
+        }
+    }
+}
+
+/// Queues an installable release for the install window, or announces it.
+async fn handle_check(app: &AppHandle, preferences: &AppPreferences, update_check: UpdateCheck) {
+    let Some(update) = &update_check.update else {
+        scheduler().pending = None;
+        return;
+    };
+    if let Some(until) = &update.deferred_until {
+        log::debug!("Update {} deferred until {until}", update.version);
+        // A release queued earlier is superseded by this one
+        scheduler().pending = None;
+        return;
+    }
+    if preferences.update_install_window.is_some() {
+        scheduler().pending = Some(update.version.clone());
+        return;
+    }
+    if scheduler().announced.as_deref() == Some(update.version.as_str()) {
+        return;
+    }
+    scheduler().announced = Some(update.version.clone());
+
+    log::info!("Update {} is available", update.version);
+    windows::broadcast(app, UPDATE_AVAILABLE_EVENT, &update_check);
+    let title = format!("Ops Flow {} is available", update.version);
+    if let Err(e) = send_native_notification(app.clone(), title, None).await {
+        log::warn!("Failed to notify about update: {e}");
+    }
+}
+
+/// When a release held back for `defer_days` may be installed, if that is
+/// still in the future. Releases without a publication date are not held.
+fn deferred_until(
+    published_at: Option<DateTime<Utc>>,
+    defer_days: Option<u32>,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let until = published_at?.checked_add_days(Days::new(u64::from(defer_days?)))?;
+    (until > now).then_some(until)
+}
+
+/// The parts of a release the policy needs.
+#[cfg_attr(not(desktop), allow(dead_code))]
+struct Release {
+    version: String,
+    notes: Option<String>,
+    published_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(desktop)]
+fn endpoint(channel: UpdateChannel) -> &'static str {
+    match channel {
+        UpdateChannel::Stable => STABLE_ENDPOINT,
+        UpdateChannel::Beta => BETA_ENDPOINT,
+    }
+}
+
+#[cfg(desktop)]
+async fn find_update(
+    app: &AppHandle,
+    channel: UpdateChannel,
+) -> Result<Option<tauri_plugin_updater::Update>, String> {
+    use tauri_plugin_updater::UpdaterExt;
+
+    let url = endpoint(channel)
+        .parse()
+        .map_err(|e| format!("Invalid update endpoint: {e}"))?;
+    app.updater_builder()
+        .endpoints(vec![url])
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("Failed to set up the updater: {e}"))?
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {e}"))
+}
+
+#[cfg(desktop)]
+fn published_at(update: &tauri_plugin_updater::Update) -> Option<DateTime<Utc>> {
+    update
+        .date
+        .as_ref()
+        .and_then(|date| DateTime::from_timestamp(date.unix_timestamp(), 0))
+}
+
+#[cfg(desktop)]
+async fn fetch_release(app: &AppHandle, channel: UpdateChannel) -> Result<Option<Release>, String> {
+    Ok(find_update(app, channel).await?.map(|update| Release {
+        published_at: published_at(&update),
+        version: update.version,
+        notes: update.body,
+    }))
+}
+
+#[cfg(desktop)]
+async fn download_and_install(
+    app: &AppHandle,
+    channel: UpdateChannel,
+    version: &str,
+    defer_days: Option<u32>,
+) -> Result<String, String> {
+    let update = find_update(app, channel)
+        .await?
+        .ok_or_else(|| "No update available".to_string())?;
+    // The updater only offers the newest release, which may be another
+    // (still deferred) one than the release that was checked
+    if update.version != version {
+        return Err(format!(
+            "Update {version} is no longer the newest release (found {})",
+            update.version
+        ));
+    }
+    if let Some(until) = deferred_until(published_at(&update), defer_days, Utc::now()) {
+        return Err(format!(
+            "Update {version} is deferred until {}",
+            until.to_rfc3339()
+        ));
+    }
+    update
+        .download_and_install(
+            |_, _| {},
+            || log::info!("Update {} downloaded", update.version),
+        )
+        .await
+        .map_err(|e| format!("Failed to install update: {e}"))?;
+    Ok(update.version)
+}
+
+#[cfg(not(desktop))]
+const UNSUPPORTED: &str = "In-app updates are not supported on this platform";
+
+#[cfg(not(desktop))]
+async fn fetch_release(
+    _app: &AppHandle,
+    _channel: UpdateChannel,
+) -> Result<Option<Release>, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+#[cfg(not(desktop))]
+async fn download_and_install(
+    _app: &AppHandle,
+    _channel: UpdateChannel,
+    _version: &str,
+    _defer_days: Option<u32>,
+) -> Result<String, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::UpdateInstallWindow;
+
+    #[test]
+    fn test_deferred_until() {
+        let now = DateTime::parse_from_rfc3339("2024-05-10T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let published = DateTime::parse_from_rfc3339("2024-05-08T09:00:00Z")
+            .unwrap()
+            .to_utc();
+
+        assert_eq!(
+            deferred_until(Some(published), Some(3), now).map(|d| d.to_rfc3339()),
+            Some("2024-05-11T09:00:00+00:00".to_string())
+        );
+        assert_eq!(deferred_until(Some(published), Some(2), now), None);
+        assert_eq!(deferred_until(Some(published), None, now), None);
+        assert_eq!(deferred_until(None, Some(3), now), None);
+    }
+
+    #[test]
+    fn test_install_window_contains() {
+        let daytime = UpdateInstallWindow {
+            start_hour: 12,
+            end_hour: 14,
+        };
+        assert!(daytime.contains(12));
+        assert!(daytime.contains(13));
+        assert!(!daytime.contains(14));
+
+        let overnight = UpdateInstallWindow {
+            start_hour: 22,
+            end_hour: 6,
+        };
+        assert!(overnight.contains(23));
+        assert!(overnight.contains(0));
+        assert!(!overnight.contains(6));
+        assert!(!overnight.contains(12));
+    }
+}
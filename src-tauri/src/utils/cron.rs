@@ -0,0 +1,243 @@
+//! Standard five-field cron expressions.
+//!
+//! Supports `*`, values, ranges (`1-5`), lists (`1,15`), steps (`*/15`,
+//! `0-30/10`) and month and weekday names (`jan`, `mon`). As in cron, a day
+//! matches when both day fields match, or either one when both are
+//! restricted.
+
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+/// Days searched for an occurrence before giving up (covers February 29).
+const MAX_SEARCH_DAYS: u64 = 5 * 366;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// A parsed cron expression (e.g., "0 23 * * fri").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    /// Bit 0 is Sunday
+    days_of_week: u64,
+    /// Whether the day-of-month field is `*`
+    any_day_of_month: bool,
+    /// Whether the day-of-week field is `*`
+    any_day_of_week: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(format!(
+                "Cron expression '{expression}' must have 5 fields, found {}",
+                fields.len()
+            ));
+        };
+        let invalid = |e: String| format!("Invalid cron expression '{expression}': {e}");
+
+        let mut days_of_week = parse_field(day_of_week, 0, 7, &WEEKDAYS).map_err(invalid)?;
+        // 7 is another name for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[]).map_err(invalid)?,
+            hours: parse_field(hour, 0, 23, &[]).map_err(invalid)?,
+            days_of_month: parse_field(day_of_month, 1, 31, &[]).map_err(invalid)?,
+            months: parse_field(month, 1, 12, &MONTHS).map_err(invalid)?,
+            days_of_week,
+            any_day_of_month: day_of_month == "*",
+            any_day_of_week: day_of_week == "*",
+        })
+    }
+
+    /// The latest occurrence at or before `time`.
+    pub fn previous(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        let limit = time.time();
+        (0..=MAX_SEARCH_DAYS).find_map(|back| {
+            let date = time.date().checked_sub_days(Days::new(back))?;
+            if !self.matches_day(date) {
+                return None;
+            }
+            let limit = (back == 0).then_some(limit);
+            let occurrence = (0..24u32).rev().find_map(|hour| {
+                if !bit(self.hours, hour) || limit.is_some_and(|l| hour > l.hour()) {
+                    return None;
+                }
+                let same_hour = limit.is_some_and(|l| hour == l.hour());
+                let minute = (0..60u32).rev().find(|minute| {
+                    bit(self.minutes, *minute)
+                        && !(same_hour && limit.is_some_and(|l| *minute > l.minute()))
+                })?;
+                NaiveTime::from_hms_opt(hour, minute, 0)
+            })?;
+            Some(date.and_time(occurrence))
+        })
+    }
+
+    /// The earliest occurrence after `time`.
+    pub fn next(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = time.time();
+        (0..=MAX_SEARCH_DAYS).find_map(|ahead| {
+            let date = time.date().checked_add_days(Days::new(ahead))?;
+            if !self.matches_day(date) {
+                return None;
+            }
+            let start = (ahead == 0).then_some(start);
+            let occurrence = (0..24u32).find_map(|hour| {
+                if !bit(self.hours, hour) || start.is_some_and(|s| hour < s.hour()) {
+                    return None;
+                }
+                let same_hour = start.is_some_and(|s| hour == s.hour());
+                let minute = (0..60u32).find(|minute| {
+                    bit(self.minutes, *minute)
+                        && !(same_hour && start.is_some_and(|s| *minute <= s.minute()))
+                })?;
+                NaiveTime::from_hms_opt(hour, minute, 0)
+            })?;
+            Some(date.and_time(occurrence))
+        })
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        if !bit(self.months, date.month()) {
+            return false;
+        }
+        let day_of_month = bit(self.days_of_month, date.day());
+        let day_of_week = bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (false, false) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+}
+
+fn bit(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parses one field into a bit set of the values it allows.
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let value = |text: &str| -> Result<u32, String> {
+        let lower = text.to_ascii_lowercase();
+        let value = match names.iter().position(|name| *name == lower) {
+            // Months are numbered from 1, weekdays from 0
+            Some(index) => index as u32 + min,
+            None => text
+                .parse()
+                .map_err(|_| format!("'{text}' is not a number"))?,
+        };
+        if !(min..=max).contains(&value) {
+            return Err(format!("{value} is outside {min}-{max}"));
+        }
+        Ok(value)
+    };
+
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("Invalid step in '{part}'"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // A single value with a step runs to the end of the range
+                None if step > 1 => (value(range)?, max),
+                None => {
+                    let single = value(range)?;
+                    (single, single)
+                }
+            },
+        };
+        if start > end {
+            return Err(format!("Invalid range '{range}'"));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(CronSchedule::parse("*/15 9-17 * * mon-fri").is_ok());
+        assert!(CronSchedule::parse("0 0 1,15 jan,jul 7").is_ok());
+        assert!(CronSchedule::parse("0 24 * * *").is_err());
+        assert!(CronSchedule::parse("0 0 * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+    }
+
+    #[test]
+    fn test_previous_and_next() {
+        // Fridays at 23:00
+        let schedule = CronSchedule::parse("0 23 * * 5").unwrap();
+        // 2024-05-01 is a Wednesday
+        assert_eq!(
+            schedule.previous(time("2024-05-01 12:00")),
+            Some(time("2024-04-26 23:00"))
+        );
+        assert_eq!(
+            schedule.next(time("2024-05-01 12:00")),
+            Some(time("2024-05-03 23:00"))
+        );
+        assert_eq!(
+            schedule.previous(time("2024-05-03 23:00")),
+            Some(time("2024-05-03 23:00"))
+        );
+        assert_eq!(
+            schedule.next(time("2024-05-03 23:00")),
+            Some(time("2024-05-10 23:00"))
+        );
+
+        let every_quarter = CronSchedule::parse("*/15 * * * *").unwrap();
+        assert_eq!(
+            every_quarter.previous(time("2024-05-01 12:14")),
+            Some(time("2024-05-01 12:00"))
+        );
+        assert_eq!(
+            every_quarter.next(time("2024-05-01 23:50")),
+            Some(time("2024-05-02 00:00"))
+        );
+    }
+
+    #[test]
+    fn test_day_fields() {
+        // The 1st of the month or any Monday
+        let either = CronSchedule::parse("0 0 1 * mon").unwrap();
+        assert_eq!(
+            either.next(time("2024-05-01 12:00")),
+            Some(time("2024-05-06 00:00"))
+        );
+
+        let leap_day = CronSchedule::parse("0 0 29 feb *").unwrap();
+        assert_eq!(
+            leap_day.previous(time("2027-01-01 00:00")),
+            Some(time("2024-02-29 00:00"))
+        );
+    }
+}
@@ -1,4 +1,6 @@
 //! Utility modules for cross-platform support and common operations.
 
+pub mod credential_checks;
+pub mod cron;
 pub mod http_client;
 pub mod platform;
@@ -97,6 +97,16 @@ pub const fn current_platform() -> &'static str {
     }
 }
 
+/// Returns the name of the OS user running the app, or "unknown".
+///
+/// Reads `USER` on macOS and Linux and `USERNAME` on Windows.
+pub fn current_user() -> String {
+    ["USER", "USERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|user| !user.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
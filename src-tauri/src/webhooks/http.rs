@@ -0,0 +1,206 @@
+//! Minimal HTTP/1.1 request handling for the webhook receiver.
+//!
+//! Webhook senders only need `POST` with a `Content-Length` body and a short
+//! response, so the receiver parses requests itself instead of pulling in a
+//! full HTTP server. The metrics endpoint reuses it for `GET /metrics`.
+
+use std::collections::HashMap;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Largest accepted header block.
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+
+/// Largest accepted request body (GitLab push events can be large).
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// A parsed HTTP request.
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    /// Request path without the query string
+    pub path: String,
+    /// Header names are lowercased
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+}
+
+/// A response status with a short JSON body.
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: serde_json::Value,
+}
+
+impl Response {
+    pub fn new(status: u16, body: serde_json::Value) -> Self {
+        Self { status, body }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::new(status, serde_json::json!({ "error": message }))
+    }
+}
+
+/// Reads one request from the stream.
+pub async fn read_request<S: AsyncRead + Unpin>(stream: &mut S) -> Result<Request, Response> {
+    let mut buffer = Vec::with_capacity(4096);
+    let header_end = loop {
+        if let Some(pos) = find_header_end(&buffer) {
+            break pos;
+        }
+        if buffer.len() > MAX_HEADER_BYTES {
+            return Err(Response::error(431, "Request headers too large"));
+        }
+
+        let mut chunk = [0u8; 4096];
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|_| Response::error(400, "Failed to read request"))?;
+        if read == 0 {
+            return Err(Response::error(400, "Incomplete request"));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = std::str::from_utf8(&buffer[..header_end])
+        .map_err(|_| Response::error(400, "Request headers are not valid UTF-8"))?;
+    let mut lines = head.split("\r\n");
+
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(Response::error(400, "Malformed request line"));
+    };
+    let path = target.split('?').next().unwrap_or_default().to_string();
+
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let content_length = match headers.get("content-length") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| Response::error(400, "Invalid Content-Length"))?,
+        None => 0,
+    };
+    if content_length > MAX_BODY_BYTES {
+        return Err(Response::error(413, "Request body too large"));
+    }
+
+    let mut body = buffer[header_end + 4..].to_vec();
+    while body.len() < content_length {
+        let mut chunk = vec![0u8; (content_length - body.len()).min(64 * 1024)];
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|_| Response::error(400, "Failed to read request body"))?;
+        if read == 0 {
+            return Err(Response::error(400, "Incomplete request body"));
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+
+    Ok(Request {
+        method: method.to_string(),
+        path,
+        headers,
+        body,
+    })
+}
+
+/// Writes a response and closes the exchange.
+pub async fn write_response<S: AsyncWrite + Unpin>(
+    stream: &mut S,
+    response: &Response,
+) -> std::io::Result<()> {
+    write_text(
+        stream,
+        response.status,
+        "application/json",
+        &response.body.to_string(),
+    )
+    .await
+}
+
+/// Writes a response with a plain body and closes the exchange.
+pub async fn write_text<S: AsyncWrite + Unpin>(
+    stream: &mut S,
+    status: u16,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        reason_phrase(status),
+        content_type,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.flush().await
+}
+
+fn find_header_end(buffer: &[u8]) -> Option<usize> {
+    buffer.windows(4).position(|w| w == b"\r\n\r\n")
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_request() {
+        let raw = b"POST /webhooks/gitlab?x=1 HTTP/1.1\r\nHost: localhost\r\nX-Gitlab-Token: s3cret\r\nContent-Length: 14\r\n\r\n{\"ref\":\"main\"}";
+        let request = read_request(&mut &raw[..]).await.unwrap();
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/webhooks/gitlab");
+        assert_eq!(request.header("x-gitlab-token"), Some("s3cret"));
+        assert_eq!(request.body, b"{\"ref\":\"main\"}");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_oversized_body() {
+        let raw = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        let err = read_request(&mut raw.as_bytes()).await.unwrap_err();
+        assert_eq!(err.status, 413);
+    }
+
+    #[tokio::test]
+    async fn test_write_response() {
+        let mut out = Vec::new();
+        write_response(&mut out, &Response::error(404, "Not found"))
+            .await
+            .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(text.ends_with("{\"error\":\"Not found\"}"));
+    }
+}
@@ -0,0 +1,330 @@
+//! Webhook receiver.
+//!
+//! A small HTTP listener that accepts events from GitLab, Harbor and other
+//! senders at `POST /webhooks/<source>` and starts every saved flow whose
+//! `webhook_trigger` node matches the event.
+//!
+//! Every request must carry the shared secret, even on loopback: any web page
+//! the user opens can post to `127.0.0.1`. Requests with an `Origin` header or
+//! a non-JSON body are refused as well, since senders never make them but
+//! browsers do.
+
+pub(crate) mod http;
+mod sources;
+
+use chrono::Utc;
+use ring::rand::SecureRandom;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+use crate::commands::flows::read_saved_flows;
+use crate::feature_flags::{self, FeatureFlag};
+use crate::flow_engine;
+use http::{Request, Response};
+
+/// Time allowed for a sender to deliver its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Keyring entry of the secret generated when none is configured.
+const GENERATED_SECRET_NAME: &str = "webhook-receiver";
+
+/// Settings for starting the webhook receiver.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WebhookReceiverConfig {
+    /// Address to listen on (e.g., "127.0.0.1" or "0.0.0.0")
+    pub bind_address: String,
+    pub port: u16,
+    /// Shared secret senders must present. May be a secret reference (e.g.,
+    /// "keyring:webhook-secret"). When unset, a secret is generated on first
+    /// start and kept in the OS keyring
+    pub secret: Option<String>,
+}
+
+/// Current state of the webhook receiver.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WebhookReceiverStatus {
+    pub running: bool,
+    /// Address the receiver listens on (e.g., "127.0.0.1:9870")
+    pub address: Option<String>,
+    /// Start timestamp (RFC 3339)
+    pub started_at: Option<String>,
+    /// Events accepted since the receiver started
+    pub events_received: u32,
+    /// Timestamp of the last accepted event (RFC 3339)
+    pub last_event_at: Option<String>,
+    /// Secret senders must present, when it was generated rather than configured
+    pub generated_secret: Option<String>,
+}
+
+/// The running receiver.
+struct Receiver {
+    status: WebhookReceiverStatus,
+    shutdown: oneshot::Sender<()>,
+}
+
+static RECEIVER: LazyLock<Mutex<Option<Receiver>>> = LazyLock::new(|| Mutex::new(None));
+
+fn receiver() -> MutexGuard<'static, Option<Receiver>> {
+    RECEIVER.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Starts listening for webhooks. Fails if the receiver is already running.
+pub async fn start(
+    app: &AppHandle,
+    config: WebhookReceiverConfig,
+) -> Result<WebhookReceiverStatus, String> {
+    feature_flags::ensure_enabled(app, FeatureFlag::WebhookReceiver)?;
+    if receiver().is_some() {
+        return Err("Webhook receiver is already running".to_string());
+    }
+
+    let bind_address = config.bind_address.trim();
+    let (secret, generated_secret) = match config.secret.as_deref().map(str::trim) {
+        Some(secret) if !secret.is_empty() => (crate::secrets::resolve(secret).await?, None),
+        _ => {
+            let secret = generated_secret().await?;
+            (secret.clone(), Some(secret))
+        }
+    };
+
+    let listener = TcpListener::bind((bind_address, config.port))
+        .await
+        .map_err(|e| format!("Failed to listen on {bind_address}:{}: {e}", config.port))?;
+    let address = listener
+        .local_addr()
+        .map(|a| a.to_string())
+        .unwrap_or_else(|_| format!("{bind_address}:{}", config.port));
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let status = WebhookReceiverStatus {
+        running: true,
+        address: Some(address.clone()),
+        started_at: Some(Utc::now().to_rfc3339()),
+        events_received: 0,
+        last_event_at: None,
+        generated_secret,
+    };
+
+    {
+        let mut receiver = receiver();
+        if receiver.is_some() {
+            return Err("Webhook receiver is already running".to_string());
+        }
+        *receiver = Some(Receiver {
+            status: status.clone(),
+            shutdown: shutdown_tx,
+        });
+    }
+
+    log::info!("Webhook receiver listening on {address}");
+    let app = app.clone();
+    tauri::async_runtime::spawn(accept_loop(app, listener, secret, shutdown_rx));
+
+    Ok(status)
+}
+
+/// Stops the receiver if it is running.
+pub fn stop() {
+    if let Some(running) = receiver().take() {
+        let _ = running.shutdown.send(());
+        log::info!("Webhook receiver stopped");
+    }
+}
+
+/// Returns the receiver state.
+pub fn status() -> WebhookReceiverStatus {
+    match receiver().as_ref() {
+        Some(running) => running.status.clone(),
+        None => WebhookReceiverStatus {
+            running: false,
+            address: None,
+            started_at: None,
+            events_received: 0,
+            last_event_at: None,
+            generated_secret: None,
+        },
+    }
+}
+
+/// Returns the secret generated for senders, creating it on first use.
+async fn generated_secret() -> Result<String, String> {
+    tokio::task::spawn_blocking(|| {
+        if let Some(secret) = crate::secrets::read_keyring_secret(GENERATED_SECRET_NAME)? {
+            return Ok(secret);
+        }
+
+        let mut bytes = [0u8; 24];
+        ring::rand::SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| "Failed to generate webhook secret".to_string())?;
+        let secret: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        crate::secrets::save_keyring_secret(GENERATED_SECRET_NAME, &secret)?;
+        log::info!("Generated a webhook secret (keyring:{GENERATED_SECRET_NAME})");
+        Ok(secret)
+    })
+    .await
+    .map_err(|e| format!("Failed to load webhook secret: {e}"))?
+}
+
+async fn accept_loop(
+    app: AppHandle,
+    listener: TcpListener,
+    secret: String,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, peer)) => {
+                    log::debug!("Webhook connection from {peer}");
+                    let app = app.clone();
+                    let secret = secret.clone();
+                    tauri::async_runtime::spawn(async move {
+                        handle_connection(&app, stream, &secret).await;
+                    });
+                }
+                Err(e) => log::warn!("Failed to accept webhook connection: {e}"),
+            },
+        }
+    }
+}
+
+async fn handle_connection(app: &AppHandle, mut stream: TcpStream, secret: &str) {
+    let response =
+        match tokio::time::timeout(REQUEST_TIMEOUT, http::read_request(&mut stream)).await {
+            Ok(Ok(request)) => handle_request(app, &request, secret),
+            Ok(Err(response)) => response,
+            Err(_) => Response::error(400, "Timed out reading request"),
+        };
+
+    if let Err(e) = http::write_response(&mut stream, &response).await {
+        log::debug!("Failed to write webhook response: {e}");
+    }
+}
+
+/// Refuses requests that aren't authenticated sender requests.
+fn check_request(request: &Request, secret: &str) -> Result<(), Response> {
+    if request.method != "POST" {
+        return Err(Response::error(405, "Only POST is supported"));
+    }
+
+    // Senders never set Origin; browsers always do on cross-origin POSTs
+    if request.header("origin").is_some() {
+        log::warn!("Rejected browser request to {}", request.path);
+        return Err(Response::error(403, "Browser requests are not accepted"));
+    }
+
+    // Browsers can send text/plain without a CORS preflight, but not JSON
+    let is_json = request
+        .header("content-type")
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case("application/json"));
+    if !is_json {
+        return Err(Response::error(
+            415,
+            "Content-Type must be application/json",
+        ));
+    }
+
+    if !sources::is_authorized(request, secret) {
+        log::warn!("Rejected webhook to {} with invalid secret", request.path);
+        return Err(Response::error(401, "Invalid webhook secret"));
+    }
+    Ok(())
+}
+
+fn handle_request(app: &AppHandle, request: &Request, secret: &str) -> Response {
+    if let Err(response) = check_request(request, secret) {
+        return response;
+    }
+
+    let event = match sources::parse_event(request) {
+        Ok(event) => event,
+        Err(e) if e.starts_with("Unknown webhook path") => return Response::error(404, &e),
+        Err(e) => return Response::error(400, &e),
+    };
+
+    log::info!("Received {} webhook: {}", event.source, event.event);
+    if let Some(running) = receiver().as_mut() {
+        running.status.events_received += 1;
+        running.status.last_event_at = Some(Utc::now().to_rfc3339());
+    }
+
+    let flows = match read_saved_flows(app) {
+        Ok(flows) => flows,
+        Err(e) => {
+            log::error!("Failed to load flows for webhook: {e}");
+            return Response::error(500, "Failed to load flows");
+        }
+    };
+
+    let runs = flow_engine::trigger_flows(app, &flows, &event);
+    log::info!(
+        "Webhook {} {} started {} flow run(s)",
+        event.source,
+        event.event,
+        runs.len()
+    );
+
+    Response::new(
+        202,
+        serde_json::json!({
+            "source": event.source,
+            "event": event.event,
+            "runs": runs.iter().map(|r| &r.id).collect::<Vec<_>>(),
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn request(headers: &[(&str, &str)]) -> Request {
+        Request {
+            method: "POST".to_string(),
+            path: "/webhooks/gitlab".to_string(),
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>(),
+            body: br#"{"object_kind":"tag_push"}"#.to_vec(),
+        }
+    }
+
+    fn status(result: Result<(), Response>) -> Option<u16> {
+        result.err().map(|response| response.status)
+    }
+
+    #[test]
+    fn test_refuses_browser_requests() {
+        // What `fetch(..., { method: "POST", body })` sends from a web page
+        let browser = request(&[
+            ("content-type", "text/plain;charset=UTF-8"),
+            ("origin", "https://example.com"),
+        ]);
+        assert_eq!(status(check_request(&browser, "s3cret")), Some(403));
+
+        let no_origin = request(&[("content-type", "text/plain")]);
+        assert_eq!(status(check_request(&no_origin, "s3cret")), Some(415));
+    }
+
+    #[test]
+    fn test_requires_the_secret() {
+        let unsigned = request(&[("content-type", "application/json")]);
+        assert_eq!(status(check_request(&unsigned, "s3cret")), Some(401));
+
+        let signed = request(&[
+            ("content-type", "application/json; charset=utf-8"),
+            ("x-gitlab-token", "s3cret"),
+        ]);
+        assert_eq!(status(check_request(&signed, "s3cret")), None);
+    }
+}
@@ -0,0 +1,157 @@
+//! Recognizes webhook senders and normalizes their events.
+//!
+//! Each sender posts to its own path (`/webhooks/<source>`) and uses its own
+//! convention for the event name and shared secret.
+
+use serde_json::Value;
+
+use super::http::Request;
+use crate::flow_engine::WebhookEvent;
+
+/// Extracts the webhook event from a request.
+pub fn parse_event(request: &Request) -> Result<WebhookEvent, String> {
+    let source = request
+        .path
+        .strip_prefix("/webhooks/")
+        .filter(|s| !s.is_empty() && !s.contains('/'))
+        .ok_or_else(|| format!("Unknown webhook path: {}", request.path))?
+        .to_ascii_lowercase();
+
+    let payload: Value = if request.body.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&request.body).map_err(|e| format!("Invalid JSON body: {e}"))?
+    };
+
+    let event = match source.as_str() {
+        // GitLab: `object_kind` is "push", "tag_push", "pipeline", ...
+        "gitlab" => payload
+            .get("object_kind")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        // Harbor: `type` is "PUSH_ARTIFACT", "SCANNING_COMPLETED", ...
+        "harbor" => payload
+            .get("type")
+            .and_then(Value::as_str)
+            .map(str::to_ascii_lowercase),
+        _ => request.header("x-event").map(str::to_string).or_else(|| {
+            payload
+                .get("event")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        }),
+    }
+    .ok_or_else(|| format!("Could not determine the event type of the {source} webhook"))?;
+
+    Ok(WebhookEvent {
+        source,
+        event,
+        payload,
+    })
+}
+
+/// Checks the shared secret sent with the request.
+///
+/// Accepts GitLab's `X-Gitlab-Token`, a generic `X-Webhook-Token`, or an
+/// `Authorization` header (Harbor sends its configured value verbatim;
+/// a `Bearer ` prefix is also accepted).
+pub fn is_authorized(request: &Request, secret: &str) -> bool {
+    let candidates = [
+        request.header("x-gitlab-token"),
+        request.header("x-webhook-token"),
+        request.header("authorization"),
+        request
+            .header("authorization")
+            .and_then(|v| v.strip_prefix("Bearer ")),
+    ];
+
+    candidates
+        .into_iter()
+        .flatten()
+        .any(|token| constant_time_eq(token.as_bytes(), secret.as_bytes()))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn request(path: &str, headers: &[(&str, &str)], body: &str) -> Request {
+        Request {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_parse_gitlab_event() {
+        let event = parse_event(&request(
+            "/webhooks/gitlab",
+            &[],
+            r#"{"object_kind":"tag_push","ref":"refs/tags/v1.0.0"}"#,
+        ))
+        .unwrap();
+        assert_eq!(event.source, "gitlab");
+        assert_eq!(event.event, "tag_push");
+    }
+
+    #[test]
+    fn test_parse_harbor_event() {
+        let event = parse_event(&request(
+            "/webhooks/harbor",
+            &[],
+            r#"{"type":"PUSH_ARTIFACT","event_data":{}}"#,
+        ))
+        .unwrap();
+        assert_eq!(event.event, "push_artifact");
+    }
+
+    #[test]
+    fn test_parse_generic_event_from_header() {
+        let event = parse_event(&request(
+            "/webhooks/generic",
+            &[("x-event", "release")],
+            "{}",
+        ))
+        .unwrap();
+        assert_eq!(event.event, "release");
+        assert!(parse_event(&request("/other", &[], "{}")).is_err());
+    }
+
+    #[test]
+    fn test_is_authorized() {
+        let secret = "s3cret";
+        assert!(is_authorized(
+            &request("/webhooks/gitlab", &[("x-gitlab-token", secret)], ""),
+            secret
+        ));
+        assert!(is_authorized(
+            &request(
+                "/webhooks/generic",
+                &[("authorization", "Bearer s3cret")],
+                ""
+            ),
+            secret
+        ));
+        assert!(!is_authorized(
+            &request("/webhooks/gitlab", &[("x-gitlab-token", "wrong")], ""),
+            secret
+        ));
+        assert!(!is_authorized(
+            &request("/webhooks/gitlab", &[], ""),
+            secret
+        ));
+    }
+}
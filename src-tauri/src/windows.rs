@@ -0,0 +1,156 @@
+//! Multi-window coordination.
+//!
+//! Shared state (poll results, event streams, flow runs) lives in the
+//! backend, never in a window, so opening another window (e.g., a pinned
+//! log viewer next to the main dashboard) starts no extra pollers or
+//! streams. Backend events are sent with [`broadcast`], which delivers them
+//! to every window except those that narrowed their subscriptions to other
+//! events. Extra windows are labelled `view-<view>`, one per view.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use tauri::webview::WebviewWindowBuilder;
+use tauri::{AppHandle, Emitter, EventTarget, Manager, WebviewUrl};
+
+use crate::app_lock::APP_LOCK_CHANGED_EVENT;
+
+/// Label prefix of windows opened for a view.
+pub const VIEW_WINDOW_PREFIX: &str = "view-";
+
+/// Events every window receives whatever it subscribed to.
+const ALWAYS_DELIVERED: [&str; 1] = [APP_LOCK_CHANGED_EVENT];
+
+/// Events each window subscribed to, by label; windows without an entry
+/// receive every event.
+static SUBSCRIPTIONS: LazyLock<Mutex<HashMap<String, BTreeSet<String>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn subscriptions() -> MutexGuard<'static, HashMap<String, BTreeSet<String>>> {
+    SUBSCRIPTIONS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// An open window.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct AppWindow {
+    pub label: String,
+    /// Events the window subscribed to; None when it receives every event
+    pub subscriptions: Option<Vec<String>>,
+}
+
+/// Sends a backend event to the windows that want it.
+pub fn broadcast<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    let result = {
+        let subscriptions = subscriptions();
+        app.emit_filter(event, payload, |target| match target {
+            EventTarget::Window { label }
+            | EventTarget::Webview { label }
+            | EventTarget::WebviewWindow { label } => delivers(&subscriptions, label, event),
+            _ => true,
+        })
+    };
+    if let Err(e) = result {
+        log::warn!("Failed to emit {event} event: {e}");
+    }
+}
+
+/// Whether a window receives an event.
+fn delivers(subscriptions: &HashMap<String, BTreeSet<String>>, label: &str, event: &str) -> bool {
+    ALWAYS_DELIVERED.contains(&event)
+        || subscriptions
+            .get(label)
+            .is_none_or(|events| events.contains(event))
+}
+
+/// Limits the events a window receives; None restores every event.
+pub fn subscribe(label: &str, events: Option<Vec<String>>) {
+    match events {
+        Some(events) => {
+            log::debug!("Window {label} subscribed to {events:?}");
+            subscriptions().insert(label.to_string(), events.into_iter().collect());
+        }
+        None => {
+            subscriptions().remove(label);
+        }
+    }
+}
+
+/// Drops the subscriptions of a closed window.
+pub fn forget(label: &str) {
+    subscriptions().remove(label);
+}
+
+/// Lists the open windows.
+pub fn list(app: &AppHandle) -> Vec<AppWindow> {
+    let subscriptions = subscriptions();
+    let mut windows: Vec<AppWindow> = app
+        .webview_windows()
+        .into_keys()
+        .map(|label| AppWindow {
+            subscriptions: subscriptions
+                .get(&label)
+                .map(|events| events.iter().cloned().collect()),
+            label,
+        })
+        .collect();
+    windows.sort_by(|a, b| a.label.cmp(&b.label));
+    windows
+}
+
+/// Opens the window of a view, or focuses it when it is already open.
+/// The view is passed to the frontend as the `view` query parameter.
+pub fn open_view(app: &AppHandle, view: &str, title: Option<&str>) -> Result<String, String> {
+    if view.is_empty()
+        || !view
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(format!(
+            "Invalid view name: {view:?} (use lowercase letters, digits and '-')"
+        ));
+    }
+    let label = format!("{VIEW_WINDOW_PREFIX}{view}");
+
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(label);
+    }
+
+    WebviewWindowBuilder::new(
+        app,
+        &label,
+        WebviewUrl::App(format!("index.html?view={view}").into()),
+    )
+    .title(title.unwrap_or("Ops Flow"))
+    .inner_size(1000.0, 700.0)
+    .build()
+    .map_err(|e| format!("Failed to open window {label}: {e}"))?;
+
+    log::info!("Opened window {label}");
+    Ok(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delivers() {
+        let subscriptions = HashMap::from([(
+            "view-logs".to_string(),
+            BTreeSet::from(["flow-run-updated".to_string()]),
+        )]);
+
+        assert!(delivers(&subscriptions, "main", "tag-detected"));
+        assert!(delivers(&subscriptions, "view-logs", "flow-run-updated"));
+        assert!(!delivers(&subscriptions, "view-logs", "tag-detected"));
+        assert!(delivers(
+            &subscriptions,
+            "view-logs",
+            APP_LOCK_CHANGED_EVENT
+        ));
+    }
+}
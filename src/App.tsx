@@ -1,5 +1,4 @@
 import { useEffect } from 'react'
-import { check } from '@tauri-apps/plugin-updater'
 import { relaunch } from '@tauri-apps/plugin-process'
 import { initializeCommandSystem } from './lib/commands'
 import { buildAppMenu, setupMenuLanguageListener } from './lib/menu'
@@ -53,54 +52,61 @@ function App() {
       mode: import.meta.env.MODE,
     })
 
-    // Auto-updater logic - check for updates 5 seconds after app loads
+    // Auto-updater logic - check for updates 5 seconds after app loads.
+    // The channel, deferral and install window are applied in Rust
     const checkForUpdates = async () => {
-      try {
-        const update = await check()
-        if (update) {
-          logger.info(`Update available: ${update.version}`)
-
-          // Show confirmation dialog
-          const shouldUpdate = confirm(
-            `Update available: ${update.version}\n\nWould you like to install this update now?`
-          )
-
-          if (shouldUpdate) {
-            try {
-              // Download and install with progress logging
-              await update.downloadAndInstall(event => {
-                switch (event.event) {
-                  case 'Started':
-                    logger.info(`Downloading ${event.data.contentLength} bytes`)
-                    break
-                  case 'Progress':
-                    logger.info(`Downloaded: ${event.data.chunkLength} bytes`)
-                    break
-                  case 'Finished':
-                    logger.info('Download complete, installing...')
-                    break
-                }
-              })
-
-              // Ask if user wants to restart now
-              const shouldRestart = confirm(
-                'Update completed successfully!\n\nWould you like to restart the app now to use the new version?'
-              )
-
-              if (shouldRestart) {
-                await relaunch()
-              }
-            } catch (updateError) {
-              logger.error(`Update installation failed: ${String(updateError)}`)
-              alert(
-                `Update failed: There was a problem with the automatic download.\n\n${String(updateError)}`
-              )
-            }
-          }
-        }
-      } catch (checkError) {
-        logger.error(`Update check failed: ${String(checkError)}`)
+      const result = await commands.checkForUpdates()
+      if (result.status === 'error') {
+        logger.error(`Update check failed: ${result.error}`)
         // Silent fail for update checks - don't bother user with network issues
+        return
+      }
+
+      const update = result.data.update
+      if (!update) return
+      if (update.deferred_until) {
+        logger.info(
+          `Update ${update.version} is deferred until ${update.deferred_until}`
+        )
+        return
+      }
+
+      // With an install window, the backend installs the update on its own
+      const preferences = await commands.loadPreferences()
+      if (
+        preferences.status === 'ok' &&
+        preferences.data.update_install_window
+      ) {
+        logger.info(
+          `Update ${update.version} will install in the install window`
+        )
+        return
+      }
+
+      logger.info(`Update available: ${update.version}`)
+
+      // Show confirmation dialog
+      const shouldUpdate = confirm(
+        `Update available: ${update.version}\n\nWould you like to install this update now?`
+      )
+      if (!shouldUpdate) return
+
+      const installed = await commands.installUpdate(update.version)
+      if (installed.status === 'error') {
+        logger.error(`Update installation failed: ${installed.error}`)
+        alert(
+          `Update failed: There was a problem with the automatic download.\n\n${installed.error}`
+        )
+        return
+      }
+
+      // Ask if user wants to restart now
+      const shouldRestart = confirm(
+        'Update completed successfully!\n\nWould you like to restart the app now to use the new version?'
+      )
+
+      if (shouldRestart) {
+        await relaunch()
       }
     }
 
@@ -18,7 +18,14 @@ import {
   SelectTrigger,
   SelectValue,
 } from '@/components/ui/select'
-import { GitBranch, Server, Layers, Search, Shield } from 'lucide-react'
+import {
+  GitBranch,
+  Server,
+  Layers,
+  Search,
+  Shield,
+  FlaskConical,
+} from 'lucide-react'
 import { useIntegrations, useSaveIntegrations } from '@/services/integrations'
 import type { Integration, IntegrationType } from '@/lib/tauri-bindings'
 
@@ -39,6 +46,7 @@ const integrationTypes: {
   { value: 'kubernetes', label: 'Kubernetes', icon: Layers },
   { value: 'sonarqube', label: 'SonarQube', icon: Search },
   { value: 'keycloak', label: 'Keycloak', icon: Shield },
+  { value: 'demo', label: 'Demo', icon: FlaskConical },
 ]
 
 export function IntegrationDialog({
@@ -113,6 +121,7 @@ export function IntegrationDialog({
           name: name.trim(),
           base_url: baseUrl.trim(),
           credentials_ref: null,
+          api_version: null,
         }
         updated = [...current, newIntegration]
       }
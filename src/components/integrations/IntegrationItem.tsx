@@ -13,6 +13,7 @@ import {
   Layers,
   Search,
   Shield,
+  FlaskConical,
   MoreVertical,
   Edit,
   Trash2,
@@ -43,6 +44,7 @@ const integrationIcons: Record<IntegrationType, typeof GitBranch> = {
   kubernetes: Layers,
   sonarqube: Search,
   keycloak: Shield,
+  demo: FlaskConical,
 }
 
 export function IntegrationItem({
@@ -43,6 +43,17 @@ async savePreferences(preferences: AppPreferences) : Promise<Result<null, string
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Returns the feature flags in effect (the saved ones, or the defaults).
+ */
+async getFeatureFlags() : Promise<Result<FeatureFlags, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_feature_flags") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Sends a native system notification.
  * On mobile platforms, returns an error as notifications are not yet supported.
@@ -55,6 +66,66 @@ async sendNativeNotification(title: string, body: string | null) : Promise<Resul
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Sends a native notification with action buttons. Returns its ID.
+ * 
+ * With `service`, the notification is routed to the service's owner.
+ */
+async sendActionableNotification(title: string, body: string | null, actions: NotificationAction[], service: ServiceRef | null) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("send_actionable_notification", { title, body, actions, service }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists recent actionable notifications, newest first.
+ */
+async listActionableNotifications() : Promise<Result<ActionableNotification[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_actionable_notifications") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Runs an action of a notification. `open` actions focus the main window
+ * and emit [`NOTIFICATION_OPEN_EVENT`] (returning no run); `run` actions
+ * start a flow run of the quick action.
+ */
+async runNotificationAction(notificationId: number, actionId: string) : Promise<Result<FlowRun | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_notification_action", { notificationId, actionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the setup progress. Starts at the first step on first run.
+ */
+async getOnboardingState() : Promise<Result<OnboardingState, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_onboarding_state") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Run a setup step (check integrations, test their connections, or create
+ * the example project) and return the updated progress.
+ */
+async completeOnboardingStep(step: OnboardingStep) : Promise<Result<OnboardingState, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("complete_onboarding_step", { step }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Saves emergency data to a JSON file for later recovery.
  * Validates filename and enforces a 10MB size limit.
@@ -143,6 +214,118 @@ async updateQuickPaneShortcut(shortcut: string | null) : Promise<Result<null, st
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Run a single quick pane action. Recorded when a macro recording is active.
+ */
+async runQuickAction(action: QuickAction) : Promise<Result<FlowRun, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_quick_action", { action }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Start recording quick pane actions into a new macro.
+ */
+async startMacroRecording() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_macro_recording") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get whether a macro is being recorded and its steps so far.
+ */
+async getMacroRecordingStatus() : Promise<Result<MacroRecordingStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_macro_recording_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop recording. Saves the recorded actions as a macro named `name`, or
+ * discards them when `name` is not provided.
+ */
+async stopMacroRecording(name: string | null) : Promise<Result<QuickPaneMacro | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_macro_recording", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replay a saved macro. Its actions run in order as one flow run.
+ */
+async runQuickPaneMacro(macroId: string) : Promise<Result<FlowRun, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_quick_pane_macro", { macroId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get whether the app lock is enabled and currently locked.
+ */
+async getAppLockStatus() : Promise<Result<AppLockStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_app_lock_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lock the app now.
+ */
+async lockApp() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lock_app") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Unlock the app with the lock passphrase.
+ */
+async unlockApp(passphrase: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unlock_app", { passphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Set, change or remove (`passphrase: null`) the lock passphrase.
+ * Requires the current passphrase when one is set.
+ */
+async setAppLockPassphrase(currentPassphrase: string | null, passphrase: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_app_lock_passphrase", { currentPassphrase, passphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Record user activity, postponing the idle lock.
+ */
+async recordAppActivity() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("record_app_activity") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Loads all projects from disk.
  */
@@ -220,6 +403,18 @@ async testIntegrationConnection(integrationId: string) : Promise<Result<boolean,
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Returns the API version used for an integration (pinned or detected) and
+ * the compatibility shims applied to its responses.
+ */
+async getIntegrationApiVersion(integrationId: string) : Promise<Result<IntegrationApiVersion, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_integration_api_version", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Loads all mappings from disk.
  */
@@ -243,693 +438,5113 @@ async saveMappings(mappings: Mapping[]) : Promise<Result<null, string>> {
 }
 },
 /**
- * Saves integration credentials to the OS keyring.
+ * Lists the recorded snapshots of integrations or mappings, oldest first.
  */
-async saveIntegrationCredentials(integrationId: string, credentials: IntegrationCredentials) : Promise<Result<null, string>> {
+async listConfigHistory(entity: ConfigHistoryEntity) : Promise<Result<ConfigSnapshotInfo[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_integration_credentials", { integrationId, credentials }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_config_history", { entity }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Gets integration credentials from the OS keyring.
+ * Returns the field-level changes to integrations or mappings between two
+ * snapshots, each attributed to the save that made it.
+ * 
+ * `from` defaults to before the first snapshot and `to` to the latest one.
  */
-async getIntegrationCredentials(integrationId: string) : Promise<Result<IntegrationCredentials | null, string>> {
+async diffConfigHistory(entity: ConfigHistoryEntity, from: string | null, to: string | null) : Promise<Result<ConfigHistoryDiff, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_integration_credentials", { integrationId }) };
+    return { status: "ok", data: await TAURI_INVOKE("diff_config_history", { entity, from, to }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Deletes integration credentials from the OS keyring.
+ * Loads all smoke test definitions from disk.
  */
-async deleteIntegrationCredentials(integrationId: string) : Promise<Result<null, string>> {
+async loadSmokeTests() : Promise<Result<SmokeTest[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_integration_credentials", { integrationId }) };
+    return { status: "ok", data: await TAURI_INVOKE("load_smoke_tests") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Load list of all saved flows (metadata only).
+ * Saves all smoke test definitions to disk.
  */
-async loadFlows() : Promise<Result<FlowMetadata[], string>> {
+async saveSmokeTests(smokeTests: SmokeTest[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("load_flows") };
+    return { status: "ok", data: await TAURI_INVOKE("save_smoke_tests", { smokeTests }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Load a specific flow by ID.
+ * Loads all uptime targets from disk.
  */
-async loadFlow(flowId: string) : Promise<Result<Flow, string>> {
+async loadUptimeTargets() : Promise<Result<UptimeTarget[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("load_flow", { flowId }) };
+    return { status: "ok", data: await TAURI_INVOKE("load_uptime_targets") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Save a flow to disk.
- * Uses atomic write (temp file + rename) to prevent corruption.
+ * Saves all uptime targets to disk.
  */
-async saveFlow(flow: Flow) : Promise<Result<null, string>> {
+async saveUptimeTargets(uptimeTargets: UptimeTarget[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_flow", { flow }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_uptime_targets", { uptimeTargets }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Delete a flow by ID.
+ * Loads all quick pane macros from disk.
  */
-async deleteFlow(flowId: string) : Promise<Result<null, string>> {
+async loadQuickPaneMacros() : Promise<Result<QuickPaneMacro[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_flow", { flowId }) };
+    return { status: "ok", data: await TAURI_INVOKE("load_quick_pane_macros") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches GitLab projects for a given integration.
+ * Saves all quick pane macros to disk.
  */
-async fetchGitlabProjects(integrationId: string) : Promise<Result<GitLabProject[], string>> {
+async saveQuickPaneMacros(macros: QuickPaneMacro[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_projects", { integrationId }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_quick_pane_macros", { macros }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches GitLab pipelines for a given project.
+ * Loads all cost rates from disk.
  */
-async fetchGitlabPipelines(integrationId: string, projectId: number) : Promise<Result<GitLabPipeline[], string>> {
+async loadCostRates() : Promise<Result<CostRate[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_pipelines", { integrationId, projectId }) };
+    return { status: "ok", data: await TAURI_INVOKE("load_cost_rates") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches GitLab webhooks for a given project.
+ * Saves all cost rates to disk.
  */
-async fetchGitlabWebhooks(integrationId: string, projectId: number) : Promise<Result<GitLabWebhook[], string>> {
+async saveCostRates(costRates: CostRate[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_webhooks", { integrationId, projectId }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_cost_rates", { costRates }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Triggers a GitLab pipeline for a given project.
+ * Loads all image scanners from disk.
  */
-async triggerGitlabPipeline(integrationId: string, projectId: number, ref: string) : Promise<Result<GitLabPipeline, string>> {
+async loadImageScanners() : Promise<Result<ImageScanner[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("trigger_gitlab_pipeline", { integrationId, projectId, ref }) };
+    return { status: "ok", data: await TAURI_INVOKE("load_image_scanners") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches Jenkins jobs for a given integration.
+ * Saves all image scanners to disk.
  */
-async fetchJenkinsJobs(integrationId: string) : Promise<Result<JenkinsJob[], string>> {
+async saveImageScanners(imageScanners: ImageScanner[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_jenkins_jobs", { integrationId }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_image_scanners", { imageScanners }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches Jenkins builds for a given job.
+ * Loads the desired state of every environment from disk.
  */
-async fetchJenkinsBuilds(integrationId: string, jobName: string) : Promise<Result<JenkinsBuild[], string>> {
+async loadDesiredStates() : Promise<Result<DesiredState[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_jenkins_builds", { integrationId, jobName }) };
+    return { status: "ok", data: await TAURI_INVOKE("load_desired_states") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches detailed information for a specific Jenkins build.
+ * Saves the desired state of every environment to disk.
  */
-async fetchJenkinsBuildDetails(integrationId: string, jobName: string, buildNumber: number) : Promise<Result<JenkinsBuild, string>> {
+async saveDesiredStates(desiredStates: DesiredState[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_jenkins_build_details", { integrationId, jobName, buildNumber }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_desired_states", { desiredStates }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Triggers a Jenkins build for a given job.
+ * Loads all tag watches from disk.
  */
-async triggerJenkinsBuild(integrationId: string, jobName: string, parameters: Partial<{ [key in string]: string }> | null) : Promise<Result<null, string>> {
+async loadTagWatches() : Promise<Result<TagWatch[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("trigger_jenkins_build", { integrationId, jobName, parameters }) };
+    return { status: "ok", data: await TAURI_INVOKE("load_tag_watches") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches Kubernetes namespaces for a given integration.
+ * Saves all tag watches to disk.
  */
-async fetchK8sNamespaces(integrationId: string) : Promise<Result<K8sNamespace[], string>> {
+async saveTagWatches(tagWatches: TagWatch[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_k8s_namespaces", { integrationId }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_tag_watches", { tagWatches }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches Kubernetes pods in a specific namespace.
+ * Loads all event recordings from disk.
  */
-async fetchK8sPods(integrationId: string, namespace: string) : Promise<Result<K8sPod[], string>> {
+async loadEventRecordings() : Promise<Result<EventRecording[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_k8s_pods", { integrationId, namespace }) };
+    return { status: "ok", data: await TAURI_INVOKE("load_event_recordings") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches Kubernetes services in a specific namespace.
+ * Saves all event recordings to disk.
  */
-async fetchK8sServices(integrationId: string, namespace: string) : Promise<Result<K8sService[], string>> {
+async saveEventRecordings(recordings: EventRecording[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_k8s_services", { integrationId, namespace }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_event_recordings", { recordings }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches detailed information for a specific Kubernetes pod.
+ * Loads all build failure rules from disk.
  */
-async fetchK8sPodDetails(integrationId: string, namespace: string, podName: string) : Promise<Result<K8sPod, string>> {
+async loadFailureRules() : Promise<Result<FailureRule[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_k8s_pod_details", { integrationId, namespace, podName }) };
+    return { status: "ok", data: await TAURI_INVOKE("load_failure_rules") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches SonarQube projects for a given integration.
+ * Saves all build failure rules to disk.
  */
-async fetchSonarqubeProjects(integrationId: string) : Promise<Result<SonarQubeProject[], string>> {
+async saveFailureRules(rules: FailureRule[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_sonarqube_projects", { integrationId }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_failure_rules", { rules }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches SonarQube metrics for a given project.
+ * Returns the built-in failure rules used while none are configured.
  */
-async fetchSonarqubeMetrics(integrationId: string, projectKey: string) : Promise<Result<SonarQubeMetrics, string>> {
+async getDefaultFailureRules() : Promise<Result<FailureRule[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_sonarqube_metrics", { integrationId, projectKey }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_default_failure_rules") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches Keycloak realms for a given integration.
+ * Loads all report definitions from disk.
  */
-async fetchKeycloakRealms(integrationId: string) : Promise<Result<KeycloakRealm[], string>> {
+async loadReportDefinitions() : Promise<Result<ReportDefinition[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_keycloak_realms", { integrationId }) };
+    return { status: "ok", data: await TAURI_INVOKE("load_report_definitions") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetches Keycloak clients for a given realm.
+ * Saves all report definitions to disk.
  */
-async fetchKeycloakClients(integrationId: string, realm: string) : Promise<Result<KeycloakClient[], string>> {
+async saveReportDefinitions(reports: ReportDefinition[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_keycloak_clients", { integrationId, realm }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_report_definitions", { reports }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
-}
-}
-
-/** user-defined events **/
-
-
-
-/** user-defined constants **/
-
-
-
-/** user-defined types **/
-
+},
 /**
- * Application preferences that persist to disk.
- * Only contains settings that should be saved between sessions.
+ * Loads all saved filters from disk.
  */
-export type AppPreferences = { theme: string; 
+async loadSavedFilters() : Promise<Result<SavedFilter[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("load_saved_filters") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Global shortcut for quick pane (e.g., "CommandOrControl+Shift+.")
- * If None, uses the default shortcut
+ * Saves all saved filters to disk.
  */
-quick_pane_shortcut: string | null; 
+async saveSavedFilters(filters: SavedFilter[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_saved_filters", { filters }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * User's preferred language (e.g., "en", "es", "de")
- * If None, uses system locale detection
+ * Run every smoke test defined for an environment.
  */
-language: string | null }
+async runSmokeTests(environmentId: string) : Promise<Result<SmokeTestReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_smoke_tests", { environmentId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * An environment represents a deployment target (dev, staging, prod, etc.)
+ * Start probing uptime targets on their schedule.
  */
-export type Environment = { 
+async startUptimeMonitor() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_uptime_monitor") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Unique identifier for the environment
+ * Stop the uptime monitor.
  */
-id: string; 
+async stopUptimeMonitor() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_uptime_monitor") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the uptime monitor state with every target's history.
+ */
+async getUptimeStatus() : Promise<Result<UptimeMonitorStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_uptime_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * List the TLS certificates found by the last certificate check.
+ */
+async listCertificates() : Promise<Result<CertificateInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_certificates") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check the TLS certificates of all integrations and ingress hosts now.
+ */
+async checkCertificates() : Promise<Result<CertificateInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_certificates") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Probe a URL stage by stage (DNS, TCP, TLS, HTTP HEAD) with per-stage timing.
+ */
+async probeEndpoint(url: string) : Promise<Result<EndpointProbe, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("probe_endpoint", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check an image against its registry's vulnerability scan (e.g., Harbor),
+ * returning whether deploys of it would be blocked and the critical CVEs.
+ */
+async checkImageSecurity(imageRef: string) : Promise<Result<ImageSecurityReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_image_security", { imageRef }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check every image running in a cluster (or in some of its namespaces)
+ * against its registry's vulnerability scan.
+ */
+async checkK8sRunningImages(integrationId: string, namespaces: string[]) : Promise<Result<ImageSecurityReport[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_k8s_running_images", { integrationId, namespaces }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetch the SBOM of an image from Harbor or the registry's referrers API.
+ */
+async fetchImageSbom(imageRef: string) : Promise<Result<ImageSbom, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_image_sbom", { imageRef }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Compare the SBOMs of two images (e.g., the deployed and a candidate image).
+ */
+async diffSboms(imageA: string, imageB: string) : Promise<Result<SbomDiff, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("diff_sboms", { imageA, imageB }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Compare the deployments of an environment's namespace with its desired
+ * state, reporting drifted and missing services.
+ */
+async checkEnvironmentDrift(environmentId: string) : Promise<Result<EnvironmentDrift, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_environment_drift", { environmentId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists annotations, optionally only those on one resource.
+ */
+async listAnnotations(resourceKey: string | null) : Promise<Result<Annotation[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_annotations", { resourceKey }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Attaches a note or bookmark to a resource.
+ */
+async addAnnotation(resourceKey: string, kind: AnnotationKind, text: string) : Promise<Result<Annotation, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_annotation", { resourceKey, kind, text }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Changes the text and kind of an annotation.
+ */
+async updateAnnotation(annotationId: string, kind: AnnotationKind, text: string) : Promise<Result<Annotation, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_annotation", { annotationId, kind, text }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Deletes an annotation.
+ */
+async deleteAnnotation(annotationId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_annotation", { annotationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Exports all annotations as JSON (same shape as `list_annotations`) or as
+ * a Markdown document grouped by resource.
+ */
+async exportAnnotations(format: AnnotationExportFormat) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_annotations", { format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Compares build `build_a` (base) with build `build_b` (head).
+ * 
+ * # Arguments
+ * * `target` - Job name for Jenkins integrations, project ID for GitLab
+ * * `build_a`, `build_b` - Build numbers (Jenkins) or pipeline IDs (GitLab)
+ */
+async compareBuilds(integrationId: string, target: string, buildA: number, buildB: number) : Promise<Result<BuildComparison, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("compare_builds", { integrationId, target, buildA, buildB }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check the configured update channel for a newer release.
+ */
+async checkForUpdates() : Promise<Result<UpdateCheck, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_for_updates") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Download and install `version`, the release returned by
+ * `check_for_updates`. Fails if the channel now offers another release or
+ * the release is deferred. Returns the installed version, which runs after
+ * a restart.
+ */
+async installUpdate(version: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("install_update", { version }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Match Jenkins jobs to the GitLab projects their Git remotes point at and
+ * propose mappings. With `create_mappings`, new links are saved as mappings.
+ */
+async detectGitlabJenkinsLinks(gitlabIntegrationId: string, jenkinsIntegrationId: string, createMappings: boolean) : Promise<Result<LinkageReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("detect_gitlab_jenkins_links", { gitlabIntegrationId, jenkinsIntegrationId, createMappings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the metrics endpoint status.
+ */
+async getMetricsEndpointStatus() : Promise<Result<MetricsEndpointStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_metrics_endpoint_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the load of the background task pool.
+ */
+async getTaskPoolStatus() : Promise<Result<TaskPoolStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_task_pool_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Estimate the requests per hour the background pollers send to each
+ * integration, with warnings for integrations close to a known rate limit.
+ */
+async estimatePollingCost() : Promise<Result<PollingCostEstimate, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("estimate_polling_cost") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Evaluate a saved filter against the current (cached) resources.
+ */
+async evaluateSavedFilter(filterId: string) : Promise<Result<SavedFilterResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("evaluate_saved_filter", { filterId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Generates a report now, regardless of its schedule.
+ */
+async generateReport(reportId: string) : Promise<Result<GeneratedReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("generate_report", { reportId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists generated report files, newest first.
+ */
+async listGeneratedReports() : Promise<Result<GeneratedReport[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_generated_reports") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Opens a generated report with the system's default application.
+ */
+async openGeneratedReport(fileName: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_generated_report", { fileName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Saves integration credentials to the OS keyring.
+ */
+async saveIntegrationCredentials(integrationId: string, credentials: IntegrationCredentials) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_integration_credentials", { integrationId, credentials }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Checks credentials locally before saving (password strength, known
+ * defaults, token format for the integration type).
+ * 
+ * Returns the issues found; an empty list means nothing looks wrong.
+ */
+async checkIntegrationCredentials(integrationType: IntegrationType, credentials: IntegrationCredentials) : Promise<Result<CredentialIssue[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_integration_credentials", { integrationType, credentials }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Gets integration credentials from the OS keyring.
+ */
+async getIntegrationCredentials(integrationId: string) : Promise<Result<IntegrationCredentials | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_integration_credentials", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Deletes integration credentials from the OS keyring.
+ */
+async deleteIntegrationCredentials(integrationId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_integration_credentials", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Saves a secret in the OS keyring for `keyring:<name>` references in config.
+ */
+async saveSecret(name: string, value: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_secret", { name, value }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Deletes a secret saved with `save_secret`.
+ */
+async deleteSecret(name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_secret", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Checks that a secret reference (e.g., "vault:secret/data/ops#token")
+ * resolves, without returning the secret. Plain values always pass.
+ */
+async checkSecretReference(reference: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_secret_reference", { reference }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Load list of all saved flows (metadata only).
+ */
+async loadFlows() : Promise<Result<FlowMetadata[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("load_flows") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Load a specific flow by ID.
+ */
+async loadFlow(flowId: string) : Promise<Result<Flow, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("load_flow", { flowId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Save a flow to disk.
+ * Uses atomic write (temp file + rename) to prevent corruption.
+ */
+async saveFlow(flow: Flow) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_flow", { flow }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete a flow by ID.
+ */
+async deleteFlow(flowId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_flow", { flowId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Start executing a saved flow.
+ * Returns the initial run state; progress is emitted as `flow-run-updated` events.
+ */
+async runFlow(flowId: string) : Promise<Result<FlowRun, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_flow", { flowId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Start executing a saved flow in an environment.
+ * 
+ * The environment's variables are available to the flow's templates as
+ * `{{ env.<key> }}`, along with `{{ env.name }}` and `{{ env.namespace }}`,
+ * and are passed on to its sub-flows.
+ */
+async runFlowInEnvironment(flowId: string, environmentId: string) : Promise<Result<FlowRun, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_flow_in_environment", { flowId, environmentId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Run a saved flow in simulation mode.
+ * 
+ * Integration nodes and gates resolve without touching real services and
+ * nodes with a fixture report its recorded outcome. When `fixtures` is not
+ * provided, the flow's recorded fixtures are loaded from disk.
+ */
+async simulateFlow(flowId: string, fixtures: FlowFixtures | null) : Promise<Result<FlowRun, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("simulate_flow", { flowId, fixtures }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the current state of a flow run.
+ */
+async getFlowRun(runId: string) : Promise<Result<FlowRun, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_flow_run", { runId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * List flow runs started since the app launched (most recent first).
+ */
+async listFlowRuns() : Promise<Result<FlowRun[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_flow_runs") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Cancel a running flow.
+ */
+async cancelFlowRun(runId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_flow_run", { runId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Provides the value requested by an input node of a running flow.
+ */
+async provideFlowInput(runId: string, nodeId: string, value: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("provide_flow_input", { runId, nodeId, value }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * List the artifacts attached to a flow run.
+ */
+async listFlowRunArtifacts(runId: string) : Promise<Result<FlowRunArtifact[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_flow_run_artifacts", { runId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Open a flow run artifact. Text artifacts (.txt, .md, .json, .log, .diff)
+ * open with the system's default application; other files are revealed in
+ * their folder rather than run.
+ */
+async openFlowRunArtifact(runId: string, nodeId: string, name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_flow_run_artifact", { runId, nodeId, name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Start the webhook receiver.
+ */
+async startWebhookReceiver(config: WebhookReceiverConfig) : Promise<Result<WebhookReceiverStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_webhook_receiver", { config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop the webhook receiver.
+ */
+async stopWebhookReceiver() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_webhook_receiver") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the webhook receiver status.
+ */
+async getWebhookReceiverStatus() : Promise<Result<WebhookReceiverStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_webhook_receiver_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Opens a window for a view (e.g., "logs"), or focuses it when already
+ * open. Returns the window label (`view-<view>`).
+ */
+async openViewWindow(view: string, title: string | null) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_view_window", { view, title }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Limits the backend events the calling window receives; None restores
+ * every event. App lock changes are always delivered.
+ */
+async subscribeWindowEvents(events: string[] | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("subscribe_window_events", { events }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists the open windows with their event subscriptions.
+ */
+async listAppWindows() : Promise<Result<AppWindow[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_app_windows") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Start polling the configured tag watches.
+ */
+async startTagWatcher() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_tag_watcher") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop the tag watcher.
+ */
+async stopTagWatcher() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_tag_watcher") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the tag watcher state with the tags detected by every watch.
+ */
+async getTagWatcherStatus() : Promise<Result<TagWatcherStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_tag_watcher_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the recorded events last seen between two times (RFC 3339), in one
+ * namespace or all recorded namespaces of an integration.
+ */
+async queryRecordedK8sEvents(integrationId: string, namespace: string | null, from: string, to: string) : Promise<Result<K8sEvent[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("query_recorded_k8s_events", { integrationId, namespace, from, to }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the state of the event stream of every recorded namespace.
+ */
+async getEventRecorderStatus() : Promise<Result<EventStreamStatus[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_event_recorder_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Start fetching the pipelines, builds and pods mapped to a project.
+ * 
+ * Returns the fetches right away; each result arrives as a `refresh-result`
+ * event as soon as it completes, and `refresh-completed` follows the last.
+ */
+async refreshAll(projectId: string) : Promise<Result<RefreshStarted, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("refresh_all", { projectId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Crawl one time-boxed slice of an integration's initial sync now (e.g.,
+ * right after it is configured); the rest continues in the background.
+ */
+async startInitialSync(integrationId: string) : Promise<Result<InitialSyncStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_initial_sync", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the initial sync progress of every GitLab integration that started one.
+ */
+async getInitialSyncStatus() : Promise<Result<InitialSyncStatus[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_initial_sync_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Snapshot the project inventory of a GitLab integration now.
+ */
+async takeProjectInventorySnapshot(integrationId: string) : Promise<Result<ProjectInventorySnapshot, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("take_project_inventory_snapshot", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * List the project inventory snapshots of a GitLab integration, oldest first.
+ */
+async listProjectInventorySnapshots(integrationId: string) : Promise<Result<ProjectInventorySnapshotInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_project_inventory_snapshots", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Diff two project inventory snapshots of a GitLab integration.
+ * 
+ * # Arguments
+ * * `to` - End snapshot; the latest one when omitted
+ */
+async diffProjectInventory(integrationId: string, from: string, to: string | null) : Promise<Result<ProjectInventoryDiff, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("diff_project_inventory", { integrationId, from, to }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches GitLab projects for a given integration.
+ */
+async fetchGitlabProjects(integrationId: string) : Promise<Result<GitLabProject[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_projects", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches GitLab pipelines for a given project.
+ */
+async fetchGitlabPipelines(integrationId: string, projectId: number) : Promise<Result<GitLabPipeline[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_pipelines", { integrationId, projectId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches a GitLab project with its latest pipelines and open merge
+ * requests.
+ * 
+ * `api` selects GraphQL (default, one request) or REST (three requests, for
+ * instances with GraphQL disabled).
+ */
+async fetchGitlabProjectOverview(integrationId: string, projectId: number, api: GitLabApi | null) : Promise<Result<GitLabProjectOverview, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_project_overview", { integrationId, projectId, api }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches GitLab webhooks for a given project.
+ */
+async fetchGitlabWebhooks(integrationId: string, projectId: number) : Promise<Result<GitLabWebhook[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_webhooks", { integrationId, projectId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches GitLab issues for a given project, aggregated per milestone and label.
+ * 
+ * `state` is "opened", "closed" or "all" (default); `labels` keeps only issues
+ * having all of the given labels.
+ */
+async fetchGitlabIssues(integrationId: string, projectId: number, state: string | null, labels: string[] | null) : Promise<Result<GitLabIssueSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_issues", { integrationId, projectId, state, labels }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches a project's milestones with issue counts and a daily burn-down.
+ * 
+ * `state` is "active" or "closed"; all milestones are returned when omitted.
+ */
+async fetchGitlabMilestones(integrationId: string, projectId: number, state: string | null) : Promise<Result<GitLabMilestone[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_milestones", { integrationId, projectId, state }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches a group's epics with issue counts. Groups without epics (a GitLab
+ * Premium feature) return an empty, unlicensed list.
+ * 
+ * `group` is the group ID or full path; `state` is "opened", "closed" or
+ * "all" (default).
+ */
+async fetchGitlabEpics(integrationId: string, group: string, state: string | null) : Promise<Result<GitLabEpicList, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_epics", { integrationId, group, state }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches a project's deploy freeze periods and whether each is in effect.
+ */
+async fetchGitlabFreezePeriods(integrationId: string, projectId: number) : Promise<Result<GitLabFreezePeriod[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_freeze_periods", { integrationId, projectId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches a project's Maven, npm, PyPI and generic packages with their
+ * versions, newest first.
+ * 
+ * `package_type` narrows the list to one of those formats.
+ */
+async fetchGitlabPackages(integrationId: string, projectId: number, packageType: string | null) : Promise<Result<GitLabPackage[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_packages", { integrationId, projectId, packageType }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Reads a text file from a project's repository (e.g., `.gitlab-ci.yml`).
+ * 
+ * `ref_name` is a branch, tag or commit; the default branch is used when omitted.
+ */
+async fetchGitlabFile(integrationId: string, projectId: number, path: string, refName: string | null) : Promise<Result<GitLabFile, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_file", { integrationId, projectId, path, refName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lints CI configuration in the context of a project.
+ * 
+ * Lints `content` when given (e.g., configuration edited in the app),
+ * otherwise the project's current configuration at `ref_name`.
+ */
+async lintGitlabCi(integrationId: string, projectId: number, content: string | null, refName: string | null) : Promise<Result<GitLabCiLintResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lint_gitlab_ci", { integrationId, projectId, content, refName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Scans the pipeline definition and CI variable names of projects for
+ * likely leaked secrets. Findings are redacted.
+ */
+async scanGitlabSecrets(integrationId: string, projectIds: number[]) : Promise<Result<SecretScanReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("scan_gitlab_secrets", { integrationId, projectIds }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists snippets tagged as runbooks (`#runbook`) in a project, or the user's
+ * personal snippets when `project_id` is not provided.
+ * 
+ * `service` keeps only runbooks tagged `service:<service>`.
+ */
+async fetchGitlabRunbooks(integrationId: string, projectId: number | null, service: string | null) : Promise<Result<GitLabRunbook[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_runbooks", { integrationId, projectId, service }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches the content of a runbook snippet.
+ */
+async fetchGitlabRunbookContent(integrationId: string, projectId: number | null, snippetId: number) : Promise<Result<GitLabRunbookContent, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_gitlab_runbook_content", { integrationId, projectId, snippetId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Triggers a GitLab pipeline for a given project.
+ */
+async triggerGitlabPipeline(integrationId: string, projectId: number, ref: string) : Promise<Result<GitLabPipeline, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("trigger_gitlab_pipeline", { integrationId, projectId, ref }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches Jenkins jobs for a given integration.
+ */
+async fetchJenkinsJobs(integrationId: string) : Promise<Result<JenkinsJob[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_jenkins_jobs", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches Jenkins builds for a given job.
+ */
+async fetchJenkinsBuilds(integrationId: string, jobName: string) : Promise<Result<JenkinsBuild[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_jenkins_builds", { integrationId, jobName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches detailed information for a specific Jenkins build.
+ */
+async fetchJenkinsBuildDetails(integrationId: string, jobName: string, buildNumber: number) : Promise<Result<JenkinsBuild, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_jenkins_build_details", { integrationId, jobName, buildNumber }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches credential metadata (IDs, types, store and domain; never secrets)
+ * from the system credential store, or from a folder's store.
+ */
+async fetchJenkinsCredentialsMetadata(integrationId: string, folder: string | null) : Promise<Result<JenkinsCredentialMetadata[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_jenkins_credentials_metadata", { integrationId, folder }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches the parameter definitions of a Jenkins job, including node and
+ * label parameters with the agents they can target.
+ */
+async fetchJenkinsJobParameters(integrationId: string, jobName: string) : Promise<Result<JenkinsParameterDefinition[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_jenkins_job_parameters", { integrationId, jobName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches a summary of a Jenkins job's configuration: SCM URLs, branches,
+ * triggers, parameters, the assigned node and referenced credentials.
+ * 
+ * Reading `config.xml` requires the Job/ExtendedRead or Configure permission.
+ */
+async fetchJenkinsJobConfigSummary(integrationId: string, jobName: string) : Promise<Result<JenkinsJobConfigSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_jenkins_job_config_summary", { integrationId, jobName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches the build retention of a Jenkins job: its build discarder, the
+ * builds the controller stores and, with the Disk Usage plugin, the disk
+ * they use. Warnings flag jobs storing more than their discarder keeps.
+ * 
+ * Without permission to read `config.xml`, the discarder is left out and a
+ * warning says so.
+ */
+async fetchJenkinsRetentionInfo(integrationId: string, jobName: string) : Promise<Result<JenkinsRetentionInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_jenkins_retention_info", { integrationId, jobName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Classifies a failed Jenkins build by matching its console log against the
+ * failure rules, and stores the classification for trend reporting.
+ */
+async classifyJenkinsBuildFailure(integrationId: string, jobName: string, buildNumber: number) : Promise<Result<FailureClassification, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("classify_jenkins_build_failure", { integrationId, jobName, buildNumber }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists the stored failure classifications of an integration, newest build
+ * first, optionally for one job.
+ */
+async listJenkinsFailureClassifications(integrationId: string, jobName: string | null) : Promise<Result<FailureClassification[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_jenkins_failure_classifications", { integrationId, jobName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Counts classified failures per category and day over the last `days`
+ * days, for one integration or all of them.
+ */
+async getJenkinsFailureTrends(integrationId: string | null, days: number) : Promise<Result<FailureTrends, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_jenkins_failure_trends", { integrationId, days }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Scans job configurations for likely leaked secrets, every top-level job
+ * when `job_names` is omitted. Findings are redacted.
+ */
+async scanJenkinsSecrets(integrationId: string, jobNames: string[] | null) : Promise<Result<SecretScanReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("scan_jenkins_secrets", { integrationId, jobNames }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Triggers a Jenkins build for a given job.
+ * 
+ * Node parameter values may list several agents separated by commas.
+ */
+async triggerJenkinsBuild(integrationId: string, jobName: string, parameters: Partial<{ [key in string]: string }> | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("trigger_jenkins_build", { integrationId, jobName, parameters }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Triggers a Jenkins job again with the exact parameters of one of its
+ * builds. Returns the parameters used.
+ */
+async rebuildJenkinsBuild(integrationId: string, jobName: string, buildNumber: number) : Promise<Result<Partial<{ [key in string]: string }>, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rebuild_jenkins_build", { integrationId, jobName, buildNumber }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replays a Jenkins Pipeline build, with its original script or with
+ * `script` as its main script.
+ */
+async replayJenkinsPipeline(integrationId: string, jobName: string, buildNumber: number, script: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("replay_jenkins_pipeline", { integrationId, jobName, buildNumber, script }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Enables or disables a Jenkins job (e.g., to pause a broken job while it is fixed).
+ */
+async setJenkinsJobEnabled(integrationId: string, jobName: string, enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_jenkins_job_enabled", { integrationId, jobName, enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches Kubernetes namespaces for a given integration.
+ */
+async fetchK8sNamespaces(integrationId: string) : Promise<Result<K8sNamespace[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_k8s_namespaces", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches Kubernetes pods in a specific namespace.
+ */
+async fetchK8sPods(integrationId: string, namespace: string) : Promise<Result<K8sPod[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_k8s_pods", { integrationId, namespace }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches Kubernetes services in a specific namespace.
+ */
+async fetchK8sServices(integrationId: string, namespace: string) : Promise<Result<K8sService[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_k8s_services", { integrationId, namespace }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches detailed information for a specific Kubernetes pod.
+ */
+async fetchK8sPodDetails(integrationId: string, namespace: string, podName: string) : Promise<Result<K8sPod, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_k8s_pod_details", { integrationId, namespace, podName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches the hosts routed by ingresses in a namespace (e.g., to add them as uptime targets).
+ */
+async fetchK8sIngressHosts(integrationId: string, namespace: string) : Promise<Result<K8sIngressHost[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_k8s_ingress_hosts", { integrationId, namespace }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches the images of the running pods with their pod and container
+ * counts and namespaces.
+ * 
+ * # Arguments
+ * * `namespaces` - Namespaces to look in; the whole cluster when empty
+ */
+async fetchK8sImageInventory(integrationId: string, namespaces: string[]) : Promise<Result<K8sImageUsage[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_k8s_image_inventory", { integrationId, namespaces }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Applies an action to every pod matching a label selector.
+ * 
+ * With `dry_run`, only returns the pods the action would affect.
+ */
+async bulkK8sPodAction(integrationId: string, namespace: string, selector: string, action: K8sPodAction, dryRun: boolean) : Promise<Result<K8sBulkPodResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("bulk_k8s_pod_action", { integrationId, namespace, selector, action, dryRun }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Checks whether deleting pods of a deployment or scaling it down keeps the
+ * guarantees of its PodDisruptionBudgets, and warns about what would break.
+ * 
+ * # Arguments
+ * * `delete_pods` - Pods about to be deleted (one when not given)
+ * * `scale_to` - Replica count about to be set
+ */
+async checkK8sEvictionSafety(integrationId: string, namespace: string, deployment: string, deletePods: number | null, scaleTo: number | null) : Promise<Result<K8sEvictionSafety, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_k8s_eviction_safety", { integrationId, namespace, deployment, deletePods, scaleTo }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Sets the image of a deployment container (e.g., for an image-bump deployment).
+ */
+async setK8sDeploymentImage(integrationId: string, namespace: string, deployment: string, container: string, image: string) : Promise<Result<K8sImageUpdate, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_k8s_deployment_image", { integrationId, namespace, deployment, container, image }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists the revisions of a deployment (newest first) with their change causes.
+ */
+async listK8sDeploymentRevisions(integrationId: string, namespace: string, deployment: string) : Promise<Result<K8sDeploymentRevision[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_k8s_deployment_revisions", { integrationId, namespace, deployment }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Rolls a deployment back to `to_revision`, or to the previous revision when omitted.
+ */
+async rollbackK8sDeployment(integrationId: string, namespace: string, deployment: string, toRevision: number | null) : Promise<Result<K8sRollback, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rollback_k8s_deployment", { integrationId, namespace, deployment, toRevision }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Creates or updates a canary deployment serving about `percent` of the traffic.
+ */
+async startCanary(integrationId: string, namespace: string, deployment: string, image: string, percent: number) : Promise<Result<K8sCanaryStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_canary", { integrationId, namespace, deployment, image, percent }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Rolls a canary's images out to the stable deployment and removes the canary.
+ */
+async promoteCanary(integrationId: string, namespace: string, deployment: string) : Promise<Result<K8sImageUpdate[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("promote_canary", { integrationId, namespace, deployment }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Removes a canary deployment, leaving the stable deployment untouched.
+ */
+async abortCanary(integrationId: string, namespace: string, deployment: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("abort_canary", { integrationId, namespace, deployment }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Switches a Service between its blue and green deployments.
+ * 
+ * Reverts to the previous color if the post-switch health checks fail.
+ */
+async switchBlueGreen(integrationId: string, namespace: string, service: string, targetColor: string) : Promise<Result<K8sBlueGreenSwitch, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("switch_blue_green", { integrationId, namespace, service, targetColor }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Estimates the monthly cost of environments from their pods' resource
+ * requests and the configured cost rates.
+ * 
+ * Estimates one environment, or every environment with a namespace when
+ * `environment_id` is not given. Each namespace is looked up on every
+ * Kubernetes integration; clusters without pods in it are left out.
+ */
+async estimateK8sCosts(environmentId: string | null) : Promise<Result<K8sEnvironmentCost[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("estimate_k8s_costs", { environmentId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches SonarQube projects for a given integration.
+ */
+async fetchSonarqubeProjects(integrationId: string) : Promise<Result<SonarQubeProject[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_sonarqube_projects", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches SonarQube metrics for a given project.
+ */
+async fetchSonarqubeMetrics(integrationId: string, projectKey: string) : Promise<Result<SonarQubeMetrics, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_sonarqube_metrics", { integrationId, projectKey }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches SonarQube quality profiles: those a project uses (one per
+ * language) when `project_key` is given, all of them otherwise.
+ */
+async fetchSonarqubeQualityProfiles(integrationId: string, projectKey: string | null) : Promise<Result<SonarQubeQualityProfile[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_sonarqube_quality_profiles", { integrationId, projectKey }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Compares the quality profile each project uses against the golden profile
+ * of its language, listing the rules that differ.
+ * 
+ * `golden_profile_keys` holds at most one profile per language; languages
+ * without one are reported without differences. All projects are checked
+ * when `project_keys` is None.
+ */
+async checkSonarqubeProfileDrift(integrationId: string, goldenProfileKeys: string[], projectKeys: string[] | null) : Promise<Result<SonarQubeProfileDrift[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_sonarqube_profile_drift", { integrationId, goldenProfileKeys, projectKeys }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches Keycloak realms for a given integration.
+ */
+async fetchKeycloakRealms(integrationId: string) : Promise<Result<KeycloakRealm[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_keycloak_realms", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches Keycloak clients for a given realm.
+ */
+async fetchKeycloakClients(integrationId: string, realm: string) : Promise<Result<KeycloakClient[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_keycloak_clients", { integrationId, realm }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches a realm's keys with their providers, algorithms and ages.
+ * 
+ * Active keys older than `max_key_age_days` (default 365) are reported as
+ * overdue for rotation.
+ */
+async fetchKeycloakRealmKeys(integrationId: string, realm: string, maxKeyAgeDays: number | null) : Promise<Result<KeycloakRealmKeys, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_keycloak_realm_keys", { integrationId, realm, maxKeyAgeDays }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Exports the users of a realm (with their attributes) as JSON or CSV.
+ */
+async exportKeycloakUsers(integrationId: string, realm: string, format: KeycloakUserFileFormat) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_keycloak_users", { integrationId, realm, format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Imports users into a realm from a JSON or CSV file (by its extension),
+ * skipping users the realm already has.
+ * 
+ * `attribute_mapping` renames source fields to user fields or attributes
+ * (an empty name drops the field). A dry run reports which users would be
+ * created without creating them.
+ */
+async importKeycloakUsers(integrationId: string, realm: string, file: string, attributeMapping: Partial<{ [key in string]: string }> | null, dryRun: boolean) : Promise<Result<KeycloakUserImportResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_keycloak_users", { integrationId, realm, file, attributeMapping, dryRun }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Emails a user a link to complete required actions, e.g. `UPDATE_PASSWORD`
+ * for a password reset or `VERIFY_EMAIL`.
+ * 
+ * The link stays valid for `lifespan_hours` (default 12, at most 720).
+ */
+async sendKeycloakActionsEmail(integrationId: string, realm: string, username: string, actions: KeycloakRequiredAction[], lifespanHours: number | null) : Promise<Result<KeycloakActionsEmail, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("send_keycloak_actions_email", { integrationId, realm, username, actions, lifespanHours }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches the projects of a demo integration.
+ */
+async fetchDemoProjects(integrationId: string) : Promise<Result<GitLabProject[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_demo_projects", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches the pipelines of a demo project.
+ */
+async fetchDemoPipelines(integrationId: string, projectId: number) : Promise<Result<GitLabPipeline[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_demo_pipelines", { integrationId, projectId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches the jobs of a demo integration.
+ */
+async fetchDemoJobs(integrationId: string) : Promise<Result<JenkinsJob[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_demo_jobs", { integrationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches the builds of a demo job.
+ */
+async fetchDemoBuilds(integrationId: string, jobName: string) : Promise<Result<JenkinsBuild[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_demo_builds", { integrationId, jobName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches the pods of a demo integration, in one namespace or all.
+ */
+async fetchDemoPods(integrationId: string, namespace: string | null) : Promise<Result<K8sPod[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_demo_pods", { integrationId, namespace }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches per-minute service metrics of a demo integration over the last
+ * `minutes` (default 60, at most a day).
+ */
+async fetchDemoMetrics(integrationId: string, minutes: number | null) : Promise<Result<DemoMetricSeries[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_demo_metrics", { integrationId, minutes }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+/**
+ * Notification sent with actions.
+ */
+export type ActionableNotification = { id: number; title: string; body: string | null; actions: NotificationAction[]; 
+/**
+ * Service the notification is about
+ */
+service: ServiceRef | null; 
+/**
+ * Owner of the service, whose Slack channel the notification targets
+ */
+owner: Ownership | null; sentAt: Timestamp }
+/**
+ * A note or bookmark on a resource.
+ */
+export type Annotation = { id: string; 
+/**
+ * Key of the annotated resource
+ */
+resource_key: string; kind: AnnotationKind; text: string; 
+/**
+ * OS user that created the annotation
+ */
+author: string; created_at: Timestamp; updated_at: Timestamp }
+export type AnnotationExportFormat = "json" | "markdown"
+export type AnnotationKind = "note" | "bookmark"
+/**
+ * Lock state reported to the frontend.
+ */
+export type AppLockStatus = { 
+/**
+ * Whether a lock passphrase is set
+ */
+passphrase_set: boolean; 
+/**
+ * Idle time before locking (None = never locks automatically)
+ */
+idle_timeout_minutes: number | null; locked: boolean }
+/**
+ * Application preferences that persist to disk.
+ * Only contains settings that should be saved between sessions.
+ */
+export type AppPreferences = { theme: string; 
+/**
+ * Global shortcut for quick pane (e.g., "CommandOrControl+Shift+.")
+ * If None, uses the default shortcut
+ */
+quick_pane_shortcut: string | null; 
+/**
+ * User's preferred language (e.g., "en", "es", "de")
+ * If None, uses system locale detection
+ */
+language: string | null; 
+/**
+ * Minutes without activity before the app locks (requires a lock passphrase)
+ * If None, the app never locks automatically
+ */
+app_lock_idle_minutes: number | null; 
+/**
+ * Local port serving internal metrics in the Prometheus text format
+ * If None, the metrics endpoint is disabled
+ */
+metrics_port: number | null; 
+/**
+ * Release channel to update from
+ * If None, the stable channel is used
+ */
+update_channel: UpdateChannel | null; 
+/**
+ * Days a release must have been published before it is offered
+ * If None, releases are offered as soon as they are published
+ */
+update_defer_days: number | null; 
+/**
+ * Local hours during which updates are installed without asking
+ * If None, available updates are only announced
+ */
+update_install_window: UpdateInstallWindow | null; 
+/**
+ * Experimental subsystems switched on or off
+ * If None, every flag has its default
+ */
+feature_flags: FeatureFlags | null }
+/**
+ * An open window.
+ */
+export type AppWindow = { label: string; 
+/**
+ * Events the window subscribed to; None when it receives every event
+ */
+subscriptions: string[] | null }
+/**
+ * A release newer than the running version.
+ */
+export type AvailableUpdate = { version: string; 
+/**
+ * Release notes (Markdown)
+ */
+notes: string | null; 
+/**
+ * Publication timestamp (RFC 3339)
+ */
+published_at: string | null; 
+/**
+ * When the deferral ends (RFC 3339), if the release is still held back
+ */
+deferred_until: string | null }
+/**
+ * Commit that went in between the two builds.
+ */
+export type BuildChange = { commit_id: string; 
+/**
+ * First line of the commit message
+ */
+title: string; author: string | null }
+export type BuildComparison = { 
+/**
+ * `build_a` of the request
+ */
+base: BuildSummary; 
+/**
+ * `build_b` of the request
+ */
+head: BuildSummary; 
+/**
+ * Head minus base; None if either build is still running
+ */
+duration_delta_seconds: number | null; 
+/**
+ * Steps of both builds, largest slowdown first
+ */
+steps: BuildStepDelta[]; 
+/**
+ * None unless both builds have a test report
+ */
+tests: TestResultDelta | null; 
+/**
+ * Commits in head that are not in base
+ */
+changes: BuildChange[] }
+/**
+ * Duration of a stage (Jenkins) or job (GitLab) in both builds.
+ */
+export type BuildStepDelta = { name: string; 
+/**
+ * None if the step didn't run in the base build
+ */
+base_seconds: number | null; 
+/**
+ * None if the step didn't run in the head build
+ */
+head_seconds: number | null; 
+/**
+ * Head minus base; None unless the step ran in both
+ */
+delta_seconds: number | null; base_status: string | null; head_status: string | null }
+/**
+ * Build or pipeline being compared.
+ */
+export type BuildSummary = { 
+/**
+ * Build number or pipeline ID
+ */
+id: string; 
+/**
+ * Status as reported by the service (e.g., "success", "failed")
+ */
+status: string; url: string; started_at: Timestamp | null; 
+/**
+ * None while the build is running
+ */
+duration_seconds: number | null; 
+/**
+ * None if the build has no test report
+ */
+tests: TestCounts | null }
+/**
+ * Certificate details of a host.
+ */
+export type CertificateInfo = { 
+/**
+ * Host name, with the port if it is not 443
+ */
+host: string; 
+/**
+ * Where the host was found (e.g., "integration: GitLab", "ingress: prod/shop")
+ */
+sources: string[]; 
+/**
+ * Subject common name
+ */
+subject: string | null; 
+/**
+ * Issuer common name
+ */
+issuer: string | null; 
+/**
+ * Expiry timestamp (RFC 3339)
+ */
+expires_at: string | null; 
+/**
+ * Whole days until expiry (negative once expired)
+ */
+days_remaining: number | null; 
+/**
+ * Check timestamp (RFC 3339)
+ */
+checked_at: string; 
+/**
+ * Why the certificate could not be read
+ */
+error: string | null }
+export type ConfigChangeKind = "item_added" | "item_removed" | "field_changed"
+/**
+ * A change between two consecutive snapshots.
+ */
+export type ConfigFieldChange = { 
+/**
+ * `id` of the changed integration or mapping
+ */
+item_id: string; kind: ConfigChangeKind; 
+/**
+ * Dotted field path; None for added and removed items
+ */
+field: string | null; 
+/**
+ * Previous value (the whole item, as JSON, for removed items)
+ */
+old_value: string | null; 
+/**
+ * New value (the whole item, as JSON, for added items)
+ */
+new_value: string | null; 
+/**
+ * Snapshot that introduced the change
+ */
+snapshot_id: string; changed_at: Timestamp; changed_by: string }
+/**
+ * Changes between two snapshots, oldest first.
+ */
+export type ConfigHistoryDiff = { entity: ConfigHistoryEntity; 
+/**
+ * Start of the range; None means before the first snapshot
+ */
+from: ConfigSnapshotInfo | null; to: ConfigSnapshotInfo; changes: ConfigFieldChange[] }
+/**
+ * Config file whose history is tracked.
+ */
+export type ConfigHistoryEntity = "integrations" | "mappings"
+/**
+ * One saved version of a config file.
+ */
+export type ConfigSnapshotInfo = { 
+/**
+ * Snapshot identifier (sortable by save time)
+ */
+id: string; saved_at: Timestamp; 
+/**
+ * OS user that saved the config
+ */
+author: string; 
+/**
+ * Number of items in the saved config
+ */
+item_count: number }
+/**
+ * Hourly prices used to estimate Kubernetes costs from resource requests.
+ */
+export type CostRate = { 
+/**
+ * Unique identifier for the rate
+ */
+id: string; 
+/**
+ * Environment this rate applies to; the default rate when not set
+ */
+environment_id: string | null; 
+/**
+ * Price of one requested CPU core per hour
+ */
+cpu_hour_price: number; 
+/**
+ * Price of one requested GiB of memory per hour
+ */
+memory_gb_hour_price: number; 
+/**
+ * Currency code (e.g., "USD", "EUR")
+ */
+currency?: string }
+/**
+ * A problem found in a credential field.
+ */
+export type CredentialIssue = { 
+/**
+ * Field the issue is about ("token", "password" or a custom key)
+ */
+field: string; severity: CredentialIssueSeverity; message: string }
+/**
+ * How serious a credential issue is.
+ */
+export type CredentialIssueSeverity = 
+/**
+ * Works, but is weak or unusual
+ */
+"warning" | 
+/**
+ * Almost certainly malformed; authentication will likely fail
+ */
+"error"
+/**
+ * Metrics of a service at one point in time.
+ */
+export type DemoMetricPoint = { timestamp: Timestamp; cpu_millicores: number; memory_mib: number; requests_per_second: number; 
+/**
+ * Share of failed requests (0 to 1)
+ */
+error_rate: number }
+/**
+ * Metrics of a service over a time window, one point per minute.
+ */
+export type DemoMetricSeries = { service: string; namespace: string; 
+/**
+ * Oldest first
+ */
+points: DemoMetricPoint[] }
+/**
+ * Expected version of a deployed service. Unset fields are not compared.
+ */
+export type DesiredService = { 
+/**
+ * Deployment name
+ */
+name: string; 
+/**
+ * Container to compare; the first container when not set
+ */
+container: string | null; 
+/**
+ * Image without its tag (e.g., "registry.example.com/team/api")
+ */
+image: string | null; 
+/**
+ * Image tag (e.g., "1.4.2")
+ */
+tag: string | null; 
+/**
+ * Helm chart version, from the deployment's `helm.sh/chart` label
+ */
+chart_version: string | null }
+/**
+ * What should be running in an environment's namespace.
+ */
+export type DesiredState = { 
+/**
+ * Environment whose namespace is compared
+ */
+environment_id: string; 
+/**
+ * Kubernetes integration of the cluster running the environment
+ */
+integration_id: string; 
+/**
+ * Expected services; deployments not listed are ignored
+ */
+services?: DesiredService[] }
+/**
+ * A new tag found by a watch.
+ */
+export type DetectedTag = { watch_id: string; project_id: number; tag: string; 
+/**
+ * Commit SHA the tag points to
+ */
+commit_sha: string; 
+/**
+ * Detection timestamp (RFC 3339)
+ */
+detected_at: string; 
+/**
+ * IDs of the flow runs started for the tag
+ */
+run_ids: string[] }
+/**
+ * A compared field whose live value differs.
+ */
+export type DriftDifference = { 
+/**
+ * "image", "tag" or "chart_version"
+ */
+field: string; expected: string; 
+/**
+ * Live value; None when it is not set
+ */
+actual: string | null }
+export type DriftStatus = "in_sync" | "drifted" | 
+/**
+ * No deployment (or container) of that name
+ */
+"missing"
+/**
+ * Result of probing an endpoint.
+ */
+export type EndpointProbe = { url: string; host: string; port: number; 
+/**
+ * Addresses the host resolved to
+ */
+resolved_addresses: string[]; 
+/**
+ * Address the TCP connection was made to
+ */
+connected_address: string | null; 
+/**
+ * HTTP status of the `HEAD` request
+ */
+http_status: number | null; stages: ProbeStage[]; 
+/**
+ * Whether every stage that ran succeeded
+ */
+success: boolean; 
+/**
+ * Total duration in milliseconds
+ */
+total_ms: number; 
+/**
+ * Human-readable verdict naming the failing stage
+ */
+diagnosis: string }
+/**
+ * An environment represents a deployment target (dev, staging, prod, etc.)
+ */
+export type Environment = { 
+/**
+ * Unique identifier for the environment
+ */
+id: string; 
+/**
+ * Environment name (e.g., "dev", "staging", "prod")
+ */
+name: string; 
+/**
+ * Kubernetes namespace (if applicable)
+ */
+namespace: string | null; 
+/**
+ * ID of the project this environment belongs to
+ */
+project_id: string; 
+/**
+ * Non-secret settings (e.g., domain, replica defaults, image registry),
+ * available to flows run in this environment as `{{ env.<key> }}`
+ */
+variables?: Partial<{ [key in string]: string }> }
+/**
+ * Drift of an environment from its desired state.
+ */
+export type EnvironmentDrift = { environment_id: string; integration_id: string; namespace: string; checked_at: Timestamp; 
+/**
+ * Services drifted or missing
+ */
+drifted: number; services: ServiceDrift[] }
+/**
+ * Kubernetes namespaces whose events are recorded for later queries.
+ */
+export type EventRecording = { 
+/**
+ * Unique identifier for the recording
+ */
+id: string; 
+/**
+ * Human-readable recording name
+ */
+name: string; 
+/**
+ * Kubernetes integration to watch
+ */
+integration_id: string; namespaces: string[]; 
+/**
+ * Days recorded events are kept
+ */
+retention_days?: number; 
+/**
+ * Record events; disabled recordings keep their history until it expires
+ */
+enabled?: boolean }
+/**
+ * State of the event stream of one namespace.
+ */
+export type EventStreamStatus = { integration_id: string; namespace: string; 
+/**
+ * Whether the watch is currently receiving events
+ */
+connected: boolean; 
+/**
+ * Events recorded since the app started
+ */
+recorded: number; 
+/**
+ * Last recorded occurrence (RFC 3339)
+ */
+last_event_at: string | null; 
+/**
+ * Why the stream last failed
+ */
+last_error: string | null }
+/**
+ * Failures of one category.
+ */
+export type FailureCategoryCount = { category: string; count: number }
+/**
+ * Failure category of a build.
+ */
+export type FailureClassification = { integration_id: string; job_name: string; build_number: number; status: JenkinsBuildStatus; 
+/**
+ * Category of the matching rule, or "unknown"
+ */
+category: string; 
+/**
+ * Rule that matched
+ */
+rule_id: string | null; 
+/**
+ * Matching line and the lines around it
+ */
+excerpt: string | null; 
+/**
+ * Line number of the match in the (possibly truncated) log
+ */
+line: number | null; build_started_at: Timestamp; classified_at: Timestamp }
+/**
+ * Log pattern that classifies a failed build.
+ */
+export type FailureRule = { 
+/**
+ * Unique identifier for the rule
+ */
+id: string; 
+/**
+ * Failure category reported for matching builds (e.g., "out_of_memory")
+ */
+category: string; 
+/**
+ * Regular expression matched against each log line (e.g., "npm ERR!")
+ */
+pattern: string }
+/**
+ * Failures per category of builds started on one day.
+ */
+export type FailureTrendPoint = { 
+/**
+ * Day (YYYY-MM-DD, UTC)
+ */
+date: string; categories: FailureCategoryCount[] }
+/**
+ * Classified failures over a period.
+ */
+export type FailureTrends = { days: number; 
+/**
+ * Failures per category, most frequent first
+ */
+totals: FailureCategoryCount[]; 
+/**
+ * Days with classified failures, oldest first
+ */
+daily: FailureTrendPoint[] }
+/**
+ * Switches for experimental subsystems, so they can ship turned off and be
+ * enabled per user. Flags missing from the preferences take their default.
+ */
+export type FeatureFlags = { 
+/**
+ * Kubernetes watch streams (the event recorder)
+ */
+watch_streams: boolean; 
+/**
+ * Local webhook receiver
+ */
+webhook_receiver: boolean; 
+/**
+ * Flow runs, however they are started
+ */
+flow_engine: boolean }
+/**
+ * Condition on one field of a resource.
+ */
+export type FilterCondition = { field: string; operator?: FilterOperator; value: string }
+/**
+ * A resource matched by a filter.
+ */
+export type FilterMatch = { 
+/**
+ * Identifies the resource across evaluations (e.g., "prod/api-7d9f")
+ */
+key: string; fields: Partial<{ [key in string]: string }> }
+export type FilterOperator = "equals" | "not_equals" | "contains" | 
+/**
+ * `*` matches any characters
+ */
+"matches" | 
+/**
+ * Comma-separated list of values
+ */
+"one_of"
+/**
+ * Outcome recorded for a node.
+ */
+export type FixtureOutcome = "succeeded" | "failed"
+/**
+ * Complete flow data including nodes and edges
+ */
+export type Flow = { id: string; name: string; created_at: string; updated_at: string; nodes: JsonValue; edges: JsonValue; viewport: JsonValue | null }
+/**
+ * Recorded node results for a simulated run, keyed by node ID.
+ */
+export type FlowFixtures = { nodes?: Partial<{ [key in string]: NodeFixture }> }
+/**
+ * Type of value an input node asks for.
+ */
+export type FlowInputKind = "text" | "number" | 
+/**
+ * One of the request's `options`
+ */
+"choice"
+/**
+ * A request for user input, emitted as the `flow-input-requested` event.
+ */
+export type FlowInputRequest = { 
+/**
+ * Run waiting for the input
+ */
+run_id: string; 
+/**
+ * Input node waiting for the input
+ */
+node_id: string; 
+/**
+ * Question shown to the user
+ */
+prompt: string; 
+/**
+ * Expected value type
+ */
+input_type: FlowInputKind; 
+/**
+ * Allowed values for `choice` inputs
+ */
+options: string[]; 
+/**
+ * Value suggested to the user
+ */
+default_value: string | null }
+/**
+ * Flow metadata for listing saved flows
+ */
+export type FlowMetadata = { id: string; name: string; created_at: string; updated_at: string }
+/**
+ * Execution state of a single node within a flow run.
+ */
+export type FlowNodeRun = { 
+/**
+ * ID of the node in the flow graph
+ */
+node_id: string; 
+/**
+ * Node label at the time the run started
+ */
+label: string; 
+/**
+ * Current node status
+ */
+status: FlowNodeStatus; 
+/**
+ * Human-readable detail (wait target, error message, etc.)
+ */
+message: string | null; 
+/**
+ * Start timestamp (RFC 3339)
+ */
+started_at: string | null; 
+/**
+ * Completion timestamp (RFC 3339)
+ */
+finished_at: string | null; 
+/**
+ * Nested run started by a `subflow` node
+ */
+child_run_id: string | null }
+/**
+ * Status of a single node within a flow run.
+ */
+export type FlowNodeStatus = 
+/**
+ * Not started yet
+ */
+"pending" | 
+/**
+ * Currently executing
+ */
+"running" | 
+/**
+ * Paused by a wait node, time-window gate or pending user input
+ */
+"waiting" | "succeeded" | "failed" | 
+/**
+ * Not executed because an upstream node did not succeed
+ */
+"skipped" | "cancelled"
+/**
+ * A single execution of a saved flow.
+ */
+export type FlowRun = { 
+/**
+ * Unique run identifier
+ */
+id: string; 
+/**
+ * ID of the flow being executed
+ */
+flow_id: string; 
+/**
+ * Flow name at the time the run started
+ */
+flow_name: string; 
+/**
+ * What started the run automatically (e.g., "webhook: gitlab tag_push");
+ * `None` for manual runs
+ */
+triggered_by: string | null; 
+/**
+ * Run that started this one through a `subflow` node
+ */
+parent_run_id: string | null; 
+/**
+ * `subflow` node of the parent run
+ */
+parent_node_id: string | null; 
+/**
+ * Whether the run is a simulation against fixtures
+ */
+simulated: boolean; 
+/**
+ * Environment the run deploys to, when started in one
+ */
+environment_id: string | null; 
+/**
+ * Overall run status
+ */
+status: FlowRunStatus; 
+/**
+ * Start timestamp (RFC 3339)
+ */
+started_at: string; 
+/**
+ * Completion timestamp (RFC 3339)
+ */
+finished_at: string | null; 
+/**
+ * Per-node execution state, in execution order
+ */
+nodes: FlowNodeRun[]; 
+/**
+ * Input nodes currently waiting for `provide_flow_input`
+ */
+pending_inputs: FlowInputRequest[]; 
+/**
+ * Names of the locks the run holds or waits for
+ */
+locks: string[]; 
+/**
+ * Lock the run is queued on, while `status` is `queued`
+ */
+queued_on: string | null }
+/**
+ * A file attached to a flow run.
+ */
+export type FlowRunArtifact = { 
+/**
+ * Node that produced the artifact
+ */
+node_id: string; 
+/**
+ * File name (e.g., "release-notes.md")
+ */
+name: string; size_bytes: number; 
+/**
+ * Last modification timestamp (RFC 3339)
+ */
+created_at: string }
+/**
+ * Overall status of a flow run.
+ */
+export type FlowRunStatus = 
+/**
+ * Waiting for a lock held by another run
+ */
+"queued" | "running" | "succeeded" | "failed" | "cancelled"
+/**
+ * A generated report file.
+ */
+export type GeneratedReport = { report_id: string; 
+/**
+ * File name within the reports directory
+ */
+file_name: string; format: ReportFormat; generated_at: Timestamp }
+/**
+ * API a GitLab read goes through.
+ * 
+ * GraphQL reads related data in one round-trip; REST works on instances
+ * where GraphQL is disabled. Mutations always use REST.
+ */
+export type GitLabApi = "rest" | "graphql"
+/**
+ * Open issues of a milestone at the end of a day.
+ */
+export type GitLabBurndownPoint = { 
+/**
+ * Day (YYYY-MM-DD)
+ */
+date: string; remaining: number }
+/**
+ * Result of linting CI configuration.
+ */
+export type GitLabCiLintResult = { 
+/**
+ * Project ID
+ */
+project_id: number; valid: boolean; 
+/**
+ * Errors (e.g., "jobs:build config contains unknown keys: scrip")
+ */
+errors: string[]; warnings: string[]; 
+/**
+ * Names of the jobs the configuration defines
+ */
+jobs: string[]; 
+/**
+ * Configuration with includes expanded (valid configurations only)
+ */
+merged_yaml: string | null }
+/**
+ * Group epic with the counts of its issues.
+ */
+export type GitLabEpic = { 
+/**
+ * Epic ID
+ */
+id: number; 
+/**
+ * Epic number within the group
+ */
+iid: number; title: string; 
+/**
+ * Epic state ("opened" or "closed")
+ */
+state: string; 
+/**
+ * Start date (YYYY-MM-DD)
+ */
+start_date: string | null; 
+/**
+ * Due date (YYYY-MM-DD)
+ */
+due_date: string | null; web_url: string; opened: number; closed: number; 
+/**
+ * Share of closed issues (0-100)
+ */
+progress_percent: number }
+/**
+ * Epics of a group.
+ */
+export type GitLabEpicList = { 
+/**
+ * Group ID or full path
+ */
+group: string; 
+/**
+ * False when epics are not available (GitLab Premium feature) or not
+ * visible to the token
+ */
+licensed: boolean; epics: GitLabEpic[] }
+/**
+ * Text file read from a repository.
+ */
+export type GitLabFile = { 
+/**
+ * Project ID
+ */
+project_id: number; file_name: string; 
+/**
+ * Path within the repository (e.g., "charts/api/values.yaml")
+ */
+file_path: string; 
+/**
+ * Branch, tag or commit the file was read at
+ */
+ref_name: string; 
+/**
+ * Last commit that changed the file
+ */
+last_commit_id: string; 
+/**
+ * Size of the whole file
+ */
+size_bytes: number; content: string; 
+/**
+ * Whether the content was cut at 1MB
+ */
+truncated: boolean }
+/**
+ * Deploy freeze period of a project, with its state at fetch time.
+ */
+export type GitLabFreezePeriod = { 
+/**
+ * Freeze period ID
+ */
+id: number; 
+/**
+ * Cron expression starting the freeze (e.g., "0 23 * * 5")
+ */
+freeze_start: string; 
+/**
+ * Cron expression ending the freeze (e.g., "0 7 * * 1")
+ */
+freeze_end: string; 
+/**
+ * Time zone of the cron expressions (e.g., "UTC")
+ */
+cron_timezone: string; 
+/**
+ * Whether the freeze is in effect now
+ */
+active: boolean; 
+/**
+ * Start of the current freeze, or of the next one when inactive
+ */
+starts_at: Timestamp | null; 
+/**
+ * End of the current or next freeze
+ */
+ends_at: Timestamp | null }
+/**
+ * Project as recorded in inventory snapshots.
+ */
+export type GitLabInventoryProject = { id: number; name: string; 
+/**
+ * Full path (e.g., "platform/checkout-api")
+ */
+path_with_namespace: string; 
+/**
+ * Missing for empty repositories
+ */
+default_branch: string | null; 
+/**
+ * "private", "internal" or "public"
+ */
+visibility: string; archived?: boolean; web_url: string }
+/**
+ * GitLab issue representation.
+ */
+export type GitLabIssue = { 
+/**
+ * Issue ID
+ */
+id: number; 
+/**
+ * Issue number within the project
+ */
+iid: number; title: string; 
+/**
+ * Issue state ("opened" or "closed")
+ */
+state: string; labels?: string[]; milestone: GitLabMilestoneRef | null; assignees?: GitLabUserRef[]; 
+/**
+ * Web URL to access the issue
+ */
+web_url: string; 
+/**
+ * Creation time
+ */
+created_at: Timestamp; 
+/**
+ * Last update time
+ */
+updated_at: Timestamp; 
+/**
+ * Due date (YYYY-MM-DD)
+ */
+due_date: string | null }
+/**
+ * Issues of a project with milestone and label aggregation.
+ */
+export type GitLabIssueSummary = { 
+/**
+ * Project ID
+ */
+project_id: number; opened: number; closed: number; 
+/**
+ * Per milestone, soonest due date first (issues without a milestone last)
+ */
+milestones: GitLabMilestoneSummary[]; 
+/**
+ * Per label, most open issues first
+ */
+labels: GitLabLabelSummary[]; issues: GitLabIssue[]; 
+/**
+ * Whether more issues matched than were fetched
+ */
+truncated: boolean }
+/**
+ * Issue counts of a label.
+ */
+export type GitLabLabelSummary = { label: string; opened: number; closed: number }
+/**
+ * Open merge request of a project.
+ */
+export type GitLabMergeRequest = { 
+/**
+ * Merge request number within the project
+ */
+iid: number; title: string; 
+/**
+ * State ("opened", "merged", "closed", "locked")
+ */
+state: string; source_branch: string; target_branch: string; 
+/**
+ * Username of the author
+ */
+author: string | null; draft: boolean; web_url: string; updated_at: Timestamp }
+/**
+ * Project milestone with its issue counts and burn-down.
+ */
+export type GitLabMilestone = { 
+/**
+ * Milestone ID
+ */
+id: number; 
+/**
+ * Milestone number within the project
+ */
+iid: number; title: string; 
+/**
+ * Milestone state ("active" or "closed")
+ */
+state: string; 
+/**
+ * Start date (YYYY-MM-DD)
+ */
+start_date: string | null; 
+/**
+ * Due date (YYYY-MM-DD)
+ */
+due_date: string | null; web_url: string; opened: number; closed: number; 
+/**
+ * Share of closed issues (0-100)
+ */
+progress_percent: number; 
+/**
+ * Share of the time from start to due date that has passed (0-100)
+ */
+elapsed_percent: number | null; 
+/**
+ * Open issues per day from the start date (or first issue) until today
+ * or the due date
+ */
+burndown: GitLabBurndownPoint[] }
+/**
+ * Milestone an issue belongs to.
+ */
+export type GitLabMilestoneRef = { 
+/**
+ * Milestone ID
+ */
+id: number; 
+/**
+ * Milestone title (e.g., "Sprint 42")
+ */
+title: string; 
+/**
+ * Due date (YYYY-MM-DD)
+ */
+due_date: string | null }
+/**
+ * Issue counts of a milestone.
+ */
+export type GitLabMilestoneSummary = { 
+/**
+ * Milestone title; None groups issues without a milestone
+ */
+milestone: string | null; 
+/**
+ * Due date (YYYY-MM-DD)
+ */
+due_date: string | null; opened: number; closed: number; 
+/**
+ * Share of closed issues (0-100)
+ */
+progress_percent: number }
+/**
+ * Package of a project's registry with its versions.
+ */
+export type GitLabPackage = { name: string; 
+/**
+ * Package format ("maven", "npm", "pypi", "generic", ...)
+ */
+package_type: string; 
+/**
+ * Newest published version (status "default")
+ */
+latest_version: string | null; 
+/**
+ * Versions, newest first
+ */
+versions: GitLabPackageVersion[] }
+/**
+ * Published version of a package.
+ */
+export type GitLabPackageVersion = { 
+/**
+ * Package ID (each version is a package of its own in GitLab)
+ */
+id: number; version: string; 
+/**
+ * Package status ("default", "hidden", "processing", "error", ...)
+ */
+status: string; created_at: Timestamp }
+/**
+ * GitLab pipeline representation.
+ */
+export type GitLabPipeline = { 
+/**
+ * Pipeline ID
+ */
+id: number; 
+/**
+ * Pipeline status (e.g., "success", "failed", "running", "pending")
+ */
+status: string; 
+/**
+ * Git reference (branch or tag)
+ */
+ref: string; 
+/**
+ * Creation time
+ */
+created_at: Timestamp }
+/**
+ * GitLab project representation.
+ */
+export type GitLabProject = { 
+/**
+ * Project ID
+ */
+id: number; 
+/**
+ * Project name
+ */
+name: string; 
+/**
+ * Project path (e.g., "group/project")
+ */
+path: string; 
+/**
+ * Web URL to access the project
+ */
+web_url: string }
+/**
+ * Project with its latest pipelines and open merge requests, as shown on
+ * the dashboard.
+ */
+export type GitLabProjectOverview = { project: GitLabProject; 
+/**
+ * Latest pipelines, newest first
+ */
+pipelines: GitLabPipeline[]; 
+/**
+ * Open merge requests, most recently updated first
+ */
+merge_requests: GitLabMergeRequest[]; 
+/**
+ * API the overview was read through
+ */
+api: GitLabApi }
+/**
+ * Snippet tagged as a runbook (`#runbook`).
+ */
+export type GitLabRunbook = { 
+/**
+ * Snippet ID
+ */
+id: number; 
+/**
+ * Project ID (None for personal snippets)
+ */
+project_id: number | null; 
+/**
+ * Title without tags
+ */
+title: string; 
+/**
+ * Description without tags
+ */
+description: string | null; 
+/**
+ * Services from `service:<name>` tags
+ */
+services: string[]; file_name: string; 
+/**
+ * Web URL to access the snippet
+ */
+web_url: string; 
+/**
+ * Last update time
+ */
+updated_at: Timestamp; author: GitLabUserRef | null }
+/**
+ * Content of a runbook snippet.
+ */
+export type GitLabRunbookContent = { 
+/**
+ * Snippet ID
+ */
+id: number; title: string; 
+/**
+ * File name (the extension tells how to render it, e.g., ".md")
+ */
+file_name: string; content: string; 
+/**
+ * Whether the content was cut at 1MB
+ */
+truncated: boolean }
+/**
+ * GitLab user reference (e.g., an issue assignee).
+ */
+export type GitLabUserRef = { 
+/**
+ * Username (e.g., "jdoe")
+ */
+username: string; 
+/**
+ * Display name
+ */
+name: string }
+/**
+ * GitLab webhook representation.
+ */
+export type GitLabWebhook = { 
+/**
+ * Webhook ID
+ */
+id: number; 
+/**
+ * Webhook URL
+ */
+url: string; 
+/**
+ * List of events this webhook subscribes to
+ */
+events: string[] }
+/**
+ * SBOM of an image.
+ */
+export type ImageSbom = { image: string; source: SbomSource; format: SbomFormat; 
+/**
+ * Packages sorted by type and name
+ */
+packages: SbomPackage[] }
+/**
+ * Harbor instance whose vulnerability scans gate deployments of its images.
+ */
+export type ImageScanner = { 
+/**
+ * Unique identifier for the scanner
+ */
+id: string; 
+/**
+ * Human-readable scanner name
+ */
+name: string; 
+/**
+ * Harbor URL (e.g., "https://harbor.example.com")
+ */
+base_url: string; 
+/**
+ * Registry hosts whose images are checked; the host of `base_url` when empty
+ */
+registry_hosts?: string[]; 
+/**
+ * Keyring entry holding the scanner credentials (defaults to the scanner ID)
+ * Anonymous access is used when no credentials are stored
+ */
+credentials_ref: string | null; 
+/**
+ * Critical vulnerabilities allowed before deploys are blocked
+ */
+max_critical?: number; 
+/**
+ * Block deploys of images without a successful scan
+ */
+block_unscanned?: boolean }
+/**
+ * Security check result of an image.
+ */
+export type ImageSecurityReport = { 
+/**
+ * Image reference that was checked
+ */
+image: string; status: ImageSecurityStatus; 
+/**
+ * Name of the scanner consulted
+ */
+scanner: string | null; 
+/**
+ * Artifact digest
+ */
+digest: string | null; 
+/**
+ * Scan status reported by the scanner (e.g., "Success", "Running")
+ */
+scan_status: string | null; critical_count: number; high_count: number; 
+/**
+ * Critical vulnerabilities allowed by the scanner
+ */
+max_critical: number; 
+/**
+ * Critical vulnerabilities found
+ */
+offending: ImageVulnerability[]; message: string }
+/**
+ * Outcome of an image security check.
+ */
+export type ImageSecurityStatus = 
+/**
+ * Scanned and within the allowed number of critical vulnerabilities
+ */
+"passed" | 
+/**
+ * Deploys of this image are blocked
+ */
+"blocked" | 
+/**
+ * No successful scan yet (allowed by the scanner)
+ */
+"not_scanned" | 
+/**
+ * No scanner is configured for the image's registry
+ */
+"unavailable"
+/**
+ * A vulnerability reported by the registry scanner.
+ */
+export type ImageVulnerability = { 
+/**
+ * Vulnerability ID (e.g., "CVE-2024-3094")
+ */
+id: string; 
+/**
+ * Affected package
+ */
+package: string; 
+/**
+ * Installed package version
+ */
+version: string; 
+/**
+ * First version with a fix (if any)
+ */
+fix_version: string | null; 
+/**
+ * Severity ("Critical", "High", "Medium", "Low", "Unknown")
+ */
+severity: string; 
+/**
+ * Link to the advisory
+ */
+link: string | null }
+export type InitialSyncState = 
+/**
+ * Crawling; more pages follow
+ */
+"in_progress" | "completed"
+/**
+ * Progress of the initial sync of an integration.
+ */
+export type InitialSyncStatus = { integration_id: string; state: InitialSyncState; 
+/**
+ * Next page to crawl (the cursor)
+ */
+next_page: number; 
+/**
+ * Projects read so far
+ */
+items: number; 
+/**
+ * Whether a slice is crawling right now
+ */
+running: boolean; started_at: Timestamp; updated_at: Timestamp; completed_at: Timestamp | null; 
+/**
+ * Why the last slice stopped early; it is retried on the next tick
+ */
+last_error: string | null }
+/**
+ * Integration configuration (does not contain credentials)
+ */
+export type Integration = { 
+/**
+ * Unique identifier for the integration
+ */
+id: string; 
+/**
+ * Type of integration
+ */
+type: IntegrationType; 
+/**
+ * Human-readable integration name
+ */
+name: string; 
+/**
+ * Base URL of the integration service
+ */
+base_url: string; 
+/**
+ * Reference to credentials stored in OS keyring
+ * This is the key used to retrieve credentials from keyring
+ */
+credentials_ref: string | null; 
+/**
+ * Remote API version to assume (e.g., "13.12") instead of detecting it
+ * For self-hosted instances that can't report their version
+ */
+api_version: string | null }
+/**
+ * API version of an integration and the shims applied to its responses.
+ */
+export type IntegrationApiVersion = { integration_id: string; 
+/**
+ * Version in use (e.g., "13.12"); None when it could not be detected
+ */
+version: string | null; 
+/**
+ * Whether the version is pinned on the integration instead of detected
+ */
+pinned: boolean; shims: string[] }
+/**
+ * Credentials for an integration (stored securely in OS keyring)
+ */
+export type IntegrationCredentials = { 
+/**
+ * API token or access token
+ */
+token: string | null; 
+/**
+ * Username (if applicable)
+ */
+username: string | null; 
+/**
+ * Password (if applicable)
+ */
+password: string | null; 
+/**
+ * Additional custom fields as key-value pairs
+ */
+custom?: Partial<{ [key in string]: string }> }
+/**
+ * Polling load on one integration.
+ */
+export type IntegrationPollingCost = { integration_id: string; name: string; integration_type: IntegrationType; requests_per_hour: number; streams: number; rate_limit: KnownRateLimit | null; 
+/**
+ * Share of the rate limit used, in percent
+ */
+limit_usage_percent: number | null }
+/**
+ * Type of integration system
+ */
+export type IntegrationType = "gitlab" | "jenkins" | "kubernetes" | "sonarqube" | "keycloak" | 
+/**
+ * Built-in synthetic data, for evaluating the app without credentials
+ */
+"demo"
+/**
+ * Jenkins build representation.
+ */
+export type JenkinsBuild = { 
+/**
+ * Build number
+ */
+number: number; 
+/**
+ * Build status
+ */
+status: JenkinsBuildStatus; 
+/**
+ * Build start time
+ */
+timestamp: Timestamp; 
+/**
+ * Build URL
+ */
+url: string; 
+/**
+ * Build duration in milliseconds (None if still building, as string to avoid i64 BigInt issues)
+ */
+duration: string | null }
+/**
+ * Build discarder (log rotation) settings of a job. Unset limits keep
+ * builds or artifacts regardless of that limit.
+ */
+export type JenkinsBuildDiscarder = { days_to_keep: number | null; num_to_keep: number | null; artifact_days_to_keep: number | null; artifact_num_to_keep: number | null }
+/**
+ * Jenkins build status enumeration.
+ */
+export type JenkinsBuildStatus = "success" | "failure" | "unstable" | "aborted" | "notbuilt" | "building" | "pending"
+/**
+ * Jenkins credential metadata (never includes the secret).
+ */
+export type JenkinsCredentialMetadata = { 
+/**
+ * Credential ID referenced by jobs (e.g., "registry-push")
+ */
+id: string; 
+/**
+ * Credential type (e.g., "Username with password", "Secret text")
+ */
+type_name: string; 
+/**
+ * Display name (secrets masked by Jenkins)
+ */
+display_name: string | null; description: string | null; 
+/**
+ * Store the credential lives in: "system" or the folder path
+ */
+store: string; 
+/**
+ * Credentials domain within the store ("global" for the unrestricted domain)
+ */
+domain: string }
+/**
+ * Jenkins job representation.
+ */
+export type JenkinsJob = { 
+/**
+ * Job name
+ */
+name: string; 
+/**
+ * Job URL
+ */
+url: string; 
+/**
+ * Job color/status indicator (e.g., "blue" for success, "red" for failure, "notbuilt" for not built)
+ */
+color: string }
+/**
+ * Parameter declared in a job's configuration.
+ */
+export type JenkinsJobConfigParameter = { name: string; 
+/**
+ * Definition type (e.g., "StringParameterDefinition")
+ */
+type_name: string; default_value: string | null }
+/**
+ * Selected fields of a job's `config.xml`.
+ */
+export type JenkinsJobConfigSummary = { 
+/**
+ * Full job name (e.g., "team-a/deploy-api")
+ */
+job_name: string; 
+/**
+ * Root element of the configuration (e.g., "flow-definition" for
+ * pipelines, "project" for freestyle jobs)
+ */
+job_type: string; description: string | null; disabled: boolean; 
+/**
+ * Git remote URLs the job checks out
+ */
+scm_urls: string[]; 
+/**
+ * Branch specifiers (e.g., "*/main")
+ */
+branches: string[]; 
+/**
+ * Jenkinsfile path of pipelines loaded from SCM
+ */
+script_path: string | null; triggers: JenkinsJobTrigger[]; parameters: JenkinsJobConfigParameter[]; 
+/**
+ * Label expression or agent the job is restricted to
+ */
+assigned_node: string | null; 
+/**
+ * IDs of the credentials the configuration references (system or folder
+ * stores)
+ */
+credential_ids: string[] }
+/**
+ * Build trigger configured on a job.
+ */
+export type JenkinsJobTrigger = { 
+/**
+ * Trigger type (e.g., "TimerTrigger", "SCMTrigger", "GitLabPushTrigger")
+ */
+kind: string; 
+/**
+ * Cron schedule of timer and SCM polling triggers
+ */
+spec: string | null }
+/**
+ * Parameter definition of a parameterized job.
+ */
+export type JenkinsParameterDefinition = { name: string; kind: JenkinsParameterKind; 
+/**
+ * Definition type reported by Jenkins (e.g., "LabelParameterDefinition")
+ */
+type_name: string; description: string | null; default_value: string | null; 
+/**
+ * Allowed values: the choices of a choice parameter, the agents a node
+ * parameter allows, or the agent labels known to Jenkins for a label parameter
+ */
+choices: string[]; 
+/**
+ * Node parameters only: several agents may be selected (comma-separated
+ * when triggering), starting one build per agent
+ */
+multiple: boolean }
+/**
+ * Kind of a job parameter.
+ */
+export type JenkinsParameterKind = "string" | "text" | "boolean" | "choice" | "password" | 
+/**
+ * Label expression selecting the agents to build on (Node and Label Parameter plugin)
+ */
+"label" | 
+/**
+ * Agent name(s) to build on (Node and Label Parameter plugin)
+ */
+"node" | "other"
+/**
+ * Build retention of a job: its discarder settings and the builds it stores.
+ */
+export type JenkinsRetentionInfo = { job_name: string; 
+/**
+ * Discarder settings (None when none is configured or `config.xml`
+ * couldn't be read)
+ */
+discarder: JenkinsBuildDiscarder | null; 
+/**
+ * Builds stored on the controller
+ */
+stored_builds: number; 
+/**
+ * Builds marked "keep forever", which discarders skip
+ */
+kept_forever: number; oldest_build_at: Timestamp | null; newest_build_at: Timestamp | null; 
+/**
+ * Disk used by the job in bytes, when a disk usage plugin reports it
+ */
+disk_usage_bytes: number | null; 
+/**
+ * Storage concerns (e.g., no discarder, more builds than it keeps)
+ */
+warnings: string[] }
+export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+/**
+ * Result of a blue-green Service switch.
+ */
+export type K8sBlueGreenSwitch = { 
+/**
+ * Namespace of the Service
+ */
+namespace: string; 
+/**
+ * Service name
+ */
+service: string; 
+/**
+ * Color selected before the switch
+ */
+previous_color: string; 
+/**
+ * Color selected now ("blue" or "green")
+ */
+active_color: string; 
+/**
+ * Deployments now receiving traffic
+ */
+deployments: string[] }
+/**
+ * Result (or dry-run preview) of a bulk pod action.
+ */
+export type K8sBulkPodResult = { namespace: string; selector: string; action: K8sPodAction; 
+/**
+ * Whether the pods were only listed
+ */
+dry_run: boolean; pods: K8sBulkPodTarget[]; 
+/**
+ * Pods the action failed on
+ */
+failed: number }
+/**
+ * A pod affected by a bulk action.
+ */
+export type K8sBulkPodTarget = { 
+/**
+ * Pod name
+ */
+name: string; 
+/**
+ * Pod phase (e.g., "Running")
+ */
+status: string; 
+/**
+ * Controlling owner (e.g., "ReplicaSet/api-7d9f8"); deleted pods without
+ * one are not recreated
+ */
+owner: string | null; 
+/**
+ * Why the action failed on this pod
+ */
+error: string | null }
+/**
+ * Canary deployment running next to a stable deployment.
+ */
+export type K8sCanaryStatus = { 
+/**
+ * Namespace of both deployments
+ */
+namespace: string; 
+/**
+ * Stable deployment name
+ */
+deployment: string; 
+/**
+ * Canary deployment name (e.g., "api-canary")
+ */
+canary_deployment: string; 
+/**
+ * Image run by the canary
+ */
+image: string; 
+/**
+ * Replicas of the stable deployment
+ */
+stable_replicas: number; 
+/**
+ * Replicas of the canary deployment
+ */
+canary_replicas: number; 
+/**
+ * Approximate share of traffic served by the canary (0-100)
+ */
+traffic_percent: number }
+/**
+ * Image of a container in a pod template.
+ */
+export type K8sContainerImage = { name: string; image: string }
+/**
+ * Monthly cost estimate of a namespace's resource requests.
+ */
+export type K8sCostEstimate = { requests: K8sResourceRequests; cpu_monthly_cost: number; memory_monthly_cost: number; monthly_cost: number; 
+/**
+ * Currency of the rate used (e.g., "USD")
+ */
+currency: string }
+/**
+ * A revision of a deployment, backed by one of its ReplicaSets.
+ */
+export type K8sDeploymentRevision = { 
+/**
+ * Value of the `deployment.kubernetes.io/revision` annotation
+ */
+revision: number; replica_set: string; 
+/**
+ * Value of the `kubernetes.io/change-cause` annotation
+ */
+change_cause: string | null; 
+/**
+ * Containers of the revision's pod template, in order
+ */
+containers: K8sContainerImage[]; created_at: Timestamp | null; replicas: number; 
+/**
+ * Whether the deployment currently runs this revision
+ */
+current: boolean }
+/**
+ * PodDisruptionBudget covering the pods of a deployment.
+ */
+export type K8sDisruptionBudget = { name: string; 
+/**
+ * Pods that must stay available (a count or a percentage, e.g., "50%")
+ */
+min_available: string | null; 
+/**
+ * Pods that may be unavailable (a count or a percentage)
+ */
+max_unavailable: string | null; 
+/**
+ * Healthy pods the budget currently counts
+ */
+current_healthy: number; 
+/**
+ * Healthy pods the budget requires
+ */
+desired_healthy: number; 
+/**
+ * Evictions the budget currently allows
+ */
+disruptions_allowed: number }
+/**
+ * Cost estimate of an environment's namespace on one cluster.
+ */
+export type K8sEnvironmentCost = { environment_id: string; environment_name: string; 
+/**
+ * Kubernetes integration (cluster) the namespace was read from
+ */
+integration_id: string; integration_name: string; estimate: K8sCostEstimate | null; 
+/**
+ * Why the namespace could not be estimated
+ */
+error: string | null }
+/**
+ * Kubernetes event (`core/v1` Event) about an object in a namespace.
+ */
+export type K8sEvent = { 
+/**
+ * Event UID; repeated occurrences share it and increase `count`
+ */
+uid: string; namespace: string; 
+/**
+ * Kind of the object the event is about (e.g., "Pod")
+ */
+object_kind: string; 
+/**
+ * Name of the object the event is about
+ */
+object_name: string; 
+/**
+ * Event type ("Normal" or "Warning")
+ */
+type: string; 
+/**
+ * Machine-readable reason (e.g., "BackOff", "Scheduled")
+ */
+reason: string; message: string; 
+/**
+ * Component that reported the event (e.g., "kubelet")
+ */
+source: string | null; 
+/**
+ * Number of occurrences
+ */
+count: number; 
+/**
+ * First occurrence
+ */
+first_seen: Timestamp; 
+/**
+ * Most recent occurrence
+ */
+last_seen: Timestamp }
+/**
+ * Whether pods of a deployment can be deleted or the deployment scaled down
+ * without breaking its availability guarantees.
+ */
+export type K8sEvictionSafety = { namespace: string; deployment: string; desired_replicas: number; ready_replicas: number; budgets: K8sDisruptionBudget[]; 
+/**
+ * Pods that can be deleted now (the budgets' allowance, or all ready
+ * pods but one when no budget covers the deployment)
+ */
+disruptions_allowed: number; 
+/**
+ * Fewest replicas that keep the budgets' minimum available
+ */
+min_safe_replicas: number; 
+/**
+ * Whether the checked action keeps the guarantees
+ */
+safe: boolean; warnings: string[] }
+/**
+ * Result of changing a deployment container's image.
+ */
+export type K8sImageUpdate = { 
+/**
+ * Namespace of the deployment
+ */
+namespace: string; 
+/**
+ * Deployment name
+ */
+deployment: string; 
+/**
+ * Container whose image was changed
+ */
+container: string; 
+/**
+ * Image before the update (if set)
+ */
+previous_image: string | null; 
+/**
+ * Image after the update
+ */
+image: string }
+/**
+ * A container image running in the cluster.
+ */
+export type K8sImageUsage = { 
+/**
+ * Image reference as written in the pod specs (e.g., "registry.example.com/team/api:1.2")
+ */
+image: string; 
+/**
+ * Digests the running containers resolved the image to
+ */
+digests: string[]; 
+/**
+ * Running pods with a container of this image
+ */
+pods: number; 
+/**
+ * Running containers of this image
+ */
+containers: number; 
+/**
+ * Namespaces the image runs in, sorted
+ */
+namespaces: string[] }
+/**
+ * Host routed by a Kubernetes ingress.
+ */
+export type K8sIngressHost = { 
+/**
+ * Namespace of the ingress
+ */
+namespace: string; 
+/**
+ * Ingress name
+ */
+ingress: string; 
+/**
+ * Host name (e.g., "shop.example.com")
+ */
+host: string; 
+/**
+ * Whether the host is served over TLS
+ */
+tls: boolean; 
+/**
+ * URL of the host (e.g., "https://shop.example.com")
+ */
+url: string }
+/**
+ * Kubernetes namespace representation.
+ */
+export type K8sNamespace = { 
+/**
+ * Namespace name
+ */
+name: string; 
+/**
+ * Namespace status (e.g., "Active", "Terminating")
+ */
+status: string; 
+/**
+ * Creation time (None if not reported)
+ */
+created_at: Timestamp | null }
+/**
+ * Kubernetes pod representation.
+ */
+export type K8sPod = { 
+/**
+ * Pod name
+ */
+name: string; 
+/**
+ * Namespace the pod belongs to
+ */
+namespace: string; 
+/**
+ * Pod status (e.g., "Running", "Pending", "Failed", "Succeeded")
+ */
+status: string; 
+/**
+ * List of container names in the pod
+ */
+containers: string[]; 
+/**
+ * Node name where the pod is running
+ */
+node: string | null }
+/**
+ * Action applied to every pod matching a label selector.
+ */
+export type K8sPodAction = 
+/**
+ * Delete the pods; pods owned by a controller are recreated
+ */
+{ type: "delete" } | 
+/**
+ * Set annotations on the pods; a null value removes the annotation
+ */
+{ type: "annotate"; annotations: Partial<{ [key in string]: string | null }> }
+/**
+ * CPU and memory requested by the active pods of a namespace.
+ */
+export type K8sResourceRequests = { 
+/**
+ * Namespace name
+ */
+namespace: string; 
+/**
+ * Running or pending pods
+ */
+pods: number; 
+/**
+ * Pods without CPU or memory requests (not priced)
+ */
+pods_without_requests: number; 
+/**
+ * Requested CPU in cores
+ */
+cpu_cores: number; 
+/**
+ * Requested memory in GiB
+ */
+memory_gib: number }
+/**
+ * Result of rolling a deployment back to an earlier revision.
+ */
+export type K8sRollback = { namespace: string; deployment: string; 
+/**
+ * Revision running before the rollback
+ */
+from_revision: number | null; 
+/**
+ * Revision whose pod template was restored
+ */
+to_revision: number; 
+/**
+ * Containers of the restored pod template
+ */
+containers: K8sContainerImage[] }
+/**
+ * Kubernetes service representation.
+ */
+export type K8sService = { 
+/**
+ * Service name
+ */
+name: string; 
+/**
+ * Namespace the service belongs to
+ */
+namespace: string; 
+/**
+ * Service type (e.g., "ClusterIP", "NodePort", "LoadBalancer", "ExternalName")
+ */
+type: string; 
+/**
+ * List of ports exposed by the service
+ */
+ports: K8sServicePort[]; 
+/**
+ * Number of endpoints (if available)
+ */
+endpoint_count: number | null }
+/**
+ * Kubernetes service port representation.
+ */
+export type K8sServicePort = { 
+/**
+ * Port name (if specified)
+ */
+name: string | null; 
+/**
+ * Port number
+ */
+port: number; 
+/**
+ * Target port (can be a number or string)
+ */
+target_port: string | null; 
+/**
+ * Protocol (e.g., "TCP", "UDP")
+ */
+protocol: string }
+/**
+ * Required-actions email sent to a user.
+ */
+export type KeycloakActionsEmail = { realm: string; username: string; 
+/**
+ * Address the email was sent to
+ */
+email: string; actions: KeycloakRequiredAction[]; 
+/**
+ * Hours the link in the email stays valid
+ */
+lifespan_hours: number }
+/**
+ * Keycloak client representation.
+ */
+export type KeycloakClient = { 
+/**
+ * Client ID
+ */
+client_id: string; 
+/**
+ * Client name
+ */
+name: string; 
+/**
+ * Whether the client is enabled
+ */
+enabled: boolean }
+/**
+ * Keycloak realm representation.
+ */
+export type KeycloakRealm = { 
+/**
+ * Realm identifier
+ */
+realm: string; 
+/**
+ * Whether the realm is enabled
+ */
+enabled: boolean }
+/**
+ * A realm key with its provider and age.
+ */
+export type KeycloakRealmKey = { 
+/**
+ * Key ID
+ */
+kid: string; 
+/**
+ * Algorithm (e.g., "RS256", "HS512")
+ */
+algorithm: string | null; 
+/**
+ * Key type (e.g., "RSA", "EC", "OCT")
+ */
+key_type: string | null; 
+/**
+ * "SIG" or "ENC"
+ */
+key_use: string | null; 
+/**
+ * "ACTIVE", "PASSIVE" or "DISABLED"
+ */
+status: string; 
+/**
+ * Whether the realm uses this key by default for its algorithm
+ */
+in_use: boolean; 
+/**
+ * Name of the key provider component
+ */
+provider_name: string | null; 
+/**
+ * Key provider type (e.g., "rsa-generated")
+ */
+provider_type: string | null; 
+/**
+ * Provider priority (highest is preferred)
+ */
+priority: number | null; 
+/**
+ * Creation time from the key certificate
+ */
+created_at: Timestamp | null; expires_at: Timestamp | null; 
+/**
+ * Whole days since creation, when known
+ */
+age_days: number | null; 
+/**
+ * Whether the key is active and older than the allowed age
+ */
+rotation_overdue: boolean }
+/**
+ * Key rotation status of a realm.
+ */
+export type KeycloakRealmKeys = { realm: string; 
+/**
+ * Age after which an active key is overdue for rotation
+ */
+max_key_age_days: number; keys: KeycloakRealmKey[]; 
+/**
+ * Number of keys overdue for rotation
+ */
+overdue_count: number }
+/**
+ * Required action a user is asked to complete by email.
+ */
+export type KeycloakRequiredAction = 
+/**
+ * Reset the password
+ */
+"UPDATE_PASSWORD" | "VERIFY_EMAIL" | 
+/**
+ * Set up an authenticator app
+ */
+"CONFIGURE_TOTP" | "UPDATE_PROFILE"
+/**
+ * File format of exported and imported users.
+ */
+export type KeycloakUserFileFormat = 
+/**
+ * Array of user objects
+ */
+"json" | 
+/**
+ * Header row of field and attribute names, one user per line
+ */
+"csv"
+/**
+ * User that could not be imported.
+ */
+export type KeycloakUserImportFailure = { 
+/**
+ * Username, or the record number when the record has none
+ */
+username: string; error: string }
+/**
+ * Outcome of a user import.
+ */
+export type KeycloakUserImportResult = { realm: string; 
+/**
+ * Whether users were only checked, not created
+ */
+dry_run: boolean; 
+/**
+ * Users created (or that would be created in a dry run)
+ */
+created: string[]; 
+/**
+ * Users skipped because the realm already has them
+ */
+existing: string[]; failed: KeycloakUserImportFailure[] }
+/**
+ * Published API rate limit of a service.
+ */
+export type KnownRateLimit = { description: string; requests_per_minute: number }
+/**
+ * A Jenkins job building a GitLab project.
+ */
+export type LinkageProposal = { gitlab_project_id: number; 
+/**
+ * Project path from its URL (e.g., "platform/api")
+ */
+gitlab_project_path: string; jenkins_job: string; 
+/**
+ * Remote URL of the job that matched
+ */
+scm_url: string; status: LinkageStatus; 
+/**
+ * Mapping linking the job (the matching one, or the conflicting one)
+ */
+mapping_id: string | null }
+/**
+ * Result of matching a Jenkins instance against a GitLab instance.
+ */
+export type LinkageReport = { proposals: LinkageProposal[]; 
+/**
+ * Jobs with Git remotes that match no GitLab project
+ */
+unmatched_jobs: string[]; 
+/**
+ * Jobs without Git remotes
+ */
+jobs_without_scm: number; 
+/**
+ * Mappings created for new proposals
+ */
+created: number; 
+/**
+ * Jobs whose configuration couldn't be read
+ */
+errors: string[] }
+/**
+ * How a proposed link relates to the existing mappings.
+ */
+export type LinkageStatus = 
+/**
+ * No mapping links the job yet
+ */
+"new" | 
+/**
+ * A mapping already links the job to the project
+ */
+"mapped" | 
+/**
+ * The job is mapped to another repository
+ */
+"conflict"
+/**
+ * Current macro recording state.
+ */
+export type MacroRecordingStatus = { recording: boolean; 
+/**
+ * Actions recorded so far
+ */
+steps: QuickAction[] }
+/**
+ * Mapping between different system identifiers
+ * Maps repository IDs to job IDs to namespaces, service names, etc.
+ */
+export type Mapping = { 
+/**
+ * Unique identifier for the mapping
+ */
+id: string; 
+/**
+ * GitLab repository ID (if applicable)
+ */
+repo_id: string | null; 
+/**
+ * Jenkins job ID (if applicable)
+ */
+job_id: string | null; 
+/**
+ * Kubernetes namespace
+ */
+namespace: string | null; 
+/**
+ * Service name in Kubernetes
+ */
+service_name: string | null; 
+/**
+ * Project ID this mapping belongs to
+ */
+project_id: string | null; 
+/**
+ * Environment ID this mapping belongs to
+ */
+environment_id: string | null; 
+/**
+ * Owner of the mapped service; unset fields fall back to the project's
+ */
+ownership?: Ownership }
+/**
+ * Current state of the metrics endpoint.
+ */
+export type MetricsEndpointStatus = { running: boolean; 
+/**
+ * Scrape URL (e.g., "http://127.0.0.1:9464/metrics")
+ */
+url: string | null; 
+/**
+ * Scrapes served since the endpoint started
+ */
+scrapes: number }
+/**
+ * Recorded result of a single node.
+ */
+export type NodeFixture = { outcome?: FixtureOutcome; 
+/**
+ * Message reported for the node (or failure reason)
+ */
+message: string | null; 
+/**
+ * Values added to the run context (e.g., `inputs.version`)
+ */
+variables?: Partial<{ [key in string]: string }> }
+/**
+ * Button shown with a notification.
+ */
+export type NotificationAction = { 
+/**
+ * Unique within the notification
+ */
+id: string; label: string; target: NotificationActionTarget }
+/**
+ * What a notification action does.
+ */
+export type NotificationActionTarget = 
+/**
+ * Brings the app to the front and navigates to a route (e.g., a build page)
+ */
+{ kind: "open"; route: string } | 
+/**
+ * Runs a quick action (e.g., retry a pipeline)
+ */
+{ kind: "run"; action: QuickAction }
+/**
+ * Paging service an on-call rotation lives in.
+ */
+export type OnCallProvider = "pagerduty" | "opsgenie" | "other"
+/**
+ * Reference to an on-call rotation in a paging service.
+ */
+export type OnCallRotation = { provider: OnCallProvider; 
+/**
+ * Schedule or rotation ID in the provider (e.g., "PD12345")
+ */
+rotation_id: string }
+/**
+ * Connection test result of one integration.
+ */
+export type OnboardingConnectionResult = { integrationId: string; integrationName: string; connected: boolean; error: string | null }
+/**
+ * Onboarding progress.
+ */
+export type OnboardingState = { completedSteps: OnboardingStep[]; 
+/**
+ * Next step to complete; `None` once onboarding is finished
+ */
+currentStep: OnboardingStep | null; 
+/**
+ * Results of the last connection test
+ */
+connectionResults: OnboardingConnectionResult[]; 
+/**
+ * Project created by the example project step
+ */
+exampleProjectId: string | null; updatedAt: Timestamp | null }
+/**
+ * Onboarding steps, in order.
+ */
+export type OnboardingStep = 
+/**
+ * At least one integration is configured
+ */
+"add_integrations" | 
+/**
+ * At least one integration connects
+ */
+"test_connections" | 
+/**
+ * An example project, environment and mapping exist
+ */
+"create_example_project"
+/**
+ * Who owns a project or service and how to reach them.
+ */
+export type Ownership = { 
+/**
+ * Owning team (e.g., "checkout")
+ */
+owner_team: string | null; 
+/**
+ * Slack channel notifications go to (e.g., "#checkout-alerts")
+ */
+slack_channel: string | null; 
+/**
+ * On-call rotation paged for incidents
+ */
+on_call: OnCallRotation | null }
+/**
+ * Estimated polling load of the current configuration.
+ */
+export type PollingCostEstimate = { 
+/**
+ * Integrations polled, busiest first
+ */
+integrations: IntegrationPollingCost[]; sources: PollingSource[]; 
+/**
+ * Requests to environment URLs (uptime probes)
+ */
+endpoint_requests_per_hour: number; total_requests_per_hour: number; warnings: string[] }
+/**
+ * Requests sent by one background poller.
+ */
+export type PollingSource = { 
+/**
+ * "tag_watch", "saved_filter", "event_recording" or "uptime"
+ */
+poller: string; 
+/**
+ * Name of the watch, filter, recording or target
+ */
+name: string; 
+/**
+ * Integration called; None for environment URLs
+ */
+integration_id: string | null; requests_per_hour: number; 
+/**
+ * Long-lived streams held open
+ */
+streams: number }
+/**
+ * Timing and outcome of a probe stage.
+ */
+export type ProbeStage = { stage: ProbeStageKind; status: ProbeStageStatus; 
+/**
+ * Duration in milliseconds (unset for skipped stages)
+ */
+duration_ms: number | null; 
+/**
+ * Result details (e.g., resolved addresses) or the error
+ */
+detail: string | null }
+/**
+ * Probe stages, in order.
+ */
+export type ProbeStageKind = "dns" | "tcp" | "tls" | "http"
+/**
+ * Outcome of a probe stage.
+ */
+export type ProbeStageStatus = "ok" | "failed" | 
+/**
+ * Not run (plain HTTP has no TLS stage; stages after a failure are skipped)
+ */
+"skipped"
+/**
+ * A project represents a software project that can have multiple environments.
+ */
+export type Project = { 
+/**
+ * Unique identifier for the project
+ */
+id: string; 
+/**
+ * Human-readable project name
+ */
+name: string; 
+/**
+ * Optional project description
+ */
+description: string | null; 
+/**
+ * List of environment IDs associated with this project
+ */
+environments: string[]; 
+/**
+ * Team owning the project, used for every service without an owner of
+ * its own
+ */
+ownership?: Ownership }
+export type ProjectChangeKind = "added" | "removed" | "renamed" | "archived" | "unarchived" | "default_branch_changed" | "visibility_changed"
+/**
+ * A change of one project between two snapshots.
+ */
+export type ProjectInventoryChange = { project_id: number; 
+/**
+ * Full path in the newer snapshot (the older one for removed projects)
+ */
+path: string; kind: ProjectChangeKind; 
+/**
+ * Previous value (the path for renames)
+ */
+from: string | null; 
+/**
+ * New value (the path for renames)
+ */
+to: string | null }
+/**
+ * Changes between two snapshots of an integration.
+ */
+export type ProjectInventoryDiff = { integration_id: string; from: ProjectInventorySnapshotInfo; to: ProjectInventorySnapshotInfo; changes: ProjectInventoryChange[] }
+/**
+ * A recorded project inventory.
+ */
+export type ProjectInventorySnapshot = { 
+/**
+ * Snapshot identifier (sortable by time)
+ */
+id: string; integration_id: string; taken_at: Timestamp; projects: GitLabInventoryProject[] }
+/**
+ * A recorded project inventory without its projects.
+ */
+export type ProjectInventorySnapshotInfo = { id: string; integration_id: string; taken_at: Timestamp; project_count: number }
+/**
+ * Action run from the quick pane. Each action runs as a `quick_action` flow node.
+ */
+export type QuickAction = 
+/**
+ * Trigger a Jenkins job
+ */
+{ action: "trigger_jenkins_build"; integrationId: string; jobName: string; parameters?: Partial<{ [key in string]: string }> } | 
+/**
+ * Trigger a GitLab pipeline on a ref
+ */
+{ action: "trigger_gitlab_pipeline"; integrationId: string; projectId: number; ref: string } | 
+/**
+ * Run the smoke tests of an environment
+ */
+{ action: "run_smoke_tests"; environmentId: string } | 
+/**
+ * Send a native notification
+ */
+{ action: "notify"; title: string; body: string | null }
+/**
+ * Named sequence of quick actions recorded in the quick pane.
+ */
+export type QuickPaneMacro = { 
+/**
+ * Unique identifier for the macro
+ */
+id: string; 
+/**
+ * Human-readable macro name
+ */
+name: string; 
+/**
+ * Actions replayed in order
+ */
+steps: QuickAction[]; 
+/**
+ * Creation timestamp (RFC 3339)
+ */
+created_at: string }
+/**
+ * Error types for recovery operations (typed for frontend matching)
+ */
+export type RecoveryError = 
+/**
+ * File does not exist (expected case, not a failure)
+ */
+{ type: "FileNotFound" } | 
+/**
+ * Filename validation failed
+ */
+{ type: "ValidationError"; message: string } | 
+/**
+ * Data exceeds size limit
+ */
+{ type: "DataTooLarge"; max_bytes: number } | 
+/**
+ * File system read/write error
+ */
+{ type: "IoError"; message: string } | 
+/**
+ * JSON serialization/deserialization error
+ */
+{ type: "ParseError"; message: string }
+/**
+ * One fetch of a refresh.
+ */
+export type RefreshSource = { 
+/**
+ * Identifier of the fetch within the refresh (e.g., "gitlab_pipelines:gitlab:42")
+ */
+id: string; kind: RefreshSourceKind; integration_id: string; 
+/**
+ * GitLab project ID, Jenkins job name or Kubernetes namespace
+ */
+target: string }
+export type RefreshSourceKind = "gitlab_pipelines" | "jenkins_builds" | "k_8s_pods"
+/**
+ * A refresh that has been started.
+ */
+export type RefreshStarted = { refresh_id: string; project_id: string; 
+/**
+ * Fetches running; one `refresh-result` event follows for each
+ */
+sources: RefreshSource[] }
+/**
+ * Report generated on demand or on a schedule.
+ */
+export type ReportDefinition = { 
+/**
+ * Unique identifier for the report
+ */
+id: string; 
+/**
+ * Report title
+ */
+name: string; kind: ReportKind; format?: ReportFormat; 
+/**
+ * Days of deployments covered by DORA metrics
+ */
+period_days?: number; 
+/**
+ * GitLab integration whose deployments feed DORA metrics
+ */
+integration_id: string | null; 
+/**
+ * Hours between scheduled generations; only generated on demand when not set
+ */
+interval_hours: number | null }
+export type ReportFormat = "html" | 
+/**
+ * HTML printed by a headless Chrome, Chromium or Edge
+ */
+"pdf"
+/**
+ * Content of a report.
+ */
+export type ReportKind = 
+/**
+ * Projects, environments, their mappings and uptime
+ */
+"environment_overview" | 
+/**
+ * Certificate expiry and uptime objectives
+ */
+"compliance" | 
+/**
+ * DORA metrics from GitLab deployments
+ */
+"dora"
+/**
+ * Named query over the resources of an integration
+ * (e.g., "failing pipelines in prod projects").
+ */
+export type SavedFilter = { 
+/**
+ * Unique identifier for the filter
+ */
+id: string; 
+/**
+ * Human-readable filter name
+ */
+name: string; resource: SavedFilterResource; integration_id: string; 
+/**
+ * GitLab project IDs, Jenkins job names or namespaces to read
+ */
+targets?: string[]; 
+/**
+ * Also read the projects, jobs or namespace mapped to this environment
+ */
+environment_id: string | null; 
+/**
+ * Conditions that must all match
+ */
+conditions?: FilterCondition[]; 
+/**
+ * Re-evaluate in the background and notify when the results change
+ */
+notify?: boolean }
+/**
+ * Resources a saved filter selects from.
+ */
+export type SavedFilterResource = 
+/**
+ * Fields: id, project_id, status, ref, created_at
+ */
+"gitlab_pipelines" | 
+/**
+ * Fields: name, color, status
+ */
+"jenkins_jobs" | 
+/**
+ * Fields: name, namespace, status, node, containers
+ */
+"k_8s_pods"
+/**
+ * Result of evaluating a saved filter.
+ */
+export type SavedFilterResult = { filter_id: string; evaluated_at: Timestamp; matches: FilterMatch[]; 
+/**
+ * Keys matching now that did not at the previous evaluation
+ */
+added: string[]; 
+/**
+ * Keys that matched at the previous evaluation and no longer do
+ */
+removed: string[]; 
+/**
+ * Targets that could not be read; changes are not tracked while any fail
+ */
+errors: string[] }
+/**
+ * Dependency changes between two images.
+ */
+export type SbomDiff = { image_a: string; image_b: string; 
+/**
+ * Packages only in the second image
+ */
+added: SbomPackage[]; 
+/**
+ * Packages only in the first image
+ */
+removed: SbomPackage[]; changed: SbomPackageChange[]; 
+/**
+ * Number of packages with the same versions in both images
+ */
+unchanged: number }
+/**
+ * SBOM document format.
+ */
+export type SbomFormat = "spdx" | "cyclonedx"
+/**
+ * A package listed in an SBOM.
+ */
+export type SbomPackage = { name: string; version: string | null; 
+/**
+ * Package URL (e.g., "pkg:npm/lodash@4.17.21")
+ */
+purl: string | null; 
+/**
+ * Ecosystem from the package URL (e.g., "npm", "deb", "maven")
+ */
+package_type: string | null }
+/**
+ * A package whose version differs between two images.
+ */
+export type SbomPackageChange = { name: string; package_type: string | null; 
+/**
+ * Versions in the first image
+ */
+from_versions: string[]; 
+/**
+ * Versions in the second image
+ */
+to_versions: string[] }
+/**
+ * Where an SBOM was read from.
+ */
+export type SbomSource = "harbor" | "oci_referrers"
+/**
+ * A likely leaked secret.
+ */
+export type SecretFinding = { 
+/**
+ * Where it was found (e.g., "deploy-api/config.xml")
+ */
+source: string; 
 /**
- * Environment name (e.g., "dev", "staging", "prod")
+ * 1-based line, for files
  */
-name: string; 
+line: number | null; kind: SecretFindingKind; 
 /**
- * Kubernetes namespace (if applicable)
+ * What matched (e.g., "GitLab personal access token")
  */
-namespace: string | null; 
+rule: string; 
 /**
- * ID of the project this environment belongs to
+ * The line with the secret redacted, or the variable name
  */
-project_id: string }
+excerpt: string }
+export type SecretFindingKind = 
 /**
- * Complete flow data including nodes and edges
+ * Matches a known token format
  */
-export type Flow = { id: string; name: string; created_at: string; updated_at: string; nodes: JsonValue; edges: JsonValue; viewport: JsonValue | null }
+"known_token" | 
 /**
- * Flow metadata for listing saved flows
+ * Random-looking string on a line mentioning a secret
  */
-export type FlowMetadata = { id: string; name: string; created_at: string; updated_at: string }
+"high_entropy" | 
 /**
- * GitLab pipeline representation.
+ * Secret-like CI variable that is not masked in job logs
  */
-export type GitLabPipeline = { 
+"unmasked_variable"
 /**
- * Pipeline ID
+ * Source that could not be read.
  */
-id: number; 
+export type SecretScanError = { source: string; error: string }
 /**
- * Pipeline status (e.g., "success", "failed", "running", "pending")
+ * Findings of a scan of one integration.
  */
-status: string; 
+export type SecretScanReport = { integration_id: string; scanned_at: Timestamp; 
 /**
- * Git reference (branch or tag)
+ * Sources read
  */
-ref: string; 
+scanned: string[]; findings: SecretFinding[]; errors: SecretScanError[] }
 /**
- * Creation timestamp (ISO 8601 format)
+ * Comparison of one desired service.
  */
-created_at: string }
+export type ServiceDrift = { service: string; status: DriftStatus; 
 /**
- * GitLab project representation.
+ * Live image of the compared container
  */
-export type GitLabProject = { 
+live_image: string | null; differences: DriftDifference[] }
 /**
- * Project ID
+ * Service a notification is about.
  */
-id: number; 
+export type ServiceRef = { projectId: string; 
 /**
- * Project name
+ * Environment, when the service's mapping is environment-specific
  */
-name: string; 
+environmentId: string | null; 
 /**
- * Project path (e.g., "group/project")
+ * Kubernetes service name; the project's owner is used when unset
  */
-path: string; 
+serviceName: string | null }
 /**
- * Web URL to access the project
+ * HTTP check run against an environment after a deployment.
  */
-web_url: string }
+export type SmokeTest = { 
 /**
- * GitLab webhook representation.
+ * Unique identifier for the smoke test
  */
-export type GitLabWebhook = { 
+id: string; 
 /**
- * Webhook ID
+ * Environment ID this check belongs to
  */
-id: number; 
+environment_id: string; 
 /**
- * Webhook URL
+ * Human-readable check name
+ */
+name: string; 
+/**
+ * URL to request with GET
  */
 url: string; 
 /**
- * List of events this webhook subscribes to
+ * Expected HTTP status code
  */
-events: string[] }
+expected_status?: number; 
 /**
- * Integration configuration (does not contain credentials)
+ * Text the response body must contain (if any)
  */
-export type Integration = { 
+expected_body: string | null; 
 /**
- * Unique identifier for the integration
+ * Timeout per attempt in seconds
  */
-id: string; 
+timeout_seconds?: number; 
 /**
- * Type of integration
+ * Additional attempts after a failed one
  */
-type: IntegrationType; 
+retries?: number; 
 /**
- * Human-readable integration name
+ * Request headers (e.g., "Authorization"); values may be secret
+ * references such as "keyring:smoke-token"
  */
-name: string; 
+headers?: Partial<{ [key in string]: string }> }
 /**
- * Base URL of the integration service
+ * Outcome of all smoke tests of an environment.
  */
-base_url: string; 
+export type SmokeTestReport = { environment_id: string; 
 /**
- * Reference to credentials stored in OS keyring
- * This is the key used to retrieve credentials from keyring
+ * Whether every check passed
  */
-credentials_ref: string | null }
+passed: boolean; results: SmokeTestResult[]; 
 /**
- * Credentials for an integration (stored securely in OS keyring)
+ * Start timestamp (RFC 3339)
  */
-export type IntegrationCredentials = { 
+started_at: string; 
 /**
- * API token or access token
+ * End timestamp (RFC 3339)
  */
-token: string | null; 
+finished_at: string }
 /**
- * Username (if applicable)
+ * Outcome of a single smoke test.
  */
-username: string | null; 
+export type SmokeTestResult = { test_id: string; name: string; url: string; passed: boolean; 
 /**
- * Password (if applicable)
+ * HTTP status of the last attempt (if a response was received)
  */
-password: string | null; 
+status: number | null; 
 /**
- * Additional custom fields as key-value pairs
+ * Number of attempts made
  */
-custom?: Partial<{ [key in string]: string }> }
+attempts: number; 
 /**
- * Type of integration system
+ * Duration of the last attempt in milliseconds
  */
-export type IntegrationType = "gitlab" | "jenkins" | "kubernetes" | "sonarqube" | "keycloak"
+duration_ms: number; 
 /**
- * Jenkins build representation.
+ * Why the last attempt failed
  */
-export type JenkinsBuild = { 
+error: string | null }
 /**
- * Build number
+ * SonarQube metrics representation.
  */
-number: number; 
+export type SonarQubeMetrics = { 
 /**
- * Build status
+ * Code coverage percentage (0-100)
  */
-status: JenkinsBuildStatus; 
+coverage: number | null; 
 /**
- * Build timestamp (Unix timestamp in milliseconds, as string to avoid i64 BigInt issues)
+ * Number of bugs
  */
-timestamp: string; 
+bugs: number; 
 /**
- * Build URL
+ * Number of vulnerabilities
  */
-url: string; 
+vulnerabilities: number; 
 /**
- * Build duration in milliseconds (None if still building, as string to avoid i64 BigInt issues)
+ * Number of code smells
  */
-duration: string | null }
+code_smells: number; 
 /**
- * Jenkins build status enumeration.
+ * Technical debt in minutes (as string to avoid i64 BigInt issues)
  */
-export type JenkinsBuildStatus = "success" | "failure" | "unstable" | "aborted" | "notbuilt" | "building" | "pending"
+technical_debt: string | null }
 /**
- * Jenkins job representation.
+ * Drift of a project's quality profile for one language from the golden
+ * profile of that language.
  */
-export type JenkinsJob = { 
+export type SonarQubeProfileDrift = { project_key: string; language: string; 
 /**
- * Job name
+ * Profile the project uses for the language
  */
-name: string; 
+profile: SonarQubeQualityProfile; 
 /**
- * Job URL
+ * Golden profile of the language (None when none was designated)
  */
-url: string; 
+golden_profile: SonarQubeQualityProfile | null; 
 /**
- * Job color/status indicator (e.g., "blue" for success, "red" for failure, "notbuilt" for not built)
+ * Rules that differ, empty when the profiles match
  */
-color: string }
-export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+differences: SonarQubeRuleDifference[]; 
 /**
- * Kubernetes namespace representation.
+ * Why the profiles couldn't be compared
  */
-export type K8sNamespace = { 
+error: string | null }
 /**
- * Namespace name
+ * SonarQube project representation.
+ */
+export type SonarQubeProject = { 
+/**
+ * Project key (unique identifier)
+ */
+key: string; 
+/**
+ * Project name
  */
 name: string; 
 /**
- * Namespace status (e.g., "Active", "Terminating")
+ * Project qualifier (e.g., "TRK" for track, "APP" for application)
  */
-status: string; 
+qualifier: string }
 /**
- * Creation timestamp (ISO 8601 format)
+ * Quality profile (the rule set a project is analyzed with, per language).
  */
-created_at: string }
+export type SonarQubeQualityProfile = { 
 /**
- * Kubernetes pod representation.
+ * Profile key (unique identifier)
  */
-export type K8sPod = { 
+key: string; name: string; 
 /**
- * Pod name
+ * Language key (e.g., "java", "js")
  */
-name: string; 
+language: string; 
 /**
- * Namespace the pod belongs to
+ * Language name (e.g., "Java")
  */
-namespace: string; 
+language_name: string; 
 /**
- * Pod status (e.g., "Running", "Pending", "Failed", "Succeeded")
+ * Whether this is the default profile of its language
  */
-status: string; 
+is_default: boolean; 
 /**
- * List of container names in the pod
+ * Name of the profile this one inherits rules from
  */
-containers: string[]; 
+parent_name: string | null; active_rule_count: number }
 /**
- * Node name where the pod is running
+ * Rule activated differently in a project's profile than in the golden one.
  */
-node: string | null }
+export type SonarQubeRuleDifference = { 
 /**
- * Kubernetes service representation.
+ * Rule key (e.g., "java:S1481")
  */
-export type K8sService = { 
+rule_key: string; name: string; kind: SonarQubeRuleDifferenceKind; 
 /**
- * Service name
+ * Severity in the golden profile (None when not active there)
+ */
+golden_severity: string | null; 
+/**
+ * Severity in the project's profile (None when not active there)
+ */
+profile_severity: string | null }
+/**
+ * How a rule differs from the golden profile.
+ */
+export type SonarQubeRuleDifferenceKind = 
+/**
+ * Active in the golden profile only
+ */
+"missing" | 
+/**
+ * Active in the project's profile only
+ */
+"extra" | 
+/**
+ * Active in both with another severity or parameters
+ */
+"modified"
+/**
+ * GitLab project polled for new tags matching a pattern.
+ */
+export type TagWatch = { 
+/**
+ * Unique identifier for the watch
+ */
+id: string; 
+/**
+ * Human-readable watch name
  */
 name: string; 
 /**
- * Namespace the service belongs to
+ * GitLab integration to poll
  */
-namespace: string; 
+integration_id: string; project_id: number; 
 /**
- * Service type (e.g., "ClusterIP", "NodePort", "LoadBalancer", "ExternalName")
+ * Tag name pattern; `*` matches any characters (e.g., "v*")
  */
-type: string; 
+pattern?: string; 
 /**
- * List of ports exposed by the service
+ * Seconds between polls
  */
-ports: K8sServicePort[]; 
+interval_seconds?: number; 
 /**
- * Number of endpoints (if available)
+ * Send a native notification for new tags
  */
-endpoint_count: number | null }
+notify?: boolean; 
 /**
- * Kubernetes service port representation.
+ * Start flows whose webhook trigger matches a GitLab `tag_push` event
  */
-export type K8sServicePort = { 
+trigger_flows?: boolean }
 /**
- * Port name (if specified)
+ * Current state of a watch.
  */
-name: string | null; 
+export type TagWatchStatus = { watch_id: string; name: string; 
 /**
- * Port number
+ * Last poll timestamp (RFC 3339)
  */
-port: number; 
+last_checked_at: string | null; 
 /**
- * Target port (can be a number or string)
+ * Why the last poll failed
  */
-target_port: string | null; 
+last_error: string | null; 
 /**
- * Protocol (e.g., "TCP", "UDP")
+ * Tags detected, newest first
  */
-protocol: string }
+detected: DetectedTag[] }
 /**
- * Keycloak client representation.
+ * Current state of the tag watcher.
  */
-export type KeycloakClient = { 
+export type TagWatcherStatus = { running: boolean; 
 /**
- * Client ID
+ * Start timestamp (RFC 3339)
  */
-client_id: string; 
+started_at: string | null; watches: TagWatchStatus[] }
 /**
- * Client name
+ * Current load of the pool.
  */
-name: string; 
+export type TaskPoolStatus = { 
 /**
- * Whether the client is enabled
+ * Background tasks running
  */
-enabled: boolean }
+running: number; 
 /**
- * Keycloak realm representation.
+ * Pollers waiting for a slot
  */
-export type KeycloakRealm = { 
+queued_pollers: number; 
 /**
- * Realm identifier
+ * Analytics tasks waiting for a slot
  */
-realm: string; 
+queued_analytics: number; 
 /**
- * Whether the realm is enabled
+ * User-initiated commands running
  */
-enabled: boolean }
+interactive: number }
+export type TestCounts = { total: number; failed: number; skipped: number }
+export type TestResultDelta = { 
 /**
- * Mapping between different system identifiers
- * Maps repository IDs to job IDs to namespaces, service names, etc.
+ * Tests failing in head but not in base
  */
-export type Mapping = { 
+newly_failing: string[]; 
 /**
- * Unique identifier for the mapping
+ * Tests failing in base but not in head (passing or removed)
  */
-id: string; 
+fixed: string[]; 
 /**
- * GitLab repository ID (if applicable)
+ * Tests failing in both builds
  */
-repo_id: string | null; 
+still_failing: string[] }
 /**
- * Jenkins job ID (if applicable)
+ * A point in time, normalized to UTC.
+ * 
+ * Deserializes from RFC 3339 / ISO 8601 strings, epoch milliseconds (number
+ * or string) and its own serialized form, so API response types can use it
+ * directly.
  */
-job_id: string | null; 
+export type Timestamp = { 
 /**
- * Kubernetes namespace
+ * RFC 3339 in UTC with millisecond precision (e.g., "2024-05-01T10:00:00.000Z")
  */
-namespace: string | null; 
+rfc3339: string; 
 /**
- * Service name in Kubernetes
+ * Unix timestamp in milliseconds (as string to avoid i64 BigInt issues)
  */
-service_name: string | null; 
+epoch_millis: string }
 /**
- * Project ID this mapping belongs to
+ * Release channel the app updates from.
  */
-project_id: string | null; 
+export type UpdateChannel = "stable" | "beta"
 /**
- * Environment ID this mapping belongs to
+ * Result of checking the update channel.
  */
-environment_id: string | null }
+export type UpdateCheck = { channel: UpdateChannel; current_version: string; 
 /**
- * A project represents a software project that can have multiple environments.
+ * Check timestamp (RFC 3339)
  */
-export type Project = { 
+checked_at: string; update: AvailableUpdate | null }
 /**
- * Unique identifier for the project
+ * Daily window of local hours, e.g. 22 to 6 for overnight.
+ */
+export type UpdateInstallWindow = { 
+/**
+ * First hour of the window (0-23)
+ */
+start_hour: number; 
+/**
+ * Hour the window ends (0-23, exclusive)
+ */
+end_hour: number }
+/**
+ * Current state of the uptime monitor.
+ */
+export type UptimeMonitorStatus = { running: boolean; 
+/**
+ * Start timestamp (RFC 3339)
+ */
+started_at: string | null; targets: UptimeTargetStatus[] }
+/**
+ * Result of one check.
+ */
+export type UptimeSample = { 
+/**
+ * Check timestamp (RFC 3339)
+ */
+checked_at: string; up: boolean; 
+/**
+ * HTTP status (if a response was received)
+ */
+status: number | null; 
+/**
+ * Response time in milliseconds
+ */
+latency_ms: number; 
+/**
+ * Why the check failed
+ */
+error: string | null }
+/**
+ * Environment URL probed on a schedule by the uptime monitor.
+ */
+export type UptimeTarget = { 
+/**
+ * Unique identifier for the target
  */
 id: string; 
 /**
- * Human-readable project name
+ * Environment ID this URL belongs to
+ */
+environment_id: string | null; 
+/**
+ * Human-readable target name
  */
 name: string; 
 /**
- * Optional project description
+ * URL to request with GET
  */
-description: string | null; 
+url: string; 
 /**
- * List of environment IDs associated with this project
+ * Seconds between checks
  */
-environments: string[] }
+interval_seconds?: number; 
 /**
- * Error types for recovery operations (typed for frontend matching)
+ * Timeout per check in seconds
  */
-export type RecoveryError = 
+timeout_seconds?: number; 
 /**
- * File does not exist (expected case, not a failure)
+ * Status that counts as up; any status below 400 when not set
  */
-{ type: "FileNotFound" } | 
+expected_status: number | null }
 /**
- * Filename validation failed
+ * Current state and history of a target.
  */
-{ type: "ValidationError"; message: string } | 
+export type UptimeTargetStatus = { target_id: string; name: string; url: string; 
 /**
- * Data exceeds size limit
+ * Whether the target is up; `None` until it was checked
  */
-{ type: "DataTooLarge"; max_bytes: number } | 
+up: boolean | null; 
 /**
- * File system read/write error
+ * Failed checks in a row
  */
-{ type: "IoError"; message: string } | 
+consecutive_failures: number; 
 /**
- * JSON serialization/deserialization error
+ * Share of successful checks in the history (0-100)
  */
-{ type: "ParseError"; message: string }
+uptime_percent: number | null; 
 /**
- * SonarQube metrics representation.
+ * Checks, oldest first
  */
-export type SonarQubeMetrics = { 
+history: UptimeSample[] }
 /**
- * Code coverage percentage (0-100)
+ * Settings for starting the webhook receiver.
  */
-coverage: number | null; 
+export type WebhookReceiverConfig = { 
 /**
- * Number of bugs
+ * Address to listen on (e.g., "127.0.0.1" or "0.0.0.0")
  */
-bugs: number; 
+bind_address: string; port: number; 
 /**
- * Number of vulnerabilities
+ * Shared secret senders must present. May be a secret reference (e.g.,
+ * "keyring:webhook-secret"). When unset, a secret is generated on first
+ * start and kept in the OS keyring
  */
-vulnerabilities: number; 
+secret: string | null }
 /**
- * Number of code smells
+ * Current state of the webhook receiver.
  */
-code_smells: number; 
+export type WebhookReceiverStatus = { running: boolean; 
 /**
- * Technical debt in minutes (as string to avoid i64 BigInt issues)
+ * Address the receiver listens on (e.g., "127.0.0.1:9870")
  */
-technical_debt: string | null }
+address: string | null; 
 /**
- * SonarQube project representation.
+ * Start timestamp (RFC 3339)
  */
-export type SonarQubeProject = { 
+started_at: string | null; 
 /**
- * Project key (unique identifier)
+ * Events accepted since the receiver started
  */
-key: string; 
+events_received: number; 
 /**
- * Project name
+ * Timestamp of the last accepted event (RFC 3339)
  */
-name: string; 
+last_event_at: string | null; 
 /**
- * Project qualifier (e.g., "TRK" for track, "APP" for application)
+ * Secret senders must present, when it was generated rather than configured
  */
-qualifier: string }
+generated_secret: string | null }
 
 /** tauri-specta globals **/
 
@@ -10,11 +10,11 @@ import {
   Submenu,
   PredefinedMenuItem,
 } from '@tauri-apps/api/menu'
-import { check } from '@tauri-apps/plugin-updater'
 import i18n from '@/i18n/config'
 import { useUIStore } from '@/store/ui-store'
 import { logger } from '@/lib/logger'
 import { notifications } from '@/lib/notifications'
+import { commands } from '@/lib/tauri-bindings'
 
 const APP_NAME = 'Ops Flow'
 
@@ -131,19 +131,26 @@ function handleAbout(): void {
 
 async function handleCheckForUpdates(): Promise<void> {
   logger.info('Check for Updates menu item clicked')
-  try {
-    const update = await check()
-    if (update) {
-      notifications.info(
-        'Update Available',
-        `Version ${update.version} is available`
-      )
-    } else {
-      notifications.success('Up to Date', 'You are running the latest version')
-    }
-  } catch (error) {
-    logger.error('Update check failed', { error })
+  const result = await commands.checkForUpdates()
+  if (result.status === 'error') {
+    logger.error('Update check failed', { error: result.error })
     notifications.error('Update Check Failed', 'Could not check for updates')
+    return
+  }
+
+  const update = result.data.update
+  if (!update) {
+    notifications.success('Up to Date', 'You are running the latest version')
+  } else if (update.deferred_until) {
+    notifications.info(
+      'Update Deferred',
+      `Version ${update.version} will be offered after ${new Date(update.deferred_until).toLocaleDateString()}`
+    )
+  } else {
+    notifications.info(
+      'Update Available',
+      `Version ${update.version} is available`
+    )
   }
 }
 
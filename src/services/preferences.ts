@@ -22,7 +22,17 @@ export function usePreferences() {
         logger.warn('Failed to load preferences, using defaults', {
           error: result.error,
         })
-        return { theme: 'system', quick_pane_shortcut: null, language: null }
+        return {
+          theme: 'system',
+          quick_pane_shortcut: null,
+          language: null,
+          app_lock_idle_minutes: null,
+          metrics_port: null,
+          update_channel: null,
+          update_defer_days: null,
+          update_install_window: null,
+          feature_flags: null,
+        }
       }
 
       logger.info('Preferences loaded successfully', {